@@ -1,8 +1,8 @@
-/// ASUSManagement device_status 暴力扫描 + 高级 API 精准测试
+/// ASUSManagement 高级 API 精准测试
 ///
-/// 思路：桌面板的 Device ID 魔法字典跟笔记本不同，
-/// 暴力扫描关键区间找出所有返回非零值的 ID。
-/// 同时用正确参数测试 GetManualFanCurvePro / GetManualFanCurve。
+/// 用正确参数测试 GetManualFanCurvePro / GetManualFanCurve。
+/// device_status 暴力扫描已移至应用内的设备 ID 扫描（`start_device_scan`），
+/// 不必再编译本示例。
 ///
 /// 用法：以管理员身份运行
 ///   cargo run --example test_asio
@@ -64,7 +64,6 @@ fn main() {
 // ─── 辅助类型 ───
 enum WmiParamVal<'a> {
     U8(u8),
-    U32(u32),
     Str(&'a str),
 }
 
@@ -195,7 +194,6 @@ unsafe fn run_test() -> Result<(), String> {
             for (name, value) in params {
                 let variant = match value {
                     WmiParamVal::U8(v) => VARIANT::from(*v as i32),
-                    WmiParamVal::U32(v) => VARIANT::from(*v as i32),
                     WmiParamVal::Str(s) => VARIANT::from(BSTR::from(*s)),
                 };
                 instance
@@ -323,84 +321,7 @@ unsafe fn run_test() -> Result<(), String> {
     }
 
     // ═══════════════════════════════════════════
-    //  第四步：device_status 暴力扫描
-    // ═══════════════════════════════════════════
-    logln!("\n══════════════════════════════════════════");
-    logln!("  device_status 暴力扫描");
-    logln!("══════════════════════════════════════════");
-
-    // 华硕 DSTS 的 Device ID 编码规则:
-    //   高16位 = 设备类(DevType), 低16位 = 设备序号(DevNum)
-    // 已知笔记本常见前缀: 0x0011xxxx (风扇), 0x0012xxxx (电源/策略)
-    // 桌面板可能用完全不同的前缀
-    let ranges: &[(&str, std::ops::RangeInclusive<u32>)] = &[
-        // 基础区间
-        ("0x0000_00xx 基础状态", 0x00000000..=0x000000FF),
-        // 各个 DevType 的前 256 个设备
-        ("0x0001_00xx", 0x00010000..=0x000100FF),
-        ("0x0002_00xx", 0x00020000..=0x000200FF),
-        ("0x0003_00xx", 0x00030000..=0x000300FF),
-        ("0x0004_00xx", 0x00040000..=0x000400FF),
-        ("0x0005_00xx", 0x00050000..=0x000500FF),
-        ("0x0006_00xx", 0x00060000..=0x000600FF),
-        ("0x0007_00xx", 0x00070000..=0x000700FF),
-        ("0x0008_00xx", 0x00080000..=0x000800FF),
-        ("0x0009_00xx", 0x00090000..=0x000900FF),
-        ("0x000A_00xx", 0x000A0000..=0x000A00FF),
-        ("0x000B_00xx", 0x000B0000..=0x000B00FF),
-        ("0x000C_00xx", 0x000C0000..=0x000C00FF),
-        ("0x000D_00xx", 0x000D0000..=0x000D00FF),
-        ("0x000E_00xx", 0x000E0000..=0x000E00FF),
-        ("0x000F_00xx", 0x000F0000..=0x000F00FF),
-        ("0x0010_00xx", 0x00100000..=0x001000FF),
-        // 笔记本已知的核心区间 — 桌面板可能复用
-        ("0x0011_00xx 风扇/温度", 0x00110000..=0x001100FF),
-        ("0x0012_00xx 电源/策略", 0x00120000..=0x001200FF),
-        ("0x0013_00xx", 0x00130000..=0x001300FF),
-        ("0x0014_00xx", 0x00140000..=0x001400FF),
-        // 更高区间
-        ("0x0020_00xx", 0x00200000..=0x002000FF),
-        ("0x0021_00xx", 0x00210000..=0x002100FF),
-        ("0x0030_00xx", 0x00300000..=0x003000FF),
-        ("0x0040_00xx", 0x00400000..=0x004000FF),
-        ("0x0050_00xx", 0x00500000..=0x005000FF),
-        ("0x0060_00xx", 0x00600000..=0x006000FF),
-        // AURA/LED 区间
-        ("0x0010_10xx AURA", 0x00101000..=0x001010FF),
-    ];
-
-    let mut total_found = 0u32;
-
-    for (label, range) in ranges {
-        log!("  扫描 {label} ...");
-        let mut hits = 0u32;
-        for did in range.clone() {
-            match call("device_status", &[("device_id", WmiParamVal::U32(did))]) {
-                Ok(out) => {
-                    let val = get_property_u32(&out, "ctrl_param").unwrap_or(0);
-                    if val != 0 && val != 0xFFFFFFFF {
-                        if hits == 0 {
-                            logln!("");
-                        }
-                        logln!("    ★ 0x{did:08X} → {val} (0x{val:08X})");
-                        hits += 1;
-                    }
-                }
-                Err(_) => {}
-            }
-        }
-        if hits == 0 {
-            logln!(" 无有效数据");
-        } else {
-            logln!("    小计: {hits} 个命中");
-        }
-        total_found += hits;
-    }
-
-    logln!("\n  ═══ 暴力扫描结束！共 {total_found} 个有效 Device ID ═══");
-
-    // ═══════════════════════════════════════════
-    //  第五步：GetLastError 诊断
+    //  第四步：GetLastError 诊断
     // ═══════════════════════════════════════════
     logln!("\n══════════════════════════════════════════");
     logln!("  GetLastError 诊断");
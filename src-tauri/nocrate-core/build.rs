@@ -0,0 +1,31 @@
+// 嵌入 WinRing0 驱动
+// 启用 sio 特性且 resources/WinRing0x64.sys 存在（或由 NOCRATE_WINRING0 指定路径）时，
+// 设置 cfg(embedded_driver)，由 sio::embedded 用 include_bytes! 编入二进制；
+// 驱动文件不纳入版本控制，缺失时照常构建，运行时回退到资源目录查找
+
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(embedded_driver)");
+    println!("cargo:rerun-if-env-changed=NOCRATE_WINRING0");
+    if env::var_os("CARGO_FEATURE_SIO").is_none() {
+        return;
+    }
+
+    let path = env::var_os("NOCRATE_WINRING0").map_or_else(
+        || {
+            let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+            manifest_dir.join("../resources/WinRing0x64.sys")
+        },
+        PathBuf::from,
+    );
+    // 监视所在目录，文件新增或替换后重新构建
+    if let Some(dir) = path.parent() {
+        println!("cargo:rerun-if-changed={}", dir.display());
+    }
+    if path.is_file() {
+        println!("cargo:rustc-cfg=embedded_driver");
+        println!("cargo:rustc-env=NOCRATE_WINRING0_PATH={}", path.display());
+    }
+}
@@ -0,0 +1,280 @@
+/// Desktop capture for the ambient (ambilight) effect.
+///
+/// Uses DXGI desktop duplication on the primary output and reduces each
+/// captured frame to a small grid of average colours; zones then sample
+/// the outer cells of that grid. Like the audio capture, the D3D objects
+/// are created and used on the effect thread only.
+use serde::{Deserialize, Serialize};
+use windows::core::Interface;
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_HARDWARE;
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D, D3D11_CPU_ACCESS_READ,
+    D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAPPED_SUBRESOURCE, D3D11_MAP_READ, D3D11_SDK_VERSION,
+    D3D11_TEXTURE2D_DESC, D3D11_USAGE_STAGING,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+use windows::Win32::Graphics::Dxgi::{
+    IDXGIDevice, IDXGIOutput1, IDXGIOutputDuplication, IDXGIResource, DXGI_ERROR_ACCESS_LOST,
+    DXGI_ERROR_WAIT_TIMEOUT, DXGI_OUTDUPL_FRAME_INFO,
+};
+
+use crate::error::Result;
+
+/// Screen edge sampled by an ambient-mode zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Maps one AURA channel to a stretch of a screen edge.
+///
+/// `start` / `end` are fractions along the edge (left → right for
+/// top / bottom, top → bottom for left / right).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmbilightZone {
+    /// AURA device index
+    pub device: usize,
+    /// Channel on that device
+    pub channel: u8,
+    pub edge: ScreenEdge,
+    pub start: f32,
+    pub end: f32,
+    /// Whether LED 0 sits at `end` instead of `start`
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+impl AmbilightZone {
+    /// Zone used for channels without a configured mapping: the whole
+    /// bottom edge, matching a strip mounted under a desk or case.
+    #[must_use]
+    pub const fn fallback(device: usize, channel: u8) -> Self {
+        Self {
+            device,
+            channel,
+            edge: ScreenEdge::Bottom,
+            start: 0.0,
+            end: 1.0,
+            reverse: false,
+        }
+    }
+}
+
+/// Grid the desktop is reduced to (columns × rows).
+const GRID_W: usize = 32;
+const GRID_H: usize = 18;
+
+/// Grid cells sampled inward from the screen edge.
+const EDGE_DEPTH: usize = 2;
+
+/// Pixel stride when averaging a grid cell; full-resolution averaging
+/// is not needed for ambient colours.
+const PIXEL_STRIDE: usize = 4;
+
+/// Desktop duplication of the primary output.
+pub struct ScreenCapture {
+    device: ID3D11Device,
+    context: ID3D11DeviceContext,
+    duplication: IDXGIOutputDuplication,
+    staging: Option<(ID3D11Texture2D, u32, u32)>,
+    /// Average colour per grid cell, row-major, `0.0..=1.0` RGB.
+    grid: Vec<[f32; 3]>,
+}
+
+#[allow(unsafe_code)]
+impl ScreenCapture {
+    /// Create a D3D11 device and start duplicating the primary output.
+    pub fn start() -> Result<Self> {
+        unsafe {
+            let mut device = None;
+            let mut context = None;
+            D3D11CreateDevice(
+                None,
+                D3D_DRIVER_TYPE_HARDWARE,
+                HMODULE::default(),
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                None,
+                D3D11_SDK_VERSION,
+                Some(&mut device),
+                None,
+                Some(&mut context),
+            )?;
+            let (Some(device), Some(context)) = (device, context) else {
+                return Err(windows::core::Error::from_win32().into());
+            };
+            let duplication = duplicate_primary(&device)?;
+
+            Ok(Self {
+                device,
+                context,
+                duplication,
+                staging: None,
+                grid: vec![[0.0; 3]; GRID_W * GRID_H],
+            })
+        }
+    }
+
+    /// Grab the latest desktop frame into the colour grid.
+    ///
+    /// Keeps the previous grid if the desktop has not changed. Recreates
+    /// the duplication after mode changes or secure-desktop switches.
+    pub fn capture(&mut self) -> Result<()> {
+        unsafe {
+            let mut info = DXGI_OUTDUPL_FRAME_INFO::default();
+            let mut resource: Option<IDXGIResource> = None;
+            match self
+                .duplication
+                .AcquireNextFrame(0, &mut info, &mut resource)
+            {
+                Ok(()) => {}
+                Err(e) if e.code() == DXGI_ERROR_WAIT_TIMEOUT => return Ok(()),
+                Err(e) if e.code() == DXGI_ERROR_ACCESS_LOST => {
+                    self.duplication = duplicate_primary(&self.device)?;
+                    self.staging = None;
+                    return Ok(());
+                }
+                Err(e) => return Err(e.into()),
+            }
+
+            let result = resource.map_or(Ok(()), |r| {
+                r.cast::<ID3D11Texture2D>().map(|t| self.read_frame(&t))
+            });
+            let _ = self.duplication.ReleaseFrame();
+            result.map_err(Into::into)
+        }
+    }
+
+    unsafe fn read_frame(&mut self, frame: &ID3D11Texture2D) {
+        unsafe {
+            let mut desc = D3D11_TEXTURE2D_DESC::default();
+            frame.GetDesc(&mut desc);
+            // HDR desktops deliver FP16 frames; only 8-bit BGRA is sampled
+            if desc.Format != DXGI_FORMAT_B8G8R8A8_UNORM {
+                return;
+            }
+
+            let staging = match &self.staging {
+                Some((t, w, h)) if *w == desc.Width && *h == desc.Height => t.clone(),
+                _ => {
+                    let staging_desc = D3D11_TEXTURE2D_DESC {
+                        Usage: D3D11_USAGE_STAGING,
+                        BindFlags: 0,
+                        CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+                        MiscFlags: 0,
+                        ..desc
+                    };
+                    let mut texture = None;
+                    if self
+                        .device
+                        .CreateTexture2D(&staging_desc, None, Some(&mut texture))
+                        .is_err()
+                    {
+                        return;
+                    }
+                    let Some(texture) = texture else { return };
+                    self.staging = Some((texture.clone(), desc.Width, desc.Height));
+                    texture
+                }
+            };
+
+            self.context.CopyResource(&staging, frame);
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            if self
+                .context
+                .Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+                .is_err()
+            {
+                return;
+            }
+
+            let width = desc.Width as usize;
+            let height = desc.Height as usize;
+            let pitch = mapped.RowPitch as usize;
+            let data = std::slice::from_raw_parts(mapped.pData.cast::<u8>(), pitch * height);
+            self.reduce(data, width, height, pitch);
+
+            self.context.Unmap(&staging, 0);
+        }
+    }
+
+    /// Average BGRA pixels into the grid.
+    fn reduce(&mut self, data: &[u8], width: usize, height: usize, pitch: usize) {
+        for gy in 0..GRID_H {
+            let (y0, y1) = (gy * height / GRID_H, (gy + 1) * height / GRID_H);
+            for gx in 0..GRID_W {
+                let (x0, x1) = (gx * width / GRID_W, (gx + 1) * width / GRID_W);
+                let mut sum = [0u32; 3];
+                let mut n = 0u32;
+                for y in (y0..y1).step_by(PIXEL_STRIDE) {
+                    for x in (x0..x1).step_by(PIXEL_STRIDE) {
+                        let p = y * pitch + x * 4;
+                        // BGRA
+                        sum[0] += u32::from(data[p + 2]);
+                        sum[1] += u32::from(data[p + 1]);
+                        sum[2] += u32::from(data[p]);
+                        n += 1;
+                    }
+                }
+                if n > 0 {
+                    self.grid[gy * GRID_W + gx] = sum.map(|c| c as f32 / n as f32 / 255.0);
+                }
+            }
+        }
+    }
+
+    /// Colours for `led_count` LEDs along the region described by `zone`.
+    pub fn sample(&self, zone: &AmbilightZone, led_count: usize) -> Vec<[f32; 3]> {
+        let horizontal = matches!(zone.edge, ScreenEdge::Top | ScreenEdge::Bottom);
+        let cells = if horizontal { GRID_W } else { GRID_H };
+        let start = zone.start.clamp(0.0, 1.0);
+        let end = zone.end.clamp(start, 1.0);
+
+        let mut colors: Vec<[f32; 3]> = (0..led_count)
+            .map(|led| {
+                let t = (led as f32 + 0.5) / led_count.max(1) as f32;
+                let pos = (end - start).mul_add(t, start);
+                // `pos` is clamped to 0.0..=1.0
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let cell = ((pos * cells as f32) as usize).min(cells - 1);
+                self.edge_average(zone.edge, cell)
+            })
+            .collect();
+        if zone.reverse {
+            colors.reverse();
+        }
+        colors
+    }
+
+    /// Average of the cells `EDGE_DEPTH` deep at position `cell` along `edge`.
+    fn edge_average(&self, edge: ScreenEdge, cell: usize) -> [f32; 3] {
+        let mut sum = [0.0f32; 3];
+        for depth in 0..EDGE_DEPTH {
+            let (x, y) = match edge {
+                ScreenEdge::Top => (cell, depth),
+                ScreenEdge::Bottom => (cell, GRID_H - 1 - depth),
+                ScreenEdge::Left => (depth, cell),
+                ScreenEdge::Right => (GRID_W - 1 - depth, cell),
+            };
+            let c = self.grid[y * GRID_W + x];
+            for i in 0..3 {
+                sum[i] += c[i];
+            }
+        }
+        sum.map(|c| c / EDGE_DEPTH as f32)
+    }
+}
+
+/// Duplicate output 0 of the adapter backing `device`.
+#[allow(unsafe_code)]
+unsafe fn duplicate_primary(device: &ID3D11Device) -> Result<IDXGIOutputDuplication> {
+    unsafe {
+        let dxgi: IDXGIDevice = device.cast()?;
+        let output: IDXGIOutput1 = dxgi.GetAdapter()?.EnumOutputs(0)?.cast()?;
+        Ok(output.DuplicateOutput(device)?)
+    }
+}
@@ -0,0 +1,250 @@
+/// WASAPI loopback capture and spectrum analysis for audio-reactive
+/// lighting.
+///
+/// Captures whatever is playing on the default render device in shared
+/// mode, downmixes to mono and keeps the most recent window of samples
+/// for band analysis. COM objects are not `Send`, so a capture must be
+/// created and used on the same thread.
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+use windows::Win32::Media::Audio::{
+    eConsole, eRender, IAudioCaptureClient, IAudioClient, IMMDeviceEnumerator, MMDeviceEnumerator,
+    AUDCLNT_BUFFERFLAGS_SILENT, AUDCLNT_SHAREMODE_SHARED, AUDCLNT_STREAMFLAGS_LOOPBACK,
+    WAVEFORMATEX, WAVEFORMATEXTENSIBLE, WAVE_FORMAT_PCM,
+};
+use windows::Win32::Media::KernelStreaming::{KSDATAFORMAT_SUBTYPE_PCM, WAVE_FORMAT_EXTENSIBLE};
+use windows::Win32::Media::Multimedia::{KSDATAFORMAT_SUBTYPE_IEEE_FLOAT, WAVE_FORMAT_IEEE_FLOAT};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_ALL,
+    COINIT_MULTITHREADED,
+};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+/// Samples kept for analysis (~21 ms at 48 kHz).
+pub const WINDOW_SIZE: usize = 1024;
+
+/// Shared-mode buffer duration requested from WASAPI (100 ns units).
+const BUFFER_DURATION: i64 = 200_000;
+
+/// Lowest / highest band centre frequencies (Hz).
+const MIN_FREQ: f32 = 60.0;
+const MAX_FREQ: f32 = 8000.0;
+
+/// Sample format of the shared-mode mix.
+#[derive(Clone, Copy)]
+enum SampleFormat {
+    F32,
+    I16,
+    I32,
+}
+
+/// Keeps COM initialized on the creating thread until dropped.
+struct ComGuard;
+
+#[allow(unsafe_code)]
+impl ComGuard {
+    fn init() -> Result<Self> {
+        unsafe { CoInitializeEx(None, COINIT_MULTITHREADED).ok()? };
+        Ok(Self)
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for ComGuard {
+    fn drop(&mut self) {
+        unsafe { CoUninitialize() };
+    }
+}
+
+/// Active loopback capture on the default output device.
+pub struct LoopbackCapture {
+    client: IAudioClient,
+    capture: IAudioCaptureClient,
+    format: SampleFormat,
+    channels: usize,
+    sample_rate: f32,
+    window: VecDeque<f32>,
+    // Must stay last: fields drop in order, and the COM interfaces above
+    // have to be released before COM is uninitialized
+    _com: ComGuard,
+}
+
+#[allow(unsafe_code)]
+impl LoopbackCapture {
+    /// Initialize COM on the current thread and start capturing.
+    pub fn start() -> Result<Self> {
+        let com = ComGuard::init()?;
+        unsafe { Self::open(com) }
+    }
+
+    unsafe fn open(com: ComGuard) -> Result<Self> {
+        unsafe {
+            let enumerator: IMMDeviceEnumerator =
+                CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let device = enumerator.GetDefaultAudioEndpoint(eRender, eConsole)?;
+            let client: IAudioClient = device.Activate(CLSCTX_ALL, None)?;
+
+            let mix = client.GetMixFormat()?;
+            let parsed = parse_format(mix)
+                .ok_or_else(|| NoCrateError::Unknown(i18n::t(Msg::AudioMixFormat).into()))
+                .and_then(|p| {
+                    client
+                        .Initialize(
+                            AUDCLNT_SHAREMODE_SHARED,
+                            AUDCLNT_STREAMFLAGS_LOOPBACK,
+                            BUFFER_DURATION,
+                            0,
+                            mix,
+                            None,
+                        )
+                        .map(|()| p)
+                        .map_err(Into::into)
+                });
+            CoTaskMemFree(Some(mix.cast()));
+            let (format, channels, sample_rate) = parsed?;
+
+            let capture: IAudioCaptureClient = client.GetService()?;
+            client.Start()?;
+
+            Ok(Self {
+                client,
+                capture,
+                format,
+                channels,
+                sample_rate,
+                window: VecDeque::with_capacity(WINDOW_SIZE),
+                _com: com,
+            })
+        }
+    }
+
+    /// Drain all pending packets into the analysis window.
+    pub fn poll(&mut self) -> Result<()> {
+        unsafe {
+            while self.capture.GetNextPacketSize()? > 0 {
+                let mut data = std::ptr::null_mut();
+                let mut frames = 0u32;
+                let mut flags = 0u32;
+                self.capture
+                    .GetBuffer(&mut data, &mut frames, &mut flags, None, None)?;
+
+                let silent = flags & AUDCLNT_BUFFERFLAGS_SILENT.0 as u32 != 0;
+                for frame in 0..frames as usize {
+                    let sample = if silent || data.is_null() {
+                        0.0
+                    } else {
+                        self.read_frame(data, frame)
+                    };
+                    if self.window.len() == WINDOW_SIZE {
+                        let _ = self.window.pop_front();
+                    }
+                    self.window.push_back(sample);
+                }
+
+                self.capture.ReleaseBuffer(frames)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Average all channels of one interleaved frame.
+    // WASAPI buffers are aligned in practice; `read_unaligned` avoids relying on it
+    #[allow(clippy::cast_ptr_alignment, clippy::cast_precision_loss)]
+    unsafe fn read_frame(&self, data: *const u8, frame: usize) -> f32 {
+        let base = frame * self.channels;
+        let sum: f32 = (0..self.channels)
+            .map(|ch| unsafe {
+                match self.format {
+                    SampleFormat::F32 => data.cast::<f32>().add(base + ch).read_unaligned(),
+                    SampleFormat::I16 => {
+                        f32::from(data.cast::<i16>().add(base + ch).read_unaligned()) / 32768.0
+                    }
+                    SampleFormat::I32 => {
+                        data.cast::<i32>().add(base + ch).read_unaligned() as f32 / 2_147_483_648.0
+                    }
+                }
+            })
+            .sum();
+        sum / self.channels as f32
+    }
+
+    /// Magnitude of `bands` log-spaced frequency bands over the current
+    /// window, each roughly in `0.0..=1.0` for full-scale input.
+    pub fn bands(&self, bands: usize) -> Vec<f32> {
+        if self.window.is_empty() || bands == 0 {
+            return vec![0.0; bands];
+        }
+        let samples: Vec<f32> = self.window.iter().copied().collect();
+        let ratio = (MAX_FREQ / MIN_FREQ).powf(1.0 / bands.max(2).saturating_sub(1) as f32);
+        (0..bands)
+            .map(|i| {
+                let freq = MIN_FREQ * ratio.powi(i32::try_from(i).unwrap_or(i32::MAX));
+                goertzel(&samples, freq, self.sample_rate)
+            })
+            .collect()
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for LoopbackCapture {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.client.Stop();
+        }
+    }
+}
+
+/// Interpret the shared-mode mix format.
+///
+/// Shared mode almost always delivers 32-bit float; 16- and 32-bit
+/// integer PCM are accepted for older drivers.
+///
+/// # Safety
+///
+/// `fmt` must point to a valid format block, including the
+/// `WAVEFORMATEXTENSIBLE` tail when its tag announces one.
+#[allow(unsafe_code)]
+unsafe fn parse_format(fmt: *const WAVEFORMATEX) -> Option<(SampleFormat, usize, f32)> {
+    let base = unsafe { fmt.read_unaligned() };
+    let extension = size_of::<WAVEFORMATEXTENSIBLE>() - size_of::<WAVEFORMATEX>();
+    let float = match u32::from(base.wFormatTag) {
+        WAVE_FORMAT_IEEE_FLOAT => true,
+        WAVE_FORMAT_PCM => false,
+        WAVE_FORMAT_EXTENSIBLE if usize::from(base.cbSize) >= extension => {
+            let sub = unsafe { fmt.cast::<WAVEFORMATEXTENSIBLE>().read_unaligned() }.SubFormat;
+            if sub == KSDATAFORMAT_SUBTYPE_IEEE_FLOAT {
+                true
+            } else if sub == KSDATAFORMAT_SUBTYPE_PCM {
+                false
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    };
+    let format = match (float, base.wBitsPerSample) {
+        (true, 32) => SampleFormat::F32,
+        (false, 16) => SampleFormat::I16,
+        (false, 32) => SampleFormat::I32,
+        _ => return None,
+    };
+    let channels = usize::from(base.nChannels);
+    (channels > 0).then_some((format, channels, base.nSamplesPerSec as f32))
+}
+
+/// Normalized magnitude of a single frequency via the Goertzel algorithm.
+fn goertzel(samples: &[f32], freq: f32, sample_rate: f32) -> f32 {
+    let n = samples.len() as f32;
+    let coeff = 2.0 * (2.0 * PI * freq / sample_rate).cos();
+    let (mut s1, mut s2) = (0.0f32, 0.0f32);
+    for &x in samples {
+        let s0 = coeff.mul_add(s1, x - s2);
+        s2 = s1;
+        s1 = s0;
+    }
+    let power = s2.mul_add(s2, s1.mul_add(s1, -coeff * s1 * s2));
+    power.max(0.0).sqrt() * 2.0 / n
+}
@@ -0,0 +1,759 @@
+/// High-level AURA controller interface.
+///
+/// Wraps a HID device handle and provides typed methods for setting
+/// effects and per-LED colours on ASUS motherboard AURA controllers.
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use hidapi::{DeviceInfo, HidApi, HidDevice};
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+use super::protocol::{
+    self, AuraChannelInfo, AuraConfigTable, AuraDirection, AuraEffect, AuraSpeed, ColorCalibration,
+    RgbColor, AURA_MB_PIDS, AURA_USAGE_PAGE, AURA_VID, MAX_LEDS_PER_PACKET, REPORT_SIZE,
+    RESPONSE_MASK,
+};
+
+/// How long to wait for a reply to a query report.
+const QUERY_TIMEOUT_MS: i32 = 500;
+
+/// Unrelated input reports to skip while waiting for a reply.
+const QUERY_MAX_REPORTS: usize = 8;
+
+/// Information about a discovered AURA device.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuraDeviceInfo {
+    /// Position in the discovered device list (used by `aura_*` commands).
+    pub index: usize,
+    /// USB Product ID.
+    pub pid: u16,
+    /// Product name reported by the device (may be empty).
+    pub product: String,
+    /// OS device path, unique per HID interface.
+    pub path: String,
+    /// Firmware version string (e.g. `AULA3-AR32-0207`), if the query succeeded.
+    pub firmware: Option<String>,
+    /// Parsed configuration table, if the query succeeded.
+    pub config: Option<AuraConfigTable>,
+    /// Lighting channels, from the configuration table or a per-PID default.
+    pub channels: Vec<AuraChannelInfo>,
+    /// Effects supported on at least one channel. Travelling effects
+    /// (comet, wave, ...) are limited to addressable channels.
+    pub effects: Vec<AuraEffect>,
+}
+
+/// A firmware effect as sent to one channel.
+#[derive(Debug, Clone, Copy)]
+struct AppliedEffect {
+    effect: AuraEffect,
+    color: RgbColor,
+    speed: AuraSpeed,
+    direction: AuraDirection,
+}
+
+impl AppliedEffect {
+    const OFF: Self = Self {
+        effect: AuraEffect::Off,
+        color: RgbColor::BLACK,
+        speed: AuraSpeed::Medium,
+        direction: AuraDirection::Forward,
+    };
+}
+
+/// The HID reports an [`AuraController`] exchanges with its device.
+///
+/// Implemented for `hidapi`'s `HidDevice`. An in-memory fake that records
+/// the written reports and answers queries can be passed to
+/// [`AuraController::from_device`] to exercise the controller
+/// off-hardware.
+pub trait AuraHid: Send {
+    /// Write one output report (Report ID at index 0).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Hid` if the device rejects the report or is gone.
+    fn write(&self, report: &[u8]) -> Result<()>;
+
+    /// Read one input report into `buf`, waiting up to `timeout_ms`.
+    /// Returns the number of bytes read, 0 on timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Hid` if the read fails.
+    fn read_timeout(&self, buf: &mut [u8], timeout_ms: i32) -> Result<usize>;
+}
+
+impl AuraHid for HidDevice {
+    fn write(&self, report: &[u8]) -> Result<()> {
+        let _ = Self::write(self, report)
+            .map_err(|e| NoCrateError::Hid(format!("HID write failed: {e}")))?;
+        Ok(())
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout_ms: i32) -> Result<usize> {
+        Self::read_timeout(self, buf, timeout_ms)
+            .map_err(|e| NoCrateError::Hid(format!("HID read failed: {e}")))
+    }
+}
+
+/// Handle to an open ASUS AURA controller.
+///
+/// The `hidapi` context is initialized once per process and never torn
+/// down, so only the open device is kept. It is closed on drop.
+pub struct AuraController {
+    /// `None` for a [simulated](Self::simulated) controller.
+    device: Option<Box<dyn AuraHid>>,
+    info: AuraDeviceInfo,
+    /// Last firmware effect applied per channel, replayed after reconnect.
+    last_effects: Mutex<BTreeMap<u8, AppliedEffect>>,
+    /// Channels [`Self::dim`] wrote to, the only ones [`Self::undim`] restores.
+    dimmed: Mutex<BTreeSet<u8>>,
+    /// Set once a HID read / write fails (device unplugged or reset).
+    io_failed: AtomicBool,
+    /// Gamma / white-point correction applied to outgoing colours.
+    calibration: Mutex<ColorCalibration>,
+}
+
+// The device is Send but not Sync. The app keeps the controllers behind a
+// Mutex in AppState, so this is safe.
+#[allow(unsafe_code)]
+unsafe impl Sync for AuraController {}
+
+impl AuraController {
+    /// Enumerate USB HID devices and open every matching AURA controller.
+    ///
+    /// Boards with both a mainboard controller and an addressable Gen2
+    /// controller expose several HID devices; all of them are returned,
+    /// ordered by PID priority in [`AURA_MB_PIDS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Hid` error if no AURA controller is found or none of the
+    /// matching devices can be opened.
+    pub fn discover_all() -> Result<Vec<Self>> {
+        let api = HidApi::new()?;
+
+        let mut candidates: Vec<_> = api.device_list().filter(|d| is_aura_interface(d)).collect();
+        candidates.sort_by_key(|d| AURA_MB_PIDS.iter().position(|&p| p == d.product_id()));
+        // The same interface can be listed more than once, not necessarily adjacently
+        let mut seen = HashSet::new();
+        candidates.retain(|d| seen.insert(d.path()));
+
+        let mut controllers = Vec::new();
+        for info in candidates {
+            let path = info.path().to_string_lossy().into_owned();
+            match api.open_path(info.path()) {
+                Ok(device) => {
+                    let product = device
+                        .get_product_string()
+                        .ok()
+                        .flatten()
+                        .unwrap_or_default();
+
+                    let mut ctrl =
+                        Self::from_device(Box::new(device), info.product_id(), product, path);
+                    ctrl.info.index = controllers.len();
+                    controllers.push(ctrl);
+                }
+                Err(e) => eprintln!("AURA: failed to open {path}: {e}"),
+            }
+        }
+
+        if !controllers.is_empty() {
+            return Ok(controllers);
+        }
+
+        Err(NoCrateError::DeviceNotPresent(
+            "No AURA motherboard controller found. Checked PIDs: [{}]".replace(
+                "[{}]",
+                &AURA_MB_PIDS
+                    .iter()
+                    .map(|p| format!("0x{p:04X}"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        ))
+    }
+
+    /// Wrap an open device and query its firmware and channel layout.
+    #[must_use]
+    pub fn from_device(device: Box<dyn AuraHid>, pid: u16, product: String, path: String) -> Self {
+        let mut ctrl = Self {
+            device: Some(device),
+            info: AuraDeviceInfo {
+                index: 0,
+                pid,
+                product,
+                path,
+                firmware: None,
+                config: None,
+                channels: Vec::new(),
+                effects: Vec::new(),
+            },
+            last_effects: Mutex::new(BTreeMap::new()),
+            dimmed: Mutex::new(BTreeSet::new()),
+            io_failed: AtomicBool::new(false),
+            calibration: Mutex::new(ColorCalibration::default()),
+        };
+        ctrl.query_capabilities();
+        ctrl
+    }
+
+    /// A controller that accepts every report without a device behind
+    /// it, for development without AURA hardware. It reports the default
+    /// channel layout of an addressable Gen 2 controller.
+    #[must_use]
+    pub fn simulated() -> Self {
+        const PID: u16 = 0x19AF;
+        let mut ctrl = Self {
+            device: None,
+            info: AuraDeviceInfo {
+                index: 0,
+                pid: PID,
+                product: "AURA LED Controller (simulated)".into(),
+                path: "simulated".into(),
+                firmware: Some("AULA3-SIM-0001".into()),
+                config: None,
+                channels: protocol::default_channels(PID),
+                effects: Vec::new(),
+            },
+            last_effects: Mutex::new(BTreeMap::new()),
+            dimmed: Mutex::new(BTreeSet::new()),
+            io_failed: AtomicBool::new(false),
+            calibration: Mutex::new(ColorCalibration::default()),
+        };
+        ctrl.info.effects = ctrl.supported_effects();
+        ctrl
+    }
+
+    /// Whether this is a [simulated](Self::simulated) controller.
+    #[must_use]
+    pub const fn is_simulated(&self) -> bool {
+        self.device.is_none()
+    }
+
+    /// Information about the connected device.
+    #[must_use]
+    pub fn info(&self) -> &AuraDeviceInfo {
+        &self.info
+    }
+
+    /// Lighting channels exposed by this controller.
+    #[must_use]
+    pub fn channels(&self) -> &[AuraChannelInfo] {
+        &self.info.channels
+    }
+
+    /// Look up a channel by index.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Hid` error if the controller has no such channel.
+    pub fn channel(&self, channel: u8) -> Result<&AuraChannelInfo> {
+        self.info
+            .channels
+            .iter()
+            .find(|c| c.index == channel)
+            .ok_or_else(|| {
+                NoCrateError::Hid(i18n::tf(
+                    Msg::AuraChannelMissing,
+                    &[
+                        ("channel", &channel),
+                        ("product", &self.info.product),
+                        ("pid", &format!("0x{:04X}", self.info.pid)),
+                    ],
+                ))
+            })
+    }
+
+    /// Read firmware version and configuration table into `info`.
+    ///
+    /// Failures are logged and fall back to the default channel layout,
+    /// since older firmware may not answer every query.
+    fn query_capabilities(&mut self) {
+        match self.query(&protocol::build_firmware_query()) {
+            Ok(reply) => self.info.firmware = protocol::parse_firmware(&reply),
+            Err(e) => eprintln!("AURA: firmware query failed on {}: {e}", self.info.path),
+        }
+        match self.query(&protocol::build_config_table_query()) {
+            Ok(reply) => self.info.config = AuraConfigTable::parse(&reply),
+            Err(e) => eprintln!("AURA: config table query failed on {}: {e}", self.info.path),
+        }
+
+        self.info.channels = self
+            .info
+            .config
+            .map(|table| table.channels(self.info.pid))
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| protocol::default_channels(self.info.pid));
+        self.info.effects = self.supported_effects();
+    }
+
+    /// Effects supported on at least one of the channels in `info`.
+    fn supported_effects(&self) -> Vec<AuraEffect> {
+        AuraEffect::ALL
+            .into_iter()
+            .filter(|e| {
+                self.info
+                    .channels
+                    .iter()
+                    .any(|ch| e.supported_by(self.info.pid, ch))
+            })
+            .collect()
+    }
+
+    // ── Effect mode ──────────────────────────────────────────
+
+    /// Set an effect mode with a base colour, speed and direction on one
+    /// channel.
+    ///
+    /// Automatically sends a commit after the effect packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Config` error if the controller or channel does not
+    /// support the effect.
+    pub fn set_effect(
+        &self,
+        channel: u8,
+        effect: AuraEffect,
+        color: RgbColor,
+        speed: AuraSpeed,
+        direction: AuraDirection,
+    ) -> Result<()> {
+        let applied = AppliedEffect {
+            effect,
+            color,
+            speed,
+            direction,
+        };
+        self.write_effect(channel, applied)?;
+        let _ = self.last_effects.lock().insert(channel, applied);
+        Ok(())
+    }
+
+    /// Send an effect and commit without recording it in `last_effects`.
+    fn write_effect(&self, channel: u8, applied: AppliedEffect) -> Result<()> {
+        let info = self.channel(channel)?;
+        if !applied.effect.supported_by(self.info.pid, info) {
+            return Err(NoCrateError::Config(format!(
+                "Effect {:?} not supported on {} (PID 0x{:04X})",
+                applied.effect, info.name, self.info.pid
+            )));
+        }
+        let color = self.calibration.lock().apply(applied.color);
+        let report = protocol::build_set_effect(
+            channel,
+            applied.effect,
+            color,
+            applied.speed,
+            applied.direction,
+        );
+        self.write(&report)?;
+
+        // Commit
+        let commit = protocol::build_commit();
+        self.write(&commit)
+    }
+
+    /// Temporarily dim every channel to `brightness` percent (0 = off)
+    /// without replacing the recorded effects, so [`Self::undim`] can
+    /// restore them.
+    ///
+    /// Firmware effects have no brightness setting, so the base colour is
+    /// scaled instead; colour-cycling effects are only dimmed at 0.
+    pub fn dim(&self, brightness: u8) {
+        let effects = self.last_effects.lock().clone();
+        let mut dimmed = self.dimmed.lock();
+        for ch in &self.info.channels {
+            let applied = match effects.get(&ch.index) {
+                Some(&recorded) if brightness > 0 => AppliedEffect {
+                    color: recorded.color.scaled(brightness),
+                    ..recorded
+                },
+                // Nothing recorded to scale: leave the firmware effect alone
+                None if brightness > 0 => continue,
+                _ => AppliedEffect::OFF,
+            };
+            // Attempted writes count too: a failed one may still have landed
+            let _ = dimmed.insert(ch.index);
+            if let Err(e) = self.write_effect(ch.index, applied) {
+                eprintln!("AURA: failed to dim channel {}: {e}", ch.index);
+            }
+        }
+    }
+
+    /// Undo [`Self::dim`] on the channels it changed: re-apply their
+    /// recorded effects at full brightness. A channel turned off without
+    /// a recorded effect returns to the firmware default (rainbow);
+    /// channels `dim` left alone keep their firmware or flash effect.
+    pub fn undim(&self) {
+        let effects = self.last_effects.lock().clone();
+        let dimmed = std::mem::take(&mut *self.dimmed.lock());
+        for channel in dimmed {
+            let applied = effects.get(&channel).copied().unwrap_or(AppliedEffect {
+                effect: AuraEffect::Rainbow,
+                ..AppliedEffect::OFF
+            });
+            if let Err(e) = self.write_effect(channel, applied) {
+                eprintln!("AURA: failed to restore channel {channel}: {e}");
+            }
+        }
+    }
+
+    /// Key of this controller in `AppConfig::aura_calibration`: the USB
+    /// PID as four lowercase hex digits.
+    #[must_use]
+    pub fn calibration_key(&self) -> String {
+        format!("{:04x}", self.info.pid)
+    }
+
+    /// Set the colour correction used for all subsequent writes. Colours
+    /// already shown are not updated.
+    pub fn set_calibration(&self, calibration: ColorCalibration) {
+        *self.calibration.lock() = calibration;
+    }
+
+    /// Pick this controller's entry from the configured calibrations,
+    /// falling back to no correction.
+    pub fn load_calibration(&self, calibrations: &HashMap<String, ColorCalibration>) {
+        self.set_calibration(
+            calibrations
+                .get(&self.calibration_key())
+                .copied()
+                .unwrap_or_default(),
+        );
+    }
+
+    /// Whether a HID read / write has failed on this handle.
+    ///
+    /// Once set the handle is unusable and the controller list should be
+    /// rediscovered (see `aura::hotplug`).
+    #[must_use]
+    pub fn io_failed(&self) -> bool {
+        self.io_failed.load(Ordering::Relaxed)
+    }
+
+    /// Re-apply the firmware effects last set on `previous`, typically the
+    /// stale handle this controller replaces after a reconnect.
+    pub fn restore_from(&self, previous: &Self) {
+        let effects = previous.last_effects.lock().clone();
+        for (channel, applied) in effects {
+            match self.write_effect(channel, applied) {
+                Ok(()) => {
+                    let _ = self.last_effects.lock().insert(channel, applied);
+                }
+                Err(e) => eprintln!("AURA: failed to restore channel {channel}: {e}"),
+            }
+        }
+    }
+
+    /// Convenience: set a solid static colour on all LEDs of a channel.
+    pub fn set_static_color(&self, channel: u8, color: RgbColor) -> Result<()> {
+        self.set_effect(
+            channel,
+            AuraEffect::Static,
+            color,
+            AuraSpeed::Medium,
+            AuraDirection::Forward,
+        )
+    }
+
+    /// Turn all LEDs of a channel off.
+    pub fn turn_off(&self, channel: u8) -> Result<()> {
+        self.set_effect(
+            channel,
+            AuraEffect::Off,
+            RgbColor::BLACK,
+            AuraSpeed::Medium,
+            AuraDirection::Forward,
+        )
+    }
+
+    /// Write the current effects of all channels to controller flash.
+    ///
+    /// Flash has limited write endurance; call this only on explicit
+    /// user request, not on every effect change.
+    pub fn save_to_flash(&self) -> Result<()> {
+        self.write(&protocol::build_save())
+    }
+
+    // ── Direct per-LED control ───────────────────────────────
+
+    /// Set individual LED colours on a channel in direct mode.
+    ///
+    /// Automatically batches into multiple HID packets if there are
+    /// more LEDs than [`MAX_LEDS_PER_PACKET`].
+    pub fn set_direct_colors(&self, channel: u8, colors: &[RgbColor]) -> Result<()> {
+        self.set_direct_colors_delta(channel, colors, None)
+    }
+
+    /// Like [`Self::set_direct_colors`], but only sends the packets whose
+    /// LEDs differ from `previous`, the frame last shown on the channel.
+    /// Nothing is sent if the frame is unchanged.
+    pub fn set_direct_colors_delta(
+        &self,
+        channel: u8,
+        colors: &[RgbColor],
+        previous: Option<&[RgbColor]>,
+    ) -> Result<()> {
+        let _ = self.channel(channel)?;
+        let changed: Vec<usize> = colors
+            .chunks(MAX_LEDS_PER_PACKET)
+            .enumerate()
+            .filter(|&(i, chunk)| {
+                let start = i * MAX_LEDS_PER_PACKET;
+                previous.is_none_or(|prev| {
+                    prev.len() != colors.len() || prev[start..start + chunk.len()] != *chunk
+                })
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        let calibration = *self.calibration.lock();
+        let last = changed.len().saturating_sub(1);
+        for (n, chunk_idx) in changed.into_iter().enumerate() {
+            let start = chunk_idx * MAX_LEDS_PER_PACKET;
+            let end = (start + MAX_LEDS_PER_PACKET).min(colors.len());
+            let chunk: Vec<RgbColor> = colors[start..end]
+                .iter()
+                .map(|&c| calibration.apply(c))
+                .collect();
+            let report = protocol::build_direct(channel, start as u8, &chunk, n == last);
+            self.write(&report)?;
+        }
+        Ok(())
+    }
+
+    // ── Internal I/O ─────────────────────────────────────────
+
+    /// Send a query report and wait for the reply echoing its command.
+    ///
+    /// The returned buffer has the same layout as an output report
+    /// (Report ID at index 0).
+    fn query(&self, report: &[u8; REPORT_SIZE]) -> Result<[u8; REPORT_SIZE]> {
+        let Some(device) = &self.device else {
+            return Err(NoCrateError::Hid(
+                "Simulated controller cannot answer queries".into(),
+            ));
+        };
+        self.write(report)?;
+        let expected = report[1] & RESPONSE_MASK;
+
+        let mut buf = [0u8; REPORT_SIZE];
+        for _ in 0..QUERY_MAX_REPORTS {
+            // hidapi strips the Report ID on devices that use none; keep index 0 for it
+            let n = device
+                .read_timeout(&mut buf[1..], QUERY_TIMEOUT_MS)
+                .inspect_err(|_| self.io_failed.store(true, Ordering::Relaxed))?;
+            if n == 0 {
+                break;
+            }
+            if buf[1] == expected {
+                return Ok(buf);
+            }
+        }
+        Err(NoCrateError::Hid(format!(
+            "No reply to command 0x{:02X}",
+            report[1]
+        )))
+    }
+
+    fn write(&self, report: &[u8]) -> Result<()> {
+        let Some(device) = &self.device else {
+            return Ok(());
+        };
+        device
+            .write(report)
+            .inspect_err(|_| self.io_failed.store(true, Ordering::Relaxed))
+    }
+}
+
+/// Whether an enumerated HID interface is an AURA lighting endpoint.
+///
+/// Each controller exposes several interfaces; only the vendor usage
+/// page accepts lighting reports.
+#[must_use]
+pub fn is_aura_interface(d: &DeviceInfo) -> bool {
+    d.vendor_id() == AURA_VID
+        && AURA_MB_PIDS.contains(&d.product_id())
+        && (d.usage_page() == 0 || d.usage_page() == AURA_USAGE_PAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::aura::protocol::{
+        AuraChannelKind, CMD_CONFIG_TABLE, CMD_FIRMWARE, CONFIG_TABLE_OFFSET,
+    };
+
+    /// In-memory controller: records every written report and answers
+    /// reads from a queue of canned replies, 0 bytes once it is empty.
+    #[derive(Clone, Default)]
+    struct FakeHid {
+        written: Arc<Mutex<Vec<Vec<u8>>>>,
+        replies: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    }
+
+    impl AuraHid for FakeHid {
+        fn write(&self, report: &[u8]) -> Result<()> {
+            self.written.lock().push(report.to_vec());
+            Ok(())
+        }
+
+        fn read_timeout(&self, buf: &mut [u8], _timeout_ms: i32) -> Result<usize> {
+            let Some(reply) = self.replies.lock().pop_front() else {
+                return Ok(0);
+            };
+            buf[..reply.len()].copy_from_slice(&reply);
+            Ok(reply.len())
+        }
+    }
+
+    /// Replies as read, i.e. without the Report ID byte.
+    fn firmware_reply() -> Vec<u8> {
+        let mut reply = vec![CMD_FIRMWARE & RESPONSE_MASK];
+        reply.extend_from_slice(b"AULA3-AR32-0207\0");
+        reply
+    }
+
+    /// Two ARGB headers, one 12V RGB header and 4 LEDs on the mainboard
+    /// channel, one of them the RGB header.
+    fn config_reply() -> Vec<u8> {
+        let mut reply = vec![0u8; REPORT_SIZE - 1];
+        reply[0] = CMD_CONFIG_TABLE & RESPONSE_MASK;
+        let table = &mut reply[CONFIG_TABLE_OFFSET - 1..];
+        table[0x02] = 2;
+        table[0x1B] = 4;
+        table[0x1D] = 1;
+        reply
+    }
+
+    fn open(hid: &FakeHid) -> AuraController {
+        AuraController::from_device(
+            Box::new(hid.clone()),
+            0x18F3,
+            "AURA Mainboard".into(),
+            "fake".into(),
+        )
+    }
+
+    #[test]
+    fn from_device_reads_firmware_and_channels() {
+        let hid = FakeHid::default();
+        hid.replies
+            .lock()
+            .extend([firmware_reply(), config_reply()]);
+
+        let ctrl = open(&hid);
+
+        assert_eq!(ctrl.info().firmware.as_deref(), Some("AULA3-AR32-0207"));
+        let channels: Vec<_> = ctrl
+            .channels()
+            .iter()
+            .map(|c| (c.index, c.name.as_str(), c.kind, c.led_count))
+            .collect();
+        assert_eq!(
+            channels,
+            [
+                (0, "Mainboard", AuraChannelKind::Fixed, 3),
+                (1, "RGB Header", AuraChannelKind::Fixed, 1),
+                (2, "ARGB Header 1", AuraChannelKind::Addressable, 120),
+                (3, "ARGB Header 2", AuraChannelKind::Addressable, 120),
+            ]
+        );
+        assert_eq!(
+            *hid.written.lock(),
+            [
+                protocol::build_firmware_query().to_vec(),
+                protocol::build_config_table_query().to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_device_falls_back_to_default_channels() {
+        let hid = FakeHid::default();
+
+        let ctrl = open(&hid);
+
+        assert_eq!(ctrl.info().firmware, None);
+        assert_eq!(
+            ctrl.channels().len(),
+            protocol::default_channels(0x18F3).len()
+        );
+    }
+
+    #[test]
+    fn set_effect_writes_effect_then_commit() {
+        let hid = FakeHid::default();
+        hid.replies
+            .lock()
+            .extend([firmware_reply(), config_reply()]);
+        let ctrl = open(&hid);
+        hid.written.lock().clear();
+
+        ctrl.set_effect(
+            2,
+            AuraEffect::Comet,
+            RgbColor::new(0x12, 0x34, 0x56),
+            AuraSpeed::Fast,
+            AuraDirection::Reverse,
+        )
+        .unwrap();
+
+        let written = hid.written.lock();
+        assert_eq!(written.len(), 2);
+        assert_eq!(
+            written[0][..9],
+            [0x00, 0x35, 0x02, 0x09, 0x12, 0x34, 0x56, 0x01, 0x01]
+        );
+        assert_eq!(written[1], protocol::build_commit());
+    }
+
+    #[test]
+    fn unsupported_effect_is_not_written() {
+        let hid = FakeHid::default();
+        let ctrl = open(&hid);
+        hid.written.lock().clear();
+
+        let result = ctrl.set_effect(
+            0,
+            AuraEffect::Wave,
+            RgbColor::WHITE,
+            AuraSpeed::Medium,
+            AuraDirection::Forward,
+        );
+
+        assert!(matches!(result, Err(NoCrateError::Config(_))));
+        assert!(hid.written.lock().is_empty());
+    }
+
+    #[test]
+    fn undim_restores_only_dimmed_channels() {
+        let hid = FakeHid::default();
+        let ctrl = open(&hid);
+        ctrl.set_static_color(1, RgbColor::new(200, 100, 50))
+            .unwrap();
+        hid.written.lock().clear();
+
+        ctrl.dim(50);
+        ctrl.undim();
+
+        let effects: Vec<_> = hid
+            .written
+            .lock()
+            .iter()
+            .filter(|r| r[2] != 0xFF)
+            .map(|r| r[2..7].to_vec())
+            .collect();
+        assert_eq!(effects, [[1, 0x01, 100, 50, 25], [1, 0x01, 200, 100, 50]]);
+    }
+}
@@ -0,0 +1,231 @@
+/// AURA DRAM lighting over SMBus.
+///
+/// RGB memory modules with ASUS AURA support carry an ENE controller on
+/// the SMBus. Registers are 16-bit and accessed indirectly: the register
+/// address is written (byte-swapped) to command `0x00`, then data is
+/// read from command `0x81` or written to command `0x01`.
+///
+/// Before anything is written, a device must answer reads of commands
+/// `0xA0`-`0xAF` with `0x00`-`0x0F`, the ENE signature OpenRGB checks, so
+/// other SMBus devices in the address range are never written to.
+///
+/// Protocol details sourced from OpenRGB's ENE SMBus controller. Modules
+/// still at the power-on address 0x77 that share it with other sticks
+/// need remapping first, which is not done here; modules already mapped
+/// into 0x70-0x77 by BIOS or other software are detected.
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::smbus::SmbusController;
+
+use super::protocol::{AuraDirection, AuraEffect, AuraSpeed, RgbColor};
+
+/// SMBus addresses probed for ENE DRAM controllers.
+pub const DRAM_ADDRESSES: std::ops::RangeInclusive<u8> = 0x70..=0x77;
+
+// ─── SMBus commands ──────────────────────────────────────────
+
+const CMD_SET_ADDRESS: u8 = 0x00;
+const CMD_WRITE_BYTE: u8 = 0x01;
+const CMD_READ_BYTE: u8 = 0x81;
+/// Reads of `CMD_SIGNATURE + i` return `i` for `i` in `0..16`.
+const CMD_SIGNATURE: u8 = 0xA0;
+
+// ─── ENE registers ───────────────────────────────────────────
+
+/// Device name / firmware string (16 bytes).
+const REG_DEVICE_NAME: u16 = 0x1000;
+/// Configuration table (64 bytes); byte 0x02 is the LED count.
+const REG_CONFIG_TABLE: u16 = 0x1C00;
+const CONFIG_LED_COUNT: u16 = 0x02;
+/// Direct-mode colours (3 bytes per LED, R B G order). This window is
+/// 15 bytes, so it only holds [`V1_MAX_LEDS`] LEDs.
+const REG_COLORS_DIRECT: u16 = 0x8000;
+/// Effect colours, 15 bytes like [`REG_COLORS_DIRECT`].
+const REG_COLORS_EFFECT: u16 = 0x8010;
+/// Larger colour windows of newer controllers, up to [`V2_MAX_LEDS`] LEDs.
+const REG_COLORS_DIRECT_V2: u16 = 0x8100;
+const REG_COLORS_EFFECT_V2: u16 = 0x8160;
+const V1_MAX_LEDS: u8 = 5;
+const V2_MAX_LEDS: u8 = 32;
+/// 1 = direct mode, 0 = effect mode.
+const REG_DIRECT: u16 = 0x8020;
+const REG_MODE: u16 = 0x8021;
+const REG_SPEED: u16 = 0x8022;
+const REG_DIRECTION: u16 = 0x8023;
+/// Write [`APPLY_VAL`] to latch the pending changes.
+const REG_APPLY: u16 = 0x80A0;
+const APPLY_VAL: u8 = 0x01;
+
+/// Name prefixes of known ENE DRAM controllers.
+const KNOWN_NAMES: &[&str] = &["DIMM_LED", "AUDA0-E6K5", "AUMA0-E6K5", "AUMA0-E8K4"];
+
+/// Fallback LED count if the configuration table reads zero; fits the
+/// original colour windows.
+const DEFAULT_LED_COUNT: u8 = V1_MAX_LEDS;
+
+/// Information about a detected DRAM lighting controller.
+#[derive(Debug, Clone, Serialize)]
+pub struct DramDeviceInfo {
+    /// Position in the detected list (used by `aura_dram_*` commands).
+    pub index: usize,
+    /// SMBus address.
+    pub address: u8,
+    /// Device name / firmware string.
+    pub name: String,
+    pub led_count: u8,
+}
+
+/// An ENE DRAM lighting controller.
+pub struct DramController {
+    smbus: Arc<SmbusController>,
+    info: DramDeviceInfo,
+}
+
+impl DramController {
+    /// Probe [`DRAM_ADDRESSES`] for ENE controllers.
+    pub fn detect_all(smbus: &Arc<SmbusController>) -> Vec<Self> {
+        let mut found = Vec::new();
+        for address in DRAM_ADDRESSES {
+            if !smbus.probe(address) {
+                continue;
+            }
+            let mut ctrl = Self {
+                smbus: Arc::clone(smbus),
+                info: DramDeviceInfo {
+                    index: found.len(),
+                    address,
+                    name: String::new(),
+                    led_count: 0,
+                },
+            };
+            match ctrl.identify() {
+                Ok(true) => {
+                    eprintln!("AURA DRAM: {:?}", ctrl.info);
+                    found.push(ctrl);
+                }
+                Ok(false) => {}
+                Err(e) => eprintln!("AURA DRAM: probe at 0x{address:02X} failed: {e}"),
+            }
+        }
+        found
+    }
+
+    /// Read the device name and LED count; `false` if not an ENE controller.
+    fn identify(&mut self) -> Result<bool> {
+        // Read-only check first: reading the name writes the register address
+        for i in 0..16u8 {
+            let value = self
+                .smbus
+                .read_byte_data(self.info.address, CMD_SIGNATURE + i)?;
+            if value != i {
+                return Ok(false);
+            }
+        }
+
+        let raw = (0..16u16)
+            .map(|i| self.read_register(REG_DEVICE_NAME + i))
+            .collect::<Result<Vec<u8>>>()?;
+        let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+        let name = String::from_utf8_lossy(&raw[..end]).trim().to_string();
+        if !KNOWN_NAMES.iter().any(|p| name.starts_with(p)) {
+            return Ok(false);
+        }
+
+        let led_count = self.read_register(REG_CONFIG_TABLE + CONFIG_LED_COUNT)?;
+        self.info.name = name;
+        self.info.led_count = if led_count == 0 {
+            DEFAULT_LED_COUNT
+        } else {
+            led_count.min(V2_MAX_LEDS)
+        };
+        Ok(true)
+    }
+
+    /// Information about the module.
+    #[must_use]
+    pub const fn info(&self) -> &DramDeviceInfo {
+        &self.info
+    }
+
+    /// Set a firmware effect with one colour on all LEDs.
+    pub fn set_effect(
+        &self,
+        effect: AuraEffect,
+        color: RgbColor,
+        speed: AuraSpeed,
+        direction: AuraDirection,
+    ) -> Result<()> {
+        let colors = vec![color; usize::from(self.info.led_count)];
+        self.write_colors(self.color_registers().1, &colors)?;
+        self.write_register(REG_DIRECT, 0)?;
+        self.write_register(REG_MODE, ene_mode(effect))?;
+        self.write_register(REG_SPEED, speed.to_raw())?;
+        self.write_register(REG_DIRECTION, direction.to_raw())?;
+        self.write_register(REG_APPLY, APPLY_VAL)
+    }
+
+    /// Set individual LED colours in direct mode.
+    pub fn set_direct_colors(&self, colors: &[RgbColor]) -> Result<()> {
+        let n = colors.len().min(usize::from(self.info.led_count));
+        self.write_register(REG_DIRECT, 1)?;
+        self.write_colors(self.color_registers().0, &colors[..n])?;
+        self.write_register(REG_APPLY, APPLY_VAL)
+    }
+
+    /// Direct and effect colour windows: the original ones are too small
+    /// for more than [`V1_MAX_LEDS`] LEDs and would spill into the mode
+    /// registers, so larger modules use the V2 windows as OpenRGB does.
+    const fn color_registers(&self) -> (u16, u16) {
+        if self.info.led_count > V1_MAX_LEDS {
+            (REG_COLORS_DIRECT_V2, REG_COLORS_EFFECT_V2)
+        } else {
+            (REG_COLORS_DIRECT, REG_COLORS_EFFECT)
+        }
+    }
+
+    fn write_colors(&self, base: u16, colors: &[RgbColor]) -> Result<()> {
+        let mut reg = base;
+        for c in colors {
+            // ENE stores colours as R, B, G
+            for byte in [c.r, c.b, c.g] {
+                self.write_register(reg, byte)?;
+                reg += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_address(&self, reg: u16) -> Result<()> {
+        self.smbus
+            .write_word_data(self.info.address, CMD_SET_ADDRESS, reg.swap_bytes())
+    }
+
+    fn read_register(&self, reg: u16) -> Result<u8> {
+        self.set_address(reg)?;
+        self.smbus.read_byte_data(self.info.address, CMD_READ_BYTE)
+    }
+
+    fn write_register(&self, reg: u16, value: u8) -> Result<()> {
+        self.set_address(reg)?;
+        self.smbus
+            .write_byte_data(self.info.address, CMD_WRITE_BYTE, value)
+    }
+}
+
+/// Map an AURA effect to the ENE mode register value.
+const fn ene_mode(effect: AuraEffect) -> u8 {
+    match effect {
+        AuraEffect::Off => 0x00,
+        AuraEffect::Static => 0x01,
+        AuraEffect::Breathing => 0x02,
+        AuraEffect::ColorCycle | AuraEffect::SpectrumCycle => 0x04,
+        AuraEffect::Rainbow => 0x05,
+        AuraEffect::FlashAndDash => 0x07,
+        AuraEffect::Comet => 0x09,
+        AuraEffect::Wave => 0x0B,
+        AuraEffect::Strobe => 0x0D,
+    }
+}
@@ -0,0 +1,7 @@
+pub mod ambient;
+pub mod audio;
+pub mod controller;
+#[cfg(feature = "sio")]
+pub mod dram;
+pub mod openrgb;
+pub mod protocol;
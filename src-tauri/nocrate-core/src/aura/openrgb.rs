@@ -0,0 +1,371 @@
+/// OpenRGB SDK client for passthrough mode.
+///
+/// Users running OpenRGB alongside NoCrate would otherwise fight over the
+/// AURA HID interface. In passthrough mode NoCrate does not open the
+/// device at all and instead streams software effect frames to the
+/// OpenRGB SDK server, which owns the hardware.
+///
+/// Only the parts of the SDK protocol needed for that are implemented.
+/// The client speaks protocol version 0 so the controller description
+/// carries no per-version extensions.
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+use super::protocol::{EffectTarget, RgbColor};
+
+/// OpenRGB passthrough settings.
+///
+/// When enabled, NoCrate leaves the AURA HID devices to OpenRGB and
+/// streams software effects through its SDK server; effect targets then
+/// address OpenRGB controllers (`device`) and zones (`channel`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenRgbConfig {
+    pub enabled: bool,
+    /// SDK server host
+    pub host: String,
+    /// SDK server port (OpenRGB default 6742)
+    pub port: u16,
+}
+
+impl Default for OpenRgbConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".into(),
+            port: 6742,
+        }
+    }
+}
+
+const MAGIC: &[u8; 4] = b"ORGB";
+const HEADER_LEN: usize = 16;
+
+// ─── Packet IDs ──────────────────────────────────────────────
+
+const REQUEST_CONTROLLER_COUNT: u32 = 0;
+const REQUEST_CONTROLLER_DATA: u32 = 1;
+const SET_CLIENT_NAME: u32 = 50;
+const UPDATE_ZONE_LEDS: u32 = 1051;
+const SET_CUSTOM_MODE: u32 = 1100;
+
+const CLIENT_NAME: &str = "NoCrate";
+
+/// Connect / read timeout; a local server answers in well under this.
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Upper bound on a response payload, to reject garbage headers.
+const MAX_PACKET_LEN: usize = 1 << 20;
+
+/// A zone of an OpenRGB controller.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenRgbZone {
+    /// Index to pass as `channel` to the software effect commands.
+    pub index: u8,
+    pub name: String,
+    pub led_count: u32,
+}
+
+/// A controller exposed by the OpenRGB server.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenRgbDevice {
+    /// Index to pass as `device` to the software effect commands.
+    pub index: usize,
+    pub name: String,
+    pub zones: Vec<OpenRgbZone>,
+}
+
+/// Connection to an OpenRGB SDK server.
+pub struct OpenRgbClient {
+    stream: TcpStream,
+}
+
+impl OpenRgbClient {
+    /// Connect to the server in `config` and register the client name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Hid` error if the server cannot be reached.
+    pub fn connect(config: &OpenRgbConfig) -> Result<Self> {
+        let addr = (config.host.as_str(), config.port)
+            .to_socket_addrs()
+            .map_err(|e| io_error(&e))?
+            .next()
+            .ok_or_else(|| {
+                NoCrateError::Hid(i18n::tf(Msg::OpenRgbResolve, &[("host", &config.host)]))
+            })?;
+        let stream = TcpStream::connect_timeout(&addr, IO_TIMEOUT).map_err(|e| io_error(&e))?;
+        stream
+            .set_read_timeout(Some(IO_TIMEOUT))
+            .and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT)))
+            .and_then(|()| stream.set_nodelay(true))
+            .map_err(|e| io_error(&e))?;
+
+        let mut client = Self { stream };
+        let mut name = CLIENT_NAME.as_bytes().to_vec();
+        name.push(0);
+        client.send(0, SET_CLIENT_NAME, &name)?;
+        Ok(client)
+    }
+
+    /// List the controllers and their zones.
+    pub fn devices(&mut self) -> Result<Vec<OpenRgbDevice>> {
+        self.send(0, REQUEST_CONTROLLER_COUNT, &[])?;
+        let data = self.receive(REQUEST_CONTROLLER_COUNT)?;
+        let count = Reader::new(&data).u32()?;
+
+        (0..count)
+            .map(|i| {
+                self.send(i, REQUEST_CONTROLLER_DATA, &[])?;
+                let data = self.receive(REQUEST_CONTROLLER_DATA)?;
+                parse_controller(&data, i as usize)
+            })
+            .collect()
+    }
+
+    /// Switch a controller to its direct / custom mode.
+    pub fn set_custom_mode(&mut self, device: usize) -> Result<()> {
+        self.send(device_id(device)?, SET_CUSTOM_MODE, &[])
+    }
+
+    /// Set the LED colours of one zone.
+    pub fn update_zone_leds(&mut self, device: usize, zone: u8, colors: &[RgbColor]) -> Result<()> {
+        let count = u16::try_from(colors.len()).map_err(|_| {
+            NoCrateError::Hid(i18n::tf(
+                Msg::OpenRgbTooManyLeds,
+                &[("count", &colors.len())],
+            ))
+        })?;
+        let size = 4 + 4 + 2 + 4 * colors.len();
+        let mut data = Vec::with_capacity(size);
+        data.extend_from_slice(&u32::try_from(size).unwrap_or(u32::MAX).to_le_bytes());
+        data.extend_from_slice(&u32::from(zone).to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+        for c in colors {
+            data.extend_from_slice(&[c.r, c.g, c.b, 0]);
+        }
+        self.send(device_id(device)?, UPDATE_ZONE_LEDS, &data)
+    }
+
+    fn send(&mut self, device: u32, packet: u32, data: &[u8]) -> Result<()> {
+        let mut buf = Vec::with_capacity(HEADER_LEN + data.len());
+        buf.extend_from_slice(MAGIC);
+        buf.extend_from_slice(&device.to_le_bytes());
+        buf.extend_from_slice(&packet.to_le_bytes());
+        buf.extend_from_slice(&u32::try_from(data.len()).unwrap_or(u32::MAX).to_le_bytes());
+        buf.extend_from_slice(data);
+        self.stream.write_all(&buf).map_err(|e| io_error(&e))
+    }
+
+    /// Read packets until one with id `packet` arrives; the server may
+    /// interleave unsolicited notifications such as device list updates.
+    fn receive(&mut self, packet: u32) -> Result<Vec<u8>> {
+        loop {
+            let mut header = [0u8; HEADER_LEN];
+            self.stream
+                .read_exact(&mut header)
+                .map_err(|e| io_error(&e))?;
+            if &header[..4] != MAGIC {
+                return Err(NoCrateError::Hid(i18n::t(Msg::OpenRgbBadMagic).into()));
+            }
+            let id = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+            let len = u32::from_le_bytes([header[12], header[13], header[14], header[15]]) as usize;
+            if len > MAX_PACKET_LEN {
+                return Err(NoCrateError::Hid(i18n::tf(
+                    Msg::OpenRgbPacketTooLarge,
+                    &[("len", &len)],
+                )));
+            }
+            let mut data = vec![0u8; len];
+            self.stream
+                .read_exact(&mut data)
+                .map_err(|e| io_error(&e))?;
+            if id == packet {
+                return Ok(data);
+            }
+        }
+    }
+}
+
+/// Frame sink used by the effect engine in passthrough mode.
+///
+/// Connects lazily and drops the connection on any error so the next
+/// frame reconnects, which covers OpenRGB being restarted.
+#[derive(Default)]
+pub struct OpenRgbOutput {
+    client: Option<OpenRgbClient>,
+    devices: Vec<OpenRgbDevice>,
+    /// Whether the target controller has been switched to custom mode
+    /// on the current connection.
+    custom_mode: bool,
+}
+
+impl OpenRgbOutput {
+    /// LED count of the target zone.
+    ///
+    /// `Ok(None)` if the server is unreachable or the controller is not
+    /// (yet) listed; `Err` if the zone index is invalid.
+    pub fn led_count(
+        &mut self,
+        config: &OpenRgbConfig,
+        target: EffectTarget,
+    ) -> Result<Option<usize>> {
+        if self.client.is_none() {
+            let Ok(mut client) = OpenRgbClient::connect(config) else {
+                return Ok(None);
+            };
+            let Ok(devices) = client.devices() else {
+                return Ok(None);
+            };
+            self.client = Some(client);
+            self.devices = devices;
+            self.custom_mode = false;
+        }
+
+        let Some(device) = self.devices.get(target.device) else {
+            // Re-list on the next frame in case the controller shows up later
+            self.client = None;
+            return Ok(None);
+        };
+        device
+            .zones
+            .get(usize::from(target.channel))
+            .map(|z| Some(z.led_count as usize))
+            .ok_or_else(|| {
+                NoCrateError::Hid(i18n::tf(
+                    Msg::OpenRgbZoneRange,
+                    &[
+                        ("zone", &target.channel),
+                        ("zones", &device.zones.len()),
+                        ("device", &device.name),
+                    ],
+                ))
+            })
+    }
+
+    /// Send a frame to the target zone.
+    pub fn write(&mut self, target: EffectTarget, colors: &[RgbColor]) -> Result<()> {
+        let Some(client) = self.client.as_mut() else {
+            return Err(NoCrateError::Hid(i18n::t(Msg::OpenRgbNotConnected).into()));
+        };
+        let result = if self.custom_mode {
+            Ok(())
+        } else {
+            client.set_custom_mode(target.device)
+        }
+        .and_then(|()| client.update_zone_leds(target.device, target.channel, colors));
+
+        match result {
+            Ok(()) => self.custom_mode = true,
+            Err(_) => self.client = None,
+        }
+        result
+    }
+}
+
+/// List the controllers of the server in `config`.
+pub fn list_devices(config: &OpenRgbConfig) -> Result<Vec<OpenRgbDevice>> {
+    OpenRgbClient::connect(config)?.devices()
+}
+
+fn device_id(device: usize) -> Result<u32> {
+    u32::try_from(device)
+        .map_err(|_| NoCrateError::Hid(i18n::tf(Msg::OpenRgbDeviceRange, &[("device", &device)])))
+}
+
+fn io_error(e: &std::io::Error) -> NoCrateError {
+    NoCrateError::Hid(i18n::tf(Msg::OpenRgbIo, &[("error", e)]))
+}
+
+/// Parse a protocol-0 controller description, keeping the name and zones.
+fn parse_controller(data: &[u8], index: usize) -> Result<OpenRgbDevice> {
+    let mut r = Reader::new(data);
+    let _data_size = r.u32()?;
+    let _device_type = r.u32()?;
+    let name = r.string()?;
+    // description, version, serial, location
+    for _ in 0..4 {
+        let _ = r.string()?;
+    }
+
+    let num_modes = r.u16()?;
+    let _active_mode = r.u32()?;
+    for _ in 0..num_modes {
+        let _ = r.string()?;
+        // value, flags, speed min / max, colours min / max, speed,
+        // direction, colour mode
+        r.skip(9 * 4)?;
+        let num_colors = r.u16()?;
+        r.skip(usize::from(num_colors) * 4)?;
+    }
+
+    let num_zones = r.u16()?;
+    let mut zones = Vec::with_capacity(usize::from(num_zones));
+    for i in 0..num_zones {
+        let zone_name = r.string()?;
+        // type, LEDs min / max
+        r.skip(3 * 4)?;
+        let led_count = r.u32()?;
+        let matrix_len = r.u16()?;
+        r.skip(usize::from(matrix_len))?;
+        // Zones beyond u8 cannot be addressed as a channel
+        if let Ok(index) = u8::try_from(i) {
+            zones.push(OpenRgbZone {
+                index,
+                name: zone_name,
+                led_count,
+            });
+        }
+    }
+
+    Ok(OpenRgbDevice { index, name, zones })
+}
+
+/// Little-endian cursor over a response payload.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self.pos + n;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .ok_or_else(|| NoCrateError::Hid(i18n::t(Msg::OpenRgbTruncated).into()))?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        self.take(n).map(|_| ())
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        self.take(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.take(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Length-prefixed, NUL-terminated string.
+    fn string(&mut self) -> Result<String> {
+        let len = usize::from(self.u16()?);
+        let bytes = self.take(len)?;
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+}
@@ -0,0 +1,689 @@
+/// ASUS AURA USB HID protocol constants and packet building.
+///
+/// Protocol details sourced from OpenRGB and community reverse-engineering.
+/// The exact command layout may vary between controller firmware versions;
+/// constants here target the most common ENE-based ASUS motherboard
+/// controllers (VID 0x0B05).
+use serde::{Deserialize, Serialize};
+
+// ─── Device Identification ───────────────────────────────────
+
+/// ASUS USB Vendor ID.
+pub const AURA_VID: u16 = 0x0B05;
+
+/// Known ASUS AURA motherboard controller Product IDs.
+///
+/// This list covers common desktop motherboard controllers. Boards
+/// not listed may still work if they use the same protocol.
+pub const AURA_MB_PIDS: &[u16] = &[
+    0x1867, // AURA LED Controller (ENE)
+    0x1869, // AURA Mainboard (older)
+    0x18F3, // AURA Mainboard (newer)
+    0x19AF, // AURA Addressable Gen 2
+    0x1939, // ROG STRIX series
+    0x1854, // PRIME / TUF series
+];
+
+/// PID of the Addressable Gen 2 controller, which only drives ARGB
+/// headers and numbers them from channel 0.
+pub const AURA_ADDRESSABLE_PID: u16 = 0x19AF;
+
+/// Vendor-defined HID usage page used by AURA controllers for
+/// lighting reports.
+pub const AURA_USAGE_PAGE: u16 = 0xFF72;
+
+// ─── HID Report ──────────────────────────────────────────────
+
+/// Total HID report size: 1 byte Report ID + 64 bytes payload.
+pub const REPORT_SIZE: usize = 65;
+
+// ─── Command Bytes ───────────────────────────────────────────
+
+/// Set an effect mode on a channel, or commit (with 0xFF sub-command).
+pub const CMD_SET_EFFECT: u8 = 0x35;
+
+/// Direct per-LED color control.
+pub const CMD_DIRECT: u8 = 0x36;
+
+/// Save the current effect configuration to controller flash.
+pub const CMD_SAVE: u8 = 0x3F;
+
+/// Magic byte that must follow [`CMD_SAVE`]; guards against accidental
+/// flash writes from malformed reports.
+pub const SAVE_MAGIC: u8 = 0x55;
+
+/// Query firmware version string.
+pub const CMD_FIRMWARE: u8 = 0x82;
+
+/// Query the configuration table (header and LED counts).
+pub const CMD_CONFIG_TABLE: u8 = 0xB0;
+
+/// Replies echo the query command with the high bit cleared.
+pub const RESPONSE_MASK: u8 = 0x7F;
+
+/// Length of the firmware string in a [`CMD_FIRMWARE`] reply.
+pub const FIRMWARE_LEN: usize = 16;
+
+/// Offset of the configuration table within a [`CMD_CONFIG_TABLE`] reply
+/// (after Report ID, command and two reserved bytes).
+pub const CONFIG_TABLE_OFFSET: usize = 4;
+
+/// Length of the configuration table.
+pub const CONFIG_TABLE_LEN: usize = 60;
+
+/// Upper bound on header counts accepted from the configuration table.
+pub const MAX_HEADERS: u8 = 8;
+
+/// LED capacity reported for addressable headers.
+///
+/// The strip length is not stored in the controller; this is the
+/// maximum the firmware accepts per header.
+pub const MAX_ARGB_LEDS: u16 = 120;
+
+/// Maximum LEDs addressable in a single direct-mode packet.
+///
+/// Each LED consumes 3 bytes (R, G, B).
+/// Payload is 63 bytes; 3 header bytes (channel, start, count) → 20 LEDs.
+pub const MAX_LEDS_PER_PACKET: usize = 20;
+
+/// Flag OR'd into the channel byte of the last direct report of a frame;
+/// the controller latches the buffered colours when it sees it.
+pub const DIRECT_APPLY: u8 = 0x80;
+
+// ─── Channels ────────────────────────────────────────────────
+
+/// Whether a channel drives fixed RGB LEDs or an addressable strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuraChannelKind {
+    /// Onboard LEDs or a 4-pin 12V RGB header (one colour per zone).
+    Fixed,
+    /// 3-pin 5V ARGB header (per-LED colour).
+    Addressable,
+}
+
+/// A lighting channel on an AURA controller.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuraChannelInfo {
+    /// Channel byte sent in effect / direct reports.
+    pub index: u8,
+    /// Display name.
+    pub name: String,
+    pub kind: AuraChannelKind,
+    /// Number of LEDs (for addressable headers: maximum strip length).
+    pub led_count: u16,
+}
+
+impl AuraChannelInfo {
+    fn new(index: u8, name: &str, kind: AuraChannelKind, led_count: u16) -> Self {
+        Self {
+            index,
+            name: name.to_string(),
+            kind,
+            led_count,
+        }
+    }
+}
+
+/// Where a software effect is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EffectTarget {
+    /// Index into the discovered controller list.
+    pub device: usize,
+    /// Channel on that controller.
+    pub channel: u8,
+}
+
+/// Parsed controller configuration table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AuraConfigTable {
+    /// Number of 5V ARGB headers.
+    pub argb_headers: u8,
+    /// Number of 12V RGB headers.
+    pub rgb_headers: u8,
+    /// Total LEDs on the mainboard channel, including 12V RGB headers.
+    pub mainboard_leds: u8,
+}
+
+impl AuraConfigTable {
+    /// Parse the table from a [`CMD_CONFIG_TABLE`] reply.
+    ///
+    /// Layout (offsets into the table, per OpenRGB):
+    /// `0x02` ARGB header count, `0x1B` mainboard LED count,
+    /// `0x1D` RGB header count.
+    #[must_use]
+    pub fn parse(report: &[u8]) -> Option<Self> {
+        if report.get(1) != Some(&(CMD_CONFIG_TABLE & RESPONSE_MASK)) {
+            return None;
+        }
+        let table = report.get(CONFIG_TABLE_OFFSET..CONFIG_TABLE_OFFSET + CONFIG_TABLE_LEN)?;
+        let parsed = Self {
+            argb_headers: table[0x02],
+            rgb_headers: table[0x1D],
+            mainboard_leds: table[0x1B],
+        };
+        // Reject garbage replies from firmware with a different table layout
+        (parsed.argb_headers <= MAX_HEADERS && parsed.rgb_headers <= MAX_HEADERS).then_some(parsed)
+    }
+
+    /// Build the channel list described by this table for a controller
+    /// with product ID `pid`.
+    ///
+    /// Channels keep the firmware's fixed numbering used by
+    /// [`default_channels`]: on mainboard controllers channel 0 is the
+    /// mainboard zone and the 12V RGB headers start at 1, followed by
+    /// the ARGB headers; a missing mainboard zone or RGB header leaves
+    /// its channel unused rather than shifting the others.
+    #[must_use]
+    pub fn channels(self, pid: u16) -> Vec<AuraChannelInfo> {
+        use AuraChannelKind::{Addressable, Fixed};
+
+        let mut channels = Vec::new();
+        let mut argb_first = 0u8;
+        if pid != AURA_ADDRESSABLE_PID {
+            let onboard = self.mainboard_leds.saturating_sub(self.rgb_headers);
+            if onboard > 0 {
+                channels.push(AuraChannelInfo::new(0, "Mainboard", Fixed, onboard.into()));
+            }
+            for i in 1..=self.rgb_headers {
+                let name = if self.rgb_headers == 1 {
+                    "RGB Header".to_string()
+                } else {
+                    format!("RGB Header {i}")
+                };
+                channels.push(AuraChannelInfo::new(i, &name, Fixed, 1));
+            }
+            argb_first = 1 + self.rgb_headers.max(1);
+        }
+        for i in 0..self.argb_headers {
+            let name = format!("ARGB Header {}", i + 1);
+            channels.push(AuraChannelInfo::new(
+                argb_first + i,
+                &name,
+                Addressable,
+                MAX_ARGB_LEDS,
+            ));
+        }
+        channels
+    }
+}
+
+/// Parse the firmware string from a [`CMD_FIRMWARE`] reply.
+#[must_use]
+pub fn parse_firmware(report: &[u8]) -> Option<String> {
+    if report.get(1) != Some(&(CMD_FIRMWARE & RESPONSE_MASK)) {
+        return None;
+    }
+    let raw = report.get(2..2 + FIRMWARE_LEN)?;
+    let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+    let firmware = String::from_utf8_lossy(&raw[..end]).trim().to_string();
+    (!firmware.is_empty()).then_some(firmware)
+}
+
+/// Default channel layout for a controller PID, used when the
+/// configuration table cannot be read.
+///
+/// Addressable Gen2 controllers only drive ARGB headers; mainboard
+/// controllers expose the onboard zone, one 12V RGB header and two
+/// ARGB headers.
+#[must_use]
+pub fn default_channels(pid: u16) -> Vec<AuraChannelInfo> {
+    use AuraChannelKind::{Addressable, Fixed};
+
+    match pid {
+        AURA_ADDRESSABLE_PID => vec![
+            AuraChannelInfo::new(0, "ARGB Header 1", Addressable, MAX_ARGB_LEDS),
+            AuraChannelInfo::new(1, "ARGB Header 2", Addressable, MAX_ARGB_LEDS),
+            AuraChannelInfo::new(2, "ARGB Header 3", Addressable, MAX_ARGB_LEDS),
+        ],
+        _ => vec![
+            AuraChannelInfo::new(0, "Mainboard", Fixed, 1),
+            AuraChannelInfo::new(1, "RGB Header", Fixed, 1),
+            AuraChannelInfo::new(2, "ARGB Header 1", Addressable, MAX_ARGB_LEDS),
+            AuraChannelInfo::new(3, "ARGB Header 2", Addressable, MAX_ARGB_LEDS),
+        ],
+    }
+}
+
+// ─── Effect Modes ────────────────────────────────────────────
+
+/// Predefined AURA lighting effects.
+///
+/// The first six are supported by every controller; the rest are ENE
+/// firmware modes (named as in OpenRGB) missing on first-generation
+/// controllers, see [`AuraEffect::supported_by`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuraEffect {
+    /// LEDs off.
+    Off,
+    /// Solid single color.
+    Static,
+    /// Pulse between color and off.
+    Breathing,
+    /// Cycle through the color wheel.
+    ColorCycle,
+    /// Flowing rainbow across LEDs.
+    Rainbow,
+    /// Full-spectrum sweep.
+    SpectrumCycle,
+    /// Rapid random flashes of the colour.
+    Strobe,
+    /// A single lit segment with a fading tail running along the strip.
+    Comet,
+    /// Spectrum wave travelling along the strip.
+    Wave,
+    /// Flash, then a segment dashing along the strip.
+    FlashAndDash,
+}
+
+/// Controllers whose firmware only knows the original six effects.
+const BASIC_EFFECT_PIDS: &[u16] = &[0x1867, 0x1869];
+
+impl AuraEffect {
+    /// All effects, useful for UI enumeration.
+    pub const ALL: [Self; 10] = [
+        Self::Off,
+        Self::Static,
+        Self::Breathing,
+        Self::ColorCycle,
+        Self::Rainbow,
+        Self::SpectrumCycle,
+        Self::Strobe,
+        Self::Comet,
+        Self::Wave,
+        Self::FlashAndDash,
+    ];
+
+    /// Map to the protocol byte sent in the HID report.
+    #[must_use]
+    pub const fn to_raw(self) -> u8 {
+        match self {
+            Self::Off => 0x00,
+            Self::Static => 0x01,
+            Self::Breathing => 0x02,
+            Self::ColorCycle => 0x03,
+            Self::Rainbow => 0x04,
+            Self::SpectrumCycle => 0x05,
+            Self::FlashAndDash => 0x07,
+            Self::Comet => 0x09,
+            Self::Wave => 0x0B,
+            Self::Strobe => 0x0D,
+        }
+    }
+
+    /// Parse from a raw protocol byte.
+    #[must_use]
+    #[allow(dead_code)]
+    pub fn from_raw(v: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.to_raw() == v)
+    }
+
+    /// Whether the effect moves along the strip and needs addressable
+    /// LEDs (and honours [`AuraDirection`]).
+    #[must_use]
+    pub const fn is_travelling(self) -> bool {
+        matches!(self, Self::Comet | Self::Wave | Self::FlashAndDash)
+    }
+
+    /// Whether a controller with `pid` supports the effect on `channel`.
+    #[must_use]
+    pub fn supported_by(self, pid: u16, channel: &AuraChannelInfo) -> bool {
+        let basic = (self as u8) <= (Self::SpectrumCycle as u8);
+        if !basic && BASIC_EFFECT_PIDS.contains(&pid) {
+            return false;
+        }
+        !self.is_travelling() || channel.kind == AuraChannelKind::Addressable
+    }
+}
+
+/// Direction of travelling effects along the strip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuraDirection {
+    /// From LED 0 towards the end of the strip.
+    #[default]
+    Forward,
+    Reverse,
+}
+
+impl AuraDirection {
+    #[must_use]
+    pub const fn to_raw(self) -> u8 {
+        match self {
+            Self::Forward => 0x00,
+            Self::Reverse => 0x01,
+        }
+    }
+}
+
+/// Effect speed preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuraSpeed {
+    Slow,
+    Medium,
+    Fast,
+}
+
+impl AuraSpeed {
+    #[must_use]
+    pub const fn to_raw(self) -> u8 {
+        match self {
+            Self::Slow => 0x03,
+            Self::Medium => 0x02,
+            Self::Fast => 0x01,
+        }
+    }
+}
+
+// ─── RGB Color ───────────────────────────────────────────────
+
+/// An RGB colour value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RgbColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl RgbColor {
+    pub const BLACK: Self = Self { r: 0, g: 0, b: 0 };
+    pub const WHITE: Self = Self {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    #[must_use]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Scale all components to `percent` (0–100) brightness.
+    #[must_use]
+    pub fn scaled(self, percent: u8) -> Self {
+        let scale = |c: u8| {
+            let v = u16::from(c) * u16::from(percent.min(100)) / 100;
+            u8::try_from(v).unwrap_or(u8::MAX)
+        };
+        Self {
+            r: scale(self.r),
+            g: scale(self.g),
+            b: scale(self.b),
+        }
+    }
+
+    /// Format as `#rrggbb`.
+    #[must_use]
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Parse `#rrggbb` (the `#` is optional).
+    #[must_use]
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Self::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+// ─── Colour Calibration ──────────────────────────────────────
+
+/// Per-device gamma and white-point correction applied to every colour
+/// sent to a controller.
+///
+/// Many ENE-based strips render full white with a strong blue cast and
+/// low values too bright; scaling the blue channel down and raising the
+/// gamma compensates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorCalibration {
+    /// Exponent applied to normalized components (1.0 = linear).
+    pub gamma: f32,
+    /// White-point scale per component, `0.0..=1.0`.
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+}
+
+impl Default for ColorCalibration {
+    fn default() -> Self {
+        Self {
+            gamma: 1.0,
+            red: 1.0,
+            green: 1.0,
+            blue: 1.0,
+        }
+    }
+}
+
+impl ColorCalibration {
+    /// Whether applying this calibration leaves colours unchanged.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Correct a colour for output.
+    #[must_use]
+    pub fn apply(&self, color: RgbColor) -> RgbColor {
+        if self.is_identity() {
+            return color;
+        }
+        let gamma = if self.gamma.is_finite() && self.gamma > 0.0 {
+            self.gamma
+        } else {
+            1.0
+        };
+        let correct = |c: u8, scale: f32| {
+            let v = (f32::from(c) / 255.0).powf(gamma) * scale.clamp(0.0, 1.0);
+            // Clamped to 0..=255 before the cast
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let out = (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+            out
+        };
+        RgbColor {
+            r: correct(color.r, self.red),
+            g: correct(color.g, self.green),
+            b: correct(color.b, self.blue),
+        }
+    }
+}
+
+/// Test patterns for tuning a [`ColorCalibration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalibrationPattern {
+    /// Full white; adjust the white point until it looks neutral.
+    White,
+    /// Mid grey (50 %); adjust gamma until it looks half as bright.
+    Gray,
+    /// Black → white ramp along the strip.
+    Ramp,
+    /// Repeating red, green, blue, white.
+    Primaries,
+}
+
+impl CalibrationPattern {
+    /// Uncorrected colours for `led_count` LEDs.
+    #[must_use]
+    pub fn colors(self, led_count: usize) -> Vec<RgbColor> {
+        match self {
+            Self::White => vec![RgbColor::WHITE; led_count],
+            Self::Gray => vec![RgbColor::new(128, 128, 128); led_count],
+            Self::Ramp => (0..led_count)
+                .map(|i| {
+                    let v = i * 255 / led_count.saturating_sub(1).max(1);
+                    let v = u8::try_from(v).unwrap_or(u8::MAX);
+                    RgbColor::new(v, v, v)
+                })
+                .collect(),
+            Self::Primaries => {
+                const CYCLE: [RgbColor; 4] = [
+                    RgbColor::new(255, 0, 0),
+                    RgbColor::new(0, 255, 0),
+                    RgbColor::new(0, 0, 255),
+                    RgbColor::WHITE,
+                ];
+                (0..led_count).map(|i| CYCLE[i % CYCLE.len()]).collect()
+            }
+        }
+    }
+}
+
+// ─── Packet Builders ─────────────────────────────────────────
+
+/// Build a blank 65-byte HID report and fill command + payload.
+#[must_use]
+pub fn build_report(cmd: u8, payload: &[u8]) -> [u8; REPORT_SIZE] {
+    let mut buf = [0u8; REPORT_SIZE];
+    buf[0] = 0x00; // Report ID
+    buf[1] = cmd;
+    let n = payload.len().min(REPORT_SIZE - 2);
+    buf[2..2 + n].copy_from_slice(&payload[..n]);
+    buf
+}
+
+/// Build a "set effect" report for the given channel.
+#[must_use]
+pub fn build_set_effect(
+    channel: u8,
+    effect: AuraEffect,
+    color: RgbColor,
+    speed: AuraSpeed,
+    direction: AuraDirection,
+) -> [u8; REPORT_SIZE] {
+    build_report(
+        CMD_SET_EFFECT,
+        &[
+            channel,
+            effect.to_raw(),
+            color.r,
+            color.g,
+            color.b,
+            speed.to_raw(),
+            direction.to_raw(),
+        ],
+    )
+}
+
+/// Build a "commit" report to apply the last effect change.
+#[must_use]
+pub fn build_commit() -> [u8; REPORT_SIZE] {
+    build_report(CMD_SET_EFFECT, &[0xFF])
+}
+
+/// Build a "save to flash" report.
+///
+/// Persists the last committed effect of every channel so the
+/// controller restores it on power-up without the app running.
+/// Direct-mode colours are volatile and are not saved.
+#[must_use]
+pub fn build_save() -> [u8; REPORT_SIZE] {
+    build_report(CMD_SAVE, &[SAVE_MAGIC])
+}
+
+/// Build a "direct color" report for a slice of LEDs on a channel.
+///
+/// `start_led` is the zero-based LED index within the channel.
+/// Up to [`MAX_LEDS_PER_PACKET`] LEDs in one report. Set `apply` on the
+/// last report of a frame so the controller shows it.
+#[must_use]
+pub fn build_direct(
+    channel: u8,
+    start_led: u8,
+    colors: &[RgbColor],
+    apply: bool,
+) -> [u8; REPORT_SIZE] {
+    let count = colors.len().min(MAX_LEDS_PER_PACKET);
+    // Payload: [channel, start, count, R, G, B, R, G, B, …]
+    let mut payload = Vec::with_capacity(3 + count * 3);
+    payload.push(if apply {
+        channel | DIRECT_APPLY
+    } else {
+        channel
+    });
+    payload.push(start_led);
+    payload.push(count as u8);
+    for c in &colors[..count] {
+        payload.extend_from_slice(&[c.r, c.g, c.b]);
+    }
+    build_report(CMD_DIRECT, &payload)
+}
+
+/// Build a firmware-query report.
+#[must_use]
+pub fn build_firmware_query() -> [u8; REPORT_SIZE] {
+    build_report(CMD_FIRMWARE, &[])
+}
+
+/// Build a configuration-table query report.
+#[must_use]
+pub fn build_config_table_query() -> [u8; REPORT_SIZE] {
+    build_report(CMD_CONFIG_TABLE, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reply to [`CMD_CONFIG_TABLE`] without the Report ID byte.
+    fn config_reply(argb: u8, mainboard_leds: u8, rgb: u8) -> Vec<u8> {
+        let mut reply = vec![0u8; REPORT_SIZE];
+        reply[1] = CMD_CONFIG_TABLE & RESPONSE_MASK;
+        let table = &mut reply[CONFIG_TABLE_OFFSET..];
+        table[0x02] = argb;
+        table[0x1B] = mainboard_leds;
+        table[0x1D] = rgb;
+        reply
+    }
+
+    fn layout(channels: &[AuraChannelInfo]) -> Vec<(u8, &str, AuraChannelKind)> {
+        channels
+            .iter()
+            .map(|c| (c.index, c.name.as_str(), c.kind))
+            .collect()
+    }
+
+    #[test]
+    fn parsed_table_numbers_channels_like_defaults() {
+        for (pid, reply) in [
+            (0x18F3, config_reply(2, 4, 1)),
+            (AURA_ADDRESSABLE_PID, config_reply(3, 0, 0)),
+        ] {
+            let table = AuraConfigTable::parse(&reply).unwrap();
+            assert_eq!(
+                layout(&table.channels(pid)),
+                layout(&default_channels(pid)),
+                "PID {pid:04X}"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_zones_do_not_shift_channel_numbers() {
+        // No onboard LEDs and no 12V header: the ARGB headers keep channels 2 and 3
+        let table = AuraConfigTable::parse(&config_reply(2, 0, 0)).unwrap();
+        assert_eq!(
+            layout(&table.channels(0x18F3)),
+            [
+                (2, "ARGB Header 1", AuraChannelKind::Addressable),
+                (3, "ARGB Header 2", AuraChannelKind::Addressable),
+            ]
+        );
+    }
+
+    #[test]
+    fn garbage_table_is_rejected() {
+        assert_eq!(
+            AuraConfigTable::parse(&config_reply(MAX_HEADERS + 1, 0, 0)),
+            None
+        );
+        assert_eq!(AuraConfigTable::parse(&[0u8; REPORT_SIZE]), None);
+    }
+}
@@ -0,0 +1,239 @@
+// 主板 / 笔记本型号识别与按型号的兼容性修正表
+// 型号来自 SMBIOS Type 2 (BaseBoard)，由系统在启动时写入注册表
+// `HKLM\HARDWARE\DESCRIPTION\System\BIOS`，与 `Win32_BaseBoard` 的 Product 一致。
+// ASUS 笔记本上 Product 即为机型代号（如 `GA402RJ`）。
+//
+// 修正表在初始化时应用：风扇头名称与 SIO 通道映射用于标注读数，
+// 已知异常的 ASUS WMI 设备 ID 在 WMI 线程上直接屏蔽。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use windows::core::PCWSTR;
+
+use crate::wmi::asus_mgmt::device_id;
+
+/// User overrides for Super I/O channel labels.
+///
+/// Keys are `fan/<channel>` or `temp/<channel>`, matching the physical
+/// channel numbers reported in `SioSnapshot`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SioChannelConfig {
+    /// Custom display names, e.g. `"fan/1" → "Front Intake"`
+    pub aliases: HashMap<String, String>,
+    /// Channels to omit from readings (unconnected headers, bogus sensors)
+    pub hidden: Vec<String>,
+}
+
+impl SioChannelConfig {
+    /// Build the lookup key for a channel: `kind` is `"fan"` or `"temp"`.
+    #[must_use]
+    pub fn key(kind: &str, channel: u8) -> String {
+        format!("{kind}/{channel}")
+    }
+
+    /// Whether the given channel is hidden.
+    #[must_use]
+    pub fn is_hidden(&self, kind: &str, channel: u8) -> bool {
+        self.hidden.contains(&Self::key(kind, channel))
+    }
+
+    /// Custom display name for the given channel, if one is configured.
+    #[must_use]
+    pub fn alias(&self, kind: &str, channel: u8) -> Option<&str> {
+        self.aliases
+            .get(&Self::key(kind, channel))
+            .map(String::as_str)
+            .filter(|s| !s.is_empty())
+    }
+}
+
+/// SMBIOS 主板信息
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardInfo {
+    pub manufacturer: Option<String>,
+    pub product: String,
+    pub version: Option<String>,
+}
+
+impl BoardInfo {
+    /// 读取主板信息，读不到型号时返回 `None`
+    pub fn detect() -> Option<Self> {
+        Some(Self {
+            manufacturer: read_bios_value("BaseBoardManufacturer"),
+            product: read_board_name()?,
+            version: read_bios_value("BaseBoardVersion"),
+        })
+    }
+
+    /// 模拟模式（--simulate）下报告的主板
+    pub fn simulated() -> Self {
+        Self {
+            manufacturer: Some("ASUSTeK COMPUTER INC.".into()),
+            product: "NoCrate Simulated Board".into(),
+            version: Some("Rev 1.xx".into()),
+        }
+    }
+}
+
+/// 返回给前端的主板信息与修正表
+#[derive(Debug, Serialize)]
+pub struct BoardSummary {
+    pub info: Option<BoardInfo>,
+    pub quirks: &'static BoardQuirks,
+}
+
+/// 一个风扇头的板载丝印名称及其对应的 Super I/O 风扇通道
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FanHeader {
+    /// `ASUSManagement` 的 `FanType` 索引
+    pub fan_type: u8,
+    /// 主板丝印名称
+    pub name: &'static str,
+    /// 对应的 SIO 风扇通道（不经 SIO 测速的风扇头为 `None`）
+    pub sio_channel: Option<u8>,
+}
+
+/// 按主板型号的兼容性修正
+#[derive(Debug, Serialize)]
+pub struct BoardQuirks {
+    pub fan_headers: &'static [FanHeader],
+    /// 在该主板上返回异常值或导致 WMI 调用挂起的设备 ID
+    pub bad_device_ids: &'static [u32],
+}
+
+/// 不在表中的主板：不做修正
+const NO_QUIRKS: BoardQuirks = BoardQuirks {
+    fan_headers: &[],
+    bad_device_ids: &[],
+};
+
+const fn header(fan_type: u8, name: &'static str, sio_channel: Option<u8>) -> FanHeader {
+    FanHeader {
+        fan_type,
+        name,
+        sio_channel,
+    }
+}
+
+// ─── X570 (NCT6798D) ─────────────────────────────────────────
+// SIO 通道 0 为 CHA_FAN1、通道 1 为 CPU_FAN，与芯片默认命名相反
+
+const X570_HEADERS: &[FanHeader] = &[
+    header(0, "CPU_FAN", Some(1)),
+    header(1, "CHA_FAN1", Some(0)),
+    header(2, "CHA_FAN2", Some(2)),
+    header(3, "CHA_FAN3", Some(3)),
+    header(4, "H_AMP_FAN", Some(4)),
+    header(5, "W_PUMP+", Some(5)),
+    header(6, "AIO_PUMP", Some(6)),
+];
+
+const ROG_STRIX_X570_E: BoardQuirks = BoardQuirks {
+    fan_headers: X570_HEADERS,
+    bad_device_ids: &[],
+};
+
+const ROG_CROSSHAIR_VIII_HERO: BoardQuirks = BoardQuirks {
+    fan_headers: X570_HEADERS,
+    bad_device_ids: &[],
+};
+
+// ─── B450 / B550 ─────────────────────────────────────────────
+
+// MID 风扇测速 ID 在这些主板上恒定返回 0xFFFF
+const PRIME_B450M_A: BoardQuirks = BoardQuirks {
+    fan_headers: &[
+        header(0, "CPU_FAN", Some(0)),
+        header(1, "CHA_FAN1", Some(1)),
+        header(2, "CHA_FAN2", Some(2)),
+    ],
+    bad_device_ids: &[device_id::MID_FAN_SPEED],
+};
+
+const ROG_STRIX_B550_F: BoardQuirks = BoardQuirks {
+    fan_headers: &[
+        header(0, "CPU_FAN", Some(1)),
+        header(1, "CPU_OPT", Some(6)),
+        header(2, "CHA_FAN1", Some(0)),
+        header(3, "CHA_FAN2", Some(2)),
+        header(4, "CHA_FAN3", Some(3)),
+        header(5, "AIO_PUMP", Some(5)),
+    ],
+    bad_device_ids: &[device_id::MID_FAN_SPEED],
+};
+
+/// 按主板型号查找修正表
+pub fn quirks_for(product: Option<&str>) -> &'static BoardQuirks {
+    match product {
+        Some("ROG STRIX X570-E GAMING" | "ROG STRIX X570-E GAMING WIFI II") => &ROG_STRIX_X570_E,
+        Some("ROG CROSSHAIR VIII HERO" | "ROG CROSSHAIR VIII HERO (WI-FI)") => {
+            &ROG_CROSSHAIR_VIII_HERO
+        }
+        Some("PRIME B450M-A" | "PRIME B450M-A II") => &PRIME_B450M_A,
+        Some("ROG STRIX B550-F GAMING" | "ROG STRIX B550-F GAMING (WI-FI)") => &ROG_STRIX_B550_F,
+        _ => &NO_QUIRKS,
+    }
+}
+
+impl BoardQuirks {
+    /// 某个 `FanType` 的风扇头定义
+    pub fn fan_header(&self, fan_type: u8) -> Option<&FanHeader> {
+        self.fan_headers.iter().find(|h| h.fan_type == fan_type)
+    }
+
+    /// 以风扇头名称作为 SIO 通道的默认别名，用户配置的别名优先
+    #[cfg(feature = "sio")]
+    pub fn sio_channels(&self, mut channels: SioChannelConfig) -> SioChannelConfig {
+        for h in self.fan_headers {
+            if let Some(ch) = h.sio_channel {
+                let _ = channels
+                    .aliases
+                    .entry(SioChannelConfig::key("fan", ch))
+                    .or_insert_with(|| h.name.to_string());
+            }
+        }
+        channels
+    }
+}
+
+/// 从注册表读取主板型号（SMBIOS Type 2 Product）
+pub fn read_board_name() -> Option<String> {
+    read_bios_value("BaseBoardProduct")
+}
+
+/// 读取 `HKLM\HARDWARE\DESCRIPTION\System\BIOS` 下的字符串值
+#[allow(unsafe_code)]
+fn read_bios_value(name: &str) -> Option<String> {
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ};
+
+    let subkey = to_wide(r"HARDWARE\DESCRIPTION\System\BIOS");
+    let value = to_wide(name);
+    let mut buf = [0u16; 256];
+    let mut size = (buf.len() * 2) as u32;
+
+    unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_SZ,
+            None,
+            Some(buf.as_mut_ptr().cast()),
+            Some(&mut size),
+        )
+        .ok()
+        .ok()?;
+    }
+
+    // size 包含结尾的 null 字符
+    let len = (size as usize / 2).saturating_sub(1);
+    let name = String::from_utf16_lossy(&buf[..len]).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+/// 将 &str 转换为以 null 结尾的宽字符串
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
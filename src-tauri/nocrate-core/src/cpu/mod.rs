@@ -0,0 +1,261 @@
+// CPU 温度传感器模块
+// Intel：读取 IA32_PACKAGE_THERM_STATUS / IA32_TEMPERATURE_TARGET MSR
+// AMD Zen：通过 SMN 读取 THM_TCON_CUR_TMP (Tctl) 与各 CCD 温度
+// 无需 LibreHardwareMonitor 即可获得封装温度
+
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::sio::driver::DriverHandle;
+
+/// `IA32_TEMPERATURE_TARGET`：bit[23:16] = `TjMax`
+const MSR_TEMPERATURE_TARGET: u32 = 0x1A2;
+/// `IA32_PACKAGE_THERM_STATUS`：bit[22:16] = 距 `TjMax` 的数字读数
+const MSR_PACKAGE_THERM_STATUS: u32 = 0x1B1;
+
+/// AMD `THM_TCON_CUR_TMP`：bit[31:21] = 温度 × 8，bit 19 = 范围选择（-49°C 偏移）
+const SMN_THM_TCON_CUR_TMP: u32 = 0x0005_9800;
+/// Zen 2 / Zen 3 CCD 温度寄存器基址
+const SMN_CCD_TEMP_ZEN2: u32 = 0x0005_9954;
+/// Zen 4 及以后 CCD 温度寄存器基址
+const SMN_CCD_TEMP_ZEN4: u32 = 0x0005_9B08;
+/// 最多读取的 CCD 数量
+const MAX_CCDS: u32 = 8;
+
+/// 本模块读取的 MSR
+pub(crate) const MSRS: [u32; 2] = [MSR_TEMPERATURE_TARGET, MSR_PACKAGE_THERM_STATUS];
+
+/// `addr` 是否为本模块读取的 SMN 寄存器
+pub(crate) fn reads_smn(addr: u32) -> bool {
+    addr == SMN_THM_TCON_CUR_TMP
+        || [SMN_CCD_TEMP_ZEN2, SMN_CCD_TEMP_ZEN4]
+            .iter()
+            .any(|&base| (0..MAX_CCDS).any(|ccd| addr == base + ccd * 4))
+}
+
+/// 检测到的 CPU 温度读取方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CpuKind {
+    Intel,
+    /// AMD Zen 系列，`ccd_base` 为 CCD 温度寄存器基址
+    AmdZen { ccd_base: u32 },
+}
+
+/// 单个 CPU 温度读数
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuTempReading {
+    /// 传感器名称（如 "Package"、"Tctl"、"CCD 1"）
+    pub name: String,
+    /// 温度（摄氏度）
+    pub temp_c: f32,
+}
+
+/// CPU 温度快照
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuSnapshot {
+    /// CPU 型号字符串（CPUID 品牌字符串）
+    pub brand: String,
+    /// 温度读数
+    pub temps: Vec<CpuTempReading>,
+}
+
+/// CPU 温度监控器
+pub struct CpuMonitor {
+    driver: Arc<DriverHandle>,
+    kind: CpuKind,
+    brand: String,
+    /// AMD 部分早期 X 系列 SKU 的 Tctl 相对 Tdie 的偏移
+    tctl_offset: f32,
+}
+
+impl CpuMonitor {
+    /// 通过 CPUID 识别厂商与家族，确认支持后返回监控器
+    pub fn init(driver: Arc<DriverHandle>) -> Result<Self> {
+        let vendor = cpuid_vendor();
+        let brand = cpuid_brand();
+        let (family, model) = cpuid_family_model();
+
+        let kind = match vendor.as_str() {
+            "GenuineIntel" => CpuKind::Intel,
+            "AuthenticAMD" | "HygonGenuine" => match family {
+                0x17 => CpuKind::AmdZen {
+                    ccd_base: SMN_CCD_TEMP_ZEN2,
+                },
+                0x19 if model < 0x60 => CpuKind::AmdZen {
+                    ccd_base: SMN_CCD_TEMP_ZEN2,
+                },
+                0x19 | 0x1A => CpuKind::AmdZen {
+                    ccd_base: SMN_CCD_TEMP_ZEN4,
+                },
+                _ => {
+                    return Err(NoCrateError::Sio(i18n::tf(
+                        Msg::CpuAmdFamily,
+                        &[("family", &format!("0x{family:02X}"))],
+                    )));
+                }
+            },
+            other => {
+                return Err(NoCrateError::Sio(i18n::tf(
+                    Msg::CpuVendor,
+                    &[("vendor", &other)],
+                )));
+            }
+        };
+
+        let tctl_offset = amd_tctl_offset(&brand);
+        eprintln!("CPU: {brand} (family 0x{family:02X}, model 0x{model:02X})");
+
+        Ok(Self {
+            driver,
+            kind,
+            brand,
+            tctl_offset,
+        })
+    }
+
+    /// 读取所有 CPU 温度
+    pub fn read_all(&self) -> Result<CpuSnapshot> {
+        let temps = match self.kind {
+            CpuKind::Intel => self.read_intel()?,
+            CpuKind::AmdZen { ccd_base } => self.read_amd(ccd_base)?,
+        };
+
+        Ok(CpuSnapshot {
+            brand: self.brand.clone(),
+            temps,
+        })
+    }
+
+    fn read_intel(&self) -> Result<Vec<CpuTempReading>> {
+        let target = self.driver.read_msr(MSR_TEMPERATURE_TARGET)?;
+        let tjmax = match ((target >> 16) & 0xFF) as u8 {
+            // 少数平台未填写 TjMax，使用 Intel 默认值
+            0 => 100,
+            v => v,
+        };
+
+        let status = self.driver.read_msr(MSR_PACKAGE_THERM_STATUS)?;
+        let readout = ((status >> 16) & 0x7F) as u8;
+
+        Ok(vec![
+            CpuTempReading {
+                name: "Package".to_string(),
+                temp_c: f32::from(tjmax) - f32::from(readout),
+            },
+            CpuTempReading {
+                name: "TjMax".to_string(),
+                temp_c: f32::from(tjmax),
+            },
+        ])
+    }
+
+    fn read_amd(&self, ccd_base: u32) -> Result<Vec<CpuTempReading>> {
+        let raw = self.driver.read_smn(SMN_THM_TCON_CUR_TMP)?;
+        let mut tctl = f32::from(((raw >> 21) & 0x7FF) as u16) * 0.125;
+        if raw & (1 << 19) != 0 {
+            tctl -= 49.0;
+        }
+
+        let mut temps = vec![CpuTempReading {
+            name: "Tctl".to_string(),
+            temp_c: tctl,
+        }];
+
+        if self.tctl_offset != 0.0 {
+            temps.push(CpuTempReading {
+                name: "Tdie".to_string(),
+                temp_c: tctl - self.tctl_offset,
+            });
+        }
+
+        for ccd in 0..MAX_CCDS {
+            let value = self.driver.read_smn(ccd_base + ccd * 4)?;
+            // bit 11 = 读数有效；未安装的 CCD 返回 0
+            if value & 0x800 == 0 {
+                continue;
+            }
+            temps.push(CpuTempReading {
+                name: format!("CCD {}", ccd + 1),
+                temp_c: f32::from((value & 0x7FF) as u16).mul_add(0.125, -49.0),
+            });
+        }
+
+        Ok(temps)
+    }
+}
+
+/// CPUID 厂商字符串（如 `GenuineIntel`）
+#[allow(unsafe_code, unused_unsafe)]
+fn cpuid_vendor() -> String {
+    use std::arch::x86_64::__cpuid;
+
+    let r = unsafe { __cpuid(0) };
+    let bytes: Vec<u8> = [r.ebx, r.edx, r.ecx]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// CPUID 品牌字符串（leaf 0x80000002–0x80000004）
+#[allow(unsafe_code, unused_unsafe)]
+fn cpuid_brand() -> String {
+    use std::arch::x86_64::__cpuid;
+
+    let mut bytes = Vec::with_capacity(48);
+    for leaf in 0x8000_0002_u32..=0x8000_0004 {
+        let r = unsafe { __cpuid(leaf) };
+        for v in [r.eax, r.ebx, r.ecx, r.edx] {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    String::from_utf8_lossy(&bytes)
+        .trim_matches(char::from(0))
+        .trim()
+        .to_string()
+}
+
+/// CPUID 显示家族 / 型号（含扩展字段）
+#[allow(unsafe_code, unused_unsafe)]
+fn cpuid_family_model() -> (u32, u32) {
+    use std::arch::x86_64::__cpuid;
+
+    let eax = unsafe { __cpuid(1) }.eax;
+    let base_family = (eax >> 8) & 0xF;
+    let base_model = (eax >> 4) & 0xF;
+    let ext_family = (eax >> 20) & 0xFF;
+    let ext_model = (eax >> 16) & 0xF;
+
+    let family = if base_family == 0xF {
+        base_family + ext_family
+    } else {
+        base_family
+    };
+    let model = if base_family == 0x6 || base_family == 0xF {
+        (ext_model << 4) | base_model
+    } else {
+        base_model
+    };
+    (family, model)
+}
+
+/// 早期 Ryzen / Threadripper X 系列的 Tctl 偏移
+/// 参考 AMD 公开说明与 `LibreHardwareMonitor` 的 `Amd17Cpu`
+fn amd_tctl_offset(brand: &str) -> f32 {
+    const OFFSETS: &[(&str, f32)] = &[
+        ("AMD Ryzen 5 1600X", 20.0),
+        ("AMD Ryzen 7 1700X", 20.0),
+        ("AMD Ryzen 7 1800X", 20.0),
+        ("AMD Ryzen 7 2700X", 10.0),
+        ("AMD Ryzen Threadripper 19", 27.0),
+        ("AMD Ryzen Threadripper 29", 27.0),
+    ];
+
+    OFFSETS
+        .iter()
+        .find(|(prefix, _)| brand.starts_with(prefix))
+        .map_or(0.0, |&(_, offset)| offset)
+}
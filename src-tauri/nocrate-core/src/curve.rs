@@ -0,0 +1,339 @@
+// 风扇曲线校验
+// 所有写入风扇曲线的路径（笔记本 / 桌面 WMI 曲线、风扇组、按配置文件保存的曲线）共用同一套规则：
+// 温度严格递增、占空比单调不减、数值在 0–100 之内、最后一点覆盖高温区间，
+// 已校准的风扇头还要求占空比不低于其停转点。违规项逐条返回，前端可直接标出出错的点
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::wmi::asus_mgmt::{FanCalibration, FanCurvePoint};
+
+/// 曲线点温度上限 (°C)
+pub const MAX_TEMP_C: u8 = 100;
+
+/// 最后一点至少要达到的温度 (°C)，保证曲线覆盖高温区间
+/// 取 70 °C 以兼容 BIOS 默认只到 75 °C 的桌面 Q-Fan 曲线
+pub const MIN_TOP_TEMP_C: u8 = 70;
+
+/// 占空比上限 (%)
+pub const FULL_DUTY_PCT: u8 = 100;
+
+/// 一条违规；`index` 为从 0 开始的点序号
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CurveViolation {
+    /// 温度不高于前一个点
+    TempNotAscending {
+        index: usize,
+        temp_c: u8,
+        prev_temp_c: u8,
+    },
+    /// 占空比低于前一个点
+    DutyDescending {
+        index: usize,
+        duty_pct: u8,
+        prev_duty_pct: u8,
+    },
+    /// 温度超过 [`MAX_TEMP_C`]
+    TempOutOfRange { index: usize, temp_c: u8 },
+    /// 占空比超过 [`FULL_DUTY_PCT`]
+    DutyOutOfRange { index: usize, duty_pct: u8 },
+    /// 占空比低于该风扇头校准得到的停转点，风扇会停转
+    BelowStallDuty {
+        index: usize,
+        duty_pct: u8,
+        min_duty_pct: u8,
+    },
+    /// 最后一点（最高温度）低于 [`MIN_TOP_TEMP_C`]
+    NoTopPoint { temp_c: u8 },
+    /// 曲线没有任何点
+    Empty,
+}
+
+impl fmt::Display for CurveViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match *self {
+            Self::TempNotAscending {
+                index,
+                temp_c,
+                prev_temp_c,
+            } => i18n::tf(
+                Msg::CurveTempNotAscending,
+                &[
+                    ("point", &(index + 1)),
+                    ("temp", &temp_c),
+                    ("prev", &prev_temp_c),
+                ],
+            ),
+            Self::DutyDescending {
+                index,
+                duty_pct,
+                prev_duty_pct,
+            } => i18n::tf(
+                Msg::CurveDutyDescending,
+                &[
+                    ("point", &(index + 1)),
+                    ("duty", &duty_pct),
+                    ("prev", &prev_duty_pct),
+                ],
+            ),
+            Self::TempOutOfRange { index, temp_c } => i18n::tf(
+                Msg::CurveTempOutOfRange,
+                &[
+                    ("point", &(index + 1)),
+                    ("temp", &temp_c),
+                    ("max", &MAX_TEMP_C),
+                ],
+            ),
+            Self::DutyOutOfRange { index, duty_pct } => i18n::tf(
+                Msg::CurveDutyOutOfRange,
+                &[
+                    ("point", &(index + 1)),
+                    ("duty", &duty_pct),
+                    ("max", &FULL_DUTY_PCT),
+                ],
+            ),
+            Self::BelowStallDuty {
+                index,
+                duty_pct,
+                min_duty_pct,
+            } => i18n::tf(
+                Msg::CurveBelowStallDuty,
+                &[
+                    ("point", &(index + 1)),
+                    ("duty", &duty_pct),
+                    ("min", &min_duty_pct),
+                ],
+            ),
+            Self::NoTopPoint { temp_c } => i18n::tf(
+                Msg::CurveNoTopPoint,
+                &[("temp", &temp_c), ("min", &MIN_TOP_TEMP_C)],
+            ),
+            Self::Empty => i18n::t(Msg::CurveEmpty).to_string(),
+        };
+        f.write_str(&text)
+    }
+}
+
+/// 列出曲线的所有违规项，按点顺序
+/// `calibration` 为该风扇头的 Q-Fan 校准结果，未校准时跳过停转点检查
+#[must_use]
+pub fn violations(
+    points: &[FanCurvePoint],
+    calibration: Option<&FanCalibration>,
+) -> Vec<CurveViolation> {
+    let Some(last) = points.last() else {
+        return vec![CurveViolation::Empty];
+    };
+
+    let mut found = Vec::new();
+    for (index, p) in points.iter().enumerate() {
+        if p.temp_c > MAX_TEMP_C {
+            found.push(CurveViolation::TempOutOfRange {
+                index,
+                temp_c: p.temp_c,
+            });
+        }
+        if p.duty_pct > FULL_DUTY_PCT {
+            found.push(CurveViolation::DutyOutOfRange {
+                index,
+                duty_pct: p.duty_pct,
+            });
+        }
+        if let Some(prev) = index.checked_sub(1).map(|i| points[i]) {
+            if p.temp_c <= prev.temp_c {
+                found.push(CurveViolation::TempNotAscending {
+                    index,
+                    temp_c: p.temp_c,
+                    prev_temp_c: prev.temp_c,
+                });
+            }
+            if p.duty_pct < prev.duty_pct {
+                found.push(CurveViolation::DutyDescending {
+                    index,
+                    duty_pct: p.duty_pct,
+                    prev_duty_pct: prev.duty_pct,
+                });
+            }
+        }
+        if let Some(cal) = calibration.filter(|c| p.duty_pct < c.min_duty_pct) {
+            found.push(CurveViolation::BelowStallDuty {
+                index,
+                duty_pct: p.duty_pct,
+                min_duty_pct: cal.min_duty_pct,
+            });
+        }
+    }
+    if last.temp_c < MIN_TOP_TEMP_C {
+        found.push(CurveViolation::NoTopPoint {
+            temp_c: last.temp_c,
+        });
+    }
+    found
+}
+
+/// 校验曲线，有违规项时返回 [`NoCrateError::InvalidCurve`]
+///
+/// # Errors
+///
+/// 曲线违反任一规则时返回 `InvalidCurve`，附带全部违规项
+pub fn validate(points: &[FanCurvePoint], calibration: Option<&FanCalibration>) -> Result<()> {
+    let found = violations(points, calibration);
+    if found.is_empty() {
+        Ok(())
+    } else {
+        Err(NoCrateError::InvalidCurve(found))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(points: &[(u8, u8)]) -> Vec<FanCurvePoint> {
+        points
+            .iter()
+            .map(|&(temp_c, duty_pct)| FanCurvePoint { temp_c, duty_pct })
+            .collect()
+    }
+
+    #[test]
+    fn valid_curve_passes() {
+        let points = curve(&[(30, 20), (50, 40), (70, 70), (90, 90)]);
+        assert!(violations(&points, None).is_empty());
+        assert!(validate(&points, None).is_ok());
+    }
+
+    #[test]
+    fn empty_curve() {
+        assert_eq!(violations(&[], None), vec![CurveViolation::Empty]);
+    }
+
+    #[test]
+    fn temp_descending() {
+        let points = curve(&[(40, 20), (35, 30), (80, 50)]);
+        assert_eq!(
+            violations(&points, None),
+            vec![CurveViolation::TempNotAscending {
+                index: 1,
+                temp_c: 35,
+                prev_temp_c: 40,
+            }]
+        );
+    }
+
+    #[test]
+    fn repeated_temp_is_not_ascending() {
+        let points = curve(&[(40, 20), (40, 30), (80, 50)]);
+        assert_eq!(
+            violations(&points, None),
+            vec![CurveViolation::TempNotAscending {
+                index: 1,
+                temp_c: 40,
+                prev_temp_c: 40,
+            }]
+        );
+    }
+
+    #[test]
+    fn curve_must_reach_the_top_of_the_range() {
+        let points = curve(&[(30, 20), (50, 40), (65, 100)]);
+        assert_eq!(
+            violations(&points, None),
+            vec![CurveViolation::NoTopPoint { temp_c: 65 }]
+        );
+        let points = curve(&[(30, 20), (50, 40), (MIN_TOP_TEMP_C, 60)]);
+        assert!(violations(&points, None).is_empty());
+    }
+
+    #[test]
+    fn duty_descending() {
+        let points = curve(&[(30, 50), (50, 40), (70, 80)]);
+        assert_eq!(
+            violations(&points, None),
+            vec![CurveViolation::DutyDescending {
+                index: 1,
+                duty_pct: 40,
+                prev_duty_pct: 50,
+            }]
+        );
+    }
+
+    #[test]
+    fn temp_out_of_range() {
+        let points = curve(&[(30, 20), (110, 100)]);
+        assert_eq!(
+            violations(&points, None),
+            vec![CurveViolation::TempOutOfRange {
+                index: 1,
+                temp_c: 110,
+            }]
+        );
+    }
+
+    #[test]
+    fn duty_out_of_range() {
+        let points = curve(&[(30, 20), (90, 120)]);
+        assert_eq!(
+            violations(&points, None),
+            vec![CurveViolation::DutyOutOfRange {
+                index: 1,
+                duty_pct: 120,
+            }]
+        );
+    }
+
+    #[test]
+    fn below_stall_duty_only_with_calibration() {
+        let points = curve(&[(30, 15), (80, 50)]);
+        let cal = FanCalibration {
+            min_duty_pct: 20,
+            min_rpm: 400,
+            max_rpm: 2000,
+        };
+        assert!(violations(&points, None).is_empty());
+        assert_eq!(
+            violations(&points, Some(&cal)),
+            vec![CurveViolation::BelowStallDuty {
+                index: 0,
+                duty_pct: 15,
+                min_duty_pct: 20,
+            }]
+        );
+    }
+
+    #[test]
+    fn all_violations_are_reported_in_point_order() {
+        let points = curve(&[(50, 60), (40, 50), (120, 130)]);
+        let err = validate(&points, None).unwrap_err();
+        let NoCrateError::InvalidCurve(found) = err else {
+            panic!("expected InvalidCurve, got {err:?}");
+        };
+        assert_eq!(
+            found,
+            vec![
+                CurveViolation::TempNotAscending {
+                    index: 1,
+                    temp_c: 40,
+                    prev_temp_c: 50,
+                },
+                CurveViolation::DutyDescending {
+                    index: 1,
+                    duty_pct: 50,
+                    prev_duty_pct: 60,
+                },
+                CurveViolation::TempOutOfRange {
+                    index: 2,
+                    temp_c: 120,
+                },
+                CurveViolation::DutyOutOfRange {
+                    index: 2,
+                    duty_pct: 130,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// ASUS 主板 EC 传感器偏移表
+//
+// 寄存器地址为 16 位：高字节为 EC bank，低字节为 bank 内偏移。
+// 偏移值参考 LibreHardwareMonitor 的 ASUS EmbeddedController 实现与 Linux asus-ec-sensors 驱动。
+
+use super::EcSensorKind;
+
+/// 单个 EC 传感器定义
+pub struct EcSensorDef {
+    pub name: &'static str,
+    pub kind: EcSensorKind,
+    /// 16 位寄存器地址（bank << 8 | index）
+    pub register: u16,
+    /// 读取的字节数（1 = 有符号温度，2 = 大端 16 位计数）
+    pub size: u8,
+    /// 原始值乘以该系数得到物理量
+    pub scale: f32,
+}
+
+const fn temp(name: &'static str, register: u16) -> EcSensorDef {
+    EcSensorDef {
+        name,
+        kind: EcSensorKind::Temperature,
+        register,
+        size: 1,
+        scale: 1.0,
+    }
+}
+
+const fn fan(name: &'static str, register: u16) -> EcSensorDef {
+    EcSensorDef {
+        name,
+        kind: EcSensorKind::Fan,
+        register,
+        size: 2,
+        scale: 1.0,
+    }
+}
+
+// ─── 通用传感器定义 ───────────────────────────────────────────
+
+const TEMP_CHIPSET: EcSensorDef = temp("芯片组", 0x003A);
+const TEMP_CPU: EcSensorDef = temp("CPU", 0x003B);
+const TEMP_MB: EcSensorDef = temp("主板", 0x003C);
+const TEMP_T_SENSOR: EcSensorDef = temp("T_Sensor", 0x003D);
+const TEMP_VRM: EcSensorDef = temp("VRM", 0x003E);
+const TEMP_WATER_IN: EcSensorDef = temp("水冷入口", 0x0100);
+const TEMP_WATER_OUT: EcSensorDef = temp("水冷出口", 0x0101);
+
+const FAN_CPU_OPT: EcSensorDef = fan("CPU_OPT", 0x00B0);
+const FAN_VRM_HS: EcSensorDef = fan("VRM 散热风扇", 0x00B2);
+const FAN_CHIPSET: EcSensorDef = fan("芯片组风扇", 0x00B4);
+
+const WATER_FLOW: EcSensorDef = EcSensorDef {
+    name: "水冷流量",
+    kind: EcSensorKind::Flow,
+    register: 0x00BC,
+    size: 2,
+    // 流量计脉冲：1 L/min ≈ 42 RPM
+    scale: 60.0 / 42.0,
+};
+
+const CURRENT_CPU: EcSensorDef = EcSensorDef {
+    name: "CPU 电流",
+    kind: EcSensorKind::Current,
+    register: 0x00F4,
+    size: 1,
+    scale: 1.0,
+};
+
+/// 按主板型号（SMBIOS BaseBoardProduct）查找 EC 传感器表
+pub fn sensors_for_board(board: &str) -> Option<&'static [EcSensorDef]> {
+    let table: &'static [EcSensorDef] = match board {
+        "PRIME X570-PRO" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_VRM,
+            TEMP_T_SENSOR,
+            FAN_CHIPSET,
+        ],
+        "ProArt X570-CREATOR WIFI" => &[
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_VRM,
+            TEMP_T_SENSOR,
+            FAN_CPU_OPT,
+        ],
+        "Pro WS X570-ACE" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_VRM,
+            FAN_CHIPSET,
+            CURRENT_CPU,
+        ],
+        "ROG CROSSHAIR VIII HERO" | "ROG CROSSHAIR VIII HERO (WI-FI)" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_T_SENSOR,
+            TEMP_VRM,
+            TEMP_WATER_IN,
+            TEMP_WATER_OUT,
+            FAN_CPU_OPT,
+            FAN_CHIPSET,
+            WATER_FLOW,
+            CURRENT_CPU,
+        ],
+        "ROG CROSSHAIR VIII DARK HERO" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_T_SENSOR,
+            TEMP_VRM,
+            TEMP_WATER_IN,
+            TEMP_WATER_OUT,
+            FAN_CPU_OPT,
+            WATER_FLOW,
+            CURRENT_CPU,
+        ],
+        "ROG CROSSHAIR VIII FORMULA" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_T_SENSOR,
+            TEMP_VRM,
+            FAN_CPU_OPT,
+            FAN_VRM_HS,
+            FAN_CHIPSET,
+            CURRENT_CPU,
+        ],
+        "ROG STRIX X570-E GAMING" | "ROG STRIX X570-F GAMING" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_T_SENSOR,
+            TEMP_VRM,
+            FAN_CHIPSET,
+            CURRENT_CPU,
+        ],
+        "ROG STRIX X570-I GAMING" => &[TEMP_T_SENSOR, FAN_VRM_HS, FAN_CHIPSET, CURRENT_CPU],
+        "ROG STRIX B550-E GAMING" | "ROG STRIX B550-I GAMING" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_T_SENSOR,
+            TEMP_VRM,
+            FAN_CPU_OPT,
+        ],
+        "ROG MAXIMUS XI HERO" | "ROG MAXIMUS XI HERO (WI-FI)" => &[
+            TEMP_CHIPSET,
+            TEMP_CPU,
+            TEMP_MB,
+            TEMP_T_SENSOR,
+            TEMP_VRM,
+            FAN_CPU_OPT,
+            CURRENT_CPU,
+        ],
+        "ROG STRIX Z690-A GAMING WIFI D4" => &[TEMP_T_SENSOR, TEMP_VRM],
+        _ => return None,
+    };
+    Some(table)
+}
@@ -0,0 +1,251 @@
+// ACPI 嵌入式控制器 (EC) 传感器模块
+// 通过 WinRing0 驱动访问 EC 端口 0x62/0x66，读取 SIO 未暴露的 VRM / 芯片组 / 水冷温度等
+
+pub mod boards;
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_ABANDONED, WAIT_OBJECT_0};
+use windows::Win32::System::Threading::{CreateMutexW, ReleaseMutex, WaitForSingleObject};
+
+use crate::board::read_board_name;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::sio::driver::DriverHandle;
+use boards::EcSensorDef;
+
+/// EC 数据端口
+pub(crate) const EC_DATA_PORT: u16 = 0x62;
+/// EC 命令 / 状态端口
+pub(crate) const EC_COMMAND_PORT: u16 = 0x66;
+
+/// 状态位：输出缓冲区满（可读）
+const EC_STATUS_OBF: u8 = 0x01;
+/// 状态位：输入缓冲区满（忙，不可写）
+const EC_STATUS_IBF: u8 = 0x02;
+
+/// ACPI EC 读命令
+pub(crate) const EC_CMD_READ: u8 = 0x80;
+/// ACPI EC 写命令
+pub(crate) const EC_CMD_WRITE: u8 = 0x81;
+
+/// ASUS EC bank 选择寄存器
+const EC_BANK_REG: u8 = 0xFF;
+
+/// 等待 EC 状态位的最大轮询次数
+const EC_WAIT_ITERATIONS: u32 = 5000;
+
+/// 与 ASUS 固件 / 其他监控软件共享的 EC 访问互斥体名称
+const EC_MUTEX_NAME: &str = "Global\\Access_EC";
+/// 获取 EC 互斥体的超时（毫秒）
+const EC_MUTEX_TIMEOUT_MS: u32 = 200;
+
+/// EC 传感器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EcSensorKind {
+    /// 温度（°C）
+    Temperature,
+    /// 风扇转速（RPM）
+    Fan,
+    /// 电流（A）
+    Current,
+    /// 流量（L/h）
+    Flow,
+}
+
+/// 单个 EC 传感器读数
+#[derive(Debug, Clone, Serialize)]
+pub struct EcReading {
+    /// 传感器名称
+    pub name: String,
+    /// 传感器类型
+    pub kind: EcSensorKind,
+    /// 换算后的数值
+    pub value: f32,
+    /// 16 位寄存器地址（bank << 8 | index）
+    pub register: u16,
+}
+
+/// EC 传感器快照
+#[derive(Debug, Clone, Serialize)]
+pub struct EcSnapshot {
+    /// 主板型号（偏移表的键）
+    pub board: String,
+    /// 所有读数
+    pub readings: Vec<EcReading>,
+}
+
+/// EC 传感器监控器
+pub struct EcMonitor {
+    driver: Arc<DriverHandle>,
+    board: String,
+    sensors: &'static [EcSensorDef],
+    /// 进程内串行化 EC 事务（跨进程由命名互斥体保证）
+    lock: Mutex<()>,
+}
+
+impl EcMonitor {
+    /// 识别主板型号并查找对应的 EC 偏移表
+    pub fn init(driver: Arc<DriverHandle>) -> Result<Self> {
+        let board = read_board_name()
+            .ok_or_else(|| NoCrateError::Sio(i18n::t(Msg::EcBoardUnknown).into()))?;
+
+        let sensors = boards::sensors_for_board(&board).ok_or_else(|| {
+            NoCrateError::Sio(i18n::tf(Msg::EcBoardUnsupported, &[("board", &board)]))
+        })?;
+
+        eprintln!("EC: 主板 {board}，{} 个传感器", sensors.len());
+
+        Ok(Self {
+            driver,
+            board,
+            sensors,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// 读取当前主板的所有 EC 传感器
+    pub fn read_all(&self) -> Result<EcSnapshot> {
+        let _guard = self.lock.lock();
+        let _ec_mutex = EcGlobalMutex::acquire()?;
+
+        let mut readings = Vec::with_capacity(self.sensors.len());
+        for def in self.sensors {
+            let value = match self.read_sensor(def) {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("[EC] 读取 {} (0x{:04X}) 失败: {e}", def.name, def.register);
+                    continue;
+                }
+            };
+
+            // 温度传感器未接入时返回 -40 / -128 等哨兵值
+            if def.kind == EcSensorKind::Temperature && (value <= -40.0 || value > 125.0) {
+                continue;
+            }
+
+            readings.push(EcReading {
+                name: def.name.to_string(),
+                kind: def.kind,
+                value,
+                register: def.register,
+            });
+        }
+
+        Ok(EcSnapshot {
+            board: self.board.clone(),
+            readings,
+        })
+    }
+
+    /// 读取单个传感器并换算
+    fn read_sensor(&self, def: &EcSensorDef) -> Result<f32> {
+        let raw = if def.size == 2 {
+            let high = self.read_banked(def.register)?;
+            let low = self.read_banked(def.register + 1)?;
+            f32::from(u16::from(high) << 8 | u16::from(low))
+        } else {
+            let value = self.read_banked(def.register)?;
+            if def.kind == EcSensorKind::Temperature {
+                f32::from(value as i8)
+            } else {
+                f32::from(value)
+            }
+        };
+        Ok(raw * def.scale)
+    }
+
+    /// 读取带 bank 的 16 位寄存器地址，读取后恢复原 bank
+    fn read_banked(&self, register: u16) -> Result<u8> {
+        let bank = (register >> 8) as u8;
+        let index = (register & 0xFF) as u8;
+
+        let prev_bank = self.read_byte(EC_BANK_REG)?;
+        if prev_bank != bank {
+            self.write_byte(EC_BANK_REG, bank)?;
+        }
+        let value = self.read_byte(index);
+        if prev_bank != bank {
+            self.write_byte(EC_BANK_REG, prev_bank)?;
+        }
+        value
+    }
+
+    /// 标准 ACPI EC 读事务
+    fn read_byte(&self, addr: u8) -> Result<u8> {
+        self.wait_input_empty()?;
+        self.driver.write_io_port_byte(EC_COMMAND_PORT, EC_CMD_READ)?;
+        self.wait_input_empty()?;
+        self.driver.write_io_port_byte(EC_DATA_PORT, addr)?;
+        self.wait_output_full()?;
+        self.driver.read_io_port_byte(EC_DATA_PORT)
+    }
+
+    /// 标准 ACPI EC 写事务
+    fn write_byte(&self, addr: u8, value: u8) -> Result<()> {
+        self.wait_input_empty()?;
+        self.driver.write_io_port_byte(EC_COMMAND_PORT, EC_CMD_WRITE)?;
+        self.wait_input_empty()?;
+        self.driver.write_io_port_byte(EC_DATA_PORT, addr)?;
+        self.wait_input_empty()?;
+        self.driver.write_io_port_byte(EC_DATA_PORT, value)
+    }
+
+    fn wait_input_empty(&self) -> Result<()> {
+        for _ in 0..EC_WAIT_ITERATIONS {
+            if self.driver.read_io_port_byte(EC_COMMAND_PORT)? & EC_STATUS_IBF == 0 {
+                return Ok(());
+            }
+        }
+        Err(NoCrateError::Timeout(i18n::t(Msg::EcInputTimeout).into()))
+    }
+
+    fn wait_output_full(&self) -> Result<()> {
+        for _ in 0..EC_WAIT_ITERATIONS {
+            if self.driver.read_io_port_byte(EC_COMMAND_PORT)? & EC_STATUS_OBF != 0 {
+                return Ok(());
+            }
+        }
+        Err(NoCrateError::Timeout(i18n::t(Msg::EcOutputTimeout).into()))
+    }
+}
+
+/// 跨进程 EC 互斥体（与 ASUS 服务、LibreHardwareMonitor 等共享）
+/// Drop 时释放并关闭句柄
+struct EcGlobalMutex(HANDLE);
+
+#[allow(unsafe_code)]
+impl EcGlobalMutex {
+    fn acquire() -> Result<Self> {
+        let name = to_wide(EC_MUTEX_NAME);
+        unsafe {
+            let handle = CreateMutexW(None, false, PCWSTR(name.as_ptr()))
+                .map_err(|e| NoCrateError::Sio(i18n::tf(Msg::EcMutexCreate, &[("error", &e)])))?;
+            let wait = WaitForSingleObject(handle, EC_MUTEX_TIMEOUT_MS);
+            if wait != WAIT_OBJECT_0 && wait != WAIT_ABANDONED {
+                let _ = CloseHandle(handle);
+                return Err(NoCrateError::Busy(i18n::t(Msg::EcBusy).into()));
+            }
+            Ok(Self(handle))
+        }
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for EcGlobalMutex {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = ReleaseMutex(self.0);
+            let _ = CloseHandle(self.0);
+        }
+    }
+}
+
+/// 将 &str 转换为以 null 结尾的宽字符串
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::curve::CurveViolation;
+use crate::i18n::{self, Msg};
+
+/// Unified error type for NoCrate operations.
+///
+/// Serialized as `{ "code": "...", "message": "..." }`, where `code` is the
+/// stable [`code`](Self::code) the frontend uses to suggest a fix (e.g.
+/// "run as administrator") and `message` the display text. `InvalidCurve`
+/// adds the list of `violations`. The message is rendered in the current
+/// [`i18n`] locale.
+#[derive(Debug, Clone, Error)]
+pub enum NoCrateError {
+    Wmi(String),
+
+    WindowsApi(u32),
+
+    Hid(String),
+
+    Config(String),
+
+    Sio(String),
+
+    Storage(String),
+
+    Gpu(String),
+
+    /// A kernel driver was refused by Windows, typically by the
+    /// vulnerable driver blocklist or memory integrity (HVCI).
+    DriverBlocked(String),
+
+    /// The operation needs administrator rights.
+    AccessDenied(String),
+
+    /// The device, controller or firmware interface does not exist on
+    /// this machine.
+    DeviceNotPresent(String),
+
+    Timeout(String),
+
+    /// The hardware is in use by another program; retrying may succeed.
+    Busy(String),
+
+    /// A fan curve breaks the rules in [`crate::curve`]; carries every
+    /// violation so the frontend can point at the offending points.
+    InvalidCurve(Vec<CurveViolation>),
+
+    Unknown(String),
+}
+
+impl fmt::Display for NoCrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (msg, detail) = match self {
+            Self::Wmi(d) => (Msg::ErrWmi, d.clone()),
+            Self::WindowsApi(hr) => (Msg::ErrWindowsApi, format!("HRESULT 0x{hr:08X}")),
+            Self::Hid(d) => (Msg::ErrHid, d.clone()),
+            Self::Config(d) => (Msg::ErrConfig, d.clone()),
+            Self::Sio(d) => (Msg::ErrSio, d.clone()),
+            Self::Storage(d) => (Msg::ErrStorage, d.clone()),
+            Self::Gpu(d) => (Msg::ErrGpu, d.clone()),
+            Self::DriverBlocked(d) => (Msg::ErrDriverBlocked, d.clone()),
+            Self::AccessDenied(d) => (Msg::ErrAccessDenied, d.clone()),
+            Self::DeviceNotPresent(d) => (Msg::ErrDeviceNotPresent, d.clone()),
+            Self::Timeout(d) => (Msg::ErrTimeout, d.clone()),
+            Self::Busy(d) => (Msg::ErrBusy, d.clone()),
+            Self::InvalidCurve(v) => (Msg::ErrInvalidCurve, describe_violations(v)),
+            Self::Unknown(d) => (Msg::ErrUnknown, d.clone()),
+        };
+        f.write_str(&i18n::tf(msg, &[("detail", &detail)]))
+    }
+}
+
+fn describe_violations(violations: &[CurveViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Convenience Result type alias with `NoCrateError`.
+pub type Result<T> = std::result::Result<T, NoCrateError>;
+
+/// `HRESULT_FROM_WIN32` of the Win32 errors given structured variants.
+const E_ACCESSDENIED: u32 = 0x8007_0005;
+const E_SHARING_VIOLATION: u32 = 0x8007_0020;
+const E_DEV_NOT_EXIST: u32 = 0x8007_0037;
+const E_BUSY: u32 = 0x8007_00AA;
+const E_WAIT_TIMEOUT: u32 = 0x8007_0102;
+const E_DEVICE_NOT_CONNECTED: u32 = 0x8007_048F;
+const E_DRIVER_BLOCKED: u32 = 0x8007_04FB;
+const E_TIMEOUT: u32 = 0x8007_05B4;
+/// `WBEM_E_ACCESS_DENIED`
+const WBEM_E_ACCESS_DENIED: u32 = 0x8004_1003;
+
+impl NoCrateError {
+    /// Stable machine-readable code, the `snake_case` variant name.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::Wmi(_) => "wmi",
+            Self::WindowsApi(_) => "windows_api",
+            Self::Hid(_) => "hid",
+            Self::Config(_) => "config",
+            Self::Sio(_) => "sio",
+            Self::Storage(_) => "storage",
+            Self::Gpu(_) => "gpu",
+            Self::DriverBlocked(_) => "driver_blocked",
+            Self::AccessDenied(_) => "access_denied",
+            Self::DeviceNotPresent(_) => "device_not_present",
+            Self::Timeout(_) => "timeout",
+            Self::Busy(_) => "busy",
+            Self::InvalidCurve(_) => "invalid_curve",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+
+    /// Wrap a Windows error with `message`, using the structured variant
+    /// for its HRESULT if there is one and `fallback` otherwise.
+    #[must_use]
+    pub fn classify(
+        err: &windows::core::Error,
+        message: String,
+        fallback: fn(String) -> Self,
+    ) -> Self {
+        match err.code().0.cast_unsigned() {
+            E_ACCESSDENIED | WBEM_E_ACCESS_DENIED => Self::AccessDenied(message),
+            E_DRIVER_BLOCKED => Self::DriverBlocked(message),
+            E_DEV_NOT_EXIST | E_DEVICE_NOT_CONNECTED => Self::DeviceNotPresent(message),
+            E_BUSY | E_SHARING_VIOLATION => Self::Busy(message),
+            E_TIMEOUT | E_WAIT_TIMEOUT => Self::Timeout(message),
+            _ => fallback(message),
+        }
+    }
+}
+
+impl Serialize for NoCrateError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let violations = match self {
+            Self::InvalidCurve(v) => Some(v),
+            _ => None,
+        };
+        let len = if violations.is_some() { 3 } else { 2 };
+        let mut s = serializer.serialize_struct("NoCrateError", len)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", &self.to_string())?;
+        if let Some(v) = violations {
+            s.serialize_field("violations", v)?;
+        }
+        s.end()
+    }
+}
+
+/// A [`NoCrateError`] as saved in a sensor trace: the stable
+/// [`code`](NoCrateError::code), plus the HRESULT of `WindowsApi`. The
+/// message is left out since it depends on the UI language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedError {
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hresult: Option<u32>,
+}
+
+impl From<&NoCrateError> for RecordedError {
+    fn from(err: &NoCrateError) -> Self {
+        Self {
+            code: err.code().to_string(),
+            hresult: match err {
+                NoCrateError::WindowsApi(hr) => Some(*hr),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl RecordedError {
+    /// Rebuild the error with `detail` in place of the original message.
+    #[must_use]
+    pub fn to_error(&self, detail: String) -> NoCrateError {
+        match self.code.as_str() {
+            "wmi" => NoCrateError::Wmi(detail),
+            "windows_api" => NoCrateError::WindowsApi(self.hresult.unwrap_or_default()),
+            "hid" => NoCrateError::Hid(detail),
+            "config" => NoCrateError::Config(detail),
+            "sio" => NoCrateError::Sio(detail),
+            "storage" => NoCrateError::Storage(detail),
+            "gpu" => NoCrateError::Gpu(detail),
+            "driver_blocked" => NoCrateError::DriverBlocked(detail),
+            "access_denied" => NoCrateError::AccessDenied(detail),
+            "device_not_present" => NoCrateError::DeviceNotPresent(detail),
+            "timeout" => NoCrateError::Timeout(detail),
+            "busy" => NoCrateError::Busy(detail),
+            "invalid_curve" => NoCrateError::InvalidCurve(Vec::new()),
+            _ => NoCrateError::Unknown(detail),
+        }
+    }
+}
+
+impl From<windows::core::Error> for NoCrateError {
+    fn from(err: windows::core::Error) -> Self {
+        let code = err.code().0.cast_unsigned();
+        let structured = Self::classify(&err, err.to_string(), Self::Unknown);
+        if matches!(structured, Self::Unknown(_)) {
+            Self::WindowsApi(code)
+        } else {
+            structured
+        }
+    }
+}
+
+impl From<hidapi::HidError> for NoCrateError {
+    fn from(err: hidapi::HidError) -> Self {
+        Self::Hid(err.to_string())
+    }
+}
+
+impl From<NoCrateError> for String {
+    fn from(err: NoCrateError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Plain messages from code that reports errors as strings.
+impl From<String> for NoCrateError {
+    fn from(message: String) -> Self {
+        Self::Unknown(message)
+    }
+}
+
+impl From<&str> for NoCrateError {
+    fn from(message: &str) -> Self {
+        Self::Unknown(message.to_string())
+    }
+}
@@ -0,0 +1,250 @@
+// AMD ADL2 后端
+// 使用 ADL2_New_QueryPMLogData_Get 读取 PMLog 传感器（RDNA / Vega 及以后）
+
+use std::ffi::{c_char, c_int, c_void, CStr};
+
+use parking_lot::Mutex;
+
+use super::{GpuDeviceSnapshot, GpuReading, GpuSensorKind, Library};
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+const ADL_OK: c_int = 0;
+/// AMD PCI 厂商 ID（ADL 的 `iVendorID` 在不同驱动版本中以十六进制或十进制 1002 报告）
+const AMD_VENDOR_IDS: [c_int; 2] = [0x1002, 1002];
+/// `PMLog` 传感器数组长度
+const ADL_PMLOG_MAX_SENSORS: usize = 256;
+/// ADL 字符串字段长度
+const ADL_MAX_PATH: usize = 256;
+
+/// `ADLSensorType` 中使用的索引
+const PMLOG_TEMPERATURE_EDGE: usize = 8;
+const PMLOG_TEMPERATURE_MEM: usize = 9;
+const PMLOG_FAN_RPM: usize = 14;
+const PMLOG_TEMPERATURE_HOTSPOT: usize = 27;
+
+/// 需要读取的 `PMLog` 传感器
+const PMLOG_SENSORS: &[(usize, &str, GpuSensorKind)] = &[
+    (PMLOG_TEMPERATURE_EDGE, "Core", GpuSensorKind::Temperature),
+    (PMLOG_TEMPERATURE_HOTSPOT, "Hotspot", GpuSensorKind::Temperature),
+    (PMLOG_TEMPERATURE_MEM, "Memory", GpuSensorKind::Temperature),
+    (PMLOG_FAN_RPM, "Fan 1", GpuSensorKind::FanRpm),
+];
+
+/// `AdapterInfo`
+#[repr(C)]
+struct AdapterInfo {
+    size: c_int,
+    adapter_index: c_int,
+    udid: [c_char; ADL_MAX_PATH],
+    bus_number: c_int,
+    device_number: c_int,
+    function_number: c_int,
+    vendor_id: c_int,
+    adapter_name: [c_char; ADL_MAX_PATH],
+    display_name: [c_char; ADL_MAX_PATH],
+    present: c_int,
+    exist: c_int,
+    driver_path: [c_char; ADL_MAX_PATH],
+    driver_path_ext: [c_char; ADL_MAX_PATH],
+    pnp_string: [c_char; ADL_MAX_PATH],
+    os_display_index: c_int,
+}
+
+/// `ADLSingleSensorData`
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SingleSensorData {
+    supported: c_int,
+    value: c_int,
+}
+
+/// `ADLPMLogDataOutput`
+#[repr(C)]
+struct PmLogDataOutput {
+    size: c_int,
+    sensors: [SingleSensorData; ADL_PMLOG_MAX_SENSORS],
+}
+
+type AdlContext = *mut c_void;
+type MallocCallback = unsafe extern "C" fn(c_int) -> *mut c_void;
+
+type FnCreate = unsafe extern "C" fn(MallocCallback, c_int, *mut AdlContext) -> c_int;
+type FnDestroy = unsafe extern "C" fn(AdlContext) -> c_int;
+type FnNumAdapters = unsafe extern "C" fn(AdlContext, *mut c_int) -> c_int;
+type FnAdapterInfo = unsafe extern "C" fn(AdlContext, *mut AdapterInfo, c_int) -> c_int;
+type FnQueryPmLog = unsafe extern "C" fn(AdlContext, c_int, *mut PmLogDataOutput) -> c_int;
+
+/// 物理 GPU（同一块显卡在 ADL 中按输出口重复出现，按 PCI 总线号去重）
+struct AdlAdapter {
+    adapter_index: c_int,
+    name: String,
+}
+
+/// 已初始化的 ADL2 上下文
+pub struct Adl {
+    context: Mutex<AdlContext>,
+    destroy: FnDestroy,
+    query_pmlog: FnQueryPmLog,
+    adapters: Vec<AdlAdapter>,
+    // 必须最后 drop：函数指针依赖库保持加载
+    _lib: Library,
+}
+
+// ADL2 上下文通过 Mutex 串行访问
+#[allow(unsafe_code)]
+unsafe impl Send for Adl {}
+#[allow(unsafe_code)]
+unsafe impl Sync for Adl {}
+
+#[allow(unsafe_code)]
+impl Adl {
+    /// 加载 atiadlxx.dll，创建上下文并枚举 AMD 显卡
+    pub fn load() -> Result<Self> {
+        let lib = Library::load("atiadlxx.dll")?;
+
+        unsafe {
+            let create: FnCreate = lib.symbol(c"ADL2_Main_Control_Create")?;
+            let destroy: FnDestroy = lib.symbol(c"ADL2_Main_Control_Destroy")?;
+            let num_adapters: FnNumAdapters = lib.symbol(c"ADL2_Adapter_NumberOfAdapters_Get")?;
+            let adapter_info: FnAdapterInfo = lib.symbol(c"ADL2_Adapter_AdapterInfo_Get")?;
+            let query_pmlog: FnQueryPmLog = lib.symbol(c"ADL2_New_QueryPMLogData_Get")?;
+
+            let mut context: AdlContext = std::ptr::null_mut();
+            if create(adl_malloc, 1, &mut context) != ADL_OK {
+                return Err(NoCrateError::Gpu(i18n::tf(
+                    Msg::GpuCallFailed,
+                    &[("func", &"ADL2_Main_Control_Create")],
+                )));
+            }
+
+            let adapters = match enumerate_adapters(context, num_adapters, adapter_info) {
+                Ok(a) if !a.is_empty() => a,
+                Ok(_) => {
+                    let _ = destroy(context);
+                    return Err(NoCrateError::Gpu(i18n::t(Msg::GpuNoAmd).into()));
+                }
+                Err(e) => {
+                    let _ = destroy(context);
+                    return Err(e);
+                }
+            };
+
+            Ok(Self {
+                context: Mutex::new(context),
+                destroy,
+                query_pmlog,
+                adapters,
+                _lib: lib,
+            })
+        }
+    }
+
+    /// 读取所有 AMD GPU
+    pub fn read_all(&self) -> Vec<GpuDeviceSnapshot> {
+        let context = self.context.lock();
+
+        let mut devices = Vec::with_capacity(self.adapters.len());
+        for (index, adapter) in (0u32..).zip(&self.adapters) {
+            let mut output = PmLogDataOutput {
+                size: std::mem::size_of::<PmLogDataOutput>() as c_int,
+                sensors: [SingleSensorData {
+                    supported: 0,
+                    value: 0,
+                }; ADL_PMLOG_MAX_SENSORS],
+            };
+
+            if unsafe { (self.query_pmlog)(*context, adapter.adapter_index, &mut output) }
+                != ADL_OK
+            {
+                eprintln!("[GPU] ADL PMLog 查询失败: {}", adapter.name);
+                continue;
+            }
+
+            let readings = PMLOG_SENSORS
+                .iter()
+                .filter(|(i, _, _)| output.sensors[*i].supported != 0)
+                .map(|&(i, name, kind)| GpuReading {
+                    name: name.to_string(),
+                    kind,
+                    value: output.sensors[i].value as f32,
+                })
+                .collect();
+
+            devices.push(GpuDeviceSnapshot {
+                index,
+                name: adapter.name.clone(),
+                backend: "adl",
+                readings,
+            });
+        }
+
+        devices
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for Adl {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = (self.destroy)(*self.context.get_mut());
+        }
+    }
+}
+
+/// 枚举 AMD 适配器并按 PCI 总线号去重
+#[allow(unsafe_code)]
+unsafe fn enumerate_adapters(
+    context: AdlContext,
+    num_adapters: FnNumAdapters,
+    adapter_info: FnAdapterInfo,
+) -> Result<Vec<AdlAdapter>> {
+    let mut count: c_int = 0;
+    if unsafe { num_adapters(context, &mut count) } != ADL_OK || count <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut infos: Vec<AdapterInfo> = (0..count)
+        .map(|_| unsafe { std::mem::zeroed() })
+        .collect();
+    let size = (std::mem::size_of::<AdapterInfo>() * infos.len()) as c_int;
+    if unsafe { adapter_info(context, infos.as_mut_ptr(), size) } != ADL_OK {
+        return Err(NoCrateError::Gpu(i18n::tf(
+            Msg::GpuCallFailed,
+            &[("func", &"ADL2_Adapter_AdapterInfo_Get")],
+        )));
+    }
+
+    let mut seen_buses = Vec::new();
+    let mut adapters = Vec::new();
+    for info in &infos {
+        if !AMD_VENDOR_IDS.contains(&info.vendor_id) || seen_buses.contains(&info.bus_number) {
+            continue;
+        }
+        seen_buses.push(info.bus_number);
+
+        let name = unsafe { CStr::from_ptr(info.adapter_name.as_ptr()) }
+            .to_string_lossy()
+            .trim()
+            .to_string();
+        adapters.push(AdlAdapter {
+            adapter_index: info.adapter_index,
+            name,
+        });
+    }
+
+    Ok(adapters)
+}
+
+/// ADL 要求调用方提供的内存分配回调
+/// 仅在少数查询中使用，本模块不调用需要释放的接口
+#[allow(unsafe_code)]
+unsafe extern "C" fn adl_malloc(size: c_int) -> *mut c_void {
+    let Ok(size) = usize::try_from(size) else {
+        return std::ptr::null_mut();
+    };
+    let Ok(layout) = std::alloc::Layout::from_size_align(size.max(1), 8) else {
+        return std::ptr::null_mut();
+    };
+    unsafe { std::alloc::alloc(layout).cast() }
+}
@@ -0,0 +1,140 @@
+// GPU 温度 / 风扇传感器模块
+// 运行时加载厂商驱动自带的 DLL，无需 LibreHardwareMonitor：
+// - NVIDIA：nvml.dll（NVML）
+// - AMD：atiadlxx.dll（ADL2 PMLog，ADLX 为 C++ 接口，Rust 侧直接使用同一驱动包中的 ADL2 C API）
+// 未安装对应驱动时相应后端不可用，不影响其他功能
+
+mod adl;
+mod nvml;
+
+use serde::Serialize;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{FreeLibrary, HMODULE};
+use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+/// GPU 传感器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuSensorKind {
+    /// 温度（°C）
+    Temperature,
+    /// 风扇转速（RPM）
+    FanRpm,
+    /// 风扇占空比（%），驱动不提供 RPM 时使用
+    FanPercent,
+}
+
+/// 单个 GPU 传感器读数
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuReading {
+    /// 传感器名称（如 "Core"、"Hotspot"、"Fan 1"）
+    pub name: String,
+    /// 传感器类型
+    pub kind: GpuSensorKind,
+    /// 数值
+    pub value: f32,
+}
+
+/// 单块 GPU 的读数
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuDeviceSnapshot {
+    /// 设备序号（在各自后端内）
+    pub index: u32,
+    /// 显卡名称
+    pub name: String,
+    /// 数据来源（"nvml" / "adl"）
+    pub backend: &'static str,
+    /// 所有读数
+    pub readings: Vec<GpuReading>,
+}
+
+/// GPU 监控器，持有已成功初始化的后端
+pub struct GpuMonitor {
+    nvml: Option<nvml::Nvml>,
+    adl: Option<adl::Adl>,
+}
+
+impl GpuMonitor {
+    /// 尝试加载 NVML 与 ADL，至少一个可用时返回监控器
+    pub fn init() -> Result<Self> {
+        let nvml = nvml::Nvml::load()
+            .inspect_err(|e| eprintln!("GPU: NVML 不可用: {e}"))
+            .ok();
+        let adl = adl::Adl::load()
+            .inspect_err(|e| eprintln!("GPU: ADL 不可用: {e}"))
+            .ok();
+
+        if nvml.is_none() && adl.is_none() {
+            return Err(NoCrateError::Gpu(i18n::t(Msg::GpuNoDriver).into()));
+        }
+
+        Ok(Self { nvml, adl })
+    }
+
+    /// 读取所有 GPU 的温度与风扇
+    pub fn read_all(&self) -> Vec<GpuDeviceSnapshot> {
+        let mut devices = Vec::new();
+        if let Some(nvml) = &self.nvml {
+            match nvml.read_all() {
+                Ok(mut d) => devices.append(&mut d),
+                Err(e) => eprintln!("[GPU] NVML 读取失败: {e}"),
+            }
+        }
+        if let Some(adl) = &self.adl {
+            devices.append(&mut adl.read_all());
+        }
+        devices
+    }
+}
+
+/// 运行时加载的动态库，Drop 时释放
+struct Library(HMODULE);
+
+// HMODULE 只是模块基址，加载后在进程内全局有效
+#[allow(unsafe_code)]
+unsafe impl Send for Library {}
+#[allow(unsafe_code)]
+unsafe impl Sync for Library {}
+
+#[allow(unsafe_code)]
+impl Library {
+    /// 按名称或完整路径加载 DLL
+    fn load(name: &str) -> Result<Self> {
+        let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        let module = unsafe { LoadLibraryW(PCWSTR(wide.as_ptr())) }.map_err(|e| {
+            NoCrateError::Gpu(i18n::tf(
+                Msg::GpuLibraryLoad,
+                &[("name", &name), ("error", &e)],
+            ))
+        })?;
+        Ok(Self(module))
+    }
+
+    /// 查找导出函数并转换为指定的函数指针类型
+    ///
+    /// # Safety
+    ///
+    /// `F` 必须是与导出函数签名一致的 `extern "C"` 函数指针类型。
+    unsafe fn symbol<F: Copy>(&self, name: &std::ffi::CStr) -> Result<F> {
+        let proc = unsafe { GetProcAddress(self.0, windows::core::PCSTR(name.as_ptr().cast())) }
+            .ok_or_else(|| {
+                NoCrateError::Gpu(i18n::tf(
+                    Msg::GpuSymbolMissing,
+                    &[("name", &name.to_string_lossy())],
+                ))
+            })?;
+        Ok(unsafe { std::mem::transmute_copy(&proc) })
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for Library {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = FreeLibrary(self.0);
+        }
+    }
+}
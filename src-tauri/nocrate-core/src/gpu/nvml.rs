@@ -0,0 +1,204 @@
+// NVIDIA NVML 后端
+// nvml.dll 随驱动安装在 System32，旧驱动位于 NVSMI 目录
+
+use std::ffi::{c_char, c_uint, c_void, CStr};
+
+use super::{GpuDeviceSnapshot, GpuReading, GpuSensorKind, Library};
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+/// NVML 库搜索路径（按顺序尝试）
+const NVML_PATHS: &[&str] = &[
+    "nvml.dll",
+    r"C:\Program Files\NVIDIA Corporation\NVSMI\nvml.dll",
+];
+
+const NVML_SUCCESS: i32 = 0;
+/// `NVML_TEMPERATURE_GPU`
+const NVML_TEMPERATURE_GPU: c_uint = 0;
+/// `nvmlDeviceGetName` 缓冲区长度
+const NVML_DEVICE_NAME_BUFFER_SIZE: usize = 96;
+
+/// `nvmlFanSpeedInfo_t`（R535+，`nvmlDeviceGetFanSpeedRPM`）
+#[repr(C)]
+struct NvmlFanSpeedInfo {
+    version: c_uint,
+    fan: c_uint,
+    speed: c_uint,
+}
+
+/// `nvmlFanSpeedInfo_v1` 版本号：sizeof | (1 << 24)
+const NVML_FAN_SPEED_INFO_V1: c_uint = std::mem::size_of::<NvmlFanSpeedInfo>() as c_uint | (1 << 24);
+
+type NvmlDevice = *mut c_void;
+
+type FnInit = unsafe extern "C" fn() -> i32;
+type FnShutdown = unsafe extern "C" fn() -> i32;
+type FnGetCount = unsafe extern "C" fn(*mut c_uint) -> i32;
+type FnGetHandle = unsafe extern "C" fn(c_uint, *mut NvmlDevice) -> i32;
+type FnGetName = unsafe extern "C" fn(NvmlDevice, *mut c_char, c_uint) -> i32;
+type FnGetTemperature = unsafe extern "C" fn(NvmlDevice, c_uint, *mut c_uint) -> i32;
+type FnGetNumFans = unsafe extern "C" fn(NvmlDevice, *mut c_uint) -> i32;
+type FnGetFanSpeed = unsafe extern "C" fn(NvmlDevice, c_uint, *mut c_uint) -> i32;
+type FnGetFanSpeedRpm = unsafe extern "C" fn(NvmlDevice, *mut NvmlFanSpeedInfo) -> i32;
+
+/// 已初始化的 NVML 实例
+pub struct Nvml {
+    shutdown: FnShutdown,
+    get_count: FnGetCount,
+    get_handle: FnGetHandle,
+    get_name: FnGetName,
+    get_temperature: FnGetTemperature,
+    get_num_fans: Option<FnGetNumFans>,
+    get_fan_speed: Option<FnGetFanSpeed>,
+    /// 新驱动才导出
+    get_fan_speed_rpm: Option<FnGetFanSpeedRpm>,
+    // 必须最后 drop：函数指针依赖库保持加载
+    _lib: Library,
+}
+
+#[allow(unsafe_code)]
+impl Nvml {
+    /// 加载 nvml.dll 并调用 `nvmlInit_v2`
+    pub fn load() -> Result<Self> {
+        let lib = NVML_PATHS
+            .iter()
+            .find_map(|p| Library::load(p).ok())
+            .ok_or_else(|| NoCrateError::Gpu(i18n::t(Msg::GpuNoNvml).into()))?;
+
+        unsafe {
+            // 先解析全部必需符号再初始化：初始化之后不再有提前返回，不会漏掉 nvmlShutdown
+            let init: FnInit = lib.symbol(c"nvmlInit_v2")?;
+            let shutdown: FnShutdown = lib.symbol(c"nvmlShutdown")?;
+            let get_count: FnGetCount = lib.symbol(c"nvmlDeviceGetCount_v2")?;
+            let get_handle: FnGetHandle = lib.symbol(c"nvmlDeviceGetHandleByIndex_v2")?;
+            let get_name: FnGetName = lib.symbol(c"nvmlDeviceGetName")?;
+            let get_temperature: FnGetTemperature = lib.symbol(c"nvmlDeviceGetTemperature")?;
+            check(init(), "nvmlInit_v2")?;
+
+            Ok(Self {
+                shutdown,
+                get_count,
+                get_handle,
+                get_name,
+                get_temperature,
+                get_num_fans: lib.symbol(c"nvmlDeviceGetNumFans").ok(),
+                get_fan_speed: lib.symbol(c"nvmlDeviceGetFanSpeed_v2").ok(),
+                get_fan_speed_rpm: lib.symbol(c"nvmlDeviceGetFanSpeedRPM").ok(),
+                _lib: lib,
+            })
+        }
+    }
+
+    /// 读取所有 NVIDIA GPU
+    pub fn read_all(&self) -> Result<Vec<GpuDeviceSnapshot>> {
+        let mut count: c_uint = 0;
+        unsafe { check((self.get_count)(&mut count), "nvmlDeviceGetCount_v2")? };
+
+        let mut devices = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let mut device: NvmlDevice = std::ptr::null_mut();
+            if unsafe { (self.get_handle)(index, &mut device) } != NVML_SUCCESS {
+                continue;
+            }
+            devices.push(GpuDeviceSnapshot {
+                index,
+                name: self.device_name(device),
+                backend: "nvml",
+                readings: self.device_readings(device),
+            });
+        }
+        Ok(devices)
+    }
+
+    fn device_name(&self, device: NvmlDevice) -> String {
+        let mut buf = [0 as c_char; NVML_DEVICE_NAME_BUFFER_SIZE];
+        let ok = unsafe { (self.get_name)(device, buf.as_mut_ptr(), buf.len() as c_uint) }
+            == NVML_SUCCESS;
+        if !ok {
+            return "NVIDIA GPU".to_string();
+        }
+        unsafe { CStr::from_ptr(buf.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn device_readings(&self, device: NvmlDevice) -> Vec<GpuReading> {
+        let mut readings = Vec::new();
+
+        let mut temp: c_uint = 0;
+        if unsafe { (self.get_temperature)(device, NVML_TEMPERATURE_GPU, &mut temp) }
+            == NVML_SUCCESS
+        {
+            readings.push(GpuReading {
+                name: "Core".to_string(),
+                kind: GpuSensorKind::Temperature,
+                value: temp as f32,
+            });
+        }
+
+        let mut num_fans: c_uint = 0;
+        let has_fans = self
+            .get_num_fans
+            .is_some_and(|f| unsafe { f(device, &mut num_fans) } == NVML_SUCCESS);
+        if !has_fans {
+            return readings;
+        }
+
+        for fan in 0..num_fans {
+            let name = format!("Fan {}", fan + 1);
+            if let Some(rpm) = self.fan_rpm(device, fan) {
+                readings.push(GpuReading {
+                    name,
+                    kind: GpuSensorKind::FanRpm,
+                    value: rpm as f32,
+                });
+                continue;
+            }
+
+            let mut percent: c_uint = 0;
+            if self
+                .get_fan_speed
+                .is_some_and(|f| unsafe { f(device, fan, &mut percent) } == NVML_SUCCESS)
+            {
+                readings.push(GpuReading {
+                    name,
+                    kind: GpuSensorKind::FanPercent,
+                    value: percent as f32,
+                });
+            }
+        }
+
+        readings
+    }
+
+    fn fan_rpm(&self, device: NvmlDevice, fan: c_uint) -> Option<c_uint> {
+        let get = self.get_fan_speed_rpm?;
+        let mut info = NvmlFanSpeedInfo {
+            version: NVML_FAN_SPEED_INFO_V1,
+            fan,
+            speed: 0,
+        };
+        (unsafe { get(device, &mut info) } == NVML_SUCCESS).then_some(info.speed)
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for Nvml {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = (self.shutdown)();
+        }
+    }
+}
+
+fn check(ret: i32, func: &str) -> Result<()> {
+    if ret == NVML_SUCCESS {
+        Ok(())
+    } else {
+        Err(NoCrateError::Gpu(i18n::tf(
+            Msg::GpuCallError,
+            &[("func", &func), ("code", &ret)],
+        )))
+    }
+}
@@ -0,0 +1,221 @@
+// HWiNFO 共享内存传感器读取
+// HWiNFO 在「Shared Memory Support」开启时发布 `Global\HWiNFO_SENS_SM2` 映射，
+// 包含传感器（设备）与读数两张表。布局来自 HWiNFO SDK（`#pragma pack(1)`），
+// 元素大小以头部给出的值为准，以兼容后续版本在元素末尾追加字段。
+// 每次读取时重新打开映射：HWiNFO 可能随时启动或退出。
+
+use serde::Serialize;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::CloseHandle;
+use windows::Win32::System::Memory::{
+    MapViewOfFile, OpenFileMappingW, UnmapViewOfFile, FILE_MAP_READ,
+};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+const MAPPING_NAME: &str = r"Global\HWiNFO_SENS_SM2";
+
+/// 头部签名 `HWiS`（HWiNFO 关闭共享内存时改为 `DEAD`）
+const SIGNATURE: u32 = 0x5369_5748;
+
+/// 头部大小：签名、版本、修订号、`poll_time`(8)、六个偏移 / 大小 / 数量字段
+const HEADER_SIZE: usize = 44;
+
+/// 字符串字段长度
+const STRING_LEN: usize = 128;
+const UNIT_LEN: usize = 16;
+
+/// 传感器元素字段偏移
+const SENSOR_NAME_USER: usize = 8 + STRING_LEN;
+/// v2 新增的 UTF-8 用户名称
+const SENSOR_NAME_USER_UTF8: usize = SENSOR_NAME_USER + STRING_LEN;
+
+/// 读数元素字段偏移
+const READING_SENSOR_INDEX: usize = 4;
+const READING_ID: usize = 8;
+const READING_LABEL_USER: usize = 12 + STRING_LEN;
+const READING_UNIT: usize = READING_LABEL_USER + STRING_LEN;
+const READING_VALUE: usize = READING_UNIT + UNIT_LEN;
+const READING_MIN: usize = READING_VALUE + 8;
+const READING_MAX: usize = READING_MIN + 8;
+/// v2 新增的 UTF-8 标签与单位
+const READING_LABEL_USER_UTF8: usize = READING_MAX + 16;
+const READING_UNIT_UTF8: usize = READING_LABEL_USER_UTF8 + STRING_LEN;
+
+/// 读数类型（`SENSOR_READING_TYPE`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HwinfoReadingType {
+    Temperature,
+    Voltage,
+    Fan,
+    Current,
+    Power,
+    Clock,
+    Usage,
+    Other,
+}
+
+impl HwinfoReadingType {
+    const fn from_raw(raw: u32) -> Option<Self> {
+        Some(match raw {
+            1 => Self::Temperature,
+            2 => Self::Voltage,
+            3 => Self::Fan,
+            4 => Self::Current,
+            5 => Self::Power,
+            6 => Self::Clock,
+            7 => Self::Usage,
+            8 => Self::Other,
+            _ => return None,
+        })
+    }
+}
+
+/// HWiNFO 中的一个传感器（设备），如 CPU、主板 SIO 芯片、显卡
+#[derive(Debug, Clone, Serialize)]
+pub struct HwinfoSensor {
+    /// 设备 ID 与实例号，组合后在同一台机器上保持稳定
+    pub id: u32,
+    pub instance: u32,
+    pub name: String,
+}
+
+/// 单个读数
+#[derive(Debug, Clone, Serialize)]
+pub struct HwinfoReading {
+    pub reading_type: HwinfoReadingType,
+    /// 所属传感器在 `sensors` 中的下标
+    pub sensor_index: usize,
+    /// 传感器内的读数 ID
+    pub id: u32,
+    pub label: String,
+    pub unit: String,
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// 共享内存快照
+#[derive(Debug, Clone, Serialize)]
+pub struct HwinfoSnapshot {
+    pub version: u32,
+    pub sensors: Vec<HwinfoSensor>,
+    pub readings: Vec<HwinfoReading>,
+}
+
+/// 读取 HWiNFO 共享内存
+///
+/// # Errors
+///
+/// HWiNFO 未运行或未开启共享内存支持时返回错误
+pub fn read_all() -> Result<HwinfoSnapshot> {
+    parse(&copy_mapping()?)
+}
+
+/// 复制整个映射区域，随后在副本上解析，避免长时间持有映射
+#[allow(unsafe_code)]
+fn copy_mapping() -> Result<Vec<u8>> {
+    let name: Vec<u16> = MAPPING_NAME.encode_utf16().chain(Some(0)).collect();
+    unsafe {
+        let mapping = OpenFileMappingW(FILE_MAP_READ.0, false, PCWSTR(name.as_ptr()))
+            .map_err(|_| NoCrateError::Unknown(i18n::t(Msg::HwinfoNotRunning).into()))?;
+        let view = MapViewOfFile(mapping, FILE_MAP_READ, 0, 0, 0);
+        if view.Value.is_null() {
+            let _ = CloseHandle(mapping);
+            return Err(NoCrateError::Unknown(i18n::t(Msg::HwinfoMap).into()));
+        }
+        let base = view.Value.cast::<u8>();
+
+        // 先读头部，得到两张表的范围后再复制全部数据
+        let header = std::slice::from_raw_parts(base, HEADER_SIZE);
+        let len = table_end(header);
+        let data = std::slice::from_raw_parts(base, len.max(HEADER_SIZE)).to_vec();
+
+        let _ = UnmapViewOfFile(view);
+        let _ = CloseHandle(mapping);
+        Ok(data)
+    }
+}
+
+/// 头部描述的数据末尾位置
+fn table_end(header: &[u8]) -> usize {
+    let field = |offset| u32_at(header, offset).unwrap_or(0) as usize;
+    let sensors = field(20) + field(24) * field(28);
+    let readings = field(32) + field(36) * field(40);
+    sensors.max(readings)
+}
+
+fn parse(data: &[u8]) -> Result<HwinfoSnapshot> {
+    if u32_at(data, 0) != Some(SIGNATURE) {
+        return Err(NoCrateError::Unknown(i18n::t(Msg::HwinfoClosed).into()));
+    }
+    let field = |offset| u32_at(data, offset).unwrap_or(0) as usize;
+    let version = u32_at(data, 4).unwrap_or(0);
+
+    let sensors = elements(data, field(20), field(24), field(28))
+        .map(|e| HwinfoSensor {
+            id: u32_at(e, 0).unwrap_or(0),
+            instance: u32_at(e, 4).unwrap_or(0),
+            name: text(e, SENSOR_NAME_USER_UTF8, SENSOR_NAME_USER, STRING_LEN),
+        })
+        .collect();
+
+    let readings = elements(data, field(32), field(36), field(40))
+        .filter_map(|e| {
+            Some(HwinfoReading {
+                reading_type: HwinfoReadingType::from_raw(u32_at(e, 0)?)?,
+                sensor_index: u32_at(e, READING_SENSOR_INDEX)? as usize,
+                id: u32_at(e, READING_ID)?,
+                label: text(e, READING_LABEL_USER_UTF8, READING_LABEL_USER, STRING_LEN),
+                unit: text(e, READING_UNIT_UTF8, READING_UNIT, UNIT_LEN),
+                value: f64_at(e, READING_VALUE)?,
+                min: f64_at(e, READING_MIN)?,
+                max: f64_at(e, READING_MAX)?,
+            })
+        })
+        .collect();
+
+    Ok(HwinfoSnapshot {
+        version,
+        sensors,
+        readings,
+    })
+}
+
+/// 按头部给出的偏移 / 元素大小 / 数量切分表格，越界部分忽略
+fn elements(data: &[u8], offset: usize, size: usize, count: usize) -> impl Iterator<Item = &[u8]> {
+    let table = data.get(offset..).unwrap_or_default();
+    let count = if size == 0 { 0 } else { count };
+    table.chunks_exact(size.max(1)).take(count)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn f64_at(data: &[u8], offset: usize) -> Option<f64> {
+    Some(f64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+/// 读取以 NUL 结尾的字符串：优先 v2 的 UTF-8 字段，否则按 Latin-1 解码旧字段
+fn text(element: &[u8], utf8_offset: usize, legacy_offset: usize, len: usize) -> String {
+    let field = |offset: usize| {
+        let bytes = element.get(offset..offset + len)?;
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(len);
+        Some(&bytes[..end]).filter(|b| !b.is_empty())
+    };
+    if let Some(s) = field(utf8_offset).and_then(|b| std::str::from_utf8(b).ok()) {
+        return s.trim().to_string();
+    }
+    field(legacy_offset)
+        .map(|b| b.iter().map(|&c| char::from(c)).collect::<String>())
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,823 @@
+// 后端文本本地化
+// 错误信息、子系统修复建议、内置传感器名称与托盘菜单等后端生成的文本按消息键查表，
+// 每种语言一份目录（`zh_cn` / `en`）。当前语言由应用在加载或修改配置时经 `set_locale` 设置，
+// 错误的 Display 与序列化、日志与事件都使用同一语言
+//
+// 目录中的 `{name}` 为占位符，由 `tf` 按名称替换
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en")]
+    En,
+}
+
+/// 当前语言，`Locale` 的判别值
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::ZhCn as u8);
+
+/// 设置后端文本使用的语言
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// 当前语言
+#[must_use]
+pub fn locale() -> Locale {
+    if LOCALE.load(Ordering::Relaxed) == Locale::En as u8 {
+        Locale::En
+    } else {
+        Locale::ZhCn
+    }
+}
+
+/// 消息键
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // 错误类别，占位符 {detail}
+    ErrWmi,
+    ErrWindowsApi,
+    ErrHid,
+    ErrConfig,
+    ErrSio,
+    ErrStorage,
+    ErrGpu,
+    ErrDriverBlocked,
+    ErrAccessDenied,
+    ErrDeviceNotPresent,
+    ErrTimeout,
+    ErrBusy,
+    ErrInvalidCurve,
+    ErrUnknown,
+
+    // 子系统未就绪
+    StillInitializing,
+    WmiNotInitialized,
+    SioNotInitialized,
+    SioNoAccess,
+    EcNotInitialized,
+    CpuNotInitialized,
+    DriverNotInstalled,
+    StorageNotInitialized,
+    GpuNotInitialized,
+    ConfigNotLoaded,
+
+    // WinRing0 驱动错误详情，占位符见各条目
+    DriverFileMissing,
+    DriverPathInvalid,
+    DriverScmOpen,
+    DriverServiceAccess,
+    DriverServiceCreate,
+    DriverServiceStart,
+    DriverForeignService,
+    DriverDeviceOpen,
+    DriverInvalidHandle,
+    DriverSimulated,
+    DriverHeldByService,
+    DriverPortRead,
+    DriverPortWrite,
+    DriverMsrRead,
+    DriverVersionRead,
+    DriverDirCreate,
+    DriverDirOpen,
+    DriverDirNotPlain,
+    DriverDirSecure,
+    DriverFileRemove,
+    DriverFileWrite,
+    DriverFileRead,
+    DriverFileQuery,
+    DriverFileSecure,
+    DriverFileMismatch,
+    DriverHash,
+
+    // SMBus 错误详情
+    SmbusNotFound,
+    SmbusBusy,
+    SmbusInUse,
+    SmbusTimeout,
+    SmbusFailed,
+
+    // Super I/O 与 LPC 错误详情，占位符见各条目
+    SioRequiresDriver,
+    SioReadIte,
+    SioClearIntrusionLatch,
+    SioNoIntrusion,
+    SioNoDump,
+    SioChipNotFound,
+    SioRemoteOutOfRange,
+    SioPolicyDenied,
+    SioTraceMissing,
+    LpcBridgeNotFound,
+    LpcDecodeRangesFull,
+
+    // EC 与 CPU 错误详情
+    EcBoardUnknown,
+    EcBoardUnsupported,
+    EcInputTimeout,
+    EcOutputTimeout,
+    EcMutexCreate,
+    EcBusy,
+    CpuAmdFamily,
+    CpuVendor,
+
+    // 显卡、存储设备与 HWiNFO 错误详情
+    GpuNoDriver,
+    GpuNoAmd,
+    GpuNoNvml,
+    GpuLibraryLoad,
+    GpuSymbolMissing,
+    GpuCallFailed,
+    GpuCallError,
+    StorageNone,
+    StorageDescriptorShort,
+    StorageNvmeOffset,
+    StorageNvmeNoTemp,
+    StorageSmartNoTemp,
+    StorageOpen,
+    StorageIoctl,
+    HwinfoNotRunning,
+    HwinfoMap,
+    HwinfoClosed,
+
+    // WMI 错误详情，占位符见各条目
+    WmiNoInterface,
+    WmiWrongBackend,
+    WmiDesktopOnly,
+    WmiAsusHwUnsupported,
+    WmiFanMethodFailed,
+    WmiCurveMethodFailed,
+    WmiPropertyConvert,
+    WmiPropertyNotI32,
+    WmiLhmNotConnected,
+
+    // AURA、OpenRGB 与软件灯效错误详情
+    AuraChannelMissing,
+    AuraDeviceMissing,
+    AuraNotAvailable,
+    AuraNotFound,
+    AuraDeviceRange,
+    AuraDramNotAvailable,
+    AuraDramRange,
+    AuraStreamNotStarted,
+    AuraStreamFps,
+    AudioMixFormat,
+    OpenRgbPassthrough,
+    OpenRgbResolve,
+    OpenRgbNotConnected,
+    OpenRgbIo,
+    OpenRgbBadMagic,
+    OpenRgbPacketTooLarge,
+    OpenRgbTruncated,
+    OpenRgbTooManyLeds,
+    OpenRgbDeviceRange,
+    OpenRgbZoneRange,
+    EffectTempRange,
+    EffectSensitivity,
+    EffectSmoothing,
+    ThreadSpawn,
+    ThreadInitDied,
+
+    // 风扇分组、校准与诊断扫描，占位符见各条目
+    FanHeaderMissing,
+    FanCalibrating,
+    FanGroupNameEmpty,
+    FanGroupEmpty,
+    FanGroupTaken,
+    FanGroupUnknown,
+    ScanRangeInvalid,
+    ScanTooLarge,
+    ScanRunning,
+
+    // 配置、文件与外部程序，占位符见各条目
+    ConfigDirCreate,
+    ConfigAlreadyLoaded,
+    ConfigRead,
+    ConfigParse,
+    ConfigSerialize,
+    ConfigWrite,
+    ConfigBackupIndex,
+    ConfigBackupRead,
+    ConfigBackupInvalid,
+    FileRead,
+    FileWrite,
+    TraceInvalid,
+    SubsystemUnknown,
+    WmiTaskFailed,
+    ApiTokenFailed,
+    LhmNotInstalled,
+    LhmStart,
+    RtssNotRunning,
+    UpdateVersionInvalid,
+    UpdateResponseInvalid,
+    UpdateNoRelease,
+
+    // 命令行管道与 NoCrate 服务，占位符见各条目
+    AppNotRunning,
+    AppStarting,
+    AppBusy,
+    AppConnect,
+    AppReplyInvalid,
+    PipeSend,
+    PipeRead,
+    RequestInvalid,
+    ServiceNotRunning,
+    ServiceNotStarted,
+    ServiceConnect,
+    ServiceBusy,
+    ServiceUnexpectedReply,
+    ServiceVersion,
+    ServiceNoWmi,
+    ServiceWmiLost,
+    ServiceNoDriver,
+    ServiceForeignObject,
+    ServiceExitCode,
+    FileMissing,
+
+    // 管理员提权与开机自启（注册表）
+    ExePathUnknown,
+    ElevationCancelled,
+    AutostartRegistryWrite,
+    AutostartRegistryDelete,
+
+    // 开机自启（计划任务）
+    AutostartUserUnknown,
+    AutostartTaskCreate,
+    AutostartTaskDelete,
+
+    // 子系统修复建议
+    HintRunAsAdmin,
+    HintDriverBlocked,
+    HintBusy,
+    HintTimeout,
+    HintWmiDriver,
+    HintLhm,
+    HintAura,
+    HintGpu,
+
+    // 子系统状态详情
+    WmiReconnecting,
+    LhmSensorCount,
+    LhmNoSensors,
+    AuraOpenRgb,
+    AuraControllerCount,
+
+    // 风扇曲线违规，占位符见各条目
+    CurveTempNotAscending,
+    CurveDutyDescending,
+    CurveTempOutOfRange,
+    CurveDutyOutOfRange,
+    CurveBelowStallDuty,
+    CurveNoTopPoint,
+    CurveEmpty,
+
+    // 托盘菜单
+    TrayShow,
+    TrayFanProfile,
+    TrayProfileStandard,
+    TrayProfilePerformance,
+    TrayProfileSilent,
+    TrayOverlay,
+    TrayQuit,
+    TrayTooltip,
+}
+
+/// 当前语言下的文本
+#[must_use]
+pub fn t(msg: Msg) -> &'static str {
+    match locale() {
+        Locale::ZhCn => zh_cn(msg),
+        Locale::En => en(msg),
+    }
+}
+
+/// 当前语言下的文本，`{name}` 占位符替换为 `args` 中同名的值
+#[must_use]
+pub fn tf(msg: Msg, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = t(msg).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    text
+}
+
+/// 内置传感器名称（芯片表与 EC 表中的中文名）在当前语言下的写法
+/// 用户别名等其他名称原样返回；带编号的名称（如 `机箱 #2`）只替换编号前的部分
+#[must_use]
+pub fn sensor_label(name: &str) -> Cow<'_, str> {
+    if locale() == Locale::ZhCn {
+        return Cow::Borrowed(name);
+    }
+    for &(zh, en) in SENSOR_LABELS {
+        if let Some(rest) = name.strip_prefix(zh) {
+            if rest.is_empty() || rest.starts_with(' ') {
+                return Cow::Owned(format!("{en}{rest}"));
+            }
+        }
+    }
+    Cow::Borrowed(name)
+}
+
+/// 内置传感器名称：`(中文, 英文)`，较长的名称在前，避免被其前缀先匹配
+const SENSOR_LABELS: &[(&str, &str)] = &[
+    ("VRM 散热风扇", "VRM Fan"),
+    ("芯片组风扇", "Chipset Fan"),
+    ("CPU 电流", "CPU Current"),
+    ("水冷流量", "Coolant Flow"),
+    ("水冷入口", "Coolant Inlet"),
+    ("水冷出口", "Coolant Outlet"),
+    ("芯片组", "Chipset"),
+    ("主板", "Motherboard"),
+    ("辅助", "Auxiliary"),
+    ("外部", "External"),
+    ("机箱", "Chassis"),
+];
+
+const fn zh_cn(msg: Msg) -> &'static str {
+    match msg {
+        Msg::ErrWmi => "WMI 错误：{detail}",
+        Msg::ErrWindowsApi => "Windows API 错误：{detail}",
+        Msg::ErrHid => "HID 错误：{detail}",
+        Msg::ErrConfig => "配置错误：{detail}",
+        Msg::ErrSio => "SIO 错误：{detail}",
+        Msg::ErrStorage => "存储设备错误：{detail}",
+        Msg::ErrGpu => "显卡错误：{detail}",
+        Msg::ErrDriverBlocked => "驱动被阻止：{detail}",
+        Msg::ErrAccessDenied => "拒绝访问：{detail}",
+        Msg::ErrDeviceNotPresent => "设备不存在：{detail}",
+        Msg::ErrTimeout => "超时：{detail}",
+        Msg::ErrBusy => "设备忙：{detail}",
+        Msg::ErrInvalidCurve => "风扇曲线无效：{detail}",
+        Msg::ErrUnknown => "未知错误：{detail}",
+
+        Msg::StillInitializing => "{what}（仍在初始化）",
+        Msg::WmiNotInitialized => "WMI 未初始化",
+        Msg::SioNotInitialized => "SIO 未初始化",
+        Msg::SioNoAccess => "SIO 未初始化，WinRing0 驱动与 WMI 均不可用",
+        Msg::EcNotInitialized => "EC 未初始化",
+        Msg::CpuNotInitialized => "CPU 温度读取未初始化",
+        Msg::DriverNotInstalled => "WinRing0 驱动未安装，可在设置中安装",
+        Msg::StorageNotInitialized => "存储设备温度未初始化",
+        Msg::GpuNotInitialized => "GPU 传感器未初始化",
+        Msg::ConfigNotLoaded => "配置文件路径未初始化",
+
+        Msg::DriverFileMissing => "找不到驱动文件: {path}",
+        Msg::DriverPathInvalid => "无法解析驱动路径: {error}",
+        Msg::DriverScmOpen => "无法打开服务控制管理器: {error}",
+        Msg::DriverServiceAccess => "无法访问驱动服务: {error}",
+        Msg::DriverServiceCreate => "无法创建驱动服务: {error}",
+        Msg::DriverServiceStart => "无法启动驱动服务: {error}",
+        Msg::DriverForeignService => "驱动服务由其他软件注册（{path}），无法启动: {error}",
+        Msg::DriverDeviceOpen => "无法打开驱动设备: {error}",
+        Msg::DriverInvalidHandle => "打开驱动设备返回无效句柄",
+        Msg::DriverSimulated => "模拟模式下没有 WinRing0 驱动",
+        Msg::DriverHeldByService => "驱动由 NoCrate 服务持有",
+        Msg::DriverPortRead => "读取 I/O 端口 {port} 失败: {error}",
+        Msg::DriverPortWrite => "写入 I/O 端口 {port} 失败: {error}",
+        Msg::DriverMsrRead => "读取 MSR {index} 失败: {error}",
+        Msg::DriverVersionRead => "读取驱动版本失败: {error}",
+        Msg::DriverDirCreate => "无法创建驱动目录 {path}: {error}",
+        Msg::DriverDirOpen => "无法打开驱动目录 {path}: {error}",
+        Msg::DriverDirNotPlain => "驱动目录不是普通目录（可能是联接或符号链接）: {path}",
+        Msg::DriverDirSecure => "无法设置驱动目录权限 {path}: {error}",
+        Msg::DriverFileRemove => "无法删除旧的驱动文件 {path}: {error}",
+        Msg::DriverFileWrite => "无法释放驱动文件 {path}: {error}",
+        Msg::DriverFileRead => "无法读取驱动文件 {path}: {error}",
+        Msg::DriverFileQuery => "无法查询 {path}: {error}",
+        Msg::DriverFileSecure => "无法设置驱动文件权限 {path}: {error}",
+        Msg::DriverFileMismatch => "释放的驱动文件校验失败: {path}",
+        Msg::DriverHash => "计算驱动哈希失败: {error}",
+
+        Msg::SmbusNotFound => "未找到 Intel / AMD SMBus 控制器",
+        Msg::SmbusBusy => "SMBus 控制器忙",
+        Msg::SmbusInUse => "SMBus 被其他程序占用",
+        Msg::SmbusTimeout => "SMBus 事务超时 (地址 {addr})",
+        Msg::SmbusFailed => "SMBus 事务失败 (地址 {addr}, 状态 {status})",
+
+        Msg::SioRequiresDriver => "{what}需要 WinRing0 驱动，当前经 {access} 访问",
+        Msg::SioReadIte => "读取 ITE 芯片",
+        Msg::SioClearIntrusionLatch => "清除机箱入侵锁存位",
+        Msg::SioNoIntrusion => "{chip} 不支持机箱入侵检测",
+        Msg::SioNoDump => "{chip} 不支持寄存器转储",
+        Msg::SioChipNotFound => "未检测到已支持的 Super I/O 芯片（Nuvoton NCT67xx / ITE IT86xx）",
+        Msg::SioRemoteOutOfRange => "远程返回的值越界: {value}",
+        Msg::SioPolicyDenied => "NoCrate 服务不执行此访问: {op}",
+        Msg::SioTraceMissing => "录制中没有寄存器读取 {register}",
+        Msg::LpcBridgeNotFound => "未找到 AMD FCH 或 Intel PCH 的 LPC/eSPI 桥",
+        Msg::LpcDecodeRangesFull => "所有 I/O 解码范围已用尽，无法为 HW Monitor 添加 ISA 解码",
+
+        Msg::EcBoardUnknown => "无法读取主板型号 (BaseBoardProduct)",
+        Msg::EcBoardUnsupported => "主板 {board} 不在 EC 传感器表中",
+        Msg::EcInputTimeout => "等待 EC 输入缓冲区超时",
+        Msg::EcOutputTimeout => "等待 EC 输出缓冲区超时",
+        Msg::EcMutexCreate => "无法创建 EC 互斥体: {error}",
+        Msg::EcBusy => "EC 正被其他程序占用",
+        Msg::CpuAmdFamily => "不支持的 AMD CPU 家族 {family}",
+        Msg::CpuVendor => "不支持的 CPU 厂商: {vendor}",
+
+        Msg::GpuNoDriver => "未找到 NVIDIA / AMD 显卡驱动接口",
+        Msg::GpuNoAmd => "未找到 AMD 显卡",
+        Msg::GpuNoNvml => "未找到 nvml.dll",
+        Msg::GpuLibraryLoad => "无法加载 {name}: {error}",
+        Msg::GpuSymbolMissing => "缺少导出函数 {name}",
+        Msg::GpuCallFailed => "{func} 失败",
+        Msg::GpuCallError => "{func} 返回错误码 {code}",
+        Msg::StorageNone => "未找到可读取温度的存储设备",
+        Msg::StorageDescriptorShort => "设备描述符长度不足",
+        Msg::StorageNvmeOffset => "NVMe 日志页偏移无效",
+        Msg::StorageNvmeNoTemp => "NVMe 未报告温度",
+        Msg::StorageSmartNoTemp => "SMART 数据中没有温度属性",
+        Msg::StorageOpen => "无法打开 PhysicalDrive{index}: {error}",
+        Msg::StorageIoctl => "IOCTL {code} 失败: {error}",
+        Msg::HwinfoNotRunning => "HWiNFO 未运行或未开启共享内存支持",
+        Msg::HwinfoMap => "无法映射 HWiNFO 共享内存",
+        Msg::HwinfoClosed => "HWiNFO 共享内存已关闭",
+
+        Msg::WmiNoInterface => "未找到支持的 ASUS WMI 接口 (ASUSManagement / ASUSATKWMI_WMNB / ASUSHW)",
+        Msg::WmiWrongBackend => "当前后端不是 {backend}",
+        Msg::WmiDesktopOnly => "{method} 仅在 Desktop 后端可用",
+        Msg::WmiAsusHwUnsupported => "ASUSHW 后端不支持 {method} 操作",
+        Msg::WmiFanMethodFailed => "{method} 失败: FanType={fan}, ErrorCode={code}",
+        Msg::WmiCurveMethodFailed => "{method} 失败: FanType={fan}, Mode={mode}, ErrorCode={code}",
+        Msg::WmiPropertyConvert => "属性 {name} 无法转换为数值类型: {error}",
+        Msg::WmiPropertyNotI32 => "属性 {name} 转换后仍非 i32",
+        Msg::WmiLhmNotConnected => "LibreHardwareMonitor 未连接",
+
+        Msg::AuraChannelMissing => "{product} (PID {pid}) 上没有通道 {channel}",
+        Msg::AuraDeviceMissing => "设备 {device} 不存在",
+        Msg::AuraNotAvailable => "AURA 控制器不可用",
+        Msg::AuraNotFound => "未找到 AURA 控制器",
+        Msg::AuraDeviceRange => "AURA 设备序号 {index} 越界（共 {count} 个）",
+        Msg::AuraDramNotAvailable => "AURA 内存灯效不可用",
+        Msg::AuraDramRange => "AURA 内存序号 {index} 越界（共 {count} 条）",
+        Msg::AuraStreamNotStarted => "AURA 实时推流未启动",
+        Msg::AuraStreamFps => "推流帧率必须为正数",
+        Msg::AudioMixFormat => "不支持的音频混音格式",
+        Msg::OpenRgbPassthrough => "已启用 OpenRGB 直通，AURA 由 OpenRGB 控制",
+        Msg::OpenRgbResolve => "OpenRGB: 无法解析 {host}",
+        Msg::OpenRgbNotConnected => "OpenRGB: 未连接",
+        Msg::OpenRgbIo => "OpenRGB: {error}",
+        Msg::OpenRgbBadMagic => "OpenRGB: 数据包标识错误",
+        Msg::OpenRgbPacketTooLarge => "OpenRGB: 数据包过大 ({len})",
+        Msg::OpenRgbTruncated => "OpenRGB: 数据包不完整",
+        Msg::OpenRgbTooManyLeds => "OpenRGB: LED 数量过多 ({count})",
+        Msg::OpenRgbDeviceRange => "OpenRGB: 设备序号 {device} 越界",
+        Msg::OpenRgbZoneRange => "OpenRGB: 区域 {zone} 越界（{device} 共 {zones} 个区域）",
+        Msg::EffectTempRange => "温度范围无效: {min} >= {max}",
+        Msg::EffectSensitivity => "灵敏度必须为正数，当前为 {value}",
+        Msg::EffectSmoothing => "平滑系数必须在 0.0..1.0 之间，当前为 {value}",
+        Msg::ThreadSpawn => "无法创建线程 {name}: {error}",
+        Msg::ThreadInitDied => "线程 {name} 在初始化时退出",
+
+        Msg::FanHeaderMissing => "风扇接口 {fan} 不存在",
+        Msg::FanCalibrating => "风扇接口 {fan} 正在校准",
+        Msg::FanGroupNameEmpty => "风扇分组名称为空",
+        Msg::FanGroupEmpty => "风扇分组 {name} 没有风扇接口",
+        Msg::FanGroupTaken => "风扇接口 {fan} 已在分组 {group} 中",
+        Msg::FanGroupUnknown => "未知的风扇分组: {name}",
+        Msg::ScanRangeInvalid => "扫描范围无效: {start}..{end}",
+        Msg::ScanTooLarge => "扫描覆盖 {total} 个 ID，最多允许 {max} 个",
+        Msg::ScanRunning => "已有设备扫描在进行",
+
+        Msg::ConfigDirCreate => "无法创建配置目录: {error}",
+        Msg::ConfigAlreadyLoaded => "配置文件路径已初始化",
+        Msg::ConfigRead => "读取配置文件失败: {error}",
+        Msg::ConfigParse => "配置文件解析失败: {error}",
+        Msg::ConfigSerialize => "序列化配置失败: {error}",
+        Msg::ConfigWrite => "写入配置文件失败: {error}",
+        Msg::ConfigBackupIndex => "备份序号 {index} 越界（1-{count}）",
+        Msg::ConfigBackupRead => "读取备份 {index} 失败: {error}",
+        Msg::ConfigBackupInvalid => "备份 {index} 无效: {error}",
+        Msg::FileRead => "读取 {path} 失败: {error}",
+        Msg::FileWrite => "写入 {path} 失败: {error}",
+        Msg::TraceInvalid => "录制文件 {path} 无效: {error}",
+        Msg::SubsystemUnknown => "未知的子系统: {name}",
+        Msg::WmiTaskFailed => "WMI 请求任务失败: {error}",
+        Msg::ApiTokenFailed => "生成 API 令牌失败: {error}",
+        Msg::LhmNotInstalled => "未安装 LibreHardwareMonitor",
+        Msg::LhmStart => "无法启动 {path}: {error}",
+        Msg::RtssNotRunning => "RTSS 未运行",
+        Msg::UpdateVersionInvalid => "无效的程序版本 {version}",
+        Msg::UpdateResponseInvalid => "GitHub 返回的数据无效: {error}",
+        Msg::UpdateNoRelease => "未找到已发布的版本",
+
+        Msg::AppNotRunning => "NoCrate 未运行",
+        Msg::AppStarting => "NoCrate 仍在启动",
+        Msg::AppBusy => "NoCrate 正忙，请重试",
+        Msg::AppConnect => "无法连接到 NoCrate: {error}",
+        Msg::AppReplyInvalid => "NoCrate 返回的应答无效: {error}",
+        Msg::PipeSend => "发送请求失败: {error}",
+        Msg::PipeRead => "读取应答失败: {error}",
+        Msg::RequestInvalid => "无效的请求: {error}",
+        Msg::ServiceNotRunning => "NoCrate 服务未运行",
+        Msg::ServiceNotStarted => "未以服务方式启动。\n\n{usage}",
+        Msg::ServiceConnect => "无法连接到 NoCrate 服务: {error}",
+        Msg::ServiceBusy => "NoCrate 服务正忙",
+        Msg::ServiceUnexpectedReply => "NoCrate 服务返回了意外的应答",
+        Msg::ServiceVersion => "NoCrate 服务版本为 {version}，请重新安装 {expected} 版",
+        Msg::ServiceNoWmi => "NoCrate 服务未找到 ASUS WMI 接口",
+        Msg::ServiceWmiLost => "NoCrate 服务没有 WMI 连接",
+        Msg::ServiceNoDriver => "NoCrate 服务未能加载 WinRing0",
+        Msg::ServiceForeignObject => "NoCrate 服务不调用 {path} 上的方法",
+        Msg::ServiceExitCode => "{name} 退出码为 {code}",
+        Msg::FileMissing => "未找到 {path}",
+
+        Msg::ExePathUnknown => "无法获取当前程序路径: {error}",
+        Msg::ElevationCancelled => "用户取消了管理员提权请求",
+        Msg::AutostartRegistryWrite => "写入注册表失败: {error}",
+        Msg::AutostartRegistryDelete => "删除注册表项失败: {error}",
+
+        Msg::AutostartUserUnknown => "无法确定当前用户",
+        Msg::AutostartTaskCreate => "创建计划任务失败: {error}",
+        Msg::AutostartTaskDelete => "删除计划任务失败: {error}",
+
+        Msg::HintRunAsAdmin => "以管理员身份重新启动 NoCrate",
+        Msg::HintDriverBlocked => {
+            "Windows 阻止了 WinRing0 驱动（易受攻击驱动阻止列表或内存完整性），可改用 LibreHardwareMonitor 读取传感器"
+        }
+        Msg::HintBusy => "关闭占用该设备的其他程序（如 Armoury Crate）后重试",
+        Msg::HintTimeout => "稍后重试；若持续超时，重启 Windows Management Instrumentation 服务",
+        Msg::HintWmiDriver => "确认已安装 ASUS 主板驱动（ATK Package / ASUS System Control Interface）",
+        Msg::HintLhm => "安装并以管理员身份运行 LibreHardwareMonitor，或在设置中启动它",
+        Msg::HintAura => "确认主板或外设带有 AURA 控制器，且未被其他灯效软件独占",
+        Msg::HintGpu => "安装 NVIDIA 或 AMD 显卡驱动",
+
+        Msg::WmiReconnecting => "WMI 连接中断，正在自动重连",
+        Msg::LhmSensorCount => "{count} 个传感器",
+        Msg::LhmNoSensors => "LibreHardwareMonitor 未报告任何传感器",
+        Msg::AuraOpenRgb => "由 OpenRGB 接管",
+        Msg::AuraControllerCount => "{count} 个控制器",
+
+        Msg::CurveTempNotAscending => "第 {point} 点温度 {temp} °C 不高于前一点的 {prev} °C",
+        Msg::CurveDutyDescending => "第 {point} 点占空比 {duty}% 低于前一点的 {prev}%",
+        Msg::CurveTempOutOfRange => "第 {point} 点温度 {temp} °C 超过 {max} °C",
+        Msg::CurveDutyOutOfRange => "第 {point} 点占空比 {duty}% 超过 {max}%",
+        Msg::CurveBelowStallDuty => "第 {point} 点占空比 {duty}% 低于风扇停转点 {min}%",
+        Msg::CurveNoTopPoint => "最后一点温度 {temp} °C 低于 {min} °C，曲线未覆盖高温区间",
+        Msg::CurveEmpty => "曲线没有任何点",
+
+        Msg::TrayShow => "显示主窗口",
+        Msg::TrayFanProfile => "风扇配置",
+        Msg::TrayProfileStandard => "标准模式",
+        Msg::TrayProfilePerformance => "性能模式",
+        Msg::TrayProfileSilent => "静音模式",
+        Msg::TrayOverlay => "显示/隐藏叠加层",
+        Msg::TrayQuit => "退出",
+        Msg::TrayTooltip => "NoCrate — ASUS 主板控制",
+    }
+}
+
+const fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::ErrWmi => "WMI error: {detail}",
+        Msg::ErrWindowsApi => "Windows API error: {detail}",
+        Msg::ErrHid => "HID error: {detail}",
+        Msg::ErrConfig => "Config error: {detail}",
+        Msg::ErrSio => "SIO error: {detail}",
+        Msg::ErrStorage => "Storage error: {detail}",
+        Msg::ErrGpu => "GPU error: {detail}",
+        Msg::ErrDriverBlocked => "Driver blocked: {detail}",
+        Msg::ErrAccessDenied => "Access denied: {detail}",
+        Msg::ErrDeviceNotPresent => "Device not present: {detail}",
+        Msg::ErrTimeout => "Timeout: {detail}",
+        Msg::ErrBusy => "Busy: {detail}",
+        Msg::ErrInvalidCurve => "Invalid fan curve: {detail}",
+        Msg::ErrUnknown => "Unknown error: {detail}",
+
+        Msg::StillInitializing => "{what} (still initializing)",
+        Msg::WmiNotInitialized => "WMI is not initialized",
+        Msg::SioNotInitialized => "SIO is not initialized",
+        Msg::SioNoAccess => "SIO is not initialized and neither the WinRing0 driver nor WMI is available",
+        Msg::EcNotInitialized => "EC is not initialized",
+        Msg::CpuNotInitialized => "CPU temperature reading is not initialized",
+        Msg::DriverNotInstalled => "The WinRing0 driver is not installed; install it from the settings",
+        Msg::StorageNotInitialized => "Storage temperature reading is not initialized",
+        Msg::GpuNotInitialized => "GPU sensors are not initialized",
+        Msg::ConfigNotLoaded => "The config file path is not initialized",
+
+        Msg::DriverFileMissing => "Driver file not found: {path}",
+        Msg::DriverPathInvalid => "Cannot resolve the driver path: {error}",
+        Msg::DriverScmOpen => "Cannot open the service control manager: {error}",
+        Msg::DriverServiceAccess => "Cannot access the driver service: {error}",
+        Msg::DriverServiceCreate => "Cannot create the driver service: {error}",
+        Msg::DriverServiceStart => "Cannot start the driver service: {error}",
+        Msg::DriverForeignService => {
+            "The driver service registered by other software ({path}) cannot be started: {error}"
+        }
+        Msg::DriverDeviceOpen => "Cannot open the driver device: {error}",
+        Msg::DriverInvalidHandle => "Opening the driver device returned an invalid handle",
+        Msg::DriverSimulated => "There is no WinRing0 driver in simulation mode",
+        Msg::DriverHeldByService => "The driver is held by the NoCrate service",
+        Msg::DriverPortRead => "Reading I/O port {port} failed: {error}",
+        Msg::DriverPortWrite => "Writing I/O port {port} failed: {error}",
+        Msg::DriverMsrRead => "Reading MSR {index} failed: {error}",
+        Msg::DriverVersionRead => "Reading the driver version failed: {error}",
+        Msg::DriverDirCreate => "Cannot create the driver folder {path}: {error}",
+        Msg::DriverDirOpen => "Cannot open the driver folder {path}: {error}",
+        Msg::DriverDirNotPlain => "The driver folder is not a plain folder (possibly a junction or symbolic link): {path}",
+        Msg::DriverDirSecure => "Cannot set permissions on the driver folder {path}: {error}",
+        Msg::DriverFileRemove => "Cannot delete the old driver file {path}: {error}",
+        Msg::DriverFileWrite => "Cannot extract the driver file {path}: {error}",
+        Msg::DriverFileRead => "Cannot read the driver file {path}: {error}",
+        Msg::DriverFileQuery => "Cannot query {path}: {error}",
+        Msg::DriverFileSecure => "Cannot set permissions on the driver file {path}: {error}",
+        Msg::DriverFileMismatch => "The extracted driver file failed verification: {path}",
+        Msg::DriverHash => "Hashing the driver failed: {error}",
+
+        Msg::SmbusNotFound => "No Intel / AMD SMBus controller found",
+        Msg::SmbusBusy => "The SMBus controller is busy",
+        Msg::SmbusInUse => "The SMBus is in use by another program",
+        Msg::SmbusTimeout => "SMBus transaction timed out (address {addr})",
+        Msg::SmbusFailed => "SMBus transaction failed (address {addr}, status {status})",
+
+        Msg::SioRequiresDriver => {
+            "{what} requires the WinRing0 driver, currently accessed through {access}"
+        }
+        Msg::SioReadIte => "Reading the ITE chip",
+        Msg::SioClearIntrusionLatch => "Clearing the chassis intrusion latch",
+        Msg::SioNoIntrusion => "{chip} does not support chassis intrusion detection",
+        Msg::SioNoDump => "{chip} does not support register dumps",
+        Msg::SioChipNotFound => "No supported Super I/O chip found (Nuvoton NCT67xx / ITE IT86xx)",
+        Msg::SioRemoteOutOfRange => "Value returned by the service is out of range: {value}",
+        Msg::SioPolicyDenied => "The NoCrate service refuses this access: {op}",
+        Msg::SioTraceMissing => "The recording has no read of register {register}",
+        Msg::LpcBridgeNotFound => "No AMD FCH or Intel PCH LPC/eSPI bridge found",
+        Msg::LpcDecodeRangesFull => {
+            "All I/O decode ranges are in use, cannot add an ISA decode for the HW Monitor"
+        }
+
+        Msg::EcBoardUnknown => "Cannot read the board model (BaseBoardProduct)",
+        Msg::EcBoardUnsupported => "Board {board} is not in the EC sensor table",
+        Msg::EcInputTimeout => "Timed out waiting for the EC input buffer",
+        Msg::EcOutputTimeout => "Timed out waiting for the EC output buffer",
+        Msg::EcMutexCreate => "Cannot create the EC mutex: {error}",
+        Msg::EcBusy => "The EC is held by another program",
+        Msg::CpuAmdFamily => "Unsupported AMD CPU family {family}",
+        Msg::CpuVendor => "Unsupported CPU vendor: {vendor}",
+
+        Msg::GpuNoDriver => "No NVIDIA / AMD graphics driver interface found",
+        Msg::GpuNoAmd => "No AMD graphics card found",
+        Msg::GpuNoNvml => "nvml.dll not found",
+        Msg::GpuLibraryLoad => "Cannot load {name}: {error}",
+        Msg::GpuSymbolMissing => "Missing exported function {name}",
+        Msg::GpuCallFailed => "{func} failed",
+        Msg::GpuCallError => "{func} returned error code {code}",
+        Msg::StorageNone => "No storage device with a readable temperature found",
+        Msg::StorageDescriptorShort => "The device descriptor is too short",
+        Msg::StorageNvmeOffset => "Invalid NVMe log page offset",
+        Msg::StorageNvmeNoTemp => "The NVMe drive reports no temperature",
+        Msg::StorageSmartNoTemp => "The SMART data has no temperature attribute",
+        Msg::StorageOpen => "Cannot open PhysicalDrive{index}: {error}",
+        Msg::StorageIoctl => "IOCTL {code} failed: {error}",
+        Msg::HwinfoNotRunning => "HWiNFO is not running or shared memory support is off",
+        Msg::HwinfoMap => "Cannot map the HWiNFO shared memory",
+        Msg::HwinfoClosed => "The HWiNFO shared memory has been closed",
+
+        Msg::WmiNoInterface => {
+            "No supported ASUS WMI interface found (ASUSManagement / ASUSATKWMI_WMNB / ASUSHW)"
+        }
+        Msg::WmiWrongBackend => "The current backend is not {backend}",
+        Msg::WmiDesktopOnly => "{method} is only available on the Desktop backend",
+        Msg::WmiAsusHwUnsupported => "The ASUSHW backend does not support {method}",
+        Msg::WmiFanMethodFailed => "{method} failed: FanType={fan}, ErrorCode={code}",
+        Msg::WmiCurveMethodFailed => {
+            "{method} failed: FanType={fan}, Mode={mode}, ErrorCode={code}"
+        }
+        Msg::WmiPropertyConvert => "Property {name} cannot be converted to a number: {error}",
+        Msg::WmiPropertyNotI32 => "Property {name} is still not an i32 after conversion",
+        Msg::WmiLhmNotConnected => "LibreHardwareMonitor is not connected",
+
+        Msg::AuraChannelMissing => "Channel {channel} not present on {product} (PID {pid})",
+        Msg::AuraDeviceMissing => "Device {device} not present",
+        Msg::AuraNotAvailable => "AURA controller not available",
+        Msg::AuraNotFound => "No AURA controller found",
+        Msg::AuraDeviceRange => "AURA device index {index} out of range ({count} found)",
+        Msg::AuraDramNotAvailable => "AURA DRAM not available",
+        Msg::AuraDramRange => "AURA DRAM index {index} out of range ({count} found)",
+        Msg::AuraStreamNotStarted => "AURA stream not started",
+        Msg::AuraStreamFps => "Stream FPS must be positive",
+        Msg::AudioMixFormat => "Unsupported audio mix format",
+        Msg::OpenRgbPassthrough => "OpenRGB passthrough is enabled, AURA is controlled by OpenRGB",
+        Msg::OpenRgbResolve => "OpenRGB: cannot resolve {host}",
+        Msg::OpenRgbNotConnected => "OpenRGB: not connected",
+        Msg::OpenRgbIo => "OpenRGB: {error}",
+        Msg::OpenRgbBadMagic => "OpenRGB: bad packet magic",
+        Msg::OpenRgbPacketTooLarge => "OpenRGB: packet too large ({len})",
+        Msg::OpenRgbTruncated => "OpenRGB: truncated packet",
+        Msg::OpenRgbTooManyLeds => "OpenRGB: too many LEDs ({count})",
+        Msg::OpenRgbDeviceRange => "OpenRGB: device index {device} out of range",
+        Msg::OpenRgbZoneRange => "OpenRGB: zone {zone} out of range ({zones} zones on {device})",
+        Msg::EffectTempRange => "Invalid temperature range: {min} >= {max}",
+        Msg::EffectSensitivity => "Sensitivity must be positive, got {value}",
+        Msg::EffectSmoothing => "Smoothing must be in 0.0..1.0, got {value}",
+        Msg::ThreadSpawn => "Failed to spawn thread {name}: {error}",
+        Msg::ThreadInitDied => "Thread {name} died during init",
+
+        Msg::FanHeaderMissing => "Fan header {fan} not present",
+        Msg::FanCalibrating => "Fan header {fan} is already being calibrated",
+        Msg::FanGroupNameEmpty => "Fan group name is empty",
+        Msg::FanGroupEmpty => "Fan group {name} has no headers",
+        Msg::FanGroupTaken => "Fan header {fan} is already in group {group}",
+        Msg::FanGroupUnknown => "Unknown fan group: {name}",
+        Msg::ScanRangeInvalid => "Invalid range {start}..{end}",
+        Msg::ScanTooLarge => "Scan covers {total} IDs, at most {max} are allowed",
+        Msg::ScanRunning => "A device scan is already running",
+
+        Msg::ConfigDirCreate => "Failed to create config directory: {error}",
+        Msg::ConfigAlreadyLoaded => "Config path already initialized",
+        Msg::ConfigRead => "Failed to read config file: {error}",
+        Msg::ConfigParse => "Config parse error: {error}",
+        Msg::ConfigSerialize => "Failed to serialize config: {error}",
+        Msg::ConfigWrite => "Failed to write config file: {error}",
+        Msg::ConfigBackupIndex => "Backup index {index} out of range (1-{count})",
+        Msg::ConfigBackupRead => "Failed to read backup {index}: {error}",
+        Msg::ConfigBackupInvalid => "Backup {index} is invalid: {error}",
+        Msg::FileRead => "Failed to read {path}: {error}",
+        Msg::FileWrite => "Failed to write {path}: {error}",
+        Msg::TraceInvalid => "Invalid trace {path}: {error}",
+        Msg::SubsystemUnknown => "Unknown subsystem: {name}",
+        Msg::WmiTaskFailed => "WMI request task failed: {error}",
+        Msg::ApiTokenFailed => "Failed to generate API token: {error}",
+        Msg::LhmNotInstalled => "LibreHardwareMonitor is not installed",
+        Msg::LhmStart => "Failed to start {path}: {error}",
+        Msg::RtssNotRunning => "RTSS is not running",
+        Msg::UpdateVersionInvalid => "Invalid app version {version}",
+        Msg::UpdateResponseInvalid => "Invalid GitHub response: {error}",
+        Msg::UpdateNoRelease => "No published release found",
+
+        Msg::AppNotRunning => "NoCrate is not running",
+        Msg::AppStarting => "NoCrate is still starting",
+        Msg::AppBusy => "NoCrate is busy, try again",
+        Msg::AppConnect => "Failed to connect to NoCrate: {error}",
+        Msg::AppReplyInvalid => "Invalid reply from NoCrate: {error}",
+        Msg::PipeSend => "Failed to send request: {error}",
+        Msg::PipeRead => "Failed to read reply: {error}",
+        Msg::RequestInvalid => "Invalid request: {error}",
+        Msg::ServiceNotRunning => "The NoCrate service is not running",
+        Msg::ServiceNotStarted => "Not started as a service.\n\n{usage}",
+        Msg::ServiceConnect => "Failed to connect to the NoCrate service: {error}",
+        Msg::ServiceBusy => "The NoCrate service is busy",
+        Msg::ServiceUnexpectedReply => "Unexpected reply from the NoCrate service",
+        Msg::ServiceVersion => {
+            "The NoCrate service is version {version}, reinstall it for {expected}"
+        }
+        Msg::ServiceNoWmi => "The NoCrate service found no ASUS WMI interface",
+        Msg::ServiceWmiLost => "The NoCrate service has no WMI connection",
+        Msg::ServiceNoDriver => "The NoCrate service could not load WinRing0",
+        Msg::ServiceForeignObject => "The NoCrate service does not call methods on {path}",
+        Msg::ServiceExitCode => "{name} exited with code {code}",
+        Msg::FileMissing => "{path} not found",
+
+        Msg::ExePathUnknown => "Cannot get the current program path: {error}",
+        Msg::ElevationCancelled => "The administrator elevation request was cancelled",
+        Msg::AutostartRegistryWrite => "Failed to write the registry: {error}",
+        Msg::AutostartRegistryDelete => "Failed to delete the registry value: {error}",
+
+        Msg::AutostartUserUnknown => "Cannot determine the current user",
+        Msg::AutostartTaskCreate => "Creating the scheduled task failed: {error}",
+        Msg::AutostartTaskDelete => "Deleting the scheduled task failed: {error}",
+
+        Msg::HintRunAsAdmin => "Restart NoCrate as administrator",
+        Msg::HintDriverBlocked => {
+            "Windows blocked the WinRing0 driver (vulnerable driver blocklist or memory integrity); use LibreHardwareMonitor to read sensors instead"
+        }
+        Msg::HintBusy => "Close other programs using the device (such as Armoury Crate) and retry",
+        Msg::HintTimeout => "Retry later; if it keeps timing out, restart the Windows Management Instrumentation service",
+        Msg::HintWmiDriver => "Make sure the ASUS motherboard drivers (ATK Package / ASUS System Control Interface) are installed",
+        Msg::HintLhm => "Install LibreHardwareMonitor and run it as administrator, or start it from the settings",
+        Msg::HintAura => "Make sure the board or a peripheral has an AURA controller not held by other lighting software",
+        Msg::HintGpu => "Install the NVIDIA or AMD graphics driver",
+
+        Msg::WmiReconnecting => "WMI connection lost, reconnecting automatically",
+        Msg::LhmSensorCount => "{count} sensors",
+        Msg::LhmNoSensors => "LibreHardwareMonitor reports no sensors",
+        Msg::AuraOpenRgb => "Handed over to OpenRGB",
+        Msg::AuraControllerCount => "{count} controllers",
+
+        Msg::CurveTempNotAscending => {
+            "point {point} temperature {temp} °C is not above the previous {prev} °C"
+        }
+        Msg::CurveDutyDescending => "point {point} duty {duty}% is below the previous {prev}%",
+        Msg::CurveTempOutOfRange => "point {point} temperature {temp} °C is above {max} °C",
+        Msg::CurveDutyOutOfRange => "point {point} duty {duty}% is above {max}%",
+        Msg::CurveBelowStallDuty => "point {point} duty {duty}% is below the fan's stall point of {min}%",
+        Msg::CurveNoTopPoint => "the last point is at {temp} °C; the curve must reach at least {min} °C",
+        Msg::CurveEmpty => "the curve has no points",
+
+        Msg::TrayShow => "Show window",
+        Msg::TrayFanProfile => "Fan profile",
+        Msg::TrayProfileStandard => "Standard",
+        Msg::TrayProfilePerformance => "Performance",
+        Msg::TrayProfileSilent => "Silent",
+        Msg::TrayOverlay => "Show/hide overlay",
+        Msg::TrayQuit => "Quit",
+        Msg::TrayTooltip => "NoCrate — ASUS motherboard control",
+    }
+}
@@ -0,0 +1,26 @@
+//! Hardware access for NoCrate: ASUS WMI, Super I/O, EC, AURA lighting
+//! and the unified sensor layer.
+//!
+//! Nothing here depends on Tauri, so the crate can be used headless (the
+//! CLI) and tested without the app shell. Runtime state, events and
+//! persistence stay in the app.
+
+pub mod aura;
+pub mod board;
+#[cfg(feature = "sio")]
+pub mod cpu;
+pub mod curve;
+#[cfg(feature = "sio")]
+pub mod ec;
+pub mod error;
+pub mod gpu;
+pub mod hwinfo;
+pub mod i18n;
+pub mod sensors;
+#[cfg(feature = "sio")]
+pub mod sio;
+#[cfg(feature = "sio")]
+pub mod smbus;
+pub mod storage;
+pub mod trace;
+pub mod wmi;
@@ -0,0 +1,368 @@
+// 传感器历史记录（分级降采样）
+// 每个传感器保存三级数据：1 秒分辨率保留 10 分钟、10 秒桶保留 2 小时、1 分钟桶保留 24 小时。
+// 每个桶记录最小 / 平均 / 最大值，降采样后峰值不会被抹平。
+// 查询时选用能覆盖所请求时长的最细一级，数小时的图表也只需传输一两千个点。
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use super::sampling::{Accumulator, Throttle};
+use super::UnifiedSensor;
+
+/// 各级的桶宽（秒）与保留的桶数，由细到粗
+const TIERS: [(u64, usize); 3] = [(1, 600), (10, 720), (60, 1440)];
+
+/// 一个时间桶的读数
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HistoryPoint {
+    /// 桶起始时间（Unix 秒）
+    pub t: u64,
+    pub min: f32,
+    pub avg: f32,
+    pub max: f32,
+}
+
+/// 查询结果
+#[derive(Debug, Clone, Serialize)]
+pub struct HistorySeries {
+    /// 所选一级的桶宽（秒）
+    pub resolution_s: u64,
+    /// 按传感器 ID，按时间升序
+    pub series: HashMap<String, Vec<HistoryPoint>>,
+}
+
+impl HistorySeries {
+    /// 转为 CSV：每行一个桶，按时间再按传感器 ID 排序
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut rows: Vec<(&String, &HistoryPoint)> = self
+            .series
+            .iter()
+            .flat_map(|(id, points)| points.iter().map(move |p| (id, p)))
+            .collect();
+        rows.sort_by(|a, b| a.1.t.cmp(&b.1.t).then_with(|| a.0.cmp(b.0)));
+
+        let mut csv = String::from("time_utc,unix_s,sensor_id,min,avg,max\n");
+        for (id, p) in rows {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                utc_timestamp(p.t),
+                p.t,
+                csv_field(id),
+                p.min,
+                p.avg,
+                p.max
+            );
+        }
+        csv
+    }
+}
+
+/// 尚未结束的桶
+#[derive(Clone, Copy)]
+struct Bucket {
+    start: u64,
+    acc: Accumulator,
+}
+
+impl Bucket {
+    fn point(&self) -> HistoryPoint {
+        HistoryPoint {
+            t: self.start,
+            min: self.acc.min,
+            avg: self.acc.avg(),
+            max: self.acc.max,
+        }
+    }
+}
+
+struct Tier {
+    resolution_s: u64,
+    capacity: usize,
+    points: VecDeque<HistoryPoint>,
+    pending: Option<Bucket>,
+}
+
+impl Tier {
+    const fn new((resolution_s, capacity): (u64, usize)) -> Self {
+        Self {
+            resolution_s,
+            capacity,
+            points: VecDeque::new(),
+            pending: None,
+        }
+    }
+
+    fn push(&mut self, t: u64, value: f32) {
+        let start = t - t % self.resolution_s;
+        match &mut self.pending {
+            Some(bucket) if bucket.start == start => bucket.acc.add(value),
+            pending => {
+                let bucket = Bucket {
+                    start,
+                    acc: Accumulator::new(value),
+                };
+                if let Some(done) = pending.replace(bucket) {
+                    if self.points.len() == self.capacity {
+                        let _ = self.points.pop_front();
+                    }
+                    self.points.push_back(done.point());
+                }
+            }
+        }
+    }
+
+    /// `from` 之后的桶，含尚未结束的桶
+    fn since(&self, from: u64) -> Vec<HistoryPoint> {
+        self.points
+            .iter()
+            .copied()
+            .chain(self.pending.map(|b| b.point()))
+            .filter(|p| p.t >= from)
+            .collect()
+    }
+}
+
+struct Inner {
+    throttle: Throttle,
+    sensors: HashMap<String, Vec<Tier>>,
+}
+
+/// 全部传感器的历史记录，由聚合层在每次读取后更新
+pub struct SensorHistory {
+    inner: Mutex<Inner>,
+}
+
+impl Default for SensorHistory {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                throttle: Throttle::default(),
+                sensors: HashMap::new(),
+            }),
+        }
+    }
+}
+
+impl SensorHistory {
+    /// 距上次计入已超过最短间隔
+    #[must_use]
+    pub fn due(&self) -> bool {
+        self.inner.lock().throttle.due(Instant::now())
+    }
+
+    /// 计入一次读数
+    pub fn record(&self, sensors: &[UnifiedSensor]) {
+        self.record_at(sensors, now(), Instant::now());
+    }
+
+    /// 以 Unix 秒 `t` 计入一次读数，`instant` 用于节流
+    fn record_at(&self, sensors: &[UnifiedSensor], t: u64, instant: Instant) {
+        let mut inner = self.inner.lock();
+        if !inner.throttle.take(instant) {
+            return;
+        }
+
+        for sensor in sensors.iter().filter(|s| s.value.is_finite()) {
+            let tiers = inner
+                .sensors
+                .entry(sensor.id.clone())
+                .or_insert_with(|| TIERS.into_iter().map(Tier::new).collect());
+            for tier in tiers {
+                tier.push(t, sensor.value);
+            }
+        }
+        drop(inner);
+    }
+
+    /// 最近 `seconds` 秒内 `ids` 的历史（`ids` 为空时为全部传感器），
+    /// 使用能覆盖该时长的最细一级；超出最粗一级时返回其全部数据
+    #[must_use]
+    pub fn query(&self, ids: &[String], seconds: u64) -> HistorySeries {
+        self.query_at(ids, seconds, now())
+    }
+
+    fn query_at(&self, ids: &[String], seconds: u64, now: u64) -> HistorySeries {
+        let tier = tier_for(seconds);
+        let from = now.saturating_sub(seconds);
+
+        let inner = self.inner.lock();
+        let series = inner
+            .sensors
+            .iter()
+            .filter(|(id, _)| ids.is_empty() || ids.contains(id))
+            .map(|(id, tiers)| (id.clone(), tiers[tier].since(from)))
+            .collect();
+        drop(inner);
+        HistorySeries {
+            resolution_s: TIERS[tier].0,
+            series,
+        }
+    }
+}
+
+/// 能覆盖 `seconds` 秒的最细一级，超出最粗一级时为最粗一级
+fn tier_for(seconds: u64) -> usize {
+    TIERS
+        .iter()
+        .position(|&(res, cap)| res * cap as u64 >= seconds)
+        .unwrap_or(TIERS.len() - 1)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// CSV 字段，含逗号或引号时加引号
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// Unix 秒转为 `YYYY-MM-DDTHH:MM:SSZ`（公历换算见 Howard Hinnant 的 `civil_from_days`）
+fn utc_timestamp(secs: u64) -> String {
+    let days = secs / 86_400;
+    let rem = secs % 86_400;
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::{SensorKind, SensorSource};
+    use super::*;
+
+    fn sensor(id: &str, value: f32) -> UnifiedSensor {
+        UnifiedSensor::new(
+            id.to_owned(),
+            SensorSource::Sio,
+            SensorKind::Temperature,
+            id,
+            "test",
+            value,
+        )
+    }
+
+    fn point(t: u64, min: f32, avg: f32, max: f32) -> HistoryPoint {
+        HistoryPoint { t, min, avg, max }
+    }
+
+    #[test]
+    fn picks_finest_tier_covering_duration() {
+        assert_eq!(tier_for(0), 0);
+        assert_eq!(tier_for(600), 0);
+        assert_eq!(tier_for(601), 1);
+        assert_eq!(tier_for(7200), 1);
+        assert_eq!(tier_for(7201), 2);
+        assert_eq!(tier_for(86_400), 2);
+        assert_eq!(tier_for(7 * 86_400), 2);
+    }
+
+    #[test]
+    fn bucket_rolls_over_and_evicts_oldest() {
+        let mut tier = Tier::new((10, 2));
+        tier.push(100, 40.0);
+        tier.push(105, 60.0);
+        tier.push(109, 50.0);
+        assert!(tier.points.is_empty());
+
+        tier.push(110, 30.0);
+        let done = tier.points[0];
+        assert_eq!((done.t, done.min, done.max), (100, 40.0, 60.0));
+        assert_eq!(done.avg, 50.0);
+        assert_eq!(tier.since(0).len(), 2);
+        assert_eq!(tier.since(110).len(), 1);
+
+        tier.push(120, 30.0);
+        tier.push(130, 30.0);
+        let starts: Vec<u64> = tier.points.iter().map(|p| p.t).collect();
+        assert_eq!(starts, [110, 120]);
+    }
+
+    #[test]
+    fn coarse_tier_buckets_across_minute_boundary() {
+        let history = SensorHistory::default();
+        let t0 = Instant::now();
+        for (t, value) in [(58, 40.0), (59, 60.0), (60, 70.0)] {
+            history.record_at(&[sensor("cpu", value)], t, t0 + Duration::from_secs(t));
+        }
+
+        let fine = history.query_at(&[], 600, 60);
+        assert_eq!(fine.resolution_s, 1);
+        assert_eq!(fine.series["cpu"].len(), 3);
+
+        let coarse = history.query_at(&["cpu".to_owned()], 86_400, 60);
+        assert_eq!(coarse.resolution_s, 60);
+        let points = &coarse.series["cpu"];
+        assert_eq!(points.len(), 2);
+        assert_eq!((points[0].t, points[0].min, points[0].max), (0, 40.0, 60.0));
+        assert_eq!((points[1].t, points[1].avg), (60, 70.0));
+    }
+
+    #[test]
+    fn throttles_and_skips_non_finite() {
+        let history = SensorHistory::default();
+        let t0 = Instant::now();
+        history.record_at(&[sensor("cpu", 40.0), sensor("nan", f32::NAN)], 10, t0);
+        history.record_at(&[sensor("cpu", 90.0)], 11, t0 + Duration::from_millis(100));
+
+        let series = history.query_at(&[], 60, 11).series;
+        assert!(!series.contains_key("nan"));
+        assert_eq!(series["cpu"].len(), 1);
+        assert_eq!(series["cpu"][0].max, 40.0);
+    }
+
+    #[test]
+    fn csv_export() {
+        let series = HistorySeries {
+            resolution_s: 1,
+            series: HashMap::from([
+                (
+                    "b".to_owned(),
+                    vec![point(1, 1.0, 1.5, 2.0), point(0, 3.0, 3.0, 3.0)],
+                ),
+                ("a,\"x\"".to_owned(), vec![point(1, 4.0, 4.0, 4.0)]),
+            ]),
+        };
+        assert_eq!(
+            series.to_csv(),
+            "time_utc,unix_s,sensor_id,min,avg,max\n\
+             1970-01-01T00:00:00Z,0,b,3,3,3\n\
+             1970-01-01T00:00:01Z,1,\"a,\"\"x\"\"\",4,4,4\n\
+             1970-01-01T00:00:01Z,1,b,1,1.5,2\n"
+        );
+    }
+
+    #[test]
+    fn formats_utc_timestamps() {
+        assert_eq!(utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(utc_timestamp(1_700_000_000), "2023-11-14T22:13:20Z");
+    }
+}
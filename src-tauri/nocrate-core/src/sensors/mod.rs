@@ -0,0 +1,615 @@
+// 统一传感器聚合层
+// 将 SIO / EC / CPU / GPU / 存储 / ASUSHW / LHM / HWiNFO 各来源合并为同一类型的列表，
+// 每个传感器具有稳定的字符串 ID（如 `sio/nct6798d/temp/1`），前端与风扇曲线均以此为准。
+//
+// 来源按优先级排列：直接读取硬件的来源在前，ASUSHW / LHM / HWiNFO 在后。
+// 同一物理传感器被多个来源报告时，后出现的条目通过 `duplicate_of` 指向首个条目。
+
+pub mod history;
+pub mod polling;
+pub mod sampling;
+pub mod smoothing;
+pub mod stats;
+pub mod units;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "sio")]
+use crate::cpu::CpuMonitor;
+#[cfg(feature = "sio")]
+use crate::ec::EcMonitor;
+use crate::gpu::GpuMonitor;
+use crate::hwinfo::{self, HwinfoReadingType, HwinfoSnapshot};
+use crate::i18n;
+#[cfg(feature = "sio")]
+use crate::sio::SioMonitor;
+use crate::storage::StorageMonitor;
+use crate::wmi::asus_mgmt::{self, AsusHWSensor, AsusHwCache};
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend};
+use crate::wmi::lhm::{self, LhmSensor, SensorFilter};
+use crate::wmi::thread::WmiThread;
+use history::SensorHistory;
+use polling::{PollPlan, PollingTiers, SensorPoller};
+use smoothing::SensorSmoother;
+use stats::SensorStatsTracker;
+use units::UnitsConfig;
+
+/// 传感器数据来源，按优先级排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[cfg_attr(not(feature = "sio"), allow(dead_code))]
+#[serde(rename_all = "snake_case")]
+pub enum SensorSource {
+    Sio,
+    Ec,
+    Cpu,
+    Gpu,
+    Storage,
+    #[serde(rename = "asushw")]
+    AsusHw,
+    Lhm,
+    Hwinfo,
+}
+
+impl SensorSource {
+    /// 全部来源，按优先级
+    pub const ALL: [Self; 8] = [
+        Self::Sio,
+        Self::Ec,
+        Self::Cpu,
+        Self::Gpu,
+        Self::Storage,
+        Self::AsusHw,
+        Self::Lhm,
+        Self::Hwinfo,
+    ];
+
+    /// 根据稳定 ID 的前缀判断传感器来源，无法识别时返回 `None`
+    #[must_use]
+    pub fn from_id(id: &str) -> Option<Self> {
+        Some(match id.split('/').next()? {
+            "sio" => Self::Sio,
+            "ec" => Self::Ec,
+            "cpu" => Self::Cpu,
+            "gpu" => Self::Gpu,
+            "storage" => Self::Storage,
+            "asushw" => Self::AsusHw,
+            "lhm" => Self::Lhm,
+            "hwinfo" => Self::Hwinfo,
+            _ => return None,
+        })
+    }
+}
+
+/// 传感器物理量类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(not(feature = "sio"), allow(dead_code))]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    /// 温度（°C）
+    Temperature,
+    /// 风扇转速（RPM）
+    Fan,
+    /// 风扇占空比（%）
+    FanPercent,
+    /// 电流（A）
+    Current,
+    /// 流量（L/h）
+    Flow,
+}
+
+/// 统一格式的传感器读数
+#[derive(Debug, Clone, Serialize)]
+pub struct UnifiedSensor {
+    /// 稳定 ID：`<来源>/<设备>/<类型>/<通道>`，不随别名或排序变化
+    pub id: String,
+    /// 数据来源
+    pub source: SensorSource,
+    /// 物理量类型
+    pub kind: SensorKind,
+    /// 显示名称（已应用用户别名，内置名称按当前语言翻译）
+    pub name: String,
+    /// 所属设备（芯片型号、主板、显卡名称等）
+    pub device: String,
+    /// 当前值（已应用用户校准与滑动平均）
+    pub value: f32,
+    /// 校准前的原始读数
+    pub raw_value: f32,
+    /// 按单位偏好换算后的 `value`
+    pub display_value: f32,
+    /// `display_value` 的单位符号（如 "°F"、"RPM"）
+    pub display_unit: &'static str,
+    /// 若与更高优先级来源的传感器重复，指向该传感器的 ID
+    pub duplicate_of: Option<String>,
+    /// 去重键：相同键的传感器视为同一物理量
+    #[serde(skip)]
+    dedup_key: Option<String>,
+}
+
+impl UnifiedSensor {
+    fn new(
+        id: String,
+        source: SensorSource,
+        kind: SensorKind,
+        name: impl Into<String>,
+        device: impl Into<String>,
+        value: f32,
+    ) -> Self {
+        Self {
+            id,
+            source,
+            kind,
+            name: name.into(),
+            device: device.into(),
+            value,
+            raw_value: value,
+            display_value: value,
+            display_unit: "",
+            duplicate_of: None,
+            dedup_key: None,
+        }
+    }
+
+    fn dedup(mut self, key: Option<String>) -> Self {
+        self.dedup_key = key;
+        self
+    }
+}
+
+/// Linear correction applied to a sensor reading: `value * scale + offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorCalibration {
+    /// Added after scaling, e.g. `10.0` when SYSTIN reads 10 °C low
+    pub offset: f32,
+    /// Multiplier applied to the raw value (1.0 = unchanged)
+    pub scale: f32,
+}
+
+impl Default for SensorCalibration {
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl SensorCalibration {
+    /// Apply the correction to a raw reading.
+    #[must_use]
+    pub const fn apply(self, value: f32) -> f32 {
+        value.mul_add(self.scale, self.offset)
+    }
+}
+
+/// 某个来源读取失败的信息
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceError {
+    pub source: SensorSource,
+    pub message: String,
+}
+
+/// 聚合后的传感器快照
+#[derive(Debug, Clone, Serialize)]
+pub struct UnifiedSensorSnapshot {
+    /// 所有传感器（按来源优先级排序）
+    pub sensors: Vec<UnifiedSensor>,
+    /// 已初始化但本次读取失败的来源
+    pub errors: Vec<SourceError>,
+}
+
+/// 参与聚合的来源及读取选项，未初始化的来源为 `None`
+#[derive(Default)]
+pub struct Sources<'a> {
+    pub wmi: Option<&'a WmiThread>,
+    /// ASUSHW 传感器元数据缓存，`None` 时每次重新枚举并刷新全部缓冲区
+    pub asushw: Option<&'a Arc<AsusHwCache>>,
+    /// ASUSHW 缓冲区的最短刷新间隔
+    pub asushw_interval: Duration,
+    pub gpu: Option<&'a GpuMonitor>,
+    pub storage: Option<&'a StorageMonitor>,
+    #[cfg(feature = "sio")]
+    pub sio: Option<&'a SioMonitor>,
+    #[cfg(feature = "sio")]
+    pub ec: Option<&'a EcMonitor>,
+    #[cfg(feature = "sio")]
+    pub cpu: Option<&'a CpuMonitor>,
+    /// 从 LHM 读取的传感器 ID，为空时读取全部
+    pub lhm_sensors: Vec<String>,
+    /// 是否读取 HWiNFO 共享内存
+    pub hwinfo_enabled: bool,
+    /// 按传感器 ID 的用户校准
+    pub calibration: HashMap<String, SensorCalibration>,
+    /// 按传感器 ID 的滑动平均窗口（秒）
+    pub smoothing: HashMap<String, u16>,
+    /// 滑动平均的读数缓存，`None` 时不平滑
+    pub smoother: Option<&'a SensorSmoother>,
+    /// 返回未经平滑（但已校准）的值；仍计入平滑缓存，统计与历史仍使用平滑后的值
+    pub unsmoothed: bool,
+    /// 轮询分级
+    pub polling: PollingTiers,
+    /// 各来源上次的读数，`None` 时每次读取全部来源
+    pub poller: Option<&'a SensorPoller>,
+    /// 会话统计，`None` 时不记录
+    pub stats: Option<&'a SensorStatsTracker>,
+    /// 历史记录，`None` 时不记录
+    pub history: Option<&'a SensorHistory>,
+    /// 显示单位偏好
+    pub units: UnitsConfig,
+}
+
+/// 读取 ASUSHW 传感器，有缓存时只在缓冲区过期后刷新
+fn read_asushw(
+    conn: &impl AsusWmi,
+    cache: Option<&AsusHwCache>,
+    interval: Duration,
+) -> Vec<AsusHWSensor> {
+    cache.map_or_else(
+        || asus_mgmt::get_asushw_sensors(conn),
+        |c| c.read(conn, interval),
+    )
+}
+
+/// 从所有可用来源读取并合并传感器
+pub fn collect(sources: &Sources<'_>) -> UnifiedSensorSnapshot {
+    let mut sensors = Vec::new();
+    let mut errors = Vec::new();
+    let plan = sources
+        .poller
+        .map_or_else(PollPlan::all, |p| p.plan(&sources.polling));
+
+    #[cfg(feature = "sio")]
+    collect_driver_sources(sources, &plan, &mut sensors, &mut errors);
+
+    if let Some(gpu) = sources.gpu.filter(|_| plan.reads(SensorSource::Gpu)) {
+        for device in gpu.read_all() {
+            sensors.extend(from_gpu(&device));
+        }
+    }
+
+    if let Some(storage) = sources.storage.filter(|_| plan.reads(SensorSource::Storage)) {
+        for reading in storage.read_all() {
+            sensors.push(UnifiedSensor::new(
+                format!("storage/{}/temp", reading.index),
+                SensorSource::Storage,
+                SensorKind::Temperature,
+                reading.model.clone(),
+                reading.model,
+                reading.temp_c,
+            ));
+        }
+    }
+
+    // 仅在本次读数会计入统计时读取散热模式
+    let record_stats = sources.stats.is_some_and(SensorStatsTracker::due);
+    let mut profile = None;
+
+    if let Some(wmi) = sources.wmi.filter(|_| plan.reads(SensorSource::AsusHw)) {
+        let lhm_ids = sources.lhm_sensors.clone();
+        let (cache, interval) = (sources.asushw.cloned(), sources.asushw_interval);
+        match wmi.execute(move |conn| {
+            let is_asushw = matches!(conn.backend, AsusWmiBackend::AsusHW { .. });
+            let asushw = if is_asushw {
+                read_asushw(conn, cache.as_deref(), interval)
+            } else {
+                Vec::new()
+            };
+            let lhm = conn
+                .lhm_connected()
+                .then(|| lhm::get_temp_and_fan_sensors(conn, SensorFilter::Ids(&lhm_ids)));
+            let profile = (record_stats && !is_asushw)
+                .then(|| asus_mgmt::get_thermal_profile(conn).ok())
+                .flatten();
+            Ok((asushw, lhm, profile))
+        }) {
+            Ok((asushw, lhm, current)) => {
+                profile = current;
+                sensors.extend(asushw.iter().filter_map(from_asushw));
+                match lhm {
+                    Some(Ok((temps, fans))) => {
+                        sensors.extend(temps.iter().chain(&fans).filter_map(from_lhm));
+                    }
+                    Some(Err(e)) => errors.push(SourceError {
+                        source: SensorSource::Lhm,
+                        message: e.to_string(),
+                    }),
+                    None => {}
+                }
+            }
+            Err(e) => errors.push(SourceError {
+                source: SensorSource::AsusHw,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    // HWiNFO 未运行属于正常情况，不作为错误报告
+    if sources.hwinfo_enabled && plan.reads(SensorSource::Hwinfo) {
+        if let Ok(snapshot) = hwinfo::read_all() {
+            sensors.extend(from_hwinfo(&snapshot));
+        }
+    }
+
+    if let Some(poller) = sources.poller {
+        poller.complete(&plan, &mut sensors, &errors);
+    }
+    mark_duplicates(&mut sensors);
+    apply_calibration(&mut sensors, &sources.calibration);
+    let unsmoothed: Option<Vec<f32>> = sources
+        .unsmoothed
+        .then(|| sensors.iter().map(|s| s.value).collect());
+    if let Some(smoother) = sources.smoother {
+        smoother.smooth(&mut sensors, &sources.smoothing);
+    }
+    if let (Some(stats), true) = (sources.stats, record_stats) {
+        stats.record(&sensors, profile);
+    }
+    if let Some(history) = sources.history {
+        history.record(&sensors);
+    }
+    if let Some(values) = unsmoothed {
+        for (s, value) in sensors.iter_mut().zip(values) {
+            s.value = value;
+        }
+    }
+    for s in &mut sensors {
+        (s.display_value, s.display_unit) = sources.units.display(s.kind, s.value);
+        if let Cow::Owned(label) = i18n::sensor_label(&s.name) {
+            s.name = label;
+        }
+    }
+    UnifiedSensorSnapshot { sensors, errors }
+}
+
+/// 按传感器 ID 应用用户配置的偏移 / 缩放
+fn apply_calibration(sensors: &mut [UnifiedSensor], calibration: &HashMap<String, SensorCalibration>) {
+    if calibration.is_empty() {
+        return;
+    }
+    for s in sensors {
+        if let Some(cal) = calibration.get(&s.id) {
+            s.value = cal.apply(s.raw_value);
+        }
+    }
+}
+
+/// 依赖 `WinRing0` 驱动的来源：SIO、EC、CPU
+#[cfg(feature = "sio")]
+fn collect_driver_sources(
+    sources: &Sources<'_>,
+    plan: &PollPlan,
+    sensors: &mut Vec<UnifiedSensor>,
+    errors: &mut Vec<SourceError>,
+) {
+    if let Some(sio) = sources.sio.filter(|_| plan.reads(SensorSource::Sio)) {
+        match sio.read_all() {
+            Ok(snapshot) => {
+                let chip = slug(&snapshot.chip_name);
+                for t in &snapshot.temps {
+                    sensors.push(UnifiedSensor::new(
+                        format!("sio/{chip}/temp/{}", t.channel),
+                        SensorSource::Sio,
+                        SensorKind::Temperature,
+                        t.name.clone(),
+                        snapshot.chip_name.clone(),
+                        t.temp_c,
+                    ));
+                }
+                for f in &snapshot.fans {
+                    sensors.push(UnifiedSensor::new(
+                        format!("sio/{chip}/fan/{}", f.channel),
+                        SensorSource::Sio,
+                        SensorKind::Fan,
+                        f.name.clone(),
+                        snapshot.chip_name.clone(),
+                        f.rpm as f32,
+                    ));
+                }
+            }
+            Err(e) => errors.push(SourceError {
+                source: SensorSource::Sio,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    if let Some(ec) = sources.ec.filter(|_| plan.reads(SensorSource::Ec)) {
+        match ec.read_all() {
+            Ok(snapshot) => {
+                for r in snapshot.readings {
+                    let (kind, tag) = match r.kind {
+                        crate::ec::EcSensorKind::Temperature => (SensorKind::Temperature, "temp"),
+                        crate::ec::EcSensorKind::Fan => (SensorKind::Fan, "fan"),
+                        crate::ec::EcSensorKind::Current => (SensorKind::Current, "current"),
+                        crate::ec::EcSensorKind::Flow => (SensorKind::Flow, "flow"),
+                    };
+                    sensors.push(UnifiedSensor::new(
+                        format!("ec/{tag}/{:04x}", r.register),
+                        SensorSource::Ec,
+                        kind,
+                        r.name,
+                        snapshot.board.clone(),
+                        r.value,
+                    ));
+                }
+            }
+            Err(e) => errors.push(SourceError {
+                source: SensorSource::Ec,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    if let Some(cpu) = sources.cpu.filter(|_| plan.reads(SensorSource::Cpu)) {
+        match cpu.read_all() {
+            Ok(snapshot) => {
+                for t in snapshot.temps {
+                    let key = match t.name.as_str() {
+                        "Package" | "Tctl" => Some("cpu/package".to_string()),
+                        _ => None,
+                    };
+                    sensors.push(
+                        UnifiedSensor::new(
+                            format!("cpu/temp/{}", slug(&t.name)),
+                            SensorSource::Cpu,
+                            SensorKind::Temperature,
+                            t.name,
+                            snapshot.brand.clone(),
+                            t.temp_c,
+                        )
+                        .dedup(key),
+                    );
+                }
+            }
+            Err(e) => errors.push(SourceError {
+                source: SensorSource::Cpu,
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+fn from_gpu(device: &crate::gpu::GpuDeviceSnapshot) -> Vec<UnifiedSensor> {
+    use crate::gpu::GpuSensorKind;
+
+    let vendor = if device.backend == "nvml" { "nvidia" } else { "amd" };
+    device
+        .readings
+        .iter()
+        .map(|r| {
+            let (kind, tag) = match r.kind {
+                GpuSensorKind::Temperature => (SensorKind::Temperature, "temp"),
+                GpuSensorKind::FanRpm => (SensorKind::Fan, "fan"),
+                GpuSensorKind::FanPercent => (SensorKind::FanPercent, "fan_percent"),
+            };
+            let name = slug(&r.name);
+            let key = (kind == SensorKind::Temperature)
+                .then(|| format!("gpu/{vendor}/{}/{name}", device.index));
+            UnifiedSensor::new(
+                format!("gpu/{}/{}/{tag}/{name}", device.backend, device.index),
+                SensorSource::Gpu,
+                kind,
+                format!("GPU {}", r.name),
+                device.name.clone(),
+                r.value,
+            )
+            .dedup(key)
+        })
+        .collect()
+}
+
+fn from_asushw(s: &AsusHWSensor) -> Option<UnifiedSensor> {
+    let (kind, tag) = match s.sensor_type.as_str() {
+        "temperature" => (SensorKind::Temperature, "temp"),
+        "fan" => (SensorKind::Fan, "fan"),
+        _ => return None,
+    };
+    Some(UnifiedSensor::new(
+        format!("asushw/{tag}/{}", s.index),
+        SensorSource::AsusHw,
+        kind,
+        s.name.clone(),
+        "ASUSHW",
+        s.value,
+    ))
+}
+
+fn from_lhm(s: &LhmSensor) -> Option<UnifiedSensor> {
+    let kind = match s.sensor_type.as_str() {
+        "Temperature" => SensorKind::Temperature,
+        "Fan" => SensorKind::Fan,
+        _ => return None,
+    };
+    Some(
+        UnifiedSensor::new(
+            format!("lhm{}", s.identifier),
+            SensorSource::Lhm,
+            kind,
+            s.name.clone(),
+            s.parent.clone(),
+            s.value,
+        )
+        .dedup(lhm_dedup_key(s)),
+    )
+}
+
+fn from_hwinfo(snapshot: &HwinfoSnapshot) -> Vec<UnifiedSensor> {
+    snapshot
+        .readings
+        .iter()
+        .filter_map(|r| {
+            let kind = match r.reading_type {
+                HwinfoReadingType::Temperature => SensorKind::Temperature,
+                HwinfoReadingType::Fan => SensorKind::Fan,
+                HwinfoReadingType::Current => SensorKind::Current,
+                _ => return None,
+            };
+            let sensor = snapshot.sensors.get(r.sensor_index)?;
+            #[allow(clippy::cast_possible_truncation)]
+            let value = r.value as f32;
+            Some(UnifiedSensor::new(
+                format!("hwinfo/{:08x}-{}/{:08x}", sensor.id, sensor.instance, r.id),
+                SensorSource::Hwinfo,
+                kind,
+                r.label.clone(),
+                sensor.name.clone(),
+                value,
+            ))
+        })
+        .collect()
+}
+
+/// LHM 中与直接读取来源重复的传感器（CPU 封装温度、GPU 核心 / 热点温度）
+fn lhm_dedup_key(s: &LhmSensor) -> Option<String> {
+    let mut parts = s.parent.trim_start_matches('/').split('/');
+    let hardware = parts.next()?;
+    let index = parts.next().unwrap_or("0");
+
+    match (hardware, s.name.as_str()) {
+        ("intelcpu" | "amdcpu", "CPU Package" | "Core (Tctl/Tdie)" | "Core (Tctl)") => {
+            Some("cpu/package".to_string())
+        }
+        ("gpu-nvidia", "GPU Core") => Some(format!("gpu/nvidia/{index}/core")),
+        ("gpu-nvidia", "GPU Hot Spot") => Some(format!("gpu/nvidia/{index}/hotspot")),
+        ("gpu-amd", "GPU Core") => Some(format!("gpu/amd/{index}/core")),
+        ("gpu-amd", "GPU Hot Spot") => Some(format!("gpu/amd/{index}/hotspot")),
+        _ => None,
+    }
+}
+
+/// 为相同去重键的后续条目设置 `duplicate_of`
+fn mark_duplicates(sensors: &mut [UnifiedSensor]) {
+    let mut primary: HashMap<String, String> = HashMap::new();
+    for s in sensors.iter_mut() {
+        let Some(key) = s.dedup_key.clone() else {
+            continue;
+        };
+        match primary.get(&key) {
+            Some(id) => s.duplicate_of = Some(id.clone()),
+            None => {
+                let _ = primary.insert(key, s.id.clone());
+            }
+        }
+    }
+}
+
+/// 生成 ID 片段：小写字母数字，其余字符折叠为 `-`
+fn slug(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    while out.ends_with('-') {
+        let _ = out.pop();
+    }
+    out
+}
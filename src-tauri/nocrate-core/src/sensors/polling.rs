@@ -0,0 +1,118 @@
+// 传感器轮询分级
+// 风扇、CPU 温度等需要每次都读取；硬盘温度等变化缓慢、读取代价高（SMART 查询）的传感器
+// 归入慢速级，每 slow_interval_secs 秒才读取一次，其间使用上次的读数。
+//
+// 读取以来源为单位：某来源的传感器全部属于慢速级时，该来源在慢速间隔内跳过，
+// 否则照常读取（顺带刷新其中的慢速传感器）。WMI 一次调用同时读取 ASUSHW 与 LHM，两者视为一组。
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::{SensorSource, SourceError, UnifiedSensor};
+
+/// 轮询分级配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PollingTiers {
+    /// 慢速级的读取间隔（秒）
+    pub slow_interval_secs: u64,
+    /// 归入慢速级的传感器 ID 前缀（如 `storage/` 为全部硬盘温度），其余为快速级
+    pub slow: Vec<String>,
+}
+
+impl Default for PollingTiers {
+    fn default() -> Self {
+        Self {
+            slow_interval_secs: 10,
+            slow: vec!["storage/".into()],
+        }
+    }
+}
+
+impl PollingTiers {
+    fn is_slow(&self, id: &str) -> bool {
+        self.slow
+            .iter()
+            .any(|prefix| id.starts_with(prefix.as_str()))
+    }
+}
+
+/// 各来源上次读取的时间与读数
+#[derive(Default)]
+pub struct SensorPoller {
+    cache: Mutex<HashMap<SensorSource, (Instant, Vec<UnifiedSensor>)>>,
+}
+
+impl SensorPoller {
+    /// 本次需要读取的来源
+    #[must_use]
+    pub fn plan(&self, tiers: &PollingTiers) -> PollPlan {
+        let interval = Duration::from_secs(tiers.slow_interval_secs);
+        let cache = self.cache.lock();
+        let mut skip: Vec<SensorSource> = cache
+            .iter()
+            .filter(|(_, (read_at, sensors))| {
+                read_at.elapsed() < interval && sensors.iter().all(|s| tiers.is_slow(&s.id))
+            })
+            .map(|(&source, _)| source)
+            .collect();
+        drop(cache);
+        // ASUSHW 与 LHM 同在一次 WMI 调用中读取，只能一起跳过
+        let wmi = [SensorSource::AsusHw, SensorSource::Lhm];
+        if !wmi.iter().all(|s| skip.contains(s)) {
+            skip.retain(|s| !wmi.contains(s));
+        }
+        PollPlan { skip }
+    }
+
+    /// 缓存本次读取的来源，并补上跳过的来源的上次读数；读取失败的来源保留旧缓存
+    pub fn complete(
+        &self,
+        plan: &PollPlan,
+        sensors: &mut Vec<UnifiedSensor>,
+        errors: &[SourceError],
+    ) {
+        let mut cache = self.cache.lock();
+        for source in SensorSource::ALL {
+            if plan.skip.contains(&source) {
+                if let Some((_, cached)) = cache.get(&source) {
+                    sensors.extend(cached.iter().cloned());
+                }
+            } else if !errors.iter().any(|e| e.source == source) {
+                let fresh = sensors
+                    .iter()
+                    .filter(|s| s.source == source)
+                    .cloned()
+                    .collect();
+                let _ = cache.insert(source, (Instant::now(), fresh));
+            }
+        }
+        drop(cache);
+        // 补上的读数排在末尾，按来源优先级恢复顺序（稳定排序，来源内顺序不变）
+        if !plan.skip.is_empty() {
+            sensors.sort_by_key(|s| s.source);
+        }
+    }
+}
+
+/// 一次聚合中要跳过的来源
+pub struct PollPlan {
+    skip: Vec<SensorSource>,
+}
+
+impl PollPlan {
+    /// 不跳过任何来源
+    #[must_use]
+    pub const fn all() -> Self {
+        Self { skip: Vec::new() }
+    }
+
+    /// 本次是否读取 `source`
+    #[must_use]
+    pub fn reads(&self, source: SensorSource) -> bool {
+        !self.skip.contains(&source)
+    }
+}
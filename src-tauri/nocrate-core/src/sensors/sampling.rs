@@ -0,0 +1,101 @@
+// 读数累加与采样节流
+// 会话统计与历史记录共用：两者都按最小 / 最大 / 总和累加读数，
+// 且都只在距上次计入超过 MIN_SAMPLE_INTERVAL 时计入一次。
+//
+// 多个调用方（前端轮询、停转检测、MQTT 等）都会聚合传感器，
+// 不做节流时平均值会偏向调用频繁的时段。
+
+use std::time::{Duration, Instant};
+
+/// 两次计入的最短间隔
+pub const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(900);
+
+/// 一组读数的最小值、最大值与总和
+#[derive(Debug, Clone, Copy)]
+pub struct Accumulator {
+    pub min: f32,
+    pub max: f32,
+    sum: f64,
+    /// 计入的读数个数
+    pub samples: u64,
+}
+
+impl Accumulator {
+    #[must_use]
+    pub fn new(value: f32) -> Self {
+        Self {
+            min: value,
+            max: value,
+            sum: f64::from(value),
+            samples: 1,
+        }
+    }
+
+    pub fn add(&mut self, value: f32) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += f64::from(value);
+        self.samples += 1;
+    }
+
+    /// 平均值
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn avg(&self) -> f32 {
+        (self.sum / self.samples as f64) as f32
+    }
+}
+
+/// 采样节流：记录上次计入的时间
+#[derive(Debug, Default)]
+pub struct Throttle {
+    last: Option<Instant>,
+}
+
+impl Throttle {
+    /// `now` 时距上次计入已超过最短间隔
+    #[must_use]
+    pub fn due(&self, now: Instant) -> bool {
+        self.last
+            .is_none_or(|t| now.saturating_duration_since(t) >= MIN_SAMPLE_INTERVAL)
+    }
+
+    /// 若已到期则把 `now` 记为上次计入时间并返回 `true`
+    pub fn take(&mut self, now: Instant) -> bool {
+        let due = self.due(now);
+        if due {
+            self.last = Some(now);
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulator_tracks_min_max_avg() {
+        let mut acc = Accumulator::new(40.0);
+        acc.add(30.0);
+        acc.add(50.0);
+        acc.add(44.0);
+        assert_eq!(acc.min, 30.0);
+        assert_eq!(acc.max, 50.0);
+        assert_eq!(acc.samples, 4);
+        assert!((acc.avg() - 41.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn throttle_drops_samples_inside_interval() {
+        let t0 = Instant::now();
+        let mut throttle = Throttle::default();
+        assert!(throttle.due(t0));
+        assert!(throttle.take(t0));
+        assert!(!throttle.take(t0 + Duration::from_millis(500)));
+        // 被丢弃的读数不推迟下一次计入
+        assert!(throttle.take(t0 + MIN_SAMPLE_INTERVAL));
+        assert!(!throttle.due(t0 + MIN_SAMPLE_INTERVAL));
+    }
+}
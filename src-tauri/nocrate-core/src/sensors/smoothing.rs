@@ -0,0 +1,133 @@
+// 传感器滑动平均
+// ASUSHW / SIO 的部分通道读数跳动较大，风扇曲线与图表随之抖动。
+// 按传感器 ID 配置时间窗口（秒），聚合层以窗口内全部读数的平均值替换当前值。
+//
+// 窗口按时间而非样本数计算：多个调用方以不同频率读取传感器，
+// 按样本数计算时实际平滑时长会随调用频率变化。
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use super::UnifiedSensor;
+
+/// 各传感器最近的读数，由聚合层在每次读取后更新
+#[derive(Default)]
+pub struct SensorSmoother {
+    samples: Mutex<HashMap<String, VecDeque<(Instant, f32)>>>,
+}
+
+impl SensorSmoother {
+    /// 计入本次读数，并将 `windows` 中配置了窗口的传感器的值替换为窗口内平均值。
+    /// 未配置窗口（或窗口为 0）的传感器不受影响，其缓存一并清除
+    pub fn smooth(&self, sensors: &mut [UnifiedSensor], windows: &HashMap<String, u16>) {
+        self.smooth_at(sensors, windows, Instant::now());
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn smooth_at(
+        &self,
+        sensors: &mut [UnifiedSensor],
+        windows: &HashMap<String, u16>,
+        now: Instant,
+    ) {
+        let mut samples = self.samples.lock();
+        samples.retain(|id, _| windows.get(id).is_some_and(|&w| w > 0));
+        if windows.is_empty() {
+            return;
+        }
+
+        for sensor in sensors.iter_mut().filter(|s| s.value.is_finite()) {
+            let Some(&window) = windows.get(&sensor.id).filter(|&&w| w > 0) else {
+                continue;
+            };
+            let window = Duration::from_secs(window.into());
+            let buf = samples.entry(sensor.id.clone()).or_default();
+            while buf.front().is_some_and(|&(t, _)| now.duration_since(t) > window) {
+                let _ = buf.pop_front();
+            }
+            buf.push_back((now, sensor.value));
+
+            let sum: f64 = buf.iter().map(|&(_, v)| f64::from(v)).sum();
+            sensor.value = (sum / buf.len() as f64) as f32;
+        }
+        drop(samples);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::super::{SensorKind, SensorSource};
+    use super::*;
+
+    fn sensor(id: &str, value: f32) -> UnifiedSensor {
+        UnifiedSensor::new(
+            id.to_owned(),
+            SensorSource::Sio,
+            SensorKind::Temperature,
+            id,
+            "test",
+            value,
+        )
+    }
+
+    fn smooth(
+        smoother: &SensorSmoother,
+        t0: Instant,
+        secs: u64,
+        values: &[(&str, f32)],
+    ) -> Vec<f32> {
+        let mut sensors: Vec<_> = values.iter().map(|&(id, v)| sensor(id, v)).collect();
+        let windows = HashMap::from([("cpu".to_owned(), 5), ("off".to_owned(), 0)]);
+        smoother.smooth_at(&mut sensors, &windows, t0 + Duration::from_secs(secs));
+        sensors.iter().map(|s| s.value).collect()
+    }
+
+    #[test]
+    fn averages_readings_inside_window() {
+        let smoother = SensorSmoother::default();
+        let t0 = Instant::now();
+        assert_eq!(smooth(&smoother, t0, 0, &[("cpu", 40.0)]), [40.0]);
+        assert_eq!(smooth(&smoother, t0, 2, &[("cpu", 60.0)]), [50.0]);
+        assert_eq!(smooth(&smoother, t0, 5, &[("cpu", 80.0)]), [60.0]);
+        // 6 秒时 0 秒的读数已超出 5 秒窗口
+        assert_eq!(smooth(&smoother, t0, 6, &[("cpu", 100.0)]), [80.0]);
+    }
+
+    #[test]
+    fn leaves_unconfigured_and_non_finite_readings() {
+        let smoother = SensorSmoother::default();
+        let t0 = Instant::now();
+        let _ = smooth(
+            &smoother,
+            t0,
+            0,
+            &[("cpu", 40.0), ("off", 40.0), ("gpu", 40.0)],
+        );
+        let values = smooth(
+            &smoother,
+            t0,
+            1,
+            &[("cpu", f32::NAN), ("off", 60.0), ("gpu", 60.0)],
+        );
+        assert!(values[0].is_nan());
+        assert_eq!(values[1..], [60.0, 60.0]);
+        assert_eq!(smooth(&smoother, t0, 2, &[("cpu", 60.0)]), [50.0]);
+
+        let ids: Vec<String> = smoother.samples.lock().keys().cloned().collect();
+        assert_eq!(ids, ["cpu"]);
+    }
+
+    #[test]
+    fn clears_cache_when_window_removed() {
+        let smoother = SensorSmoother::default();
+        let t0 = Instant::now();
+        let _ = smooth(&smoother, t0, 0, &[("cpu", 40.0)]);
+        let mut sensors = [sensor("cpu", 60.0)];
+        smoother.smooth_at(&mut sensors, &HashMap::new(), t0 + Duration::from_secs(1));
+        assert_eq!(sensors[0].value, 60.0);
+        assert!(smoother.samples.lock().is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+// 传感器会话统计
+// 记录自应用启动（或上次重置）以来每个传感器的最小 / 平均 / 最大值，
+// 并按采样时的散热模式另行分组，与 HWiNFO 的统计列对应。
+// 累加与节流见 sampling 模块。
+
+use std::collections::HashMap;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use super::sampling::{Accumulator, Throttle};
+use super::UnifiedSensor;
+use crate::wmi::asus_mgmt::ThermalProfile;
+
+/// 单个传感器的统计值
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SensorStats {
+    pub min: f32,
+    pub avg: f32,
+    pub max: f32,
+    /// 计入的读数个数
+    pub samples: u64,
+}
+
+/// 全部传感器的统计
+#[derive(Debug, Clone, Serialize)]
+pub struct SensorStatsSnapshot {
+    /// 开始统计的时间（Unix 秒）
+    pub since: u64,
+    /// 按传感器 ID
+    pub session: HashMap<String, SensorStats>,
+    /// 按散热模式、再按传感器 ID；无法读取模式时的读数只计入 `session`
+    pub by_profile: HashMap<ThermalProfile, HashMap<String, SensorStats>>,
+}
+
+impl From<&Accumulator> for SensorStats {
+    fn from(acc: &Accumulator) -> Self {
+        Self {
+            min: acc.min,
+            avg: acc.avg(),
+            max: acc.max,
+            samples: acc.samples,
+        }
+    }
+}
+
+struct Inner {
+    since: SystemTime,
+    throttle: Throttle,
+    session: HashMap<String, Accumulator>,
+    by_profile: HashMap<ThermalProfile, HashMap<String, Accumulator>>,
+}
+
+impl Inner {
+    fn new() -> Self {
+        Self {
+            since: SystemTime::now(),
+            throttle: Throttle::default(),
+            session: HashMap::new(),
+            by_profile: HashMap::new(),
+        }
+    }
+}
+
+/// 会话统计，由聚合层在每次读取后更新
+pub struct SensorStatsTracker {
+    inner: Mutex<Inner>,
+}
+
+impl Default for SensorStatsTracker {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Inner::new()),
+        }
+    }
+}
+
+impl SensorStatsTracker {
+    /// 距上次计入已超过最短间隔
+    #[must_use]
+    pub fn due(&self) -> bool {
+        self.inner.lock().throttle.due(Instant::now())
+    }
+
+    /// 计入一次读数；`profile` 为采样时的散热模式
+    pub fn record(&self, sensors: &[UnifiedSensor], profile: Option<ThermalProfile>) {
+        self.record_at(sensors, profile, Instant::now());
+    }
+
+    fn record_at(&self, sensors: &[UnifiedSensor], profile: Option<ThermalProfile>, now: Instant) {
+        let mut inner = self.inner.lock();
+        if !inner.throttle.take(now) {
+            return;
+        }
+
+        for sensor in sensors.iter().filter(|s| s.value.is_finite()) {
+            add(&mut inner.session, &sensor.id, sensor.value);
+            if let Some(profile) = profile {
+                add(
+                    inner.by_profile.entry(profile).or_default(),
+                    &sensor.id,
+                    sensor.value,
+                );
+            }
+        }
+    }
+
+    /// 当前统计
+    #[must_use]
+    pub fn snapshot(&self) -> SensorStatsSnapshot {
+        let inner = self.inner.lock();
+        let stats = |map: &HashMap<String, Accumulator>| {
+            map.iter()
+                .map(|(id, acc)| (id.clone(), SensorStats::from(acc)))
+                .collect()
+        };
+        SensorStatsSnapshot {
+            since: inner
+                .since
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            session: stats(&inner.session),
+            by_profile: inner
+                .by_profile
+                .iter()
+                .map(|(&profile, map)| (profile, stats(map)))
+                .collect(),
+        }
+    }
+
+    /// 清空统计并从现在重新开始
+    pub fn reset(&self) {
+        *self.inner.lock() = Inner::new();
+    }
+}
+
+fn add(map: &mut HashMap<String, Accumulator>, id: &str, value: f32) {
+    match map.get_mut(id) {
+        Some(acc) => acc.add(value),
+        None => {
+            let _ = map.insert(id.to_owned(), Accumulator::new(value));
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::{SensorKind, SensorSource};
+    use super::*;
+
+    fn sensor(id: &str, value: f32) -> UnifiedSensor {
+        UnifiedSensor::new(
+            id.to_owned(),
+            SensorSource::Sio,
+            SensorKind::Temperature,
+            id,
+            "test",
+            value,
+        )
+    }
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    #[test]
+    fn session_stats() {
+        let tracker = SensorStatsTracker::default();
+        let t0 = Instant::now();
+        for (t, value) in [(0, 40.0), (1, 60.0), (2, 50.0)] {
+            tracker.record_at(&[sensor("cpu", value)], None, t0 + secs(t));
+        }
+        let stats = tracker.snapshot().session["cpu"];
+        assert_eq!(stats.min, 40.0);
+        assert_eq!(stats.avg, 50.0);
+        assert_eq!(stats.max, 60.0);
+        assert_eq!(stats.samples, 3);
+    }
+
+    #[test]
+    fn skips_non_finite_and_throttled_readings() {
+        let tracker = SensorStatsTracker::default();
+        let t0 = Instant::now();
+        tracker.record_at(&[sensor("cpu", 40.0), sensor("nan", f32::NAN)], None, t0);
+        tracker.record_at(
+            &[sensor("cpu", 90.0)],
+            None,
+            t0 + Duration::from_millis(100),
+        );
+        tracker.record_at(&[sensor("cpu", f32::INFINITY)], None, t0 + secs(1));
+
+        let snapshot = tracker.snapshot();
+        assert!(!snapshot.session.contains_key("nan"));
+        let stats = snapshot.session["cpu"];
+        assert_eq!(stats.max, 40.0);
+        assert_eq!(stats.samples, 1);
+    }
+
+    #[test]
+    fn groups_by_profile() {
+        let tracker = SensorStatsTracker::default();
+        let t0 = Instant::now();
+        tracker.record_at(&[sensor("cpu", 40.0)], Some(ThermalProfile::Silent), t0);
+        tracker.record_at(
+            &[sensor("cpu", 80.0)],
+            Some(ThermalProfile::Performance),
+            t0 + secs(1),
+        );
+        tracker.record_at(&[sensor("cpu", 60.0)], None, t0 + secs(2));
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.session["cpu"].samples, 3);
+        assert_eq!(snapshot.by_profile.len(), 2);
+        let silent = snapshot.by_profile[&ThermalProfile::Silent]["cpu"];
+        assert_eq!((silent.max, silent.samples), (40.0, 1));
+        let performance = snapshot.by_profile[&ThermalProfile::Performance]["cpu"];
+        assert_eq!((performance.min, performance.samples), (80.0, 1));
+    }
+
+    #[test]
+    fn reset_clears_stats_and_throttle() {
+        let tracker = SensorStatsTracker::default();
+        tracker.record(&[sensor("cpu", 40.0)], Some(ThermalProfile::Standard));
+        assert!(!tracker.due());
+
+        tracker.reset();
+        let snapshot = tracker.snapshot();
+        assert!(snapshot.session.is_empty());
+        assert!(snapshot.by_profile.is_empty());
+        assert!(tracker.due());
+
+        tracker.record(&[sensor("cpu", 70.0)], None);
+        assert_eq!(tracker.snapshot().session["cpu"].min, 70.0);
+    }
+}
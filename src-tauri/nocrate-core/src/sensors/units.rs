@@ -0,0 +1,58 @@
+// 显示单位偏好
+// 读数本身始终以 °C / RPM 等原始单位保存与比较（`value` / `raw_value`），
+// 另附按用户偏好换算的 `display_value` 与 `display_unit`，前端、OSD 与本地 API 据此显示，结果一致
+
+use serde::{Deserialize, Serialize};
+
+use super::SensorKind;
+
+/// 温度显示单位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// 将摄氏度换算为该单位
+    #[must_use]
+    pub const fn convert(self, celsius: f32) -> f32 {
+        match self {
+            Self::Celsius => celsius,
+            Self::Fahrenheit => celsius.mul_add(1.8, 32.0),
+        }
+    }
+
+    /// 单位符号
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+        }
+    }
+}
+
+/// Display units for readings returned by the backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitsConfig {
+    /// Unit of `display_value` for temperatures
+    pub temperature: TemperatureUnit,
+}
+
+impl UnitsConfig {
+    /// 按偏好换算一个读数，返回 `(显示值, 单位符号)`；温度以外的物理量原样返回
+    #[must_use]
+    pub const fn display(self, kind: SensorKind, value: f32) -> (f32, &'static str) {
+        match kind {
+            SensorKind::Temperature => (self.temperature.convert(value), self.temperature.symbol()),
+            SensorKind::Fan => (value, "RPM"),
+            SensorKind::FanPercent => (value, "%"),
+            SensorKind::Current => (value, "A"),
+            SensorKind::Flow => (value, "L/h"),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// Super I/O 寄存器访问方式
+// 芯片寄存器有两条读取路径：
+// - WinRing0 内核驱动直接读写 I/O 端口（DriverHandle）
+// - ASUS 桌面 WMI 后端的 asio_hw_fun19/21/23（AsioHwAccess），由 ASUS 驱动代为访问，无需加载 WinRing0
+//
+// WMI 路径只能读取，且只覆盖 Nuvoton 风格的 bank 寄存器与逻辑设备寄存器；
+// 需要直接端口读写的操作（ITE 芯片、清除入侵锁存位）仍依赖内核驱动。
+
+use super::driver::DriverHandle;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::wmi::asio_hw::{self, HwmRegister};
+use crate::wmi::thread::WmiThread;
+
+/// Super I/O 寄存器访问方式
+pub trait SioAccess: Send + Sync {
+    /// 访问方式名称（日志与错误信息用）
+    fn name(&self) -> &'static str;
+
+    /// 读取 HW Monitor 的 bank 寄存器
+    /// `base` 为 HW Monitor 基地址，WMI 路径由 ASUS 驱动自行确定，忽略此参数
+    fn read_hwm(&self, base: u16, bank: u8, reg: u8) -> Result<u8>;
+
+    /// 按顺序读取多个 HW Monitor 寄存器，默认逐个读取
+    fn read_hwm_many(&self, base: u16, registers: &[HwmRegister]) -> Result<Vec<u8>> {
+        registers
+            .iter()
+            .map(|&(bank, reg)| self.read_hwm(base, bank, reg))
+            .collect()
+    }
+
+    /// 读取逻辑设备（LDN）的配置寄存器，0x20/0x21 等全局寄存器与所选 LDN 无关
+    fn read_ldn(&self, config_port: u16, ldn: u8, reg: u8) -> Result<u8>;
+
+    /// 直接端口访问所需的驱动句柄，WMI 路径为 `None`
+    fn driver(&self) -> Option<&DriverHandle>;
+}
+
+/// 取得直接端口访问的驱动句柄，`what` 为需要它的操作（用于错误信息）
+pub fn require_driver(access: &dyn SioAccess, what: Msg) -> Result<&DriverHandle> {
+    access.driver().ok_or_else(|| {
+        NoCrateError::Sio(i18n::tf(
+            Msg::SioRequiresDriver,
+            &[("what", &i18n::t(what)), ("access", &access.name())],
+        ))
+    })
+}
+
+impl SioAccess for DriverHandle {
+    fn name(&self) -> &'static str {
+        "WinRing0"
+    }
+
+    /// Nuvoton ISA HW Monitor 使用 (base+5) 作为地址端口、(base+6) 作为数据端口
+    /// bank 切换通过写寄存器 0x4E 实现
+    fn read_hwm(&self, base: u16, bank: u8, reg: u8) -> Result<u8> {
+        // 切换 bank：地址端口 ← 0x4E，数据端口 ← bank
+        self.write_io_port_byte(base + 5, 0x4E)?;
+        self.write_io_port_byte(base + 6, bank)?;
+        // 读取目标寄存器：地址端口 ← reg，数据端口 → value
+        self.write_io_port_byte(base + 5, reg)?;
+        self.read_io_port_byte(base + 6)
+    }
+
+    /// 以 Nuvoton 扩展功能模式读取，读完即退出
+    fn read_ldn(&self, config_port: u16, ldn: u8, reg: u8) -> Result<u8> {
+        let data_port = config_port + 1;
+
+        // 进入扩展功能模式：向配置端口连写两次 0x87
+        self.write_io_port_byte(config_port, 0x87)?;
+        self.write_io_port_byte(config_port, 0x87)?;
+
+        self.write_io_port_byte(config_port, 0x07)?;
+        self.write_io_port_byte(data_port, ldn)?;
+        self.write_io_port_byte(config_port, reg)?;
+        let value = self.read_io_port_byte(data_port)?;
+
+        // 退出扩展功能模式
+        self.write_io_port_byte(config_port, 0xAA)?;
+        Ok(value)
+    }
+
+    fn driver(&self) -> Option<&DriverHandle> {
+        Some(self)
+    }
+}
+
+/// 经 ASUS 桌面 WMI 后端 `asio_hw_fun*` 访问
+/// 每次读取都在 WMI 线程上执行，批量读取合并为一次 fun23 调用
+pub struct AsioHwAccess {
+    wmi: WmiThread,
+}
+
+impl AsioHwAccess {
+    #[must_use]
+    pub const fn new(wmi: WmiThread) -> Self {
+        Self { wmi }
+    }
+}
+
+impl SioAccess for AsioHwAccess {
+    fn name(&self) -> &'static str {
+        "ASUS WMI"
+    }
+
+    fn read_hwm(&self, _base: u16, bank: u8, reg: u8) -> Result<u8> {
+        self.wmi
+            .execute(move |conn| asio_hw::read_hwm_register(conn, (bank, reg)))
+    }
+
+    fn read_hwm_many(&self, _base: u16, registers: &[HwmRegister]) -> Result<Vec<u8>> {
+        let registers = registers.to_vec();
+        self.wmi
+            .execute(move |conn| asio_hw::read_hwm_registers(conn, &registers))
+    }
+
+    /// fun19 使用 ASUS 驱动自己的配置端口，忽略 `config_port`
+    fn read_ldn(&self, _config_port: u16, ldn: u8, reg: u8) -> Result<u8> {
+        self.wmi
+            .execute(move |conn| asio_hw::read_ldn_register(conn, ldn, reg))
+    }
+
+    fn driver(&self) -> Option<&DriverHandle> {
+        None
+    }
+}
@@ -0,0 +1,128 @@
+// Super I/O 芯片通用类型定义与 Chip trait
+
+use serde::Serialize;
+
+use super::access::SioAccess;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::wmi::asus_mgmt::FanPresence;
+
+/// 风扇转速读数
+#[derive(Debug, Clone, Serialize)]
+pub struct FanReading {
+    /// 风扇名称（如 "CPU Fan"、"Chassis #1"）
+    pub name: String,
+    /// 转速 (RPM)，0 表示停转或未接入
+    pub rpm: u32,
+    /// Super I/O 物理通道编号
+    pub channel: u8,
+    /// 区分停转与未接入：芯片只按转速给出，`SioMonitor` 再按启动以来是否转过修正
+    pub status: FanPresence,
+}
+
+/// 温度传感器读数
+#[derive(Debug, Clone, Serialize)]
+pub struct TempReading {
+    /// 传感器名称（如 "CPU"、"Mainboard"、"AUXTIN1"）
+    pub name: String,
+    /// 温度（摄氏度）
+    pub temp_c: f32,
+    /// Super I/O 物理通道编号
+    pub channel: u8,
+}
+
+/// 机箱入侵检测读数（CASEOPEN#）
+#[derive(Debug, Clone, Serialize)]
+pub struct IntrusionReading {
+    /// 检测通道名称（如 "CASEOPEN0"）
+    pub name: String,
+    /// 是否检测到机箱被打开（锁存位，需手动清除）
+    pub triggered: bool,
+}
+
+/// 当前所有传感器读数的快照
+#[derive(Debug, Clone, Serialize)]
+pub struct SioSnapshot {
+    /// 所有风扇读数
+    pub fans: Vec<FanReading>,
+    /// 所有温度读数
+    pub temps: Vec<TempReading>,
+    /// 机箱入侵状态，`None` 表示芯片不支持或本次读取失败
+    pub intrusion: Option<IntrusionReading>,
+    /// 芯片型号名称
+    pub chip_name: String,
+}
+
+/// HW Monitor 寄存器转储中的一个 bank
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterBank {
+    /// bank 编号，没有 bank 的芯片（ITE）为 0
+    pub bank: u8,
+    /// 寄存器 0x00–0xFF 的值
+    pub registers: Vec<u8>,
+}
+
+/// HW Monitor 全部寄存器的转储，用于远程排查温度 / 风扇通道映射
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterDump {
+    /// 芯片型号名称
+    pub chip_name: String,
+    /// 芯片 ID（配置寄存器 0x20/0x21）
+    pub chip_id: u16,
+    /// HW Monitor I/O 基地址
+    pub base_addr: u16,
+    /// 访问方式（`WinRing0` / ASUS WMI）
+    pub access: &'static str,
+    pub banks: Vec<RegisterBank>,
+}
+
+/// Super I/O 芯片状态信息
+#[derive(Debug, Clone, Serialize)]
+pub struct SioStatus {
+    /// 是否可用
+    pub available: bool,
+    /// 芯片型号（如有）
+    pub chip_name: Option<String>,
+    /// 错误信息（如有）
+    pub error: Option<String>,
+}
+
+/// Super I/O 芯片 trait
+/// 每种芯片系列（Nuvoton、ITE）各自实现此 trait
+pub trait Chip: Send + Sync {
+    /// 返回芯片型号名称
+    fn chip_name(&self) -> &str;
+
+    /// HW Monitor I/O 基地址，不经端口访问的芯片（模拟）为 `None`
+    fn base_addr(&self) -> Option<u16> {
+        None
+    }
+
+    /// 读取所有风扇转速
+    fn read_fans(&self, io: &dyn SioAccess) -> Result<Vec<FanReading>>;
+
+    /// 读取所有温度传感器
+    fn read_temps(&self, io: &dyn SioAccess) -> Result<Vec<TempReading>>;
+
+    /// 读取机箱入侵状态，默认不支持
+    fn read_intrusion(&self, _io: &dyn SioAccess) -> Result<Option<IntrusionReading>> {
+        Ok(None)
+    }
+
+    /// 清除机箱入侵锁存位
+    fn clear_intrusion(&self, _io: &dyn SioAccess) -> Result<()> {
+        Err(NoCrateError::Sio(i18n::tf(
+            Msg::SioNoIntrusion,
+            &[("chip", &self.chip_name())],
+        )))
+    }
+
+    /// 转储 HW Monitor 全部寄存器，默认不支持
+    /// 部分告警状态寄存器读后清零，转储会清掉这些锁存位
+    fn dump_registers(&self, _io: &dyn SioAccess) -> Result<RegisterDump> {
+        Err(NoCrateError::Sio(i18n::tf(
+            Msg::SioNoDump,
+            &[("chip", &self.chip_name())],
+        )))
+    }
+}
@@ -1,47 +1,45 @@
 // Super I/O 芯片自动检测
 // 通过 I/O 端口 0x2E/0x4E 探测配置空间，识别 Nuvoton / ITE 芯片型号并读取 HW Monitor 基地址
+// 经 ASUS WMI 访问时只能读取逻辑设备寄存器，仅探测 Nuvoton
 
+use super::access::SioAccess;
 use super::chips::Chip;
-use super::driver::DriverHandle;
 use super::ite::IteChip;
+use super::lpc;
 use super::nuvoton::NuvotonChip;
 use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+/// Nuvoton HW Monitor 逻辑设备号
+pub(super) const NUVOTON_HWM_LDN: u8 = 0x0B;
 
 /// 探测芯片，返回初始化好的 Chip 实现
-pub fn detect_chip(drv: &DriverHandle) -> Result<Box<dyn Chip>> {
-    eprintln!("[SIO] 开始芯片检测...");
+pub fn detect_chip(io: &dyn SioAccess) -> Result<Box<dyn Chip>> {
+    eprintln!("[SIO] 开始芯片检测（{}）...", io.name());
     // 依次在两个标准配置端口上探测
     for &config_port in &[0x2E_u16, 0x4E_u16] {
         eprintln!("[SIO] 探测配置端口 0x{config_port:02X}");
         // 先尝试 Nuvoton/Winbond（Fintek 共用入口序列）
-        if let Some(chip) = try_nuvoton(drv, config_port)? {
+        if let Some(chip) = try_nuvoton(io, config_port)? {
             return Ok(chip);
         }
 
         // 再尝试 ITE
-        if let Some(chip) = try_ite(drv, config_port)? {
+        if let Some(chip) = try_ite(io, config_port)? {
             return Ok(chip);
         }
     }
 
-    Err(NoCrateError::Sio(
-        "未检测到已支持的 Super I/O 芯片（Nuvoton NCT67xx / ITE IT86xx）".into(),
+    Err(NoCrateError::DeviceNotPresent(
+        i18n::t(Msg::SioChipNotFound).into(),
     ))
 }
 
 /// 尝试以 Nuvoton/Winbond 协议探测
-fn try_nuvoton(drv: &DriverHandle, port: u16) -> Result<Option<Box<dyn Chip>>> {
-    let data_port = port + 1;
-
-    // Nuvoton 进入扩展功能模式：向配置端口连写两次 0x87
-    drv.write_io_port_byte(port, 0x87)?;
-    drv.write_io_port_byte(port, 0x87)?;
-
+fn try_nuvoton(io: &dyn SioAccess, port: u16) -> Result<Option<Box<dyn Chip>>> {
     // 读取芯片 ID（寄存器 0x20 高字节、0x21 低字节）
-    drv.write_io_port_byte(port, 0x20)?;
-    let id_high = drv.read_io_port_byte(data_port)? as u16;
-    drv.write_io_port_byte(port, 0x21)?;
-    let id_low = drv.read_io_port_byte(data_port)? as u16;
+    let id_high = io.read_ldn(port, NUVOTON_HWM_LDN, 0x20)? as u16;
+    let id_low = io.read_ldn(port, NUVOTON_HWM_LDN, 0x21)? as u16;
 
     let chip_id = (id_high << 8) | id_low;
 
@@ -57,28 +55,15 @@ fn try_nuvoton(drv: &DriverHandle, port: u16) -> Result<Option<Box<dyn Chip>>> {
         0xC800 => "NCT6791D",
         0xC910 => "NCT6792D",
         0xC950 => "NCT6795D",
-        _ => {
-            // 不是 Nuvoton，退出扩展功能模式
-            drv.write_io_port_byte(port, 0xAA)?;
-            return Ok(None);
-        }
+        _ => return Ok(None),
     };
 
-    // 选择 LDN 0x0B（Nuvoton HW Monitor 逻辑设备号）
-    drv.write_io_port_byte(port, 0x07)?;
-    drv.write_io_port_byte(data_port, 0x0B)?;
-
     // 读取 HW Monitor 基地址（寄存器 0x60 高字节、0x61 低字节）
-    drv.write_io_port_byte(port, 0x60)?;
-    let base_high = drv.read_io_port_byte(data_port)? as u16;
-    drv.write_io_port_byte(port, 0x61)?;
-    let base_low = drv.read_io_port_byte(data_port)? as u16;
+    let base_high = io.read_ldn(port, NUVOTON_HWM_LDN, 0x60)? as u16;
+    let base_low = io.read_ldn(port, NUVOTON_HWM_LDN, 0x61)? as u16;
 
     let base_addr = (base_high << 8) | base_low;
 
-    // 退出扩展功能模式
-    drv.write_io_port_byte(port, 0xAA)?;
-
     if base_addr == 0 || base_addr == 0xFFFF {
         return Ok(None);
     }
@@ -87,20 +72,27 @@ fn try_nuvoton(drv: &DriverHandle, port: u16) -> Result<Option<Box<dyn Chip>>> {
         "SIO: 检测到 {chip_name}，Chip ID=0x{chip_id:04X}，HW Monitor 基地址=0x{base_addr:04X}"
     );
 
-    // 确保 LPC 桥解码此 I/O 范围（AMD FCH 需要显式配置）
-    if let Err(e) = drv.enable_lpc_io_decode(base_addr) {
-        eprintln!("[SIO] LPC I/O 解码配置警告: {e}");
+    // 确保 LPC 桥解码此 I/O 范围（AMD FCH / Intel PCH 固件未转发时需要显式配置），WMI 路径由 ASUS 驱动负责
+    if let Some(drv) = io.driver() {
+        if let Err(e) = lpc::enable_io_decode(drv, base_addr) {
+            eprintln!("[SIO] LPC I/O 解码配置警告: {e}");
+        }
     }
 
     Ok(Some(Box::new(NuvotonChip::new(
         chip_name.to_string(),
         chip_id,
         base_addr,
+        port,
     ))))
 }
 
 /// 尝试以 ITE 协议探测
-fn try_ite(drv: &DriverHandle, port: u16) -> Result<Option<Box<dyn Chip>>> {
+fn try_ite(io: &dyn SioAccess, port: u16) -> Result<Option<Box<dyn Chip>>> {
+    // ITE 的配置模式需要直接端口访问
+    let Some(drv) = io.driver() else {
+        return Ok(None);
+    };
     let data_port = port + 1;
 
     // ITE 进入配置模式的密钥序列（取决于端口地址）
@@ -131,6 +123,8 @@ fn try_ite(drv: &DriverHandle, port: u16) -> Result<Option<Box<dyn Chip>>> {
         0x8695 => "IT8695E",
         0x8686 => "IT8686E",
         0x8628 => "IT8628E",
+        0x8665 => "IT8665E",
+        0x8792 => "IT8792E",
         _ => {
             // 不是 ITE，退出配置模式
             drv.write_io_port_byte(port, 0x02)?;
@@ -0,0 +1,112 @@
+// Super I/O 诊断
+// 按需读取 HW Monitor 逻辑设备的激活状态、基地址、关键寄存器与原始端口值，排查读数异常时导出
+// 只覆盖 Nuvoton 风格的配置空间；原始端口读取需要 WinRing0 驱动，经 ASUS WMI 访问时跳过
+
+use serde::Serialize;
+
+use super::access::SioAccess;
+use super::detect::NUVOTON_HWM_LDN;
+use crate::error::Result;
+
+/// 两个标准配置端口
+const CONFIG_PORTS: [u16; 2] = [0x2E, 0x4E];
+
+/// 相对 HW Monitor 基地址读取原始值的偏移
+const RAW_OFFSETS: [u16; 5] = [0, 1, 5, 6, 7];
+
+/// Nuvoton 厂商 ID 寄存器（bank 0, 0x4F）的期望值
+const NUVOTON_VENDOR_ID: u8 = 0x5C;
+
+/// 一次原始端口读取
+#[derive(Debug, Clone, Serialize)]
+pub struct RawPortRead {
+    pub port: u16,
+    pub value: u8,
+}
+
+/// 一个配置端口上的诊断结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SioPortDiagnostics {
+    /// 配置端口（0x2E / 0x4E）
+    pub config_port: u16,
+    /// 芯片 ID（寄存器 0x20/0x21），0xFFFF 表示端口上没有芯片
+    pub chip_id: u16,
+    /// HW Monitor 逻辑设备的激活寄存器（0x30）
+    pub activate_reg: u8,
+    /// 激活寄存器 bit0
+    pub active: bool,
+    /// HW Monitor 基地址（寄存器 0x60/0x61）
+    pub base: u16,
+    /// bank 0 寄存器 0x4F（Nuvoton 厂商 ID），基地址无效时为 `None`
+    pub vendor_id: Option<u8>,
+    /// 厂商 ID 是否为 Nuvoton 的 0x5C
+    pub vendor_id_ok: bool,
+    /// bank 0 寄存器 0x27（SYSTIN 温度原始值）
+    pub systin_raw: Option<u8>,
+    /// 基地址附近端口的原始值，仅直接端口访问时读取
+    pub raw_ports: Vec<RawPortRead>,
+    /// 以 base+0 / base+1 为地址 / 数据端口读取的寄存器 0x4F，仅直接端口访问时读取
+    pub alt_vendor_id: Option<u8>,
+}
+
+/// Super I/O 诊断结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SioDiagnostics {
+    /// 访问方式（`WinRing0` / ASUS WMI）
+    pub access: &'static str,
+    pub ports: Vec<SioPortDiagnostics>,
+}
+
+/// 在两个标准配置端口上执行诊断
+pub fn run(io: &dyn SioAccess) -> Result<SioDiagnostics> {
+    let ports = CONFIG_PORTS
+        .iter()
+        .map(|&port| diagnose_port(io, port))
+        .collect::<Result<_>>()?;
+    Ok(SioDiagnostics {
+        access: io.name(),
+        ports,
+    })
+}
+
+fn diagnose_port(io: &dyn SioAccess, config_port: u16) -> Result<SioPortDiagnostics> {
+    let ldn = |reg| io.read_ldn(config_port, NUVOTON_HWM_LDN, reg);
+    let chip_id = (u16::from(ldn(0x20)?) << 8) | u16::from(ldn(0x21)?);
+    let activate_reg = ldn(0x30)?;
+    let base = (u16::from(ldn(0x60)?) << 8) | u16::from(ldn(0x61)?);
+
+    let mut diag = SioPortDiagnostics {
+        config_port,
+        chip_id,
+        activate_reg,
+        active: activate_reg & 1 != 0,
+        base,
+        vendor_id: None,
+        vendor_id_ok: false,
+        systin_raw: None,
+        raw_ports: Vec::new(),
+        alt_vendor_id: None,
+    };
+    if base == 0 || base == 0xFFFF {
+        return Ok(diag);
+    }
+
+    let regs = io.read_hwm_many(base, &[(0, 0x4F), (0, 0x27)])?;
+    diag.vendor_id = Some(regs[0]);
+    diag.vendor_id_ok = regs[0] == NUVOTON_VENDOR_ID;
+    diag.systin_raw = Some(regs[1]);
+
+    if let Some(drv) = io.driver() {
+        for offset in RAW_OFFSETS {
+            let port = base + offset;
+            diag.raw_ports.push(RawPortRead {
+                port,
+                value: drv.read_io_port_byte(port)?,
+            });
+        }
+        // 部分芯片把 base+0 / base+1 作为地址 / 数据端口
+        drv.write_io_port_byte(base, 0x4F)?;
+        diag.alt_vendor_id = Some(drv.read_io_port_byte(base + 1)?);
+    }
+    Ok(diag)
+}
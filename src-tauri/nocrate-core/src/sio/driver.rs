@@ -0,0 +1,815 @@
+// WinRing0x64 内核驱动管理
+// 负责驱动安装、卸载、设备句柄管理，以及 I/O 端口读写原语
+// 远程句柄不加载驱动，把原语转发给持有驱动的进程（如以 LocalSystem 运行的 NoCrate 服务）
+#![allow(unsafe_code)]
+
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_SERVICE_ALREADY_RUNNING, ERROR_SERVICE_DOES_NOT_EXIST, HANDLE,
+    INVALID_HANDLE_VALUE,
+};
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_ATTRIBUTE_NORMAL, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::Services::{
+    CloseServiceHandle, ControlService, CreateServiceW, DeleteService, OpenSCManagerW,
+    OpenServiceW, QueryServiceConfigW, QueryServiceStatus, StartServiceW, QUERY_SERVICE_CONFIGW,
+    SC_HANDLE, SC_MANAGER_ALL_ACCESS, SC_MANAGER_CONNECT, SERVICE_ALL_ACCESS,
+    SERVICE_CONTROL_STOP, SERVICE_DEMAND_START, SERVICE_ERROR_NORMAL, SERVICE_KERNEL_DRIVER,
+    SERVICE_QUERY_CONFIG, SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_STATUS,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::sio::embedded;
+
+/// WinRing0 IOCTL 命令码
+/// CTL_CODE(DeviceType=40000, Function, METHOD_BUFFERED, Access)
+/// READ_IO_PORT_BYTE:  CTL_CODE(0x9C40, 0x833, 0, FILE_READ_ACCESS=1)
+/// WRITE_IO_PORT_BYTE: CTL_CODE(0x9C40, 0x836, 0, FILE_WRITE_ACCESS=2)
+/// READ_PCI_CONFIG:    CTL_CODE(0x9C40, 0x851, 0, FILE_READ_ACCESS=1)
+/// WRITE_PCI_CONFIG:   CTL_CODE(0x9C40, 0x852, 0, FILE_WRITE_ACCESS=2)
+const IOCTL_OLS_READ_IO_PORT_BYTE: u32 = 0x9C40_60CC;
+const IOCTL_OLS_WRITE_IO_PORT_BYTE: u32 = 0x9C40_A0D8;
+/// DWORD I/O 端口读写（用于 PCI CF8/CFC 直接访问）
+/// READ_IO_PORT_DWORD: CTL_CODE(0x9C40, 0x835, 0, FILE_READ_ACCESS=1)
+const IOCTL_OLS_READ_IO_PORT_DWORD: u32 = 0x9C40_60D4;
+/// WRITE_IO_PORT_DWORD: CTL_CODE(0x9C40, 0x838, 0, FILE_WRITE_ACCESS=2)
+const IOCTL_OLS_WRITE_IO_PORT_DWORD: u32 = 0x9C40_A0E0;
+/// READ_MSR: CTL_CODE(0x9C40, 0x821, 0, FILE_ANY_ACCESS=0)
+const IOCTL_OLS_READ_MSR: u32 = 0x9C40_2084;
+/// `GET_DRIVER_VERSION`: `CTL_CODE(0x9C40, 0x800, 0, FILE_ANY_ACCESS=0)`，输出 major.minor.revision.release 各一字节
+const IOCTL_OLS_GET_DRIVER_VERSION: u32 = 0x9C40_2000;
+
+/// 驱动设备路径
+const DEVICE_PATH: &str = r"\\.\WinRing0_1_2_0";
+/// 驱动服务名称
+const SERVICE_NAME: &str = "WinRing0_1_2_0";
+
+/// 转发给持有驱动的进程执行的访问原语
+/// PCI 配置空间与 SMN 访问按整个事务转发，由执行方串行化 CF8/CFC 并按寄存器检查白名单
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PortOp {
+    ReadByte {
+        port: u16,
+    },
+    WriteByte {
+        port: u16,
+        value: u8,
+    },
+    ReadPci {
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u32,
+    },
+    WritePci {
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u32,
+        value: u32,
+    },
+    ReadSmn {
+        addr: u32,
+    },
+    ReadMsr {
+        index: u32,
+    },
+}
+
+impl PortOp {
+    /// 是否只读取、不改变硬件状态，只有这类原语可以在连接中断后重发
+    #[must_use]
+    pub const fn is_read(self) -> bool {
+        !matches!(self, Self::WriteByte { .. } | Self::WritePci { .. })
+    }
+}
+
+/// 执行 [`PortOp`] 的另一进程
+pub trait PortForwarder: Send + Sync {
+    /// 执行一个原语；读操作返回读到的值，写操作返回 0
+    fn forward(&self, op: PortOp) -> Result<u64>;
+}
+
+/// 驱动服务在服务控制管理器中的登记状态
+#[derive(Debug, Clone, Default)]
+pub struct ServiceState {
+    /// 服务已注册
+    pub installed: bool,
+    /// 服务正在运行，即驱动已加载
+    pub running: bool,
+    /// 服务登记的驱动文件路径
+    pub image_path: Option<PathBuf>,
+}
+
+/// 查询驱动服务状态，不需要管理员权限
+pub fn service_state() -> Result<ServiceState> {
+    let access = SERVICE_QUERY_STATUS | SERVICE_QUERY_CONFIG;
+    let state = with_service(SC_MANAGER_CONNECT, access, |svc| {
+        let mut status = SERVICE_STATUS::default();
+        unsafe { QueryServiceStatus(svc, &raw mut status)? };
+        Ok(ServiceState {
+            installed: true,
+            running: status.dwCurrentState == SERVICE_RUNNING,
+            image_path: image_path(svc),
+        })
+    })?;
+    Ok(state.unwrap_or_default())
+}
+
+/// 停止并删除驱动服务，服务不存在时不算错误
+/// 仍有进程打开设备时，服务会在句柄全部关闭后才真正删除
+pub fn remove_service() -> Result<()> {
+    with_service(SC_MANAGER_ALL_ACCESS, SERVICE_ALL_ACCESS, stop_and_delete).map(|_| ())
+}
+
+/// 仅当服务登记的驱动文件就是 `driver_path` 时停止并删除服务
+/// 同名服务也可能由其他使用 `WinRing0` 的软件注册，这种服务保留不动
+fn remove_own_service(driver_path: &Path) -> Result<()> {
+    with_service(SC_MANAGER_ALL_ACCESS, SERVICE_ALL_ACCESS, |svc| {
+        if is_own_service(svc, driver_path) {
+            stop_and_delete(svc)
+        } else {
+            Ok(())
+        }
+    })
+    .map(|_| ())
+}
+
+fn stop_and_delete(svc: SC_HANDLE) -> windows::core::Result<()> {
+    let mut status = SERVICE_STATUS::default();
+    unsafe {
+        let _ = ControlService(svc, SERVICE_CONTROL_STOP, &raw mut status);
+        DeleteService(svc)
+    }
+}
+
+/// 服务登记的驱动文件是否为 `driver_path`（NoCrate 释放或找到的那份）
+fn is_own_service(svc: SC_HANDLE, driver_path: &Path) -> bool {
+    image_path(svc).is_some_and(|p| same_path(&p, driver_path))
+}
+
+/// 忽略 `\\?\` 前缀与大小写比较两个路径
+fn same_path(a: &Path, b: &Path) -> bool {
+    let plain = |p: &Path| {
+        let s = p.to_string_lossy();
+        s.strip_prefix(r"\\?\").unwrap_or(&s).to_lowercase()
+    };
+    plain(a) == plain(b)
+}
+
+/// 以 `access` 打开驱动服务并执行 `f`，服务不存在时返回 `None`
+fn with_service<T>(
+    scm_access: u32,
+    access: u32,
+    f: impl FnOnce(SC_HANDLE) -> windows::core::Result<T>,
+) -> Result<Option<T>> {
+    let classify = |e: windows::core::Error| {
+        NoCrateError::classify(
+            &e,
+            i18n::tf(Msg::DriverServiceAccess, &[("error", &e)]),
+            NoCrateError::Sio,
+        )
+    };
+    unsafe {
+        let scm = OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), scm_access).map_err(classify)?;
+        let svc_name = to_wide(SERVICE_NAME);
+        let result = match OpenServiceW(scm, PCWSTR(svc_name.as_ptr()), access) {
+            Ok(svc) => {
+                let result = f(svc).map(Some).map_err(classify);
+                let _ = CloseServiceHandle(svc);
+                result
+            }
+            Err(e) if e.code() == ERROR_SERVICE_DOES_NOT_EXIST.to_hresult() => Ok(None),
+            Err(e) => Err(classify(e)),
+        };
+        let _ = CloseServiceHandle(scm);
+        result
+    }
+}
+
+/// 服务登记的驱动文件路径，内核驱动的路径通常带 NT 前缀，这里去掉
+fn image_path(svc: SC_HANDLE) -> Option<PathBuf> {
+    let mut needed = 0u32;
+    unsafe {
+        let _ = QueryServiceConfigW(svc, None, 0, &raw mut needed);
+        // 按 QUERY_SERVICE_CONFIGW 对齐分配，字符串紧跟在结构体之后
+        let words = (needed as usize).div_ceil(size_of::<u64>());
+        let mut buffer = vec![0u64; words.max(1)];
+        let config = buffer.as_mut_ptr().cast::<QUERY_SERVICE_CONFIGW>();
+        QueryServiceConfigW(svc, Some(config), needed, &raw mut needed).ok()?;
+        let path = (*config).lpBinaryPathName.to_string().ok()?;
+        let path = path.strip_prefix(r"\??\").unwrap_or(&path);
+        Some(PathBuf::from(path))
+    }
+}
+
+/// 把远程读到的值收窄为端口宽度
+fn narrow<T: TryFrom<u64>>(value: u64) -> Result<T> {
+    T::try_from(value).map_err(|_| {
+        NoCrateError::Sio(i18n::tf(
+            Msg::SioRemoteOutOfRange,
+            &[("value", &format!("0x{value:X}"))],
+        ))
+    })
+}
+
+/// I/O 端口读写失败，`port` 为显示用的端口描述
+fn port_error(msg: Msg, port: String, e: &windows::core::Error) -> NoCrateError {
+    NoCrateError::Sio(i18n::tf(msg, &[("port", &port), ("error", e)]))
+}
+
+/// WinRing0 驱动句柄，持有设备和服务控制管理器的引用。
+/// Drop 时自动关闭设备句柄，非常驻时同时卸载驱动服务。
+pub struct DriverHandle {
+    device: HANDLE,
+    /// 本进程使用的驱动文件，用于判断驱动服务是否由 NoCrate 注册
+    driver_path: PathBuf,
+    /// CF8/CFC 地址-数据两步访问不是原子的，句柄被多个子系统共享时需串行化
+    pci_lock: Mutex<()>,
+    /// 模拟模式：没有加载驱动，所有端口访问都返回错误
+    simulated: bool,
+    /// 远程模式：没有加载驱动，原语转发给持有驱动的进程
+    remote: Option<Box<dyn PortForwarder>>,
+    /// 常驻：Drop 时只关闭设备句柄，驱动服务保持注册（按需启动），下次启动无需重新安装
+    resident: AtomicBool,
+}
+
+// HANDLE (DeviceIoControl) 可以安全地跨线程使用
+// 多步事务由各调用方的 Mutex（以及 pci_lock）保证串行
+#[allow(unsafe_code)]
+unsafe impl Send for DriverHandle {}
+#[allow(unsafe_code)]
+unsafe impl Sync for DriverHandle {}
+
+impl DriverHandle {
+    /// 安装并打开 WinRing0 内核驱动。
+    ///
+    /// 流程：释放内嵌的 .sys 文件（未内嵌时从资源目录查找）→ 注册为内核服务 → 启动服务 → 打开设备句柄
+    pub fn open(resource_dir: &Path) -> Result<Self> {
+        if let Some(driver_path) = embedded::extract()? {
+            return Self::open_with_path(&driver_path);
+        }
+        // 驱动 .sys 文件路径（从 Tauri 资源目录提取）
+        let driver_path = resource_dir.join("WinRing0x64.sys");
+        if !driver_path.exists() {
+            // 如果资源目录没有，尝试当前 exe 同目录
+            let exe_dir = std::env::current_exe()
+                .ok()
+                .and_then(|p| p.parent().map(|d| d.join("WinRing0x64.sys")));
+            if let Some(alt) = exe_dir {
+                if alt.exists() {
+                    return Self::open_with_path(&alt);
+                }
+            }
+            return Err(NoCrateError::Sio(i18n::tf(
+                Msg::DriverFileMissing,
+                &[("path", &driver_path.display())],
+            )));
+        }
+        Self::open_with_path(&driver_path)
+    }
+
+    /// 使用指定路径的驱动文件安装并打开
+    fn open_with_path(driver_path: &Path) -> Result<Self> {
+        let driver_path_abs = std::fs::canonicalize(driver_path)
+            .map_err(|e| NoCrateError::Sio(i18n::tf(Msg::DriverPathInvalid, &[("error", &e)])))?;
+
+        // 先尝试用已有服务启动，没有可用的服务时再创建
+        if !Self::try_start_existing_service(&driver_path_abs)? {
+            Self::install_service(&driver_path_abs)?;
+        }
+
+        // 打开设备句柄
+        let device = Self::open_device()?;
+
+        Ok(Self {
+            device,
+            driver_path: driver_path_abs,
+            pci_lock: Mutex::new(()),
+            resident: AtomicBool::new(false),
+            simulated: false,
+            remote: None,
+        })
+    }
+
+    /// 模拟模式使用的句柄，不安装驱动、不需要管理员权限
+    /// 依赖真实端口读写的子系统（EC、CPU、SMBus）会按"设备不存在"处理
+    #[must_use]
+    pub fn simulated() -> Self {
+        Self {
+            device: HANDLE::default(),
+            driver_path: PathBuf::new(),
+            pci_lock: Mutex::new(()),
+            resident: AtomicBool::new(false),
+            simulated: true,
+            remote: None,
+        }
+    }
+
+    /// 把端口访问转发给 `forwarder` 的句柄，本进程不需要管理员权限
+    /// bank 切换等多步端口事务仍由本进程的锁串行化，PCI 配置空间与 SMN 访问整体转发
+    #[must_use]
+    pub fn remote(forwarder: Box<dyn PortForwarder>) -> Self {
+        Self {
+            device: HANDLE::default(),
+            driver_path: PathBuf::new(),
+            pci_lock: Mutex::new(()),
+            resident: AtomicBool::new(false),
+            simulated: false,
+            remote: Some(forwarder),
+        }
+    }
+
+    /// 是否为模拟句柄
+    #[must_use]
+    pub const fn is_simulated(&self) -> bool {
+        self.simulated
+    }
+
+    /// 是否为远程句柄
+    #[must_use]
+    pub const fn is_remote(&self) -> bool {
+        self.remote.is_some()
+    }
+
+    /// 设置句柄释放后是否保留驱动服务，见 [`remove_service`] 显式卸载
+    pub fn set_resident(&self, resident: bool) {
+        self.resident.store(resident, Ordering::Relaxed);
+    }
+
+    /// 执行一个原语，供持有驱动的进程响应远程句柄；读操作返回读到的值，写操作返回 0
+    pub fn execute(&self, op: PortOp) -> Result<u64> {
+        match op {
+            PortOp::ReadByte { port } => self.read_io_port_byte(port).map(u64::from),
+            PortOp::WriteByte { port, value } => self.write_io_port_byte(port, value).map(|()| 0),
+            PortOp::ReadPci {
+                bus,
+                device,
+                function,
+                offset,
+            } => self
+                .read_pci_config(bus, device, function, offset)
+                .map(u64::from),
+            PortOp::WritePci {
+                bus,
+                device,
+                function,
+                offset,
+                value,
+            } => self
+                .write_pci_config(bus, device, function, offset, value)
+                .map(|()| 0),
+            PortOp::ReadSmn { addr } => self.read_smn(addr).map(u64::from),
+            PortOp::ReadMsr { index } => self.read_msr(index),
+        }
+    }
+
+    /// 设备句柄，模拟模式下返回错误
+    fn device(&self) -> Result<HANDLE> {
+        if self.simulated {
+            return Err(NoCrateError::DeviceNotPresent(
+                i18n::t(Msg::DriverSimulated).into(),
+            ));
+        }
+        Ok(self.device)
+    }
+
+    /// 尝试启动已经存在的驱动服务，服务不存在时返回 `false`
+    /// NoCrate 自己注册的服务无法启动时（如异常退出后残留、驱动文件已不存在）删除它并返回 `false`，
+    /// 交由 [`Self::install_service`] 重新创建；其他软件注册的同名服务不删除，返回错误
+    fn try_start_existing_service(driver_path: &Path) -> Result<bool> {
+        unsafe {
+            let scm = OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), SC_MANAGER_ALL_ACCESS)
+                .map_err(|e| {
+                    NoCrateError::classify(
+                        &e,
+                        i18n::tf(Msg::DriverScmOpen, &[("error", &e)]),
+                        NoCrateError::Sio,
+                    )
+                })?;
+
+            let svc_name = to_wide(SERVICE_NAME);
+            let svc = OpenServiceW(scm, PCWSTR(svc_name.as_ptr()), SERVICE_ALL_ACCESS);
+
+            match svc {
+                Ok(svc_handle) => {
+                    // 服务已存在，尝试启动（可能已经在运行）
+                    let started = match StartServiceW(svc_handle, None) {
+                        Err(e) if e.code() != ERROR_SERVICE_ALREADY_RUNNING.to_hresult() => {
+                            eprintln!("Warning: existing WinRing0 service failed to start: {e}");
+                            if is_own_service(svc_handle, driver_path) {
+                                let _ = stop_and_delete(svc_handle);
+                                Ok(false)
+                            } else {
+                                let path = image_path(svc_handle).unwrap_or_default();
+                                Err(NoCrateError::classify(
+                                    &e,
+                                    i18n::tf(
+                                        Msg::DriverForeignService,
+                                        &[("path", &path.display()), ("error", &e)],
+                                    ),
+                                    NoCrateError::Sio,
+                                ))
+                            }
+                        }
+                        _ => Ok(true),
+                    };
+                    let _ = CloseServiceHandle(svc_handle);
+                    let _ = CloseServiceHandle(scm);
+                    started
+                }
+                Err(_) => {
+                    let _ = CloseServiceHandle(scm);
+                    Ok(false)
+                }
+            }
+        }
+    }
+
+    /// 创建并启动内核驱动服务
+    fn install_service(driver_path: &Path) -> Result<()> {
+        unsafe {
+            let scm = OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), SC_MANAGER_ALL_ACCESS)
+                .map_err(|e| {
+                    NoCrateError::classify(
+                        &e,
+                        i18n::tf(Msg::DriverScmOpen, &[("error", &e)]),
+                        NoCrateError::Sio,
+                    )
+                })?;
+
+            let svc_name = to_wide(SERVICE_NAME);
+            let display_name = to_wide("WinRing0_1_2_0");
+            let binary_path = to_wide(&driver_path.to_string_lossy());
+
+            let svc = CreateServiceW(
+                scm,
+                PCWSTR(svc_name.as_ptr()),
+                PCWSTR(display_name.as_ptr()),
+                SERVICE_ALL_ACCESS,
+                SERVICE_KERNEL_DRIVER,
+                SERVICE_DEMAND_START,
+                SERVICE_ERROR_NORMAL,
+                PCWSTR(binary_path.as_ptr()),
+                PCWSTR::null(),
+                None,
+                PCWSTR::null(),
+                PCWSTR::null(),
+                PCWSTR::null(),
+            )
+            .map_err(|e| {
+                let _ = CloseServiceHandle(scm);
+                NoCrateError::classify(
+                    &e,
+                    i18n::tf(Msg::DriverServiceCreate, &[("error", &e)]),
+                    NoCrateError::Sio,
+                )
+            })?;
+
+            let start_result = StartServiceW(svc, None);
+            if let Err(e) = start_result {
+                // 如果已经在运行，忽略错误
+                let code = e.code().0 as u32;
+                if code != 0x80070420 {
+                    // ERROR_SERVICE_ALREADY_RUNNING
+                    let _ = DeleteService(svc);
+                    let _ = CloseServiceHandle(svc);
+                    let _ = CloseServiceHandle(scm);
+                    return Err(NoCrateError::classify(
+                        &e,
+                        i18n::tf(Msg::DriverServiceStart, &[("error", &e)]),
+                        NoCrateError::Sio,
+                    ));
+                }
+            }
+
+            let _ = CloseServiceHandle(svc);
+            let _ = CloseServiceHandle(scm);
+            Ok(())
+        }
+    }
+
+    /// 打开驱动设备句柄
+    fn open_device() -> Result<HANDLE> {
+        use windows::Win32::Foundation::GENERIC_READ;
+        use windows::Win32::Foundation::GENERIC_WRITE;
+
+        let path = to_wide(DEVICE_PATH);
+        unsafe {
+            let handle = CreateFileW(
+                PCWSTR(path.as_ptr()),
+                (GENERIC_READ.0 | GENERIC_WRITE.0).into(),
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_ATTRIBUTE_NORMAL,
+                None,
+            )
+            .map_err(|e| {
+                NoCrateError::classify(
+                    &e,
+                    i18n::tf(Msg::DriverDeviceOpen, &[("error", &e)]),
+                    NoCrateError::Sio,
+                )
+            })?;
+
+            if handle == INVALID_HANDLE_VALUE {
+                return Err(NoCrateError::Sio(i18n::t(Msg::DriverInvalidHandle).into()));
+            }
+
+            Ok(handle)
+        }
+    }
+
+    /// 从 I/O 端口读取一个字节
+    pub fn read_io_port_byte(&self, port: u16) -> Result<u8> {
+        if let Some(remote) = &self.remote {
+            return remote.forward(PortOp::ReadByte { port }).and_then(narrow);
+        }
+        let mut input = port as u32;
+        let mut output: u32 = 0;
+        let mut bytes_returned: u32 = 0;
+
+        unsafe {
+            DeviceIoControl(
+                self.device()?,
+                IOCTL_OLS_READ_IO_PORT_BYTE,
+                Some(std::ptr::addr_of_mut!(input).cast()),
+                std::mem::size_of::<u32>() as u32,
+                Some(std::ptr::addr_of_mut!(output).cast()),
+                std::mem::size_of::<u32>() as u32,
+                Some(&mut bytes_returned),
+                None,
+            )
+            .map_err(|e| port_error(Msg::DriverPortRead, format!("0x{port:04X}"), &e))?;
+        }
+
+        Ok(output as u8)
+    }
+
+    /// 写入一个字节到 I/O 端口
+    pub fn write_io_port_byte(&self, port: u16, value: u8) -> Result<()> {
+        // WinRing0 OLS_WRITE_IO_PORT_INPUT 结构体：
+        // struct { ULONG PortNumber; union { ULONG LongData; UCHAR CharData; }; }
+        // 共 8 字节：前 4 字节 = 端口号，后 4 字节 = 数据（仅低字节有效）
+        #[repr(C)]
+        struct WriteInput {
+            port: u32,
+            data: u32,
+        }
+        if let Some(remote) = &self.remote {
+            return remote.forward(PortOp::WriteByte { port, value }).map(|_| ());
+        }
+        let mut input = WriteInput {
+            port: port as u32,
+            data: value as u32,
+        };
+        let mut bytes_returned: u32 = 0;
+
+        unsafe {
+            DeviceIoControl(
+                self.device()?,
+                IOCTL_OLS_WRITE_IO_PORT_BYTE,
+                Some(std::ptr::addr_of_mut!(input).cast()),
+                // 传入 5 字节（offsetof(CharData) + sizeof(u8)），与原版 C 代码一致
+                5u32,
+                None,
+                0,
+                Some(&mut bytes_returned),
+                None,
+            )
+            .map_err(|e| port_error(Msg::DriverPortWrite, format!("0x{port:04X}"), &e))?;
+        }
+
+        Ok(())
+    }
+
+    /// 已加载驱动的版本号，形如 `1.2.0.5`
+    /// 远程与模拟句柄没有本地设备，返回错误
+    pub fn version(&self) -> Result<String> {
+        if self.remote.is_some() {
+            return Err(NoCrateError::DeviceNotPresent(
+                i18n::t(Msg::DriverHeldByService).into(),
+            ));
+        }
+        let mut output: u32 = 0;
+        let mut bytes_returned: u32 = 0;
+
+        unsafe {
+            DeviceIoControl(
+                self.device()?,
+                IOCTL_OLS_GET_DRIVER_VERSION,
+                None,
+                0,
+                Some(std::ptr::addr_of_mut!(output).cast()),
+                u32::try_from(std::mem::size_of::<u32>()).unwrap_or(0),
+                Some(&raw mut bytes_returned),
+                None,
+            )
+            .map_err(|e| NoCrateError::Sio(i18n::tf(Msg::DriverVersionRead, &[("error", &e)])))?;
+        }
+
+        let [major, minor, revision, release] = output.to_be_bytes();
+        Ok(format!("{major}.{minor}.{revision}.{release}"))
+    }
+
+    /// 读取 MSR（Model-Specific Register）
+    /// 驱动在当前线程所在的逻辑处理器上执行 RDMSR
+    pub fn read_msr(&self, index: u32) -> Result<u64> {
+        if let Some(remote) = &self.remote {
+            return remote.forward(PortOp::ReadMsr { index });
+        }
+        let mut input = index;
+        let mut output: u64 = 0;
+        let mut bytes_returned: u32 = 0;
+
+        unsafe {
+            DeviceIoControl(
+                self.device()?,
+                IOCTL_OLS_READ_MSR,
+                Some(std::ptr::addr_of_mut!(input).cast()),
+                std::mem::size_of::<u32>() as u32,
+                Some(std::ptr::addr_of_mut!(output).cast()),
+                std::mem::size_of::<u64>() as u32,
+                Some(&mut bytes_returned),
+                None,
+            )
+            .map_err(|e| {
+                NoCrateError::Sio(i18n::tf(
+                    Msg::DriverMsrRead,
+                    &[("index", &format!("0x{index:X}")), ("error", &e)],
+                ))
+            })?;
+        }
+
+        Ok(output)
+    }
+
+    /// 读取 I/O 端口 DWORD（用于 PCI CF8/CFC 访问）
+    fn read_io_port_dword(&self, port: u16) -> Result<u32> {
+        let mut input = port as u32;
+        let mut output: u32 = 0;
+        let mut bytes_returned: u32 = 0;
+
+        unsafe {
+            DeviceIoControl(
+                self.device()?,
+                IOCTL_OLS_READ_IO_PORT_DWORD,
+                Some(std::ptr::addr_of_mut!(input).cast()),
+                std::mem::size_of::<u32>() as u32,
+                Some(std::ptr::addr_of_mut!(output).cast()),
+                std::mem::size_of::<u32>() as u32,
+                Some(&mut bytes_returned),
+                None,
+            )
+            .map_err(|e| port_error(Msg::DriverPortRead, format!("DWORD 0x{port:04X}"), &e))?;
+        }
+
+        Ok(output)
+    }
+
+    /// 写入 I/O 端口 DWORD（用于 PCI CF8/CFC 访问）
+    fn write_io_port_dword(&self, port: u16, value: u32) -> Result<()> {
+        #[repr(C)]
+        struct WriteInput {
+            port: u32,
+            data: u32,
+        }
+        let mut input = WriteInput {
+            port: port as u32,
+            data: value,
+        };
+        let mut bytes_returned: u32 = 0;
+
+        unsafe {
+            DeviceIoControl(
+                self.device()?,
+                IOCTL_OLS_WRITE_IO_PORT_DWORD,
+                Some(std::ptr::addr_of_mut!(input).cast()),
+                std::mem::size_of::<WriteInput>() as u32, // 8 字节（DWORD 完整写入）
+                None,
+                0,
+                Some(&mut bytes_returned),
+                None,
+            )
+            .map_err(|e| port_error(Msg::DriverPortWrite, format!("DWORD 0x{port:04X}"), &e))?;
+        }
+
+        Ok(())
+    }
+
+    /// 通过传统 PCI CF8/CFC 端口读取配置空间 DWORD
+    /// 比 HalGetBusData IOCTL 更可靠，直接操作 I/O 端口 0xCF8/0xCFC
+    pub fn read_pci_config(&self, bus: u8, dev: u8, func: u8, reg_addr: u32) -> Result<u32> {
+        if let Some(remote) = &self.remote {
+            let op = PortOp::ReadPci {
+                bus,
+                device: dev,
+                function: func,
+                offset: reg_addr,
+            };
+            return remote.forward(op).and_then(narrow);
+        }
+        let _guard = self.pci_lock.lock();
+        self.read_pci_config_unlocked(bus, dev, func, reg_addr)
+    }
+
+    fn read_pci_config_unlocked(&self, bus: u8, dev: u8, func: u8, reg_addr: u32) -> Result<u32> {
+        // CONFIG_ADDRESS = (1<<31) | (bus<<16) | (device<<11) | (function<<8) | (register & 0xFC)
+        let config_addr: u32 = 0x8000_0000
+            | ((bus as u32) << 16)
+            | (((dev as u32) & 0x1F) << 11)
+            | (((func as u32) & 0x07) << 8)
+            | (reg_addr & 0xFC);
+
+        self.write_io_port_dword(0xCF8, config_addr)?;
+        self.read_io_port_dword(0xCFC)
+    }
+
+    /// 通过传统 PCI CF8/CFC 端口写入配置空间 DWORD
+    pub fn write_pci_config(
+        &self,
+        bus: u8,
+        dev: u8,
+        func: u8,
+        reg_addr: u32,
+        value: u32,
+    ) -> Result<()> {
+        if let Some(remote) = &self.remote {
+            let op = PortOp::WritePci {
+                bus,
+                device: dev,
+                function: func,
+                offset: reg_addr,
+                value,
+            };
+            return remote.forward(op).map(|_| ());
+        }
+        let _guard = self.pci_lock.lock();
+        self.write_pci_config_unlocked(bus, dev, func, reg_addr, value)
+    }
+
+    fn write_pci_config_unlocked(
+        &self,
+        bus: u8,
+        dev: u8,
+        func: u8,
+        reg_addr: u32,
+        value: u32,
+    ) -> Result<()> {
+        let config_addr: u32 = 0x8000_0000
+            | ((bus as u32) << 16)
+            | (((dev as u32) & 0x1F) << 11)
+            | (((func as u32) & 0x07) << 8)
+            | (reg_addr & 0xFC);
+
+        self.write_io_port_dword(0xCF8, config_addr)?;
+        self.write_io_port_dword(0xCFC, value)
+    }
+
+    /// 读取 AMD SMN（System Management Network）寄存器
+    /// 通过根复合体 (0/0/0) 的 0x60 索引 / 0x64 数据寄存器间接访问
+    pub fn read_smn(&self, addr: u32) -> Result<u32> {
+        if let Some(remote) = &self.remote {
+            return remote.forward(PortOp::ReadSmn { addr }).and_then(narrow);
+        }
+        let _guard = self.pci_lock.lock();
+        self.write_pci_config_unlocked(0, 0, 0, 0x60, addr)?;
+        self.read_pci_config_unlocked(0, 0, 0, 0x64)
+    }
+}
+
+impl Drop for DriverHandle {
+    fn drop(&mut self) {
+        if self.simulated || self.remote.is_some() {
+            return;
+        }
+        unsafe {
+            // 关闭设备句柄
+            let _ = CloseHandle(self.device);
+        }
+        // 非常驻时停止并删除驱动服务（借用其他软件的服务时不删除）
+        if !self.resident.load(Ordering::Relaxed) {
+            let _ = remove_own_service(&self.driver_path);
+        }
+    }
+}
+
+/// 将 &str 转换为以 null 结尾的宽字符串
+fn to_wide(s: &str) -> Vec<u16> {
+    OsStr::new(s)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
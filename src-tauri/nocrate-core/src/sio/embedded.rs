@@ -0,0 +1,298 @@
+// 内嵌的 WinRing0 驱动
+// 构建时找到 WinRing0x64.sys 就编入二进制（见 build.rs），运行时释放到 %ProgramData%\NoCrate，
+// 该目录归 Administrators 所有、只有 SYSTEM 与管理员可写，普通用户无法替换将以内核权限加载的文件；
+// 每次加载前校验文件的 SHA-256 与内嵌副本一致，不一致时重新释放
+//
+// 普通用户可以抢先创建该目录并放入联接或硬链接，因此目录与文件都不跟随重解析点打开，
+// 权限设在已打开的句柄上；有多个链接的文件不复用，重新释放时先删除再以 CREATE_NEW 创建
+
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+#[cfg(embedded_driver)]
+static DRIVER: &[u8] = include_bytes!(env!("NOCRATE_WINRING0_PATH"));
+
+/// 本次构建是否内嵌了驱动
+#[must_use]
+pub const fn is_embedded() -> bool {
+    cfg!(embedded_driver)
+}
+
+/// 释放内嵌驱动并返回其路径，未内嵌时返回 `None`
+/// 已释放的文件与内嵌副本一致时直接复用
+#[cfg_attr(not(embedded_driver), allow(clippy::missing_const_for_fn))]
+pub fn extract() -> Result<Option<PathBuf>> {
+    #[cfg(embedded_driver)]
+    {
+        imp::extract(DRIVER).map(Some)
+    }
+    #[cfg(not(embedded_driver))]
+    {
+        Ok(None)
+    }
+}
+
+#[cfg(embedded_driver)]
+#[allow(unsafe_code)]
+mod imp {
+    use std::fs::{self, File, OpenOptions};
+    use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::{Path, PathBuf};
+
+    use windows::core::{w, BOOL, PCWSTR};
+    use windows::Win32::Foundation::{LocalFree, HANDLE, HLOCAL};
+    use windows::Win32::Security::Authorization::{
+        ConvertStringSecurityDescriptorToSecurityDescriptorW, SetSecurityInfo, SDDL_REVISION_1,
+        SE_FILE_OBJECT,
+    };
+    use windows::Win32::Security::Cryptography::{BCryptHash, BCRYPT_SHA256_ALG_HANDLE};
+    use windows::Win32::Security::{
+        GetSecurityDescriptorDacl, GetSecurityDescriptorOwner, ACL, DACL_SECURITY_INFORMATION,
+        OWNER_SECURITY_INFORMATION, PROTECTED_DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR,
+        PSID,
+    };
+    use windows::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, FILE_ATTRIBUTE_DIRECTORY,
+        FILE_ATTRIBUTE_REPARSE_POINT, FILE_FLAG_BACKUP_SEMANTICS, FILE_FLAG_OPEN_REPARSE_POINT,
+        FILE_GENERIC_READ, FILE_READ_ATTRIBUTES, FILE_SHARE_READ, READ_CONTROL, WRITE_DAC,
+        WRITE_OWNER,
+    };
+
+    use crate::error::{NoCrateError, Result};
+    use crate::i18n::{self, Msg};
+
+    /// 释放目录的权限：所有者为 Administrators，SYSTEM 与管理员完全控制，
+    /// 普通用户只读；不继承上级目录的权限，新建的文件继承同样的权限
+    const DIR_SDDL: PCWSTR = w!("O:BAD:P(A;OICI;FA;;;SY)(A;OICI;FA;;;BA)(A;OICI;FRFX;;;BU)");
+
+    /// 复用已释放的驱动文件前设到文件上的权限，与目录继承给新文件的相同
+    const FILE_SDDL: PCWSTR = w!("O:BAD:P(A;;FA;;;SY)(A;;FA;;;BA)(A;;FRFX;;;BU)");
+
+    const FILE_NAME: &str = "WinRing0x64.sys";
+
+    type Digest = [u8; 32];
+
+    pub fn extract(driver: &[u8]) -> Result<PathBuf> {
+        let dir = driver_dir();
+        match fs::create_dir(&dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                return Err(NoCrateError::Sio(i18n::tf(
+                    Msg::DriverDirCreate,
+                    &[("path", &dir.display()), ("error", &e)],
+                )))
+            }
+        }
+        // 持有不共享删除的目录句柄，期间目录不能被改名或替换
+        let _dir = open_dir(&dir)?;
+
+        let path = dir.join(FILE_NAME);
+        let expected = sha256(driver)?;
+        if reusable(&path, &expected)? {
+            return Ok(path);
+        }
+
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(NoCrateError::Sio(i18n::tf(
+                    Msg::DriverFileRemove,
+                    &[("path", &path.display()), ("error", &e)],
+                )))
+            }
+        }
+        let write_err = |e: std::io::Error| {
+            NoCrateError::Sio(i18n::tf(
+                Msg::DriverFileWrite,
+                &[("path", &path.display()), ("error", &e)],
+            ))
+        };
+        // CREATE_NEW：路径上已有任何对象（包括链接）时失败，不会写入别处的文件
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .share_mode(FILE_SHARE_READ.0)
+            .custom_flags(FILE_FLAG_OPEN_REPARSE_POINT.0)
+            .open(&path)
+            .map_err(write_err)?;
+        file.write_all(driver).map_err(write_err)?;
+        // 从同一句柄读回校验，确认加载的正是内嵌的驱动
+        if hash_file(&mut file, &path)? != expected {
+            return Err(NoCrateError::Sio(i18n::tf(
+                Msg::DriverFileMismatch,
+                &[("path", &path.display())],
+            )));
+        }
+        Ok(path)
+    }
+
+    fn driver_dir() -> PathBuf {
+        std::env::var_os("ProgramData")
+            .map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from)
+            .join("NoCrate")
+    }
+
+    /// 不跟随重解析点打开释放目录，确认是普通目录后设置 [`DIR_SDDL`]；需要管理员权限
+    fn open_dir(dir: &Path) -> Result<File> {
+        let handle = OpenOptions::new()
+            .access_mode(FILE_READ_ATTRIBUTES.0 | READ_CONTROL.0 | WRITE_DAC.0 | WRITE_OWNER.0)
+            .share_mode(FILE_SHARE_READ.0)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0 | FILE_FLAG_OPEN_REPARSE_POINT.0)
+            .open(dir)
+            .map_err(|e| {
+                NoCrateError::Sio(i18n::tf(
+                    Msg::DriverDirOpen,
+                    &[("path", &dir.display()), ("error", &e)],
+                ))
+            })?;
+        let info = file_info(&handle, dir)?;
+        if info.dwFileAttributes & FILE_ATTRIBUTE_REPARSE_POINT.0 != 0
+            || info.dwFileAttributes & FILE_ATTRIBUTE_DIRECTORY.0 == 0
+        {
+            return Err(NoCrateError::Sio(i18n::tf(
+                Msg::DriverDirNotPlain,
+                &[("path", &dir.display())],
+            )));
+        }
+        secure(&handle, DIR_SDDL).map_err(|e| {
+            NoCrateError::classify(
+                &e,
+                i18n::tf(
+                    Msg::DriverDirSecure,
+                    &[("path", &dir.display()), ("error", &e)],
+                ),
+                NoCrateError::Sio,
+            )
+        })?;
+        Ok(handle)
+    }
+
+    /// 已释放的文件能否直接使用：是只有一个链接的普通文件且与内嵌副本一致
+    /// 不共享写入地打开，并先设置 [`FILE_SDDL`] 再校验，校验后普通用户无法再改写
+    fn reusable(path: &Path, expected: &Digest) -> Result<bool> {
+        let opened = OpenOptions::new()
+            .access_mode(FILE_GENERIC_READ.0 | WRITE_DAC.0 | WRITE_OWNER.0)
+            .share_mode(FILE_SHARE_READ.0)
+            .custom_flags(FILE_FLAG_OPEN_REPARSE_POINT.0)
+            .open(path);
+        let mut file = match opened {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(e) => {
+                return Err(NoCrateError::Sio(i18n::tf(
+                    Msg::DriverFileRead,
+                    &[("path", &path.display()), ("error", &e)],
+                )))
+            }
+        };
+        let info = file_info(&file, path)?;
+        if info.dwFileAttributes & FILE_ATTRIBUTE_REPARSE_POINT.0 != 0 {
+            eprintln!(
+                "Warning: {} is a reparse point, replacing it",
+                path.display()
+            );
+            return Ok(false);
+        }
+        if info.nNumberOfLinks != 1 {
+            eprintln!(
+                "Warning: {} has {} links, replacing it",
+                path.display(),
+                info.nNumberOfLinks
+            );
+            return Ok(false);
+        }
+        secure(&file, FILE_SDDL).map_err(|e| {
+            NoCrateError::classify(
+                &e,
+                i18n::tf(
+                    Msg::DriverFileSecure,
+                    &[("path", &path.display()), ("error", &e)],
+                ),
+                NoCrateError::Sio,
+            )
+        })?;
+        Ok(hash_file(&mut file, path)? == *expected)
+    }
+
+    fn file_info(file: &File, path: &Path) -> Result<BY_HANDLE_FILE_INFORMATION> {
+        let mut info = BY_HANDLE_FILE_INFORMATION::default();
+        unsafe { GetFileInformationByHandle(HANDLE(file.as_raw_handle()), &raw mut info) }
+            .map_err(|e| {
+                NoCrateError::Sio(i18n::tf(
+                    Msg::DriverFileQuery,
+                    &[("path", &path.display()), ("error", &e)],
+                ))
+            })?;
+        Ok(info)
+    }
+
+    /// 从头读取整个文件并计算 SHA-256
+    fn hash_file(file: &mut File, path: &Path) -> Result<Digest> {
+        let mut data = Vec::new();
+        file.seek(SeekFrom::Start(0))
+            .and_then(|_| file.read_to_end(&mut data))
+            .map_err(|e| {
+                NoCrateError::Sio(i18n::tf(
+                    Msg::DriverFileRead,
+                    &[("path", &path.display()), ("error", &e)],
+                ))
+            })?;
+        sha256(&data)
+    }
+
+    fn sha256(data: &[u8]) -> Result<Digest> {
+        let mut digest = Digest::default();
+        unsafe { BCryptHash(BCRYPT_SHA256_ALG_HANDLE, None, data, &mut digest) }
+            .ok()
+            .map_err(|e| NoCrateError::Sio(i18n::tf(Msg::DriverHash, &[("error", &e)])))?;
+        Ok(digest)
+    }
+
+    /// 把 `sddl` 的所有者与受保护的 DACL 设到已打开的句柄上
+    fn secure(file: &File, sddl: PCWSTR) -> windows::core::Result<()> {
+        unsafe {
+            let mut security = PSECURITY_DESCRIPTOR::default();
+            ConvertStringSecurityDescriptorToSecurityDescriptorW(
+                sddl,
+                SDDL_REVISION_1,
+                &raw mut security,
+                None,
+            )?;
+            let applied = apply(HANDLE(file.as_raw_handle()), security);
+            let _ = LocalFree(Some(HLOCAL(security.0)));
+            applied
+        }
+    }
+
+    unsafe fn apply(handle: HANDLE, security: PSECURITY_DESCRIPTOR) -> windows::core::Result<()> {
+        let mut defaulted = BOOL::default();
+        let mut owner = PSID::default();
+        GetSecurityDescriptorOwner(security, &raw mut owner, &raw mut defaulted)?;
+        let mut present = BOOL::default();
+        let mut dacl: *mut ACL = std::ptr::null_mut();
+        GetSecurityDescriptorDacl(
+            security,
+            &raw mut present,
+            &raw mut dacl,
+            &raw mut defaulted,
+        )?;
+        SetSecurityInfo(
+            handle,
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION
+                | DACL_SECURITY_INFORMATION
+                | PROTECTED_DACL_SECURITY_INFORMATION,
+            Some(owner),
+            None,
+            Some(dacl.cast_const()),
+            None,
+        )
+        .ok()
+    }
+}
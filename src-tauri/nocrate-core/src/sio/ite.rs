@@ -0,0 +1,303 @@
+// ITE IT86xxE 系列 Super I/O 芯片传感器读取
+//
+// 支持型号：IT8628E、IT8665E、IT8686E、IT8688E、IT8689E、IT8695E、IT8792E
+// 温度：TMPIN1–3 为标准通道，TMPIN4–6 为外部 / PECI 扩展通道
+// 风扇路数与 FAN6、TMPIN4–6 的寄存器位置因型号而异，见 IteLayout
+// 寄存器定义参考 LibreHardwareMonitor、Linux it87 驱动与 ITE 数据手册
+
+use super::access::{require_driver, SioAccess};
+use super::chips::{Chip, FanReading, RegisterBank, RegisterDump, TempReading};
+use super::driver::DriverHandle;
+use crate::error::Result;
+use crate::i18n::Msg;
+use crate::wmi::asus_mgmt::FanPresence;
+
+/// ITE IT86xxE 芯片实例
+pub struct IteChip {
+    name: String,
+    chip_id: u16,
+    /// Environment Controller I/O 基地址
+    base_addr: u16,
+}
+
+impl IteChip {
+    pub fn new(name: String, chip_id: u16, base_addr: u16) -> Self {
+        Self {
+            name,
+            chip_id,
+            base_addr,
+        }
+    }
+
+    /// 读取 EC 寄存器
+    /// ITE 使用 地址端口 (base + 0x05) / 数据端口 (base + 0x06)
+    fn read_register(&self, drv: &DriverHandle, reg: u8) -> Result<u8> {
+        drv.write_io_port_byte(self.base_addr + 0x05, reg)?;
+        drv.read_io_port_byte(self.base_addr + 0x06)
+    }
+}
+
+/// ITE 风扇转速计通道定义
+/// 16-bit 计数值 = (高字节 << 8) | 低字节
+/// RPM = 1,350,000 / count
+#[derive(Clone, Copy)]
+struct IteFanChannel {
+    name: &'static str,
+    /// 计数值低字节寄存器
+    count_low_reg: u8,
+    /// 计数值高字节寄存器（扩展寄存器）
+    count_high_reg: u8,
+    /// 通道编号
+    channel: u8,
+}
+
+/// ITE 温度传感器通道定义
+struct IteTempChannel {
+    name: &'static str,
+    /// 温度寄存器（8-bit 带符号整数）
+    reg: u8,
+    /// 通道编号
+    channel: u8,
+}
+
+/// FAN1–FAN5，各型号位置相同
+const FAN_CHANNELS: &[IteFanChannel] = &[
+    IteFanChannel {
+        name: "CPU Fan",
+        count_low_reg: 0x0D,
+        count_high_reg: 0x18,
+        channel: 0,
+    }, // FAN1
+    IteFanChannel {
+        name: "机箱 #1",
+        count_low_reg: 0x0E,
+        count_high_reg: 0x19,
+        channel: 1,
+    }, // FAN2
+    IteFanChannel {
+        name: "机箱 #2",
+        count_low_reg: 0x0F,
+        count_high_reg: 0x1A,
+        channel: 2,
+    }, // FAN3
+    IteFanChannel {
+        name: "机箱 #3",
+        count_low_reg: 0x80,
+        count_high_reg: 0x81,
+        channel: 3,
+    }, // FAN4
+    IteFanChannel {
+        name: "机箱 #4",
+        count_low_reg: 0x82,
+        count_high_reg: 0x83,
+        channel: 4,
+    }, // FAN5
+];
+
+/// FAN6（IT8628E、IT8686E、IT8689E 等）
+const FAN6: IteFanChannel = IteFanChannel {
+    name: "机箱 #5",
+    count_low_reg: 0x4C,
+    count_high_reg: 0x4D,
+    channel: 5,
+};
+
+/// IT8665E 的 FAN6
+const FAN6_IT8665: IteFanChannel = IteFanChannel {
+    name: "机箱 #5",
+    count_low_reg: 0x93,
+    count_high_reg: 0x94,
+    channel: 5,
+};
+
+/// ITE 温度通道
+const TEMP_CHANNELS: &[IteTempChannel] = &[
+    IteTempChannel {
+        name: "CPU",
+        reg: 0x29,
+        channel: 0,
+    }, // TMPIN1
+    IteTempChannel {
+        name: "主板",
+        reg: 0x2A,
+        channel: 1,
+    }, // TMPIN2
+    IteTempChannel {
+        name: "辅助",
+        reg: 0x2B,
+        channel: 2,
+    }, // TMPIN3
+];
+
+/// ITE 扩展温度通道（TMPIN4–TMPIN6）
+/// 外部热敏电阻 / PECI / AMD TSI 的读数映射到这些寄存器，
+/// 部分 ASUS SKU 仅在此处提供 CPU 封装与 VRM 温度
+const TEMP_CHANNELS_EXT: &[IteTempChannel] = &[
+    IteTempChannel {
+        name: "外部 1",
+        reg: 0x2C,
+        channel: 3,
+    }, // TMPIN4
+    IteTempChannel {
+        name: "外部 2",
+        reg: 0x2D,
+        channel: 4,
+    }, // TMPIN5
+    IteTempChannel {
+        name: "外部 3",
+        reg: 0x2E,
+        channel: 5,
+    }, // TMPIN6
+];
+
+/// IT8665E 的扩展温度通道，位于 0x3E–0x40
+const TEMP_CHANNELS_EXT_IT8665: &[IteTempChannel] = &[
+    IteTempChannel {
+        name: "外部 1",
+        reg: 0x3E,
+        channel: 3,
+    }, // TMPIN4
+    IteTempChannel {
+        name: "外部 2",
+        reg: 0x3F,
+        channel: 4,
+    }, // TMPIN5
+    IteTempChannel {
+        name: "外部 3",
+        reg: 0x40,
+        channel: 5,
+    }, // TMPIN6
+];
+
+/// 某一型号的风扇与温度寄存器布局
+struct IteLayout {
+    /// FAN1–FAN5 中存在的路数
+    fans: usize,
+    /// FAN6，没有第 6 路时为 `None`
+    fan6: Option<IteFanChannel>,
+    /// TMPIN4–TMPIN6
+    temps_ext: &'static [IteTempChannel],
+}
+
+impl Chip for IteChip {
+    fn chip_name(&self) -> &str {
+        &self.name
+    }
+
+    fn base_addr(&self) -> Option<u16> {
+        Some(self.base_addr)
+    }
+
+    fn read_fans(&self, io: &dyn SioAccess) -> Result<Vec<FanReading>> {
+        let drv = require_driver(io, Msg::SioReadIte)?;
+        let mut fans = Vec::new();
+
+        // 确认 16-bit 风扇计数器模式已开启
+        // Configuration Register 0x0C bit 6: 16-bit 模式
+        let config = self.read_register(drv, 0x0C)?;
+        let is_16bit = (config & 0x40) != 0;
+
+        let layout = self.layout();
+        for fc in FAN_CHANNELS[..layout.fans].iter().chain(&layout.fan6) {
+            let low = self.read_register(drv, fc.count_low_reg)? as u16;
+
+            let count = if is_16bit {
+                let high = self.read_register(drv, fc.count_high_reg)? as u16;
+                (high << 8) | low
+            } else {
+                // 8-bit 模式下低字节即为全部计数值
+                low
+            };
+
+            // 计算 RPM：count=0 或 0xFFFF 表示停转/未接入
+            let rpm = if count == 0 || count == 0xFFFF {
+                0
+            } else {
+                1_350_000 / count as u32
+            };
+
+            fans.push(FanReading {
+                name: fc.name.to_string(),
+                rpm,
+                channel: fc.channel,
+                status: FanPresence::from_rpm(rpm),
+            });
+        }
+
+        Ok(fans)
+    }
+
+    fn dump_registers(&self, io: &dyn SioAccess) -> Result<RegisterDump> {
+        let drv = require_driver(io, Msg::SioReadIte)?;
+        // Environment Controller 没有 bank，寄存器全部在一页内
+        let registers = (0..=u8::MAX)
+            .map(|reg| self.read_register(drv, reg))
+            .collect::<Result<_>>()?;
+        Ok(RegisterDump {
+            chip_name: self.name.clone(),
+            chip_id: self.chip_id,
+            base_addr: self.base_addr,
+            access: io.name(),
+            banks: vec![RegisterBank { bank: 0, registers }],
+        })
+    }
+
+    fn read_temps(&self, io: &dyn SioAccess) -> Result<Vec<TempReading>> {
+        let drv = require_driver(io, Msg::SioReadIte)?;
+        let mut temps = Vec::new();
+
+        for tc in TEMP_CHANNELS.iter().chain(self.layout().temps_ext) {
+            let raw = self.read_register(drv, tc.reg)? as i8;
+            let temp_c = raw as f32;
+
+            // 过滤无效读数（未接入的扩展通道通常返回 0x80 = -128）
+            if temp_c < -40.0 || temp_c > 125.0 {
+                continue;
+            }
+
+            temps.push(TempReading {
+                name: tc.name.to_string(),
+                temp_c,
+                channel: tc.channel,
+            });
+        }
+
+        Ok(temps)
+    }
+}
+
+impl IteChip {
+    /// 该芯片的风扇与温度寄存器布局
+    /// 参考 Linux it87 驱动：FAN6 通常位于 0x4C / 0x4D，IT8665E 则位于 0x93 / 0x94；
+    /// TMPIN4–6 通常紧接 TMPIN1–3（0x2C–0x2E），IT8665E 位于 0x3E–0x40；
+    /// IT8792E 常作为第二颗芯片，只有 3 路风扇与 3 路温度
+    const fn layout(&self) -> IteLayout {
+        match self.chip_id {
+            0x8628 | 0x8686 | 0x8689 | 0x8695 => IteLayout {
+                fans: 5,
+                fan6: Some(FAN6),
+                temps_ext: TEMP_CHANNELS_EXT,
+            },
+            0x8688 => IteLayout {
+                fans: 5,
+                fan6: None,
+                temps_ext: TEMP_CHANNELS_EXT,
+            },
+            0x8665 => IteLayout {
+                fans: 5,
+                fan6: Some(FAN6_IT8665),
+                temps_ext: TEMP_CHANNELS_EXT_IT8665,
+            },
+            0x8792 => IteLayout {
+                fans: 3,
+                fan6: None,
+                temps_ext: &[],
+            },
+            _ => IteLayout {
+                fans: 5,
+                fan6: None,
+                temps_ext: &[],
+            },
+        }
+    }
+}
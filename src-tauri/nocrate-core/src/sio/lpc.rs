@@ -0,0 +1,450 @@
+// LPC / eSPI 桥的 I/O 解码
+// Super I/O 挂在 LPC（或 eSPI）总线上，CPU 的 I/O 周期只有落在桥接器已使能的解码范围内才会被转发，
+// 否则读回 0xFF；固件没有为 HW Monitor 基地址打开解码时需要补上
+// 支持 AMD FCH（0/0x14/3）的 Wide IO 与 Intel PCH（0/0x1F/0）的通用 I/O 解码范围（LGIR）
+// 100 系列及之后的 Intel PCH 在 DMI（私有配置空间）中还有一份 LGIR 副本，须由软件同时写入，不会自动同步；
+// 这里只写 LPC / eSPI 桥，因此仅在此前的 PCH 上修改 LGIR
+
+use serde::Serialize;
+
+use super::driver::DriverHandle;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+/// 桥接器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LpcBridgeKind {
+    AmdFch,
+    IntelPch,
+}
+
+/// 桥接器在 PCI 上的位置
+#[derive(Debug, Clone, Copy)]
+struct Bridge {
+    kind: LpcBridgeKind,
+    bus: u8,
+    device: u8,
+    function: u8,
+    vendor_id: u16,
+}
+
+impl Bridge {
+    fn read(self, drv: &DriverHandle, offset: u8) -> Result<u32> {
+        drv.read_pci_config(self.bus, self.device, self.function, u32::from(offset))
+    }
+
+    fn write(self, drv: &DriverHandle, offset: u8, value: u32) -> Result<()> {
+        drv.write_pci_config(
+            self.bus,
+            self.device,
+            self.function,
+            u32::from(offset),
+            value,
+        )
+    }
+}
+
+const BRIDGES: [Bridge; 2] = [
+    Bridge {
+        kind: LpcBridgeKind::AmdFch,
+        bus: 0,
+        device: 0x14,
+        function: 3,
+        vendor_id: 0x1022,
+    },
+    Bridge {
+        kind: LpcBridgeKind::IntelPch,
+        bus: 0,
+        device: 0x1F,
+        function: 0,
+        vendor_id: 0x8086,
+    },
+];
+
+// AMD FCH LPC 桥寄存器
+/// IO Port Decode Enable
+const AMD_IO_PORT_DECODE: u8 = 0x44;
+/// IO/Mem Port Decode Enable
+const AMD_IO_MEM_DECODE: u8 = 0x48;
+/// Wide IO 0（bit 15:0）/ Wide IO 1（bit 31:16）基地址
+const AMD_WIDE_IO_01: u8 = 0x64;
+/// Alternative Wide IO Range Enable
+const AMD_ALT_WIDE_IO: u8 = 0x74;
+/// Wide IO 2 基地址（bit 15:0）
+const AMD_WIDE_IO_2: u8 = 0x90;
+/// 0x48 中 0x2E/0x2F 与 0x4E/0x4F 配置端口的使能位
+const AMD_SIO_2E_ENABLE: u32 = 1 << 0;
+const AMD_SIO_4E_ENABLE: u32 = 1 << 1;
+/// 0x48 中 Wide IO 0/1/2 的使能位
+const AMD_WIDE_IO_ENABLE: [u32; 3] = [1 << 2, 1 << 24, 1 << 25];
+/// 0x74 中把 Wide IO 0/1/2 从 512 字节改为 16 字节的位
+const AMD_WIDE_IO_ALT: [u32; 3] = [1 << 0, 1 << 2, 1 << 3];
+
+// Intel PCH LPC / eSPI 桥寄存器
+/// I/O Decode Ranges（bit 15:0）与 I/O Enables（bit 31:16）
+const INTEL_IO_DECODE: u8 = 0x80;
+/// LPC Generic I/O Range 1~4
+const INTEL_GEN_DECODE: [u8; 4] = [0x84, 0x88, 0x8C, 0x90];
+const INTEL_GEN_NAMES: [&str; 4] = ["LGIR1", "LGIR2", "LGIR3", "LGIR4"];
+/// LGIR 的使能位与地址掩码（bit 23:18 对应地址 bit 7:2）
+const INTEL_GEN_ENABLE: u32 = 1;
+/// 为 HW Monitor 打开 16 字节范围：屏蔽地址 bit 3:2
+const INTEL_GEN_MASK_16: u32 = 0b11 << 18;
+/// I/O Enables 中 0x2E/0x2F 与 0x4E/0x4F 的使能位
+const INTEL_SIO_2E_ENABLE: u32 = 1 << (16 + 12);
+const INTEL_SIO_4E_ENABLE: u32 = 1 << (16 + 13);
+
+/// LGIR 只存在于 LPC 桥、无 DMI 副本的 PCH（6 ~ 9 系列、C600 / C610）的 LPC 桥设备 ID 范围
+const INTEL_LGIR_ONLY_PCH_IDS: [(u16, u16); 8] = [
+    (0x1C40, 0x1C5F),
+    (0x1D40, 0x1D5F),
+    (0x1E40, 0x1E5F),
+    (0x8C40, 0x8C5F),
+    (0x8CC0, 0x8CDF),
+    (0x8D40, 0x8D5F),
+    (0x9C40, 0x9C5F),
+    (0x9CC0, 0x9CDF),
+];
+
+/// 一个原始寄存器值
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodeRegister {
+    pub offset: u8,
+    pub name: &'static str,
+    pub value: u32,
+}
+
+/// 一段可配置的 I/O 解码范围
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodeRange {
+    pub name: &'static str,
+    pub base: u16,
+    pub size: u16,
+    pub enabled: bool,
+}
+
+impl DecodeRange {
+    fn contains(&self, port: u16) -> bool {
+        self.enabled
+            && port >= self.base
+            && u32::from(port) < u32::from(self.base) + u32::from(self.size)
+    }
+}
+
+/// 一个 HW Monitor 基地址的解码情况
+#[derive(Debug, Clone, Serialize)]
+pub struct HwmDecode {
+    pub base: u16,
+    /// 覆盖地址 / 数据端口（base+5 / base+6）的解码范围，`None` 表示未被转发
+    pub range: Option<&'static str>,
+}
+
+/// LPC / eSPI 桥解码寄存器导出
+#[derive(Debug, Clone, Serialize)]
+pub struct LpcDecodeDump {
+    pub bridge: LpcBridgeKind,
+    pub bus: u8,
+    pub device: u8,
+    pub function: u8,
+    pub vendor_id: u16,
+    pub device_id: u16,
+    /// 0x2E/0x2F 配置端口是否被转发
+    pub sio_2e_decoded: bool,
+    /// 0x4E/0x4F 配置端口是否被转发
+    pub sio_4e_decoded: bool,
+    pub registers: Vec<DecodeRegister>,
+    pub ranges: Vec<DecodeRange>,
+    pub hwm: Vec<HwmDecode>,
+}
+
+/// 导出桥接器的解码寄存器，并检查 `hwm_bases` 中每个 HW Monitor 基地址是否被转发
+pub fn dump(drv: &DriverHandle, hwm_bases: &[u16]) -> Result<LpcDecodeDump> {
+    let (bridge, device_id) = find_bridge(drv)?
+        .ok_or_else(|| NoCrateError::DeviceNotPresent(i18n::t(Msg::LpcBridgeNotFound).into()))?;
+    let mut registers = vec![DecodeRegister {
+        offset: 0x00,
+        name: "Vendor/Device ID",
+        value: bridge.read(drv, 0x00)?,
+    }];
+    let decode = match bridge.kind {
+        LpcBridgeKind::AmdFch => amd_decode(drv, bridge, &mut registers)?,
+        LpcBridgeKind::IntelPch => intel_decode(drv, bridge, &mut registers)?,
+    };
+
+    let hwm = hwm_bases
+        .iter()
+        .map(|&base| HwmDecode {
+            base,
+            range: decoding_range(&decode.ranges, base).map(|r| r.name),
+        })
+        .collect();
+    Ok(LpcDecodeDump {
+        bridge: bridge.kind,
+        bus: bridge.bus,
+        device: bridge.device,
+        function: bridge.function,
+        vendor_id: bridge.vendor_id,
+        device_id,
+        sio_2e_decoded: decode.sio_2e,
+        sio_4e_decoded: decode.sio_4e,
+        registers,
+        ranges: decode.ranges,
+        hwm,
+    })
+}
+
+/// 从寄存器解析出的解码状态
+struct Decode {
+    ranges: Vec<DecodeRange>,
+    sio_2e: bool,
+    sio_4e: bool,
+}
+
+/// 读取 AMD FCH 的解码寄存器
+fn amd_decode(
+    drv: &DriverHandle,
+    bridge: Bridge,
+    registers: &mut Vec<DecodeRegister>,
+) -> Result<Decode> {
+    let mut reg = |offset, name| {
+        let value = bridge.read(drv, offset)?;
+        registers.push(DecodeRegister {
+            offset,
+            name,
+            value,
+        });
+        Ok::<_, NoCrateError>(value)
+    };
+    let _ = reg(AMD_IO_PORT_DECODE, "IO Port Decode Enable")?;
+    let io_mem = reg(AMD_IO_MEM_DECODE, "IO/Mem Port Decode Enable")?;
+    let wide_01 = reg(AMD_WIDE_IO_01, "Wide IO 0/1")?;
+    let alt = reg(AMD_ALT_WIDE_IO, "Alternative Wide IO Range Enable")?;
+    let wide_2 = reg(AMD_WIDE_IO_2, "Wide IO 2")?;
+    Ok(Decode {
+        ranges: amd_wide_ranges(io_mem, wide_01, alt, wide_2),
+        sio_2e: io_mem & AMD_SIO_2E_ENABLE != 0,
+        sio_4e: io_mem & AMD_SIO_4E_ENABLE != 0,
+    })
+}
+
+/// 读取 Intel PCH 的解码寄存器
+fn intel_decode(
+    drv: &DriverHandle,
+    bridge: Bridge,
+    registers: &mut Vec<DecodeRegister>,
+) -> Result<Decode> {
+    let io_decode = bridge.read(drv, INTEL_IO_DECODE)?;
+    registers.push(DecodeRegister {
+        offset: INTEL_IO_DECODE,
+        name: "IO Decode Ranges/Enables",
+        value: io_decode,
+    });
+    let mut ranges = Vec::new();
+    for (offset, name) in INTEL_GEN_DECODE.into_iter().zip(INTEL_GEN_NAMES) {
+        let value = bridge.read(drv, offset)?;
+        registers.push(DecodeRegister {
+            offset,
+            name,
+            value,
+        });
+        ranges.push(intel_gen_range(name, value));
+    }
+    Ok(Decode {
+        ranges,
+        sio_2e: io_decode & INTEL_SIO_2E_ENABLE != 0,
+        sio_4e: io_decode & INTEL_SIO_4E_ENABLE != 0,
+    })
+}
+
+/// 检查并启用 LPC 桥对 HW Monitor I/O 范围的解码
+/// 用于确保 Super I/O HW Monitor 的 ISA I/O 空间被正确转发到 LPC 总线
+pub fn enable_io_decode(drv: &DriverHandle, base_addr: u16) -> Result<()> {
+    let Some((bridge, device_id)) = find_bridge(drv)? else {
+        return Ok(());
+    };
+    eprintln!(
+        "[SIO-LPC] LPC bridge {:?} VendorID:DeviceID = {:04X}:{device_id:04X}",
+        bridge.kind, bridge.vendor_id
+    );
+    let configured = match bridge.kind {
+        LpcBridgeKind::AmdFch => amd_enable(drv, bridge, base_addr)?,
+        LpcBridgeKind::IntelPch => intel_enable(drv, bridge, device_id, base_addr)?,
+    };
+    if !configured {
+        return Ok(());
+    }
+
+    // 验证
+    let test_val = drv.read_io_port_byte(base_addr + 5)?;
+    eprintln!("[SIO-LPC] 解码配置后验证: read base+5 = 0x{test_val:02X}");
+    Ok(())
+}
+
+/// 在 AMD FCH 上找一个未使用的 Wide IO 范围转发 `base_addr`，已被转发时返回 `false`
+fn amd_enable(drv: &DriverHandle, bridge: Bridge, base_addr: u16) -> Result<bool> {
+    let io_mem = bridge.read(drv, AMD_IO_MEM_DECODE)?;
+    let wide_01 = bridge.read(drv, AMD_WIDE_IO_01)?;
+    let wide_2 = bridge.read(drv, AMD_WIDE_IO_2)?;
+    let ranges = amd_wide_ranges(io_mem, wide_01, bridge.read(drv, AMD_ALT_WIDE_IO)?, wide_2);
+    let Some(slot) = free_slot(&ranges, base_addr)? else {
+        return Ok(false);
+    };
+
+    eprintln!("[SIO-LPC] 配置 Wide IO {slot} = 0x{base_addr:04X}");
+    let base = u32::from(base_addr);
+    match slot {
+        0 => bridge.write(drv, AMD_WIDE_IO_01, (wide_01 & 0xFFFF_0000) | base)?,
+        1 => bridge.write(drv, AMD_WIDE_IO_01, (wide_01 & 0x0000_FFFF) | (base << 16))?,
+        _ => bridge.write(drv, AMD_WIDE_IO_2, (wide_2 & 0xFFFF_0000) | base)?,
+    }
+    bridge.write(drv, AMD_IO_MEM_DECODE, io_mem | AMD_WIDE_IO_ENABLE[slot])?;
+    Ok(true)
+}
+
+/// 在 Intel PCH 上找一个未使用的 LGIR，打开包含 `base_addr` 的 16 字节范围，
+/// 已被转发或 PCH 带有 DMI 副本（只写一份会使两者不一致）时返回 `false`
+fn intel_enable(
+    drv: &DriverHandle,
+    bridge: Bridge,
+    device_id: u16,
+    base_addr: u16,
+) -> Result<bool> {
+    let mut ranges = Vec::new();
+    for (offset, name) in INTEL_GEN_DECODE.into_iter().zip(INTEL_GEN_NAMES) {
+        ranges.push(intel_gen_range(name, bridge.read(drv, offset)?));
+    }
+    let Some(slot) = free_slot(&ranges, base_addr)? else {
+        return Ok(false);
+    };
+    if !INTEL_LGIR_ONLY_PCH_IDS
+        .iter()
+        .any(|&(first, last)| (first..=last).contains(&device_id))
+    {
+        eprintln!("[SIO-LPC] PCH {device_id:04X} 的 LGIR 在 DMI 中另有副本，不修改解码范围");
+        return Ok(false);
+    }
+
+    // 范围基地址须按大小对齐；HW Monitor 基地址按 8 字节对齐，地址 / 数据端口 base+5 / base+6
+    // 在向下对齐到 16 字节后的范围内
+    let base = u32::from(base_addr & !0xF);
+    eprintln!("[SIO-LPC] 配置 {} = 0x{base:04X}/16", ranges[slot].name);
+    bridge.write(
+        drv,
+        INTEL_GEN_DECODE[slot],
+        base | INTEL_GEN_MASK_16 | INTEL_GEN_ENABLE,
+    )?;
+    Ok(true)
+}
+
+/// 打印各范围并找一个可用于 `base_addr` 的空闲范围；已被转发时返回 `None`
+fn free_slot(ranges: &[DecodeRange], base_addr: u16) -> Result<Option<usize>> {
+    for range in ranges {
+        eprintln!(
+            "[SIO-LPC] {}: base=0x{:04X} size={} enabled={}",
+            range.name, range.base, range.size, range.enabled
+        );
+    }
+    if let Some(range) = decoding_range(ranges, base_addr) {
+        eprintln!("[SIO-LPC] HW Monitor I/O 范围已由 {} 解码", range.name);
+        return Ok(None);
+    }
+    ranges
+        .iter()
+        .position(|r| !r.enabled)
+        .map(Some)
+        .ok_or_else(|| NoCrateError::Sio(i18n::t(Msg::LpcDecodeRangesFull).into()))
+}
+
+/// 本模块是否读取（`write` 为 `true` 时：写入）桥接器的这个配置寄存器
+pub(crate) fn touches(bus: u8, device: u8, function: u8, offset: u32, write: bool) -> bool {
+    let Some(bridge) = BRIDGES
+        .iter()
+        .find(|b| (b.bus, b.device, b.function) == (bus, device, function))
+    else {
+        return false;
+    };
+    let (read, written): (&[u8], &[u8]) = match bridge.kind {
+        LpcBridgeKind::AmdFch => (
+            &[
+                0x00,
+                AMD_IO_PORT_DECODE,
+                AMD_IO_MEM_DECODE,
+                AMD_WIDE_IO_01,
+                AMD_ALT_WIDE_IO,
+                AMD_WIDE_IO_2,
+            ],
+            &[AMD_IO_MEM_DECODE, AMD_WIDE_IO_01, AMD_WIDE_IO_2],
+        ),
+        LpcBridgeKind::IntelPch => (
+            &[
+                0x00,
+                INTEL_IO_DECODE,
+                INTEL_GEN_DECODE[0],
+                INTEL_GEN_DECODE[1],
+                INTEL_GEN_DECODE[2],
+                INTEL_GEN_DECODE[3],
+            ],
+            &INTEL_GEN_DECODE,
+        ),
+    };
+    let registers = if write { written } else { read };
+    registers.iter().any(|&r| u32::from(r) == offset)
+}
+
+/// 按厂商 ID 找到桥接器，返回其位置与设备 ID
+fn find_bridge(drv: &DriverHandle) -> Result<Option<(Bridge, u16)>> {
+    for bridge in BRIDGES {
+        let id = drv.read_pci_config(bridge.bus, bridge.device, bridge.function, 0x00)?;
+        if low(id) == bridge.vendor_id {
+            return Ok(Some((bridge, high(id))));
+        }
+    }
+    Ok(None)
+}
+
+/// AMD FCH 的三个 Wide IO 范围
+fn amd_wide_ranges(io_mem: u32, wide_01: u32, alt: u32, wide_2: u32) -> Vec<DecodeRange> {
+    let bases = [low(wide_01), high(wide_01), low(wide_2)];
+    ["Wide IO 0", "Wide IO 1", "Wide IO 2"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| DecodeRange {
+            name,
+            base: bases[i],
+            size: if alt & AMD_WIDE_IO_ALT[i] != 0 {
+                16
+            } else {
+                512
+            },
+            enabled: io_mem & AMD_WIDE_IO_ENABLE[i] != 0,
+        })
+        .collect()
+}
+
+/// Intel 通用 I/O 解码范围：bit 0 使能，bit 15:2 基地址，bit 23:18 为地址 bit 7:2 的掩码
+const fn intel_gen_range(name: &'static str, value: u32) -> DecodeRange {
+    DecodeRange {
+        name,
+        base: low(value) & 0xFFFC,
+        size: (high(value) & 0xFC) + 4,
+        enabled: value & INTEL_GEN_ENABLE != 0,
+    }
+}
+
+/// 同时覆盖 HW Monitor 地址 / 数据端口的范围
+fn decoding_range(ranges: &[DecodeRange], base: u16) -> Option<&DecodeRange> {
+    ranges
+        .iter()
+        .find(|r| r.contains(base.saturating_add(5)) && r.contains(base.saturating_add(6)))
+}
+
+const fn low(value: u32) -> u16 {
+    let [a, b, _, _] = value.to_le_bytes();
+    u16::from_le_bytes([a, b])
+}
+
+const fn high(value: u32) -> u16 {
+    let [_, _, a, b] = value.to_le_bytes();
+    u16::from_le_bytes([a, b])
+}
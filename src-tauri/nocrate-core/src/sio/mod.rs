@@ -0,0 +1,332 @@
+// Super I/O 模块
+// 读取 Super I/O 芯片的风扇转速和温度传感器
+// 优先经 ASUS WMI（asio_hw_fun*）访问，不可用时通过 WinRing0x64 内核驱动访问
+
+pub mod access;
+pub mod chips;
+pub mod detect;
+pub mod diag;
+pub mod driver;
+pub mod embedded;
+pub mod ite;
+pub mod lpc;
+pub mod nuvoton;
+pub mod policy;
+pub mod sim;
+pub mod trace;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::board::SioChannelConfig;
+use crate::error::Result;
+use crate::wmi::asus_mgmt::FanPresence;
+use crate::wmi::thread::WmiThread;
+use access::{AsioHwAccess, SioAccess};
+use chips::{Chip, FanReading, RegisterDump, SioSnapshot, SioStatus};
+use driver::DriverHandle;
+
+/// Super I/O 传感器监控器
+/// 持有寄存器访问方式和芯片实例，通过 Mutex 保证线程安全
+pub struct SioMonitor {
+    inner: Mutex<SioInner>,
+    chip_name: String,
+    /// 用户配置的通道别名与隐藏列表
+    channels: Mutex<SioChannelConfig>,
+}
+
+struct SioInner {
+    access: Arc<dyn SioAccess>,
+    chip: Box<dyn Chip>,
+    /// 转速毛刺过滤
+    tach: TachFilter,
+    /// 启动以来转速非零过的通道，其 0 RPM 读数报告为停转而非未接入
+    seen_spinning: HashSet<u8>,
+}
+
+impl SioMonitor {
+    /// 初始化 SIO 监控器
+    /// 使用已加载的 WinRing0 驱动 → 探测 Super I/O 芯片 → 返回初始化完成的监控器
+    pub fn init(driver: Arc<DriverHandle>) -> Result<Self> {
+        let chip = detect::detect_chip(driver.as_ref())?;
+        Self::with_chip(driver, chip)
+    }
+
+    /// 经 ASUS 桌面 WMI 后端的 `asio_hw_fun*` 初始化，无需加载内核驱动
+    /// 仅支持 Nuvoton 芯片；后端不是 Desktop 或读取失败时返回错误，由调用方改用驱动
+    pub fn init_wmi(wmi: WmiThread) -> Result<Self> {
+        let access: Arc<dyn SioAccess> = Arc::new(AsioHwAccess::new(wmi));
+        let chip = detect::detect_chip(access.as_ref())?;
+        Self::with_chip(access, chip)
+    }
+
+    /// 用探测到的芯片完成初始化
+    fn with_chip(access: Arc<dyn SioAccess>, chip: Box<dyn Chip>) -> Result<Self> {
+        let chip_name = chip.chip_name().to_string();
+
+        eprintln!("SIO: 初始化成功，芯片: {chip_name}，访问方式: {}", access.name());
+
+        // 初始化后立即做一次测试读取，输出诊断信息
+        {
+            let fans = chip.read_fans(access.as_ref())?;
+            let temps = chip.read_temps(access.as_ref())?;
+            eprintln!("[SIO] 测试读取 — 风扇:");
+            for f in &fans {
+                eprintln!("[SIO]   {} (ch{}): {} RPM", f.name, f.channel, f.rpm);
+            }
+            eprintln!("[SIO] 测试读取 — 温度:");
+            for t in &temps {
+                eprintln!("[SIO]   {} (ch{}): {:.1}°C", t.name, t.channel, t.temp_c);
+            }
+        }
+
+        Ok(Self {
+            inner: Mutex::new(SioInner {
+                access,
+                chip,
+                tach: TachFilter::default(),
+                seen_spinning: HashSet::new(),
+            }),
+            chip_name,
+            channels: Mutex::new(SioChannelConfig::default()),
+        })
+    }
+
+    /// 使用模拟芯片初始化（--simulate），不访问硬件
+    pub fn simulated(driver: Arc<DriverHandle>) -> Self {
+        let chip: Box<dyn Chip> = Box::new(sim::SimChip::new());
+        let chip_name = chip.chip_name().to_string();
+        eprintln!("SIO: 模拟模式，芯片: {chip_name}");
+        Self {
+            inner: Mutex::new(SioInner {
+                access: driver,
+                chip,
+                tach: TachFilter::default(),
+                seen_spinning: HashSet::new(),
+            }),
+            chip_name,
+            channels: Mutex::new(SioChannelConfig::default()),
+        }
+    }
+
+    /// 以当前访问方式执行 `f`，期间暂停传感器读取
+    pub fn with_access<T>(&self, f: impl FnOnce(&dyn SioAccess) -> T) -> T {
+        let inner = self.inner.lock();
+        f(inner.access.as_ref())
+    }
+
+    /// 更新通道别名与隐藏配置，之后的 `read_all` 均按新配置输出
+    pub fn set_channel_config(&self, channels: SioChannelConfig) {
+        *self.channels.lock() = channels;
+    }
+
+    /// 读取所有传感器数据快照
+    /// 返回前应用用户配置的别名并剔除隐藏通道，保证所有调用方看到一致的名称
+    pub fn read_all(&self) -> Result<SioSnapshot> {
+        let (mut fans, mut temps, intrusion) = {
+            let mut inner = self.inner.lock();
+            let mut fans = inner.chip.read_fans(inner.access.as_ref())?;
+            inner.tach.filter(&mut fans);
+            for f in &mut fans {
+                if f.rpm > 0 {
+                    let _ = inner.seen_spinning.insert(f.channel);
+                }
+                f.status = FanPresence::from_rpm(f.rpm)
+                    .with_known_present(inner.seen_spinning.contains(&f.channel));
+            }
+            // 入侵检测只是附带信息，读取失败时按未知处理，不影响转速与温度
+            let intrusion = inner
+                .chip
+                .read_intrusion(inner.access.as_ref())
+                .unwrap_or_else(|e| {
+                    eprintln!("SIO: 读取机箱入侵状态失败: {e}");
+                    None
+                });
+            (
+                fans,
+                inner.chip.read_temps(inner.access.as_ref())?,
+                intrusion,
+            )
+        };
+
+        let channels = self.channels.lock();
+        fans.retain(|f| !channels.is_hidden("fan", f.channel));
+        for f in &mut fans {
+            if let Some(alias) = channels.alias("fan", f.channel) {
+                f.name = alias.to_string();
+            }
+        }
+        temps.retain(|t| !channels.is_hidden("temp", t.channel));
+        for t in &mut temps {
+            if let Some(alias) = channels.alias("temp", t.channel) {
+                t.name = alias.to_string();
+            }
+        }
+
+        Ok(SioSnapshot {
+            fans,
+            temps,
+            intrusion,
+            chip_name: self.chip_name.clone(),
+        })
+    }
+
+    /// 转储芯片 HW Monitor 的全部寄存器，期间暂停传感器读取
+    pub fn dump_registers(&self) -> Result<RegisterDump> {
+        let inner = self.inner.lock();
+        inner.chip.dump_registers(inner.access.as_ref())
+    }
+
+    /// 清除机箱入侵锁存位
+    pub fn clear_intrusion(&self) -> Result<()> {
+        let inner = self.inner.lock();
+        inner.chip.clear_intrusion(inner.access.as_ref())
+    }
+
+    /// 获取状态信息
+    pub fn status(&self) -> SioStatus {
+        SioStatus {
+            available: true,
+            chip_name: Some(self.chip_name.clone()),
+            error: None,
+        }
+    }
+}
+
+/// SIO 不可用时的状态
+pub fn unavailable_status(error: &str) -> SioStatus {
+    SioStatus {
+        available: false,
+        chip_name: None,
+        error: Some(error.to_string()),
+    }
+}
+
+// ================================================================
+// 转速毛刺过滤
+// 读取计数寄存器时与芯片更新发生竞争，偶尔得到 100000 RPM 之类的离谱值。
+// 每个通道保留最近 TACH_WINDOW 次原始读数，高出其中位数超过 TACH_MAX_DEVIATION
+// 的读数以中位数代替。原始读数仍计入窗口，转速真实跳变时中位数会在
+// 窗口过半后跟上，不会被永久拒绝。
+// 只拒绝向上的毛刺：转速下降（包括停转为 0）原样输出，停转检测与过热保护不会被延迟。
+// ================================================================
+
+/// 每个通道参与中位数的读数个数
+const TACH_WINDOW: usize = 5;
+
+/// 允许偏离中位数的比例
+const TACH_MAX_DEVIATION: f32 = 0.5;
+
+/// 超过此值的读数无论历史如何都视为毛刺，且不计入窗口
+const TACH_MAX_RPM: u32 = 30_000;
+
+#[derive(Default)]
+struct TachFilter {
+    history: HashMap<u8, VecDeque<u32>>,
+}
+
+impl TachFilter {
+    fn filter(&mut self, fans: &mut [FanReading]) {
+        for fan in fans {
+            let history = self.history.entry(fan.channel).or_default();
+            let median = median(history);
+            if fan.rpm > TACH_MAX_RPM {
+                fan.rpm = median.unwrap_or(0);
+                continue;
+            }
+
+            if history.len() == TACH_WINDOW {
+                let _ = history.pop_front();
+            }
+            history.push_back(fan.rpm);
+
+            // 窗口未满、中位数为 0（停转 → 启动）或转速下降时不判断偏离
+            let Some(median) = median.filter(|&m| m > 0 && fan.rpm > m) else {
+                continue;
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let deviation = (fan.rpm as f32 - median as f32).abs() / median as f32;
+            if deviation > TACH_MAX_DEVIATION {
+                fan.rpm = median;
+            }
+        }
+    }
+}
+
+/// 窗口已满时的中位数
+fn median(history: &VecDeque<u32>) -> Option<u32> {
+    if history.len() < TACH_WINDOW {
+        return None;
+    }
+    let mut sorted: Vec<u32> = history.iter().copied().collect();
+    sorted.sort_unstable();
+    Some(sorted[sorted.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fan(rpm: u32) -> FanReading {
+        FanReading {
+            name: "CPU Fan".into(),
+            rpm,
+            channel: 0,
+            status: FanPresence::from_rpm(rpm),
+        }
+    }
+
+    /// 依次送入读数，返回每次过滤后的转速
+    fn run(filter: &mut TachFilter, readings: &[u32]) -> Vec<u32> {
+        readings
+            .iter()
+            .map(|&rpm| {
+                let mut fans = [fan(rpm)];
+                filter.filter(&mut fans);
+                fans[0].rpm
+            })
+            .collect()
+    }
+
+    #[test]
+    fn upward_spike_is_replaced_by_median() {
+        let mut filter = TachFilter::default();
+        let out = run(&mut filter, &[1200, 1210, 1190, 1205, 1200, 2600, 1195]);
+        assert_eq!(out, [1200, 1210, 1190, 1205, 1200, 1200, 1195]);
+    }
+
+    #[test]
+    fn absurd_reading_is_never_passed_or_recorded() {
+        let mut filter = TachFilter::default();
+        let out = run(&mut filter, &[1200, 100_000, 1200]);
+        assert_eq!(out, [1200, 0, 1200]);
+        assert_eq!(filter.history[&0].len(), 2);
+    }
+
+    #[test]
+    fn real_stall_passes_immediately() {
+        let mut filter = TachFilter::default();
+        let out = run(&mut filter, &[1200, 1200, 1200, 1200, 1200, 0, 0]);
+        assert_eq!(out, [1200, 1200, 1200, 1200, 1200, 0, 0]);
+    }
+
+    #[test]
+    fn readings_pass_until_window_is_full() {
+        let mut filter = TachFilter::default();
+        let out = run(&mut filter, &[1200, 5000, 1200, 9000]);
+        assert_eq!(out, [1200, 5000, 1200, 9000]);
+    }
+
+    #[test]
+    fn sustained_speed_up_is_followed() {
+        let mut filter = TachFilter::default();
+        let out = run(
+            &mut filter,
+            &[800, 800, 800, 800, 800, 2000, 2000, 2000, 2000, 2000],
+        );
+        // 新转速占窗口过半后被接受
+        assert_eq!(out, [800, 800, 800, 800, 800, 800, 800, 800, 2000, 2000]);
+    }
+}
@@ -3,38 +3,70 @@
 // 支持型号：NCT6791D、NCT6792D、NCT6795D、NCT6796D、NCT6798D、NCT6799D
 // 寄存器定义参考 LibreHardwareMonitor 与 Nuvoton 数据手册
 
-use super::chips::{Chip, FanReading, TempReading};
+use super::access::{require_driver, SioAccess};
+use super::chips::{Chip, FanReading, IntrusionReading, RegisterBank, RegisterDump, TempReading};
 use super::driver::DriverHandle;
 use crate::error::Result;
+use crate::i18n::Msg;
+use crate::wmi::asus_mgmt::FanPresence;
 
 /// Nuvoton NCT67xxD 芯片实例
 pub struct NuvotonChip {
     name: String,
-    #[allow(dead_code)]
     chip_id: u16,
     /// HW Monitor I/O 基地址
     base_addr: u16,
+    /// Super I/O 配置端口（0x2E 或 0x4E），清除入侵锁存位时需要
+    config_port: u16,
 }
 
+/// CASEOPEN0 状态位：Bank 4 / 0x5A bit 4
+/// 参考 Linux nct6775 驱动 `NCT6779_REG_ALARM` / `NCT6779_ALARM_BITS`（intrusion0 = 12）
+const INTRUSION_STATUS_BANK: u8 = 4;
+const INTRUSION_STATUS_REG: u8 = 0x5A;
+const INTRUSION_STATUS_BIT: u8 = 0x10;
+
+/// 寄存器转储覆盖的 bank（0x0–0xF，风扇控制延伸到 bank 8~A）
+const DUMP_BANKS: std::ops::RangeInclusive<u8> = 0..=0x0F;
+
+/// 清除 CASEOPEN0：LDN 0x0A (ACPI) 配置寄存器 0xE6 bit 5，置位后再清零
+const INTRUSION_CLEAR_LDN: u8 = 0x0A;
+const INTRUSION_CLEAR_REG: u8 = 0xE6;
+const INTRUSION_CLEAR_BIT: u8 = 0x20;
+
 impl NuvotonChip {
-    pub fn new(name: String, chip_id: u16, base_addr: u16) -> Self {
+    pub fn new(name: String, chip_id: u16, base_addr: u16, config_port: u16) -> Self {
         Self {
             name,
             chip_id,
             base_addr,
+            config_port,
         }
     }
 
-    /// 读取指定 bank 和寄存器的值
-    /// Nuvoton ISA HW Monitor 使用 (base+5) 作为地址端口、(base+6) 作为数据端口
-    /// bank 切换通过写寄存器 0x4E 实现
-    fn read_register(&self, drv: &DriverHandle, bank: u8, reg: u8) -> Result<u8> {
-        // 切换 bank：地址端口 ← 0x4E，数据端口 ← bank
-        drv.write_io_port_byte(self.base_addr + 5, 0x4E)?;
-        drv.write_io_port_byte(self.base_addr + 6, bank)?;
-        // 读取目标寄存器：地址端口 ← reg，数据端口 → value
-        drv.write_io_port_byte(self.base_addr + 5, reg)?;
-        drv.read_io_port_byte(self.base_addr + 6)
+    /// 在扩展功能模式下对指定 LDN 的配置寄存器执行读-改-写
+    fn modify_config_register(
+        &self,
+        drv: &DriverHandle,
+        ldn: u8,
+        reg: u8,
+        f: impl Fn(u8) -> u8,
+    ) -> Result<()> {
+        let port = self.config_port;
+        let data_port = port + 1;
+
+        // 进入扩展功能模式
+        drv.write_io_port_byte(port, 0x87)?;
+        drv.write_io_port_byte(port, 0x87)?;
+
+        drv.write_io_port_byte(port, 0x07)?;
+        drv.write_io_port_byte(data_port, ldn)?;
+        drv.write_io_port_byte(port, reg)?;
+        let value = drv.read_io_port_byte(data_port)?;
+        drv.write_io_port_byte(data_port, f(value))?;
+
+        // 退出扩展功能模式
+        drv.write_io_port_byte(port, 0xAA)
     }
 }
 
@@ -171,14 +203,22 @@ impl Chip for NuvotonChip {
         &self.name
     }
 
-    fn read_fans(&self, drv: &DriverHandle) -> Result<Vec<FanReading>> {
+    fn base_addr(&self) -> Option<u16> {
+        Some(self.base_addr)
+    }
+
+    fn read_fans(&self, io: &dyn SioAccess) -> Result<Vec<FanReading>> {
+        // 风扇计数值在 Bank 4，全部通道一次读取
+        let registers: Vec<_> = FAN_CHANNELS
+            .iter()
+            .flat_map(|fc| [(4, fc.count_high_reg), (4, fc.count_low_reg)])
+            .collect();
+        let values = io.read_hwm_many(self.base_addr, &registers)?;
+
         let mut fans = Vec::new();
 
-        for fc in FAN_CHANNELS {
-            // 风扇计数值在 Bank 4
-            let high = self.read_register(drv, 4, fc.count_high_reg)? as u16;
-            let low = self.read_register(drv, 4, fc.count_low_reg)? as u16;
-            let count = (high << 8) | low;
+        for (fc, pair) in FAN_CHANNELS.iter().zip(values.chunks_exact(2)) {
+            let count = (u16::from(pair[0]) << 8) | u16::from(pair[1]);
 
             // 计算 RPM：count=0 或 0xFFFF 表示停转/未接入
             let rpm = if count == 0 || count == 0xFFFF {
@@ -191,18 +231,25 @@ impl Chip for NuvotonChip {
                 name: fc.name.to_string(),
                 rpm,
                 channel: fc.channel,
+                status: FanPresence::from_rpm(rpm),
             });
         }
 
         Ok(fans)
     }
 
-    fn read_temps(&self, drv: &DriverHandle) -> Result<Vec<TempReading>> {
+    fn read_temps(&self, io: &dyn SioAccess) -> Result<Vec<TempReading>> {
+        let registers: Vec<_> = TEMP_CHANNELS
+            .iter()
+            .flat_map(|tc| [(tc.bank, tc.int_reg), (tc.frac_bank, tc.frac_reg)])
+            .collect();
+        let values = io.read_hwm_many(self.base_addr, &registers)?;
+
         let mut temps = Vec::new();
 
-        for tc in TEMP_CHANNELS {
-            let int_val = self.read_register(drv, tc.bank, tc.int_reg)? as i8;
-            let frac_val = self.read_register(drv, tc.frac_bank, tc.frac_reg)?;
+        for (tc, pair) in TEMP_CHANNELS.iter().zip(values.chunks_exact(2)) {
+            let int_val = i8::from_ne_bytes([pair[0]]);
+            let frac_val = pair[1];
 
             // 温度 = 整数部分 + 小数部分高 1 位（0.5°C 精度）
             let frac = if frac_val & 0x80 != 0 { 0.5 } else { 0.0 };
@@ -223,4 +270,40 @@ impl Chip for NuvotonChip {
 
         Ok(temps)
     }
+
+    fn read_intrusion(&self, io: &dyn SioAccess) -> Result<Option<IntrusionReading>> {
+        let status = io.read_hwm(self.base_addr, INTRUSION_STATUS_BANK, INTRUSION_STATUS_REG)?;
+        Ok(Some(IntrusionReading {
+            name: "CASEOPEN0".to_string(),
+            triggered: status & INTRUSION_STATUS_BIT != 0,
+        }))
+    }
+
+    fn dump_registers(&self, io: &dyn SioAccess) -> Result<RegisterDump> {
+        let mut banks = Vec::new();
+        for bank in DUMP_BANKS {
+            let registers: Vec<_> = (0..=u8::MAX).map(|reg| (bank, reg)).collect();
+            banks.push(RegisterBank {
+                bank,
+                registers: io.read_hwm_many(self.base_addr, &registers)?,
+            });
+        }
+        Ok(RegisterDump {
+            chip_name: self.name.clone(),
+            chip_id: self.chip_id,
+            base_addr: self.base_addr,
+            access: io.name(),
+            banks,
+        })
+    }
+
+    fn clear_intrusion(&self, io: &dyn SioAccess) -> Result<()> {
+        let drv = require_driver(io, Msg::SioClearIntrusionLatch)?;
+        self.modify_config_register(drv, INTRUSION_CLEAR_LDN, INTRUSION_CLEAR_REG, |v| {
+            v | INTRUSION_CLEAR_BIT
+        })?;
+        self.modify_config_register(drv, INTRUSION_CLEAR_LDN, INTRUSION_CLEAR_REG, |v| {
+            v & !INTRUSION_CLEAR_BIT
+        })
+    }
 }
@@ -0,0 +1,103 @@
+// 远程端口访问白名单
+// NoCrate 服务以 LocalSystem 运行，替未提权的应用执行 PortOp；原样放行会让该用户的任何进程
+// 获得任意端口 I/O（PCI 配置空间、0xCF9 复位、0xB2 SMI 等），因此只放行应用实际用到的访问：
+// - Super I/O 配置端口 0x2E/0x2F、0x4E/0x4F
+// - 服务自己检测到的 HW Monitor 基址与 SMBus 主机寄存器范围
+// - EC 0x62/0x66（命令端口只接受读 / 写命令）与检测 SMBus 基址用的 AMD PM 寄存器
+// - LPC 桥与 SMBus 控制器的固定 PCI 配置寄存器、CPU 温度的 SMN 地址与 MSR
+
+use super::detect;
+use super::driver::{DriverHandle, PortOp};
+use super::lpc;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::{cpu, ec, smbus};
+
+/// Super I/O 配置索引 / 数据端口
+const SIO_CONFIG_PORTS: [u16; 4] = [0x2E, 0x2F, 0x4E, 0x4F];
+
+/// HW Monitor 基址起的端口数，覆盖 base+5 / base+6 与诊断读取的 base+0/1/7
+const HWM_PORTS: u16 = 8;
+
+/// SMBus 主机寄存器的端口数
+const SMBUS_PORTS: u16 = 8;
+
+/// 持有驱动的进程放行的访问
+#[derive(Debug, Clone, Default)]
+pub struct PortAllowlist {
+    hwm_base: Option<u16>,
+    smbus_base: Option<u16>,
+}
+
+impl PortAllowlist {
+    /// 在本地驱动上检测 Super I/O 芯片与 SMBus 控制器，得到可放行的基址
+    #[must_use]
+    pub fn detect(drv: &DriverHandle) -> Self {
+        let hwm_base = detect::detect_chip(drv)
+            .inspect_err(|e| eprintln!("[SIO] 白名单未包含 HW Monitor: {e}"))
+            .ok()
+            .and_then(|chip| chip.base_addr());
+        let smbus_base = smbus::detect_base(drv).map(|(_, base)| base);
+        Self {
+            hwm_base,
+            smbus_base,
+        }
+    }
+
+    /// 检查 `op` 是否在白名单内，不在时返回 `AccessDenied`
+    pub fn check(&self, op: PortOp) -> Result<()> {
+        let allowed = match op {
+            PortOp::ReadByte { port } => {
+                self.device_port(port)
+                    || port == ec::EC_DATA_PORT
+                    || port == ec::EC_COMMAND_PORT
+                    || port == smbus::AMD_PM_DATA
+            }
+            PortOp::WriteByte { port, value } => {
+                self.device_port(port)
+                    || port == ec::EC_DATA_PORT
+                    || (port == ec::EC_COMMAND_PORT
+                        && matches!(value, ec::EC_CMD_READ | ec::EC_CMD_WRITE))
+                    || (port == smbus::AMD_PM_INDEX && smbus::AMD_PM_SMBUS_REGS.contains(&value))
+            }
+            PortOp::ReadPci {
+                bus,
+                device,
+                function,
+                offset,
+            } => {
+                lpc::touches(bus, device, function, offset, false)
+                    || smbus::reads_pci(bus, device, function, offset)
+            }
+            PortOp::WritePci {
+                bus,
+                device,
+                function,
+                offset,
+                ..
+            } => lpc::touches(bus, device, function, offset, true),
+            PortOp::ReadSmn { addr } => cpu::reads_smn(addr),
+            PortOp::ReadMsr { index } => cpu::MSRS.contains(&index),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(NoCrateError::AccessDenied(i18n::tf(
+                Msg::SioPolicyDenied,
+                &[("op", &format!("{op:?}"))],
+            )))
+        }
+    }
+
+    /// Super I/O 配置端口，或检测到的 HW Monitor / SMBus 范围内的端口，可任意读写
+    fn device_port(&self, port: u16) -> bool {
+        let within = |base: Option<u16>, len: u16| {
+            base.is_some_and(|base| {
+                port >= base && u32::from(port) < u32::from(base) + u32::from(len)
+            })
+        };
+        SIO_CONFIG_PORTS.contains(&port)
+            || within(self.hwm_base, HWM_PORTS)
+            || within(self.smbus_base, SMBUS_PORTS)
+    }
+}
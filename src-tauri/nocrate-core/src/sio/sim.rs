@@ -0,0 +1,107 @@
+// 模拟 Super I/O 芯片（--simulate）
+//
+// 不访问任何端口，按时间缓慢起伏生成风扇转速与温度读数，
+// 机箱入侵锁存位保存在内存中，可被清除
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use super::access::SioAccess;
+use super::chips::{Chip, FanReading, IntrusionReading, TempReading};
+use crate::error::Result;
+use crate::wmi::asus_mgmt::FanPresence;
+use crate::wmi::sim::wobble;
+
+/// 模拟芯片显示的型号
+const CHIP_NAME: &str = "NCT6798D (模拟)";
+
+/// 风扇通道：名称、基准转速、波动幅度、周期（秒）
+const FANS: &[(&str, f32, f32, f32)] = &[
+    ("CPU Fan", 1100.0, 250.0, 90.0),
+    ("机箱 #1", 850.0, 120.0, 120.0),
+    ("机箱 #2", 820.0, 120.0, 130.0),
+    ("机箱 #3", 0.0, 0.0, 1.0),
+    ("AIO Pump", 2400.0, 60.0, 45.0),
+];
+
+/// 温度通道：名称、基准温度、波动幅度、周期（秒）
+const TEMPS: &[(&str, f32, f32, f32)] = &[
+    ("主板", 34.0, 2.0, 300.0),
+    ("CPU", 52.0, 14.0, 90.0),
+    ("辅助", 41.0, 4.0, 200.0),
+    ("辅助 1", 38.0, 3.0, 240.0),
+];
+
+/// 模拟芯片实例
+pub struct SimChip {
+    started: Instant,
+    /// 机箱入侵锁存位，初始为已触发，便于测试清除流程
+    intrusion: AtomicBool,
+}
+
+impl SimChip {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            intrusion: AtomicBool::new(true),
+        }
+    }
+}
+
+impl Default for SimChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chip for SimChip {
+    fn chip_name(&self) -> &str {
+        CHIP_NAME
+    }
+
+    fn read_fans(&self, _io: &dyn SioAccess) -> Result<Vec<FanReading>> {
+        Ok(FANS
+            .iter()
+            .zip(0u8..)
+            .map(|(&(name, base, amplitude, period), channel)| {
+                let rpm = to_rpm(wobble(self.started, base, amplitude, period));
+                FanReading {
+                    name: name.to_string(),
+                    rpm,
+                    channel,
+                    status: FanPresence::from_rpm(rpm),
+                }
+            })
+            .collect())
+    }
+
+    fn read_temps(&self, _io: &dyn SioAccess) -> Result<Vec<TempReading>> {
+        Ok(TEMPS
+            .iter()
+            .zip(0u8..)
+            .map(|(&(name, base, amplitude, period), channel)| TempReading {
+                name: name.to_string(),
+                temp_c: (wobble(self.started, base, amplitude, period) * 10.0).round() / 10.0,
+                channel,
+            })
+            .collect())
+    }
+
+    fn read_intrusion(&self, _io: &dyn SioAccess) -> Result<Option<IntrusionReading>> {
+        Ok(Some(IntrusionReading {
+            name: "CASEOPEN0".to_string(),
+            triggered: self.intrusion.load(Ordering::Relaxed),
+        }))
+    }
+
+    fn clear_intrusion(&self, _io: &dyn SioAccess) -> Result<()> {
+        self.intrusion.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+const fn to_rpm(v: f32) -> u32 {
+    v.max(0.0).round() as u32
+}
@@ -0,0 +1,190 @@
+// Super I/O 寄存器读取的录制与回放
+// RecordingAccess 包装实际的访问方式，记录每次 bank / 逻辑设备寄存器读取及其原始值；
+// ReplayAccess 按录制结果应答同样的读取，让芯片检测与 Nuvoton 解析代码可在没有该芯片的机器上运行
+//
+// 只记录经 SioAccess 的寄存器读取；ITE 芯片与清除入侵锁存位直接读写端口，不在录制范围内，
+// 回放时也没有驱动句柄
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::access::SioAccess;
+use super::driver::DriverHandle;
+use crate::error::{NoCrateError, RecordedError, Result};
+use crate::i18n::{self, Msg};
+use crate::wmi::asio_hw::HwmRegister;
+
+/// 一次寄存器读取
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SioRegister {
+    /// HW Monitor bank 寄存器
+    Hwm { base: u16, bank: u8, reg: u8 },
+    /// 逻辑设备配置寄存器
+    Ldn { config_port: u16, ldn: u8, reg: u8 },
+}
+
+/// 录制的读取与结果，失败时保存错误码
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SioRead {
+    pub register: SioRegister,
+    pub value: std::result::Result<u8, RecordedError>,
+}
+
+/// Super I/O 读取录制，按读取顺序排列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SioTrace {
+    /// 录制时的访问方式（`WinRing0` / ASUS WMI）
+    pub access: String,
+    pub reads: Vec<SioRead>,
+}
+
+/// 记录经 `inner` 的每次寄存器读取
+pub struct RecordingAccess<'a> {
+    inner: &'a dyn SioAccess,
+    reads: Mutex<Vec<SioRead>>,
+}
+
+impl<'a> RecordingAccess<'a> {
+    #[must_use]
+    pub const fn new(inner: &'a dyn SioAccess) -> Self {
+        Self {
+            inner,
+            reads: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 至今录制的读取
+    #[must_use]
+    pub fn into_trace(self) -> SioTrace {
+        SioTrace {
+            access: self.inner.name().to_string(),
+            reads: self.reads.into_inner(),
+        }
+    }
+
+    fn record(&self, register: SioRegister, value: &Result<u8>) {
+        self.reads.lock().push(SioRead {
+            register,
+            value: value.as_ref().map(|&v| v).map_err(RecordedError::from),
+        });
+    }
+}
+
+impl SioAccess for RecordingAccess<'_> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn read_hwm(&self, base: u16, bank: u8, reg: u8) -> Result<u8> {
+        let value = self.inner.read_hwm(base, bank, reg);
+        self.record(SioRegister::Hwm { base, bank, reg }, &value);
+        value
+    }
+
+    /// 保留内部访问方式的批量读取，逐个记录结果
+    fn read_hwm_many(&self, base: u16, registers: &[HwmRegister]) -> Result<Vec<u8>> {
+        let values = self.inner.read_hwm_many(base, registers);
+        match &values {
+            Ok(values) => {
+                for (&(bank, reg), &value) in registers.iter().zip(values) {
+                    self.record(SioRegister::Hwm { base, bank, reg }, &Ok(value));
+                }
+            }
+            Err(e) => {
+                for &(bank, reg) in registers {
+                    self.record(SioRegister::Hwm { base, bank, reg }, &Err(e.clone()));
+                }
+            }
+        }
+        values
+    }
+
+    fn read_ldn(&self, config_port: u16, ldn: u8, reg: u8) -> Result<u8> {
+        let value = self.inner.read_ldn(config_port, ldn, reg);
+        self.record(
+            SioRegister::Ldn {
+                config_port,
+                ldn,
+                reg,
+            },
+            &value,
+        );
+        value
+    }
+
+    /// 直接端口访问不录制，但仍交给内部驱动执行，录制期间芯片照常工作
+    fn driver(&self) -> Option<&DriverHandle> {
+        self.inner.driver()
+    }
+}
+
+/// 按 [`SioTrace`] 应答寄存器读取
+/// 同一寄存器多次读取时依次返回录制的值，用完后重复最后一个；未录制的寄存器返回错误
+pub struct ReplayAccess {
+    reads: Vec<SioRead>,
+    used: Mutex<Vec<bool>>,
+}
+
+impl ReplayAccess {
+    #[must_use]
+    pub fn new(trace: SioTrace) -> Self {
+        let used = Mutex::new(vec![false; trace.reads.len()]);
+        Self {
+            reads: trace.reads,
+            used,
+        }
+    }
+
+    fn answer(&self, register: SioRegister) -> Result<u8> {
+        let matching: Vec<usize> = self
+            .reads
+            .iter()
+            .enumerate()
+            .filter(|(_, read)| read.register == register)
+            .map(|(i, _)| i)
+            .collect();
+        let index = {
+            let mut used = self.used.lock();
+            let index = matching
+                .iter()
+                .copied()
+                .find(|&i| !used[i])
+                .or_else(|| matching.last().copied())
+                .ok_or_else(|| {
+                    NoCrateError::Sio(i18n::tf(
+                        Msg::SioTraceMissing,
+                        &[("register", &format!("{register:?}"))],
+                    ))
+                })?;
+            used[index] = true;
+            index
+        };
+        self.reads[index]
+            .value
+            .clone()
+            .map_err(|e| e.to_error(format!("{register:?}")))
+    }
+}
+
+impl SioAccess for ReplayAccess {
+    fn name(&self) -> &'static str {
+        "Replay"
+    }
+
+    fn read_hwm(&self, base: u16, bank: u8, reg: u8) -> Result<u8> {
+        self.answer(SioRegister::Hwm { base, bank, reg })
+    }
+
+    fn read_ldn(&self, config_port: u16, ldn: u8, reg: u8) -> Result<u8> {
+        self.answer(SioRegister::Ldn {
+            config_port,
+            ldn,
+            reg,
+        })
+    }
+
+    fn driver(&self) -> Option<&DriverHandle> {
+        None
+    }
+}
@@ -0,0 +1,281 @@
+// SMBus 主控制器访问
+// 通过 WinRing0 端口 I/O 操作芯片组 SMBus 控制器，用于访问内存条上的 RGB 控制器等从设备：
+// - Intel：PCH SMBus（i801 兼容，PCI 00:1F.4，BAR4 为 I/O 基址）
+// - AMD：FCH SMBus（PIIX4 兼容，基址由 PM 寄存器 0x00/0x01 给出，通常为 0x0B00）
+// 两者的主机寄存器布局相同，仅检测方式不同
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::sio::driver::DriverHandle;
+
+// ─── 主机寄存器（相对基址） ─────────────────────────────────
+const HST_STS: u16 = 0x00;
+const HST_CNT: u16 = 0x02;
+const HST_CMD: u16 = 0x03;
+const XMIT_SLVA: u16 = 0x04;
+const HST_D0: u16 = 0x05;
+const HST_D1: u16 = 0x06;
+
+// HST_STS 位
+const STS_HOST_BUSY: u8 = 0x01;
+const STS_INTR: u8 = 0x02;
+const STS_DEV_ERR: u8 = 0x04;
+const STS_BUS_ERR: u8 = 0x08;
+const STS_FAILED: u8 = 0x10;
+/// Intel 独有：硬件信号量，读到 0 即获得，写 1 释放
+const STS_INUSE: u8 = 0x40;
+const STS_ERROR_MASK: u8 = STS_DEV_ERR | STS_BUS_ERR | STS_FAILED;
+/// 清除所有状态位（写 1 清零），不含 INUSE
+const STS_CLEAR: u8 = 0x9E;
+
+// HST_CNT 协议（bit 4:2）与启动位
+const CNT_BYTE: u8 = 0x04;
+const CNT_BYTE_DATA: u8 = 0x08;
+const CNT_WORD_DATA: u8 = 0x0C;
+const CNT_START: u8 = 0x40;
+
+/// 单次事务的最长等待时间
+const TRANSACTION_TIMEOUT: Duration = Duration::from_millis(25);
+
+// ─── 检测 ────────────────────────────────────────────────────
+/// Intel PCH SMBus 功能位置
+const INTEL_SMBUS_DEV: u8 = 0x1F;
+const INTEL_SMBUS_FUNC: u8 = 4;
+/// 检测时读取的 Intel SMBus 配置寄存器：ID、类代码、BAR4、HOSTC
+const INTEL_SMBUS_REGS: [u32; 4] = [0x00, 0x08, 0x20, 0x40];
+/// PCI 类代码：Serial Bus / SMBus
+const PCI_CLASS_SMBUS: u32 = 0x0C05;
+/// AMD FCH PM 寄存器索引 / 数据端口
+pub(crate) const AMD_PM_INDEX: u16 = 0xCD6;
+pub(crate) const AMD_PM_DATA: u16 = 0xCD7;
+/// 检测基址时读取的 PM 寄存器
+pub(crate) const AMD_PM_SMBUS_REGS: [u8; 2] = [0x00, 0x01];
+/// AMD FCH SMBus 默认基址
+const AMD_SMBUS_DEFAULT_BASE: u16 = 0x0B00;
+
+/// SMBus 控制器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmbusKind {
+    Intel,
+    Amd,
+}
+
+/// 已检测到的 SMBus 主控制器
+pub struct SmbusController {
+    driver: Arc<DriverHandle>,
+    kind: SmbusKind,
+    base: u16,
+    /// 串行化多步事务
+    lock: Mutex<()>,
+}
+
+impl SmbusController {
+    /// 检测 Intel PCH 或 AMD FCH SMBus 控制器
+    pub fn detect(driver: Arc<DriverHandle>) -> Result<Self> {
+        let (kind, base) = detect_base(&driver).ok_or_else(|| {
+            NoCrateError::DeviceNotPresent(i18n::t(Msg::SmbusNotFound).into())
+        })?;
+
+        eprintln!("[SMBus] {kind:?} 控制器，基址 0x{base:04X}");
+        Ok(Self {
+            driver,
+            kind,
+            base,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// 探测从设备是否应答（Receive Byte）
+    pub fn probe(&self, addr: u8) -> bool {
+        self.transaction(addr, true, 0, CNT_BYTE, None).is_ok()
+    }
+
+    /// Read Byte Data
+    pub fn read_byte_data(&self, addr: u8, command: u8) -> Result<u8> {
+        self.transaction(addr, true, command, CNT_BYTE_DATA, None)
+            .map(|(lo, _)| lo)
+    }
+
+    /// Write Byte Data
+    pub fn write_byte_data(&self, addr: u8, command: u8, value: u8) -> Result<()> {
+        self.transaction(addr, false, command, CNT_BYTE_DATA, Some([value, 0]))
+            .map(|_| ())
+    }
+
+    /// Write Word Data（低字节先发送）
+    pub fn write_word_data(&self, addr: u8, command: u8, value: u16) -> Result<()> {
+        self.transaction(
+            addr,
+            false,
+            command,
+            CNT_WORD_DATA,
+            Some(value.to_le_bytes()),
+        )
+        .map(|_| ())
+    }
+
+    /// 执行一次 SMBus 事务，返回 (D0, D1)
+    fn transaction(
+        &self,
+        addr: u8,
+        read: bool,
+        command: u8,
+        protocol: u8,
+        data: Option<[u8; 2]>,
+    ) -> Result<(u8, u8)> {
+        let _guard = self.lock.lock();
+        self.acquire()?;
+        let result = self.transaction_locked(addr, read, command, protocol, data);
+        self.release();
+        result
+    }
+
+    fn transaction_locked(
+        &self,
+        addr: u8,
+        read: bool,
+        command: u8,
+        protocol: u8,
+        data: Option<[u8; 2]>,
+    ) -> Result<(u8, u8)> {
+        let d = &self.driver;
+        let b = self.base;
+
+        let status = d.read_io_port_byte(b + HST_STS)?;
+        if status & STS_HOST_BUSY != 0 {
+            return Err(NoCrateError::Busy(i18n::t(Msg::SmbusBusy).into()));
+        }
+        d.write_io_port_byte(b + HST_STS, STS_CLEAR)?;
+
+        d.write_io_port_byte(b + XMIT_SLVA, (addr << 1) | u8::from(read))?;
+        d.write_io_port_byte(b + HST_CMD, command)?;
+        if let Some([d0, d1]) = data {
+            d.write_io_port_byte(b + HST_D0, d0)?;
+            d.write_io_port_byte(b + HST_D1, d1)?;
+        }
+        d.write_io_port_byte(b + HST_CNT, protocol | CNT_START)?;
+
+        let start = Instant::now();
+        let status = loop {
+            let status = d.read_io_port_byte(b + HST_STS)?;
+            if status & STS_HOST_BUSY == 0 && status & (STS_INTR | STS_ERROR_MASK) != 0 {
+                break status;
+            }
+            if start.elapsed() > TRANSACTION_TIMEOUT {
+                return Err(NoCrateError::Timeout(i18n::tf(
+                    Msg::SmbusTimeout,
+                    &[("addr", &format!("0x{addr:02X}"))],
+                )));
+            }
+            thread::yield_now();
+        };
+        d.write_io_port_byte(b + HST_STS, STS_CLEAR)?;
+
+        if status & STS_ERROR_MASK != 0 {
+            return Err(NoCrateError::Sio(i18n::tf(
+                Msg::SmbusFailed,
+                &[
+                    ("addr", &format!("0x{addr:02X}")),
+                    ("status", &format!("0x{status:02X}")),
+                ],
+            )));
+        }
+
+        Ok((
+            d.read_io_port_byte(b + HST_D0)?,
+            d.read_io_port_byte(b + HST_D1)?,
+        ))
+    }
+
+    /// Intel：获取 INUSE 硬件信号量，避免与 BIOS / ME 同时访问
+    fn acquire(&self) -> Result<()> {
+        if self.kind != SmbusKind::Intel {
+            return Ok(());
+        }
+        let start = Instant::now();
+        while self.driver.read_io_port_byte(self.base + HST_STS)? & STS_INUSE != 0 {
+            if start.elapsed() > TRANSACTION_TIMEOUT {
+                return Err(NoCrateError::Busy(i18n::t(Msg::SmbusInUse).into()));
+            }
+            thread::yield_now();
+        }
+        Ok(())
+    }
+
+    fn release(&self) {
+        if self.kind == SmbusKind::Intel {
+            let _ = self
+                .driver
+                .write_io_port_byte(self.base + HST_STS, STS_INUSE);
+        }
+    }
+}
+
+/// 检测时是否读取这个 PCI 配置寄存器
+pub(crate) fn reads_pci(bus: u8, device: u8, function: u8, offset: u32) -> bool {
+    match (bus, device, function) {
+        (0, INTEL_SMBUS_DEV, INTEL_SMBUS_FUNC) => INTEL_SMBUS_REGS.contains(&offset),
+        (0, 0x14, 0) => offset == 0x00,
+        _ => false,
+    }
+}
+
+/// 检测控制器类型与主机寄存器基址，不创建控制器
+pub fn detect_base(driver: &DriverHandle) -> Option<(SmbusKind, u16)> {
+    detect_intel(driver)
+        .map(|b| (SmbusKind::Intel, b))
+        .or_else(|| detect_amd(driver).map(|b| (SmbusKind::Amd, b)))
+}
+
+/// Intel PCH：PCI 00:1F.4，BAR4 (0x20) 为 I/O 基址，HOSTC (0x40) bit0 为主机使能
+fn detect_intel(driver: &DriverHandle) -> Option<u16> {
+    let id = driver
+        .read_pci_config(0, INTEL_SMBUS_DEV, INTEL_SMBUS_FUNC, 0x00)
+        .ok()?;
+    if id & 0xFFFF != 0x8086 {
+        return None;
+    }
+    let class = driver
+        .read_pci_config(0, INTEL_SMBUS_DEV, INTEL_SMBUS_FUNC, 0x08)
+        .ok()?;
+    if class >> 16 != PCI_CLASS_SMBUS {
+        return None;
+    }
+    let hostc = driver
+        .read_pci_config(0, INTEL_SMBUS_DEV, INTEL_SMBUS_FUNC, 0x40)
+        .ok()?;
+    if hostc & 0x01 == 0 {
+        eprintln!("[SMBus] Intel SMBus 主机未使能");
+        return None;
+    }
+    let bar = driver
+        .read_pci_config(0, INTEL_SMBUS_DEV, INTEL_SMBUS_FUNC, 0x20)
+        .ok()?;
+    u16::try_from(bar & 0xFFFC).ok().filter(|&b| b != 0)
+}
+
+/// AMD FCH：PCI 00:14.0 为 AMD SMBus，基址来自 PM 寄存器 0x00（bit4 使能）/ 0x01（高字节）
+fn detect_amd(driver: &DriverHandle) -> Option<u16> {
+    let id = driver.read_pci_config(0, 0x14, 0, 0x00).ok()?;
+    if id & 0xFFFF != 0x1022 {
+        return None;
+    }
+    let pm_read = |reg: u8| -> Option<u8> {
+        driver.write_io_port_byte(AMD_PM_INDEX, reg).ok()?;
+        driver.read_io_port_byte(AMD_PM_DATA).ok()
+    };
+    let [lo_reg, hi_reg] = AMD_PM_SMBUS_REGS;
+    let lo = pm_read(lo_reg)?;
+    let hi = pm_read(hi_reg)?;
+    if lo & 0x10 != 0 && hi != 0 {
+        Some(u16::from(hi) << 8)
+    } else {
+        Some(AMD_SMBUS_DEFAULT_BASE)
+    }
+}
@@ -0,0 +1,338 @@
+// 存储设备温度传感器模块
+// NVMe：IOCTL_STORAGE_QUERY_PROPERTY 读取 SMART / Health Information 日志页 (0x02)
+// SATA：SMART_RCV_DRIVE_DATA 读取 SMART 属性 194 (0xC2)
+// 不依赖 WinRing0 驱动，使用系统自带的存储栈 IOCTL
+
+use serde::Serialize;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, GENERIC_READ, GENERIC_WRITE, HANDLE};
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_ATTRIBUTE_NORMAL, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+
+/// `IOCTL_STORAGE_QUERY_PROPERTY`: `CTL_CODE(0x2D, 0x500, 0, FILE_ANY_ACCESS)`
+const IOCTL_STORAGE_QUERY_PROPERTY: u32 = 0x002D_1400;
+/// `SMART_RCV_DRIVE_DATA`: `CTL_CODE(0x7, 0x22, 0, FILE_READ_ACCESS | FILE_WRITE_ACCESS)`
+const SMART_RCV_DRIVE_DATA: u32 = 0x0007_C088;
+
+/// `StorageDeviceProperty`
+const PROPERTY_DEVICE: u32 = 0;
+/// `StorageDeviceProtocolSpecificProperty`
+const PROPERTY_PROTOCOL_SPECIFIC: u32 = 50;
+/// `PropertyStandardQuery`
+const QUERY_STANDARD: u32 = 0;
+
+/// `ProtocolTypeNvme`
+const PROTOCOL_TYPE_NVME: u32 = 3;
+/// `NVMeDataTypeLogPage`
+const NVME_DATA_TYPE_LOG_PAGE: u32 = 2;
+/// `NVMe` SMART / Health Information 日志页
+const NVME_LOG_PAGE_HEALTH_INFO: u32 = 0x02;
+/// `NVMe` 日志页长度
+const NVME_LOG_PAGE_SIZE: usize = 512;
+
+/// `STORAGE_PROTOCOL_SPECIFIC_DATA` 结构大小
+const PROTOCOL_SPECIFIC_DATA_SIZE: usize = 40;
+/// `STORAGE_PROPERTY_QUERY.AdditionalParameters` 偏移
+const QUERY_ADDITIONAL_PARAMS_OFFSET: usize = 8;
+
+/// `BusTypeAta` / `BusTypeSata` / `BusTypeNvme`
+const BUS_TYPE_ATA: u32 = 0x03;
+const BUS_TYPE_SATA: u32 = 0x0B;
+const BUS_TYPE_NVME: u32 = 0x11;
+
+/// ATA SMART 命令 / 子命令
+const ATA_SMART_CMD: u8 = 0xB0;
+const ATA_SMART_READ_ATTRIBUTES: u8 = 0xD0;
+/// SMART 属性：温度（摄氏度）
+const SMART_ATTR_TEMPERATURE: u8 = 0xC2;
+/// SMART 属性：气流温度（部分 SSD 只提供该项）
+const SMART_ATTR_AIRFLOW_TEMPERATURE: u8 = 0xBE;
+/// `SENDCMDINPARAMS` 大小（含 1 字节 bBuffer）
+const SENDCMDINPARAMS_SIZE: usize = 33;
+/// `SENDCMDOUTPARAMS` 头部大小（cBufferSize + DRIVERSTATUS）
+const SENDCMDOUTPARAMS_HEADER: usize = 16;
+/// SMART 数据块大小
+const SMART_DATA_SIZE: usize = 512;
+
+/// 最多枚举的物理磁盘数量
+const MAX_PHYSICAL_DRIVES: u32 = 16;
+
+/// 驱动器总线类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBus {
+    Nvme,
+    Sata,
+}
+
+/// 单个驱动器温度读数
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageTempReading {
+    /// 物理磁盘编号（`\\.\PhysicalDriveN`）
+    pub index: u32,
+    /// 设备型号
+    pub model: String,
+    /// 总线类型
+    pub bus: StorageBus,
+    /// 温度（摄氏度）
+    pub temp_c: f32,
+}
+
+/// 已识别且支持温度读取的驱动器
+#[derive(Debug, Clone)]
+struct StorageDrive {
+    index: u32,
+    model: String,
+    bus: StorageBus,
+}
+
+/// 存储设备温度监控器
+pub struct StorageMonitor {
+    drives: Vec<StorageDrive>,
+}
+
+impl StorageMonitor {
+    /// 枚举物理磁盘，保留能够读取温度的 `NVMe` / SATA 设备
+    pub fn init() -> Result<Self> {
+        let drives: Vec<StorageDrive> = (0..MAX_PHYSICAL_DRIVES)
+            .filter_map(probe_drive)
+            .collect();
+
+        if drives.is_empty() {
+            return Err(NoCrateError::Storage(i18n::t(Msg::StorageNone).into()));
+        }
+
+        for d in &drives {
+            eprintln!("Storage: PhysicalDrive{} {} ({:?})", d.index, d.model, d.bus);
+        }
+
+        Ok(Self { drives })
+    }
+
+    /// 读取所有驱动器温度，单个设备失败时跳过
+    pub fn read_all(&self) -> Vec<StorageTempReading> {
+        self.drives
+            .iter()
+            .filter_map(|d| match read_drive_temp(d) {
+                Ok(temp_c) => Some(StorageTempReading {
+                    index: d.index,
+                    model: d.model.clone(),
+                    bus: d.bus,
+                    temp_c,
+                }),
+                Err(e) => {
+                    eprintln!("[Storage] 读取 PhysicalDrive{} 温度失败: {e}", d.index);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// 识别单个物理磁盘的型号与总线类型，并确认温度可读
+fn probe_drive(index: u32) -> Option<StorageDrive> {
+    let handle = DriveHandle::open(index).ok()?;
+    let (model, bus_type) = query_device_descriptor(&handle).ok()?;
+
+    let bus = match bus_type {
+        BUS_TYPE_NVME => StorageBus::Nvme,
+        BUS_TYPE_ATA | BUS_TYPE_SATA => StorageBus::Sata,
+        _ => return None,
+    };
+
+    let drive = StorageDrive { index, model, bus };
+    read_temp_with_handle(&handle, &drive).ok().map(|_| drive)
+}
+
+fn read_drive_temp(drive: &StorageDrive) -> Result<f32> {
+    let handle = DriveHandle::open(drive.index)?;
+    read_temp_with_handle(&handle, drive)
+}
+
+fn read_temp_with_handle(handle: &DriveHandle, drive: &StorageDrive) -> Result<f32> {
+    match drive.bus {
+        StorageBus::Nvme => read_nvme_temp(handle),
+        StorageBus::Sata => read_smart_temp(handle, drive.index),
+    }
+}
+
+/// 查询 `STORAGE_DEVICE_DESCRIPTOR`，返回 (型号, 总线类型)
+fn query_device_descriptor(handle: &DriveHandle) -> Result<(String, u32)> {
+    let mut query = [0u8; 12];
+    put_u32(&mut query, 0, PROPERTY_DEVICE);
+    put_u32(&mut query, 4, QUERY_STANDARD);
+
+    let mut out = vec![0u8; 1024];
+    let len = handle.ioctl(IOCTL_STORAGE_QUERY_PROPERTY, &mut query, &mut out)?;
+    if len < 32 {
+        return Err(NoCrateError::Storage(
+            i18n::t(Msg::StorageDescriptorShort).into(),
+        ));
+    }
+
+    // STORAGE_DEVICE_DESCRIPTOR: +16 ProductIdOffset, +28 BusType
+    let product_offset = get_u32(&out, 16) as usize;
+    let bus_type = get_u32(&out, 28);
+
+    let model = if product_offset != 0 && product_offset < len {
+        let bytes = &out[product_offset..len];
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+    } else {
+        String::new()
+    };
+
+    Ok((model, bus_type))
+}
+
+/// 读取 `NVMe` Health Information 日志页中的复合温度（开尔文）
+fn read_nvme_temp(handle: &DriveHandle) -> Result<f32> {
+    let size = QUERY_ADDITIONAL_PARAMS_OFFSET + PROTOCOL_SPECIFIC_DATA_SIZE + NVME_LOG_PAGE_SIZE;
+    let mut buf = vec![0u8; size];
+
+    // STORAGE_PROPERTY_QUERY
+    put_u32(&mut buf, 0, PROPERTY_PROTOCOL_SPECIFIC);
+    put_u32(&mut buf, 4, QUERY_STANDARD);
+
+    // STORAGE_PROTOCOL_SPECIFIC_DATA（位于 AdditionalParameters）
+    let p = QUERY_ADDITIONAL_PARAMS_OFFSET;
+    put_u32(&mut buf, p, PROTOCOL_TYPE_NVME);
+    put_u32(&mut buf, p + 4, NVME_DATA_TYPE_LOG_PAGE);
+    put_u32(&mut buf, p + 8, NVME_LOG_PAGE_HEALTH_INFO);
+    put_u32(&mut buf, p + 16, PROTOCOL_SPECIFIC_DATA_SIZE as u32);
+    put_u32(&mut buf, p + 20, NVME_LOG_PAGE_SIZE as u32);
+
+    let mut out = vec![0u8; size];
+    let _ = handle.ioctl(IOCTL_STORAGE_QUERY_PROPERTY, &mut buf, &mut out)?;
+
+    // STORAGE_PROTOCOL_DATA_DESCRIPTOR: Version, Size, 然后是 ProtocolSpecificData
+    let data_offset = p + get_u32(&out, p + 16) as usize;
+    if data_offset + 3 > out.len() {
+        return Err(NoCrateError::Storage(
+            i18n::t(Msg::StorageNvmeOffset).into(),
+        ));
+    }
+
+    // 日志页 byte 1-2：Composite Temperature（开尔文）
+    let kelvin = u16::from_le_bytes([out[data_offset + 1], out[data_offset + 2]]);
+    if kelvin == 0 {
+        return Err(NoCrateError::Storage(
+            i18n::t(Msg::StorageNvmeNoTemp).into(),
+        ));
+    }
+    Ok(f32::from(kelvin) - 273.15)
+}
+
+/// 通过 ATA SMART READ DATA 读取温度属性
+fn read_smart_temp(handle: &DriveHandle, index: u32) -> Result<f32> {
+    // SENDCMDINPARAMS
+    let mut input = [0u8; SENDCMDINPARAMS_SIZE];
+    put_u32(&mut input, 0, SMART_DATA_SIZE as u32); // cBufferSize
+    // IDEREGS (偏移 4)：Features, SectorCount, SectorNumber, CylLow, CylHigh, DriveHead, Command
+    input[4] = ATA_SMART_READ_ATTRIBUTES;
+    input[5] = 1;
+    input[6] = 1;
+    input[7] = 0x4F;
+    input[8] = 0xC2;
+    input[9] = 0xA0 | (((index & 1) as u8) << 4);
+    input[10] = ATA_SMART_CMD;
+    input[12] = (index & 0xFF) as u8; // bDriveNumber
+
+    let mut out = vec![0u8; SENDCMDOUTPARAMS_HEADER + SMART_DATA_SIZE];
+    let _ = handle.ioctl(SMART_RCV_DRIVE_DATA, &mut input, &mut out)?;
+
+    // SMART 数据：偏移 2 起为 30 个 12 字节属性条目
+    let data = &out[SENDCMDOUTPARAMS_HEADER..];
+    let attr_raw = |id: u8| {
+        data[2..2 + 30 * 12]
+            .chunks_exact(12)
+            .find(|a| a[0] == id)
+            .map(|a| a[5])
+    };
+
+    let temp = attr_raw(SMART_ATTR_TEMPERATURE)
+        .or_else(|| attr_raw(SMART_ATTR_AIRFLOW_TEMPERATURE))
+        .filter(|&t| t > 0 && t < 128)
+        .ok_or_else(|| NoCrateError::Storage(i18n::t(Msg::StorageSmartNoTemp).into()))?;
+
+    Ok(f32::from(temp))
+}
+
+fn put_u32(buf: &mut [u8], offset: usize, value: u32) {
+    buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn get_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([buf[offset], buf[offset + 1], buf[offset + 2], buf[offset + 3]])
+}
+
+/// 物理磁盘句柄，Drop 时自动关闭
+struct DriveHandle(HANDLE);
+
+#[allow(unsafe_code)]
+impl DriveHandle {
+    fn open(index: u32) -> Result<Self> {
+        let path = to_wide(&format!(r"\\.\PhysicalDrive{index}"));
+        unsafe {
+            let handle = CreateFileW(
+                PCWSTR(path.as_ptr()),
+                GENERIC_READ.0 | GENERIC_WRITE.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_ATTRIBUTE_NORMAL,
+                None,
+            )
+            .map_err(|e| {
+                NoCrateError::Storage(i18n::tf(
+                    Msg::StorageOpen,
+                    &[("index", &index), ("error", &e)],
+                ))
+            })?;
+            Ok(Self(handle))
+        }
+    }
+
+    /// 发送 IOCTL，返回输出字节数
+    fn ioctl(&self, code: u32, input: &mut [u8], output: &mut [u8]) -> Result<usize> {
+        let mut bytes_returned: u32 = 0;
+        unsafe {
+            DeviceIoControl(
+                self.0,
+                code,
+                Some(input.as_mut_ptr().cast()),
+                input.len() as u32,
+                Some(output.as_mut_ptr().cast()),
+                output.len() as u32,
+                Some(&mut bytes_returned),
+                None,
+            )
+            .map_err(|e| {
+                NoCrateError::Storage(i18n::tf(
+                    Msg::StorageIoctl,
+                    &[("code", &format!("0x{code:08X}")), ("error", &e)],
+                ))
+            })?;
+        }
+        Ok(bytes_returned as usize)
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for DriveHandle {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseHandle(self.0);
+        }
+    }
+}
+
+/// 将 &str 转换为以 null 结尾的宽字符串
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
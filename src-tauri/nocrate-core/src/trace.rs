@@ -0,0 +1,151 @@
+// 传感器读取录制与回放
+// 录制时经 RecordingWmi / RecordingAccess 执行一遍固定的探测（风扇、温度、风扇策略、功耗限制、
+// Super I/O 芯片检测与读数），保存原始应答与当时的解析结果；回放时经 ReplayWmi / ReplayAccess
+// 执行同样的探测，与录制时的结果比对，用于在没有该主板 / 芯片的机器上做回归检查
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "sio")]
+use crate::error::RecordedError;
+#[cfg(feature = "sio")]
+use crate::sio::{
+    access::SioAccess,
+    chips::{FanReading, TempReading},
+    detect,
+    trace::{RecordingAccess, ReplayAccess, SioTrace},
+};
+use crate::wmi::asus_mgmt::{self, AsusHWSensor, DesktopFanPolicy, FanInfo, ThermalProfile};
+use crate::wmi::connection::AsusWmi;
+use crate::wmi::ppt::{self, PowerLimits};
+use crate::wmi::trace::{RecordingWmi, ReplayWmi, WmiTrace};
+
+/// 经 ASUS WMI 探测的解析结果
+#[derive(Debug, Clone, Serialize)]
+pub struct WmiProbe {
+    pub backend: String,
+    pub thermal_profile: Option<ThermalProfile>,
+    pub fans: Vec<FanInfo>,
+    pub desktop_fan_policies: Vec<DesktopFanPolicy>,
+    pub asushw_sensors: Vec<AsusHWSensor>,
+    pub power_limits: Option<PowerLimits>,
+}
+
+/// Super I/O 芯片检测与读数
+#[cfg(feature = "sio")]
+#[derive(Debug, Clone, Serialize)]
+pub struct SioProbe {
+    pub chip_name: String,
+    pub fans: Vec<FanReading>,
+    pub temps: Vec<TempReading>,
+}
+
+/// 一次探测的全部解析结果，失败的部分保存错误码
+/// 未录制 Super I/O 时不输出 `sio`，同一录制在是否启用 `sio` 特性的构建下比对结果相同
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TraceReport {
+    pub wmi: Option<WmiProbe>,
+    #[cfg(feature = "sio")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sio: Option<std::result::Result<SioProbe, RecordedError>>,
+}
+
+/// 录制文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorTrace {
+    /// 录制时的主板型号
+    pub board: Option<String>,
+    /// 手工编写而非实机录制（测试用的示例）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub synthetic: bool,
+    pub wmi: Option<WmiTrace>,
+    #[cfg(feature = "sio")]
+    pub sio: Option<SioTrace>,
+    /// 录制时的解析结果（[`TraceReport`] 序列化后的 JSON）
+    pub expected: serde_json::Value,
+}
+
+/// 回放结果
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceReplay {
+    pub report: TraceReport,
+    /// 解析结果与录制时一致
+    pub matches_recording: bool,
+}
+
+/// 读取 WMI 探测涉及的全部数据
+pub fn probe_wmi(conn: &impl AsusWmi) -> WmiProbe {
+    WmiProbe {
+        backend: conn.backend().backend_type().to_string(),
+        thermal_profile: asus_mgmt::get_thermal_profile(conn).ok(),
+        fans: asus_mgmt::get_all_fan_speeds(conn),
+        desktop_fan_policies: asus_mgmt::get_all_desktop_fan_policies(conn),
+        asushw_sensors: asus_mgmt::get_asushw_sensors(conn),
+        power_limits: ppt::get_power_limits(conn).ok(),
+    }
+}
+
+/// 检测 Super I/O 芯片并读取一次风扇与温度
+#[cfg(feature = "sio")]
+pub fn probe_sio(io: &dyn SioAccess) -> crate::error::Result<SioProbe> {
+    let chip = detect::detect_chip(io)?;
+    Ok(SioProbe {
+        chip_name: chip.chip_name().to_string(),
+        fans: chip.read_fans(io)?,
+        temps: chip.read_temps(io)?,
+    })
+}
+
+/// 在 `conn` 上执行 WMI 探测并录制
+pub fn record_wmi(conn: &impl AsusWmi) -> (WmiTrace, WmiProbe) {
+    let recorder = RecordingWmi::new(conn);
+    let probe = probe_wmi(&recorder);
+    (recorder.into_trace(), probe)
+}
+
+/// 经 `io` 执行 Super I/O 探测并录制
+#[cfg(feature = "sio")]
+pub fn record_sio(io: &dyn SioAccess) -> (SioTrace, std::result::Result<SioProbe, RecordedError>) {
+    let recorder = RecordingAccess::new(io);
+    let probe = probe_sio(&recorder).map_err(|e| RecordedError::from(&e));
+    (recorder.into_trace(), probe)
+}
+
+impl SensorTrace {
+    /// 由各部分的录制组成录制文件，`report` 为录制时的解析结果
+    #[must_use]
+    pub fn new(
+        board: Option<String>,
+        wmi: Option<WmiTrace>,
+        #[cfg(feature = "sio")] sio: Option<SioTrace>,
+        report: &TraceReport,
+    ) -> Self {
+        Self {
+            board,
+            synthetic: false,
+            wmi,
+            #[cfg(feature = "sio")]
+            sio,
+            expected: serde_json::to_value(report).unwrap_or_default(),
+        }
+    }
+
+    /// 以录制的应答重新执行探测，并与录制时的解析结果比对
+    #[must_use]
+    pub fn replay(&self) -> TraceReplay {
+        let report = TraceReport {
+            wmi: self
+                .wmi
+                .clone()
+                .map(|trace| probe_wmi(&ReplayWmi::new(trace))),
+            #[cfg(feature = "sio")]
+            sio: self.sio.clone().map(|trace| {
+                probe_sio(&ReplayAccess::new(trace)).map_err(|e| RecordedError::from(&e))
+            }),
+        };
+        let matches_recording = serde_json::to_value(&report).is_ok_and(|v| v == self.expected);
+        TraceReplay {
+            report,
+            matches_recording,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+/// ASUS `asio_hw_fun*` hardware access through the desktop WMI backend.
+///
+/// The `ASUSManagement` class exposes the ASUS I/O driver's register
+/// access as WMI methods, which lets the Super I/O hardware monitor be
+/// read without loading a kernel driver of our own:
+///
+/// - `asio_hw_fun07(wPort)` — read one I/O port byte
+/// - `asio_hw_fun19(LDN, Index)` — read a Super I/O logical device register
+/// - `asio_hw_fun21(Bank, Index)` — read one HW-monitor register
+/// - `asio_hw_fun23(BankIndexArray)` — read many HW-monitor registers in
+///   one call; the request is a comma-separated list of hex `bank,index`
+///   pairs (`"00,4F,04,C0"`) and the reply a string of hex bytes in
+///   `DataArray`
+///
+/// Every WMI call costs about a millisecond, so reading a whole bank one
+/// register at a time is slow. [`read_hwm_registers`] batches through
+/// fun23 and falls back to fun21 when the firmware lacks it or returns a
+/// reply that cannot be parsed.
+use crate::error::{NoCrateError, Result};
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend, WmiParam};
+
+/// Registers requested per fun23 call, keeping the request string short.
+const MAX_BATCH: usize = 32;
+
+/// A HW-monitor register: bank and index within the bank.
+pub type HwmRegister = (u8, u8);
+
+fn instance_path(conn: &impl AsusWmi) -> Result<&str> {
+    match conn.backend() {
+        AsusWmiBackend::Desktop { instance_path } => Ok(instance_path),
+        _ => Err(NoCrateError::Wmi(
+            "asio_hw_fun* is only available on desktop backends".into(),
+        )),
+    }
+}
+
+fn to_byte(value: u32, what: &str) -> Result<u8> {
+    u8::try_from(value)
+        .map_err(|_| NoCrateError::Wmi(format!("{what} returned 0x{value:X}, not a byte")))
+}
+
+/// Read one I/O port byte (`asio_hw_fun07`).
+pub fn read_port(conn: &impl AsusWmi, port: u16) -> Result<u8> {
+    let out = conn.exec_method_v2(
+        instance_path(conn)?,
+        "asio_hw_fun07",
+        &[("wPort", WmiParam::U32(port.into()))],
+    )?;
+    to_byte(out.get_u32("bData")?, "asio_hw_fun07")
+}
+
+/// Read a register of a Super I/O logical device (`asio_hw_fun19`).
+pub fn read_ldn_register(conn: &impl AsusWmi, ldn: u8, index: u8) -> Result<u8> {
+    let out = conn.exec_method_v2(
+        instance_path(conn)?,
+        "asio_hw_fun19",
+        &[("LDN", WmiParam::U8(ldn)), ("Index", WmiParam::U8(index))],
+    )?;
+    to_byte(out.get_u32("Data")?, "asio_hw_fun19")
+}
+
+/// Read one HW-monitor register (`asio_hw_fun21`).
+pub fn read_hwm_register(conn: &impl AsusWmi, (bank, index): HwmRegister) -> Result<u8> {
+    let out = conn.exec_method_v2(
+        instance_path(conn)?,
+        "asio_hw_fun21",
+        &[("Bank", WmiParam::U8(bank)), ("Index", WmiParam::U8(index))],
+    )?;
+    to_byte(out.get_u32("Data")?, "asio_hw_fun21")
+}
+
+/// Read up to [`MAX_BATCH`] HW-monitor registers in one `asio_hw_fun23`
+/// call.
+pub fn read_hwm_batch(conn: &impl AsusWmi, registers: &[HwmRegister]) -> Result<Vec<u8>> {
+    let request = bank_index_array(registers);
+    let out = conn.exec_method_v2(
+        instance_path(conn)?,
+        "asio_hw_fun23",
+        &[("BankIndexArray", WmiParam::Str(&request))],
+    )?;
+    parse_data_array(&out.get_string("DataArray")?, registers.len())
+}
+
+/// Read any number of HW-monitor registers, in order.
+///
+/// Batches through fun23; if a batch fails, that batch and every later
+/// one are read register by register through fun21 instead.
+pub fn read_hwm_registers(conn: &impl AsusWmi, registers: &[HwmRegister]) -> Result<Vec<u8>> {
+    let mut values = Vec::with_capacity(registers.len());
+    let mut batched = true;
+    for chunk in registers.chunks(MAX_BATCH) {
+        if batched {
+            match read_hwm_batch(conn, chunk) {
+                Ok(data) => {
+                    values.extend(data);
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("asio_hw: fun23 batch read failed, using fun21: {e}");
+                    batched = false;
+                }
+            }
+        }
+        for &register in chunk {
+            values.push(read_hwm_register(conn, register)?);
+        }
+    }
+    Ok(values)
+}
+
+/// Read all 256 registers of a HW-monitor bank.
+pub fn read_hwm_bank(conn: &impl AsusWmi, bank: u8) -> Result<Vec<u8>> {
+    let registers: Vec<HwmRegister> = (0..=u8::MAX).map(|index| (bank, index)).collect();
+    read_hwm_registers(conn, &registers)
+}
+
+/// fun23 request string: `"BB,II,BB,II,..."` in hex.
+fn bank_index_array(registers: &[HwmRegister]) -> String {
+    registers
+        .iter()
+        .map(|(bank, index)| format!("{bank:02X},{index:02X}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parse a fun23 `DataArray` reply for `expected` registers.
+///
+/// The reply is a list of hex bytes separated by commas, semicolons or
+/// whitespace, optionally `0x`-prefixed. Some firmware echoes each
+/// request as `bank,index,data`; a reply of exactly three times the
+/// expected length is read that way.
+pub fn parse_data_array(reply: &str, expected: usize) -> Result<Vec<u8>> {
+    let bytes = reply
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            let digits = token
+                .strip_prefix("0x")
+                .or_else(|| token.strip_prefix("0X"))
+                .unwrap_or(token);
+            u8::from_str_radix(digits, 16).map_err(|_| {
+                NoCrateError::Wmi(format!("asio_hw_fun23: bad byte {token:?} in {reply:?}"))
+            })
+        })
+        .collect::<Result<Vec<u8>>>()?;
+
+    if bytes.len() == expected {
+        Ok(bytes)
+    } else if bytes.len() == expected * 3 {
+        Ok(bytes.chunks_exact(3).map(|triple| triple[2]).collect())
+    } else {
+        Err(NoCrateError::Wmi(format!(
+            "asio_hw_fun23: expected {expected} bytes, got {} in {reply:?}",
+            bytes.len()
+        )))
+    }
+}
@@ -0,0 +1,1892 @@
+/// ASUS WMI interface wrapper.
+///
+/// Provides typed access to the ASUS motherboard management interface
+/// exposed via WMI. Supports two backends:
+///
+/// - **Laptop** (`ASUSATKWMI_WMNB`): Methods `DSTS` / `DEVS`
+/// - **Desktop** (`ASUSManagement`): Methods `device_status` / `device_ctrl`
+///
+/// The low-level `dsts` / `devs` calls are routed through
+/// [`AsusWmi::dsts`] / [`AsusWmi::devs`] which handle the
+/// backend-specific method names and parameter mapping. Every helper takes
+/// `&impl AsusWmi`, so it runs against a [`SimWmi`](crate::wmi::sim::SimWmi)
+/// as well as the real [`WmiConnection`](crate::wmi::connection::WmiConnection).
+///
+/// Device IDs sourced from the Linux kernel `asus-wmi` driver
+/// (`include/linux/platform_data/x86/asus-wmi.h`) and the Armoury Crate
+/// / ASUS WMI desktop driver.
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::wmi::atkacpi::AtkAcpi;
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend, WmiParam};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// ASUS WMI Device IDs — shared between laptop and desktop backends.
+///
+/// Reference: Linux kernel `include/linux/platform_data/x86/asus-wmi.h`
+///            and g-helper `AsusACPI.cs`.
+pub mod device_id {
+    /// CPU fan tachometer — RPM (read-only).
+    pub const CPU_FAN_SPEED: u32 = 0x0011_0013;
+
+    /// GPU / chassis-fan-1 tachometer — RPM (read-only).
+    pub const GPU_FAN_SPEED: u32 = 0x0011_0014;
+
+    /// Middle / chassis-fan-2 tachometer — RPM (read-only).
+    pub const MID_FAN_SPEED: u32 = 0x0011_0031;
+
+    /// Throttle thermal policy — the overall "profile"
+    /// (Standard 0 / Performance 1 / Silent 2).
+    pub const THROTTLE_THERMAL_POLICY: u32 = 0x0012_0075;
+
+    /// CPU fan curve (laptop only, 16-byte buffer per thermal profile).
+    pub const CPU_FAN_CURVE: u32 = 0x0011_0024;
+
+    /// GPU fan curve (laptop only).
+    pub const GPU_FAN_CURVE: u32 = 0x0011_0025;
+
+    /// Middle fan curve (laptop only).
+    pub const MID_FAN_CURVE: u32 = 0x0011_0032;
+
+    /// Battery charge threshold in percent (laptop only, write-only).
+    pub const BATTERY_CHARGE_LIMIT: u32 = 0x0012_0057;
+
+    /// GPU MUX switch (laptop only): 0 = dGPU drives the panel,
+    /// 1 = hybrid (iGPU drives the panel). Applies after a reboot.
+    pub const GPU_MUX: u32 = 0x0009_0016;
+
+    /// dGPU disable / "eco" mode (laptop only): 1 = dGPU powered off.
+    pub const DGPU_DISABLE: u32 = 0x0009_0020;
+}
+
+/// DSTS status bit set when the queried device exists.
+pub const DSTS_PRESENCE_BIT: u32 = 0x0001_0000;
+
+// ---------------------------------------------------------------------------
+// Low-level WMI helpers
+// ---------------------------------------------------------------------------
+
+/// Read a device status value — routed through the detected backend.
+///
+/// Returns the raw status u32.
+pub fn dsts(conn: &impl AsusWmi, device_id: u32) -> Result<u32> {
+    conn.dsts(device_id)
+}
+
+/// Write a device control value — routed through the detected backend.
+///
+/// Returns the raw result status.
+pub fn devs(conn: &impl AsusWmi, device_id: u32, control: u32) -> Result<u32> {
+    conn.devs(device_id, control)
+}
+
+// ---------------------------------------------------------------------------
+// Typed enums
+// ---------------------------------------------------------------------------
+
+/// Identifies which fan header to query or control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FanTarget {
+    /// CPU fan header.
+    Cpu,
+    /// GPU / first chassis fan header.
+    Gpu,
+    /// Middle / second chassis fan header.
+    Mid,
+}
+
+impl FanTarget {
+    /// All known fan targets, handy for iteration.
+    pub const ALL: [Self; 3] = [Self::Cpu, Self::Gpu, Self::Mid];
+
+    /// Name as serialized for the frontend.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Gpu => "gpu",
+            Self::Mid => "mid",
+        }
+    }
+
+    /// DSTS device ID for reading this fan's RPM.
+    #[must_use]
+    pub const fn speed_device_id(self) -> u32 {
+        match self {
+            Self::Cpu => device_id::CPU_FAN_SPEED,
+            Self::Gpu => device_id::GPU_FAN_SPEED,
+            Self::Mid => device_id::MID_FAN_SPEED,
+        }
+    }
+}
+
+/// ASUS thermal-profile presets.
+///
+/// These correspond to the three profiles available in ASUS BIOS and
+/// Armoury Crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThermalProfile {
+    /// Default balanced mode.
+    Standard,
+    /// Maximum performance / fan boost.
+    Performance,
+    /// Quieter fans at the cost of thermals.
+    Silent,
+}
+
+impl ThermalProfile {
+    /// All profiles, in Fn+F5 cycle order.
+    pub const ALL: [Self; 3] = [Self::Standard, Self::Performance, Self::Silent];
+
+    /// Name as serialized for the config and frontend.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Performance => "performance",
+            Self::Silent => "silent",
+        }
+    }
+
+    /// Convert to the raw DEVS control value.
+    #[must_use]
+    pub const fn to_raw(self) -> u32 {
+        match self {
+            Self::Standard => 0,
+            Self::Performance => 1,
+            Self::Silent => 2,
+        }
+    }
+
+    /// Parse from a raw DSTS status value.
+    #[must_use]
+    pub fn from_raw(value: u32) -> Option<Self> {
+        match value & 0xFF {
+            0 => Some(Self::Standard),
+            1 => Some(Self::Performance),
+            2 => Some(Self::Silent),
+            _ => None,
+        }
+    }
+
+    /// Next profile in the Fn+F5 cycle: Standard → Performance → Silent.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Standard => Self::Performance,
+            Self::Performance => Self::Silent,
+            Self::Silent => Self::Standard,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fan speed
+// ---------------------------------------------------------------------------
+
+/// Read the current fan speed in RPM for the given target.
+///
+/// Returns `0` if the fan header is unconnected or unsupported.
+pub fn get_fan_speed(conn: &impl AsusWmi, target: FanTarget) -> Result<u32> {
+    let raw = dsts(conn, target.speed_device_id())?;
+    // Lower 16 bits hold the RPM; upper bits may carry status flags.
+    Ok(raw & 0xFFFF)
+}
+
+/// Whether a fan reading of 0 RPM means a stopped fan or an empty header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FanPresence {
+    /// Reporting a non-zero speed.
+    Spinning,
+    /// Reading 0 RPM, but the header is known to have a fan (it spun
+    /// earlier, or the firmware reports it as present). Semi-passive
+    /// modes or a stall.
+    Stopped,
+    /// Reading 0 RPM and never seen spinning: most likely nothing is
+    /// connected.
+    Absent,
+}
+
+impl FanPresence {
+    /// Status from the speed alone, before any presence knowledge:
+    /// `Spinning`, or `Absent` at 0 RPM.
+    #[must_use]
+    pub const fn from_rpm(rpm: u32) -> Self {
+        if rpm > 0 {
+            Self::Spinning
+        } else {
+            Self::Absent
+        }
+    }
+
+    /// `Absent` becomes `Stopped` when the header is known to be populated.
+    #[must_use]
+    pub const fn with_known_present(self, present: bool) -> Self {
+        match self {
+            Self::Absent if present => Self::Stopped,
+            other => other,
+        }
+    }
+}
+
+/// Snapshot of a single fan header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanInfo {
+    pub target: FanTarget,
+    pub rpm: u32,
+    pub status: FanPresence,
+}
+
+/// Presence flag in the upper bits of a fan speed DSTS result.
+const FAN_PRESENT_BIT: u32 = 0x0001_0000;
+
+/// Bitmask of the [`FanTarget`]s seen at a non-zero speed since startup.
+static FANS_SEEN_SPINNING: AtomicU8 = AtomicU8::new(0);
+
+/// Read speeds for all known fan headers.
+///
+/// Headers that fail to respond (e.g. not present on a given board) are
+/// silently skipped. A header at 0 RPM is reported `Stopped` rather than
+/// `Absent` once it has been seen spinning or if the firmware sets its
+/// presence flag.
+pub fn get_all_fan_speeds(conn: &impl AsusWmi) -> Vec<FanInfo> {
+    FanTarget::ALL
+        .iter()
+        .zip(0u8..)
+        .filter_map(|(&target, index)| {
+            let raw = dsts(conn, target.speed_device_id()).ok()?;
+            let rpm = raw & 0xFFFF;
+            let bit = 1 << index;
+            let seen = if rpm > 0 {
+                FANS_SEEN_SPINNING.fetch_or(bit, Ordering::Relaxed) | bit
+            } else {
+                FANS_SEEN_SPINNING.load(Ordering::Relaxed)
+            };
+            let present = seen & bit != 0 || raw & FAN_PRESENT_BIT != 0;
+            Some(FanInfo {
+                target,
+                rpm,
+                status: FanPresence::from_rpm(rpm).with_known_present(present),
+            })
+        })
+        .collect()
+}
+
+/// Fan speeds and thermal profile, read together in one WMI round-trip.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanStatus {
+    pub fans: Vec<FanInfo>,
+    pub profile: ThermalProfile,
+}
+
+/// Read all fan speeds and the active thermal profile.
+pub fn get_fan_status(conn: &impl AsusWmi) -> Result<FanStatus> {
+    Ok(FanStatus {
+        fans: get_all_fan_speeds(conn),
+        profile: get_thermal_profile(conn)?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Thermal profile
+// ---------------------------------------------------------------------------
+
+/// Read the currently active thermal profile.
+pub fn get_thermal_profile(conn: &impl AsusWmi) -> Result<ThermalProfile> {
+    let raw = dsts(conn, device_id::THROTTLE_THERMAL_POLICY)?;
+    ThermalProfile::from_raw(raw)
+        .ok_or_else(|| NoCrateError::Wmi(format!("Unknown thermal-profile raw value: 0x{raw:08X}")))
+}
+
+/// Set the active thermal profile.
+pub fn set_thermal_profile(conn: &impl AsusWmi, profile: ThermalProfile) -> Result<()> {
+    let _status = devs(conn, device_id::THROTTLE_THERMAL_POLICY, profile.to_raw())?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Fan curve
+// ---------------------------------------------------------------------------
+
+/// Number of control points in an ASUS fan curve.
+pub const FAN_CURVE_POINTS: usize = 8;
+
+/// A single temperature → duty-cycle mapping point.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FanCurvePoint {
+    /// Temperature threshold in °C (0–100).
+    pub temp_c: u8,
+    /// Fan duty-cycle percentage (0–100).
+    pub duty_pct: u8,
+}
+
+/// A complete fan curve with [`FAN_CURVE_POINTS`] pairs.
+///
+/// Points must be sorted by ascending temperature. The fan controller
+/// linearly interpolates between adjacent points.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanCurve {
+    pub target: FanTarget,
+    pub points: [FanCurvePoint; FAN_CURVE_POINTS],
+}
+
+impl FanCurve {
+    /// A sensible default curve: gentle ramp from 30 % at 30 °C to 100 % at 90 °C.
+    #[must_use]
+    pub fn default_for(target: FanTarget) -> Self {
+        Self {
+            target,
+            points: [
+                FanCurvePoint {
+                    temp_c: 30,
+                    duty_pct: 30,
+                },
+                FanCurvePoint {
+                    temp_c: 40,
+                    duty_pct: 35,
+                },
+                FanCurvePoint {
+                    temp_c: 50,
+                    duty_pct: 45,
+                },
+                FanCurvePoint {
+                    temp_c: 60,
+                    duty_pct: 55,
+                },
+                FanCurvePoint {
+                    temp_c: 70,
+                    duty_pct: 65,
+                },
+                FanCurvePoint {
+                    temp_c: 75,
+                    duty_pct: 75,
+                },
+                FanCurvePoint {
+                    temp_c: 80,
+                    duty_pct: 85,
+                },
+                FanCurvePoint {
+                    temp_c: 90,
+                    duty_pct: 100,
+                },
+            ],
+        }
+    }
+}
+
+impl FanTarget {
+    /// Laptop fan-curve device ID for this fan.
+    #[must_use]
+    pub const fn curve_device_id(self) -> u32 {
+        match self {
+            Self::Cpu => device_id::CPU_FAN_CURVE,
+            Self::Gpu => device_id::GPU_FAN_CURVE,
+            Self::Mid => device_id::MID_FAN_CURVE,
+        }
+    }
+}
+
+impl ThermalProfile {
+    /// Fan-curve bank of this profile. The firmware numbers the banks
+    /// with Performance and Silent swapped relative to the profile IDs.
+    #[must_use]
+    pub const fn fan_curve_bank(self) -> u32 {
+        match self {
+            Self::Standard => 0,
+            Self::Performance => 2,
+            Self::Silent => 1,
+        }
+    }
+}
+
+impl FanCurve {
+    /// Parse a 16-byte laptop curve buffer: 8 temperatures, then 8 duty
+    /// percentages.
+    fn from_buffer(target: FanTarget, buf: &[u8; 16]) -> Option<Self> {
+        // An all-zero buffer means the fan has no curve on this model
+        if buf.iter().all(|&b| b == 0) {
+            return None;
+        }
+        let mut points = [FanCurvePoint {
+            temp_c: 0,
+            duty_pct: 0,
+        }; FAN_CURVE_POINTS];
+        for (i, point) in points.iter_mut().enumerate() {
+            point.temp_c = buf[i];
+            point.duty_pct = buf[FAN_CURVE_POINTS + i].min(100);
+        }
+        Some(Self { target, points })
+    }
+
+    fn to_buffer(&self) -> [u8; 16] {
+        let mut buf = [0u8; 16];
+        for (i, point) in self.points.iter().enumerate() {
+            buf[i] = point.temp_c;
+            buf[FAN_CURVE_POINTS + i] = point.duty_pct;
+        }
+        buf
+    }
+
+    /// Check the curve against the rules in [`crate::curve`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidCurve` with every rule the curve breaks.
+    pub fn validate(&self) -> Result<()> {
+        crate::curve::validate(&self.points, None)
+    }
+}
+
+/// Read the laptop fan curve of `target` stored in the bank of `profile`.
+///
+/// Returns `None` if the fan has no curve on this model.
+pub fn get_laptop_fan_curve(
+    conn: &impl AsusWmi,
+    target: FanTarget,
+    profile: ThermalProfile,
+) -> Result<Option<FanCurve>> {
+    require_laptop(conn, "Fan curves")?;
+    let buf = AtkAcpi::open()?.dsts_buffer(target.curve_device_id(), profile.fan_curve_bank())?;
+    Ok(FanCurve::from_buffer(target, &buf))
+}
+
+/// Write a laptop fan curve. The firmware applies it to the bank of the
+/// active thermal profile until the profile is switched.
+pub fn set_laptop_fan_curve(conn: &impl AsusWmi, curve: &FanCurve) -> Result<()> {
+    require_laptop(conn, "Fan curves")?;
+    curve.validate()?;
+    let status =
+        AtkAcpi::open()?.devs_buffer(curve.target.curve_device_id(), &curve.to_buffer())?;
+    if status == 0 {
+        return Err(NoCrateError::Wmi(format!(
+            "Fan curve for {:?} rejected by firmware",
+            curve.target
+        )));
+    }
+    Ok(())
+}
+
+/// Write the custom curves saved for `profile`, if any.
+pub fn apply_laptop_fan_curves(
+    conn: &impl AsusWmi,
+    curves: &HashMap<ThermalProfile, Vec<FanCurve>>,
+    profile: ThermalProfile,
+) -> Result<()> {
+    curves
+        .get(&profile)
+        .into_iter()
+        .flatten()
+        .try_for_each(|curve| set_laptop_fan_curve(conn, curve))
+}
+
+// ===========================================================================
+// Desktop motherboard support (ASUSManagement WMI class)
+// ===========================================================================
+
+/// Maximum number of fan headers to probe on a desktop motherboard.
+///
+/// ASUS desktop boards typically expose FanType 0–3 via `GetFanPolicy`.
+/// Headers returning `ErrorCode != 0` are considered absent.
+pub const DESKTOP_MAX_FAN_HEADERS: u8 = 8;
+
+/// Fan control mode on desktop boards.
+///
+/// Returned by `GetFanPolicy` as the `Mode` string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DesktopFanMode {
+    /// PWM (pulse-width modulation) control.
+    Pwm,
+    /// Voltage-controlled (DC).
+    Dc,
+    /// Automatic control (board decides PWM/DC).
+    Auto,
+}
+
+impl DesktopFanMode {
+    /// All modes, handy for iteration / UI.
+    pub const ALL: [Self; 3] = [Self::Pwm, Self::Dc, Self::Auto];
+
+    pub fn from_wmi(s: &str) -> Self {
+        match s.to_uppercase().as_str() {
+            "PWM" => Self::Pwm,
+            "DC" => Self::Dc,
+            _ => Self::Auto,
+        }
+    }
+
+    pub fn to_wmi(self) -> &'static str {
+        match self {
+            Self::Pwm => "PWM",
+            Self::Dc => "DC",
+            Self::Auto => "AUTO",
+        }
+    }
+}
+
+/// Fan policy profile on desktop boards.
+///
+/// Returned by `GetFanPolicy` as the `Profile` string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DesktopFanProfile {
+    /// User-defined manual curve.
+    Manual,
+    /// Default automatic curve.
+    Standard,
+}
+
+impl DesktopFanProfile {
+    pub fn from_wmi(s: &str) -> Self {
+        match s.to_uppercase().as_str() {
+            "MANUAL" => Self::Manual,
+            _ => Self::Standard,
+        }
+    }
+
+    pub fn to_wmi(self) -> &'static str {
+        match self {
+            Self::Manual => "MANUAL",
+            Self::Standard => "STANDARD",
+        }
+    }
+}
+
+/// Complete fan policy for a single desktop fan header.
+///
+/// Read via `ASUSManagement.GetFanPolicy(FanType)` and written back
+/// via `ASUSManagement.SetFanPolicy(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopFanPolicy {
+    /// Fan header index (0-based: 0 = CPU, 1–3 = chassis).
+    pub fan_type: u8,
+    /// Control mode: PWM (DC) or AUTO.
+    pub mode: DesktopFanMode,
+    /// Curve profile: MANUAL or STANDARD.
+    pub profile: DesktopFanProfile,
+    /// Temperature source (e.g. "CPU").
+    pub source: String,
+    /// Minimum RPM threshold; slower fans are reported as failed at boot
+    /// (0 = ignore). See [`check_low_limit`].
+    pub low_limit: u32,
+    /// Board header name from the quirks table, if known.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Super I/O fan channel measuring this header, if known.
+    #[serde(default)]
+    pub sio_channel: Option<u8>,
+}
+
+/// Read the fan policy for a single desktop fan header.
+///
+/// Returns `None` if the header does not exist (ErrorCode != 0).
+pub fn get_desktop_fan_policy(
+    conn: &impl AsusWmi,
+    fan_type: u8,
+) -> Result<Option<DesktopFanPolicy>> {
+    let instance_path = match conn.backend() {
+        AsusWmiBackend::Desktop { instance_path } => instance_path.clone(),
+        _ => {
+            return Err(NoCrateError::Wmi(
+                "GetFanPolicy is only available on desktop backends".into(),
+            ));
+        }
+    };
+
+    let out = conn.exec_method_v2(
+        &instance_path,
+        "GetFanPolicy",
+        &[("FanType", WmiParam::U8(fan_type))],
+    )?;
+
+    let error_code = out.get_u32("ErrorCode")?;
+    if error_code != 0 {
+        return Ok(None); // Fan header not present
+    }
+
+    let mode = out.get_string("Mode")?;
+    let profile = out.get_string("Profile")?;
+    let source = out.get_string("Source")?;
+    let low_limit = out.get_u32("LowLimit")?;
+
+    Ok(Some(DesktopFanPolicy {
+        fan_type,
+        mode: DesktopFanMode::from_wmi(&mode),
+        profile: DesktopFanProfile::from_wmi(&profile),
+        source,
+        low_limit,
+        name: None,
+        sio_channel: None,
+    }))
+}
+
+/// Read fan policies for all present desktop fan headers.
+///
+/// Probes FanType 0 through [`DESKTOP_MAX_FAN_HEADERS`] and returns
+/// only headers that respond without error.
+pub fn get_all_desktop_fan_policies(conn: &impl AsusWmi) -> Vec<DesktopFanPolicy> {
+    (0..DESKTOP_MAX_FAN_HEADERS)
+        .filter_map(|ft| get_desktop_fan_policy(conn, ft).ok().flatten())
+        .collect()
+}
+
+/// Write a fan policy to a desktop fan header.
+///
+/// # Errors
+///
+/// Returns an error if the WMI call fails or the backend is not desktop.
+pub fn set_desktop_fan_policy(conn: &impl AsusWmi, policy: &DesktopFanPolicy) -> Result<()> {
+    let instance_path = match conn.backend() {
+        AsusWmiBackend::Desktop { instance_path } => instance_path.clone(),
+        _ => {
+            return Err(NoCrateError::Wmi(
+                "SetFanPolicy is only available on desktop backends".into(),
+            ));
+        }
+    };
+
+    let out = conn.exec_method_v2(
+        &instance_path,
+        "SetFanPolicy",
+        &[
+            ("FanType", WmiParam::U8(policy.fan_type)),
+            ("LowLimit", WmiParam::U32(policy.low_limit)),
+            ("Mode", WmiParam::Str(policy.mode.to_wmi())),
+            ("Profile", WmiParam::Str(policy.profile.to_wmi())),
+            // Source 可以为空字符串（CPU Fan 时）
+            // WMI 方法会根据 FanType 自动确定默认源
+            ("Source", WmiParam::Str(&policy.source)),
+        ],
+    )?;
+
+    let error_code = out.get_u32("ErrorCode")?;
+    if error_code != 0 {
+        return Err(desktop_error(
+            conn,
+            i18n::tf(
+                Msg::WmiFanMethodFailed,
+                &[
+                    ("method", &"SetFanPolicy"),
+                    ("fan", &policy.fan_type),
+                    ("code", &error_code),
+                ],
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Temperature sources offered by ASUS BIOS fan settings, used when the
+/// firmware does not declare the accepted `Source` values.
+pub const DEFAULT_FAN_SOURCES: &[&str] = &["CPU", "MB", "VRM", "PCH", "T_SENSOR"];
+
+/// Temperature sources a desktop fan header can follow.
+#[derive(Debug, Clone, Serialize)]
+pub struct DesktopFanSources {
+    /// Fan header index.
+    pub fan_type: u8,
+    /// Source of the current policy (may be empty on the CPU header).
+    pub current: String,
+    /// Source names accepted by `SetFanPolicy`.
+    pub available: Vec<String>,
+    /// Whether `available` comes from the firmware or is
+    /// [`DEFAULT_FAN_SOURCES`].
+    pub declared: bool,
+}
+
+/// List the temperature sources of every present desktop fan header.
+///
+/// The names come from the `Values` qualifier of the `Source` parameter
+/// of `SetFanPolicy`, falling back to [`DEFAULT_FAN_SOURCES`]. A header's
+/// current source is always included, so the UI can show it even if the
+/// firmware does not declare it.
+///
+/// # Errors
+///
+/// Returns an error if the backend is not desktop.
+pub fn get_desktop_fan_sources(conn: &impl AsusWmi) -> Result<Vec<DesktopFanSources>> {
+    let AsusWmiBackend::Desktop { instance_path } = conn.backend() else {
+        return Err(NoCrateError::Wmi(
+            "Fan sources are only available on desktop backends".into(),
+        ));
+    };
+
+    let declared = conn
+        .method_param_values(instance_path, "SetFanPolicy", "Source")
+        .ok()
+        .filter(|names| !names.is_empty());
+
+    Ok(get_all_desktop_fan_policies(conn)
+        .into_iter()
+        .map(|policy| {
+            let mut available = declared.clone().unwrap_or_else(|| {
+                DEFAULT_FAN_SOURCES
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            });
+            if !policy.source.is_empty()
+                && !available
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(&policy.source))
+            {
+                available.insert(0, policy.source.clone());
+            }
+            DesktopFanSources {
+                fan_type: policy.fan_type,
+                current: policy.source,
+                available,
+                declared: declared.is_some(),
+            }
+        })
+        .collect())
+}
+
+// ---------------------------------------------------------------------------
+// Desktop fan calibration (Q-Fan Tuning)
+// ---------------------------------------------------------------------------
+
+/// State of a header's Q-Fan calibration run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FanCalibrationState {
+    /// Never calibrated since boot.
+    Idle,
+    /// Sweeping the duty range; the fan speeds up and slows down.
+    Running,
+    /// Finished, the result is available.
+    Done,
+    /// Aborted, e.g. the fan did not spin or was unplugged.
+    Failed,
+}
+
+impl FanCalibrationState {
+    /// Parse the `Status` output of `GetFanCalibrationStatus`.
+    #[must_use]
+    pub const fn from_raw(value: u32) -> Self {
+        match value {
+            1 => Self::Running,
+            2 => Self::Done,
+            3 => Self::Failed,
+            _ => Self::Idle,
+        }
+    }
+}
+
+/// Measured characteristics of a fan header, as found by BIOS
+/// "Q-Fan Tuning".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FanCalibration {
+    /// Lowest duty in percent at which the fan keeps spinning.
+    pub min_duty_pct: u8,
+    /// Speed at `min_duty_pct`.
+    pub min_rpm: u32,
+    /// Speed at 100 % duty.
+    pub max_rpm: u32,
+}
+
+/// Progress of a header's calibration run.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanCalibrationStatus {
+    /// Fan header index.
+    pub fan_type: u8,
+    pub state: FanCalibrationState,
+    /// 0–100, only meaningful while running.
+    pub progress_pct: u8,
+    /// Set once the run is done.
+    pub result: Option<FanCalibration>,
+}
+
+/// Start Q-Fan calibration of a desktop fan header
+/// (`ASUSManagement.StartFanCalibration(FanType)`).
+///
+/// The run takes a minute or two per header and continues in the
+/// firmware; poll [`get_fan_calibration_status`] for progress. Fan
+/// control is suspended on the header meanwhile.
+///
+/// # Errors
+///
+/// Returns an error if the backend is not desktop, the firmware lacks
+/// the method or rejects the header.
+pub fn start_fan_calibration(conn: &impl AsusWmi, fan_type: u8) -> Result<()> {
+    let AsusWmiBackend::Desktop { instance_path } = conn.backend() else {
+        return Err(NoCrateError::Wmi(
+            "Fan calibration is only available on desktop backends".into(),
+        ));
+    };
+
+    let out = conn.exec_method_v2(
+        instance_path,
+        "StartFanCalibration",
+        &[("FanType", WmiParam::U8(fan_type))],
+    )?;
+
+    let error_code = out.get_u32("ErrorCode")?;
+    if error_code != 0 {
+        return Err(desktop_error(
+            conn,
+            i18n::tf(
+                Msg::WmiFanMethodFailed,
+                &[
+                    ("method", &"StartFanCalibration"),
+                    ("fan", &fan_type),
+                    ("code", &error_code),
+                ],
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Read the calibration progress of a desktop fan header
+/// (`ASUSManagement.GetFanCalibrationStatus(FanType)`).
+///
+/// # Errors
+///
+/// Returns an error if the backend is not desktop, the firmware lacks
+/// the method or the header does not exist.
+pub fn get_fan_calibration_status(
+    conn: &impl AsusWmi,
+    fan_type: u8,
+) -> Result<FanCalibrationStatus> {
+    let AsusWmiBackend::Desktop { instance_path } = conn.backend() else {
+        return Err(NoCrateError::Wmi(
+            "Fan calibration is only available on desktop backends".into(),
+        ));
+    };
+
+    let out = conn.exec_method_v2(
+        instance_path,
+        "GetFanCalibrationStatus",
+        &[("FanType", WmiParam::U8(fan_type))],
+    )?;
+
+    let error_code = out.get_u32("ErrorCode")?;
+    if error_code != 0 {
+        return Err(desktop_error(
+            conn,
+            i18n::tf(
+                Msg::WmiFanMethodFailed,
+                &[
+                    ("method", &"GetFanCalibrationStatus"),
+                    ("fan", &fan_type),
+                    ("code", &error_code),
+                ],
+            ),
+        ));
+    }
+
+    let state = FanCalibrationState::from_raw(out.get_u32("Status")?);
+    let result = if state == FanCalibrationState::Done {
+        Some(FanCalibration {
+            min_duty_pct: u8::try_from(out.get_u32("MinDuty")?).unwrap_or(100),
+            min_rpm: out.get_u32("MinRpm")?,
+            max_rpm: out.get_u32("MaxRpm")?,
+        })
+    } else {
+        None
+    };
+
+    Ok(FanCalibrationStatus {
+        fan_type,
+        state,
+        progress_pct: u8::try_from(out.get_u32("Progress")?.min(100)).unwrap_or(100),
+        result,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Fan curve preview
+// ---------------------------------------------------------------------------
+
+/// One sample of a curve preview.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CurvePreviewPoint {
+    /// Input temperature in °C.
+    pub temp_c: f32,
+    /// Duty the curve yields at `temp_c`, in percent.
+    pub duty_pct: f32,
+    /// Estimated fan speed, when calibration data is available.
+    pub rpm: Option<u32>,
+}
+
+/// Duty in percent at `temp_c` on `points`, linearly interpolated the
+/// way the firmware does. Outside the curve the first or last duty holds.
+#[must_use]
+pub fn curve_duty_at(points: &[FanCurvePoint], temp_c: f32) -> f32 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return 0.0;
+    };
+    if temp_c <= f32::from(first.temp_c) {
+        return f32::from(first.duty_pct);
+    }
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (t0, t1) = (f32::from(a.temp_c), f32::from(b.temp_c));
+        if temp_c <= t1 {
+            if t1 <= t0 {
+                return f32::from(b.duty_pct);
+            }
+            let d0 = f32::from(a.duty_pct);
+            return ((temp_c - t0) / (t1 - t0)).mul_add(f32::from(b.duty_pct) - d0, d0);
+        }
+    }
+    f32::from(last.duty_pct)
+}
+
+/// Estimated speed at `duty_pct` from calibration data: zero below the
+/// minimum duty, then linear up to the speed at 100 %.
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn calibrated_rpm(calibration: &FanCalibration, duty_pct: f32) -> u32 {
+    let min_duty = f32::from(calibration.min_duty_pct);
+    if duty_pct < min_duty {
+        return 0;
+    }
+    if min_duty >= 100.0 {
+        return calibration.max_rpm;
+    }
+    let (lo, hi) = (calibration.min_rpm as f32, calibration.max_rpm as f32);
+    let t = ((duty_pct - min_duty) / (100.0 - min_duty)).clamp(0.0, 1.0);
+    t.mul_add(hi - lo, lo).max(0.0).round() as u32
+}
+
+/// Run a temperature trace through a curve without touching hardware.
+/// Speeds are only estimated when `calibration` is given.
+#[must_use]
+pub fn preview_fan_curve(
+    points: &[FanCurvePoint],
+    temps: &[f32],
+    calibration: Option<&FanCalibration>,
+) -> Vec<CurvePreviewPoint> {
+    temps
+        .iter()
+        .map(|&temp_c| {
+            let duty_pct = curve_duty_at(points, temp_c).clamp(0.0, 100.0);
+            CurvePreviewPoint {
+                temp_c,
+                duty_pct,
+                rpm: calibration.map(|c| calibrated_rpm(c, duty_pct)),
+            }
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Desktop fan low limit
+// ---------------------------------------------------------------------------
+
+/// Highest low limit the BIOS offers for a header, in RPM.
+pub const LOW_LIMIT_MAX_RPM: u32 = 1000;
+
+/// Why a desktop fan low limit is accepted but risky.
+///
+/// The BIOS halts POST with "CPU fan error" when a header spins below its
+/// low limit, so a limit above the slowest speed the fan reaches can stop
+/// the next boot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LowLimitWarning {
+    /// The fan runs as slow as `min_rpm` at its lowest duty, below the
+    /// limit.
+    AboveMinRpm { min_rpm: u32 },
+    /// Limit 0 on the CPU header: a failed CPU fan goes unnoticed.
+    MonitoringDisabled,
+    /// The header was never calibrated, the limit cannot be checked
+    /// against the fan's speed range.
+    Uncalibrated,
+}
+
+/// Validate a low limit for a desktop fan header against its Q-Fan
+/// calibration, if known.
+///
+/// Returns the warnings for a limit that can be written.
+///
+/// # Errors
+///
+/// Returns `Wmi` if the limit is above [`LOW_LIMIT_MAX_RPM`] or not below
+/// the fan's full speed, which would report a fan error on every boot.
+pub fn check_low_limit(
+    fan_type: u8,
+    low_limit: u32,
+    calibration: Option<&FanCalibration>,
+) -> Result<Vec<LowLimitWarning>> {
+    if low_limit > LOW_LIMIT_MAX_RPM {
+        return Err(NoCrateError::Wmi(format!(
+            "Low limit {low_limit} RPM is above the maximum of {LOW_LIMIT_MAX_RPM} RPM"
+        )));
+    }
+
+    let mut warnings = Vec::new();
+    if low_limit == 0 {
+        if fan_type == 0 {
+            warnings.push(LowLimitWarning::MonitoringDisabled);
+        }
+        return Ok(warnings);
+    }
+
+    match calibration {
+        Some(cal) if low_limit >= cal.max_rpm => {
+            return Err(NoCrateError::Wmi(format!(
+                "Low limit {low_limit} RPM is not below the fan's full speed of {} RPM",
+                cal.max_rpm
+            )));
+        }
+        Some(cal) if low_limit > cal.min_rpm => {
+            warnings.push(LowLimitWarning::AboveMinRpm {
+                min_rpm: cal.min_rpm,
+            });
+        }
+        Some(_) => {}
+        None => warnings.push(LowLimitWarning::Uncalibrated),
+    }
+    Ok(warnings)
+}
+
+// ---------------------------------------------------------------------------
+// Desktop fan curve (GetManualFanCurvePro / SetManualFanCurvePro)
+// ---------------------------------------------------------------------------
+
+/// 桌面主板 8 点风扇曲线。
+///
+/// 通过 `GetManualFanCurvePro` 读取，`SetManualFanCurvePro` 写入。
+/// 每条曲线绑定一个 FanType + Mode 组合。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopFanCurve {
+    /// 风扇头索引 (0 = CPU, 1 = Chassis1, ...)
+    pub fan_type: u8,
+    /// 控制模式 (PWM / DC / AUTO)
+    pub mode: DesktopFanMode,
+    /// 8 个温度→占空比映射点
+    pub points: [FanCurvePoint; FAN_CURVE_POINTS],
+}
+
+/// 读取桌面主板某个风扇头在指定模式下的 8 点曲线。
+///
+/// # 参数
+/// - `fan_type`: 风扇头索引 (0–7)
+/// - `mode`: 控制模式 (PWM / DC / AUTO)
+///
+/// # 返回
+/// - `Ok(Some(curve))` — 成功读取
+/// - `Ok(None)` — 该风扇头不存在 (ErrorCode != 0)
+/// - `Err(...)` — WMI 调用失败
+pub fn get_desktop_fan_curve_pro(
+    conn: &impl AsusWmi,
+    fan_type: u8,
+    mode: DesktopFanMode,
+) -> Result<Option<DesktopFanCurve>> {
+    let instance_path = match conn.backend() {
+        AsusWmiBackend::Desktop { instance_path } => instance_path.clone(),
+        _ => {
+            return Err(desktop_only("GetManualFanCurvePro"));
+        }
+    };
+
+    let out = conn.exec_method_v2(
+        &instance_path,
+        "GetManualFanCurvePro",
+        &[
+            ("FanType", WmiParam::U8(fan_type)),
+            ("Mode", WmiParam::Str(mode.to_wmi())),
+        ],
+    )?;
+
+    let error_code = out.get_u32("ErrorCode")?;
+    if error_code != 0 {
+        return Ok(None);
+    }
+
+    // 解析 8 个点：Point1Temp ~ Point8Temp, Point1Duty ~ Point8Duty
+    let mut points = [FanCurvePoint {
+        temp_c: 0,
+        duty_pct: 0,
+    }; FAN_CURVE_POINTS];
+
+    for i in 0..FAN_CURVE_POINTS {
+        let idx = i + 1;
+        let temp_name = format!("Point{idx}Temp");
+        let duty_name = format!("Point{idx}Duty");
+
+        points[i].temp_c = out.get_u32(&temp_name)? as u8;
+        points[i].duty_pct = out.get_u32(&duty_name)? as u8;
+    }
+
+    Ok(Some(DesktopFanCurve {
+        fan_type,
+        mode,
+        points,
+    }))
+}
+
+/// 写入桌面主板某个风扇头在指定模式下的 8 点曲线。
+///
+/// # 校验
+/// 按 [`crate::curve`] 的规则校验，违规时返回 `InvalidCurve`；停转点检查由调用方
+/// 传入校准结果另行完成
+pub fn set_desktop_fan_curve_pro(conn: &impl AsusWmi, curve: &DesktopFanCurve) -> Result<()> {
+    crate::curve::validate(&curve.points, None)?;
+
+    let instance_path = match conn.backend() {
+        AsusWmiBackend::Desktop { instance_path } => instance_path.clone(),
+        _ => {
+            return Err(desktop_only("SetManualFanCurvePro"));
+        }
+    };
+
+    // 组装 WMI 参数：FanType + Mode + Point1Temp...Point8Temp + Point1Duty...Point8Duty
+    let mut params: Vec<(&str, WmiParam)> = Vec::with_capacity(2 + FAN_CURVE_POINTS * 2);
+    params.push(("FanType", WmiParam::U8(curve.fan_type)));
+    params.push(("Mode", WmiParam::Str(curve.mode.to_wmi())));
+
+    // WMI 的参数名不能用动态 String，需要用 'static str
+    // 硬编码 8 个点的参数名
+    const TEMP_NAMES: [&str; 8] = [
+        "Point1Temp",
+        "Point2Temp",
+        "Point3Temp",
+        "Point4Temp",
+        "Point5Temp",
+        "Point6Temp",
+        "Point7Temp",
+        "Point8Temp",
+    ];
+    const DUTY_NAMES: [&str; 8] = [
+        "Point1Duty",
+        "Point2Duty",
+        "Point3Duty",
+        "Point4Duty",
+        "Point5Duty",
+        "Point6Duty",
+        "Point7Duty",
+        "Point8Duty",
+    ];
+
+    for i in 0..FAN_CURVE_POINTS {
+        params.push((TEMP_NAMES[i], WmiParam::U8(curve.points[i].temp_c)));
+        params.push((DUTY_NAMES[i], WmiParam::U8(curve.points[i].duty_pct)));
+    }
+
+    let out = conn.exec_method_v2(&instance_path, "SetManualFanCurvePro", &params)?;
+
+    let error_code = out.get_u32("ErrorCode")?;
+    if error_code != 0 {
+        return Err(desktop_error(
+            conn,
+            i18n::tf(
+                Msg::WmiCurveMethodFailed,
+                &[
+                    ("method", &"SetManualFanCurvePro"),
+                    ("fan", &curve.fan_type),
+                    ("mode", &curve.mode.to_wmi()),
+                    ("code", &error_code),
+                ],
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Error for a method that only the Desktop backend has.
+fn desktop_only(method: &str) -> NoCrateError {
+    NoCrateError::Wmi(i18n::tf(Msg::WmiDesktopOnly, &[("method", &method)]))
+}
+
+/// A `Wmi` error for a desktop call the firmware refused, with the code
+/// `GetLastError` reports for it when there is one.
+fn desktop_error(conn: &impl AsusWmi, message: String) -> NoCrateError {
+    match conn.last_error() {
+        Some(code) => NoCrateError::Wmi(format!("{message} (GetLastError 0x{code:08X})")),
+        None => NoCrateError::Wmi(message),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Desktop 3-point fan curve (GetManualFanCurve / SetManualFanCurve)
+// ---------------------------------------------------------------------------
+
+/// 非 Pro 版本曲线的点数（Low / Mid / High）。
+pub const FAN_CURVE_3_POINTS: usize = 3;
+
+/// 3 点曲线各点的 WMI 参数名 `(温度, 占空比)`，按 Low / Mid / High 顺序。
+const CURVE_3_PARAMS: [(&str, &str); FAN_CURVE_3_POINTS] = [
+    ("LowTemp", "LowDuty"),
+    ("MidTemp", "MidDuty"),
+    ("HighTemp", "HighDuty"),
+];
+
+/// 桌面主板 3 点风扇曲线（Low / Mid / High）。
+///
+/// 通过 `GetManualFanCurve` 读取，`SetManualFanCurve` 写入。部分较旧的
+/// `ASUSManagement` 固件只实现了这一版本，没有 Pro 的 8 点曲线。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopFanCurve3 {
+    /// 风扇头索引 (0 = CPU, 1 = Chassis1, ...)
+    pub fan_type: u8,
+    /// 控制模式 (PWM / DC / AUTO)
+    pub mode: DesktopFanMode,
+    /// Low / Mid / High 三个温度→占空比映射点
+    pub points: [FanCurvePoint; FAN_CURVE_3_POINTS],
+}
+
+/// 读取桌面主板某个风扇头在指定模式下的 3 点曲线。
+///
+/// 返回值含义同 [`get_desktop_fan_curve_pro`]：`Ok(None)` 表示该风扇头
+/// 不存在。
+pub fn get_desktop_fan_curve_3(
+    conn: &impl AsusWmi,
+    fan_type: u8,
+    mode: DesktopFanMode,
+) -> Result<Option<DesktopFanCurve3>> {
+    let AsusWmiBackend::Desktop { instance_path } = conn.backend() else {
+        return Err(desktop_only("GetManualFanCurve"));
+    };
+
+    let out = conn.exec_method_v2(
+        instance_path,
+        "GetManualFanCurve",
+        &[
+            ("FanType", WmiParam::U8(fan_type)),
+            ("Mode", WmiParam::Str(mode.to_wmi())),
+        ],
+    )?;
+
+    if out.get_u32("ErrorCode")? != 0 {
+        return Ok(None);
+    }
+
+    let mut points = [FanCurvePoint {
+        temp_c: 0,
+        duty_pct: 0,
+    }; FAN_CURVE_3_POINTS];
+    for (point, (temp_name, duty_name)) in points.iter_mut().zip(CURVE_3_PARAMS) {
+        point.temp_c = u8::try_from(out.get_u32(temp_name)?).unwrap_or(u8::MAX);
+        point.duty_pct = u8::try_from(out.get_u32(duty_name)?).unwrap_or(u8::MAX);
+    }
+
+    Ok(Some(DesktopFanCurve3 {
+        fan_type,
+        mode,
+        points,
+    }))
+}
+
+/// 写入桌面主板某个风扇头在指定模式下的 3 点曲线。
+///
+/// 校验规则同 [`set_desktop_fan_curve_pro`]。
+pub fn set_desktop_fan_curve_3(conn: &impl AsusWmi, curve: &DesktopFanCurve3) -> Result<()> {
+    crate::curve::validate(&curve.points, None)?;
+
+    let AsusWmiBackend::Desktop { instance_path } = conn.backend() else {
+        return Err(desktop_only("SetManualFanCurve"));
+    };
+
+    let mut params: Vec<(&str, WmiParam)> = Vec::with_capacity(2 + FAN_CURVE_3_POINTS * 2);
+    params.push(("FanType", WmiParam::U8(curve.fan_type)));
+    params.push(("Mode", WmiParam::Str(curve.mode.to_wmi())));
+    for (point, (temp_name, duty_name)) in curve.points.iter().zip(CURVE_3_PARAMS) {
+        params.push((temp_name, WmiParam::U8(point.temp_c)));
+        params.push((duty_name, WmiParam::U8(point.duty_pct)));
+    }
+
+    let out = conn.exec_method_v2(instance_path, "SetManualFanCurve", &params)?;
+
+    let error_code = out.get_u32("ErrorCode")?;
+    if error_code != 0 {
+        return Err(desktop_error(
+            conn,
+            i18n::tf(
+                Msg::WmiCurveMethodFailed,
+                &[
+                    ("method", &"SetManualFanCurve"),
+                    ("fan", &curve.fan_type),
+                    ("mode", &curve.mode.to_wmi()),
+                    ("code", &error_code),
+                ],
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// 探测所有存在的桌面风扇头及其支持的模式。
+///
+/// 返回 `(fan_type, Vec<DesktopFanMode>)` 列表。
+/// 通过尝试 `GetManualFanCurvePro` 来判断某 FanType+Mode 组合是否可用。
+pub fn probe_desktop_fan_types(conn: &impl AsusWmi) -> Vec<(u8, Vec<DesktopFanMode>)> {
+    let mut result = Vec::new();
+
+    for ft in 0..DESKTOP_MAX_FAN_HEADERS {
+        let mut modes = Vec::new();
+        for mode in DesktopFanMode::ALL {
+            if let Ok(Some(_)) = get_desktop_fan_curve_pro(conn, ft, mode) {
+                modes.push(mode);
+            }
+        }
+        if !modes.is_empty() {
+            result.push((ft, modes));
+        }
+    }
+
+    result
+}
+
+// ---------------------------------------------------------------------------
+// Laptop controls
+// ---------------------------------------------------------------------------
+
+/// Lowest accepted battery charge limit; lower values risk leaving the
+/// laptop unable to run on battery at all.
+pub const BATTERY_LIMIT_MIN: u8 = 20;
+
+/// Return an error unless the backend is the laptop `ASUSATKWMI_WMNB`.
+pub fn require_laptop(conn: &impl AsusWmi, feature: &str) -> Result<()> {
+    if matches!(conn.backend(), AsusWmiBackend::Laptop { .. }) {
+        Ok(())
+    } else {
+        Err(NoCrateError::Wmi(format!(
+            "{feature} is only available on laptop backends"
+        )))
+    }
+}
+
+/// Read a presence-checked on/off laptop device, `None` if absent.
+fn read_switch(conn: &impl AsusWmi, device_id: u32) -> Result<Option<bool>> {
+    let raw = dsts(conn, device_id)?;
+    Ok((raw & DSTS_PRESENCE_BIT != 0).then_some(raw & 1 != 0))
+}
+
+/// Write an on/off laptop device, failing if the firmware rejects it.
+fn write_switch(conn: &impl AsusWmi, device_id: u32, on: bool, name: &str) -> Result<()> {
+    let status = devs(conn, device_id, u32::from(on))?;
+    if status == 0 {
+        return Err(NoCrateError::Wmi(format!(
+            "{name} write rejected by firmware"
+        )));
+    }
+    Ok(())
+}
+
+/// Stop charging the battery at `percent` (100 = no limit).
+///
+/// The firmware does not report the current threshold, so callers keep
+/// the last value themselves.
+pub fn set_battery_charge_limit(conn: &impl AsusWmi, percent: u8) -> Result<()> {
+    require_laptop(conn, "Battery charge limit")?;
+    if !(BATTERY_LIMIT_MIN..=100).contains(&percent) {
+        return Err(NoCrateError::Wmi(format!(
+            "Battery charge limit {percent}% out of range ({BATTERY_LIMIT_MIN}-100)"
+        )));
+    }
+    let status = devs(conn, device_id::BATTERY_CHARGE_LIMIT, u32::from(percent))?;
+    if status == 0 {
+        return Err(NoCrateError::Wmi(
+            "Battery charge limit not supported by this laptop".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Laptop GPU mode, as named by Armoury Crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuMode {
+    /// dGPU powered off, iGPU only.
+    Eco,
+    /// Hybrid graphics: iGPU drives the panel, dGPU on demand.
+    Standard,
+    /// MUX switched: the dGPU drives the panel directly.
+    Ultimate,
+}
+
+/// Current GPU mode and which switches the laptop has.
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuModeInfo {
+    pub mode: GpuMode,
+    /// The GPU MUX device is present (Ultimate available).
+    pub mux_supported: bool,
+    /// The dGPU disable device is present (Eco available).
+    pub eco_supported: bool,
+}
+
+/// Result of a GPU mode change.
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuModeChange {
+    /// The MUX was switched; the new mode applies after a reboot.
+    pub reboot_required: bool,
+}
+
+/// Read the GPU mode.
+///
+/// After a MUX switch this already reports the mode pending the reboot.
+pub fn get_gpu_mode(conn: &impl AsusWmi) -> Result<GpuModeInfo> {
+    require_laptop(conn, "GPU mode")?;
+    let mux = read_switch(conn, device_id::GPU_MUX).unwrap_or(None);
+    let dgpu_off = read_switch(conn, device_id::DGPU_DISABLE).unwrap_or(None);
+    if mux.is_none() && dgpu_off.is_none() {
+        return Err(NoCrateError::Wmi(
+            "GPU MUX / dGPU switching not supported by this laptop".into(),
+        ));
+    }
+
+    let mode = if mux == Some(false) {
+        GpuMode::Ultimate
+    } else if dgpu_off == Some(true) {
+        GpuMode::Eco
+    } else {
+        GpuMode::Standard
+    };
+    Ok(GpuModeInfo {
+        mode,
+        mux_supported: mux.is_some(),
+        eco_supported: dgpu_off.is_some(),
+    })
+}
+
+/// Switch the GPU mode.
+///
+/// Leaving or entering Ultimate flips the MUX, which needs a reboot. The
+/// dGPU cannot be powered off while it drives the panel, so Eco is only
+/// reachable from Standard once the MUX is back in hybrid mode.
+pub fn set_gpu_mode(conn: &impl AsusWmi, mode: GpuMode) -> Result<GpuModeChange> {
+    let current = get_gpu_mode(conn)?;
+    if current.mode == mode {
+        return Ok(GpuModeChange {
+            reboot_required: false,
+        });
+    }
+
+    match mode {
+        GpuMode::Ultimate => {
+            if !current.mux_supported {
+                return Err(NoCrateError::Wmi(
+                    "GPU MUX not supported by this laptop".into(),
+                ));
+            }
+            // The MUX switch is refused while the dGPU is off
+            if current.mode == GpuMode::Eco {
+                write_switch(conn, device_id::DGPU_DISABLE, false, "dGPU disable")?;
+            }
+            write_switch(conn, device_id::GPU_MUX, false, "GPU MUX")?;
+            Ok(GpuModeChange {
+                reboot_required: true,
+            })
+        }
+        GpuMode::Standard => {
+            if current.mode == GpuMode::Ultimate {
+                write_switch(conn, device_id::GPU_MUX, true, "GPU MUX")?;
+                return Ok(GpuModeChange {
+                    reboot_required: true,
+                });
+            }
+            write_switch(conn, device_id::DGPU_DISABLE, false, "dGPU disable")?;
+            Ok(GpuModeChange {
+                reboot_required: false,
+            })
+        }
+        GpuMode::Eco => {
+            if !current.eco_supported {
+                return Err(NoCrateError::Wmi(
+                    "dGPU disable not supported by this laptop".into(),
+                ));
+            }
+            if current.mode == GpuMode::Ultimate {
+                return Err(NoCrateError::Wmi(
+                    "Switch to Standard and reboot before enabling Eco mode".into(),
+                ));
+            }
+            write_switch(conn, device_id::DGPU_DISABLE, true, "dGPU disable")?;
+            Ok(GpuModeChange {
+                reboot_required: false,
+            })
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ASUSHW Sensor types & helpers
+// ---------------------------------------------------------------------------
+
+/// A single sensor reading from the ASUSHW backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsusHWSensor {
+    /// Zero-based sensor index.
+    pub index: u32,
+    /// Human-readable name (e.g. "CPU Temperature", "CPU Fan").
+    pub name: String,
+    /// `"temperature"` (°C) or `"fan"` (RPM).
+    pub sensor_type: String,
+    /// Current value (°C for temps, RPM for fans).
+    pub value: f32,
+    /// Internal source group ID (for `sensor_update_buffer`).
+    pub source: u32,
+    /// Internal data-type flag (3 = micro-units).
+    pub data_type: u32,
+}
+
+/// Discover all sensors from the ASUSHW backend.
+///
+/// Enumerates sensors via `sensor_get_number` / `sensor_get_info`,
+/// updates buffers, and reads current values. Pollers should use
+/// [`AsusHwCache`] instead, which enumerates only once.
+pub fn get_asushw_sensors(conn: &impl AsusWmi) -> Vec<AsusHWSensor> {
+    let mut sensors = enumerate_asushw_sensors(conn);
+    let sources: HashSet<u32> = sensors.iter().map(|s| s.source).collect();
+    for &src in &sources {
+        update_asushw_buffer(conn, src);
+    }
+    read_asushw_values(conn, &mut sensors);
+    sensors
+}
+
+/// Enumerate the temperature and fan sensors, with their values left at 0.
+fn enumerate_asushw_sensors(conn: &impl AsusWmi) -> Vec<AsusHWSensor> {
+    let count = match conn.asushw_sensor_count() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("[ASUSHW] sensor_get_number failed: {e}");
+            return vec![];
+        }
+    };
+    eprintln!("[ASUSHW] Found {count} sensors");
+
+    let mut sensors = Vec::new();
+    for i in 0..count {
+        match conn.asushw_sensor_info(i) {
+            Ok((source, stype, data_type, name)) => {
+                let type_str = match stype {
+                    1 => "temperature",
+                    2 => "fan",
+                    _ => continue, // skip unknown types
+                };
+                sensors.push(AsusHWSensor {
+                    index: i,
+                    name,
+                    sensor_type: type_str.to_string(),
+                    value: 0.0,
+                    source,
+                    data_type,
+                });
+            }
+            Err(e) => eprintln!("[ASUSHW] sensor_get_info({i}) failed: {e}"),
+        }
+    }
+    sensors
+}
+
+/// Ask the firmware to refresh the values of one source group.
+fn update_asushw_buffer(conn: &impl AsusWmi, source: u32) {
+    if let Err(e) = conn.asushw_update_buffer(source) {
+        eprintln!("[ASUSHW] sensor_update_buffer({source}) failed: {e}");
+    }
+}
+
+/// Read the buffered value of every sensor.
+fn read_asushw_values(conn: &impl AsusWmi, sensors: &mut [AsusHWSensor]) {
+    for sensor in sensors {
+        match conn.asushw_sensor_value(sensor.index) {
+            Ok(raw) => {
+                sensor.value = if sensor.data_type == 3 {
+                    // Micro-units (e.g. 60_000_000 → 60.0°C)
+                    raw as f32 / 1_000_000.0
+                } else {
+                    raw as f32
+                };
+            }
+            Err(e) => {
+                eprintln!("[ASUSHW] sensor_get_value({}) failed: {e}", sensor.index);
+            }
+        }
+    }
+}
+
+/// Sensor metadata and buffer refresh times shared by the ASUSHW pollers.
+///
+/// Enumerating the sensors costs two WMI calls per sensor and does not
+/// change while the backend is up, so it is done once. Each source buffer
+/// is refreshed at most once per poll interval; calls in between only read
+/// the buffered values.
+#[derive(Default)]
+pub struct AsusHwCache {
+    inner: Mutex<AsusHwCacheInner>,
+}
+
+#[derive(Default)]
+struct AsusHwCacheInner {
+    /// `None` until the sensors have been enumerated.
+    sensors: Option<Vec<AsusHWSensor>>,
+    /// Last `sensor_update_buffer` call per source group.
+    updated: HashMap<u32, Instant>,
+}
+
+impl AsusHwCache {
+    /// Read all ASUSHW sensors, refreshing source buffers older than
+    /// `interval`.
+    pub fn read(&self, conn: &impl AsusWmi, interval: Duration) -> Vec<AsusHWSensor> {
+        let mut inner = self.inner.lock();
+        let AsusHwCacheInner { sensors, updated } = &mut *inner;
+        if sensors.is_none() {
+            let found = enumerate_asushw_sensors(conn);
+            // Enumeration failed or the backend is still starting: retry on
+            // the next call instead of caching an empty list.
+            if found.is_empty() {
+                return found;
+            }
+            *sensors = Some(found);
+        }
+        let Some(sensors) = sensors.as_mut() else {
+            return Vec::new();
+        };
+
+        let now = Instant::now();
+        let sources: HashSet<u32> = sensors.iter().map(|s| s.source).collect();
+        for src in sources {
+            let due = updated
+                .get(&src)
+                .is_none_or(|last| now.duration_since(*last) >= interval);
+            if due {
+                update_asushw_buffer(conn, src);
+                let _ = updated.insert(src, now);
+            }
+        }
+        read_asushw_values(conn, sensors);
+        let sensors = sensors.clone();
+        drop(inner);
+        sensors
+    }
+
+    /// Forget the cached sensors, e.g. after the WMI connection was
+    /// recreated.
+    pub fn invalidate(&self) {
+        *self.inner.lock() = AsusHwCacheInner::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::curve::CurveViolation;
+    use crate::wmi::connection::MethodOutput;
+    use crate::wmi::sim::SimValue;
+
+    /// In-memory board: DSTS answers from a table, method calls are
+    /// recorded with their parameters rendered as strings.
+    struct FakeWmi {
+        backend: AsusWmiBackend,
+        dsts: HashMap<u32, u32>,
+        calls: RefCell<Vec<(String, Vec<(String, String)>)>>,
+    }
+
+    impl FakeWmi {
+        fn new(dsts: &[(u32, u32)]) -> Self {
+            Self {
+                backend: AsusWmiBackend::Desktop {
+                    instance_path: "ASUSManagement.InstanceName='FAKE'".into(),
+                },
+                dsts: dsts.iter().copied().collect(),
+                calls: RefCell::default(),
+            }
+        }
+    }
+
+    impl AsusWmi for FakeWmi {
+        fn backend(&self) -> &AsusWmiBackend {
+            &self.backend
+        }
+
+        fn dsts(&self, device_id: u32) -> Result<u32> {
+            self.dsts
+                .get(&device_id)
+                .copied()
+                .ok_or_else(|| NoCrateError::Wmi(format!("no device 0x{device_id:08X}")))
+        }
+
+        fn devs(&self, _device_id: u32, _control: u32) -> Result<u32> {
+            Ok(1)
+        }
+
+        fn exec_method_v2(
+            &self,
+            _object_path: &str,
+            method_name: &str,
+            params: &[(&str, WmiParam<'_>)],
+        ) -> Result<MethodOutput> {
+            let params = params
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        WmiParam::U8(v) => v.to_string(),
+                        WmiParam::U32(v) => v.to_string(),
+                        WmiParam::Str(s) => (*s).to_string(),
+                    };
+                    ((*name).to_string(), value)
+                })
+                .collect();
+            self.calls
+                .borrow_mut()
+                .push((method_name.to_string(), params));
+            Ok(MethodOutput::Simulated(HashMap::from([(
+                "ErrorCode".to_string(),
+                SimValue::U32(0),
+            )])))
+        }
+
+        fn method_param_values(
+            &self,
+            _object_path: &str,
+            _method_name: &str,
+            _param: &str,
+        ) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn point(temp_c: u8, duty_pct: u8) -> FanCurvePoint {
+        FanCurvePoint { temp_c, duty_pct }
+    }
+
+    #[test]
+    fn fan_status_decodes_speeds_and_profile() {
+        let conn = FakeWmi::new(&[
+            // 1200 RPM with the presence flag
+            (device_id::CPU_FAN_SPEED, 0x0001_04B0),
+            // 0 RPM, but the firmware reports a fan on the header
+            (device_id::MID_FAN_SPEED, FAN_PRESENT_BIT),
+            (device_id::THROTTLE_THERMAL_POLICY, 0x0001_0002),
+        ]);
+
+        let status = get_fan_status(&conn).unwrap();
+
+        assert_eq!(status.profile, ThermalProfile::Silent);
+        let fans: Vec<_> = status
+            .fans
+            .iter()
+            .map(|f| (f.target, f.rpm, f.status))
+            .collect();
+        assert_eq!(
+            fans,
+            [
+                (FanTarget::Cpu, 1200, FanPresence::Spinning),
+                (FanTarget::Mid, 0, FanPresence::Stopped),
+            ]
+        );
+    }
+
+    #[test]
+    fn fan_status_rejects_unknown_profile() {
+        let conn = FakeWmi::new(&[(device_id::THROTTLE_THERMAL_POLICY, 0x07)]);
+        assert!(matches!(get_fan_status(&conn), Err(NoCrateError::Wmi(_))));
+    }
+
+    #[test]
+    fn laptop_curve_buffer_round_trips() {
+        let curve = FanCurve::default_for(FanTarget::Gpu);
+        let buf = curve.to_buffer();
+
+        assert_eq!(buf[..FAN_CURVE_POINTS], [30, 40, 50, 60, 70, 75, 80, 90]);
+        assert_eq!(buf[FAN_CURVE_POINTS..], [30, 35, 45, 55, 65, 75, 85, 100]);
+
+        let parsed = FanCurve::from_buffer(FanTarget::Gpu, &buf).unwrap();
+        assert_eq!(parsed.to_buffer(), buf);
+    }
+
+    #[test]
+    fn laptop_curve_buffer_clamps_duty_and_detects_missing_curve() {
+        assert!(FanCurve::from_buffer(FanTarget::Cpu, &[0; 16]).is_none());
+
+        let mut buf = FanCurve::default_for(FanTarget::Cpu).to_buffer();
+        buf[15] = 0xFF;
+        let parsed = FanCurve::from_buffer(FanTarget::Cpu, &buf).unwrap();
+        assert_eq!(parsed.points[7].duty_pct, 100);
+    }
+
+    #[test]
+    fn curve_validation_reports_broken_points() {
+        assert!(FanCurve::default_for(FanTarget::Cpu).validate().is_ok());
+
+        let mut curve = FanCurve::default_for(FanTarget::Cpu);
+        curve.points[3] = point(45, 55);
+        match curve.validate() {
+            Err(NoCrateError::InvalidCurve(found)) => assert_eq!(
+                found,
+                [CurveViolation::TempNotAscending {
+                    index: 3,
+                    temp_c: 45,
+                    prev_temp_c: 50,
+                }]
+            ),
+            other => panic!("expected InvalidCurve, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn desktop_curve_is_sent_point_by_point() {
+        let conn = FakeWmi::new(&[]);
+        let curve = DesktopFanCurve {
+            fan_type: 2,
+            mode: DesktopFanMode::Pwm,
+            points: FanCurve::default_for(FanTarget::Cpu).points,
+        };
+
+        set_desktop_fan_curve_pro(&conn, &curve).unwrap();
+
+        let calls = conn.calls.borrow();
+        let [(method, params)] = calls.as_slice() else {
+            panic!("expected one call, got {calls:?}");
+        };
+        assert_eq!(method, "SetManualFanCurvePro");
+        assert_eq!(params.len(), 2 + FAN_CURVE_POINTS * 2);
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(param("FanType"), Some("2"));
+        assert_eq!(param("Mode"), Some("PWM"));
+        assert_eq!(param("Point1Temp"), Some("30"));
+        assert_eq!(param("Point1Duty"), Some("30"));
+        assert_eq!(param("Point8Temp"), Some("90"));
+        assert_eq!(param("Point8Duty"), Some("100"));
+    }
+
+    #[test]
+    fn invalid_desktop_curve_is_not_sent() {
+        let conn = FakeWmi::new(&[]);
+        let mut points = FanCurve::default_for(FanTarget::Cpu).points;
+        points[0].duty_pct = 120;
+        let curve = DesktopFanCurve {
+            fan_type: 0,
+            mode: DesktopFanMode::Dc,
+            points,
+        };
+
+        assert!(matches!(
+            set_desktop_fan_curve_pro(&conn, &curve),
+            Err(NoCrateError::InvalidCurve(_))
+        ));
+        assert!(conn.calls.borrow().is_empty());
+    }
+}
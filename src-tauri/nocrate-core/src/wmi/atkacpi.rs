@@ -0,0 +1,122 @@
+/// ASUS ATKACPI driver channel.
+///
+/// The WMI `DSTS` / `DEVS` methods only carry 32-bit values, but some
+/// laptop device IDs (fan curves) read and write byte buffers. The ATK
+/// ACPI driver installed with ASUS System Control Interface exposes the
+/// same methods through a `DeviceIoControl` call that takes and returns
+/// raw buffers, which is what Armoury Crate and g-helper use.
+///
+/// Input layout: `method_id: u32 | args_len: u32 | args`.
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, GENERIC_READ, GENERIC_WRITE, HANDLE};
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_ATTRIBUTE_NORMAL, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+
+use crate::error::{NoCrateError, Result};
+
+const DEVICE_PATH: &str = r"\\.\ATKACPI";
+
+/// `CTL_CODE(0x22, 0x903, METHOD_BUFFERED, FILE_ANY_ACCESS)`
+const IOCTL_ATK_ACPI_CALL: u32 = 0x0022_240C;
+
+/// ASCII `DSTS`, little-endian.
+const METHOD_DSTS: u32 = 0x5354_5344;
+/// ASCII `DEVS`, little-endian.
+const METHOD_DEVS: u32 = 0x5356_4544;
+
+/// Size of the output buffer returned by the driver.
+const OUTPUT_LEN: u32 = 16;
+
+/// Open handle to the ATKACPI driver, closed on drop.
+pub struct AtkAcpi(HANDLE);
+
+#[allow(unsafe_code)]
+impl AtkAcpi {
+    /// Open the driver.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ASUS System Control Interface driver is
+    /// not installed.
+    pub fn open() -> Result<Self> {
+        let path = to_wide(DEVICE_PATH);
+        unsafe {
+            let handle = CreateFileW(
+                PCWSTR(path.as_ptr()),
+                GENERIC_READ.0 | GENERIC_WRITE.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_ATTRIBUTE_NORMAL,
+                None,
+            )
+            .map_err(|e| {
+                NoCrateError::classify(
+                    &e,
+                    format!("Cannot open ATKACPI driver: {e}"),
+                    NoCrateError::DeviceNotPresent,
+                )
+            })?;
+            Ok(Self(handle))
+        }
+    }
+
+    /// `DSTS(device_id, arg)` returning the raw output buffer.
+    pub fn dsts_buffer(&self, device_id: u32, arg: u32) -> Result<[u8; OUTPUT_LEN as usize]> {
+        let mut args = Vec::with_capacity(8);
+        args.extend_from_slice(&device_id.to_le_bytes());
+        args.extend_from_slice(&arg.to_le_bytes());
+        self.call(METHOD_DSTS, &args)
+    }
+
+    /// `DEVS(device_id, params)` returning the status word.
+    pub fn devs_buffer(&self, device_id: u32, params: &[u8]) -> Result<u32> {
+        let mut args = Vec::with_capacity(4 + params.len());
+        args.extend_from_slice(&device_id.to_le_bytes());
+        args.extend_from_slice(params);
+        let out = self.call(METHOD_DEVS, &args)?;
+        Ok(u32::from_le_bytes([out[0], out[1], out[2], out[3]]))
+    }
+
+    fn call(&self, method: u32, args: &[u8]) -> Result<[u8; OUTPUT_LEN as usize]> {
+        let args_len = u32::try_from(args.len())
+            .map_err(|_| NoCrateError::Wmi("ATKACPI arguments too long".into()))?;
+        let mut input = Vec::with_capacity(8 + args.len());
+        input.extend_from_slice(&method.to_le_bytes());
+        input.extend_from_slice(&args_len.to_le_bytes());
+        input.extend_from_slice(args);
+
+        let mut output = [0u8; OUTPUT_LEN as usize];
+        let mut bytes_returned: u32 = 0;
+        unsafe {
+            DeviceIoControl(
+                self.0,
+                IOCTL_ATK_ACPI_CALL,
+                Some(input.as_ptr().cast()),
+                args_len + 8,
+                Some(output.as_mut_ptr().cast()),
+                OUTPUT_LEN,
+                Some(&raw mut bytes_returned),
+                None,
+            )
+            .map_err(|e| NoCrateError::Wmi(format!("ATKACPI call 0x{method:08X} failed: {e}")))?;
+        }
+        Ok(output)
+    }
+}
+
+#[allow(unsafe_code)]
+impl Drop for AtkAcpi {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseHandle(self.0);
+        }
+    }
+}
+
+/// Convert a &str to a null-terminated wide string.
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
@@ -16,19 +16,37 @@
 /// - **ASUSHW** (`ASUSHW`): Sensor-based backend providing read-only access
 ///   to temperature and fan RPM data via `sensor_get_*` methods.
 ///   Used as fallback when `ASUSManagement` is unavailable.
-use windows::core::BSTR;
+///
+/// With [`WmiConnection::simulated`] the methods are answered by the
+/// in-memory board in [`sim`](crate::wmi::sim) instead of COM, and with
+/// [`WmiConnection::remote`] they are forwarded to another process, such
+/// as the privileged `NoCrate` service, that owns the real connection.
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use windows::core::{w, BSTR, HSTRING, PCWSTR};
 use windows::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CoInitializeSecurity, CoSetProxyBlanket, CoUninitialize,
     CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, EOAC_NONE, RPC_C_AUTHN_LEVEL_CALL,
     RPC_C_IMP_LEVEL_IMPERSONATE,
 };
-use windows::Win32::System::Variant::{VariantChangeType, VARIANT, VAR_CHANGE_FLAGS, VT_I4};
+use windows::Win32::System::Ole::{
+    SafeArrayDestroy, SafeArrayGetElement, SafeArrayGetLBound, SafeArrayGetUBound,
+};
+use windows::Win32::System::Variant::{
+    VariantChangeType, VARENUM, VARIANT, VAR_CHANGE_FLAGS, VT_ARRAY, VT_BSTR, VT_I4,
+};
 use windows::Win32::System::Wmi::{
-    IWbemClassObject, IWbemLocator, IWbemServices, WbemLocator, WBEM_FLAG_FORWARD_ONLY,
-    WBEM_FLAG_RETURN_IMMEDIATELY, WBEM_FLAG_RETURN_WBEM_COMPLETE,
+    IWbemClassObject, IWbemLocator, IWbemObjectSink, IWbemServices, WbemLocator,
+    WBEM_FLAG_FORWARD_ONLY, WBEM_FLAG_NONSYSTEM_ONLY, WBEM_FLAG_RETURN_IMMEDIATELY,
+    WBEM_FLAG_RETURN_WBEM_COMPLETE, WBEM_GENERIC_FLAG_TYPE,
 };
 
 use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::wmi::asio_hw;
+use crate::wmi::sim::{SimValue, SimWmi};
 
 /// A typed WMI method parameter value.
 ///
@@ -44,7 +62,7 @@ pub enum WmiParam<'a> {
 }
 
 /// Detected ASUS WMI backend variant.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AsusWmiBackend {
     /// Laptop: class `ASUSATKWMI_WMNB`, methods `DSTS`/`DEVS`.
     Laptop { instance_path: String },
@@ -76,17 +94,202 @@ impl AsusWmiBackend {
     }
 }
 
+/// Output parameters of a WMI method call.
+pub enum MethodOutput {
+    /// Object returned by `ExecMethod`.
+    Com(IWbemClassObject),
+    /// Values returned by the simulated board.
+    Simulated(HashMap<String, SimValue>),
+}
+
+impl MethodOutput {
+    /// Read a numeric output parameter.
+    pub fn get_u32(&self, name: &str) -> Result<u32> {
+        match self {
+            Self::Com(obj) => WmiConnection::get_property_u32(obj, name),
+            Self::Simulated(values) => match values.get(name) {
+                Some(SimValue::U32(v)) => Ok(*v),
+                _ => Err(NoCrateError::Wmi(format!(
+                    "Property {name} is not a number"
+                ))),
+            },
+        }
+    }
+
+    /// Read a string output parameter.
+    pub fn get_string(&self, name: &str) -> Result<String> {
+        match self {
+            Self::Com(obj) => WmiConnection::get_property_string(obj, name),
+            Self::Simulated(values) => match values.get(name) {
+                Some(SimValue::Str(s)) => Ok(s.clone()),
+                _ => Err(NoCrateError::Wmi(format!(
+                    "Property {name} is not a string value"
+                ))),
+            },
+        }
+    }
+
+    /// Every string and numeric output parameter, e.g. to record the call
+    /// in a [`trace`](crate::wmi::trace). Parameters of other types are
+    /// left out.
+    #[allow(unsafe_code)]
+    pub fn values(&self) -> Result<HashMap<String, SimValue>> {
+        let obj = match self {
+            Self::Com(obj) => obj,
+            Self::Simulated(values) => return Ok(values.clone()),
+        };
+        unsafe {
+            let names = obj.GetNames(PCWSTR::null(), WBEM_FLAG_NONSYSTEM_ONLY, std::ptr::null())?;
+            let lower = SafeArrayGetLBound(names, 1)?;
+            let upper = SafeArrayGetUBound(names, 1)?;
+            let mut values = HashMap::new();
+            for index in lower..=upper {
+                let mut name = BSTR::default();
+                SafeArrayGetElement(names, &raw const index, (&raw mut name).cast())?;
+                let name = name.to_string();
+
+                let mut val = VARIANT::default();
+                obj.Get(&BSTR::from(name.as_str()), 0, &raw mut val, None, None)?;
+                // 按原始类型区分，避免数字被转成字符串或反之
+                let value = if val.Anonymous.Anonymous.vt == VT_BSTR {
+                    WmiConnection::get_property_string(obj, &name).map(SimValue::Str)
+                } else {
+                    WmiConnection::get_property_u32(obj, &name).map(SimValue::U32)
+                };
+                if let Ok(value) = value {
+                    let _ = values.insert(name, value);
+                }
+            }
+            SafeArrayDestroy(names)?;
+            Ok(values)
+        }
+    }
+}
+
+/// The ASUS WMI calls the typed helpers in
+/// [`asus_mgmt`](crate::wmi::asus_mgmt) and [`ppt`](crate::wmi::ppt) are
+/// built on.
+///
+/// Implemented by [`WmiConnection`] and by the in-memory [`SimWmi`], so
+/// code written against `&impl AsusWmi` can be exercised off-hardware.
+pub trait AsusWmi {
+    /// The detected backend.
+    fn backend(&self) -> &AsusWmiBackend;
+
+    /// Read a device status value, see [`WmiConnection::dsts`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` if the call fails or the backend has no device IDs.
+    fn dsts(&self, device_id: u32) -> Result<u32>;
+
+    /// Write a device control value, see [`WmiConnection::devs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` if the call fails or the backend has no device IDs.
+    fn devs(&self, device_id: u32, control: u32) -> Result<u32>;
+
+    /// Execute a method with mixed-type parameters, see
+    /// [`WmiConnection::exec_method_v2`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` if the method does not exist or the call fails.
+    fn exec_method_v2(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<MethodOutput>;
+
+    /// Names a method parameter accepts, see
+    /// [`WmiConnection::method_param_values`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` if the parameter declares no values.
+    fn method_param_values(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        param: &str,
+    ) -> Result<Vec<String>>;
+
+    /// Code `ASUSManagement.GetLastError` reports for the previous call,
+    /// see [`WmiConnection::last_error`]. `None` by default.
+    fn last_error(&self) -> Option<u32> {
+        None
+    }
+
+    /// Number of ASUSHW sensors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` unless the backend is ASUSHW.
+    fn asushw_sensor_count(&self) -> Result<u32> {
+        Err(wrong_backend("ASUSHW"))
+    }
+
+    /// `(source, sensor_type, data_type, name)` of an ASUSHW sensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` unless the backend is ASUSHW.
+    fn asushw_sensor_info(&self, _index: u32) -> Result<(u32, u32, u32, String)> {
+        Err(wrong_backend("ASUSHW"))
+    }
+
+    /// Refresh the ASUSHW sensor buffer of a source group.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` unless the backend is ASUSHW.
+    fn asushw_update_buffer(&self, _source: u32) -> Result<()> {
+        Err(wrong_backend("ASUSHW"))
+    }
+
+    /// Current value of an ASUSHW sensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` unless the backend is ASUSHW.
+    fn asushw_sensor_value(&self, _index: u32) -> Result<u32> {
+        Err(wrong_backend("ASUSHW"))
+    }
+}
+
+/// Error for a call that needs another backend.
+fn wrong_backend(backend: &str) -> NoCrateError {
+    NoCrateError::Wmi(i18n::tf(Msg::WmiWrongBackend, &[("backend", &backend)]))
+}
+
+/// Where method calls go.
+enum Transport {
+    Com {
+        services: IWbemServices,
+        /// Kept to connect to the LHM namespace after startup.
+        locator: IWbemLocator,
+        /// Optional connection to `root\LibreHardwareMonitor` namespace.
+        /// `None` if LHM is not installed or not running.
+        lhm_services: RefCell<Option<IWbemServices>>,
+    },
+    Simulated(SimWmi),
+    /// Method calls are forwarded to a connection in another process.
+    Remote(Box<dyn AsusWmi>),
+}
+
 /// RAII wrapper around a WMI connection to `root\WMI`.
 ///
 /// COM is initialized on construction and cleaned up on drop.
 /// This struct is **not** `Send`/`Sync` — it must live on the thread
 /// that created it. Use the `WmiThread` helper for cross-thread access.
 pub struct WmiConnection {
-    services: IWbemServices,
+    transport: Transport,
     pub backend: AsusWmiBackend,
-    /// Optional connection to `root\LibreHardwareMonitor` namespace.
-    /// `None` if LHM is not installed or not running.
-    lhm_services: Option<IWbemServices>,
+    /// Device IDs known to misbehave on this board (see `board::quirks_for`);
+    /// `dsts` / `devs` reject them without calling into WMI.
+    pub blocked_device_ids: &'static [u32],
 }
 
 impl WmiConnection {
@@ -168,16 +371,70 @@ impl WmiConnection {
             eprintln!("[WMI] Backend detected: {}", backend.label());
 
             // Try to connect to LHM namespace (non-fatal)
-            let lhm_services = Self::try_connect_lhm(&locator);
+            let lhm_services = RefCell::new(Self::try_connect_lhm(&locator));
 
             Ok(Self {
-                services,
+                transport: Transport::Com {
+                    services,
+                    locator,
+                    lhm_services,
+                },
                 backend,
-                lhm_services,
+                blocked_device_ids: &[],
             })
         }
     }
 
+    /// A connection to a simulated desktop board, for development and
+    /// tests without ASUS hardware. Does not touch COM.
+    #[must_use]
+    pub fn simulated() -> Self {
+        eprintln!("[WMI] Simulation mode, using a fake ASUSManagement board");
+        let sim = SimWmi::new();
+        Self {
+            backend: sim.backend().clone(),
+            transport: Transport::Simulated(sim),
+            blocked_device_ids: &[],
+        }
+    }
+
+    /// A connection whose method calls go through `remote`, which has
+    /// the real connection in another process. Does not touch COM, so
+    /// event subscriptions and the LHM namespace are not available.
+    #[must_use]
+    pub fn remote(remote: Box<dyn AsusWmi>) -> Self {
+        Self {
+            backend: remote.backend().clone(),
+            transport: Transport::Remote(remote),
+            blocked_device_ids: &[],
+        }
+    }
+
+    /// Whether this is a [`simulated`](Self::simulated) connection.
+    #[must_use]
+    pub const fn is_simulated(&self) -> bool {
+        matches!(self.transport, Transport::Simulated(_))
+    }
+
+    /// Whether this is a [`remote`](Self::remote) connection.
+    #[must_use]
+    pub const fn is_remote(&self) -> bool {
+        matches!(self.transport, Transport::Remote(_))
+    }
+
+    /// The `root\WMI` services, unless simulated or remote.
+    fn services(&self) -> Result<&IWbemServices> {
+        match &self.transport {
+            Transport::Com { services, .. } => Ok(services),
+            Transport::Simulated(_) => {
+                Err(NoCrateError::Wmi("Not available in simulation mode".into()))
+            }
+            Transport::Remote(_) => Err(NoCrateError::Wmi(
+                "Not available through the NoCrate service".into(),
+            )),
+        }
+    }
+
     /// Probe available ASUS WMI classes and return the first working backend.
     ///
     /// Detection order:
@@ -186,6 +443,9 @@ impl WmiConnection {
     /// 3. `ASUSHW` (ASUS hardware sensor monitoring — read-only)
     #[allow(unsafe_code)]
     unsafe fn detect_backend(services: &IWbemServices) -> Result<AsusWmiBackend> {
+        // Reported instead of "not found" if a probe was refused
+        let mut denied = None;
+
         // 1. Try desktop: ASUSManagement (enumerate instances)
         eprintln!("[WMI] Probing ASUSManagement …");
         match Self::find_first_instance(services, "ASUSManagement") {
@@ -195,7 +455,12 @@ impl WmiConnection {
                     instance_path: path,
                 });
             }
-            Err(e) => eprintln!("[WMI]   ✗ ASUSManagement: {e}"),
+            Err(e) => {
+                eprintln!("[WMI]   ✗ ASUSManagement: {e}");
+                if matches!(e, NoCrateError::AccessDenied(_)) {
+                    denied = Some(e);
+                }
+            }
         }
 
         // 2. Try laptop: ASUSATKWMI_WMNB with common instance path
@@ -240,11 +505,19 @@ impl WmiConnection {
                     instance_path: path,
                 });
             }
-            Err(e) => eprintln!("[WMI]   ✗ ASUSHW: {e}"),
+            Err(e) => {
+                eprintln!("[WMI]   ✗ ASUSHW: {e}");
+                if matches!(e, NoCrateError::AccessDenied(_)) {
+                    denied = Some(e);
+                }
+            }
         }
 
-        Err(NoCrateError::Wmi(
-            "未找到支持的 ASUS WMI 接口 (ASUSManagement / ASUSATKWMI_WMNB / ASUSHW)".into(),
+        if let Some(e) = denied {
+            return Err(e);
+        }
+        Err(NoCrateError::DeviceNotPresent(
+            i18n::t(Msg::WmiNoInterface).into(),
         ))
     }
 
@@ -264,7 +537,11 @@ impl WmiConnection {
                 None,
             )
             .map_err(|e| {
-                NoCrateError::Wmi(format!("CreateInstanceEnum failed for {class_name}: {e}"))
+                NoCrateError::classify(
+                    &e,
+                    format!("CreateInstanceEnum failed for {class_name}: {e}"),
+                    NoCrateError::Wmi,
+                )
             })?;
 
         let mut returned: u32 = 0;
@@ -293,7 +570,7 @@ impl WmiConnection {
     pub fn get_object(&self, path: &str) -> Result<IWbemClassObject> {
         unsafe {
             let mut obj = None;
-            self.services.GetObject(
+            self.services()?.GetObject(
                 &BSTR::from(path),
                 WBEM_FLAG_RETURN_WBEM_COMPLETE,
                 None,
@@ -304,59 +581,19 @@ impl WmiConnection {
         }
     }
 
-    /// Execute a WMI method on a given object path.
-    ///
-    /// 1. Gets the class definition
-    /// 2. Gets the method input parameter signature
-    /// 3. Spawns an instance and fills parameters
-    /// 4. Calls ExecMethod and returns the output object
-    #[allow(unsafe_code)]
+    /// Execute a WMI method on a given object path, see
+    /// [`exec_method_v2`](Self::exec_method_v2).
     pub fn exec_method(
         &self,
         object_path: &str,
         method_name: &str,
         params: &[(&str, u32)],
-    ) -> Result<IWbemClassObject> {
-        unsafe {
-            // GetMethod only works on class definitions, not instances.
-            // Extract the class name (everything before the first '.') so
-            // we can retrieve the class definition for the method signature.
-            let class_name = object_path.split('.').next().unwrap_or(object_path);
-            let class_obj = self.get_object(class_name)?;
-
-            // Get input parameter definition for the method
-            let mut in_params_def = None;
-            class_obj.GetMethod(&BSTR::from(method_name), 0, &mut in_params_def, &mut None)?;
-
-            let in_params = match in_params_def {
-                Some(def) => {
-                    let instance = def.SpawnInstance(0)?;
-                    // Fill in parameters
-                    for &(name, value) in params {
-                        let variant = VARIANT::from(i32::try_from(value).unwrap_or(value as i32));
-                        instance.Put(&BSTR::from(name), 0, &variant, 0)?;
-                    }
-                    Some(instance)
-                }
-                None => None,
-            };
-
-            // Execute the method
-            let mut out_params = None;
-            self.services.ExecMethod(
-                &BSTR::from(object_path),
-                &BSTR::from(method_name),
-                Default::default(),
-                None,
-                in_params.as_ref(),
-                Some(&mut out_params),
-                None,
-            )?;
-
-            out_params.ok_or_else(|| {
-                NoCrateError::Wmi(format!("ExecMethod returned no output for {method_name}"))
-            })
-        }
+    ) -> Result<MethodOutput> {
+        let params: Vec<_> = params
+            .iter()
+            .map(|&(name, value)| (name, WmiParam::U32(value)))
+            .collect();
+        self.exec_method_v2(object_path, method_name, &params)
     }
 
     // -----------------------------------------------------------------------
@@ -369,19 +606,21 @@ impl WmiConnection {
     /// - **Desktop**: calls `device_status(device_id)` → `ctrl_param`
     /// - **AsusHW**: not supported (sensor-only backend)
     pub fn dsts(&self, device_id: u32) -> Result<u32> {
+        self.check_device_id(device_id)?;
         match &self.backend {
             AsusWmiBackend::Laptop { instance_path } => {
                 let out = self.exec_method(instance_path, "DSTS", &[("Device_ID", device_id)])?;
-                Self::get_property_u32(&out, "Device_Status")
+                out.get_u32("Device_Status")
             }
             AsusWmiBackend::Desktop { instance_path } => {
                 let out =
                     self.exec_method(instance_path, "device_status", &[("device_id", device_id)])?;
-                Self::get_property_u32(&out, "ctrl_param")
+                out.get_u32("ctrl_param")
             }
-            AsusWmiBackend::AsusHW { .. } => Err(NoCrateError::Wmi(
-                "ASUSHW 后端不支持 device_status 操作".into(),
-            )),
+            AsusWmiBackend::AsusHW { .. } => Err(NoCrateError::Wmi(i18n::tf(
+                Msg::WmiAsusHwUnsupported,
+                &[("method", &"device_status")],
+            ))),
         }
     }
 
@@ -391,6 +630,7 @@ impl WmiConnection {
     /// - **Desktop**: calls `device_ctrl(device_id, ctrl_param)` → (void)
     /// - **AsusHW**: not supported (sensor-only backend)
     pub fn devs(&self, device_id: u32, control: u32) -> Result<u32> {
+        self.check_device_id(device_id)?;
         match &self.backend {
             AsusWmiBackend::Laptop { instance_path } => {
                 let out = self.exec_method(
@@ -398,7 +638,7 @@ impl WmiConnection {
                     "DEVS",
                     &[("Device_ID", device_id), ("Control_Status", control)],
                 )?;
-                Self::get_property_u32(&out, "Device_Status")
+                out.get_u32("Device_Status")
             }
             AsusWmiBackend::Desktop { instance_path } => {
                 // device_ctrl may not return a meaningful value
@@ -409,10 +649,43 @@ impl WmiConnection {
                 )?;
                 Ok(1) // Success sentinel (matching laptop convention)
             }
-            AsusWmiBackend::AsusHW { .. } => Err(NoCrateError::Wmi(
-                "ASUSHW 后端不支持 device_ctrl 操作".into(),
-            )),
+            AsusWmiBackend::AsusHW { .. } => Err(NoCrateError::Wmi(i18n::tf(
+                Msg::WmiAsusHwUnsupported,
+                &[("method", &"device_ctrl")],
+            ))),
+        }
+    }
+
+    /// Reject device IDs blocked by a board quirk.
+    fn check_device_id(&self, device_id: u32) -> Result<()> {
+        if self.blocked_device_ids.contains(&device_id) {
+            return Err(NoCrateError::Wmi(format!(
+                "Device ID 0x{device_id:08X} disabled on this board"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Subscribe `sink` to a WQL event query on `root\WMI`
+    /// (`ExecNotificationQueryAsync`). Events are delivered on COM worker
+    /// threads until the connection is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is rejected, e.g. the event class
+    /// does not exist on this machine.
+    #[allow(unsafe_code)]
+    pub fn subscribe_events(&self, query: &str, sink: &IWbemObjectSink) -> Result<()> {
+        unsafe {
+            self.services()?.ExecNotificationQueryAsync(
+                &BSTR::from("WQL"),
+                &BSTR::from(query),
+                WBEM_GENERIC_FLAG_TYPE(0),
+                None,
+                sink,
+            )?;
         }
+        Ok(())
     }
 
     /// Read a u32 value from a WMI class object property.
@@ -433,12 +706,16 @@ impl WmiConnection {
             // 对于其他数值类型 (VT_UI1, VT_I2, VT_UI2, VT_UI4 等)，
             // 使用 VariantChangeType 强制转换为 VT_I4
             let mut coerced = VARIANT::default();
-            VariantChangeType(&mut coerced, &val, VAR_CHANGE_FLAGS(0), VT_I4)
-                .map_err(|e| NoCrateError::Wmi(format!("属性 {name} 无法转换为数值类型: {e}")))?;
+            VariantChangeType(&mut coerced, &val, VAR_CHANGE_FLAGS(0), VT_I4).map_err(|e| {
+                NoCrateError::Wmi(i18n::tf(
+                    Msg::WmiPropertyConvert,
+                    &[("name", &name), ("error", &e)],
+                ))
+            })?;
 
-            let i4: i32 = (&coerced)
-                .try_into()
-                .map_err(|_| NoCrateError::Wmi(format!("属性 {name} 转换后仍非 i32")))?;
+            let i4: i32 = (&coerced).try_into().map_err(|_| {
+                NoCrateError::Wmi(i18n::tf(Msg::WmiPropertyNotI32, &[("name", &name)]))
+            })?;
             Ok(i4 as u32)
         }
     }
@@ -461,13 +738,40 @@ impl WmiConnection {
     ///
     /// Similar to [`exec_method`] but accepts [`WmiParam`] values
     /// supporting `u8`, `u32`, and string parameters.
-    #[allow(unsafe_code)]
+    ///
+    /// On the desktop backend a failed call is followed by
+    /// [`last_error`](Self::last_error), whose code is added to the error.
     pub fn exec_method_v2(
         &self,
         object_path: &str,
         method_name: &str,
         params: &[(&str, WmiParam<'_>)],
-    ) -> Result<IWbemClassObject> {
+    ) -> Result<MethodOutput> {
+        match &self.transport {
+            Transport::Simulated(sim) => return sim.exec(method_name, params),
+            // The remote side adds its own GetLastError code
+            Transport::Remote(remote) => {
+                return remote.exec_method_v2(object_path, method_name, params)
+            }
+            Transport::Com { .. } => {}
+        }
+        self.exec_com(object_path, method_name, params)
+            .map_err(|e| self.with_last_error(method_name, e))
+    }
+
+    /// Call a method through COM:
+    ///
+    /// 1. Gets the class definition
+    /// 2. Gets the method input parameter signature
+    /// 3. Spawns an instance and fills parameters
+    /// 4. Calls ExecMethod and returns the output object
+    #[allow(unsafe_code)]
+    fn exec_com(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<MethodOutput> {
         unsafe {
             let class_name = object_path.split('.').next().unwrap_or(object_path);
             let class_obj = self.get_object(class_name)?;
@@ -494,7 +798,7 @@ impl WmiConnection {
             };
 
             let mut out_params = None;
-            self.services.ExecMethod(
+            self.services()?.ExecMethod(
                 &BSTR::from(object_path),
                 &BSTR::from(method_name),
                 Default::default(),
@@ -504,12 +808,100 @@ impl WmiConnection {
                 None,
             )?;
 
-            out_params.ok_or_else(|| {
+            out_params.map(MethodOutput::Com).ok_or_else(|| {
                 NoCrateError::Wmi(format!("ExecMethod returned no output for {method_name}"))
             })
         }
     }
 
+    /// Code `ASUSManagement.GetLastError` reports for the previous call,
+    /// the firmware's reason for refusing it. `None` on other backends, if
+    /// the firmware has no such method, or if it reports no error.
+    pub fn last_error(&self) -> Option<u32> {
+        match &self.transport {
+            Transport::Simulated(_) => return None,
+            Transport::Remote(remote) => return remote.last_error(),
+            Transport::Com { .. } => {}
+        }
+        let path = self.desktop_path().ok()?;
+        let code = self
+            .exec_com(path, "GetLastError", &[])
+            .and_then(|out| out.get_u32("ErrorCode"))
+            .ok()?;
+        (code != 0).then_some(code)
+    }
+
+    /// Add the [`last_error`](Self::last_error) code to the error of a
+    /// failed `method_name` call. Errors with a more specific variant
+    /// (access denied, busy, ...) are returned unchanged.
+    fn with_last_error(&self, method_name: &str, err: NoCrateError) -> NoCrateError {
+        if !matches!(err, NoCrateError::Wmi(_) | NoCrateError::WindowsApi(_)) {
+            return err;
+        }
+        match self.last_error() {
+            Some(code) => NoCrateError::Wmi(format!(
+                "{method_name} failed: {err} (GetLastError 0x{code:08X})"
+            )),
+            None => err,
+        }
+    }
+
+    /// Names listed in the `Values` qualifier of a method's input
+    /// parameter, i.e. the values the firmware's MOF declares for an
+    /// enumerated string parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` if the method or parameter does not exist or the
+    /// parameter has no `Values` qualifier.
+    #[allow(unsafe_code)]
+    pub fn method_param_values(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        param: &str,
+    ) -> Result<Vec<String>> {
+        match &self.transport {
+            Transport::Simulated(sim) => {
+                return sim.method_param_values(object_path, method_name, param)
+            }
+            Transport::Remote(remote) => {
+                return remote.method_param_values(object_path, method_name, param)
+            }
+            Transport::Com { .. } => {}
+        }
+        unsafe {
+            let class_name = object_path.split('.').next().unwrap_or(object_path);
+            let class_obj = self.get_object(class_name)?;
+
+            let mut in_params_def = None;
+            class_obj.GetMethod(&BSTR::from(method_name), 0, &mut in_params_def, &mut None)?;
+            let in_params_def = in_params_def
+                .ok_or_else(|| NoCrateError::Wmi(format!("{method_name} has no parameters")))?;
+
+            let qualifiers = in_params_def.GetPropertyQualifierSet(&HSTRING::from(param))?;
+            let mut values = VARIANT::default();
+            qualifiers.Get(w!("Values"), 0, &raw mut values, std::ptr::null_mut())?;
+
+            let raw = &values.Anonymous.Anonymous;
+            if raw.vt != VARENUM(VT_ARRAY.0 | VT_BSTR.0) {
+                return Err(NoCrateError::Wmi(format!(
+                    "{method_name}.{param} declares no string values"
+                )));
+            }
+            let array = raw.Anonymous.parray;
+            let lower = SafeArrayGetLBound(array, 1)?;
+            let upper = SafeArrayGetUBound(array, 1)?;
+            let mut names = Vec::new();
+            for index in lower..=upper {
+                let mut name = BSTR::default();
+                SafeArrayGetElement(array, &raw const index, (&raw mut name).cast())?;
+                names.push(name.to_string());
+            }
+            Ok(names)
+        }
+    }
+
     // -----------------------------------------------------------------------
     // ASUSHW sensor helpers
     // -----------------------------------------------------------------------
@@ -518,7 +910,7 @@ impl WmiConnection {
     fn asushw_path(&self) -> Result<&str> {
         match &self.backend {
             AsusWmiBackend::AsusHW { instance_path } => Ok(instance_path),
-            _ => Err(NoCrateError::Wmi("当前后端不是 ASUSHW".into())),
+            _ => Err(wrong_backend("ASUSHW")),
         }
     }
 
@@ -527,14 +919,14 @@ impl WmiConnection {
     pub fn asushw_sensor_version(&self) -> Result<u32> {
         let path = self.asushw_path()?;
         let out = self.exec_method(path, "sensor_get_version", &[])?;
-        Self::get_property_u32(&out, "Data")
+        out.get_u32("Data")
     }
 
     /// Get the total number of ASUSHW sensors (`sensor_get_number`).
     pub fn asushw_sensor_count(&self) -> Result<u32> {
         let path = self.asushw_path()?;
         let out = self.exec_method(path, "sensor_get_number", &[])?;
-        Self::get_property_u32(&out, "Data")
+        out.get_u32("Data")
     }
 
     /// Get sensor info for a given index (`sensor_get_info`).
@@ -545,10 +937,10 @@ impl WmiConnection {
     pub fn asushw_sensor_info(&self, index: u32) -> Result<(u32, u32, u32, String)> {
         let path = self.asushw_path()?;
         let out = self.exec_method(path, "sensor_get_info", &[("Index", index)])?;
-        let source = Self::get_property_u32(&out, "Source")?;
-        let sensor_type = Self::get_property_u32(&out, "Type")?;
-        let data_type = Self::get_property_u32(&out, "Data_Type")?;
-        let name = Self::get_property_string(&out, "Name")?;
+        let source = out.get_u32("Source")?;
+        let sensor_type = out.get_u32("Type")?;
+        let data_type = out.get_u32("Data_Type")?;
+        let name = out.get_string("Name")?;
         Ok((source, sensor_type, data_type, name))
     }
 
@@ -563,7 +955,7 @@ impl WmiConnection {
     pub fn asushw_sensor_value(&self, index: u32) -> Result<u32> {
         let path = self.asushw_path()?;
         let out = self.exec_method(path, "sensor_get_value", &[("Index", index)])?;
-        Self::get_property_u32(&out, "Data")
+        out.get_u32("Data")
     }
 
     // -----------------------------------------------------------------------
@@ -574,7 +966,7 @@ impl WmiConnection {
     fn desktop_path(&self) -> Result<&str> {
         match &self.backend {
             AsusWmiBackend::Desktop { instance_path } => Ok(instance_path),
-            _ => Err(NoCrateError::Wmi("当前后端不是 Desktop".into())),
+            _ => Err(wrong_backend("Desktop")),
         }
     }
 
@@ -591,7 +983,7 @@ impl WmiConnection {
         let label = "fun07(wPort=0x2E)".to_string();
         let r = self
             .exec_method_v2(path, "asio_hw_fun07", &[("wPort", WmiParam::U32(0x2E))])
-            .and_then(|out| Self::get_property_u32(&out, "bData"));
+            .and_then(|out| out.get_u32("bData"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -599,7 +991,7 @@ impl WmiConnection {
         let label = "fun07(wPort=0x0295)".to_string();
         let r = self
             .exec_method_v2(path, "asio_hw_fun07", &[("wPort", WmiParam::U32(0x0295))])
-            .and_then(|out| Self::get_property_u32(&out, "bData"));
+            .and_then(|out| out.get_u32("bData"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -607,7 +999,7 @@ impl WmiConnection {
         let label = "fun07(wPort=0x61)".to_string();
         let r = self
             .exec_method_v2(path, "asio_hw_fun07", &[("wPort", WmiParam::U32(0x61))])
-            .and_then(|out| Self::get_property_u32(&out, "bData"));
+            .and_then(|out| out.get_u32("bData"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -620,7 +1012,7 @@ impl WmiConnection {
                 "asio_hw_fun21",
                 &[("Bank", WmiParam::U8(0)), ("Index", WmiParam::U8(0x4F))],
             )
-            .and_then(|out| Self::get_property_u32(&out, "Data"));
+            .and_then(|out| out.get_u32("Data"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -632,7 +1024,7 @@ impl WmiConnection {
                 "asio_hw_fun21",
                 &[("Bank", WmiParam::U8(0)), ("Index", WmiParam::U8(0x27))],
             )
-            .and_then(|out| Self::get_property_u32(&out, "Data"));
+            .and_then(|out| out.get_u32("Data"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -644,7 +1036,7 @@ impl WmiConnection {
                 "asio_hw_fun21",
                 &[("Bank", WmiParam::U8(4)), ("Index", WmiParam::U8(0xC0))],
             )
-            .and_then(|out| Self::get_property_u32(&out, "Data"));
+            .and_then(|out| out.get_u32("Data"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -655,7 +1047,7 @@ impl WmiConnection {
                 "asio_hw_fun21",
                 &[("Bank", WmiParam::U8(4)), ("Index", WmiParam::U8(0xC1))],
             )
-            .and_then(|out| Self::get_property_u32(&out, "Data"));
+            .and_then(|out| out.get_u32("Data"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -668,24 +1060,15 @@ impl WmiConnection {
                 "asio_hw_fun19",
                 &[("LDN", WmiParam::U8(0x0B)), ("Index", WmiParam::U8(0x20))],
             )
-            .and_then(|out| Self::get_property_u32(&out, "Data"));
+            .and_then(|out| out.get_u32("Data"));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
         // --- fun23: 批量读 Bank+Index ---
-        let label = "fun23('00,4F') [Vendor ID batch]".to_string();
-        let r = self
-            .exec_method_v2(
-                path,
-                "asio_hw_fun23",
-                &[("BankIndexArray", WmiParam::Str("00,4F"))],
-            )
-            .and_then(|out| Self::get_property_string(&out, "DataArray"))
-            .map(|s| {
-                eprintln!("[WMI-TEST] fun23 DataArray raw: '{s}'");
-                // 尝试解析返回的字符串
-                s.parse::<u32>().unwrap_or(0xDEAD)
-            });
+        // Vendor ID 与 SYSTIN 一次读取，应与上面 fun21 的结果一致
+        let label = "fun23('00,4F,00,27') [Vendor ID + SYSTIN batch]".to_string();
+        let r = asio_hw::read_hwm_batch(self, &[(0x00, 0x4F), (0x00, 0x27)])
+            .map(|data| u32::from_be_bytes([0, 0, data[0], data[1]]));
         eprintln!("[WMI-TEST] {label}: {:?}", r);
         results.push((label, r));
 
@@ -741,9 +1124,29 @@ impl WmiConnection {
         }
     }
 
-    /// Get a reference to the LHM `IWbemServices`, if available.
-    pub fn lhm_services(&self) -> Option<&IWbemServices> {
-        self.lhm_services.as_ref()
+    /// Whether the LHM namespace is connected.
+    pub fn lhm_connected(&self) -> bool {
+        match &self.transport {
+            Transport::Com { lhm_services, .. } => lhm_services.borrow().is_some(),
+            Transport::Simulated(_) | Transport::Remote(_) => false,
+        }
+    }
+
+    /// (Re)connect to the LHM namespace, e.g. after LibreHardwareMonitor
+    /// was started or restarted. Returns whether it is connected.
+    pub fn connect_lhm(&self) -> bool {
+        let Transport::Com {
+            locator,
+            lhm_services,
+            ..
+        } = &self.transport
+        else {
+            return false;
+        };
+        let svc = Self::try_connect_lhm(locator);
+        let connected = svc.is_some();
+        *lhm_services.borrow_mut() = svc;
+        connected
     }
 
     /// Execute a WQL query on the LHM namespace and iterate results.
@@ -756,10 +1159,11 @@ impl WmiConnection {
     /// Returns an error if LHM is not connected or the query fails.
     #[allow(unsafe_code)]
     pub fn lhm_query(&self, wql: &str) -> Result<Vec<IWbemClassObject>> {
-        let services = self
-            .lhm_services
-            .as_ref()
-            .ok_or_else(|| NoCrateError::Wmi("LibreHardwareMonitor 未连接".into()))?;
+        let services = match &self.transport {
+            Transport::Com { lhm_services, .. } => lhm_services.borrow().clone(),
+            Transport::Simulated(_) | Transport::Remote(_) => None,
+        }
+        .ok_or_else(|| NoCrateError::Wmi(i18n::t(Msg::WmiLhmNotConnected).into()))?;
 
         unsafe {
             let enumerator = services.ExecQuery(
@@ -787,9 +1191,65 @@ impl WmiConnection {
     }
 }
 
+impl AsusWmi for WmiConnection {
+    fn backend(&self) -> &AsusWmiBackend {
+        &self.backend
+    }
+
+    fn dsts(&self, device_id: u32) -> Result<u32> {
+        Self::dsts(self, device_id)
+    }
+
+    fn devs(&self, device_id: u32, control: u32) -> Result<u32> {
+        Self::devs(self, device_id, control)
+    }
+
+    fn exec_method_v2(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<MethodOutput> {
+        Self::exec_method_v2(self, object_path, method_name, params)
+    }
+
+    fn method_param_values(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        param: &str,
+    ) -> Result<Vec<String>> {
+        Self::method_param_values(self, object_path, method_name, param)
+    }
+
+    fn last_error(&self) -> Option<u32> {
+        Self::last_error(self)
+    }
+
+    fn asushw_sensor_count(&self) -> Result<u32> {
+        Self::asushw_sensor_count(self)
+    }
+
+    fn asushw_sensor_info(&self, index: u32) -> Result<(u32, u32, u32, String)> {
+        Self::asushw_sensor_info(self, index)
+    }
+
+    fn asushw_update_buffer(&self, source: u32) -> Result<()> {
+        Self::asushw_update_buffer(self, source)
+    }
+
+    fn asushw_sensor_value(&self, index: u32) -> Result<u32> {
+        Self::asushw_sensor_value(self, index)
+    }
+}
+
 impl Drop for WmiConnection {
     #[allow(unsafe_code)]
     fn drop(&mut self) {
+        // Only the COM transport initialized COM on this thread
+        if !matches!(self.transport, Transport::Com { .. }) {
+            return;
+        }
         unsafe {
             CoUninitialize();
         }
@@ -0,0 +1,146 @@
+/// ASUS ATK ACPI event subscription.
+///
+/// On laptops the ATK driver raises `AsusAtkWmiEvent` in `root\WMI` for
+/// hotkeys and platform notifications, with the notify code in `EventID`.
+/// Codes follow the Linux `asus-wmi` / `asus-nb-wmi` drivers. The firmware
+/// only reports the key press; switching the thermal profile for Fn+F5 is
+/// left to the application, as Armoury Crate does.
+use std::sync::mpsc;
+
+use serde::Serialize;
+use windows::Win32::System::Wmi::IWbemObjectSink;
+
+use super::connection::WmiConnection;
+use crate::error::Result;
+
+/// WQL query for all ATK events.
+const ATK_EVENT_QUERY: &str = "SELECT * FROM AsusAtkWmiEvent";
+
+/// Known `EventID` codes.
+mod code {
+    /// Fn+F5 fan mode key on older models (`NOTIFY_KBD_FBM`).
+    pub const FAN_BOOST_KEY: u32 = 0x99;
+    /// Fn+F5 thermal profile key (`NOTIFY_KBD_TTP`).
+    pub const PROFILE_KEY: u32 = 0xAE;
+    /// Switched to battery power.
+    pub const ON_BATTERY: u32 = 0x57;
+    /// Switched to AC power.
+    pub const ON_AC: u32 = 0x58;
+    /// Keyboard backlight up / down / toggle.
+    pub const KBD_BACKLIGHT_UP: u32 = 0xC4;
+    pub const KBD_BACKLIGHT_DOWN: u32 = 0xC5;
+    pub const KBD_BACKLIGHT_TOGGLE: u32 = 0xC7;
+    /// Lid flipped into / out of tablet mode (`NOTIFY_LID_FLIP`, ROG variant).
+    pub const LID_FLIP: u32 = 0xFA;
+    pub const LID_FLIP_ROG: u32 = 0xBD;
+    /// ROG / Armoury Crate key.
+    pub const ROG_KEY: u32 = 0x38;
+}
+
+/// Decoded ATK event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AtkEventKind {
+    /// Fn+F5: cycle the thermal profile.
+    ProfileKey,
+    OnBattery,
+    OnAc,
+    LidFlip,
+    KeyboardBacklightUp,
+    KeyboardBacklightDown,
+    KeyboardBacklightToggle,
+    RogKey,
+    /// Code not mapped above; see `code` of [`AtkEvent`].
+    Other,
+}
+
+/// An ATK event as forwarded to the frontend.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AtkEvent {
+    pub code: u32,
+    pub kind: AtkEventKind,
+}
+
+impl AtkEvent {
+    #[must_use]
+    pub const fn from_code(code: u32) -> Self {
+        let kind = match code {
+            code::PROFILE_KEY | code::FAN_BOOST_KEY => AtkEventKind::ProfileKey,
+            code::ON_BATTERY => AtkEventKind::OnBattery,
+            code::ON_AC => AtkEventKind::OnAc,
+            code::LID_FLIP | code::LID_FLIP_ROG => AtkEventKind::LidFlip,
+            code::KBD_BACKLIGHT_UP => AtkEventKind::KeyboardBacklightUp,
+            code::KBD_BACKLIGHT_DOWN => AtkEventKind::KeyboardBacklightDown,
+            code::KBD_BACKLIGHT_TOGGLE => AtkEventKind::KeyboardBacklightToggle,
+            code::ROG_KEY => AtkEventKind::RogKey,
+            _ => AtkEventKind::Other,
+        };
+        Self { code, kind }
+    }
+}
+
+// The `#[implement]` expansion needs these lints allowed, so the sink
+// lives in its own module
+#[allow(unsafe_code, clippy::inline_always, clippy::ref_as_ptr)]
+mod sink {
+    use std::sync::mpsc;
+
+    use windows::core::{implement, Ref, BSTR, HRESULT};
+    use windows::Win32::System::Wmi::{IWbemClassObject, IWbemObjectSink, IWbemObjectSink_Impl};
+
+    use super::AtkEvent;
+    use crate::wmi::connection::WmiConnection;
+
+    /// COM sink receiving `AsusAtkWmiEvent` instances. WMI calls it on its
+    /// own worker threads, so it only decodes the event and hands it over.
+    #[implement(IWbemObjectSink)]
+    pub struct EventSink {
+        pub events: mpsc::Sender<AtkEvent>,
+    }
+
+    impl IWbemObjectSink_Impl for EventSink_Impl {
+        fn Indicate(
+            &self,
+            lobjectcount: i32,
+            apobjarray: *const Option<IWbemClassObject>,
+        ) -> windows::core::Result<()> {
+            let count = usize::try_from(lobjectcount).unwrap_or(0);
+            if apobjarray.is_null() || count == 0 {
+                return Ok(());
+            }
+            // SAFETY: WMI passes `lobjectcount` valid object pointers
+            let objects = unsafe { std::slice::from_raw_parts(apobjarray, count) };
+            for obj in objects.iter().flatten() {
+                match WmiConnection::get_property_u32(obj, "EventID") {
+                    Ok(code) => {
+                        let _ = self.events.send(AtkEvent::from_code(code));
+                    }
+                    Err(e) => eprintln!("[WMI] AsusAtkWmiEvent without EventID: {e}"),
+                }
+            }
+            Ok(())
+        }
+
+        fn SetStatus(
+            &self,
+            _lflags: i32,
+            _hresult: HRESULT,
+            _strparam: &BSTR,
+            _pobjparam: Ref<'_, IWbemClassObject>,
+        ) -> windows::core::Result<()> {
+            Ok(())
+        }
+    }
+}
+
+/// Subscribe to ATK events, sending each one to `events`. Must run on the
+/// WMI thread; the subscription ends when `conn` is dropped.
+///
+/// # Errors
+///
+/// Returns an error if `AsusAtkWmiEvent` does not exist (desktop boards,
+/// ATK driver missing).
+pub fn subscribe_atk_events(conn: &WmiConnection, events: mpsc::Sender<AtkEvent>) -> Result<()> {
+    let sink: IWbemObjectSink = sink::EventSink { events }.into();
+    conn.subscribe_events(ATK_EVENT_QUERY, &sink)
+}
@@ -0,0 +1,219 @@
+/// WMI class and method explorer.
+///
+/// Lists the methods of the ASUS WMI classes with their input and output
+/// parameter signatures, read from the class definitions in `root\WMI`
+/// through `BeginMethodEnumeration` / `NextMethod`. Firmware versions
+/// differ in which fan-control methods they expose, and a dump of this is
+/// the quickest way for a user to report what their board offers.
+///
+/// Only class definitions are read; no method is called. Not available
+/// on a [`simulated`](WmiConnection::simulated) connection.
+use serde::Serialize;
+use windows::core::{w, BSTR};
+use windows::Win32::System::Ole::{SafeArrayGetElement, SafeArrayGetLBound, SafeArrayGetUBound};
+use windows::Win32::System::Variant::{VARENUM, VARIANT, VT_ARRAY, VT_BSTR};
+use windows::Win32::System::Wmi::{IWbemClassObject, IWbemQualifierSet, WBEM_FLAG_NONSYSTEM_ONLY};
+
+use crate::error::Result;
+use crate::wmi::connection::WmiConnection;
+
+/// The classes of the three ASUS WMI backends.
+pub const ASUS_CLASSES: [&str; 3] = ["ASUSManagement", "ASUSHW", "ASUSATKWMI_WMNB"];
+
+/// `CIM_FLAG_ARRAY`, set on the CIM type of array parameters.
+const CIM_FLAG_ARRAY: i32 = 0x2000;
+
+/// An input or output parameter of a WMI method.
+#[derive(Debug, Clone, Serialize)]
+pub struct WmiParamInfo {
+    pub name: String,
+    /// CIM type name, e.g. `uint32` or `string[]`.
+    pub cim_type: String,
+    /// `ID` qualifier: the position of the parameter in the call.
+    pub id: Option<i32>,
+    /// `Values` qualifier: the names declared for an enumerated parameter.
+    pub values: Vec<String>,
+}
+
+/// A method and its signature.
+#[derive(Debug, Clone, Serialize)]
+pub struct WmiMethodInfo {
+    pub name: String,
+    /// `WmiMethodId` qualifier: the ACPI method ID behind the WMI method.
+    pub method_id: Option<i32>,
+    pub inputs: Vec<WmiParamInfo>,
+    /// Output parameters, including `ReturnValue` if the method has one.
+    pub outputs: Vec<WmiParamInfo>,
+}
+
+/// The methods of one class, or why it could not be read (usually
+/// because the firmware does not define it).
+#[derive(Debug, Clone, Serialize)]
+pub struct WmiClassInfo {
+    pub class_name: String,
+    pub methods: Vec<WmiMethodInfo>,
+    pub error: Option<String>,
+}
+
+/// Describe every class in [`ASUS_CLASSES`].
+pub fn explore(conn: &WmiConnection) -> Vec<WmiClassInfo> {
+    ASUS_CLASSES
+        .iter()
+        .map(|&class_name| match describe_class(conn, class_name) {
+            Ok(methods) => WmiClassInfo {
+                class_name: class_name.to_string(),
+                methods,
+                error: None,
+            },
+            Err(e) => WmiClassInfo {
+                class_name: class_name.to_string(),
+                methods: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        })
+        .collect()
+}
+
+/// The methods of `class_name` with their parameters, in declaration
+/// order.
+///
+/// # Errors
+///
+/// Returns `Wmi` if the class does not exist or cannot be read.
+#[allow(unsafe_code)]
+pub fn describe_class(conn: &WmiConnection, class_name: &str) -> Result<Vec<WmiMethodInfo>> {
+    let class_obj = conn.get_object(class_name)?;
+    let mut methods = Vec::new();
+    unsafe {
+        class_obj.BeginMethodEnumeration(0)?;
+        loop {
+            let mut name = BSTR::default();
+            let mut inputs = None;
+            let mut outputs = None;
+            // WBEM_S_NO_MORE_DATA is a success code and leaves the name empty
+            let next = class_obj.NextMethod(0, &raw mut name, &raw mut inputs, &raw mut outputs);
+            if next.is_err() || name.is_empty() {
+                break;
+            }
+            let method_id = class_obj
+                .GetMethodQualifierSet(&name)
+                .ok()
+                .and_then(|q| qualifier_i32(&q, w!("WmiMethodId")));
+            methods.push(WmiMethodInfo {
+                name: name.to_string(),
+                method_id,
+                inputs: inputs.as_ref().map(params).unwrap_or_default(),
+                outputs: outputs.as_ref().map(params).unwrap_or_default(),
+            });
+        }
+        class_obj.EndMethodEnumeration()?;
+    }
+    Ok(methods)
+}
+
+/// The properties of a method signature object, sorted by `ID` where
+/// present.
+#[allow(unsafe_code)]
+fn params(signature: &IWbemClassObject) -> Vec<WmiParamInfo> {
+    let mut params = Vec::new();
+    unsafe {
+        if signature
+            .BeginEnumeration(WBEM_FLAG_NONSYSTEM_ONLY.0)
+            .is_err()
+        {
+            return params;
+        }
+        loop {
+            let mut name = BSTR::default();
+            let mut cim_type = 0;
+            let next = signature.Next(
+                0,
+                &raw mut name,
+                std::ptr::null_mut(),
+                &raw mut cim_type,
+                std::ptr::null_mut(),
+            );
+            if next.is_err() || name.is_empty() {
+                break;
+            }
+            let qualifiers = signature.GetPropertyQualifierSet(&name).ok();
+            params.push(WmiParamInfo {
+                name: name.to_string(),
+                cim_type: cim_type_name(cim_type),
+                id: qualifiers.as_ref().and_then(|q| qualifier_i32(q, w!("ID"))),
+                values: qualifiers
+                    .as_ref()
+                    .and_then(|q| qualifier_strings(q, w!("Values")))
+                    .unwrap_or_default(),
+            });
+        }
+        let _ = signature.EndEnumeration();
+    }
+    params.sort_by_key(|p| p.id.unwrap_or(i32::MAX));
+    params
+}
+
+#[allow(unsafe_code)]
+fn qualifier_i32(qualifiers: &IWbemQualifierSet, name: windows::core::PCWSTR) -> Option<i32> {
+    let mut value = VARIANT::default();
+    unsafe {
+        qualifiers
+            .Get(name, 0, &raw mut value, std::ptr::null_mut())
+            .ok()?;
+    }
+    i32::try_from(&value).ok()
+}
+
+#[allow(unsafe_code)]
+fn qualifier_strings(
+    qualifiers: &IWbemQualifierSet,
+    name: windows::core::PCWSTR,
+) -> Option<Vec<String>> {
+    let mut value = VARIANT::default();
+    unsafe {
+        qualifiers
+            .Get(name, 0, &raw mut value, std::ptr::null_mut())
+            .ok()?;
+        let raw = &value.Anonymous.Anonymous;
+        if raw.vt != VARENUM(VT_ARRAY.0 | VT_BSTR.0) {
+            return None;
+        }
+        let array = raw.Anonymous.parray;
+        let lower = SafeArrayGetLBound(array, 1).ok()?;
+        let upper = SafeArrayGetUBound(array, 1).ok()?;
+        let mut strings = Vec::new();
+        for index in lower..=upper {
+            let mut s = BSTR::default();
+            SafeArrayGetElement(array, &raw const index, (&raw mut s).cast()).ok()?;
+            strings.push(s.to_string());
+        }
+        Some(strings)
+    }
+}
+
+fn cim_type_name(cim_type: i32) -> String {
+    let name = match cim_type & !CIM_FLAG_ARRAY {
+        2 => "sint16",
+        3 => "sint32",
+        4 => "real32",
+        5 => "real64",
+        8 => "string",
+        11 => "boolean",
+        13 => "object",
+        16 => "sint8",
+        17 => "uint8",
+        18 => "uint16",
+        19 => "uint32",
+        20 => "sint64",
+        21 => "uint64",
+        101 => "datetime",
+        102 => "reference",
+        103 => "char16",
+        _ => return format!("unknown({cim_type})"),
+    };
+    if cim_type & CIM_FLAG_ARRAY == 0 {
+        name.to_string()
+    } else {
+        format!("{name}[]")
+    }
+}
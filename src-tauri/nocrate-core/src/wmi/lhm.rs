@@ -55,6 +55,18 @@ pub struct LhmSensor {
     pub parent: String,
 }
 
+/// A hardware node sensors hang off.
+#[derive(Debug, Clone, Serialize)]
+pub struct LhmHardware {
+    /// Unique identifier, e.g. `/gpu-nvidia/0`.
+    pub identifier: String,
+    pub name: String,
+    /// Hardware type: "Cpu", "GpuNvidia", "Motherboard", "SuperIO", etc.
+    pub hardware_type: String,
+    /// Parent hardware identifier (empty for top-level nodes).
+    pub parent: String,
+}
+
 /// A group of sensors categorized by type.
 #[derive(Debug, Clone, Serialize)]
 pub struct LhmSensorSnapshot {
@@ -71,7 +83,7 @@ pub struct LhmSensorSnapshot {
 
 /// Check if LHM WMI is accessible.
 pub fn get_lhm_status(conn: &WmiConnection) -> LhmStatus {
-    if conn.lhm_services().is_none() {
+    if !conn.lhm_connected() {
         return LhmStatus::Unavailable;
     }
     match conn.lhm_query("SELECT Identifier FROM Sensor") {
@@ -136,10 +148,77 @@ fn get_property_f32(
     }
 }
 
-/// Fetch all sensors and group them by type.
-pub fn get_all_sensors(conn: &WmiConnection) -> Result<LhmSensorSnapshot> {
-    let rows = conn.lhm_query("SELECT * FROM Sensor")?;
+/// Which LHM sensors a query returns.
+#[derive(Debug, Clone, Copy)]
+pub enum SensorFilter<'a> {
+    All,
+    /// Direct children of a hardware node, e.g. `/gpu-nvidia/0`.
+    Parent(&'a str),
+    /// Identifiers starting with a prefix, e.g. `/gpu-nvidia/0/`.
+    Prefix(&'a str),
+    /// Exact identifiers; an empty list means all sensors.
+    Ids(&'a [String]),
+}
+
+impl SensorFilter<'_> {
+    /// WQL condition, or `None` for all sensors.
+    fn condition(self) -> Option<String> {
+        match self {
+            Self::All | Self::Ids([]) => None,
+            Self::Parent(parent) => Some(format!("Parent = '{}'", wql_escape(parent))),
+            Self::Prefix(prefix) => Some(format!(
+                "Identifier LIKE '{}%'",
+                wql_escape(prefix)
+                    .replace('[', "[[]")
+                    .replace('%', "[%]")
+                    .replace('_', "[_]")
+            )),
+            Self::Ids(ids) => Some(
+                ids.iter()
+                    .map(|id| format!("Identifier = '{}'", wql_escape(id)))
+                    .collect::<Vec<_>>()
+                    .join(" OR "),
+            ),
+        }
+    }
+}
+
+/// Escape a WQL string literal.
+fn wql_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Fetch the sensors matching `filter`, optionally restricted to one or
+/// more sensor types.
+pub fn query_sensors(
+    conn: &WmiConnection,
+    filter: SensorFilter<'_>,
+    sensor_types: &[&str],
+) -> Result<Vec<LhmSensor>> {
+    let types = (!sensor_types.is_empty()).then(|| {
+        sensor_types
+            .iter()
+            .map(|t| format!("SensorType = '{}'", wql_escape(t)))
+            .collect::<Vec<_>>()
+            .join(" OR ")
+    });
+    let wql = match (filter.condition(), types) {
+        (None, None) => "SELECT * FROM Sensor".to_string(),
+        (Some(c), None) | (None, Some(c)) => format!("SELECT * FROM Sensor WHERE {c}"),
+        (Some(c), Some(t)) => format!("SELECT * FROM Sensor WHERE ({c}) AND ({t})"),
+    };
+    let rows = conn.lhm_query(&wql)?;
+    Ok(rows
+        .iter()
+        .filter_map(|obj| parse_sensor(conn, obj))
+        .collect())
+}
 
+/// Fetch the sensors matching `filter` and group them by type.
+pub fn get_all_sensors(
+    conn: &WmiConnection,
+    filter: SensorFilter<'_>,
+) -> Result<LhmSensorSnapshot> {
     let mut snapshot = LhmSensorSnapshot {
         temperatures: Vec::new(),
         fans: Vec::new(),
@@ -150,18 +229,16 @@ pub fn get_all_sensors(conn: &WmiConnection) -> Result<LhmSensorSnapshot> {
         powers: Vec::new(),
     };
 
-    for obj in &rows {
-        if let Some(sensor) = parse_sensor(conn, obj) {
-            match sensor.sensor_type.as_str() {
-                "Temperature" => snapshot.temperatures.push(sensor),
-                "Fan" => snapshot.fans.push(sensor),
-                "Control" => snapshot.controls.push(sensor),
-                "Voltage" => snapshot.voltages.push(sensor),
-                "Clock" => snapshot.clocks.push(sensor),
-                "Load" => snapshot.loads.push(sensor),
-                "Power" => snapshot.powers.push(sensor),
-                _ => {} // Ignore other types
-            }
+    for sensor in query_sensors(conn, filter, &[])? {
+        match sensor.sensor_type.as_str() {
+            "Temperature" => snapshot.temperatures.push(sensor),
+            "Fan" => snapshot.fans.push(sensor),
+            "Control" => snapshot.controls.push(sensor),
+            "Voltage" => snapshot.voltages.push(sensor),
+            "Clock" => snapshot.clocks.push(sensor),
+            "Load" => snapshot.loads.push(sensor),
+            "Power" => snapshot.powers.push(sensor),
+            _ => {} // Ignore other types
         }
     }
 
@@ -169,19 +246,28 @@ pub fn get_all_sensors(conn: &WmiConnection) -> Result<LhmSensorSnapshot> {
 }
 
 /// Fetch only temperature and fan sensors (lightweight query for dashboard).
-pub fn get_temp_and_fan_sensors(conn: &WmiConnection) -> Result<(Vec<LhmSensor>, Vec<LhmSensor>)> {
-    let temps_rows = conn.lhm_query("SELECT * FROM Sensor WHERE SensorType = 'Temperature'")?;
-    let fans_rows = conn.lhm_query("SELECT * FROM Sensor WHERE SensorType = 'Fan'")?;
-
-    let temps: Vec<LhmSensor> = temps_rows
-        .iter()
-        .filter_map(|obj| parse_sensor(conn, obj))
-        .collect();
+pub fn get_temp_and_fan_sensors(
+    conn: &WmiConnection,
+    filter: SensorFilter<'_>,
+) -> Result<(Vec<LhmSensor>, Vec<LhmSensor>)> {
+    Ok(query_sensors(conn, filter, &["Temperature", "Fan"])?
+        .into_iter()
+        .partition(|s| s.sensor_type == "Temperature"))
+}
 
-    let fans: Vec<LhmSensor> = fans_rows
+/// List the hardware nodes (CPU, GPU, mainboard, SuperIO chip, …).
+pub fn get_hardware(conn: &WmiConnection) -> Result<Vec<LhmHardware>> {
+    let rows = conn.lhm_query("SELECT * FROM Hardware")?;
+    Ok(rows
         .iter()
-        .filter_map(|obj| parse_sensor(conn, obj))
-        .collect();
-
-    Ok((temps, fans))
+        .filter_map(|obj| {
+            Some(LhmHardware {
+                identifier: WmiConnection::get_property_string(obj, "Identifier").ok()?,
+                name: WmiConnection::get_property_string(obj, "Name").ok()?,
+                hardware_type: WmiConnection::get_property_string(obj, "HardwareType")
+                    .unwrap_or_default(),
+                parent: WmiConnection::get_property_string(obj, "Parent").unwrap_or_default(),
+            })
+        })
+        .collect())
 }
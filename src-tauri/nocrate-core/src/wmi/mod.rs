@@ -0,0 +1,11 @@
+pub mod asio_hw;
+pub mod asus_mgmt;
+pub mod atkacpi;
+pub mod connection;
+pub mod events;
+pub mod explore;
+pub mod lhm;
+pub mod ppt;
+pub mod sim;
+pub mod thread;
+pub mod trace;
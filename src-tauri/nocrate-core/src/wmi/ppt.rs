@@ -0,0 +1,266 @@
+/// Laptop CPU / GPU power limits (PPT).
+///
+/// ROG laptops expose the AMD / Intel package power tracking limits and
+/// the NVIDIA Dynamic Boost budget as ASUS WMI device IDs. The firmware
+/// accepts any value, so writes are validated against per-model ranges
+/// taken from the Linux `asus-armoury` driver; unknown models get a
+/// conservative generic range.
+///
+/// The firmware resets the limits on every thermal profile change, so
+/// limits are stored per profile and re-applied after each switch.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NoCrateError, Result};
+use crate::wmi::asus_mgmt::{devs, dsts, require_laptop, ThermalProfile, DSTS_PRESENCE_BIT};
+use crate::wmi::connection::AsusWmi;
+
+/// Power-limit device IDs (laptop only), values in watts.
+pub mod device_id {
+    /// Sustained power limit (AMD SPL / Intel PL1).
+    pub const PPT_SPL: u32 = 0x0012_00A3;
+
+    /// Slow package power tracking limit (AMD sPPT / Intel PL2).
+    pub const PPT_SPPT: u32 = 0x0012_00A0;
+
+    /// Fast package power tracking limit (AMD fPPT).
+    pub const PPT_FPPT: u32 = 0x0012_00C1;
+
+    /// NVIDIA Dynamic Boost: extra watts shifted from CPU to GPU.
+    pub const NV_DYNAMIC_BOOST: u32 = 0x0012_00C0;
+}
+
+/// One adjustable power limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerLimit {
+    Spl,
+    Sppt,
+    Fppt,
+    NvDynamicBoost,
+}
+
+impl PowerLimit {
+    pub const ALL: [Self; 4] = [Self::Spl, Self::Sppt, Self::Fppt, Self::NvDynamicBoost];
+
+    #[must_use]
+    pub const fn device_id(self) -> u32 {
+        match self {
+            Self::Spl => device_id::PPT_SPL,
+            Self::Sppt => device_id::PPT_SPPT,
+            Self::Fppt => device_id::PPT_FPPT,
+            Self::NvDynamicBoost => device_id::NV_DYNAMIC_BOOST,
+        }
+    }
+}
+
+/// Limits in watts; `None` leaves the firmware value alone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerLimits {
+    pub spl: Option<u8>,
+    pub sppt: Option<u8>,
+    pub fppt: Option<u8>,
+    pub nv_dynamic_boost: Option<u8>,
+}
+
+impl PowerLimits {
+    #[must_use]
+    pub const fn get(self, limit: PowerLimit) -> Option<u8> {
+        match limit {
+            PowerLimit::Spl => self.spl,
+            PowerLimit::Sppt => self.sppt,
+            PowerLimit::Fppt => self.fppt,
+            PowerLimit::NvDynamicBoost => self.nv_dynamic_boost,
+        }
+    }
+
+    const fn set(&mut self, limit: PowerLimit, watts: Option<u8>) {
+        match limit {
+            PowerLimit::Spl => self.spl = watts,
+            PowerLimit::Sppt => self.sppt = watts,
+            PowerLimit::Fppt => self.fppt = watts,
+            PowerLimit::NvDynamicBoost => self.nv_dynamic_boost = watts,
+        }
+    }
+}
+
+/// Accepted range of a limit, in watts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PowerLimitRange {
+    pub limit: PowerLimit,
+    pub min: u8,
+    pub max: u8,
+}
+
+/// Power-limit ranges and firmware values returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerLimitsInfo {
+    /// Board name used to pick the ranges, if it could be read.
+    pub board: Option<String>,
+    pub ranges: Vec<PowerLimitRange>,
+    pub current: PowerLimits,
+}
+
+/// Per-model ranges for SPL / sPPT / fPPT / Dynamic Boost.
+struct ModelRanges {
+    /// Board name prefix (e.g. `GA402R` matches `GA402RJ`).
+    prefix: &'static str,
+    spl: (u8, u8),
+    sppt: (u8, u8),
+    fppt: (u8, u8),
+    /// `None` for models without an NVIDIA dGPU.
+    nv_dynamic_boost: Option<(u8, u8)>,
+}
+
+const MODELS: &[ModelRanges] = &[
+    // ROG Zephyrus G14 (2022)
+    ModelRanges {
+        prefix: "GA402R",
+        spl: (15, 80),
+        sppt: (25, 80),
+        fppt: (25, 80),
+        nv_dynamic_boost: Some((5, 25)),
+    },
+    // ROG Zephyrus G14 (2024)
+    ModelRanges {
+        prefix: "GA403U",
+        spl: (15, 80),
+        sppt: (25, 80),
+        fppt: (25, 80),
+        nv_dynamic_boost: Some((5, 25)),
+    },
+    // ROG Strix G15 (2021)
+    ModelRanges {
+        prefix: "G513Q",
+        spl: (15, 80),
+        sppt: (15, 80),
+        fppt: (15, 80),
+        nv_dynamic_boost: Some((5, 25)),
+    },
+    // ROG Strix G16 (2023)
+    ModelRanges {
+        prefix: "G614J",
+        spl: (28, 140),
+        sppt: (28, 175),
+        fppt: (28, 175),
+        nv_dynamic_boost: Some((5, 25)),
+    },
+    // ROG Zephyrus M16 (2022)
+    ModelRanges {
+        prefix: "GU603Z",
+        spl: (28, 135),
+        sppt: (28, 135),
+        fppt: (28, 135),
+        nv_dynamic_boost: Some((5, 20)),
+    },
+    // ROG Flow X13 (2023)
+    ModelRanges {
+        prefix: "GV302X",
+        spl: (15, 65),
+        sppt: (25, 65),
+        fppt: (25, 65),
+        nv_dynamic_boost: Some((5, 20)),
+    },
+    // ROG Ally
+    ModelRanges {
+        prefix: "RC71L",
+        spl: (7, 30),
+        sppt: (15, 35),
+        fppt: (15, 43),
+        nv_dynamic_boost: None,
+    },
+];
+
+/// Range used for models not in [`MODELS`].
+const GENERIC: ModelRanges = ModelRanges {
+    prefix: "",
+    spl: (15, 65),
+    sppt: (15, 65),
+    fppt: (15, 65),
+    nv_dynamic_boost: Some((5, 15)),
+};
+
+/// Ranges of the limits supported on `board` (unknown or `None` board:
+/// the generic ranges).
+#[must_use]
+pub fn ranges_for_board(board: Option<&str>) -> Vec<PowerLimitRange> {
+    let model = board
+        .and_then(|b| MODELS.iter().find(|m| b.starts_with(m.prefix)))
+        .unwrap_or(&GENERIC);
+    let range = |limit, (min, max)| PowerLimitRange { limit, min, max };
+    let mut ranges = vec![
+        range(PowerLimit::Spl, model.spl),
+        range(PowerLimit::Sppt, model.sppt),
+        range(PowerLimit::Fppt, model.fppt),
+    ];
+    if let Some(boost) = model.nv_dynamic_boost {
+        ranges.push(range(PowerLimit::NvDynamicBoost, boost));
+    }
+    ranges
+}
+
+/// Check every set limit against `ranges`.
+pub fn validate(limits: PowerLimits, ranges: &[PowerLimitRange]) -> Result<()> {
+    for limit in PowerLimit::ALL {
+        let Some(watts) = limits.get(limit) else {
+            continue;
+        };
+        let range = ranges
+            .iter()
+            .find(|r| r.limit == limit)
+            .ok_or_else(|| NoCrateError::Wmi(format!("{limit:?} not supported on this model")))?;
+        if !(range.min..=range.max).contains(&watts) {
+            return Err(NoCrateError::Wmi(format!(
+                "{limit:?} {watts} W out of range ({}-{} W)",
+                range.min, range.max
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Read the limits the firmware reports, `None` for absent devices.
+///
+/// Some models only report the presence bit and a zero value until a
+/// limit has been written.
+pub fn get_power_limits(conn: &impl AsusWmi) -> Result<PowerLimits> {
+    require_laptop(conn, "Power limits")?;
+    let mut limits = PowerLimits::default();
+    for limit in PowerLimit::ALL {
+        let watts = dsts(conn, limit.device_id())
+            .ok()
+            .filter(|raw| raw & DSTS_PRESENCE_BIT != 0)
+            .map(|raw| (raw & 0xFF) as u8);
+        limits.set(limit, watts);
+    }
+    Ok(limits)
+}
+
+/// Write every set limit. The caller validates against the model ranges.
+pub fn set_power_limits(conn: &impl AsusWmi, limits: PowerLimits) -> Result<()> {
+    require_laptop(conn, "Power limits")?;
+    for limit in PowerLimit::ALL {
+        if let Some(watts) = limits.get(limit) {
+            let status = devs(conn, limit.device_id(), u32::from(watts))?;
+            if status == 0 {
+                return Err(NoCrateError::Wmi(format!(
+                    "{limit:?} not supported by this laptop"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write the limits saved for `profile`, if any.
+pub fn apply_for_profile(
+    conn: &impl AsusWmi,
+    profiles: &HashMap<ThermalProfile, PowerLimits>,
+    profile: ThermalProfile,
+) -> Result<()> {
+    profiles
+        .get(&profile)
+        .map_or(Ok(()), |&limits| set_power_limits(conn, limits))
+}
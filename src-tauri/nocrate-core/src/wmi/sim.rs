@@ -0,0 +1,410 @@
+/// Simulated `ASUSManagement` board for working without ASUS hardware.
+///
+/// Answers the WMI methods NoCrate calls on a desktop board from an
+/// in-memory state: four fan headers with their policies, Pro curves and
+/// Q-Fan calibration, and the thermal profile. Fan speeds follow the
+/// active curve of each header as a fake CPU temperature slowly rises and
+/// falls, so the dashboard moves like it would on a real board. Writes are kept and
+/// read back, which is enough for frontend work and automated tests.
+use std::cell::RefCell;
+use std::f32::consts::TAU;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NoCrateError, Result};
+use crate::wmi::asus_mgmt::{
+    curve_duty_at, device_id, DesktopFanMode, DesktopFanProfile, FanCurvePoint, ThermalProfile,
+    FAN_CURVE_POINTS,
+};
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend, MethodOutput, WmiParam};
+
+/// Instance path of the simulated `ASUSManagement` object.
+pub const INSTANCE_PATH: &str = "ASUSManagement.InstanceName='SIMULATED'";
+
+/// `ErrorCode` returned for a fan header the board does not have.
+const ERROR_NOT_PRESENT: u32 = 1;
+
+/// Temperature sources the simulated firmware declares for fan policies.
+const SOURCES: &[&str] = &["CPU", "MB", "VRM", "PCH", "T_SENSOR"];
+
+/// Duration of a simulated Q-Fan calibration run.
+const CALIBRATION_TIME: Duration = Duration::from_secs(20);
+
+/// Lowest duty every simulated fan keeps spinning at.
+const CALIBRATED_MIN_DUTY: u32 = 20;
+
+/// A value in the output parameters of a simulated method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SimValue {
+    U32(u32),
+    Str(String),
+}
+
+/// Curve used by headers in the STANDARD profile.
+const STANDARD_CURVE: [(u8, u8); FAN_CURVE_POINTS] = [
+    (20, 20),
+    (30, 25),
+    (40, 30),
+    (50, 40),
+    (60, 55),
+    (70, 70),
+    (80, 85),
+    (90, 100),
+];
+
+struct SimHeader {
+    max_rpm: u32,
+    mode: DesktopFanMode,
+    profile: DesktopFanProfile,
+    source: String,
+    low_limit: u32,
+    /// Manual curve per mode, in [`DesktopFanMode::ALL`] order.
+    curves: [[FanCurvePoint; FAN_CURVE_POINTS]; 3],
+    /// Start of the last Q-Fan calibration run.
+    calibration_started: Option<Instant>,
+}
+
+impl SimHeader {
+    fn new(max_rpm: u32, source: &str, low_limit: u32) -> Self {
+        let curve = STANDARD_CURVE.map(|(temp_c, duty_pct)| FanCurvePoint { temp_c, duty_pct });
+        Self {
+            max_rpm,
+            mode: DesktopFanMode::Pwm,
+            profile: DesktopFanProfile::Standard,
+            source: source.to_string(),
+            low_limit,
+            curves: [curve; 3],
+            calibration_started: None,
+        }
+    }
+
+    fn curve(&self, mode: DesktopFanMode) -> &[FanCurvePoint; FAN_CURVE_POINTS] {
+        &self.curves[mode_index(mode)]
+    }
+
+    /// Duty in percent at `temp_c`, interpolated on the active curve.
+    fn duty(&self, temp_c: f32, profile: ThermalProfile) -> f32 {
+        let points = match self.profile {
+            DesktopFanProfile::Manual => *self.curve(self.mode),
+            DesktopFanProfile::Standard => {
+                STANDARD_CURVE.map(|(temp_c, duty_pct)| FanCurvePoint { temp_c, duty_pct })
+            }
+        };
+        let duty = curve_duty_at(&points, temp_c);
+        let scaled = match (self.profile, profile) {
+            (DesktopFanProfile::Manual, _) | (_, ThermalProfile::Standard) => duty,
+            (_, ThermalProfile::Performance) => duty * 1.2,
+            (_, ThermalProfile::Silent) => duty * 0.8,
+        };
+        scaled.min(100.0)
+    }
+}
+
+struct SimState {
+    headers: Vec<SimHeader>,
+    profile: ThermalProfile,
+}
+
+/// In-memory stand-in for the `ASUSManagement` WMI object.
+pub struct SimWmi {
+    backend: AsusWmiBackend,
+    state: RefCell<SimState>,
+    started: Instant,
+}
+
+impl Default for SimWmi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimWmi {
+    /// A board with a CPU fan, two chassis fans and an AIO pump header.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            backend: AsusWmiBackend::Desktop {
+                instance_path: INSTANCE_PATH.to_string(),
+            },
+            state: RefCell::new(SimState {
+                headers: vec![
+                    SimHeader::new(1800, "CPU", 200),
+                    SimHeader::new(1400, "MB", 0),
+                    SimHeader::new(1400, "MB", 0),
+                    SimHeader::new(3000, "CPU", 600),
+                ],
+                profile: ThermalProfile::Standard,
+            }),
+            started: Instant::now(),
+        }
+    }
+
+    /// Fake CPU temperature the fan speeds follow.
+    fn cpu_temp(&self) -> f32 {
+        wobble(self.started, 52.0, 14.0, 90.0)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn rpm(&self, fan_type: usize) -> u32 {
+        let state = self.state.borrow();
+        let Some(header) = state.headers.get(fan_type) else {
+            return 0;
+        };
+        let duty = header.duty(self.cpu_temp(), state.profile);
+        // A little noise, like a real tachometer
+        let jitter = wobble(self.started, 0.0, 8.0, 3.0 + fan_type as f32);
+        to_u32((duty / 100.0).mul_add(header.max_rpm as f32, jitter))
+    }
+
+    /// Answer a call to `method` with `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Wmi` for methods the simulation does not implement and
+    /// for missing parameters.
+    pub fn exec(&self, method: &str, params: &[(&str, WmiParam<'_>)]) -> Result<MethodOutput> {
+        let out = match method {
+            "device_status" => {
+                let value = match param_u32(params, "device_id")? {
+                    device_id::CPU_FAN_SPEED => self.rpm(0),
+                    device_id::GPU_FAN_SPEED => self.rpm(1),
+                    device_id::MID_FAN_SPEED => self.rpm(2),
+                    device_id::THROTTLE_THERMAL_POLICY => self.state.borrow().profile.to_raw(),
+                    _ => 0,
+                };
+                vec![("ctrl_param".to_string(), SimValue::U32(value))]
+            }
+            "device_ctrl" => {
+                let control = param_u32(params, "ctrl_param")?;
+                if param_u32(params, "device_id")? == device_id::THROTTLE_THERMAL_POLICY {
+                    let profile = ThermalProfile::from_raw(control).ok_or_else(|| {
+                        NoCrateError::Wmi(format!("Unknown thermal profile {control}"))
+                    })?;
+                    self.state.borrow_mut().profile = profile;
+                }
+                Vec::new()
+            }
+            "GetFanPolicy" => self.get_fan_policy(params)?,
+            "SetFanPolicy" => self.set_fan_policy(params)?,
+            "StartFanCalibration" => self.start_calibration(params)?,
+            "GetFanCalibrationStatus" => self.calibration_status(params)?,
+            "GetManualFanCurvePro" => self.get_curve(params)?,
+            "SetManualFanCurvePro" => self.set_curve(params)?,
+            _ => {
+                return Err(NoCrateError::Wmi(format!(
+                    "{method} is not available in simulation mode"
+                )))
+            }
+        };
+        Ok(MethodOutput::Simulated(out.into_iter().collect()))
+    }
+
+    fn get_fan_policy(&self, params: &[(&str, WmiParam<'_>)]) -> Result<Vec<(String, SimValue)>> {
+        let fan_type = param_u32(params, "FanType")? as usize;
+        let state = self.state.borrow();
+        let Some(h) = state.headers.get(fan_type) else {
+            return Ok(not_present());
+        };
+        Ok(vec![
+            ("ErrorCode".into(), SimValue::U32(0)),
+            ("Mode".into(), SimValue::Str(h.mode.to_wmi().into())),
+            ("Profile".into(), SimValue::Str(h.profile.to_wmi().into())),
+            ("Source".into(), SimValue::Str(h.source.clone())),
+            ("LowLimit".into(), SimValue::U32(h.low_limit)),
+        ])
+    }
+
+    fn start_calibration(
+        &self,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<Vec<(String, SimValue)>> {
+        let fan_type = param_u32(params, "FanType")? as usize;
+        let mut state = self.state.borrow_mut();
+        let Some(h) = state.headers.get_mut(fan_type) else {
+            return Ok(not_present());
+        };
+        h.calibration_started = Some(Instant::now());
+        Ok(vec![("ErrorCode".into(), SimValue::U32(0))])
+    }
+
+    fn calibration_status(
+        &self,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<Vec<(String, SimValue)>> {
+        let fan_type = param_u32(params, "FanType")? as usize;
+        let state = self.state.borrow();
+        let Some(h) = state.headers.get(fan_type) else {
+            return Ok(not_present());
+        };
+        let (status, progress) = match h.calibration_started {
+            None => (0, 0),
+            Some(t) if t.elapsed() < CALIBRATION_TIME => (
+                1,
+                to_u32(t.elapsed().as_secs_f32() / CALIBRATION_TIME.as_secs_f32() * 100.0),
+            ),
+            Some(_) => (2, 100),
+        };
+        Ok(vec![
+            ("ErrorCode".into(), SimValue::U32(0)),
+            ("Status".into(), SimValue::U32(status)),
+            ("Progress".into(), SimValue::U32(progress)),
+            ("MinDuty".into(), SimValue::U32(CALIBRATED_MIN_DUTY)),
+            (
+                "MinRpm".into(),
+                SimValue::U32(h.max_rpm * CALIBRATED_MIN_DUTY / 100),
+            ),
+            ("MaxRpm".into(), SimValue::U32(h.max_rpm)),
+        ])
+    }
+
+    fn set_fan_policy(&self, params: &[(&str, WmiParam<'_>)]) -> Result<Vec<(String, SimValue)>> {
+        let fan_type = param_u32(params, "FanType")? as usize;
+        let mut state = self.state.borrow_mut();
+        let Some(h) = state.headers.get_mut(fan_type) else {
+            return Ok(not_present());
+        };
+        h.mode = DesktopFanMode::from_wmi(param_str(params, "Mode")?);
+        h.profile = DesktopFanProfile::from_wmi(param_str(params, "Profile")?);
+        h.source = param_str(params, "Source")?.to_string();
+        h.low_limit = param_u32(params, "LowLimit")?;
+        Ok(vec![("ErrorCode".into(), SimValue::U32(0))])
+    }
+
+    fn get_curve(&self, params: &[(&str, WmiParam<'_>)]) -> Result<Vec<(String, SimValue)>> {
+        let fan_type = param_u32(params, "FanType")? as usize;
+        let mode = DesktopFanMode::from_wmi(param_str(params, "Mode")?);
+        let state = self.state.borrow();
+        let Some(h) = state.headers.get(fan_type) else {
+            return Ok(not_present());
+        };
+        let mut out = vec![("ErrorCode".to_string(), SimValue::U32(0))];
+        for (i, p) in h.curve(mode).iter().enumerate() {
+            let idx = i + 1;
+            out.push((format!("Point{idx}Temp"), SimValue::U32(p.temp_c.into())));
+            out.push((format!("Point{idx}Duty"), SimValue::U32(p.duty_pct.into())));
+        }
+        Ok(out)
+    }
+
+    fn set_curve(&self, params: &[(&str, WmiParam<'_>)]) -> Result<Vec<(String, SimValue)>> {
+        let fan_type = param_u32(params, "FanType")? as usize;
+        let mode = DesktopFanMode::from_wmi(param_str(params, "Mode")?);
+        let mut points = [FanCurvePoint {
+            temp_c: 0,
+            duty_pct: 0,
+        }; FAN_CURVE_POINTS];
+        for (i, p) in points.iter_mut().enumerate() {
+            let idx = i + 1;
+            p.temp_c = to_u8(param_u32(params, &format!("Point{idx}Temp"))?);
+            p.duty_pct = to_u8(param_u32(params, &format!("Point{idx}Duty"))?);
+        }
+        let mut state = self.state.borrow_mut();
+        let Some(h) = state.headers.get_mut(fan_type) else {
+            return Ok(not_present());
+        };
+        h.curves[mode_index(mode)] = points;
+        Ok(vec![("ErrorCode".into(), SimValue::U32(0))])
+    }
+}
+
+/// Only the desktop calls are answered; there is no ASUSHW sensor table.
+impl AsusWmi for SimWmi {
+    fn backend(&self) -> &AsusWmiBackend {
+        &self.backend
+    }
+
+    fn dsts(&self, device_id: u32) -> Result<u32> {
+        self.exec("device_status", &[("device_id", WmiParam::U32(device_id))])?
+            .get_u32("ctrl_param")
+    }
+
+    fn devs(&self, device_id: u32, control: u32) -> Result<u32> {
+        let _out = self.exec(
+            "device_ctrl",
+            &[
+                ("device_id", WmiParam::U32(device_id)),
+                ("ctrl_param", WmiParam::U32(control)),
+            ],
+        )?;
+        Ok(1)
+    }
+
+    fn exec_method_v2(
+        &self,
+        _object_path: &str,
+        method_name: &str,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<MethodOutput> {
+        self.exec(method_name, params)
+    }
+
+    fn method_param_values(
+        &self,
+        _object_path: &str,
+        method_name: &str,
+        param: &str,
+    ) -> Result<Vec<String>> {
+        match (method_name, param) {
+            ("SetFanPolicy", "Source") => Ok(SOURCES.iter().map(ToString::to_string).collect()),
+            _ => Err(NoCrateError::Wmi(format!(
+                "{method_name}.{param} declares no string values"
+            ))),
+        }
+    }
+}
+
+/// A value that swings around `base` by `amplitude` once every
+/// `period_s` seconds, for fake sensor readings.
+pub(crate) fn wobble(started: Instant, base: f32, amplitude: f32, period_s: f32) -> f32 {
+    let t = started.elapsed().as_secs_f32();
+    amplitude.mul_add((t / period_s * TAU).sin(), base)
+}
+
+fn mode_index(mode: DesktopFanMode) -> usize {
+    DesktopFanMode::ALL
+        .iter()
+        .position(|&m| m == mode)
+        .unwrap_or(0)
+}
+
+fn not_present() -> Vec<(String, SimValue)> {
+    vec![("ErrorCode".into(), SimValue::U32(ERROR_NOT_PRESENT))]
+}
+
+fn param<'a>(params: &'a [(&str, WmiParam<'a>)], name: &str) -> Result<&'a WmiParam<'a>> {
+    params
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| v)
+        .ok_or_else(|| NoCrateError::Wmi(format!("Missing parameter {name}")))
+}
+
+fn param_u32(params: &[(&str, WmiParam<'_>)], name: &str) -> Result<u32> {
+    match param(params, name)? {
+        WmiParam::U8(v) => Ok((*v).into()),
+        WmiParam::U32(v) => Ok(*v),
+        WmiParam::Str(_) => Err(NoCrateError::Wmi(format!(
+            "Parameter {name} is not a number"
+        ))),
+    }
+}
+
+fn param_str<'a>(params: &'a [(&str, WmiParam<'a>)], name: &str) -> Result<&'a str> {
+    match param(params, name)? {
+        WmiParam::Str(s) => Ok(s),
+        _ => Err(NoCrateError::Wmi(format!(
+            "Parameter {name} is not a string"
+        ))),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+const fn to_u32(v: f32) -> u32 {
+    v.max(0.0).round() as u32
+}
+
+fn to_u8(v: u32) -> u8 {
+    u8::try_from(v).unwrap_or(u8::MAX)
+}
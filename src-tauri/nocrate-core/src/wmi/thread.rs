@@ -0,0 +1,375 @@
+/// Dedicated WMI thread: owns the COM connection, runs queued calls with
+/// a timeout and re-creates the connection after repeated failures.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::wmi::connection::{AsusWmi, WmiConnection};
+
+/// Outcome of a WMI request, as seen by the worker loop.
+enum RequestOutcome {
+    Ok,
+    /// The call failed with a COM error, i.e. the connection may be broken.
+    ConnectionError(String),
+    /// The call failed for another reason (unsupported device, bad argument).
+    OtherError,
+}
+
+/// A request to execute on the WMI thread.
+/// Receives `None` while the connection is down.
+type WmiCall = Box<dyn FnOnce(Option<&WmiConnection>) -> RequestOutcome + Send>;
+
+/// A queued [`WmiCall`] and its claim flag. Whichever of the worker and
+/// the timed-out caller sets the flag first decides: the worker runs the
+/// call, or the caller reports it cancelled and the worker drops it.
+struct WmiRequest {
+    call: WmiCall,
+    claimed: Arc<AtomicBool>,
+}
+
+/// One operation of a batch submitted with [`WmiThread::execute_batch`].
+pub type WmiOp<T> = Box<dyn FnOnce(&WmiConnection) -> Result<T> + Send>;
+
+/// Consecutive COM failures after which the connection is re-created.
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// Minimum interval between reconnection attempts while disconnected.
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Longest time a caller waits for a WMI request, including time queued
+/// behind other requests.
+const EXECUTE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// WMI connection status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WmiStatus {
+    Connected,
+    Reconnecting,
+    Disconnected,
+}
+
+/// WMI connection health, passed to the status listener on every status
+/// change.
+#[derive(Debug, Clone, Serialize)]
+pub struct WmiHealth {
+    pub status: WmiStatus,
+    /// Consecutive COM failures since the last successful call.
+    pub consecutive_failures: u32,
+    /// Successful reconnections since startup.
+    pub reconnects: u32,
+    /// Last COM error, cleared on reconnection.
+    pub last_error: Option<String>,
+}
+
+/// Callback invoked with the new health when the status changes.
+type StatusListener = Box<dyn Fn(&WmiHealth) + Send + Sync>;
+
+/// Health state shared between the worker thread and callers.
+#[derive(Clone)]
+struct SharedHealth {
+    health: Arc<Mutex<WmiHealth>>,
+    listener: Arc<OnceLock<StatusListener>>,
+}
+
+impl SharedHealth {
+    /// Update the health and notify the listener if the status changed.
+    fn update(&self, f: impl FnOnce(&mut WmiHealth)) {
+        let (changed, health) = {
+            let mut health = self.health.lock();
+            let before = health.status;
+            f(&mut health);
+            (health.status != before, health.clone())
+        };
+        if changed {
+            if let Some(listener) = self.listener.get() {
+                listener(&health);
+            }
+        }
+    }
+}
+
+/// Thread-safe handle to the dedicated WMI thread.
+///
+/// Because COM objects (IWbemServices) are not Send/Sync, we run all WMI
+/// operations on a single dedicated thread and communicate via channels.
+/// When calls keep failing with COM errors (WMI service restart, driver
+/// update) the worker drops the connection and creates a new one.
+///
+/// Clones share the same thread.
+#[derive(Clone)]
+pub struct WmiThread {
+    sender: mpsc::Sender<WmiRequest>,
+    shared: SharedHealth,
+}
+
+impl WmiThread {
+    /// Spawn the dedicated WMI thread and establish the COM connection.
+    /// `blocked_device_ids` are rejected by the connection (board quirks).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WMI connection fails during initialization.
+    pub fn spawn(blocked_device_ids: &'static [u32]) -> Result<Self> {
+        Self::spawn_with(move || {
+            WmiConnection::new().map(|mut c| {
+                c.blocked_device_ids = blocked_device_ids;
+                c
+            })
+        })
+    }
+
+    /// Spawn the WMI thread with a [simulated](WmiConnection::simulated)
+    /// connection instead of COM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the thread cannot be spawned.
+    pub fn spawn_simulated() -> Result<Self> {
+        Self::spawn_with(|| Ok(WmiConnection::simulated()))
+    }
+
+    /// Spawn the WMI thread with a [remote](WmiConnection::remote)
+    /// connection; `connect` opens the link to the process owning the real
+    /// connection, and is called again to reconnect.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first `connect`.
+    pub fn spawn_remote(
+        blocked_device_ids: &'static [u32],
+        connect: impl Fn() -> Result<Box<dyn AsusWmi>> + Send + 'static,
+    ) -> Result<Self> {
+        Self::spawn_with(move || {
+            connect().map(|remote| {
+                let mut c = WmiConnection::remote(remote);
+                c.blocked_device_ids = blocked_device_ids;
+                c
+            })
+        })
+    }
+
+    fn spawn_with(connect: impl Fn() -> Result<WmiConnection> + Send + 'static) -> Result<Self> {
+        let (init_tx, init_rx) = mpsc::channel::<std::result::Result<(), NoCrateError>>();
+        let (req_tx, req_rx) = mpsc::channel::<WmiRequest>();
+        let shared = SharedHealth {
+            health: Arc::new(Mutex::new(WmiHealth {
+                status: WmiStatus::Connected,
+                consecutive_failures: 0,
+                reconnects: 0,
+                last_error: None,
+            })),
+            listener: Arc::new(OnceLock::new()),
+        };
+
+        let worker_shared = shared.clone();
+        let _handle = thread::Builder::new()
+            .name("nocrate-wmi".into())
+            .spawn(move || {
+                // Attempt to create the WMI connection on this thread
+                let conn = match connect() {
+                    Ok(c) => {
+                        let _ = init_tx.send(Ok(()));
+                        c
+                    }
+                    Err(e) => {
+                        let _ = init_tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                worker_loop(&req_rx, Some(conn), connect, &worker_shared);
+
+                // The connection drops here → CoUninitialize on this thread
+            })
+            .map_err(|e| {
+                NoCrateError::Unknown(i18n::tf(
+                    Msg::ThreadSpawn,
+                    &[("name", &"nocrate-wmi"), ("error", &e)],
+                ))
+            })?;
+
+        // Wait for initialization result
+        init_rx.recv().map_err(|_| {
+            NoCrateError::Wmi(i18n::tf(Msg::ThreadInitDied, &[("name", &"nocrate-wmi")]))
+        })??;
+
+        Ok(Self {
+            sender: req_tx,
+            shared,
+        })
+    }
+
+    /// Set the callback notified when the connection status changes.
+    /// Only the first listener set is kept.
+    pub fn set_status_listener(&self, listener: impl Fn(&WmiHealth) + Send + Sync + 'static) {
+        let _ = self.shared.listener.set(Box::new(listener));
+    }
+
+    /// Current connection health.
+    #[must_use]
+    pub fn health(&self) -> WmiHealth {
+        self.shared.health.lock().clone()
+    }
+
+    /// Execute a closure on the WMI thread and receive the result.
+    ///
+    /// The closure runs on the dedicated WMI thread with access to the
+    /// `WmiConnection`. The result is sent back via a oneshot channel and
+    /// waited for at most [`EXECUTE_TIMEOUT`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WMI thread is dead, the connection is being
+    /// re-created, the call times out, or the closure returns an error.
+    pub fn execute<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&WmiConnection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        send_request(&self.sender, f)
+    }
+
+    /// Run several operations in one round-trip to the WMI thread, in
+    /// order. Each operation gets its own result, so one failing read
+    /// does not discard the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the batch as a whole could not run (thread
+    /// dead, connection lost, timeout).
+    pub fn execute_batch<T>(&self, ops: Vec<WmiOp<T>>) -> Result<Vec<Result<T>>>
+    where
+        T: Send + 'static,
+    {
+        self.execute(move |conn| Ok(ops.into_iter().map(|op| op(conn)).collect()))
+    }
+}
+
+/// Queue `f` on the WMI thread and wait up to [`EXECUTE_TIMEOUT`] for its
+/// result. A call still queued at the timeout is cancelled and never
+/// runs, so a write reported as failed cannot reach the hardware later;
+/// one already running is left to finish and its result is discarded.
+fn send_request<F, T>(sender: &mpsc::Sender<WmiRequest>, f: F) -> Result<T>
+where
+    F: FnOnce(&WmiConnection) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let claimed = Arc::new(AtomicBool::new(false));
+
+    let call: WmiCall = Box::new(move |conn| {
+        let result = conn.map_or_else(
+            || {
+                Err(NoCrateError::Wmi(
+                    "WMI connection lost, reconnecting".into(),
+                ))
+            },
+            f,
+        );
+        let outcome = match &result {
+            Ok(_) => RequestOutcome::Ok,
+            Err(e @ NoCrateError::WindowsApi(_)) => RequestOutcome::ConnectionError(e.to_string()),
+            Err(_) => RequestOutcome::OtherError,
+        };
+        let _ = tx.send(result);
+        outcome
+    });
+
+    sender
+        .send(WmiRequest {
+            call,
+            claimed: Arc::clone(&claimed),
+        })
+        .map_err(|_| NoCrateError::Wmi("WMI thread is no longer running".into()))?;
+
+    match rx.recv_timeout(EXECUTE_TIMEOUT) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            let fate = if claimed.swap(true, Ordering::AcqRel) {
+                "it is still running and may yet take effect"
+            } else {
+                "it was cancelled"
+            };
+            Err(NoCrateError::Timeout(format!(
+                "WMI call did not complete within {}s, {fate}",
+                EXECUTE_TIMEOUT.as_secs()
+            )))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(NoCrateError::Wmi("WMI thread did not respond".into()))
+        }
+    }
+}
+
+/// Process requests until the channel is closed, re-creating the
+/// connection after [`FAILURE_THRESHOLD`] consecutive COM failures.
+fn worker_loop(
+    requests: &mpsc::Receiver<WmiRequest>,
+    mut conn: Option<WmiConnection>,
+    connect: impl Fn() -> Result<WmiConnection>,
+    shared: &SharedHealth,
+) {
+    let mut last_attempt: Option<Instant> = None;
+
+    for request in requests {
+        // Cancelled by a caller that gave up waiting
+        if request.claimed.swap(true, Ordering::AcqRel) {
+            continue;
+        }
+        if conn.is_none() && last_attempt.is_none_or(|t| t.elapsed() >= RECONNECT_BACKOFF) {
+            last_attempt = Some(Instant::now());
+            match connect() {
+                Ok(c) => {
+                    eprintln!("WMI: reconnected");
+                    conn = Some(c);
+                    shared.update(|h| {
+                        h.status = WmiStatus::Connected;
+                        h.consecutive_failures = 0;
+                        h.reconnects += 1;
+                        h.last_error = None;
+                    });
+                }
+                Err(e) => {
+                    eprintln!("WMI: reconnection failed: {e}");
+                    shared.update(|h| {
+                        h.status = WmiStatus::Disconnected;
+                        h.last_error = Some(e.to_string());
+                    });
+                }
+            }
+        }
+
+        match (request.call)(conn.as_ref()) {
+            RequestOutcome::Ok => {
+                if conn.is_some() {
+                    shared.update(|h| h.consecutive_failures = 0);
+                }
+            }
+            // Not a connection problem, but not proof of a healthy one either
+            RequestOutcome::OtherError => {}
+            RequestOutcome::ConnectionError(e) => {
+                let mut failures = 0;
+                shared.update(|h| {
+                    h.consecutive_failures += 1;
+                    h.last_error = Some(e);
+                    failures = h.consecutive_failures;
+                });
+                if failures >= FAILURE_THRESHOLD && conn.is_some() {
+                    eprintln!("WMI: {failures} consecutive failures, re-creating connection");
+                    // Drop the old connection first so COM is uninitialized
+                    // before the new one initializes it again
+                    conn = None;
+                    last_attempt = None;
+                    shared.update(|h| h.status = WmiStatus::Reconnecting);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,424 @@
+/// Recording and replay of ASUS WMI calls.
+///
+/// [`RecordingWmi`] wraps a live connection and keeps every call made
+/// through [`AsusWmi`] together with its raw answer. The resulting
+/// [`WmiTrace`] can be saved on a user's machine and fed back through
+/// [`ReplayWmi`], which answers the same calls from the recording, so the
+/// parsing in [`asus_mgmt`](crate::wmi::asus_mgmt),
+/// [`ppt`](crate::wmi::ppt) and [`asio_hw`](crate::wmi::asio_hw) can be
+/// exercised for boards the developer does not own.
+///
+/// Only calls going through the trait are captured; the object path of
+/// method calls is left out since the backend in the trace already
+/// identifies the instance.
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NoCrateError, RecordedError, Result};
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend, MethodOutput, WmiParam};
+use crate::wmi::sim::SimValue;
+
+/// Owned copy of a [`WmiParam`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraceParam {
+    U8(u8),
+    U32(u32),
+    Str(String),
+}
+
+impl TraceParam {
+    /// Borrow as a [`WmiParam`].
+    #[must_use]
+    pub fn as_param(&self) -> WmiParam<'_> {
+        match self {
+            Self::U8(v) => WmiParam::U8(*v),
+            Self::U32(v) => WmiParam::U32(*v),
+            Self::Str(s) => WmiParam::Str(s),
+        }
+    }
+}
+
+impl From<&WmiParam<'_>> for TraceParam {
+    fn from(param: &WmiParam<'_>) -> Self {
+        match param {
+            WmiParam::U8(v) => Self::U8(*v),
+            WmiParam::U32(v) => Self::U32(*v),
+            WmiParam::Str(s) => Self::Str((*s).to_string()),
+        }
+    }
+}
+
+/// A call made through [`AsusWmi`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "call", rename_all = "snake_case")]
+pub enum WmiRequest {
+    Dsts {
+        device_id: u32,
+    },
+    Devs {
+        device_id: u32,
+        control: u32,
+    },
+    Exec {
+        method: String,
+        params: Vec<(String, TraceParam)>,
+    },
+    ParamValues {
+        method: String,
+        param: String,
+    },
+    AsushwSensorCount,
+    AsushwSensorInfo {
+        index: u32,
+    },
+    AsushwUpdateBuffer {
+        source: u32,
+    },
+    AsushwSensorValue {
+        index: u32,
+    },
+}
+
+/// The raw answer to a [`WmiRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WmiResponse {
+    Value(u32),
+    Done,
+    Output(HashMap<String, SimValue>),
+    Names(Vec<String>),
+    SensorInfo(u32, u32, u32, String),
+}
+
+/// A recorded call and its answer; failures keep the error code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WmiCall {
+    pub request: WmiRequest,
+    pub response: std::result::Result<WmiResponse, RecordedError>,
+}
+
+/// Calls recorded on one connection, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WmiTrace {
+    pub backend: AsusWmiBackend,
+    pub calls: Vec<WmiCall>,
+}
+
+/// Records every call made through it on `inner`.
+pub struct RecordingWmi<'a, W: AsusWmi> {
+    inner: &'a W,
+    calls: RefCell<Vec<WmiCall>>,
+}
+
+impl<'a, W: AsusWmi> RecordingWmi<'a, W> {
+    pub const fn new(inner: &'a W) -> Self {
+        Self {
+            inner,
+            calls: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// The calls recorded so far.
+    pub fn into_trace(self) -> WmiTrace {
+        WmiTrace {
+            backend: self.inner.backend().clone(),
+            calls: self.calls.into_inner(),
+        }
+    }
+
+    /// Run `call` on the inner connection and record its answer.
+    fn record<T>(
+        &self,
+        request: WmiRequest,
+        call: impl FnOnce(&W) -> Result<T>,
+        to_response: impl FnOnce(&T) -> WmiResponse,
+    ) -> Result<T> {
+        let result = call(self.inner);
+        let response = match &result {
+            Ok(value) => Ok(to_response(value)),
+            Err(e) => Err(e.into()),
+        };
+        self.calls.borrow_mut().push(WmiCall { request, response });
+        result
+    }
+}
+
+impl<W: AsusWmi> AsusWmi for RecordingWmi<'_, W> {
+    fn backend(&self) -> &AsusWmiBackend {
+        self.inner.backend()
+    }
+
+    fn dsts(&self, device_id: u32) -> Result<u32> {
+        self.record(
+            WmiRequest::Dsts { device_id },
+            |w| w.dsts(device_id),
+            |&v| WmiResponse::Value(v),
+        )
+    }
+
+    fn devs(&self, device_id: u32, control: u32) -> Result<u32> {
+        self.record(
+            WmiRequest::Devs { device_id, control },
+            |w| w.devs(device_id, control),
+            |&v| WmiResponse::Value(v),
+        )
+    }
+
+    /// The output is read out in full and returned as a simulated one,
+    /// so the caller sees exactly what was recorded.
+    fn exec_method_v2(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<MethodOutput> {
+        let request = WmiRequest::Exec {
+            method: method_name.to_string(),
+            params: params
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), value.into()))
+                .collect(),
+        };
+        let values = self.record(
+            request,
+            |w| w.exec_method_v2(object_path, method_name, params)?.values(),
+            |values| WmiResponse::Output(values.clone()),
+        )?;
+        Ok(MethodOutput::Simulated(values))
+    }
+
+    fn method_param_values(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        param: &str,
+    ) -> Result<Vec<String>> {
+        self.record(
+            WmiRequest::ParamValues {
+                method: method_name.to_string(),
+                param: param.to_string(),
+            },
+            |w| w.method_param_values(object_path, method_name, param),
+            |names| WmiResponse::Names(names.clone()),
+        )
+    }
+
+    /// Passed through unrecorded; it only adds detail to error messages.
+    fn last_error(&self) -> Option<u32> {
+        self.inner.last_error()
+    }
+
+    fn asushw_sensor_count(&self) -> Result<u32> {
+        self.record(
+            WmiRequest::AsushwSensorCount,
+            AsusWmi::asushw_sensor_count,
+            |&v| WmiResponse::Value(v),
+        )
+    }
+
+    fn asushw_sensor_info(&self, index: u32) -> Result<(u32, u32, u32, String)> {
+        self.record(
+            WmiRequest::AsushwSensorInfo { index },
+            |w| w.asushw_sensor_info(index),
+            |(source, kind, data_type, name)| {
+                WmiResponse::SensorInfo(*source, *kind, *data_type, name.clone())
+            },
+        )
+    }
+
+    fn asushw_update_buffer(&self, source: u32) -> Result<()> {
+        self.record(
+            WmiRequest::AsushwUpdateBuffer { source },
+            |w| w.asushw_update_buffer(source),
+            |()| WmiResponse::Done,
+        )
+    }
+
+    fn asushw_sensor_value(&self, index: u32) -> Result<u32> {
+        self.record(
+            WmiRequest::AsushwSensorValue { index },
+            |w| w.asushw_sensor_value(index),
+            |&v| WmiResponse::Value(v),
+        )
+    }
+}
+
+/// Make `request` on `conn` and return the answer as [`RecordingWmi`]
+/// would record it. Method calls go to `object_path`. Used to serve calls
+/// forwarded from another process.
+///
+/// # Errors
+///
+/// Returns the error of the call.
+pub fn perform(
+    conn: &impl AsusWmi,
+    object_path: &str,
+    request: &WmiRequest,
+) -> Result<WmiResponse> {
+    match request {
+        WmiRequest::Dsts { device_id } => conn.dsts(*device_id).map(WmiResponse::Value),
+        WmiRequest::Devs { device_id, control } => {
+            conn.devs(*device_id, *control).map(WmiResponse::Value)
+        }
+        WmiRequest::Exec { method, params } => {
+            let params: Vec<(&str, WmiParam<'_>)> = params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_param()))
+                .collect();
+            conn.exec_method_v2(object_path, method, &params)?
+                .values()
+                .map(WmiResponse::Output)
+        }
+        WmiRequest::ParamValues { method, param } => conn
+            .method_param_values(object_path, method, param)
+            .map(WmiResponse::Names),
+        WmiRequest::AsushwSensorCount => conn.asushw_sensor_count().map(WmiResponse::Value),
+        WmiRequest::AsushwSensorInfo { index } => {
+            conn.asushw_sensor_info(*index)
+                .map(|(source, kind, data_type, name)| {
+                    WmiResponse::SensorInfo(source, kind, data_type, name)
+                })
+        }
+        WmiRequest::AsushwUpdateBuffer { source } => conn
+            .asushw_update_buffer(*source)
+            .map(|()| WmiResponse::Done),
+        WmiRequest::AsushwSensorValue { index } => {
+            conn.asushw_sensor_value(*index).map(WmiResponse::Value)
+        }
+    }
+}
+
+/// Answers calls from a [`WmiTrace`]. Writes are not applied; they get
+/// their recorded answer like reads.
+///
+/// Repeated calls get the recorded answers in order, then the last one
+/// again. A call missing from the trace fails with `Wmi`.
+pub struct ReplayWmi {
+    backend: AsusWmiBackend,
+    calls: Vec<WmiCall>,
+    used: RefCell<Vec<bool>>,
+}
+
+impl ReplayWmi {
+    #[must_use]
+    pub fn new(trace: WmiTrace) -> Self {
+        let used = RefCell::new(vec![false; trace.calls.len()]);
+        Self {
+            backend: trace.backend,
+            calls: trace.calls,
+            used,
+        }
+    }
+
+    fn answer(&self, request: &WmiRequest) -> Result<WmiResponse> {
+        let mut used = self.used.borrow_mut();
+        let matching: Vec<usize> = self
+            .calls
+            .iter()
+            .enumerate()
+            .filter(|(_, call)| call.request == *request)
+            .map(|(i, _)| i)
+            .collect();
+        let index = matching
+            .iter()
+            .copied()
+            .find(|&i| !used[i])
+            .or_else(|| matching.last().copied())
+            .ok_or_else(|| NoCrateError::Wmi(format!("{request:?} is not in the trace")))?;
+        used[index] = true;
+        self.calls[index]
+            .response
+            .clone()
+            .map_err(|e| e.to_error(format!("{request:?} failed in the trace")))
+    }
+
+    fn answer_value(&self, request: &WmiRequest) -> Result<u32> {
+        match self.answer(request)? {
+            WmiResponse::Value(v) => Ok(v),
+            other => Err(unexpected(request, &other)),
+        }
+    }
+}
+
+fn unexpected(request: &WmiRequest, response: &WmiResponse) -> NoCrateError {
+    NoCrateError::Wmi(format!(
+        "Trace answers {request:?} with unexpected {response:?}"
+    ))
+}
+
+impl AsusWmi for ReplayWmi {
+    fn backend(&self) -> &AsusWmiBackend {
+        &self.backend
+    }
+
+    fn dsts(&self, device_id: u32) -> Result<u32> {
+        self.answer_value(&WmiRequest::Dsts { device_id })
+    }
+
+    fn devs(&self, device_id: u32, control: u32) -> Result<u32> {
+        self.answer_value(&WmiRequest::Devs { device_id, control })
+    }
+
+    fn exec_method_v2(
+        &self,
+        _object_path: &str,
+        method_name: &str,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<MethodOutput> {
+        let request = WmiRequest::Exec {
+            method: method_name.to_string(),
+            params: params
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), value.into()))
+                .collect(),
+        };
+        match self.answer(&request)? {
+            WmiResponse::Output(values) => Ok(MethodOutput::Simulated(values)),
+            other => Err(unexpected(&request, &other)),
+        }
+    }
+
+    fn method_param_values(
+        &self,
+        _object_path: &str,
+        method_name: &str,
+        param: &str,
+    ) -> Result<Vec<String>> {
+        let request = WmiRequest::ParamValues {
+            method: method_name.to_string(),
+            param: param.to_string(),
+        };
+        match self.answer(&request)? {
+            WmiResponse::Names(names) => Ok(names),
+            other => Err(unexpected(&request, &other)),
+        }
+    }
+
+    fn asushw_sensor_count(&self) -> Result<u32> {
+        self.answer_value(&WmiRequest::AsushwSensorCount)
+    }
+
+    fn asushw_sensor_info(&self, index: u32) -> Result<(u32, u32, u32, String)> {
+        let request = WmiRequest::AsushwSensorInfo { index };
+        match self.answer(&request)? {
+            WmiResponse::SensorInfo(source, kind, data_type, name) => {
+                Ok((source, kind, data_type, name))
+            }
+            other => Err(unexpected(&request, &other)),
+        }
+    }
+
+    fn asushw_update_buffer(&self, source: u32) -> Result<()> {
+        let request = WmiRequest::AsushwUpdateBuffer { source };
+        match self.answer(&request)? {
+            WmiResponse::Done => Ok(()),
+            other => Err(unexpected(&request, &other)),
+        }
+    }
+
+    fn asushw_sensor_value(&self, index: u32) -> Result<u32> {
+        self.answer_value(&WmiRequest::AsushwSensorValue { index })
+    }
+}
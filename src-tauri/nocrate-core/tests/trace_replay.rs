@@ -0,0 +1,46 @@
+//! Replays recorded sensor traces and checks the parsed result still
+//! matches what was seen on the recording machine.
+
+use nocrate_core::i18n::{self, Locale};
+use nocrate_core::trace::SensorTrace;
+use nocrate_core::wmi::trace::WmiResponse;
+
+/// A hand-written ROG laptop trace: thermal profile, two of three fans
+/// and the PPT limits. Failed calls carry error codes, not messages.
+const LAPTOP_TRACE: &str = include_str!("fixtures/laptop-ga402rj.json");
+
+fn load(json: &str) -> SensorTrace {
+    serde_json::from_str(json).expect("fixture is a valid sensor trace")
+}
+
+#[test]
+fn laptop_trace_replays_to_recorded_report() {
+    let replay = load(LAPTOP_TRACE).replay();
+
+    assert!(
+        replay.matches_recording,
+        "replay differs from recording: {:#?}",
+        replay.report
+    );
+}
+
+#[test]
+fn replay_does_not_depend_on_locale() {
+    let trace = load(LAPTOP_TRACE);
+    assert!(trace.synthetic);
+
+    i18n::set_locale(Locale::En);
+    let matches = trace.replay().matches_recording;
+    i18n::set_locale(Locale::default());
+    assert!(matches);
+}
+
+#[test]
+fn changed_answer_is_detected() {
+    let mut trace = load(LAPTOP_TRACE);
+    let calls = &mut trace.wmi.as_mut().expect("fixture has a WMI trace").calls;
+    // Thermal profile: Performance → Silent
+    calls[0].response = Ok(WmiResponse::Value(2));
+
+    assert!(!trace.replay().matches_recording);
+}
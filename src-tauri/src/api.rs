@@ -0,0 +1,296 @@
+/// Local HTTP / WebSocket control API.
+///
+/// Lets scripts and tools such as Stream Deck plugins read sensors and
+/// switch profiles or lighting without the Tauri frontend. The listener
+/// only binds to `127.0.0.1`, and every request must carry the access
+/// token from the config, either as `Authorization: Bearer <token>` or,
+/// for WebSocket clients that cannot set headers, as `?token=<token>`.
+///
+/// - `GET /sensors`: unified sensor snapshot
+/// - `POST /profile`: `{"profile": "silent"}`
+/// - `POST /aura/effect`: same fields as the `aura_set_effect` command
+/// - `GET /ws`: WebSocket pushing `{"sensors", "profile"}` every second
+///
+/// Like the metrics endpoint, the listener follows the config.
+use std::fmt::Write as _;
+use std::io::{Cursor, Read};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+use windows::Win32::Security::Cryptography::{BCryptGenRandom, BCRYPT_USE_SYSTEM_PREFERRED_RNG};
+
+use crate::aura::protocol::{AuraDirection, AuraEffect, AuraSpeed, RgbColor};
+use crate::commands::aura::{stop_direct_output, with_aura};
+use crate::commands::fan::switch_thermal_profile;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::sensors;
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, ThermalProfile};
+
+/// Interval at which the config is checked while waiting for requests.
+const CONFIG_CHECK: Duration = Duration::from_secs(1);
+
+/// Interval between WebSocket pushes.
+const WS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Largest accepted request body.
+const MAX_BODY: u64 = 64 * 1024;
+
+type JsonResponse = Response<Cursor<Vec<u8>>>;
+
+/// Spawn the API listener thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-api".into())
+        .spawn(move || serve_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("API: failed to spawn listener thread: {e}");
+    }
+}
+
+/// Generate a random access token (32 hex digits).
+///
+/// # Errors
+///
+/// Returns an error if the system RNG fails.
+#[allow(unsafe_code)]
+pub fn generate_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    unsafe { BCryptGenRandom(None, &mut bytes, BCRYPT_USE_SYSTEM_PREFERRED_RNG) }
+        .ok()
+        .map_err(|e| NoCrateError::Unknown(i18n::tf(Msg::ApiTokenFailed, &[("error", &e)])))?;
+    Ok(bytes.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    }))
+}
+
+fn serve_loop(app: &AppHandle) {
+    // Port the listener was last started on (also after a failed bind)
+    let mut bound: Option<u16> = None;
+    let mut server: Option<Server> = None;
+
+    loop {
+        let Some(state) = app.try_state::<AppState>() else {
+            thread::sleep(CONFIG_CHECK);
+            continue;
+        };
+        let cfg = state.config.get().api;
+        if cfg.enabled && cfg.token.is_empty() {
+            let saved =
+                generate_token().and_then(|token| state.config.update(|c| c.api.token = token));
+            if let Err(e) = saved {
+                eprintln!("API: {e}");
+            }
+        }
+        let wanted = cfg.enabled.then_some(cfg.port);
+        if wanted != bound {
+            server = None;
+            if let Some(port) = wanted {
+                match Server::http(("127.0.0.1", port)) {
+                    Ok(s) => {
+                        eprintln!("API: listening on http://127.0.0.1:{port}");
+                        server = Some(s);
+                    }
+                    Err(e) => eprintln!("API: failed to listen on port {port}: {e}"),
+                }
+            } else if bound.is_some() {
+                eprintln!("API: listener stopped");
+            }
+            bound = wanted;
+        }
+
+        let Some(s) = &server else {
+            thread::sleep(CONFIG_CHECK);
+            continue;
+        };
+        match s.recv_timeout(CONFIG_CHECK) {
+            Ok(Some(request)) => handle(app, &state, request),
+            Ok(None) => {}
+            Err(e) => eprintln!("API: failed to accept request: {e}"),
+        }
+    }
+}
+
+fn handle(app: &AppHandle, state: &AppState, mut request: Request) {
+    let token = state.config.get().api.token;
+    if token.is_empty() || !authorized(&request, &token) {
+        let _ = request.respond(error(401, "missing or invalid token"));
+        return;
+    }
+
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let response = match (request.method(), path.as_str()) {
+        (Method::Get, "/sensors") => json_response(200, &sensors::collect(state)),
+        (Method::Post, "/profile") => match read_json::<ProfileRequest>(&mut request) {
+            Ok(body) => match switch_thermal_profile(app, state, body.profile) {
+                Ok(()) => json_response(200, &json!({ "profile": body.profile })),
+                Err(e) => error(500, &e.to_string()),
+            },
+            Err(e) => error(400, &e),
+        },
+        (Method::Post, "/aura/effect") => match read_json::<EffectRequest>(&mut request) {
+            Ok(body) => match set_effect(state, &body) {
+                Ok(()) => Response::from_string("").with_status_code(204),
+                Err(e) => error(500, &e.to_string()),
+            },
+            Err(e) => error(400, &e),
+        },
+        (Method::Get, "/ws") => {
+            upgrade_websocket(app, request);
+            return;
+        }
+        _ => error(404, "not found"),
+    };
+    let _ = request.respond(response);
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileRequest {
+    profile: ThermalProfile,
+}
+
+/// Body of `POST /aura/effect`, mirroring `aura_set_effect`.
+#[derive(Debug, Deserialize)]
+struct EffectRequest {
+    effect: AuraEffect,
+    color: Option<RgbColor>,
+    speed: Option<AuraSpeed>,
+    direction: Option<AuraDirection>,
+    device: Option<usize>,
+    channel: Option<u8>,
+}
+
+fn set_effect(state: &AppState, body: &EffectRequest) -> Result<()> {
+    stop_direct_output(state);
+    with_aura(state, body.device, |ctrl| {
+        ctrl.set_effect(
+            body.channel.unwrap_or(0),
+            body.effect,
+            body.color.unwrap_or(RgbColor::WHITE),
+            body.speed.unwrap_or(AuraSpeed::Medium),
+            body.direction.unwrap_or_default(),
+        )
+    })
+}
+
+/// Check the bearer token or `token` query parameter.
+fn authorized(request: &Request, token: &str) -> bool {
+    let header = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "));
+    let query = request
+        .url()
+        .split_once('?')
+        .and_then(|(_, q)| q.split('&').find_map(|kv| kv.strip_prefix("token=")));
+    header
+        .into_iter()
+        .chain(query)
+        .any(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes()))
+}
+
+/// Compare without leaking the position of the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(
+    request: &mut Request,
+) -> std::result::Result<T, String> {
+    let mut body = String::new();
+    let _ = request
+        .as_reader()
+        .take(MAX_BODY)
+        .read_to_string(&mut body)
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+fn json_response(status: u16, body: &impl Serialize) -> JsonResponse {
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+    Response::from_string(serde_json::to_string(body).unwrap_or_default())
+        .with_status_code(status)
+        .with_header(content_type)
+}
+
+fn error(status: u16, message: &str) -> JsonResponse {
+    json_response(status, &json!({ "error": message }))
+}
+
+/// Complete the WebSocket handshake and stream readings on a new thread.
+fn upgrade_websocket(app: &AppHandle, request: Request) {
+    let key = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Sec-WebSocket-Key"))
+        .map(|h| derive_accept_key(h.value.as_bytes()));
+    let Some(accept) = key else {
+        let _ = request.respond(error(400, "expected a WebSocket upgrade"));
+        return;
+    };
+
+    let accept = Header::from_bytes("Sec-WebSocket-Accept", accept).expect("header is valid");
+    let response = Response::empty(StatusCode(101)).with_header(accept);
+    let stream = request.upgrade("websocket", response);
+
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("nocrate-api-ws".into())
+        .spawn(move || {
+            let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+            push_loop(&app, &mut socket);
+        });
+    if let Err(e) = spawned {
+        eprintln!("API: failed to spawn WebSocket thread: {e}");
+    }
+}
+
+/// Push readings until the client goes away, the API is disabled or the
+/// token it connected with is replaced.
+fn push_loop<S: std::io::Read + std::io::Write>(app: &AppHandle, socket: &mut WebSocket<S>) {
+    let Some(token) = app
+        .try_state::<AppState>()
+        .map(|s| s.config.get().api.token)
+    else {
+        return;
+    };
+    loop {
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        let cfg = state.config.get().api;
+        if !cfg.enabled || cfg.token != token {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+            return;
+        }
+        let profile = state
+            .wmi
+            .get()
+            .and_then(|wmi| wmi.execute(asus_mgmt::get_thermal_profile).ok());
+        let message = json!({
+            "sensors": sensors::collect(&state),
+            "profile": profile,
+        });
+        if socket.send(Message::text(message.to_string())).is_err() {
+            return;
+        }
+        thread::sleep(WS_INTERVAL);
+    }
+}
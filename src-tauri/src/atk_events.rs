@@ -0,0 +1,94 @@
+/// Forwarding of ASUS ATK hardware events to the frontend.
+///
+/// The subscription lives on the WMI thread's connection, so it is lost
+/// whenever the connection is re-created; a background thread receives the
+/// events, re-subscribes after each reconnection and emits every event.
+/// The Fn+F5 profile key is handled here, since the firmware leaves the
+/// profile switch to the application.
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::fan::{apply_thermal_profile, remember_thermal_profile};
+use crate::state::AppState;
+use crate::wmi::asus_mgmt;
+use crate::wmi::events::{self, AtkEvent, AtkEventKind};
+
+/// Interval at which the WMI connection is checked for reconnections.
+const RESUBSCRIBE_CHECK: Duration = Duration::from_secs(5);
+
+/// Event emitted to the frontend for every ATK event.
+pub const ATK_EVENT: &str = "atk-event";
+
+/// Event emitted to the frontend with the new profile after a profile key
+/// press switched it.
+pub const THERMAL_PROFILE_CHANGED_EVENT: &str = "thermal-profile-changed";
+
+/// Spawn the ATK event thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-atk-events".into())
+        .spawn(move || event_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("ATK: failed to spawn event thread: {e}");
+    }
+}
+
+fn event_loop(app: &AppHandle) {
+    let (tx, rx) = mpsc::channel::<AtkEvent>();
+    // Reconnection count of the connection holding the subscription
+    let mut subscribed: Option<u32> = None;
+
+    loop {
+        if let Some(state) = app.try_state::<AppState>() {
+            if let Some(wmi) = state.wmi.get() {
+                let reconnects = wmi.health().reconnects;
+                if subscribed != Some(reconnects) {
+                    let sender = tx.clone();
+                    match wmi.execute(move |conn| events::subscribe_atk_events(conn, sender)) {
+                        Ok(()) => eprintln!("ATK: subscribed to hardware events"),
+                        Err(e) => eprintln!("ATK: event subscription unavailable: {e}"),
+                    }
+                    // Not retried until the next reconnection; a missing
+                    // event class does not appear later
+                    subscribed = Some(reconnects);
+                }
+            }
+        }
+
+        match rx.recv_timeout(RESUBSCRIBE_CHECK) {
+            Ok(event) => handle_event(app, event),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+fn handle_event(app: &AppHandle, event: AtkEvent) {
+    let _ = app.emit(ATK_EVENT, event);
+    if event.kind != AtkEventKind::ProfileKey {
+        return;
+    }
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let Some(wmi) = state.wmi.get() else {
+        return;
+    };
+
+    let config = state.config.get();
+    let switched = wmi.execute(move |conn| {
+        let next = asus_mgmt::get_thermal_profile(conn)?.next();
+        apply_thermal_profile(conn, &config, next)?;
+        Ok(next)
+    });
+    match switched {
+        Ok(profile) => {
+            remember_thermal_profile(&state, profile);
+            let _ = app.emit(THERMAL_PROFILE_CHANGED_EVENT, profile);
+        }
+        Err(e) => eprintln!("ATK: failed to switch thermal profile: {e}"),
+    }
+}
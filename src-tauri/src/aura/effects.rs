@@ -0,0 +1,526 @@
+/// Software lighting effects.
+///
+/// Unlike the firmware effects in [`super::protocol::AuraEffect`], these
+/// are computed on the host and streamed to the controller as direct-mode
+/// frames from a background thread. Only one software effect runs at a
+/// time; starting a new one stops the previous.
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::sensors::{self, SensorSource};
+use crate::state::AppState;
+
+use super::ambient::ScreenCapture;
+use super::audio::LoopbackCapture;
+use super::openrgb::OpenRgbOutput;
+pub use super::protocol::EffectTarget;
+use super::protocol::RgbColor;
+use crate::config::AmbilightZone;
+
+/// Interval between direct-mode frames.
+const FRAME_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Retry interval while the target controller is disconnected.
+const RECONNECT_WAIT: Duration = Duration::from_secs(1);
+
+/// Interval between sensor reads for sensor-driven effects.
+const SENSOR_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Fraction of the remaining distance to the target colour covered per
+/// frame, so colour changes fade instead of jumping once per sensor read.
+const FADE_STEP: f32 = 0.15;
+
+/// Interval between desktop captures for the ambient effect.
+const AMBIENT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Upper bound on analysed frequency bands; longer strips share bands.
+const MAX_AUDIO_BANDS: usize = 32;
+
+/// A software effect and its parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SoftwareEffect {
+    /// Map a temperature sensor onto a blue → green → red gradient.
+    Temperature {
+        /// Unified sensor ID (see [`crate::sensors`]).
+        sensor_id: String,
+        /// Temperature (°C) shown as pure blue.
+        min_temp: f32,
+        /// Temperature (°C) shown as pure red.
+        max_temp: f32,
+    },
+    /// Drive per-LED brightness from the spectrum of the audio currently
+    /// playing (WASAPI loopback). Bass maps to the start of the strip.
+    Audio {
+        /// Gain applied to band levels (1.0 = unity).
+        sensitivity: f32,
+        /// Fraction of the previous level kept each frame, `0.0..1.0`;
+        /// higher values decay more slowly.
+        smoothing: f32,
+    },
+    /// Mirror the colours at the screen edges (ambilight). The screen
+    /// region for each channel comes from `AppConfig::ambilight_zones`.
+    Ambient,
+}
+
+/// Currently running effect, returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningEffect {
+    pub effect: SoftwareEffect,
+    pub target: EffectTarget,
+}
+
+struct Worker {
+    running: RunningEffect,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl Worker {
+    /// Signal the render thread to exit and wait for it.
+    fn shut_down(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.handle.join();
+    }
+}
+
+/// Owns the background thread that streams software effects.
+pub struct EffectEngine {
+    worker: Mutex<Option<Worker>>,
+    /// Output brightness in percent, applied to every frame (lowered by
+    /// the lighting schedule).
+    brightness: Arc<AtomicU8>,
+}
+
+impl Default for EffectEngine {
+    fn default() -> Self {
+        Self {
+            worker: Mutex::new(None),
+            brightness: Arc::new(AtomicU8::new(100)),
+        }
+    }
+}
+
+impl EffectEngine {
+    /// Start `effect` on `target`, replacing any running effect.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the effect parameters are invalid, the render
+    /// thread cannot be spawned or the effect input (e.g. audio capture)
+    /// fails to initialize.
+    pub fn start(
+        &self,
+        app: AppHandle,
+        effect: SoftwareEffect,
+        target: EffectTarget,
+    ) -> Result<()> {
+        effect.validate()?;
+        // Held until the new worker is stored, so concurrent starts cannot
+        // both spawn a render thread
+        let mut worker = self.worker.lock();
+        if let Some(w) = worker.take() {
+            w.shut_down();
+        }
+        let state = app.state::<AppState>();
+        if !state.config.get().openrgb.enabled {
+            state.aura_stream.ensure_running(app.clone())?;
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (init_tx, init_rx) = mpsc::channel::<Result<()>>();
+        let handle = {
+            let stop = Arc::clone(&stop);
+            let brightness = Arc::clone(&self.brightness);
+            let effect = effect.clone();
+            thread::Builder::new()
+                .name("nocrate-aura-effect".into())
+                .spawn(move || {
+                    // Renderer state (e.g. the audio capture) is not Send, so
+                    // it is created on this thread and the result reported back
+                    let mut renderer = match Renderer::new(&effect) {
+                        Ok(r) => {
+                            let _ = init_tx.send(Ok(()));
+                            r
+                        }
+                        Err(e) => {
+                            let _ = init_tx.send(Err(e));
+                            return;
+                        }
+                    };
+                    render_loop(&app, &effect, &mut renderer, target, &stop, &brightness);
+                })
+                .map_err(|e| {
+                    NoCrateError::Unknown(i18n::tf(
+                        Msg::ThreadSpawn,
+                        &[("name", &"nocrate-aura-effect"), ("error", &e)],
+                    ))
+                })?
+        };
+
+        init_rx.recv().map_err(|_| {
+            NoCrateError::Unknown(i18n::tf(
+                Msg::ThreadInitDied,
+                &[("name", &"nocrate-aura-effect")],
+            ))
+        })??;
+
+        *worker = Some(Worker {
+            running: RunningEffect { effect, target },
+            stop,
+            handle,
+        });
+        Ok(())
+    }
+
+    /// Stop the running effect, if any, and wait for its thread to exit.
+    pub fn stop(&self) {
+        let worker = self.worker.lock().take();
+        if let Some(w) = worker {
+            w.shut_down();
+        }
+    }
+
+    /// Set the output brightness (0–100 %) of software effects.
+    pub fn set_brightness(&self, percent: u8) {
+        self.brightness.store(percent.min(100), Ordering::Relaxed);
+    }
+
+    /// The effect currently being rendered.
+    pub fn running(&self) -> Option<RunningEffect> {
+        self.worker
+            .lock()
+            .as_ref()
+            .filter(|w| !w.handle.is_finished())
+            .map(|w| w.running.clone())
+    }
+}
+
+impl SoftwareEffect {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Temperature {
+                min_temp, max_temp, ..
+            } => {
+                if min_temp >= max_temp {
+                    return Err(NoCrateError::Config(i18n::tf(
+                        Msg::EffectTempRange,
+                        &[("min", min_temp), ("max", max_temp)],
+                    )));
+                }
+            }
+            Self::Audio {
+                sensitivity,
+                smoothing,
+            } => {
+                if sensitivity.is_nan() || *sensitivity <= 0.0 {
+                    return Err(NoCrateError::Config(i18n::tf(
+                        Msg::EffectSensitivity,
+                        &[("value", sensitivity)],
+                    )));
+                }
+                if !(0.0..1.0).contains(smoothing) {
+                    return Err(NoCrateError::Config(i18n::tf(
+                        Msg::EffectSmoothing,
+                        &[("value", smoothing)],
+                    )));
+                }
+            }
+            Self::Ambient => {}
+        }
+        Ok(())
+    }
+}
+
+/// Per-effect state kept by the render thread between frames.
+enum Renderer {
+    Temperature {
+        current: Option<[f32; 3]>,
+        goal: [f32; 3],
+        last_read: Option<Instant>,
+        /// Source of the selected sensor, the only one read each tick.
+        source: Option<SensorSource>,
+    },
+    Audio {
+        capture: LoopbackCapture,
+        levels: Vec<f32>,
+    },
+    Ambient {
+        capture: ScreenCapture,
+        current: Vec<[f32; 3]>,
+        last_capture: Option<Instant>,
+    },
+}
+
+impl Renderer {
+    fn new(effect: &SoftwareEffect) -> Result<Self> {
+        Ok(match effect {
+            SoftwareEffect::Temperature { sensor_id, .. } => Self::Temperature {
+                current: None,
+                goal: [0.0, 0.0, 1.0],
+                last_read: None,
+                source: SensorSource::from_id(sensor_id),
+            },
+            SoftwareEffect::Audio { .. } => Self::Audio {
+                capture: LoopbackCapture::start()?,
+                levels: Vec::new(),
+            },
+            SoftwareEffect::Ambient => Self::Ambient {
+                capture: ScreenCapture::start()?,
+                current: Vec::new(),
+                last_capture: None,
+            },
+        })
+    }
+
+    /// Compute the next frame for a channel with `led_count` LEDs.
+    fn frame(
+        &mut self,
+        effect: &SoftwareEffect,
+        state: &AppState,
+        target: EffectTarget,
+        led_count: usize,
+    ) -> Vec<RgbColor> {
+        match (self, effect) {
+            (
+                Self::Temperature {
+                    current,
+                    goal,
+                    last_read,
+                    source,
+                },
+                SoftwareEffect::Temperature {
+                    sensor_id,
+                    min_temp,
+                    max_temp,
+                },
+            ) => {
+                if last_read.is_none_or(|t| t.elapsed() >= SENSOR_INTERVAL) {
+                    *last_read = Some(Instant::now());
+                    // Keep the previous colour if the sensor is temporarily unavailable
+                    let snapshot = source.map(|src| sensors::collect_only(state, &[src]));
+                    let reading = snapshot
+                        .as_ref()
+                        .and_then(|snap| snap.sensors.iter().find(|s| &s.id == sensor_id));
+                    if let Some(s) = reading {
+                        let t = ((s.value - min_temp) / (max_temp - min_temp)).clamp(0.0, 1.0);
+                        *goal = temperature_gradient(t);
+                    }
+                }
+                let color = current.map_or(*goal, |c| fade(c, *goal));
+                *current = Some(color);
+                vec![to_rgb(color); led_count]
+            }
+            (
+                Self::Audio { capture, levels },
+                SoftwareEffect::Audio {
+                    sensitivity,
+                    smoothing,
+                },
+            ) => {
+                if let Err(e) = capture.poll() {
+                    eprintln!("AURA effect: audio capture failed: {e}");
+                }
+                let bands = capture.bands(led_count.clamp(1, MAX_AUDIO_BANDS));
+                levels.resize(bands.len(), 0.0);
+                for (level, band) in levels.iter_mut().zip(&bands) {
+                    let target = (band * sensitivity).min(1.0);
+                    // Rise immediately, decay according to smoothing
+                    *level = target.max(*level * smoothing);
+                }
+                (0..led_count)
+                    .map(|led| {
+                        let band = led * levels.len() / led_count.max(1);
+                        let hue = led as f32 / led_count.max(1) as f32;
+                        let [r, g, b] = spectrum_color(hue);
+                        to_rgb([r * levels[band], g * levels[band], b * levels[band]])
+                    })
+                    .collect()
+            }
+            (
+                Self::Ambient {
+                    capture,
+                    current,
+                    last_capture,
+                },
+                SoftwareEffect::Ambient,
+            ) => {
+                if last_capture.is_none_or(|t| t.elapsed() >= AMBIENT_INTERVAL) {
+                    *last_capture = Some(Instant::now());
+                    if let Err(e) = capture.capture() {
+                        eprintln!("AURA effect: screen capture failed: {e}");
+                    }
+                }
+                let zone = state
+                    .config
+                    .get()
+                    .ambilight_zones
+                    .into_iter()
+                    .find(|z| z.device == target.device && z.channel == target.channel)
+                    .unwrap_or_else(|| AmbilightZone::fallback(target.device, target.channel));
+                let goal = capture.sample(&zone, led_count);
+                if current.len() == goal.len() {
+                    for (c, g) in current.iter_mut().zip(&goal) {
+                        *c = fade(*c, *g);
+                    }
+                } else {
+                    *current = goal;
+                }
+                current.iter().copied().map(to_rgb).collect()
+            }
+            _ => unreachable!("renderer is created from the same effect"),
+        }
+    }
+}
+
+/// Where frames are sent: the AURA HID controllers, or an OpenRGB server
+/// in passthrough mode.
+enum Output {
+    Hid,
+    OpenRgb(OpenRgbOutput),
+}
+
+impl Output {
+    /// LED count of the target channel; `Ok(None)` while it is not
+    /// present, `Err` if it can never be rendered to.
+    fn led_count(&mut self, state: &AppState, target: EffectTarget) -> Result<Option<usize>> {
+        match self {
+            Self::Hid => {
+                let aura = state.aura.lock();
+                aura.get(target.device)
+                    .map(|c| {
+                        c.channel(target.channel)
+                            .map(|ch| usize::from(ch.led_count))
+                    })
+                    .transpose()
+            }
+            Self::OpenRgb(output) => output.led_count(&state.config.get().openrgb, target),
+        }
+    }
+
+    fn write(&mut self, state: &AppState, target: EffectTarget, frame: &[RgbColor]) -> Result<()> {
+        match self {
+            // Packet batching and pacing are left to the frame stream
+            Self::Hid => {
+                state.aura_stream.push(target, frame.to_vec());
+                Ok(())
+            }
+            Self::OpenRgb(output) => output.write(target, frame),
+        }
+    }
+}
+
+/// Stream frames until `stop` is set or the target channel disappears.
+fn render_loop(
+    app: &AppHandle,
+    effect: &SoftwareEffect,
+    renderer: &mut Renderer,
+    target: EffectTarget,
+    stop: &AtomicBool,
+    brightness: &AtomicU8,
+) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let mut output = if state.config.get().openrgb.enabled {
+        Output::OpenRgb(OpenRgbOutput::default())
+    } else {
+        Output::Hid
+    };
+
+    // Set while the target is unavailable; the hot-plug monitor replaces
+    // stale controllers, so keep waiting rather than ending the effect
+    let mut waiting = false;
+
+    while !stop.load(Ordering::Relaxed) {
+        let led_count = match output.led_count(&state, target) {
+            Ok(n) => n,
+            Err(e) => {
+                eprintln!("AURA effect: {e}");
+                return;
+            }
+        };
+
+        let result = led_count.map_or_else(
+            || {
+                Err(NoCrateError::Hid(i18n::tf(
+                    Msg::AuraDeviceMissing,
+                    &[("device", &target.device)],
+                )))
+            },
+            |led_count| {
+                // Sensor reads can be slow; compute the frame without holding the AURA lock
+                let mut frame = renderer.frame(effect, &state, target, led_count);
+                let percent = brightness.load(Ordering::Relaxed);
+                if percent < 100 {
+                    for c in &mut frame {
+                        *c = c.scaled(percent);
+                    }
+                }
+                output.write(&state, target, &frame)
+            },
+        );
+
+        match result {
+            Ok(()) => {
+                if waiting {
+                    eprintln!("AURA effect: target available again, resuming");
+                    waiting = false;
+                }
+                thread::sleep(FRAME_INTERVAL);
+            }
+            Err(e) => {
+                if !waiting {
+                    eprintln!("AURA effect: {e}; waiting for reconnect");
+                    waiting = true;
+                }
+                thread::sleep(RECONNECT_WAIT);
+            }
+        }
+    }
+}
+
+/// Blue (0.0) → green (0.5) → red (1.0).
+fn temperature_gradient(t: f32) -> [f32; 3] {
+    if t < 0.5 {
+        let k = t * 2.0;
+        [0.0, k, 1.0 - k]
+    } else {
+        let k = (t - 0.5) * 2.0;
+        [k, 1.0 - k, 0.0]
+    }
+}
+
+/// Hue sweep red (0.0) → yellow → green → cyan → blue (1.0).
+fn spectrum_color(position: f32) -> [f32; 3] {
+    let h = position.clamp(0.0, 1.0) * 4.0;
+    match h {
+        h if h < 1.0 => [1.0, h, 0.0],
+        h if h < 2.0 => [2.0 - h, 1.0, 0.0],
+        h if h < 3.0 => [0.0, 1.0, h - 2.0],
+        h => [0.0, 4.0 - h, 1.0],
+    }
+}
+
+fn fade(from: [f32; 3], to: [f32; 3]) -> [f32; 3] {
+    [0, 1, 2].map(|i| (to[i] - from[i]).mul_add(FADE_STEP, from[i]))
+}
+
+fn to_rgb(c: [f32; 3]) -> RgbColor {
+    // Clamped to 0..=255 before the cast
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    RgbColor {
+        r: channel(c[0]),
+        g: channel(c[1]),
+        b: channel(c[2]),
+    }
+}
@@ -0,0 +1,135 @@
+/// AURA controller hot-plug handling.
+///
+/// A USB reset or resume from sleep invalidates the open HID handles, after
+/// which every write fails. A background thread polls the HID device list
+/// and rediscovers the controllers when the set of AURA interfaces changes
+/// or a handle has failed, then re-applies the last firmware effects.
+/// Interfaces that are present but cannot be opened (held exclusively by
+/// another app) do not count as a change once discovery has tried them.
+/// Polling is paused while OpenRGB passthrough owns the devices and in
+/// simulation mode.
+use std::thread;
+use std::time::Duration;
+
+use hidapi::HidApi;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::Result;
+use crate::state::AppState;
+
+use super::controller::{is_aura_interface, AuraController};
+use super::protocol::AURA_VID;
+use super::schedule;
+
+/// Interval between device list polls.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Event emitted to the frontend after the controller list is rebuilt.
+pub const DEVICES_CHANGED_EVENT: &str = "aura-devices-changed";
+
+/// Spawn the hot-plug monitor thread.
+pub fn spawn_monitor(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-aura-hotplug".into())
+        .spawn(move || monitor_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("AURA: failed to spawn hot-plug monitor: {e}");
+    }
+}
+
+fn monitor_loop(app: &AppHandle) {
+    let mut api = match HidApi::new() {
+        Ok(api) => api,
+        Err(e) => {
+            eprintln!("AURA: hot-plug monitor disabled: {e}");
+            return;
+        }
+    };
+
+    // Interfaces present at the last rediscovery, opened or not; `None`
+    // until the first one, when the open controllers stand in for it
+    let mut attempted: Option<Vec<String>> = None;
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        if state.simulated || state.config.get().openrgb.enabled {
+            continue;
+        }
+
+        // Only AURA VID devices need re-enumerating
+        let _ = api.reset_devices();
+        if let Err(e) = api.add_devices(AURA_VID, 0) {
+            eprintln!("AURA: device enumeration failed: {e}");
+            continue;
+        }
+        let mut present: Vec<String> = api
+            .device_list()
+            .filter(|d| is_aura_interface(d))
+            .map(|d| d.path().to_string_lossy().into_owned())
+            .collect();
+        present.sort_unstable();
+        present.dedup();
+
+        let (mut open, failed) = {
+            let aura = state.aura.lock();
+            let open: Vec<String> = aura.iter().map(|c| c.info().path.clone()).collect();
+            (open, aura.iter().any(AuraController::io_failed))
+        };
+        open.sort_unstable();
+        let known = attempted.as_ref().unwrap_or(&open);
+
+        if present != *known || failed {
+            match reconnect(&state) {
+                Ok(n) => eprintln!("AURA: reconnected, {n} controller(s) available"),
+                Err(e) => eprintln!("AURA: no controllers after device change: {e}"),
+            }
+            attempted = Some(present);
+            let _ = app.emit(DEVICES_CHANGED_EVENT, ());
+        }
+    }
+}
+
+/// Rediscover all controllers, replacing the current list.
+///
+/// Effects last set on a controller are re-applied to the new handle with
+/// the same device path (or, failing that, the same PID). Returns the number
+/// of controllers now available.
+///
+/// # Errors
+///
+/// Returns `Hid` error if no controller can be found; the list is emptied
+/// so commands report the device as unavailable until it returns.
+pub fn reconnect(state: &AppState) -> Result<usize> {
+    let discovered = if state.simulated {
+        Ok(vec![AuraController::simulated()])
+    } else {
+        AuraController::discover_all()
+    };
+
+    let mut aura = state.aura.lock();
+    let controllers = match discovered {
+        Ok(c) => c,
+        Err(e) => {
+            aura.clear();
+            return Err(e);
+        }
+    };
+
+    let calibrations = state.config.get().aura_calibration;
+    for ctrl in &controllers {
+        ctrl.load_calibration(&calibrations);
+        let previous = aura
+            .iter()
+            .find(|old| old.info().path == ctrl.info().path)
+            .or_else(|| aura.iter().find(|old| old.info().pid == ctrl.info().pid));
+        if let Some(old) = previous {
+            ctrl.restore_from(old);
+        }
+        schedule::apply_to(state, ctrl);
+    }
+
+    *aura = controllers;
+    Ok(aura.len())
+}
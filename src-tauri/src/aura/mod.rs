@@ -1,2 +1,8 @@
-pub mod controller;
-pub mod protocol;
+#[cfg(feature = "sio")]
+pub use nocrate_core::aura::dram;
+pub use nocrate_core::aura::{ambient, audio, controller, openrgb, protocol};
+
+pub mod effects;
+pub mod hotplug;
+pub mod schedule;
+pub mod stream;
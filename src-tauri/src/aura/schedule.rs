@@ -0,0 +1,83 @@
+/// Lighting schedule: dim or turn off AURA lighting during a configured
+/// daily window (e.g. at night) and restore it afterwards.
+///
+/// Runs independently of fan profiles. Firmware effects are dimmed on the
+/// controllers without replacing the recorded effects, and software
+/// effects through the effect engine's output brightness. Effects set by
+/// the user inside the window apply at full brightness until the next
+/// window starts.
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::hotkeys;
+use crate::state::AppState;
+
+use super::controller::AuraController;
+
+/// Interval between schedule checks.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Spawn the schedule thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-aura-schedule".into())
+        .spawn(move || schedule_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("AURA: failed to spawn lighting schedule: {e}");
+    }
+}
+
+fn schedule_loop(app: &AppHandle) {
+    // Brightness currently applied by the schedule, if inside the window
+    let mut dimmed: Option<u8> = None;
+
+    loop {
+        if let Some(state) = app.try_state::<AppState>() {
+            let brightness = active_brightness(&state);
+            // Lighting turned off by the hotkey stays off; turning it back
+            // on applies the schedule
+            if brightness != dimmed && !hotkeys::aura_off() {
+                if let Some(b) = brightness {
+                    eprintln!("AURA: lighting schedule active, brightness {b}%");
+                    for ctrl in state.aura.lock().iter() {
+                        ctrl.dim(b);
+                    }
+                    state.aura_effects.set_brightness(b);
+                } else {
+                    eprintln!("AURA: lighting schedule ended, restoring effects");
+                    for ctrl in state.aura.lock().iter() {
+                        ctrl.undim();
+                    }
+                    state.aura_effects.set_brightness(100);
+                }
+                dimmed = brightness;
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Brightness the schedule currently requires, or `None` outside the
+/// window.
+pub fn active_brightness(state: &AppState) -> Option<u8> {
+    let schedule = state.config.get().lighting_schedule;
+    schedule
+        .contains(local_minute())
+        .then_some(schedule.brightness.min(100))
+}
+
+/// Apply the schedule to a newly (re)connected controller.
+pub fn apply_to(state: &AppState, ctrl: &AuraController) {
+    if let Some(b) = active_brightness(state) {
+        ctrl.dim(b);
+    }
+}
+
+/// Minutes since local midnight.
+#[allow(unsafe_code)]
+fn local_minute() -> u16 {
+    let now = unsafe { windows::Win32::System::SystemInformation::GetLocalTime() };
+    now.wHour * 60 + now.wMinute
+}
@@ -0,0 +1,219 @@
+/// Direct-mode frame streaming with frame pacing.
+///
+/// Producers (software effects, external sources via `aura_push_stream_frame`)
+/// only replace the pending frame of a channel; a single flush thread sends
+/// the latest frame of each channel at a fixed rate. Only the packets that
+/// changed since the last flush are written, with the apply flag on the
+/// last one, and unchanged channels are periodically resent in full so
+/// controllers that time out of direct mode keep showing the frame.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::state::AppState;
+
+use super::effects::EffectTarget;
+use super::protocol::RgbColor;
+
+/// Frame rate used when none is requested.
+pub const DEFAULT_FPS: u32 = 30;
+
+/// Highest accepted frame rate; the HID link cannot sustain much more
+/// for several long strips.
+const MAX_FPS: u32 = 60;
+
+/// Interval after which an unchanged frame is resent in full.
+const KEEPALIVE: Duration = Duration::from_secs(1);
+
+/// Per-channel buffers shared with the flush thread.
+#[derive(Default)]
+struct Buffers {
+    /// Latest frame pushed per target, not yet flushed.
+    pending: HashMap<EffectTarget, Vec<RgbColor>>,
+    /// Frame last sent per target and when it was last sent in full.
+    sent: HashMap<EffectTarget, (Vec<RgbColor>, Instant)>,
+}
+
+struct Flusher {
+    fps: u32,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+/// Stream status returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamStatus {
+    pub running: bool,
+    pub fps: u32,
+}
+
+/// Frame buffer and flush thread for direct-mode output.
+#[derive(Default)]
+pub struct FrameStream {
+    buffers: Arc<Mutex<Buffers>>,
+    flusher: Mutex<Option<Flusher>>,
+}
+
+impl FrameStream {
+    /// Start flushing at `fps`, restarting the thread if the rate differs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Config` error for a zero rate, or an error if the flush
+    /// thread cannot be spawned.
+    pub fn start(&self, app: AppHandle, fps: u32) -> Result<()> {
+        if fps == 0 {
+            return Err(NoCrateError::Config(i18n::t(Msg::AuraStreamFps).into()));
+        }
+        let fps = fps.min(MAX_FPS);
+        let mut flusher = self.flusher.lock();
+        if flusher
+            .as_ref()
+            .is_some_and(|f| f.fps == fps && !f.handle.is_finished())
+        {
+            return Ok(());
+        }
+        if let Some(f) = flusher.take() {
+            f.stop.store(true, Ordering::Relaxed);
+            let _ = f.handle.join();
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let stop = Arc::clone(&stop);
+            let buffers = Arc::clone(&self.buffers);
+            thread::Builder::new()
+                .name("nocrate-aura-stream".into())
+                .spawn(move || flush_loop(&app, &buffers, fps, &stop))
+                .map_err(|e| {
+                    NoCrateError::Unknown(i18n::tf(
+                        Msg::ThreadSpawn,
+                        &[("name", &"nocrate-aura-stream"), ("error", &e)],
+                    ))
+                })?
+        };
+        *flusher = Some(Flusher { fps, stop, handle });
+        Ok(())
+    }
+
+    /// Stop flushing and drop all buffered frames, so a firmware effect
+    /// set afterwards is not overwritten by a stale frame.
+    pub fn stop(&self) {
+        let flusher = self.flusher.lock().take();
+        if let Some(f) = flusher {
+            f.stop.store(true, Ordering::Relaxed);
+            let _ = f.handle.join();
+        }
+        let mut buffers = self.buffers.lock();
+        buffers.pending.clear();
+        buffers.sent.clear();
+    }
+
+    /// Start at [`DEFAULT_FPS`] unless already running at any rate.
+    pub fn ensure_running(&self, app: AppHandle) -> Result<()> {
+        if self.status().running {
+            return Ok(());
+        }
+        self.start(app, DEFAULT_FPS)
+    }
+
+    /// Queue a frame for `target`, replacing any frame not yet flushed.
+    pub fn push(&self, target: EffectTarget, colors: Vec<RgbColor>) {
+        let _ = self.buffers.lock().pending.insert(target, colors);
+    }
+
+    /// Whether the flush thread is running, and at what rate.
+    pub fn status(&self) -> StreamStatus {
+        self.flusher
+            .lock()
+            .as_ref()
+            .filter(|f| !f.handle.is_finished())
+            .map_or(
+                StreamStatus {
+                    running: false,
+                    fps: 0,
+                },
+                |f| StreamStatus {
+                    running: true,
+                    fps: f.fps,
+                },
+            )
+    }
+}
+
+fn flush_loop(app: &AppHandle, buffers: &Mutex<Buffers>, fps: u32, stop: &AtomicBool) {
+    let interval = Duration::from_secs(1) / fps;
+    let mut next = Instant::now();
+
+    while !stop.load(Ordering::Relaxed) {
+        if let Some(state) = app.try_state::<AppState>() {
+            flush(&state, buffers);
+        }
+
+        // Pace against the schedule rather than sleeping a fixed interval,
+        // so slow HID writes do not lower the effective frame rate
+        next += interval;
+        let now = Instant::now();
+        if next > now {
+            thread::sleep(next - now);
+        } else {
+            next = now;
+        }
+    }
+}
+
+/// Send the pending frame of every channel, or a keepalive for channels
+/// whose frame has not changed in [`KEEPALIVE`].
+fn flush(state: &AppState, buffers: &Mutex<Buffers>) {
+    let mut buffers = buffers.lock();
+    let Buffers { pending, sent } = &mut *buffers;
+
+    let mut frames: Vec<(EffectTarget, Vec<RgbColor>, bool)> = pending
+        .drain()
+        .map(|(target, colors)| (target, colors, false))
+        .collect();
+    for (target, (colors, at)) in sent.iter() {
+        if at.elapsed() >= KEEPALIVE && !frames.iter().any(|(t, ..)| t == target) {
+            frames.push((*target, colors.clone(), true));
+        }
+    }
+    if frames.is_empty() {
+        return;
+    }
+
+    let aura = state.aura.lock();
+    for (target, colors, full) in frames {
+        let Some(ctrl) = aura.get(target.device) else {
+            let _ = sent.remove(&target);
+            continue;
+        };
+        let previous = if full {
+            None
+        } else {
+            sent.get(&target).map(|(prev, _)| prev.as_slice())
+        };
+        match ctrl.set_direct_colors_delta(target.channel, &colors, previous) {
+            Ok(()) => {
+                let at = if previous.is_none() {
+                    Instant::now()
+                } else {
+                    sent.get(&target).map_or_else(Instant::now, |(_, at)| *at)
+                };
+                let _ = sent.insert(target, (colors, at));
+            }
+            Err(e) => {
+                // Resend in full once the device is back
+                eprintln!("AURA stream: {e}");
+                let _ = sent.remove(&target);
+            }
+        }
+    }
+}
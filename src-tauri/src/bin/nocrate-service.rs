@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    nocrate_lib::service::main()
+}
@@ -0,0 +1,102 @@
+/// `nocrate-cli`: command-line control of fans, profile and lighting.
+///
+/// Requests go to the running app over the [`ipc`](crate::ipc) pipe, so
+/// they do not fight it for the hardware. When the app is not running,
+/// the CLI opens the needed subsystems itself and runs the same code.
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use crate::aura::controller::AuraController;
+use crate::board::{self, BoardInfo};
+use crate::config::ConfigStore;
+use crate::i18n::{self, Msg};
+use crate::ipc::{self, Reply, Request};
+use crate::state::WmiThread;
+use crate::wmi::asus_mgmt::ThermalProfile;
+
+/// Same directory as Tauri's `app_data_dir` for the app identifier.
+const APP_IDENTIFIER: &str = "com.xiaoxiao.nocrate";
+
+const USAGE: &str = "\
+Usage: nocrate-cli <command>
+
+Commands:
+  fans                  Show fan speeds
+  profile               Show the thermal profile
+  profile <name>        Switch to standard, performance or silent
+  aura off              Turn off all AURA lighting
+  show                  Bring the NoCrate window to the front";
+
+/// Entry point of the `nocrate-cli` binary.
+#[must_use]
+pub fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(request) = parse(&args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let reply = ipc::send(request).unwrap_or_else(|| run_direct(request));
+    match reply {
+        Ok(reply) => {
+            print(&reply);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Option<Request> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["fans"] => Some(Request::Fans),
+        ["profile"] => Some(Request::Profile { profile: None }),
+        ["profile", name] => ThermalProfile::ALL
+            .into_iter()
+            .find(|p| p.as_str() == *name)
+            .map(|p| Request::Profile { profile: Some(p) }),
+        ["aura", "off"] => Some(Request::AuraOff),
+        ["show"] => Some(Request::Show),
+        _ => None,
+    }
+}
+
+fn print(reply: &Reply) {
+    match reply {
+        Reply::Fans(fans) => {
+            for fan in fans {
+                println!("{:<4} {:>5} RPM", fan.target.as_str(), fan.rpm);
+            }
+        }
+        Reply::Profile(profile) => println!("{}", profile.as_str()),
+        Reply::Done => {}
+    }
+}
+
+/// Run the request without the app, opening only what it needs.
+fn run_direct(request: Request) -> Result<Reply, String> {
+    let app_data_dir = env::var_os("APPDATA")
+        .map(|dir| PathBuf::from(dir).join(APP_IDENTIFIER))
+        .ok_or("APPDATA is not set")?;
+    let config = ConfigStore::init(app_data_dir)
+        .map_err(|e| e.to_string())?
+        .get();
+
+    if matches!(request, Request::AuraOff) {
+        // In passthrough mode the HID devices belong to OpenRGB
+        if config.openrgb.enabled {
+            return Err(i18n::t(Msg::OpenRgbPassthrough).into());
+        }
+        let aura = AuraController::discover_all().map_err(|e| e.to_string())?;
+        return ipc::execute(None, &aura, &config, request);
+    }
+
+    let board = BoardInfo::detect();
+    let quirks = board::quirks_for(board.as_ref().map(|b| b.product.as_str()));
+    let wmi = WmiThread::spawn(quirks.bad_device_ids).map_err(|e| e.to_string())?;
+    ipc::execute(Some(&wmi), &[], &config, request)
+}
@@ -1,70 +1,404 @@
 /// AURA ARGB control commands exposed to the frontend.
 ///
 /// All operations acquire the `AppState::aura` Mutex and delegate to
-/// `AuraController` methods. If no controller was discovered at
-/// startup, commands return an error.
-use tauri::State;
+/// `AuraController` methods. Every `aura_*` command takes an optional
+/// `device` index into the discovered controller list (default 0). If
+/// no controller was discovered at startup, commands return an error.
+use tauri::{AppHandle, State};
 
-use crate::aura::controller::AuraDeviceInfo;
-use crate::aura::protocol::{AuraEffect, AuraSpeed, RgbColor};
+use crate::aura::controller::{AuraController, AuraDeviceInfo};
+#[cfg(feature = "sio")]
+use crate::aura::dram::{DramController, DramDeviceInfo};
+use crate::aura::effects::{EffectTarget, RunningEffect, SoftwareEffect};
+use crate::aura::hotplug;
+use crate::aura::openrgb::{self, OpenRgbDevice};
+use crate::aura::protocol::{
+    AuraChannelInfo, AuraDirection, AuraEffect, AuraSpeed, CalibrationPattern, RgbColor,
+};
+use crate::aura::stream::{self, StreamStatus};
+use crate::error::NoCrateError;
+use crate::i18n::{self, Msg};
 use crate::state::AppState;
 
-/// Helper: borrow the AURA controller or return an error string.
-fn with_aura<T>(
-    state: &State<'_, AppState>,
-    f: impl FnOnce(&crate::aura::controller::AuraController) -> crate::error::Result<T>,
-) -> Result<T, String> {
-    let guard = state.aura.lock();
-    let ctrl = guard
-        .as_ref()
-        .ok_or_else(|| "AURA controller not available".to_string())?;
-    f(ctrl).map_err(Into::into)
+/// Helper: borrow the AURA controller at `device` (default 0) or return
+/// an error string.
+///
+/// If the call fails because the HID handle went stale (USB reset, resume
+/// from sleep), the controllers are rediscovered and the call retried once.
+pub fn with_aura<T>(
+    state: &AppState,
+    device: Option<usize>,
+    f: impl Fn(&AuraController) -> crate::error::Result<T>,
+) -> Result<T, NoCrateError> {
+    let index = device.unwrap_or(0);
+    let call = || {
+        let guard = state.aura.lock();
+        let ctrl = guard.get(index).ok_or_else(|| {
+            if guard.is_empty() {
+                NoCrateError::DeviceNotPresent(i18n::t(Msg::AuraNotAvailable).into())
+            } else {
+                NoCrateError::Unknown(i18n::tf(
+                    Msg::AuraDeviceRange,
+                    &[("index", &index), ("count", &guard.len())],
+                ))
+            }
+        })?;
+        Ok::<_, NoCrateError>((f(ctrl), ctrl.io_failed()))
+    };
+
+    match call()? {
+        (Err(e), true) => {
+            eprintln!("AURA: {e}; reconnecting");
+            if hotplug::reconnect(state).is_err() {
+                return Err(e);
+            }
+            call()?.0
+        }
+        (result, _) => result,
+    }
 }
 
-/// Check whether an AURA controller is connected.
+/// Helper: save an effect set on the first channel of the first device
+/// as the last AURA effect, restored at startup. `None` keeps the saved
+/// colour or speed.
+fn remember_effect(
+    state: &AppState,
+    device: Option<usize>,
+    channel: Option<u8>,
+    effect: AuraEffect,
+    color: Option<RgbColor>,
+    speed: Option<AuraSpeed>,
+) {
+    if device.unwrap_or(0) != 0 || channel.unwrap_or(0) != 0 {
+        return;
+    }
+    let name = |v: serde_json::Value| v.as_str().map(str::to_owned);
+    let updated = state.config.update(|cfg| {
+        if let Some(v) = serde_json::to_value(effect).ok().and_then(name) {
+            cfg.last_aura_effect = v;
+        }
+        if let Some(c) = color {
+            cfg.last_aura_color = c.to_hex();
+        }
+        if let Some(v) = speed
+            .and_then(|s| serde_json::to_value(s).ok())
+            .and_then(name)
+        {
+            cfg.last_aura_speed = v;
+        }
+    });
+    if let Err(e) = updated {
+        eprintln!("Warning: failed to save AURA effect: {e}");
+    }
+}
+
+/// Helper: stop the software effect and the frame stream so neither
+/// overwrites the firmware effect or frame about to be set.
+pub fn stop_direct_output(state: &AppState) {
+    state.aura_effects.stop();
+    state.aura_stream.stop();
+}
+
+/// Check whether at least one AURA controller is connected.
 #[tauri::command]
 pub fn aura_is_available(state: State<'_, AppState>) -> bool {
-    state.aura.lock().is_some()
+    !state.aura.lock().is_empty()
+}
+
+/// Get info about every connected AURA controller.
+#[tauri::command]
+pub fn aura_get_device_info(state: State<'_, AppState>) -> Vec<AuraDeviceInfo> {
+    state
+        .aura
+        .lock()
+        .iter()
+        .map(|ctrl| ctrl.info().clone())
+        .collect()
 }
 
-/// Get info about the connected AURA controller.
+/// List the lighting channels (onboard zone, RGB / ARGB headers) of a controller.
 #[tauri::command]
-pub fn aura_get_device_info(state: State<'_, AppState>) -> Result<AuraDeviceInfo, String> {
-    let guard = state.aura.lock();
-    let ctrl = guard
-        .as_ref()
-        .ok_or_else(|| "AURA controller not available".to_string())?;
-    Ok(ctrl.info().clone())
+pub fn aura_get_channels(
+    state: State<'_, AppState>,
+    device: Option<usize>,
+) -> Result<Vec<AuraChannelInfo>, NoCrateError> {
+    with_aura(&state, device, |ctrl| Ok(ctrl.channels().to_vec()))
 }
 
-/// Set an effect mode with colour and speed.
+/// Set an effect mode with colour, speed and direction (default forward)
+/// on a channel (default 0). Fails if the device or channel does not
+/// support the effect.
 #[tauri::command]
 pub fn aura_set_effect(
     state: State<'_, AppState>,
     effect: AuraEffect,
     color: RgbColor,
     speed: AuraSpeed,
-) -> Result<(), String> {
-    with_aura(&state, |ctrl| ctrl.set_effect(effect, color, speed))
+    direction: Option<AuraDirection>,
+    device: Option<usize>,
+    channel: Option<u8>,
+) -> Result<(), NoCrateError> {
+    stop_direct_output(&state);
+    with_aura(&state, device, |ctrl| {
+        ctrl.set_effect(
+            channel.unwrap_or(0),
+            effect,
+            color,
+            speed,
+            direction.unwrap_or_default(),
+        )
+    })?;
+    remember_effect(&state, device, channel, effect, Some(color), Some(speed));
+    Ok(())
 }
 
-/// Set a static solid colour on all LEDs.
+/// Set a static solid colour on all LEDs of a channel (default 0).
 #[tauri::command]
-pub fn aura_set_static_color(state: State<'_, AppState>, color: RgbColor) -> Result<(), String> {
-    with_aura(&state, |ctrl| ctrl.set_static_color(color))
+pub fn aura_set_static_color(
+    state: State<'_, AppState>,
+    color: RgbColor,
+    device: Option<usize>,
+    channel: Option<u8>,
+) -> Result<(), NoCrateError> {
+    stop_direct_output(&state);
+    with_aura(&state, device, |ctrl| {
+        ctrl.set_static_color(channel.unwrap_or(0), color)
+    })?;
+    remember_effect(
+        &state,
+        device,
+        channel,
+        AuraEffect::Static,
+        Some(color),
+        None,
+    );
+    Ok(())
 }
 
-/// Turn all LEDs off.
+/// Turn all LEDs of a channel (default 0) off.
 #[tauri::command]
-pub fn aura_turn_off(state: State<'_, AppState>) -> Result<(), String> {
-    with_aura(&state, |ctrl| ctrl.turn_off())
+pub fn aura_turn_off(
+    state: State<'_, AppState>,
+    device: Option<usize>,
+    channel: Option<u8>,
+) -> Result<(), NoCrateError> {
+    stop_direct_output(&state);
+    with_aura(&state, device, |ctrl| ctrl.turn_off(channel.unwrap_or(0)))?;
+    remember_effect(&state, device, channel, AuraEffect::Off, None, None);
+    Ok(())
 }
 
-/// Set individual LED colours in direct mode.
+/// Set individual LED colours on a channel (default 0) in direct mode.
 #[tauri::command]
 pub fn aura_set_direct_colors(
     state: State<'_, AppState>,
     colors: Vec<RgbColor>,
-) -> Result<(), String> {
-    with_aura(&state, |ctrl| ctrl.set_direct_colors(&colors))
+    device: Option<usize>,
+    channel: Option<u8>,
+) -> Result<(), NoCrateError> {
+    stop_direct_output(&state);
+    with_aura(&state, device, |ctrl| {
+        ctrl.set_direct_colors(channel.unwrap_or(0), &colors)
+    })
+}
+
+/// Show a calibration test pattern on a channel (default 0) using the
+/// device's current gamma / white-point correction. Save a new
+/// correction via `update_config` and call again to compare.
+#[tauri::command]
+pub fn aura_show_calibration_pattern(
+    state: State<'_, AppState>,
+    pattern: CalibrationPattern,
+    device: Option<usize>,
+    channel: Option<u8>,
+) -> Result<(), NoCrateError> {
+    stop_direct_output(&state);
+    let channel = channel.unwrap_or(0);
+    with_aura(&state, device, |ctrl| {
+        let led_count = usize::from(ctrl.channel(channel)?.led_count);
+        ctrl.set_direct_colors(channel, &pattern.colors(led_count))
+    })
+}
+
+/// Persist the current effects of a controller to its flash so lighting
+/// survives reboots without the app running.
+#[tauri::command]
+pub fn aura_save_to_flash(
+    state: State<'_, AppState>,
+    device: Option<usize>,
+) -> Result<(), NoCrateError> {
+    with_aura(&state, device, AuraController::save_to_flash)
+}
+
+/// Start a software effect streamed from the host (e.g. temperature
+/// reactive lighting), replacing any running one. The firmware / direct
+/// commands above stop it automatically.
+#[tauri::command]
+pub fn aura_start_software_effect(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    effect: SoftwareEffect,
+    device: Option<usize>,
+    channel: Option<u8>,
+) -> Result<(), NoCrateError> {
+    let target = EffectTarget {
+        device: device.unwrap_or(0),
+        channel: channel.unwrap_or(0),
+    };
+    // Validate the target up front so the frontend gets an error instead of a silent stop
+    let openrgb = state.config.get().openrgb;
+    if openrgb.enabled {
+        let devices = openrgb::list_devices(&openrgb)?;
+        let zones = devices
+            .get(target.device)
+            .map(|d| d.zones.len())
+            .ok_or_else(|| i18n::tf(Msg::OpenRgbDeviceRange, &[("device", &target.device)]))?;
+        if usize::from(target.channel) >= zones {
+            return Err(i18n::tf(
+                Msg::OpenRgbZoneRange,
+                &[
+                    ("zone", &target.channel),
+                    ("zones", &zones),
+                    ("device", &target.device),
+                ],
+            )
+            .into());
+        }
+    } else {
+        with_aura(&state, Some(target.device), |ctrl| {
+            ctrl.channel(target.channel).map(|_| ())
+        })?;
+    }
+    state.aura_effects.start(app, effect, target)
+}
+
+/// Start the direct-mode frame stream at `fps` (default 30, max 60).
+/// Frames pushed with `aura_push_stream_frame` are then sent at that rate,
+/// only the changed packets of each frame being written.
+#[tauri::command]
+pub fn aura_start_stream(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    fps: Option<u32>,
+) -> Result<(), NoCrateError> {
+    state.aura_effects.stop();
+    state
+        .aura_stream
+        .start(app, fps.unwrap_or(stream::DEFAULT_FPS))
+}
+
+/// Queue a frame for a channel (default 0); replaces any frame not yet
+/// sent.
+#[tauri::command]
+pub fn aura_push_stream_frame(
+    state: State<'_, AppState>,
+    colors: Vec<RgbColor>,
+    device: Option<usize>,
+    channel: Option<u8>,
+) -> Result<(), NoCrateError> {
+    if !state.aura_stream.status().running {
+        return Err(i18n::t(Msg::AuraStreamNotStarted).into());
+    }
+    state.aura_stream.push(
+        EffectTarget {
+            device: device.unwrap_or(0),
+            channel: channel.unwrap_or(0),
+        },
+        colors,
+    );
+    Ok(())
+}
+
+/// Stop the frame stream and discard buffered frames.
+#[tauri::command]
+pub fn aura_stop_stream(state: State<'_, AppState>) {
+    stop_direct_output(&state);
+}
+
+/// Get whether the frame stream is running, and its rate.
+#[tauri::command]
+pub fn aura_get_stream_status(state: State<'_, AppState>) -> StreamStatus {
+    state.aura_stream.status()
+}
+
+/// Stop the running software effect, if any.
+#[tauri::command]
+pub fn aura_stop_software_effect(state: State<'_, AppState>) {
+    state.aura_effects.stop();
+}
+
+/// Get the running software effect, if any.
+#[tauri::command]
+pub fn aura_get_software_effect(state: State<'_, AppState>) -> Option<RunningEffect> {
+    state.aura_effects.running()
+}
+
+/// List the controllers of the configured OpenRGB server, whose indices
+/// are used as software effect targets in passthrough mode.
+#[tauri::command]
+pub fn aura_openrgb_get_devices(
+    state: State<'_, AppState>,
+) -> Result<Vec<OpenRgbDevice>, NoCrateError> {
+    openrgb::list_devices(&state.config.get().openrgb)
+}
+
+/// Helper: borrow the DRAM lighting controller at `device` (default 0).
+#[cfg(feature = "sio")]
+fn with_dram<T>(
+    state: &State<'_, AppState>,
+    device: Option<usize>,
+    f: impl FnOnce(&DramController) -> crate::error::Result<T>,
+) -> Result<T, NoCrateError> {
+    let index = device.unwrap_or(0);
+    let modules = state.aura_dram.read();
+    let ctrl = modules.get(index).ok_or_else(|| {
+        if modules.is_empty() {
+            NoCrateError::DeviceNotPresent(i18n::t(Msg::AuraDramNotAvailable).into())
+        } else {
+            NoCrateError::Unknown(i18n::tf(
+                Msg::AuraDramRange,
+                &[("index", &index), ("count", &modules.len())],
+            ))
+        }
+    })?;
+    f(ctrl)
+}
+
+/// Get info about every detected AURA DRAM module.
+#[cfg(feature = "sio")]
+#[tauri::command]
+pub fn aura_get_dram_devices(state: State<'_, AppState>) -> Vec<DramDeviceInfo> {
+    state
+        .aura_dram
+        .read()
+        .iter()
+        .map(|c| c.info().clone())
+        .collect()
+}
+
+/// Set an effect mode with colour and speed on a DRAM module (default 0).
+#[cfg(feature = "sio")]
+#[tauri::command]
+pub fn aura_dram_set_effect(
+    state: State<'_, AppState>,
+    effect: AuraEffect,
+    color: RgbColor,
+    speed: AuraSpeed,
+    direction: Option<AuraDirection>,
+    device: Option<usize>,
+) -> Result<(), NoCrateError> {
+    with_dram(&state, device, |ctrl| {
+        ctrl.set_effect(effect, color, speed, direction.unwrap_or_default())
+    })
+}
+
+/// Set individual LED colours on a DRAM module (default 0) in direct mode.
+#[cfg(feature = "sio")]
+#[tauri::command]
+pub fn aura_dram_set_direct_colors(
+    state: State<'_, AppState>,
+    colors: Vec<RgbColor>,
+    device: Option<usize>,
+) -> Result<(), NoCrateError> {
+    with_dram(&state, device, |ctrl| ctrl.set_direct_colors(&colors))
 }
@@ -0,0 +1,140 @@
+/// Backend capability matrix.
+///
+/// The frontend used to guess which features work from the backend
+/// string alone, which cannot tell a laptop with a custom fan curve from
+/// one without, or a desktop that lost its Super I/O driver.
+/// `get_capabilities` probes the WMI backend once and combines the result
+/// with the subsystems that initialized at startup.
+use serde::Serialize;
+use tauri::State;
+
+use crate::error::NoCrateError;
+use crate::state::{AppState, WmiThreadAsync};
+use crate::wmi::asus_mgmt::{self, FanTarget};
+use crate::wmi::connection::AsusWmiBackend;
+use crate::wmi::ppt::{self, PowerLimits};
+
+/// What the detected hardware supports.
+#[derive(Debug, Clone, Default, Serialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Capabilities {
+    /// `"desktop"`, `"laptop"`, `"asushw"` or `"unavailable"`, as
+    /// returned by `get_wmi_backend`.
+    pub backend: String,
+    pub is_laptop: bool,
+    pub is_desktop: bool,
+    /// The firmware reports a thermal profile that can be switched.
+    pub can_set_thermal_profile: bool,
+    /// Desktop fan headers accept a manual duty curve.
+    pub can_set_fan_duty: bool,
+    /// Fan curves can be written (laptop curve banks or desktop headers).
+    pub has_curve_write: bool,
+    /// Desktop fan headers can be calibrated.
+    pub has_fan_calibration: bool,
+    /// Laptop battery charge limit.
+    pub has_battery_limit: bool,
+    /// Laptop GPU MUX or dGPU switch.
+    pub has_gpu_mode: bool,
+    /// Laptop PPT power limits.
+    pub has_power_limits: bool,
+    /// The ASUSHW sensor class is present.
+    pub has_asushw_sensors: bool,
+    /// Super I/O fan and temperature readings.
+    pub has_sio: bool,
+    /// Embedded controller sensors.
+    pub has_ec: bool,
+    /// At least one AURA controller is connected.
+    pub has_aura: bool,
+    /// AURA memory modules on the memory bus.
+    pub has_aura_dram: bool,
+    /// NVIDIA / AMD GPU sensors.
+    pub has_gpu_sensors: bool,
+    /// Drive temperatures.
+    pub has_storage_sensors: bool,
+}
+
+/// Backend-dependent part, probed on the WMI thread.
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct WmiCapabilities {
+    backend: String,
+    is_laptop: bool,
+    is_desktop: bool,
+    can_set_thermal_profile: bool,
+    has_desktop_fans: bool,
+    has_laptop_curves: bool,
+    has_gpu_mode: bool,
+    has_power_limits: bool,
+    has_asushw_sensors: bool,
+}
+
+/// Probe which features the detected backends support.
+#[tauri::command]
+pub async fn get_capabilities(state: State<'_, AppState>) -> Result<Capabilities, NoCrateError> {
+    let wmi = match state.wmi.get() {
+        Some(wmi) => {
+            wmi.execute_async(|conn| {
+                let backend = conn.backend.backend_type().to_string();
+                let (is_laptop, is_desktop) = match &conn.backend {
+                    AsusWmiBackend::Laptop { .. } => (true, false),
+                    AsusWmiBackend::Desktop { .. } | AsusWmiBackend::AsusHW { .. } => (false, true),
+                };
+                let profile = asus_mgmt::get_thermal_profile(conn).ok();
+                let has_laptop_curves = is_laptop
+                    && profile.is_some_and(|profile| {
+                        FanTarget::ALL.into_iter().any(|target| {
+                            matches!(
+                                asus_mgmt::get_laptop_fan_curve(conn, target, profile),
+                                Ok(Some(_))
+                            )
+                        })
+                    });
+                Ok(WmiCapabilities {
+                    backend,
+                    is_laptop,
+                    is_desktop,
+                    can_set_thermal_profile: profile.is_some(),
+                    has_desktop_fans: !asus_mgmt::get_all_desktop_fan_policies(conn).is_empty(),
+                    has_laptop_curves,
+                    has_gpu_mode: asus_mgmt::get_gpu_mode(conn).is_ok(),
+                    has_power_limits: ppt::get_power_limits(conn)
+                        .is_ok_and(|limits| limits != PowerLimits::default()),
+                    has_asushw_sensors: !asus_mgmt::get_asushw_sensors(conn).is_empty(),
+                })
+            })
+            .await?
+        }
+        None => WmiCapabilities {
+            backend: "unavailable".to_string(),
+            ..WmiCapabilities::default()
+        },
+    };
+
+    #[cfg_attr(not(feature = "sio"), allow(unused_mut))]
+    let mut caps = Capabilities {
+        backend: wmi.backend,
+        is_laptop: wmi.is_laptop,
+        is_desktop: wmi.is_desktop,
+        can_set_thermal_profile: wmi.can_set_thermal_profile,
+        can_set_fan_duty: wmi.has_desktop_fans,
+        has_curve_write: wmi.has_desktop_fans || wmi.has_laptop_curves,
+        has_fan_calibration: wmi.has_desktop_fans,
+        has_battery_limit: wmi.is_laptop,
+        has_gpu_mode: wmi.has_gpu_mode,
+        has_power_limits: wmi.has_power_limits,
+        has_asushw_sensors: wmi.has_asushw_sensors,
+        has_aura: !state.aura.lock().is_empty(),
+        has_gpu_sensors: state.gpu.is_some(),
+        has_storage_sensors: state.storage.is_some(),
+        ..Capabilities::default()
+    };
+
+    #[cfg(feature = "sio")]
+    {
+        caps.has_sio = state.sio.is_some();
+        caps.has_ec = state.ec.is_some();
+        caps.has_aura_dram = !state.aura_dram.read().is_empty();
+    }
+
+    Ok(caps)
+}
@@ -1,11 +1,21 @@
+use std::collections::HashMap;
+
 use tauri::State;
 
-use crate::config::AppConfig;
+use crate::aura::hotplug;
+use crate::aura::protocol::ColorCalibration;
+use crate::config::{
+    AmbilightZone, ApiConfig, AppConfig, ConfigBackup, ConfigStore, EcoConfig, FanStallConfig, HotkeyConfig,
+    LightingSchedule, Locale, MetricsConfig, MqttConfig, OpenRgbConfig, OverlayConfig, PollingTiers,
+    PowerSourceConfig, RtssConfig, SensorCalibration, SioChannelConfig, ThermalFailsafeConfig,
+    UnitsConfig, UpdateConfig,
+};
+use crate::error::NoCrateError;
 use crate::state::AppState;
 
 /// Get the full application configuration.
 #[tauri::command]
-pub fn get_config(state: State<'_, AppState>) -> Result<AppConfig, String> {
+pub fn get_config(state: State<'_, AppState>) -> Result<AppConfig, NoCrateError> {
     Ok(state.config.get())
 }
 
@@ -15,7 +25,9 @@ pub fn update_config(
     state: State<'_, AppState>,
     theme: Option<String>,
     close_to_tray: Option<bool>,
+    start_minimized: Option<bool>,
     auto_start: Option<bool>,
+    restore_on_startup: Option<bool>,
     fan_poll_interval_ms: Option<u64>,
     last_thermal_profile: Option<u8>,
     last_aura_effect: Option<String>,
@@ -23,40 +35,189 @@ pub fn update_config(
     last_aura_speed: Option<String>,
     temp_alert_enabled: Option<bool>,
     temp_alert_threshold: Option<u8>,
-) -> Result<AppConfig, String> {
-    state
-        .config
-        .update(|cfg| {
-            if let Some(v) = theme {
-                cfg.theme = v;
-            }
-            if let Some(v) = close_to_tray {
-                cfg.close_to_tray = v;
-            }
-            if let Some(v) = auto_start {
-                cfg.auto_start = v;
-            }
-            if let Some(v) = fan_poll_interval_ms {
-                cfg.fan_poll_interval_ms = v;
-            }
-            if let Some(v) = last_thermal_profile {
-                cfg.last_thermal_profile = v;
-            }
-            if let Some(v) = last_aura_effect {
-                cfg.last_aura_effect = v;
-            }
-            if let Some(v) = last_aura_color {
-                cfg.last_aura_color = v;
-            }
-            if let Some(v) = last_aura_speed {
-                cfg.last_aura_speed = v;
-            }
-            if let Some(v) = temp_alert_enabled {
-                cfg.temp_alert_enabled = v;
-            }
-            if let Some(v) = temp_alert_threshold {
-                cfg.temp_alert_threshold = v;
-            }
-        })
-        .map_err(|e| e.to_string())
+    fan_stall: Option<FanStallConfig>,
+    thermal_failsafe: Option<ThermalFailsafeConfig>,
+    power_source: Option<PowerSourceConfig>,
+    sio_channels: Option<SioChannelConfig>,
+    sensor_calibration: Option<HashMap<String, SensorCalibration>>,
+    sensor_smoothing: Option<HashMap<String, u16>>,
+    sensor_polling: Option<PollingTiers>,
+    units: Option<UnitsConfig>,
+    locale: Option<Locale>,
+    eco: Option<EcoConfig>,
+    ambilight_zones: Option<Vec<AmbilightZone>>,
+    openrgb: Option<OpenRgbConfig>,
+    lighting_schedule: Option<LightingSchedule>,
+    aura_calibration: Option<HashMap<String, ColorCalibration>>,
+    rtss: Option<RtssConfig>,
+    overlay: Option<OverlayConfig>,
+    metrics: Option<MetricsConfig>,
+    mqtt: Option<MqttConfig>,
+    api: Option<ApiConfig>,
+    hotkeys: Option<HotkeyConfig>,
+    updates: Option<UpdateConfig>,
+) -> Result<AppConfig, NoCrateError> {
+    // Battery fan curves are written on the next profile switch, check them now
+    if let Some(v) = &power_source {
+        for curve in v.battery_fan_curves.values().flatten() {
+            curve.validate()?;
+        }
+    }
+    let previous = state.config.get();
+    let saved = state.config.update(|cfg| {
+        if let Some(v) = theme {
+            cfg.theme = v;
+        }
+        if let Some(v) = close_to_tray {
+            cfg.close_to_tray = v;
+        }
+        if let Some(v) = start_minimized {
+            cfg.start_minimized = v;
+        }
+        if let Some(v) = auto_start {
+            cfg.auto_start = v;
+        }
+        if let Some(v) = restore_on_startup {
+            cfg.restore_on_startup = v;
+        }
+        if let Some(v) = fan_poll_interval_ms {
+            cfg.fan_poll_interval_ms = v;
+        }
+        if let Some(v) = last_thermal_profile {
+            cfg.last_thermal_profile = v;
+        }
+        if let Some(v) = last_aura_effect {
+            cfg.last_aura_effect = v;
+        }
+        if let Some(v) = last_aura_color {
+            cfg.last_aura_color = v;
+        }
+        if let Some(v) = last_aura_speed {
+            cfg.last_aura_speed = v;
+        }
+        if let Some(v) = temp_alert_enabled {
+            cfg.temp_alert_enabled = v;
+        }
+        if let Some(v) = temp_alert_threshold {
+            cfg.temp_alert_threshold = v;
+        }
+        if let Some(v) = fan_stall {
+            cfg.fan_stall = v;
+        }
+        if let Some(v) = thermal_failsafe {
+            cfg.thermal_failsafe = v;
+        }
+        if let Some(v) = power_source {
+            cfg.power_source = v;
+        }
+        if let Some(v) = sio_channels {
+            cfg.sio_channels = v;
+        }
+        if let Some(v) = sensor_calibration {
+            cfg.sensor_calibration = v;
+        }
+        if let Some(v) = sensor_smoothing {
+            cfg.sensor_smoothing = v;
+        }
+        if let Some(v) = sensor_polling {
+            cfg.sensor_polling = v;
+        }
+        if let Some(v) = units {
+            cfg.units = v;
+        }
+        if let Some(v) = locale {
+            cfg.locale = v;
+        }
+        if let Some(v) = eco {
+            cfg.eco = v;
+        }
+        if let Some(v) = ambilight_zones {
+            cfg.ambilight_zones = v;
+        }
+        if let Some(v) = openrgb {
+            cfg.openrgb = v;
+        }
+        if let Some(v) = lighting_schedule {
+            cfg.lighting_schedule = v;
+        }
+        if let Some(v) = aura_calibration {
+            cfg.aura_calibration = v;
+        }
+        if let Some(v) = rtss {
+            cfg.rtss = v;
+        }
+        if let Some(v) = overlay {
+            cfg.overlay = v;
+        }
+        if let Some(v) = metrics {
+            cfg.metrics = v;
+        }
+        if let Some(v) = mqtt {
+            cfg.mqtt = v;
+        }
+        if let Some(v) = api {
+            cfg.api = v;
+        }
+        if let Some(v) = hotkeys {
+            cfg.hotkeys = v;
+        }
+        if let Some(v) = updates {
+            cfg.updates = v;
+        }
+    })?;
+
+    apply_config(&state, &previous, &saved);
+    Ok(saved)
+}
+
+/// List the rotating config backups, newest (index 1) first.
+#[tauri::command]
+pub fn list_config_backups() -> Vec<ConfigBackup> {
+    ConfigStore::backups()
+}
+
+/// Restore the config from backup `index` and apply it.
+#[tauri::command]
+pub fn restore_config_backup(
+    state: State<'_, AppState>,
+    index: usize,
+) -> Result<AppConfig, NoCrateError> {
+    let previous = state.config.get();
+    let restored = state.config.restore_backup(index)?;
+    apply_config(&state, &previous, &restored);
+    Ok(restored)
+}
+
+/// Helper: apply the runtime side effects of a config change.
+pub fn apply_config(state: &AppState, previous: &AppConfig, updated: &AppConfig) {
+    // Push channel labels to the live SIO monitor so readings reflect them immediately
+    #[cfg(feature = "sio")]
+    if let Some(sio) = state.sio.get() {
+        sio.set_channel_config(state.quirks.sio_channels(updated.sio_channels.clone()));
+    }
+
+    // Whether the driver service outlives the handle is decided when it is dropped
+    #[cfg(feature = "sio")]
+    if let Some(driver) = state.driver.get() {
+        driver.set_resident(updated.driver.keep_installed);
+    }
+
+    // New colour corrections apply from the next write
+    if updated.aura_calibration != previous.aura_calibration {
+        for ctrl in state.aura.lock().iter() {
+            ctrl.load_calibration(&updated.aura_calibration);
+        }
+    }
+
+    // Hand the HID devices over to / back from OpenRGB; a running effect
+    // would keep writing to the old output
+    if updated.openrgb.enabled != previous.openrgb.enabled {
+        state.aura_effects.stop();
+        state.aura_stream.stop();
+        if updated.openrgb.enabled {
+            state.aura.lock().clear();
+        } else if let Err(e) = hotplug::reconnect(state) {
+            eprintln!("AURA: no controllers after leaving OpenRGB passthrough: {e}");
+        }
+    }
 }
@@ -0,0 +1,103 @@
+/// Dashboard snapshot command.
+///
+/// The overview refresh used to invoke the backend type, fan status,
+/// unified sensors and AURA state separately. `get_dashboard_snapshot`
+/// gathers all of it on the backend: the backend type, fan speeds and
+/// thermal profile in one WMI round-trip, the sensors in one aggregation
+/// pass, and the AURA state from memory.
+use serde::Serialize;
+use tauri::State;
+
+use crate::aura::effects::RunningEffect;
+use crate::aura::stream::StreamStatus;
+use crate::error::NoCrateError;
+use crate::sensors::{self, SensorKind, SourceError, UnifiedSensor};
+use crate::state::{AppState, WmiThreadAsync};
+use crate::wmi::asus_mgmt::{self, FanInfo, ThermalProfile};
+
+/// Everything the dashboard shows, read in one invoke.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardSnapshot {
+    /// `"desktop"`, `"laptop"`, `"asushw"` or `"unavailable"`, as
+    /// returned by `get_wmi_backend`.
+    pub backend: String,
+    /// `None` if WMI is unavailable or the backend has no thermal profile.
+    pub profile: Option<ThermalProfile>,
+    /// Fan headers read through ASUS WMI.
+    pub fans: Vec<FanInfo>,
+    /// Fan speed and duty sensors of every other source (Super I/O, EC,
+    /// GPU, LHM, ...).
+    pub fan_sensors: Vec<UnifiedSensor>,
+    /// Temperature sensors, without duplicates of higher-priority sources.
+    pub temperatures: Vec<UnifiedSensor>,
+    /// Sensor sources that failed to read.
+    pub sensor_errors: Vec<SourceError>,
+    pub aura: AuraSummary,
+}
+
+/// AURA lighting state.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuraSummary {
+    /// At least one AURA controller is connected.
+    pub available: bool,
+    /// Last firmware effect set from the app, e.g. `"rainbow"`.
+    pub effect: String,
+    /// Last colour set from the app as `#RRGGBB`.
+    pub color: String,
+    /// Software effect currently driving the LEDs, if any.
+    pub software_effect: Option<RunningEffect>,
+    /// Direct-mode frame stream state.
+    pub stream: StreamStatus,
+}
+
+/// Read the whole dashboard state in one call.
+#[tauri::command]
+pub async fn get_dashboard_snapshot(
+    state: State<'_, AppState>,
+) -> Result<DashboardSnapshot, NoCrateError> {
+    let (backend, profile, fans) = match state.wmi.get() {
+        Some(wmi) => {
+            wmi.execute_async(|conn| {
+                Ok((
+                    conn.backend.backend_type().to_string(),
+                    asus_mgmt::get_thermal_profile(conn).ok(),
+                    asus_mgmt::get_all_fan_speeds(conn),
+                ))
+            })
+            .await?
+        }
+        None => ("unavailable".to_string(), None, Vec::new()),
+    };
+
+    let snapshot = sensors::collect(&state);
+    let (fan_sensors, temperatures) = snapshot
+        .sensors
+        .into_iter()
+        .filter(|s| s.duplicate_of.is_none())
+        .filter(|s| {
+            matches!(
+                s.kind,
+                SensorKind::Fan | SensorKind::FanPercent | SensorKind::Temperature
+            )
+        })
+        .partition(|s| s.kind != SensorKind::Temperature);
+
+    let config = state.config.get();
+    let aura = AuraSummary {
+        available: !state.aura.lock().is_empty(),
+        effect: config.last_aura_effect,
+        color: config.last_aura_color,
+        software_effect: state.aura_effects.running(),
+        stream: state.aura_stream.status(),
+    };
+
+    Ok(DashboardSnapshot {
+        backend,
+        profile,
+        fans,
+        fan_sensors,
+        temperatures,
+        sensor_errors: snapshot.errors,
+        aura,
+    })
+}
@@ -0,0 +1,263 @@
+/// ASUS WMI diagnostics: device ID scanner and class/method explorer.
+///
+/// Mapping a new board used to mean compiling the `test_asio` example and
+/// reading its console output. `start_device_scan` runs the same
+/// `device_status` / `DSTS` sweep from the app on a background thread,
+/// one call at a time with a pause in between so fan control and sensor
+/// polling keep their turn on the WMI thread. Only reads are issued and
+/// the board's blocked device IDs are skipped by the connection itself.
+/// Hits, progress and the final summary are streamed to the frontend;
+/// `cancel_device_scan` stops a running scan.
+///
+/// `explore_wmi_classes` lists the methods and parameter signatures of
+/// the ASUS WMI classes, so users can report which fan-control APIs their
+/// firmware exposes.
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::fan::with_wmi_async;
+use crate::error::NoCrateError;
+use crate::i18n::{self, Msg};
+use crate::state::AppState;
+use crate::wmi::asus_mgmt;
+use crate::wmi::explore::{self, WmiClassInfo};
+
+/// Event emitted to the frontend with a [`DeviceScanEvent`].
+pub const DEVICE_SCAN_EVENT: &str = "device-scan";
+
+/// Pause between two calls unless the caller asks for another one.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Shortest pause accepted, to keep the WMI thread responsive.
+const MIN_INTERVAL: Duration = Duration::from_millis(2);
+
+/// Largest number of IDs one scan may cover.
+const MAX_IDS: u64 = 65_536;
+
+/// A progress event is emitted every this many IDs.
+const PROGRESS_EVERY: u64 = 64;
+
+/// Values the firmware returns for IDs it does not implement.
+const NOT_IMPLEMENTED: [u32; 3] = [0, 0xFFFF_FFFE, 0xFFFF_FFFF];
+
+/// The first 256 devices of every device type the `test_asio` example
+/// swept: the device type is the high word of the ID.
+const DEFAULT_RANGES: [(u32, u32); 28] = [
+    (0x0000_0000, 0x0000_00FF),
+    (0x0001_0000, 0x0001_00FF),
+    (0x0002_0000, 0x0002_00FF),
+    (0x0003_0000, 0x0003_00FF),
+    (0x0004_0000, 0x0004_00FF),
+    (0x0005_0000, 0x0005_00FF),
+    (0x0006_0000, 0x0006_00FF),
+    (0x0007_0000, 0x0007_00FF),
+    (0x0008_0000, 0x0008_00FF),
+    (0x0009_0000, 0x0009_00FF),
+    (0x000A_0000, 0x000A_00FF),
+    (0x000B_0000, 0x000B_00FF),
+    (0x000C_0000, 0x000C_00FF),
+    (0x000D_0000, 0x000D_00FF),
+    (0x000E_0000, 0x000E_00FF),
+    (0x000F_0000, 0x000F_00FF),
+    (0x0010_0000, 0x0010_00FF),
+    (0x0010_1000, 0x0010_10FF),
+    (0x0011_0000, 0x0011_00FF),
+    (0x0012_0000, 0x0012_00FF),
+    (0x0013_0000, 0x0013_00FF),
+    (0x0014_0000, 0x0014_00FF),
+    (0x0020_0000, 0x0020_00FF),
+    (0x0021_0000, 0x0021_00FF),
+    (0x0030_0000, 0x0030_00FF),
+    (0x0040_0000, 0x0040_00FF),
+    (0x0050_0000, 0x0050_00FF),
+    (0x0060_0000, 0x0060_00FF),
+];
+
+/// Set while a scan runs; only one runs at a time.
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Set by `cancel_device_scan`, checked before every call.
+static CANCEL: AtomicBool = AtomicBool::new(false);
+
+/// Inclusive range of device IDs to scan.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DeviceIdRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl DeviceIdRange {
+    const fn ids(self) -> RangeInclusive<u32> {
+        self.start..=self.end
+    }
+
+    fn len(self) -> u64 {
+        u64::from(self.end.saturating_sub(self.start)) + 1
+    }
+}
+
+/// Update streamed during a scan.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeviceScanEvent {
+    /// The device answered with a value that looks implemented.
+    Hit {
+        device_id: u32,
+        value: u32,
+    },
+    Progress {
+        scanned: u64,
+        total: u64,
+    },
+    Finished {
+        scanned: u64,
+        total: u64,
+        hits: u32,
+        /// IDs whose call failed (blocked, unsupported or timed out).
+        errors: u32,
+        cancelled: bool,
+        /// Why the scan stopped early, if it did not finish or get
+        /// cancelled.
+        error: Option<NoCrateError>,
+    },
+}
+
+/// Clears [`RUNNING`] when the scan thread ends, however it ends.
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// Scan `ranges` (the `test_asio` ranges if `None`) with `device_status`
+/// / `DSTS`, pausing `interval_ms` between calls. Results arrive as
+/// `device-scan` events.
+#[tauri::command]
+pub fn start_device_scan(
+    app: AppHandle,
+    ranges: Option<Vec<DeviceIdRange>>,
+    interval_ms: Option<u64>,
+) -> Result<(), NoCrateError> {
+    let ranges = ranges.unwrap_or_else(|| {
+        DEFAULT_RANGES
+            .iter()
+            .map(|&(start, end)| DeviceIdRange { start, end })
+            .collect()
+    });
+    if let Some(r) = ranges.iter().find(|r| r.start > r.end) {
+        return Err(NoCrateError::Unknown(i18n::tf(
+            Msg::ScanRangeInvalid,
+            &[
+                ("start", &format!("0x{:08X}", r.start)),
+                ("end", &format!("0x{:08X}", r.end)),
+            ],
+        )));
+    }
+    let total: u64 = ranges.iter().map(|r| r.len()).sum();
+    if total > MAX_IDS {
+        return Err(NoCrateError::Unknown(i18n::tf(
+            Msg::ScanTooLarge,
+            &[("total", &total), ("max", &MAX_IDS)],
+        )));
+    }
+    let interval = interval_ms
+        .map_or(DEFAULT_INTERVAL, Duration::from_millis)
+        .max(MIN_INTERVAL);
+    // Fail early rather than in the first event
+    let _ = app.state::<AppState>().wmi.require(Msg::WmiNotInitialized)?;
+
+    if RUNNING.swap(true, Ordering::AcqRel) {
+        return Err(NoCrateError::Busy(i18n::t(Msg::ScanRunning).into()));
+    }
+    CANCEL.store(false, Ordering::Release);
+    let spawned = thread::Builder::new()
+        .name("nocrate-device-scan".into())
+        .spawn(move || {
+            let _running = RunningGuard;
+            scan(&app, &ranges, total, interval);
+        });
+    if let Err(e) = spawned {
+        RUNNING.store(false, Ordering::Release);
+        return Err(NoCrateError::Unknown(i18n::tf(
+            Msg::ThreadSpawn,
+            &[("name", &"nocrate-device-scan"), ("error", &e)],
+        )));
+    }
+    Ok(())
+}
+
+/// Stop the running device scan after its current call.
+#[tauri::command]
+pub fn cancel_device_scan() {
+    CANCEL.store(true, Ordering::Release);
+}
+
+/// Methods and parameter signatures of `ASUSManagement`, `ASUSHW` and
+/// `ASUSATKWMI_WMNB`. Classes the firmware does not define come back with
+/// their error instead of methods.
+#[tauri::command]
+pub async fn explore_wmi_classes(
+    state: State<'_, AppState>,
+) -> Result<Vec<WmiClassInfo>, NoCrateError> {
+    with_wmi_async(&state, |conn| Ok(explore::explore(conn))).await
+}
+
+fn scan(app: &AppHandle, ranges: &[DeviceIdRange], total: u64, interval: Duration) {
+    let mut scanned = 0;
+    let mut hits = 0;
+    let mut errors = 0;
+    let mut error = None;
+
+    'ranges: for &range in ranges {
+        for device_id in range.ids() {
+            if CANCEL.load(Ordering::Acquire) {
+                break 'ranges;
+            }
+            // The WMI thread may be replaced while the scan runs
+            let wmi = match app.state::<AppState>().wmi.require(Msg::WmiNotInitialized) {
+                Ok(wmi) => wmi,
+                Err(e) => {
+                    error = Some(e);
+                    break 'ranges;
+                }
+            };
+            match wmi.execute(move |conn| asus_mgmt::dsts(conn, device_id)) {
+                Ok(value) if !NOT_IMPLEMENTED.contains(&value) => {
+                    hits += 1;
+                    let _ = app.emit(DEVICE_SCAN_EVENT, DeviceScanEvent::Hit { device_id, value });
+                }
+                Ok(_) => {}
+                Err(_) => errors += 1,
+            }
+            scanned += 1;
+            if scanned % PROGRESS_EVERY == 0 {
+                let _ = app.emit(
+                    DEVICE_SCAN_EVENT,
+                    DeviceScanEvent::Progress { scanned, total },
+                );
+            }
+            thread::sleep(interval);
+        }
+    }
+
+    let cancelled = CANCEL.load(Ordering::Acquire);
+    eprintln!("Device scan: {hits} hits in {scanned} of {total} IDs ({errors} errors)");
+    let _ = app.emit(
+        DEVICE_SCAN_EVENT,
+        DeviceScanEvent::Finished {
+            scanned,
+            total,
+            hits,
+            errors,
+            cancelled,
+            error,
+        },
+    );
+}
@@ -0,0 +1,114 @@
+/// `WinRing0` kernel driver management commands.
+///
+/// The driver is only loaded once the user installed it from the settings
+/// (`AppConfig::driver`); these commands report its state and install or
+/// remove it explicitly.
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::error::NoCrateError;
+use crate::sio::driver;
+use crate::sio::embedded;
+use crate::state::{AppState, SubsystemReady, SUBSYSTEM_READY_EVENT};
+
+/// State of the `WinRing0` driver.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Serialize)]
+pub struct DriverStatus {
+    /// The user installed the driver from the settings.
+    pub enabled: bool,
+    /// The driver service is kept registered across restarts.
+    pub keep_installed: bool,
+    /// The driver service is registered with the service control manager.
+    pub installed: bool,
+    /// The driver service is running, i.e. the driver is loaded.
+    pub running: bool,
+    /// Version reported by the loaded driver.
+    pub version: Option<String>,
+    /// Driver file the service points at.
+    pub image_path: Option<String>,
+    /// The driver is embedded in this build.
+    pub embedded: bool,
+    /// Windows refused to load the driver (vulnerable driver blocklist or
+    /// memory integrity).
+    pub blocked: bool,
+    /// The driver is held by the `NoCrate` service.
+    pub via_service: bool,
+    /// Why the driver could not be opened, if it could not.
+    pub error: Option<NoCrateError>,
+}
+
+fn driver_status(state: &AppState) -> Result<DriverStatus, NoCrateError> {
+    let service = driver::service_state()?;
+    let handle = state.driver.get();
+    let error = state.driver.error().filter(|_| handle.is_none());
+    let config = state.config.get().driver;
+    Ok(DriverStatus {
+        enabled: config.enabled,
+        keep_installed: config.keep_installed,
+        installed: service.installed,
+        running: service.running,
+        version: handle.as_ref().and_then(|d| d.version().ok()),
+        image_path: service.image_path.map(|p| p.display().to_string()),
+        embedded: embedded::is_embedded(),
+        blocked: matches!(error, Some(NoCrateError::DriverBlocked(_))),
+        via_service: handle.is_some_and(|d| d.is_remote()),
+        error,
+    })
+}
+
+/// Whether the driver is installed, loaded and usable.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_driver_status(state: State<'_, AppState>) -> Result<DriverStatus, NoCrateError> {
+    driver_status(&state)
+}
+
+/// Keep the driver service registered when the app exits, so the next
+/// launch only has to open the device. `uninstall_driver` still removes it.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn set_driver_resident(
+    state: State<'_, AppState>,
+    keep_installed: bool,
+) -> Result<DriverStatus, NoCrateError> {
+    let _ = state
+        .config
+        .update(|c| c.driver.keep_installed = keep_installed)?;
+    if let Some(driver) = state.driver.get() {
+        driver.set_resident(keep_installed);
+    }
+    driver_status(&state)
+}
+
+/// Install and load the driver, then bring up the sensors that need it.
+/// Remembered, so the driver is loaded on later launches too.
+#[tauri::command]
+pub async fn install_driver(app: AppHandle) -> Result<DriverStatus, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let ready = |name: &'static str, error: Option<NoCrateError>| {
+            let _ = app.emit(SUBSYSTEM_READY_EVENT, SubsystemReady { name, error });
+        };
+        let installed = state.install_driver(&ready);
+        if let Err(e) = &installed {
+            eprintln!("Warning: WinRing0 driver installation failed: {e}");
+        }
+        installed.and_then(|()| driver_status(&state))
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// Release the sensors that use the driver, then stop and remove the
+/// driver service. The driver is not loaded again until reinstalled.
+#[tauri::command]
+pub async fn uninstall_driver(app: AppHandle) -> Result<DriverStatus, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        state.uninstall_driver()?;
+        driver_status(&state)
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
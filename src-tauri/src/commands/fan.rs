@@ -1,108 +1,673 @@
 /// Fan control commands exposed to the frontend via Tauri's invoke system.
 ///
 /// All WMI operations are dispatched to the dedicated WMI thread through
-/// `AppState::wmi.execute()`, keeping the Tauri main / async runtime
-/// unblocked.
-use tauri::State;
+/// `AppState::wmi.execute_async()`. The commands are `async` so Tauri runs
+/// them off the main thread, and a hung WMI call times out instead of
+/// blocking the invoke handler.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::state::AppState;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::atk_events::THERMAL_PROFILE_CHANGED_EVENT;
+use crate::config::{AppConfig, FanGroup, FanGroupCurve};
+use crate::curve;
+use crate::error::NoCrateError;
+use crate::fan_calibration;
+use crate::i18n::{self, Msg};
+use crate::power_source::PowerSource;
+use crate::state::{AppState, WmiHealth, WmiOp, WmiThread, WmiThreadAsync};
 use crate::wmi::asus_mgmt::{
-    self, AsusHWSensor, DesktopFanCurve, DesktopFanMode, DesktopFanPolicy, FanCurve, FanInfo,
-    FanTarget, ThermalProfile,
+    self, AsusHWSensor, CurvePreviewPoint, DesktopFanCurve, DesktopFanCurve3, DesktopFanMode,
+    DesktopFanPolicy, DesktopFanSources, FanCalibrationState, FanCalibrationStatus, FanCurve,
+    FanCurvePoint, FanInfo, FanStatus, FanTarget, LowLimitWarning, ThermalProfile,
+    DESKTOP_MAX_FAN_HEADERS, FAN_CURVE_POINTS,
 };
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend, WmiConnection};
+use crate::wmi::ppt;
+
+/// Helper: get the WmiThread or return the init error.
+fn wmi_thread(state: &State<'_, AppState>) -> Result<WmiThread, NoCrateError> {
+    state.wmi.require(Msg::WmiNotInitialized)
+}
 
-/// Helper: get a reference to the WmiThread or return an error string.
-fn with_wmi<F, T>(state: &State<'_, AppState>, f: F) -> Result<T, String>
+/// Helper: execute a closure on the WMI thread, blocking the caller.
+pub fn with_wmi<F, T>(state: &State<'_, AppState>, f: F) -> Result<T, NoCrateError>
 where
-    F: FnOnce(&crate::wmi::connection::WmiConnection) -> crate::error::Result<T> + Send + 'static,
+    F: FnOnce(&WmiConnection) -> crate::error::Result<T> + Send + 'static,
     T: Send + 'static,
 {
-    let wmi = state.wmi.as_ref().ok_or_else(|| {
-        state
-            .wmi_error
-            .as_deref()
-            .unwrap_or("WMI 未初始化")
-            .to_string()
-    })?;
-    wmi.execute(f).map_err(Into::into)
+    wmi_thread(state)?.execute(f)
+}
+
+/// Helper: execute a closure on the WMI thread from an async command.
+pub async fn with_wmi_async<F, T>(state: &State<'_, AppState>, f: F) -> Result<T, NoCrateError>
+where
+    F: FnOnce(&WmiConnection) -> crate::error::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    wmi_thread(state)?.execute_async(f).await
 }
 
 /// Get the current RPM for a specific fan header.
 #[tauri::command]
-pub fn get_fan_speed(state: State<'_, AppState>, target: FanTarget) -> Result<u32, String> {
-    with_wmi(&state, move |conn| asus_mgmt::get_fan_speed(conn, target))
+pub async fn get_fan_speed(
+    state: State<'_, AppState>,
+    target: FanTarget,
+) -> Result<u32, NoCrateError> {
+    with_wmi_async(&state, move |conn| asus_mgmt::get_fan_speed(conn, target)).await
 }
 
 /// Get RPM readings for every detected fan header.
 #[tauri::command]
-pub fn get_all_fan_speeds(state: State<'_, AppState>) -> Result<Vec<FanInfo>, String> {
-    with_wmi(&state, |conn| Ok(asus_mgmt::get_all_fan_speeds(conn)))
+pub async fn get_all_fan_speeds(state: State<'_, AppState>) -> Result<Vec<FanInfo>, NoCrateError> {
+    with_wmi_async(&state, |conn| Ok(asus_mgmt::get_all_fan_speeds(conn))).await
+}
+
+/// Get all fan speeds and the active thermal profile in one WMI
+/// round-trip, for polling.
+#[tauri::command]
+pub async fn get_fan_status(state: State<'_, AppState>) -> Result<FanStatus, NoCrateError> {
+    with_wmi_async(&state, asus_mgmt::get_fan_status).await
 }
 
 /// Get the currently active thermal profile.
 #[tauri::command]
-pub fn get_thermal_profile(state: State<'_, AppState>) -> Result<ThermalProfile, String> {
-    with_wmi(&state, |conn| asus_mgmt::get_thermal_profile(conn))
+pub async fn get_thermal_profile(
+    state: State<'_, AppState>,
+) -> Result<ThermalProfile, NoCrateError> {
+    with_wmi_async(&state, |conn| asus_mgmt::get_thermal_profile(conn)).await
 }
 
-/// Set the thermal profile (Standard / Performance / Silent).
+/// Set the thermal profile (Standard / Performance / Silent) and apply
+/// the power limits and laptop fan curves saved for it.
 #[tauri::command]
-pub fn set_thermal_profile(
+pub async fn set_thermal_profile(
     state: State<'_, AppState>,
     profile: ThermalProfile,
-) -> Result<(), String> {
-    with_wmi(&state, move |conn| {
-        asus_mgmt::set_thermal_profile(conn, profile)
+) -> Result<(), NoCrateError> {
+    let config = state.config.get();
+    with_wmi_async(&state, move |conn| {
+        apply_thermal_profile(conn, &config, profile)
     })
+    .await?;
+    remember_thermal_profile(&state, profile);
+    Ok(())
+}
+
+/// Switch the thermal profile and re-apply the power limits and laptop
+/// fan curves saved for it on the current power source, which the
+/// firmware resets on every switch.
+pub fn apply_thermal_profile(
+    conn: &impl AsusWmi,
+    config: &AppConfig,
+    profile: ThermalProfile,
+) -> crate::error::Result<()> {
+    let source = PowerSource::current();
+    asus_mgmt::set_thermal_profile(conn, profile)?;
+    ppt::apply_for_profile(conn, &config.power_limits_for(source), profile)?;
+    asus_mgmt::apply_laptop_fan_curves(conn, &config.laptop_fan_curves_for(source), profile)
+}
+
+/// Switch the thermal profile on behalf of a remote client (MQTT, local
+/// API) and tell the frontend, which did not initiate the change.
+pub fn switch_thermal_profile(
+    app: &AppHandle,
+    state: &AppState,
+    profile: ThermalProfile,
+) -> crate::error::Result<()> {
+    let wmi = state.wmi.require(Msg::WmiNotInitialized)?;
+    let config = state.config.get();
+    wmi.execute(move |conn| apply_thermal_profile(conn, &config, profile))?;
+    remember_thermal_profile(state, profile);
+    let _ = app.emit(THERMAL_PROFILE_CHANGED_EVENT, profile);
+    Ok(())
+}
+
+/// Save `profile` as the last selected one, restored at startup.
+pub fn remember_thermal_profile(state: &AppState, profile: ThermalProfile) {
+    let raw = u8::try_from(profile.to_raw()).unwrap_or_default();
+    if let Err(e) = state.config.update(|cfg| cfg.last_thermal_profile = raw) {
+        eprintln!("Warning: failed to save thermal profile: {e}");
+    }
 }
 
 /// Get a sensible default fan curve for a given target.
 ///
-/// Returns a local default — hardware curve read/write is not yet
-/// implemented (requires byte-buffer WMI support).
+/// Returns a local default, used where the hardware curve cannot be read
+/// (desktop boards, fans without a laptop curve).
 #[tauri::command]
 pub fn get_default_fan_curve(target: FanTarget) -> FanCurve {
     FanCurve::default_for(target)
 }
 
+/// Get the fan curve of a thermal profile (default: the active one) on a
+/// power source (default: AC).
+///
+/// On laptops this is the saved custom curve, or else the curve stored
+/// in the firmware bank of that profile. Other backends, and fans without
+/// a laptop curve, get the local default.
+#[tauri::command]
+pub async fn get_fan_curve(
+    state: State<'_, AppState>,
+    target: FanTarget,
+    profile: Option<ThermalProfile>,
+    source: Option<PowerSource>,
+) -> Result<FanCurve, NoCrateError> {
+    let saved = state
+        .config
+        .get()
+        .laptop_fan_curves_for(source.unwrap_or(PowerSource::Ac));
+    with_wmi_async(&state, move |conn| {
+        if !matches!(conn.backend, AsusWmiBackend::Laptop { .. }) {
+            return Ok(FanCurve::default_for(target));
+        }
+        let profile = match profile {
+            Some(p) => p,
+            None => asus_mgmt::get_thermal_profile(conn)?,
+        };
+        if let Some(curve) = saved
+            .get(&profile)
+            .and_then(|curves| curves.iter().find(|c| c.target == target))
+        {
+            return Ok(curve.clone());
+        }
+        Ok(asus_mgmt::get_laptop_fan_curve(conn, target, profile)?
+            .unwrap_or_else(|| FanCurve::default_for(target)))
+    })
+    .await
+}
+
+/// Save a custom laptop fan curve for a thermal profile on a power source
+/// (default: AC) and write it right away if that profile and source are
+/// active.
+#[tauri::command]
+pub async fn set_fan_curve(
+    state: State<'_, AppState>,
+    profile: ThermalProfile,
+    curve: FanCurve,
+    source: Option<PowerSource>,
+) -> Result<(), NoCrateError> {
+    curve.validate()?;
+    let source = source.unwrap_or(PowerSource::Ac);
+    let written = curve.clone();
+    with_wmi_async(&state, move |conn| {
+        if source == PowerSource::current() && asus_mgmt::get_thermal_profile(conn)? == profile {
+            asus_mgmt::set_laptop_fan_curve(conn, &written)?;
+        }
+        Ok(())
+    })
+    .await?;
+    let _ = state.config.update(|cfg| {
+        let curves = cfg
+            .laptop_fan_curves_mut(source)
+            .entry(profile)
+            .or_default();
+        curves.retain(|c| c.target != curve.target);
+        curves.push(curve);
+    })?;
+    Ok(())
+}
+
+/// Drop the custom laptop fan curves of a thermal profile on a power
+/// source (default: AC). If that profile and source are active, the
+/// profile is re-applied so the firmware curves, or the AC curves after a
+/// battery reset, take over.
+#[tauri::command]
+pub async fn reset_fan_curves(
+    state: State<'_, AppState>,
+    profile: ThermalProfile,
+    source: Option<PowerSource>,
+) -> Result<(), NoCrateError> {
+    let source = source.unwrap_or(PowerSource::Ac);
+    let config = state.config.update(|cfg| {
+        let _ = cfg.laptop_fan_curves_mut(source).remove(&profile);
+    })?;
+    with_wmi_async(&state, move |conn| {
+        if source == PowerSource::current() && asus_mgmt::get_thermal_profile(conn)? == profile {
+            apply_thermal_profile(conn, &config, profile)?;
+        }
+        Ok(())
+    })
+    .await
+}
+
 // ---------------------------------------------------------------------------
 // Desktop-specific commands
 // ---------------------------------------------------------------------------
 
 /// Returns `"desktop"`, `"laptop"`, `"asushw"`, or `"unavailable"` depending on the detected backend.
 #[tauri::command]
-pub fn get_wmi_backend(state: State<'_, AppState>) -> Result<String, String> {
-    match &state.wmi {
-        Some(wmi) => wmi
-            .execute(|conn| Ok(conn.backend.backend_type().to_string()))
-            .map_err(Into::into),
+pub async fn get_wmi_backend(state: State<'_, AppState>) -> Result<String, NoCrateError> {
+    match state.wmi.get() {
+        Some(wmi) => {
+            wmi.execute_async(|conn| Ok(conn.backend.backend_type().to_string()))
+                .await
+        }
         None => Ok("unavailable".to_string()),
     }
 }
 
+/// Get the WMI connection health, or `None` if WMI never initialized.
+/// Changes are also pushed with the `wmi-status` event.
+#[tauri::command]
+pub fn get_wmi_health(state: State<'_, AppState>) -> Option<WmiHealth> {
+    state.wmi.get().as_ref().map(WmiThread::health)
+}
+
 /// Get fan policies for all present desktop fan headers.
 ///
 /// Only meaningful when the backend is `desktop`.
 #[tauri::command]
-pub fn get_desktop_fan_policies(
+pub async fn get_desktop_fan_policies(
     state: State<'_, AppState>,
-) -> Result<Vec<DesktopFanPolicy>, String> {
-    with_wmi(&state, |conn| {
+) -> Result<Vec<DesktopFanPolicy>, NoCrateError> {
+    let mut policies = with_wmi_async(&state, |conn| {
         Ok(asus_mgmt::get_all_desktop_fan_policies(conn))
     })
+    .await?;
+    // Label headers with the board's silkscreen names and SIO channels
+    for policy in &mut policies {
+        if let Some(header) = state.quirks.fan_header(policy.fan_type) {
+            policy.name = Some(header.name.to_string());
+            policy.sio_channel = header.sio_channel;
+        }
+    }
+    Ok(policies)
 }
 
 /// Update a single desktop fan header's policy.
 ///
-/// Only meaningful when the backend is `desktop`.
+/// Only meaningful when the backend is `desktop`. The low limit is
+/// validated like in [`set_fan_low_limit`].
 #[tauri::command]
-pub fn set_desktop_fan_policy(
+pub async fn set_desktop_fan_policy(
     state: State<'_, AppState>,
     policy: DesktopFanPolicy,
-) -> Result<(), String> {
-    with_wmi(&state, move |conn| {
+) -> Result<(), NoCrateError> {
+    let calibration = state.config.get().fan_calibration;
+    let _ = asus_mgmt::check_low_limit(
+        policy.fan_type,
+        policy.low_limit,
+        calibration.get(&policy.fan_type),
+    )?;
+    let saved = policy.clone();
+    with_wmi_async(&state, move |conn| {
         asus_mgmt::set_desktop_fan_policy(conn, &policy)
     })
+    .await?;
+    remember_desktop_fan_policy(&state, saved)
+}
+
+/// Save a policy written to the firmware, restored at startup.
+fn remember_desktop_fan_policy(
+    state: &AppState,
+    policy: DesktopFanPolicy,
+) -> Result<(), NoCrateError> {
+    let _ = state.config.update(|cfg| {
+        let _ = cfg.desktop_fan_policies.insert(policy.fan_type, policy);
+    })?;
+    Ok(())
+}
+
+/// List the temperature sources each desktop fan header can follow, for
+/// the policy's source dropdown.
+#[tauri::command]
+pub async fn get_desktop_fan_sources(
+    state: State<'_, AppState>,
+) -> Result<Vec<DesktopFanSources>, NoCrateError> {
+    with_wmi_async(&state, asus_mgmt::get_desktop_fan_sources).await
+}
+
+/// Change only the low limit of a desktop fan header.
+///
+/// The limit is validated against the header's Q-Fan calibration; limits
+/// that can be written but may trigger the BIOS "CPU fan error" on boot
+/// are written anyway and returned as warnings.
+#[tauri::command]
+pub async fn set_fan_low_limit(
+    state: State<'_, AppState>,
+    fan_type: u8,
+    low_limit: u32,
+) -> Result<Vec<LowLimitWarning>, NoCrateError> {
+    let calibration = state.config.get().fan_calibration;
+    let warnings = asus_mgmt::check_low_limit(fan_type, low_limit, calibration.get(&fan_type))?;
+    let policy = with_wmi_async(&state, move |conn| {
+        let mut policy = asus_mgmt::get_desktop_fan_policy(conn, fan_type)?.ok_or_else(|| {
+            NoCrateError::DeviceNotPresent(i18n::tf(Msg::FanHeaderMissing, &[("fan", &fan_type)]))
+        })?;
+        policy.low_limit = low_limit;
+        asus_mgmt::set_desktop_fan_policy(conn, &policy)?;
+        Ok(policy)
+    })
+    .await?;
+    remember_desktop_fan_policy(&state, policy)?;
+    Ok(warnings)
+}
+
+/// Result of copying a fan curve to one header.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanCurveCopy {
+    pub fan_type: u8,
+    /// Warnings for the copied low limit, as from [`set_fan_low_limit`].
+    pub warnings: Vec<LowLimitWarning>,
+}
+
+/// Copy the policy and curve of the desktop header `from` to the headers
+/// `to`, or to every other chassis header (all but the CPU header) when
+/// `to` is `None`.
+///
+/// Runs as one WMI operation. The low limit and the curve are checked
+/// against each target's Q-Fan calibration before anything is written, so
+/// a copy that is invalid for one header leaves all of them unchanged.
+#[tauri::command]
+pub async fn copy_fan_curve(
+    state: State<'_, AppState>,
+    from: u8,
+    to: Option<Vec<u8>>,
+) -> Result<Vec<FanCurveCopy>, NoCrateError> {
+    let calibration = state.config.get().fan_calibration;
+    let not_present = |fan_type: u8| {
+        NoCrateError::DeviceNotPresent(i18n::tf(Msg::FanHeaderMissing, &[("fan", &fan_type)]))
+    };
+    let (copies, policies) = with_wmi_async(&state, move |conn| {
+        let policy =
+            asus_mgmt::get_desktop_fan_policy(conn, from)?.ok_or_else(|| not_present(from))?;
+        let curve = asus_mgmt::get_desktop_fan_curve_pro(conn, from, policy.mode)?;
+        let mut targets = to.unwrap_or_else(|| {
+            asus_mgmt::get_all_desktop_fan_policies(conn)
+                .iter()
+                .map(|p| p.fan_type)
+                .filter(|&t| t != 0)
+                .collect()
+        });
+        targets.sort_unstable();
+        targets.dedup();
+        targets.retain(|&t| t != from);
+
+        let mut copies = Vec::with_capacity(targets.len());
+        for &fan_type in &targets {
+            if asus_mgmt::get_desktop_fan_policy(conn, fan_type)?.is_none() {
+                return Err(not_present(fan_type));
+            }
+            let cal = calibration.get(&fan_type);
+            let warnings = asus_mgmt::check_low_limit(fan_type, policy.low_limit, cal)?;
+            if let Some(curve) = &curve {
+                curve::validate(&curve.points, cal)?;
+            }
+            copies.push(FanCurveCopy { fan_type, warnings });
+        }
+
+        let mut policies = Vec::with_capacity(targets.len());
+        for fan_type in targets {
+            let target_policy = DesktopFanPolicy {
+                fan_type,
+                name: None,
+                sio_channel: None,
+                ..policy.clone()
+            };
+            asus_mgmt::set_desktop_fan_policy(conn, &target_policy)?;
+            if let Some(curve) = &curve {
+                let target_curve = DesktopFanCurve {
+                    fan_type,
+                    mode: curve.mode,
+                    points: curve.points,
+                };
+                asus_mgmt::set_desktop_fan_curve_pro(conn, &target_curve)?;
+            }
+            policies.push(target_policy);
+        }
+        Ok((copies, policies))
+    })
+    .await?;
+    let _ = state.config.update(|cfg| {
+        for policy in policies {
+            let _ = cfg.desktop_fan_policies.insert(policy.fan_type, policy);
+        }
+    })?;
+    Ok(copies)
+}
+
+/// Start Q-Fan calibration of a desktop fan header.
+///
+/// Progress is reported through `fan-calibration` events; the result is
+/// saved to the config when the run finishes.
+#[tauri::command]
+pub async fn start_fan_calibration(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    fan_type: u8,
+) -> Result<(), NoCrateError> {
+    with_wmi_async(&state, move |conn| {
+        if asus_mgmt::get_fan_calibration_status(conn, fan_type)?.state
+            == FanCalibrationState::Running
+        {
+            return Err(NoCrateError::Busy(i18n::tf(
+                Msg::FanCalibrating,
+                &[("fan", &fan_type)],
+            )));
+        }
+        asus_mgmt::start_fan_calibration(conn, fan_type)
+    })
+    .await?;
+    fan_calibration::spawn(app, fan_type);
+    Ok(())
+}
+
+/// Read the calibration progress of a desktop fan header.
+#[tauri::command]
+pub async fn get_fan_calibration_status(
+    state: State<'_, AppState>,
+    fan_type: u8,
+) -> Result<FanCalibrationStatus, NoCrateError> {
+    with_wmi_async(&state, move |conn| {
+        asus_mgmt::get_fan_calibration_status(conn, fan_type)
+    })
+    .await
+}
+
+/// Run a temperature trace through a fan curve without touching hardware.
+///
+/// Speeds are estimated from the saved calibration of `fan_type`; they
+/// are `null` when no header is given or it was never calibrated.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)] // Tauri hands command arguments over by value
+pub fn preview_fan_curve(
+    state: State<'_, AppState>,
+    points: Vec<FanCurvePoint>,
+    temps: Vec<f32>,
+    fan_type: Option<u8>,
+) -> Vec<CurvePreviewPoint> {
+    let calibration = fan_type.and_then(|t| state.config.get().fan_calibration.get(&t).copied());
+    asus_mgmt::preview_fan_curve(&points, &temps, calibration.as_ref())
+}
+
+// ---------------------------------------------------------------------------
+// Fan groups
+// ---------------------------------------------------------------------------
+
+/// A header of a fan group and its current speed.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanGroupMember {
+    pub fan_type: u8,
+    /// Board header name from the quirks table, if known.
+    pub name: Option<String>,
+    /// `None` when the header is not measured through Super I/O.
+    pub rpm: Option<u32>,
+}
+
+/// A fan group with the speeds of its headers.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanGroupStatus {
+    pub name: String,
+    pub members: Vec<FanGroupMember>,
+    /// Slowest measured header, `None` if no header is measured.
+    pub min_rpm: Option<u32>,
+    /// Fastest measured header, `None` if no header is measured.
+    pub max_rpm: Option<u32>,
+    pub curve: Option<FanGroupCurve>,
+}
+
+/// Get the configured fan groups with the current speed of every header
+/// and the slowest and fastest of each group.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)] // Tauri hands command arguments over by value
+pub fn get_fan_groups(state: State<'_, AppState>) -> Vec<FanGroupStatus> {
+    let rpms = sio_fan_rpms(&state);
+    state
+        .config
+        .get()
+        .fan_groups
+        .into_iter()
+        .map(|group| {
+            let members: Vec<FanGroupMember> = group
+                .fan_types
+                .iter()
+                .map(|&fan_type| {
+                    let header = state.quirks.fan_header(fan_type);
+                    FanGroupMember {
+                        fan_type,
+                        name: header.map(|h| h.name.to_string()),
+                        rpm: header
+                            .and_then(|h| h.sio_channel)
+                            .and_then(|ch| rpms.get(&ch).copied()),
+                    }
+                })
+                .collect();
+            let min_rpm = members.iter().filter_map(|m| m.rpm).min();
+            let max_rpm = members.iter().filter_map(|m| m.rpm).max();
+            FanGroupStatus {
+                name: group.name,
+                members,
+                min_rpm,
+                max_rpm,
+                curve: group.curve,
+            }
+        })
+        .collect()
+}
+
+/// Current RPM per Super I/O fan channel, empty without Super I/O.
+#[cfg_attr(not(feature = "sio"), allow(unused_variables))]
+fn sio_fan_rpms(state: &AppState) -> HashMap<u8, u32> {
+    #[cfg(feature = "sio")]
+    {
+        if let Some(snapshot) = state.sio.get().and_then(|sio| sio.read_all().ok()) {
+            return snapshot.fans.iter().map(|f| (f.channel, f.rpm)).collect();
+        }
+    }
+    HashMap::new()
+}
+
+/// Create the fan group `name` with the headers `fan_types`, or change
+/// the headers of an existing one. A header can only be in one group; the
+/// shared curve is set with [`set_fan_group_curve`].
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)] // Tauri hands command arguments over by value
+pub fn set_fan_group(
+    state: State<'_, AppState>,
+    name: String,
+    mut fan_types: Vec<u8>,
+) -> Result<(), NoCrateError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(NoCrateError::Config(i18n::t(Msg::FanGroupNameEmpty).into()));
+    }
+    fan_types.sort_unstable();
+    fan_types.dedup();
+    if fan_types.is_empty() {
+        return Err(NoCrateError::Config(i18n::tf(
+            Msg::FanGroupEmpty,
+            &[("name", &name)],
+        )));
+    }
+    if let Some(&fan_type) = fan_types.iter().find(|&&t| t >= DESKTOP_MAX_FAN_HEADERS) {
+        return Err(NoCrateError::Config(i18n::tf(
+            Msg::FanHeaderMissing,
+            &[("fan", &fan_type)],
+        )));
+    }
+    let groups = state.config.get().fan_groups;
+    if let Some((other, fan_type)) = groups.iter().filter(|g| g.name != name).find_map(|g| {
+        g.fan_types
+            .iter()
+            .find(|t| fan_types.contains(t))
+            .map(|t| (g, t))
+    }) {
+        return Err(NoCrateError::Config(i18n::tf(
+            Msg::FanGroupTaken,
+            &[("fan", &fan_type), ("group", &other.name)],
+        )));
+    }
+    let _ = state.config.update(|cfg| {
+        if let Some(group) = cfg.fan_groups.iter_mut().find(|g| g.name == name) {
+            group.fan_types = fan_types;
+        } else {
+            cfg.fan_groups.push(FanGroup {
+                name,
+                fan_types,
+                curve: None,
+            });
+        }
+    })?;
+    Ok(())
+}
+
+/// Delete a fan group. Its headers keep the curve last written to them.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)] // Tauri hands command arguments over by value
+pub fn delete_fan_group(state: State<'_, AppState>, name: String) -> Result<(), NoCrateError> {
+    let _ = state
+        .config
+        .update(|cfg| cfg.fan_groups.retain(|g| g.name != name))?;
+    Ok(())
+}
+
+/// Write one 8-point curve to every header of a fan group and save it as
+/// the group's curve.
+///
+/// The curve is checked against every member's calibration first. Stops
+/// at the first header that fails to write; the headers before it keep
+/// the new curve and the saved group curve is left unchanged.
+#[tauri::command]
+pub async fn set_fan_group_curve(
+    state: State<'_, AppState>,
+    name: String,
+    mode: DesktopFanMode,
+    points: [FanCurvePoint; FAN_CURVE_POINTS],
+) -> Result<(), NoCrateError> {
+    let fan_types = state
+        .config
+        .get()
+        .fan_groups
+        .into_iter()
+        .find(|g| g.name == name)
+        .ok_or_else(|| NoCrateError::Config(i18n::tf(Msg::FanGroupUnknown, &[("name", &name)])))?
+        .fan_types;
+    let calibration = state.config.get().fan_calibration;
+    for fan_type in &fan_types {
+        curve::validate(&points, calibration.get(fan_type))?;
+    }
+    with_wmi_async(&state, move |conn| {
+        for fan_type in fan_types {
+            let curve = DesktopFanCurve {
+                fan_type,
+                mode,
+                points,
+            };
+            asus_mgmt::set_desktop_fan_curve_pro(conn, &curve)?;
+        }
+        Ok(())
+    })
+    .await?;
+    let _ = state.config.update(|cfg| {
+        if let Some(group) = cfg.fan_groups.iter_mut().find(|g| g.name == name) {
+            group.curve = Some(FanGroupCurve { mode, points });
+        }
+    })?;
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -111,10 +676,15 @@ pub fn set_desktop_fan_policy(
 
 /// Get all detected ASUSHW sensors (temperatures + fan RPMs).
 ///
-/// Only meaningful when the backend is `asushw`.
+/// Only meaningful when the backend is `asushw`. The sensor list is cached
+/// and the firmware buffers are refreshed at most once per fan poll interval.
 #[tauri::command]
-pub fn get_asushw_sensors(state: State<'_, AppState>) -> Result<Vec<AsusHWSensor>, String> {
-    with_wmi(&state, |conn| Ok(asus_mgmt::get_asushw_sensors(conn)))
+pub async fn get_asushw_sensors(
+    state: State<'_, AppState>,
+) -> Result<Vec<AsusHWSensor>, NoCrateError> {
+    let cache = Arc::clone(&state.asushw);
+    let interval = Duration::from_millis(state.config.get().fan_poll_interval_ms);
+    with_wmi_async(&state, move |conn| Ok(cache.read(conn, interval))).await
 }
 
 // ---------------------------------------------------------------------------
@@ -125,45 +695,104 @@ pub fn get_asushw_sensors(state: State<'_, AppState>) -> Result<Vec<AsusHWSensor
 ///
 /// 返回 `null` 表示该风扇头不存在。
 #[tauri::command]
-pub fn get_desktop_fan_curve(
+pub async fn get_desktop_fan_curve(
     state: State<'_, AppState>,
     fan_type: u8,
     mode: DesktopFanMode,
-) -> Result<Option<DesktopFanCurve>, String> {
-    with_wmi(&state, move |conn| {
+) -> Result<Option<DesktopFanCurve>, NoCrateError> {
+    with_wmi_async(&state, move |conn| {
         asus_mgmt::get_desktop_fan_curve_pro(conn, fan_type, mode)
     })
+    .await
+}
+
+/// 批量读取多个 `(fan_type, mode)` 的曲线，只需一次 WMI 线程往返。
+///
+/// 结果与请求一一对应，不存在或读取失败的项为 `null`。
+#[tauri::command]
+pub async fn get_desktop_fan_curves(
+    state: State<'_, AppState>,
+    requests: Vec<(u8, DesktopFanMode)>,
+) -> Result<Vec<Option<DesktopFanCurve>>, NoCrateError> {
+    let ops: Vec<WmiOp<Option<DesktopFanCurve>>> = requests
+        .into_iter()
+        .map(|(fan_type, mode)| -> WmiOp<_> {
+            Box::new(move |conn| asus_mgmt::get_desktop_fan_curve_pro(conn, fan_type, mode))
+        })
+        .collect();
+    let results = wmi_thread(&state)?.execute_batch_async(ops).await?;
+    Ok(results.into_iter().map(|r| r.ok().flatten()).collect())
 }
 
 /// 写入某个风扇头的 8 点曲线。
 ///
-/// 会校验温度单调递增和 Duty 范围。
+/// 按 [`curve`] 的规则校验，已校准的风扇头还会检查停转点。
 #[tauri::command]
-pub fn set_desktop_fan_curve(
+pub async fn set_desktop_fan_curve(
     state: State<'_, AppState>,
     curve: DesktopFanCurve,
-) -> Result<(), String> {
-    with_wmi(&state, move |conn| {
+) -> Result<(), NoCrateError> {
+    let calibration = state.config.get().fan_calibration;
+    curve::validate(&curve.points, calibration.get(&curve.fan_type))?;
+    with_wmi_async(&state, move |conn| {
         asus_mgmt::set_desktop_fan_curve_pro(conn, &curve)
     })
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// Desktop 3 点风扇曲线命令 (GetManualFanCurve / SetManualFanCurve)
+// ---------------------------------------------------------------------------
+
+/// 读取某个风扇头在指定模式下的 3 点曲线，供只实现非 Pro 方法的固件使用。
+///
+/// 返回 `null` 表示该风扇头不存在。
+#[tauri::command]
+pub async fn get_desktop_fan_curve_3(
+    state: State<'_, AppState>,
+    fan_type: u8,
+    mode: DesktopFanMode,
+) -> Result<Option<DesktopFanCurve3>, NoCrateError> {
+    with_wmi_async(&state, move |conn| {
+        asus_mgmt::get_desktop_fan_curve_3(conn, fan_type, mode)
+    })
+    .await
+}
+
+/// 写入某个风扇头的 3 点曲线。
+///
+/// 校验规则同 [`set_desktop_fan_curve`]。
+#[tauri::command]
+pub async fn set_desktop_fan_curve_3(
+    state: State<'_, AppState>,
+    curve: DesktopFanCurve3,
+) -> Result<(), NoCrateError> {
+    let calibration = state.config.get().fan_calibration;
+    curve::validate(&curve.points, calibration.get(&curve.fan_type))?;
+    with_wmi_async(&state, move |conn| {
+        asus_mgmt::set_desktop_fan_curve_3(conn, &curve)
+    })
+    .await
 }
 
 /// 探测所有存在的风扇头及其支持的控制模式。
 ///
 /// 返回 `[(fan_type, [modes...])]` 列表。
 #[tauri::command]
-pub fn probe_desktop_fan_types(
+pub async fn probe_desktop_fan_types(
     state: State<'_, AppState>,
-) -> Result<Vec<(u8, Vec<DesktopFanMode>)>, String> {
-    with_wmi(&state, |conn| Ok(asus_mgmt::probe_desktop_fan_types(conn)))
+) -> Result<Vec<(u8, Vec<DesktopFanMode>)>, NoCrateError> {
+    with_wmi_async(&state, |conn| Ok(asus_mgmt::probe_desktop_fan_types(conn))).await
 }
 
 /// 测试 asio_hw_fun* WMI 方法的可用性。
 ///
 /// 返回一个包含 (方法名, 返回值/错误) 的诊断列表。
 #[tauri::command]
-pub fn test_asio_hw_fun(state: State<'_, AppState>) -> Result<Vec<(String, String)>, String> {
-    with_wmi(&state, |conn| {
+pub async fn test_asio_hw_fun(
+    state: State<'_, AppState>,
+) -> Result<Vec<(String, String)>, NoCrateError> {
+    with_wmi_async(&state, |conn| {
         let results = conn.test_asio_hw_fun()?;
         Ok(results
             .into_iter()
@@ -176,6 +805,7 @@ pub fn test_asio_hw_fun(state: State<'_, AppState>) -> Result<Vec<(String, Strin
             })
             .collect())
     })
+    .await
 }
 
 // ---------------------------------------------------------------------------
@@ -183,30 +813,108 @@ pub fn test_asio_hw_fun(state: State<'_, AppState>) -> Result<Vec<(String, Strin
 // ---------------------------------------------------------------------------
 
 #[cfg(feature = "sio")]
-use crate::sio::chips::{SioSnapshot, SioStatus};
+use crate::sio::access::{AsioHwAccess, SioAccess};
+#[cfg(feature = "sio")]
+use crate::sio::chips::{RegisterDump, SioSnapshot, SioStatus};
+#[cfg(feature = "sio")]
+use crate::sio::diag::{self, SioDiagnostics};
+#[cfg(feature = "sio")]
+use crate::sio::lpc::{self, LpcDecodeDump};
+#[cfg(feature = "sio")]
+use tauri::Manager;
 
 /// 获取 Super I/O 传感器快照（风扇 RPM + 温度）
 #[cfg(feature = "sio")]
 #[tauri::command]
-pub fn get_sio_sensors(state: State<'_, AppState>) -> Result<SioSnapshot, String> {
-    let sio = state.sio.as_ref().ok_or_else(|| {
-        state
-            .sio_error
-            .as_deref()
-            .unwrap_or("SIO 未初始化")
-            .to_string()
-    })?;
-    sio.read_all().map_err(|e| e.to_string())
+pub fn get_sio_sensors(state: State<'_, AppState>) -> Result<SioSnapshot, NoCrateError> {
+    state.sio.require(Msg::SioNotInitialized)?.read_all()
+}
+
+/// 清除 Super I/O 机箱入侵锁存位
+#[cfg(feature = "sio")]
+#[tauri::command]
+pub fn clear_sio_intrusion(state: State<'_, AppState>) -> Result<(), NoCrateError> {
+    state.sio.require(Msg::SioNotInitialized)?.clear_intrusion()
 }
 
 /// 获取 Super I/O 状态信息
 #[cfg(feature = "sio")]
 #[tauri::command]
 pub fn get_sio_status(state: State<'_, AppState>) -> SioStatus {
-    match &state.sio {
-        Some(sio) => sio.status(),
-        None => {
-            crate::sio::unavailable_status(state.sio_error.as_deref().unwrap_or("SIO 未初始化"))
-        }
+    state.sio.get().map_or_else(
+        || {
+            let error = state.sio.error().map(|e| e.to_string());
+            crate::sio::unavailable_status(
+                error.as_deref().unwrap_or(i18n::t(Msg::SioNotInitialized)),
+            )
+        },
+        |sio| sio.status(),
+    )
+}
+
+/// 按需执行 Super I/O 诊断（LDN 激活状态、基地址、关键寄存器与原始端口值），供排查时导出
+/// 优先使用正在运行的 SIO 访问方式；SIO 未初始化时改用 `WinRing0` 驱动或 ASUS WMI
+#[cfg(feature = "sio")]
+#[tauri::command]
+pub async fn run_sio_diagnostics(app: AppHandle) -> Result<SioDiagnostics, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_sio_access(&app.state::<AppState>(), diag::run)?
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// 转储已检测芯片 HW Monitor 的全部 bank 与寄存器，供远程排查温度 / 风扇通道映射错误
+/// 经 ASUS WMI 访问时按批读取 16 个 bank，需要数秒
+#[cfg(feature = "sio")]
+#[tauri::command]
+pub async fn dump_sio_registers(app: AppHandle) -> Result<RegisterDump, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        app.state::<AppState>()
+            .sio
+            .require(Msg::SioNotInitialized)?
+            .dump_registers()
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// 导出 LPC / eSPI 桥（AMD FCH / Intel PCH）的 I/O 解码寄存器，并检查各 HW Monitor 基地址是否被转发
+/// 用于排查 SIO 读数全为 0xFF 的问题；需要 `WinRing0` 驱动访问 PCI 配置空间
+#[cfg(feature = "sio")]
+#[tauri::command]
+pub async fn get_lpc_decode(app: AppHandle) -> Result<LpcDecodeDump, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let driver = app
+            .state::<AppState>()
+            .driver
+            .require(Msg::DriverNotInstalled)?;
+        let bases: Vec<u16> = diag::run(driver.as_ref())?
+            .ports
+            .iter()
+            .map(|p| p.base)
+            .filter(|&base| base != 0 && base != 0xFFFF)
+            .collect();
+        lpc::dump(&driver, &bases)
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// 以可用的 Super I/O 访问方式执行 `f`
+/// 优先使用正在运行的 SIO 访问方式；SIO 未初始化时改用 `WinRing0` 驱动或 ASUS WMI
+/// 经 WMI 访问时 `f` 内部会调用 WMI 线程，不可在 WMI 线程上调用
+#[cfg(feature = "sio")]
+pub fn with_sio_access<T>(
+    state: &AppState,
+    f: impl FnOnce(&dyn SioAccess) -> T,
+) -> Result<T, NoCrateError> {
+    if let Some(sio) = state.sio.get() {
+        return Ok(sio.with_access(f));
+    }
+    if let Some(driver) = state.driver.get() {
+        return Ok(f(driver.as_ref()));
     }
+    let wmi = state.wmi.require(Msg::SioNoAccess)?;
+    Ok(f(&AsioHwAccess::new(wmi)))
 }
@@ -0,0 +1,103 @@
+/// Laptop-only controls (battery, GPU mode, power limits) exposed to the
+/// frontend.
+///
+/// These device IDs only exist on the laptop `ASUSATKWMI_WMNB` backend;
+/// on desktop boards the commands return an error.
+use tauri::State;
+
+use super::fan::with_wmi;
+use crate::board::read_board_name;
+use crate::error::NoCrateError;
+use crate::power_source::PowerSource;
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, GpuMode, GpuModeChange, GpuModeInfo, ThermalProfile};
+use crate::wmi::ppt::{self, PowerLimits, PowerLimitsInfo};
+
+/// Get the saved battery charge limit (`null` if never set from the app).
+#[tauri::command]
+pub fn get_battery_charge_limit(state: State<'_, AppState>) -> Option<u8> {
+    state.config.get().battery_charge_limit
+}
+
+/// Stop charging the battery at `percent` (20–100) and remember the value
+/// so it is re-applied at startup.
+#[tauri::command]
+pub fn set_battery_charge_limit(
+    state: State<'_, AppState>,
+    percent: u8,
+) -> Result<(), NoCrateError> {
+    with_wmi(&state, move |conn| {
+        asus_mgmt::set_battery_charge_limit(conn, percent)
+    })?;
+    let _ = state
+        .config
+        .update(|cfg| cfg.battery_charge_limit = Some(percent))?;
+    Ok(())
+}
+
+/// Get the GPU mode (Eco / Standard / Ultimate) and the available switches.
+#[tauri::command]
+pub fn get_gpu_mode(state: State<'_, AppState>) -> Result<GpuModeInfo, NoCrateError> {
+    with_wmi(&state, asus_mgmt::get_gpu_mode)
+}
+
+/// Switch the GPU mode. The result reports whether a reboot is needed
+/// (any change of the MUX, i.e. entering or leaving Ultimate).
+#[tauri::command]
+pub fn set_gpu_mode(
+    state: State<'_, AppState>,
+    mode: GpuMode,
+) -> Result<GpuModeChange, NoCrateError> {
+    with_wmi(&state, move |conn| asus_mgmt::set_gpu_mode(conn, mode))
+}
+
+/// Get the power-limit ranges for this model and the values the firmware
+/// currently reports.
+#[tauri::command]
+pub fn get_power_limits(state: State<'_, AppState>) -> Result<PowerLimitsInfo, NoCrateError> {
+    let board = read_board_name();
+    let ranges = ppt::ranges_for_board(board.as_deref());
+    let current = with_wmi(&state, ppt::get_power_limits)?;
+    Ok(PowerLimitsInfo {
+        board,
+        ranges,
+        current,
+    })
+}
+
+/// Save the power limits of a thermal profile on a power source (default:
+/// AC) and apply them right away if that profile and source are active.
+/// All-`null` limits clear the profile.
+#[tauri::command]
+pub fn set_power_limits(
+    state: State<'_, AppState>,
+    profile: ThermalProfile,
+    limits: PowerLimits,
+    source: Option<PowerSource>,
+) -> Result<(), NoCrateError> {
+    let ranges = ppt::ranges_for_board(read_board_name().as_deref());
+    ppt::validate(limits, &ranges)?;
+    let source = source.unwrap_or(PowerSource::Ac);
+
+    with_wmi(&state, move |conn| {
+        if source == PowerSource::current() && asus_mgmt::get_thermal_profile(conn)? == profile {
+            ppt::set_power_limits(conn, limits)?;
+        }
+        Ok(())
+    })?;
+    let _ = state.config.update(|cfg| {
+        let saved = cfg.power_limits_mut(source);
+        if limits == PowerLimits::default() {
+            let _ = saved.remove(&profile);
+        } else {
+            let _ = saved.insert(profile, limits);
+        }
+    })?;
+    Ok(())
+}
+
+/// Whether the machine currently runs on AC or battery.
+#[tauri::command]
+pub fn get_power_source() -> PowerSource {
+    PowerSource::current()
+}
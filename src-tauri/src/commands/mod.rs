@@ -1,8 +1,16 @@
 pub mod aura;
+pub mod capabilities;
 pub mod config;
+pub mod dashboard;
+pub mod diagnostics;
+#[cfg(feature = "sio")]
+pub mod driver;
 pub mod fan;
+pub mod laptop;
 pub mod sensor;
+pub mod status;
 pub mod system;
+pub mod trace;
 
 /// Placeholder greet command for initial setup verification.
 #[tauri::command]
@@ -1,36 +1,192 @@
 /// Sensor monitoring commands exposed to the frontend via Tauri's invoke system.
 ///
-/// Provides access to LibreHardwareMonitor (LHM) WMI sensor data.
-/// All WMI operations are dispatched to the dedicated WMI thread.
-use tauri::State;
+/// Provides access to LibreHardwareMonitor (LHM) WMI sensor data and the
+/// ACPI embedded controller. All WMI operations are dispatched to the
+/// dedicated WMI thread. The commands are `async` so sensor polling never
+/// blocks the main thread.
+use tauri::{AppHandle, Manager, State};
 
+use super::fan::with_wmi_async;
+use crate::error::NoCrateError;
+use crate::hwinfo::{self, HwinfoSnapshot};
+use crate::i18n::{self, Msg};
+use crate::lhm_launch;
+use crate::sensors::history::HistorySeries;
+use crate::sensors::stats::SensorStatsSnapshot;
+use crate::sensors::{self, UnifiedSensorSnapshot};
 use crate::state::AppState;
-use crate::wmi::lhm::{self, LhmSensorSnapshot, LhmStatus};
-
-/// Helper: execute a closure on the WMI thread.
-fn with_wmi<F, T>(state: &State<'_, AppState>, f: F) -> Result<T, String>
-where
-    F: FnOnce(&crate::wmi::connection::WmiConnection) -> crate::error::Result<T> + Send + 'static,
-    T: Send + 'static,
-{
-    let wmi = state.wmi.as_ref().ok_or_else(|| {
+use crate::wmi::lhm::{self, LhmHardware, LhmSensor, LhmSensorSnapshot, LhmStatus, SensorFilter};
+
+/// Check if LibreHardwareMonitor is accessible.
+#[tauri::command]
+pub async fn get_lhm_status(state: State<'_, AppState>) -> Result<LhmStatus, NoCrateError> {
+    with_wmi_async(&state, |conn| Ok(lhm::get_lhm_status(conn))).await
+}
+
+/// Get the LibreHardwareMonitor executable found on this machine, if any.
+#[tauri::command]
+pub async fn get_lhm_install(state: State<'_, AppState>) -> Result<Option<String>, NoCrateError> {
+    Ok(lhm_launch::find_install(&state.config.get().lhm).map(|p| p.display().to_string()))
+}
+
+/// Start LibreHardwareMonitor hidden if its WMI namespace is unavailable,
+/// and wait (up to 30 s) until its sensors are published.
+#[tauri::command]
+pub async fn launch_lhm(app: AppHandle) -> Result<LhmStatus, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        lhm_launch::ensure_running(&state)
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// Get all sensor readings grouped by type, limited to the sensors
+/// selected in `lhm.sensors` of the config (all if none are selected).
+#[tauri::command]
+pub async fn get_lhm_sensors(
+    state: State<'_, AppState>,
+) -> Result<LhmSensorSnapshot, NoCrateError> {
+    let ids = state.config.get().lhm.sensors;
+    with_wmi_async(&state, move |conn| {
+        lhm::get_all_sensors(conn, SensorFilter::Ids(&ids))
+    })
+    .await
+}
+
+/// List the LHM hardware nodes, for picking sensors by hardware.
+#[tauri::command]
+pub async fn get_lhm_hardware(
+    state: State<'_, AppState>,
+) -> Result<Vec<LhmHardware>, NoCrateError> {
+    with_wmi_async(&state, lhm::get_hardware).await
+}
+
+/// Query LHM sensors under one hardware node (`parent`, e.g.
+/// `/gpu-nvidia/0`) or whose identifier starts with `prefix`; all sensors
+/// if neither is given.
+#[tauri::command]
+pub async fn query_lhm_sensors(
+    state: State<'_, AppState>,
+    parent: Option<String>,
+    prefix: Option<String>,
+) -> Result<Vec<LhmSensor>, NoCrateError> {
+    with_wmi_async(&state, move |conn| {
+        let filter = match (&parent, &prefix) {
+            (Some(parent), _) => SensorFilter::Parent(parent),
+            (None, Some(prefix)) => SensorFilter::Prefix(prefix),
+            (None, None) => SensorFilter::All,
+        };
+        lhm::query_sensors(conn, filter, &[])
+    })
+    .await
+}
+
+/// Read every available sensor source merged into one list with stable IDs.
+/// Values are smoothed per `sensor_smoothing` unless `unsmoothed` is set.
+#[tauri::command]
+pub async fn get_all_sensors_unified(
+    state: State<'_, AppState>,
+    unsmoothed: Option<bool>,
+) -> Result<UnifiedSensorSnapshot, NoCrateError> {
+    Ok(sensors::collect_with(&state, unsmoothed.unwrap_or(false)))
+}
+
+/// Get the min / avg / max of every unified sensor since startup (or the
+/// last reset), overall and per thermal profile.
+#[tauri::command]
+pub fn get_sensor_stats(state: State<'_, AppState>) -> SensorStatsSnapshot {
+    state.sensor_stats.snapshot()
+}
+
+/// Clear the sensor statistics and start over.
+#[tauri::command]
+pub fn reset_sensor_stats(state: State<'_, AppState>) {
+    state.sensor_stats.reset();
+}
+
+/// Get the history of the last `seconds` of the given sensors (all if
+/// none are given), as min / avg / max buckets. The bucket width grows
+/// with the span: 1 s up to 10 min, 10 s up to 2 h, 1 min up to 24 h.
+#[tauri::command]
+pub fn get_sensor_history(
+    state: State<'_, AppState>,
+    ids: Option<Vec<String>>,
+    seconds: u64,
+) -> HistorySeries {
+    state
+        .sensor_history
+        .query(&ids.unwrap_or_default(), seconds)
+}
+
+/// Write the buffered history of the last `range` seconds of the given
+/// sensors (all if empty) to a CSV file at `path`, one row per bucket.
+/// Returns the number of rows written.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn export_history(
+    state: State<'_, AppState>,
+    sensor_ids: Vec<String>,
+    range: u64,
+    path: String,
+) -> Result<usize, NoCrateError> {
+    let history = state.sensor_history.query(&sensor_ids, range);
+    let rows = history.series.values().map(Vec::len).sum();
+    std::fs::write(&path, history.to_csv()).map_err(|e| {
+        NoCrateError::Unknown(i18n::tf(Msg::FileWrite, &[("path", &path), ("error", &e)]))
+    })?;
+    Ok(rows)
+}
+
+/// Read `NVMe` / SATA drive temperatures via the Windows storage IOCTLs.
+#[tauri::command]
+pub async fn get_storage_temperatures(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::storage::StorageTempReading>, NoCrateError> {
+    let storage = state.storage.as_ref().ok_or_else(|| {
         state
-            .wmi_error
-            .as_deref()
-            .unwrap_or("WMI 未初始化")
-            .to_string()
+            .storage_error
+            .clone()
+            .unwrap_or_else(|| i18n::t(Msg::StorageNotInitialized).into())
     })?;
-    wmi.execute(f).map_err(Into::into)
+    Ok(storage.read_all())
 }
 
-/// Check if LibreHardwareMonitor is accessible.
+/// Read GPU core / hotspot temperatures and fan speeds via NVML or ADL.
+#[tauri::command]
+pub async fn get_gpu_sensors(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::gpu::GpuDeviceSnapshot>, NoCrateError> {
+    let gpu = state.gpu.as_ref().ok_or_else(|| {
+        state
+            .gpu_error
+            .clone()
+            .unwrap_or_else(|| i18n::t(Msg::GpuNotInitialized).into())
+    })?;
+    Ok(gpu.read_all())
+}
+
+/// Read every sensor published by HWiNFO's shared memory. Fails if
+/// HWiNFO is not running or its shared memory support is disabled.
+#[tauri::command]
+pub async fn get_hwinfo_sensors() -> Result<HwinfoSnapshot, NoCrateError> {
+    hwinfo::read_all()
+}
+
+/// Read all EC sensors defined for the detected board.
+#[cfg(feature = "sio")]
 #[tauri::command]
-pub fn get_lhm_status(state: State<'_, AppState>) -> Result<LhmStatus, String> {
-    with_wmi(&state, |conn| Ok(lhm::get_lhm_status(conn)))
+pub async fn get_ec_sensors(
+    state: State<'_, AppState>,
+) -> Result<crate::ec::EcSnapshot, NoCrateError> {
+    state.ec.require(Msg::EcNotInitialized)?.read_all()
 }
 
-/// Get all sensor readings grouped by type.
+/// Read CPU package / Tctl / CCD temperatures directly from MSRs or AMD SMN.
+#[cfg(feature = "sio")]
 #[tauri::command]
-pub fn get_lhm_sensors(state: State<'_, AppState>) -> Result<LhmSensorSnapshot, String> {
-    with_wmi(&state, |conn| lhm::get_all_sensors(conn))
+pub async fn get_cpu_temperatures(
+    state: State<'_, AppState>,
+) -> Result<crate::cpu::CpuSnapshot, NoCrateError> {
+    state.cpu.require(Msg::CpuNotInitialized)?.read_all()
 }
@@ -0,0 +1,330 @@
+/// Aggregated subsystem status.
+///
+/// The state of each hardware subsystem used to be spread over
+/// `get_wmi_health`, `get_sio_status`, `get_lhm_status`, the AURA
+/// controller list and the error of whichever sensor command failed.
+/// `get_system_status` reports all of them in one call, each with its
+/// init error and a suggested fix the settings page can show as is.
+/// `reinit_subsystem` retries a failed one after the fix, without
+/// restarting the app.
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use super::system::is_admin;
+use crate::config::PausedService;
+use crate::conflicts::{self, Conflict};
+use crate::error::NoCrateError;
+use crate::i18n::{self, Msg};
+#[cfg(feature = "sio")]
+use crate::state::Subsystem;
+use crate::state::{self, AppState, WmiThreadAsync};
+use crate::wmi::lhm::{self, LhmStatus};
+use crate::wmi::thread::WmiStatus;
+
+/// Whether a subsystem can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemState {
+    Ready,
+    /// Still being brought up in the background after startup.
+    Initializing,
+    /// Working with reduced functionality, or recovering from errors.
+    Degraded,
+    Unavailable,
+    /// Turned off in the settings.
+    Disabled,
+}
+
+/// State of one subsystem.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemStatus {
+    /// `"wmi"`, `"lhm"`, `"aura"`, `"storage"`, `"gpu"`, `"sio"`, `"ec"`
+    /// or `"cpu"`.
+    pub name: &'static str,
+    pub state: SubsystemState,
+    /// Why the subsystem is not ready, if known.
+    pub error: Option<NoCrateError>,
+    /// What was found, e.g. the Super I/O chip or the number of LHM sensors.
+    pub detail: Option<String>,
+    /// What the user can do about it.
+    pub remediation: Option<String>,
+}
+
+/// Status of every subsystem.
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemStatus {
+    /// The process runs elevated.
+    pub admin: bool,
+    /// Started with `--simulate`.
+    pub simulated: bool,
+    pub subsystems: Vec<SubsystemStatus>,
+}
+
+impl SubsystemStatus {
+    const fn ready(name: &'static str, detail: Option<String>) -> Self {
+        Self {
+            name,
+            state: SubsystemState::Ready,
+            error: None,
+            detail,
+            remediation: None,
+        }
+    }
+
+    const fn initializing(name: &'static str) -> Self {
+        Self {
+            name,
+            state: SubsystemState::Initializing,
+            error: None,
+            detail: None,
+            remediation: None,
+        }
+    }
+
+    fn failed(name: &'static str, error: Option<NoCrateError>, admin: bool) -> Self {
+        let remediation = remediation(name, error.as_ref(), admin);
+        Self {
+            name,
+            state: SubsystemState::Unavailable,
+            error,
+            detail: None,
+            remediation,
+        }
+    }
+}
+
+/// Status of a subsystem kept in a [`Subsystem`] slot; `detail` describes
+/// the running instance.
+#[cfg(feature = "sio")]
+fn slot_status<T: Clone>(
+    name: &'static str,
+    slot: &Subsystem<T>,
+    admin: bool,
+    detail: impl FnOnce(T) -> Option<String>,
+) -> SubsystemStatus {
+    match slot.get() {
+        Some(value) => SubsystemStatus::ready(name, detail(value)),
+        None if slot.is_pending() => SubsystemStatus::initializing(name),
+        None => SubsystemStatus::failed(name, slot.error(), admin),
+    }
+}
+
+/// Suggested fix for a failed subsystem.
+fn remediation(name: &str, error: Option<&NoCrateError>, admin: bool) -> Option<String> {
+    let needs_admin = matches!(name, "wmi" | "sio" | "ec" | "cpu");
+    let hint = match error {
+        Some(NoCrateError::AccessDenied(_)) => Msg::HintRunAsAdmin,
+        _ if needs_admin && !admin => Msg::HintRunAsAdmin,
+        Some(NoCrateError::DriverBlocked(_)) => Msg::HintDriverBlocked,
+        Some(NoCrateError::Busy(_)) => Msg::HintBusy,
+        Some(NoCrateError::Timeout(_)) => Msg::HintTimeout,
+        Some(NoCrateError::DeviceNotPresent(_)) => return None,
+        _ => match name {
+            "wmi" => Msg::HintWmiDriver,
+            "lhm" => Msg::HintLhm,
+            "aura" => Msg::HintAura,
+            "gpu" => Msg::HintGpu,
+            _ => return None,
+        },
+    };
+    Some(i18n::t(hint).to_string())
+}
+
+/// Get the state of every hardware subsystem in one call.
+#[tauri::command]
+pub async fn get_system_status(state: State<'_, AppState>) -> Result<SystemStatus, NoCrateError> {
+    let admin = is_admin();
+    let mut subsystems = Vec::new();
+
+    if state.wmi.is_pending() {
+        subsystems.push(SubsystemStatus::initializing("wmi"));
+        subsystems.push(SubsystemStatus::initializing("lhm"));
+    } else if let Some(wmi) = state.wmi.get() {
+        let health = wmi.health();
+        let backend = wmi
+            .execute_async(|conn| {
+                Ok((
+                    conn.backend.backend_type().to_string(),
+                    lhm::get_lhm_status(conn),
+                ))
+            })
+            .await;
+        subsystems.push(match health.status {
+            WmiStatus::Connected => SubsystemStatus::ready(
+                "wmi",
+                backend.as_ref().ok().map(|(backend, _)| backend.clone()),
+            ),
+            WmiStatus::Reconnecting | WmiStatus::Disconnected => SubsystemStatus {
+                state: SubsystemState::Degraded,
+                error: health.last_error.map(NoCrateError::Wmi),
+                remediation: Some(i18n::t(Msg::WmiReconnecting).to_string()),
+                ..SubsystemStatus::ready("wmi", None)
+            },
+        });
+        subsystems.push(match backend {
+            Ok((_, LhmStatus::Available { sensor_count })) => {
+                SubsystemStatus::ready(
+                    "lhm",
+                    Some(i18n::tf(Msg::LhmSensorCount, &[("count", &sensor_count)])),
+                )
+            }
+            Ok((_, LhmStatus::NoSensors)) => SubsystemStatus {
+                state: SubsystemState::Degraded,
+                detail: Some(i18n::t(Msg::LhmNoSensors).to_string()),
+                remediation: remediation("lhm", None, admin),
+                ..SubsystemStatus::ready("lhm", None)
+            },
+            Ok((_, LhmStatus::Unavailable)) => SubsystemStatus::failed("lhm", None, admin),
+            Err(e) => SubsystemStatus::failed("lhm", Some(e), admin),
+        });
+    } else {
+        subsystems.push(SubsystemStatus::failed("wmi", state.wmi.error(), admin));
+        subsystems.push(SubsystemStatus::failed("lhm", None, admin));
+    }
+
+    let controllers = state.aura.lock().len();
+    subsystems.push(if state.config.get().openrgb.enabled {
+        SubsystemStatus {
+            state: SubsystemState::Disabled,
+            detail: Some(i18n::t(Msg::AuraOpenRgb).to_string()),
+            ..SubsystemStatus::ready("aura", None)
+        }
+    } else if controllers > 0 {
+        SubsystemStatus::ready(
+            "aura",
+            Some(i18n::tf(Msg::AuraControllerCount, &[("count", &controllers)])),
+        )
+    } else if state.is_initializing() {
+        SubsystemStatus::initializing("aura")
+    } else {
+        SubsystemStatus::failed("aura", None, admin)
+    });
+
+    subsystems.push(if state.storage.is_some() {
+        SubsystemStatus::ready("storage", None)
+    } else {
+        SubsystemStatus::failed("storage", state.storage_error.clone(), admin)
+    });
+    subsystems.push(if state.gpu.is_some() {
+        SubsystemStatus::ready("gpu", None)
+    } else {
+        SubsystemStatus::failed("gpu", state.gpu_error.clone(), admin)
+    });
+
+    #[cfg(feature = "sio")]
+    {
+        subsystems.push(slot_status("sio", &state.sio, admin, |sio| {
+            sio.status().chip_name
+        }));
+        subsystems.push(slot_status("ec", &state.ec, admin, |_| None));
+        subsystems.push(slot_status("cpu", &state.cpu, admin, |_| None));
+    }
+
+    Ok(SystemStatus {
+        admin,
+        simulated: state.simulated,
+        subsystems,
+    })
+}
+
+/// Retry initializing `name` (`"wmi"`, `"sio"` or `"aura"`), e.g. after
+/// installing a driver or starting a service. A running instance is only
+/// replaced if the new one initializes.
+#[tauri::command]
+pub async fn reinit_subsystem(app: AppHandle, name: String) -> Result<(), NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        match name.as_str() {
+            "wmi" => {
+                let wmi = state.reinit_wmi()?;
+                state::forward_wmi_status(&app, &wmi);
+            }
+            #[cfg(feature = "sio")]
+            "sio" => {
+                let _ = state.reinit_sio()?;
+            }
+            "aura" => {
+                if state.reinit_aura(&app) == 0 {
+                    return Err(NoCrateError::DeviceNotPresent(
+                        i18n::t(Msg::AuraNotFound).into(),
+                    ));
+                }
+            }
+            _ => {
+                return Err(NoCrateError::Unknown(i18n::tf(
+                    Msg::SubsystemUnknown,
+                    &[("name", &name)],
+                )));
+            }
+        }
+        eprintln!("Subsystem {name} re-initialized");
+        Ok(())
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// Other software currently running that drives the same fans, lighting
+/// or sensors, e.g. Armoury Crate.
+#[tauri::command]
+pub fn get_conflicts() -> Vec<Conflict> {
+    conflicts::detect()
+}
+
+/// Stop and disable the Armoury Crate services so their agents stop
+/// re-applying fan policies. Only called after the user confirmed; the
+/// services stay disabled, across reboots too, until
+/// `restore_paused_services`. Each paused service is saved to the config
+/// right away, so a failure part way through loses nothing.
+#[tauri::command]
+pub async fn pause_conflicting_services(app: AppHandle) -> Result<Vec<PausedService>, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let mut first_error = None;
+        for &name in conflicts::pausable_services() {
+            if state.config.get().paused_services.iter().any(|s| s.name == name) {
+                continue;
+            }
+            match conflicts::pause_service(name) {
+                Ok(Some(paused)) => {
+                    eprintln!("Paused service {name}");
+                    let _ = state.config.update(|cfg| cfg.paused_services.push(paused))?;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Failed to pause service {name}: {e}");
+                    first_error = first_error.or(Some(e));
+                }
+            }
+        }
+        first_error.map_or_else(|| Ok(state.config.get().paused_services), Err)
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// Give the services paused by `pause_conflicting_services` their start
+/// type back and start those that were running. Services that could not
+/// be restored stay in the config for the next attempt.
+#[tauri::command]
+pub async fn restore_paused_services(app: AppHandle) -> Result<(), NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let mut first_error = None;
+        for service in state.config.get().paused_services {
+            match conflicts::restore_service(&service) {
+                Ok(()) => {
+                    eprintln!("Restored service {}", service.name);
+                    let _ = state.config.update(|cfg| cfg.paused_services.retain(|s| *s != service))?;
+                }
+                Err(e) => {
+                    eprintln!("Failed to restore service {}: {e}", service.name);
+                    first_error = first_error.or(Some(e));
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
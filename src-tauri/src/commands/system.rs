@@ -1,16 +1,33 @@
 /// System-level commands (admin check, UAC elevation, auto-start, etc.)
 use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
 
-use windows::core::PCWSTR;
-use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use serde::Serialize;
+use tauri::State;
+use windows::core::{BSTR, PCWSTR};
+use windows::Win32::Foundation::{CloseHandle, HANDLE, RPC_E_CHANGED_MODE};
 use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+};
 use windows::Win32::System::Registry::{
     RegDeleteValueW, RegOpenKeyExW, RegSetValueExW, HKEY_CURRENT_USER, KEY_SET_VALUE, REG_SZ,
 };
+use windows::Win32::System::TaskScheduler::{
+    ITaskFolder, ITaskService, TaskScheduler, TASK_CREATE_OR_UPDATE, TASK_LOGON_INTERACTIVE_TOKEN,
+};
 use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+use windows::Win32::System::Variant::VARIANT;
 use windows::Win32::UI::Shell::ShellExecuteW;
 use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
 
+use crate::board::BoardSummary;
+use crate::error::NoCrateError;
+use crate::i18n::{self, Msg};
+use crate::state::AppState;
+use crate::service::{self, ServiceStatus};
+use crate::updates::{self, UpdateInfo};
+
 /// Check whether the current process is running with elevated (admin) privileges.
 #[tauri::command]
 pub fn is_admin() -> bool {
@@ -23,8 +40,9 @@ pub fn is_admin() -> bool {
 /// If the user declines the UAC prompt, `ShellExecuteW` returns ≤ 32 and we
 /// return an error instead of exiting.
 #[tauri::command]
-pub fn restart_as_admin(app: tauri::AppHandle) -> Result<(), String> {
-    let exe = std::env::current_exe().map_err(|e| format!("无法获取当前程序路径: {e}"))?;
+pub fn restart_as_admin(app: tauri::AppHandle) -> Result<(), NoCrateError> {
+    let exe =
+        std::env::current_exe().map_err(|e| i18n::tf(Msg::ExePathUnknown, &[("error", &e)]))?;
     let exe_wide: Vec<u16> = exe
         .as_os_str()
         .encode_wide()
@@ -50,7 +68,7 @@ pub fn restart_as_admin(app: tauri::AppHandle) -> Result<(), String> {
         app.exit(0);
         Ok(())
     } else {
-        Err("用户取消了管理员提权请求".into())
+        Err(i18n::t(Msg::ElevationCancelled).into())
     }
 }
 
@@ -61,27 +79,72 @@ pub fn restart_as_admin(app: tauri::AppHandle) -> Result<(), String> {
 const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
 const APP_VALUE_NAME: &str = "NoCrate";
 
-/// Enable or disable auto-start at login via the Windows registry.
+/// How auto-start at login is set up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoStartMethod {
+    /// Scheduled task running with highest privileges.
+    Task,
+    /// `HKCU\...\Run` value; prompts for UAC at login.
+    Registry,
+}
+
+/// Enable or disable auto-start at login.
 ///
-/// Writes to `HKCU\Software\Microsoft\Windows\CurrentVersion\Run`.
+/// A `Run` key entry cannot start the app with the administrator rights
+/// its manifest requires without a UAC prompt at every login. When this
+/// process is elevated, a Task Scheduler logon task with highest
+/// privileges is used instead and any registry entry is removed; otherwise
+/// the app falls back to `HKCU\Software\Microsoft\Windows\CurrentVersion\Run`.
+/// Either way the app starts in the tray ([`MINIMIZED_ARG`](crate::MINIMIZED_ARG)).
 #[tauri::command]
-pub fn set_auto_start(enabled: bool) -> Result<(), String> {
+pub fn set_auto_start(enabled: bool) -> Result<(), NoCrateError> {
     if enabled {
-        let exe = std::env::current_exe()
-            .map_err(|e| format!("无法获取当前程序路径: {e}"))?;
-        let exe_path = format!("\"{}\"", exe.display());
-        registry_set_run_value(APP_VALUE_NAME, &exe_path)
-            .map_err(|e| format!("写入注册表失败: {e}"))
+        let exe =
+            std::env::current_exe().map_err(|e| i18n::tf(Msg::ExePathUnknown, &[("error", &e)]))?;
+        if is_elevated().unwrap_or(false) {
+            create_logon_task(&exe)?;
+            let _ = registry_delete_run_value(APP_VALUE_NAME);
+            return Ok(());
+        }
+        let exe_path = format!("\"{}\" {}", exe.display(), crate::MINIMIZED_ARG);
+        registry_set_run_value(APP_VALUE_NAME, &exe_path).map_err(|e| {
+            NoCrateError::classify(
+                &e,
+                i18n::tf(Msg::AutostartRegistryWrite, &[("error", &e)]),
+                NoCrateError::Unknown,
+            )
+        })
     } else {
-        registry_delete_run_value(APP_VALUE_NAME)
-            .map_err(|e| format!("删除注册表项失败: {e}"))
+        if logon_task_exists() {
+            delete_logon_task()?;
+        }
+        registry_delete_run_value(APP_VALUE_NAME).map_err(|e| {
+            NoCrateError::classify(
+                &e,
+                i18n::tf(Msg::AutostartRegistryDelete, &[("error", &e)]),
+                NoCrateError::Unknown,
+            )
+        })
     }
 }
 
-/// Check whether the auto-start registry key is currently set.
+/// Check whether auto-start is currently set up, by either method.
 #[tauri::command]
 pub fn get_auto_start_enabled() -> bool {
-    registry_has_run_value(APP_VALUE_NAME)
+    get_auto_start_method().is_some()
+}
+
+/// How auto-start is currently set up, or `None` if it is off.
+#[tauri::command]
+pub fn get_auto_start_method() -> Option<AutoStartMethod> {
+    if logon_task_exists() {
+        Some(AutoStartMethod::Task)
+    } else if registry_has_run_value(APP_VALUE_NAME) {
+        Some(AutoStartMethod::Registry)
+    } else {
+        None
+    }
 }
 
 /// Write a value to `HKCU\...\Run`.
@@ -175,6 +238,141 @@ fn registry_has_run_value(name: &str) -> bool {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Auto-start (Task Scheduler)
+// ---------------------------------------------------------------------------
+
+/// Name of the logon task in the Task Scheduler root folder.
+const TASK_NAME: &str = "NoCrate";
+
+/// Task definition: start at the current user's logon with highest
+/// privileges, on battery too, without a run time limit.
+const TASK_XML: &str = r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Description>Start NoCrate at logon with administrator rights</Description>
+  </RegistrationInfo>
+  <Triggers>
+    <LogonTrigger>
+      <Enabled>true</Enabled>
+      <UserId>$USER</UserId>
+    </LogonTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Author">
+      <UserId>$USER</UserId>
+      <LogonType>InteractiveToken</LogonType>
+      <RunLevel>HighestAvailable</RunLevel>
+    </Principal>
+  </Principals>
+  <Settings>
+    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>
+    <Priority>7</Priority>
+  </Settings>
+  <Actions Context="Author">
+    <Exec>
+      <Command>$COMMAND</Command>
+      <Arguments>$ARGUMENTS</Arguments>
+    </Exec>
+  </Actions>
+</Task>
+"#;
+
+/// Create (or replace) the logon task for `exe`. Requires elevation.
+#[allow(unsafe_code)]
+fn create_logon_task(exe: &Path) -> Result<(), NoCrateError> {
+    let user = match (std::env::var("USERDOMAIN"), std::env::var("USERNAME")) {
+        (Ok(domain), Ok(name)) => format!(r"{domain}\{name}"),
+        (Err(_), Ok(name)) => name,
+        _ => return Err(i18n::t(Msg::AutostartUserUnknown).into()),
+    };
+    let xml = TASK_XML
+        .replace("$USER", &xml_escape(&user))
+        .replace("$COMMAND", &xml_escape(&exe.display().to_string()))
+        .replace("$ARGUMENTS", crate::MINIMIZED_ARG);
+
+    with_task_folder(|folder| unsafe {
+        folder
+            .RegisterTask(
+                &BSTR::from(TASK_NAME),
+                &BSTR::from(xml),
+                TASK_CREATE_OR_UPDATE.0,
+                &VARIANT::default(),
+                &VARIANT::default(),
+                TASK_LOGON_INTERACTIVE_TOKEN,
+                &VARIANT::default(),
+            )
+            .map(drop)
+    })
+    .map_err(|e| {
+        NoCrateError::classify(
+            &e,
+            i18n::tf(Msg::AutostartTaskCreate, &[("error", &e)]),
+            NoCrateError::Unknown,
+        )
+    })
+}
+
+/// Delete the logon task. Requires elevation.
+#[allow(unsafe_code)]
+fn delete_logon_task() -> Result<(), NoCrateError> {
+    with_task_folder(|folder| unsafe { folder.DeleteTask(&BSTR::from(TASK_NAME), 0) }).map_err(
+        |e| {
+            NoCrateError::classify(
+                &e,
+                i18n::tf(Msg::AutostartTaskDelete, &[("error", &e)]),
+                NoCrateError::Unknown,
+            )
+        },
+    )
+}
+
+/// Check whether the logon task exists.
+#[allow(unsafe_code)]
+fn logon_task_exists() -> bool {
+    with_task_folder(|folder| unsafe { folder.GetTask(&BSTR::from(TASK_NAME)).map(drop) }).is_ok()
+}
+
+/// Run `f` on the Task Scheduler root folder.
+///
+/// The task is registered through the Task Scheduler API rather than by
+/// handing `schtasks.exe` an XML file, so there is no file on disk for
+/// another process to swap and no executable looked up by name. `f` must
+/// not return COM interfaces, as COM may be uninitialized on return.
+#[allow(unsafe_code)]
+fn with_task_folder<T>(
+    f: impl FnOnce(&ITaskFolder) -> windows::core::Result<T>,
+) -> windows::core::Result<T> {
+    unsafe {
+        // A thread already in a single-threaded apartment can use COM as is
+        let hr = CoInitializeEx(None, COINIT_MULTITHREADED);
+        if hr.is_err() && hr != RPC_E_CHANGED_MODE {
+            return Err(hr.into());
+        }
+        let result = (|| {
+            let service: ITaskService =
+                CoCreateInstance(&TaskScheduler, None, CLSCTX_INPROC_SERVER)?;
+            let none = VARIANT::default();
+            service.Connect(&none, &none, &none, &none)?;
+            f(&service.GetFolder(&BSTR::from("\\"))?)
+        })();
+        if hr.is_ok() {
+            CoUninitialize();
+        }
+        result
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 #[allow(unsafe_code)]
 fn is_elevated() -> Option<bool> {
     unsafe {
@@ -199,3 +397,79 @@ fn is_elevated() -> Option<bool> {
         Some(elevation.TokenIsElevated != 0)
     }
 }
+
+/// Get the detected board model and the quirks applied for it.
+#[tauri::command]
+pub fn get_board_info(state: State<'_, AppState>) -> BoardSummary {
+    BoardSummary {
+        info: state.board.clone(),
+        quirks: state.quirks,
+    }
+}
+
+/// Show or hide the sensor overlay window. Returns whether it is now
+/// visible.
+///
+/// Async so the window is not created on the main thread while it is
+/// blocked by this command.
+#[tauri::command]
+pub async fn toggle_overlay(app: tauri::AppHandle) -> bool {
+    crate::overlay::toggle(&app)
+}
+
+/// Replace the local API token with a new random one and return it.
+/// Clients using the old token are disconnected.
+#[tauri::command]
+pub fn regenerate_api_token(state: State<'_, AppState>) -> Result<String, NoCrateError> {
+    let token = crate::api::generate_token()?;
+    let _ = state
+        .config
+        .update(|cfg| cfg.api.token.clone_from(&token))?;
+    Ok(token)
+}
+
+/// The last `n` lines the app logged, oldest first. New lines arrive as
+/// `log-line` events.
+#[tauri::command]
+pub fn get_recent_logs(n: usize) -> Vec<crate::logs::LogLine> {
+    crate::logs::recent(n)
+}
+
+/// Compare the running version with the newest GitHub release and return
+/// its changelog and download link.
+#[tauri::command]
+pub async fn check_for_updates(state: State<'_, AppState>) -> Result<UpdateInfo, NoCrateError> {
+    let include_prereleases = state.config.get().updates.include_prereleases;
+    tauri::async_runtime::spawn_blocking(move || updates::check(include_prereleases))
+        .await
+        .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+// ---------------------------------------------------------------------------
+// NoCrate service
+// ---------------------------------------------------------------------------
+
+/// Whether the `NoCrate` service is installed and running.
+#[tauri::command]
+pub fn get_service_status() -> ServiceStatus {
+    service::status()
+}
+
+/// Install and start the `NoCrate` service for the current user, through a
+/// UAC prompt when not elevated. The app switches over to it on the next
+/// `reinit_subsystem` or restart.
+#[tauri::command]
+pub async fn install_service() -> Result<ServiceStatus, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(|| service::install().map(|()| service::status()))
+        .await
+        .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// Stop and remove the `NoCrate` service, through a UAC prompt when not
+/// elevated. Without it the app needs administrator rights again.
+#[tauri::command]
+pub async fn uninstall_service() -> Result<ServiceStatus, NoCrateError> {
+    tauri::async_runtime::spawn_blocking(|| service::uninstall().map(|()| service::status()))
+        .await
+        .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
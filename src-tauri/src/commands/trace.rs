@@ -0,0 +1,77 @@
+/// Sensor trace recording and replay.
+///
+/// `record_sensor_trace` runs the probes of [`crate::trace`] through
+/// recording backends and writes the raw WMI answers and Super I/O
+/// register values, together with what the app parsed from them, to a
+/// JSON file a user can attach to a bug report. `replay_sensor_trace`
+/// feeds such a file back through the same parsing code and tells
+/// whether the result still matches the recording.
+use tauri::{AppHandle, Manager};
+
+use crate::error::NoCrateError;
+use crate::i18n::{self, Msg};
+use crate::state::AppState;
+use crate::trace::{self, SensorTrace, TraceReplay, TraceReport};
+
+/// Record the raw hardware reads of one probe to `path`.
+#[tauri::command]
+pub async fn record_sensor_trace(app: AppHandle, path: String) -> Result<(), NoCrateError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let mut report = TraceReport::default();
+
+        let wmi = match state.wmi.get() {
+            Some(wmi) => {
+                let (trace, probe) = wmi.execute(|conn| Ok(trace::record_wmi(conn)))?;
+                report.wmi = Some(probe);
+                Some(trace)
+            }
+            None => None,
+        };
+
+        // Outside the WMI closure: the ASUS WMI access calls the WMI thread
+        #[cfg(feature = "sio")]
+        let sio = match crate::commands::fan::with_sio_access(&state, trace::record_sio) {
+            Ok((trace, probe)) => {
+                report.sio = Some(probe);
+                Some(trace)
+            }
+            Err(e) => {
+                report.sio = Some(Err((&e).into()));
+                None
+            }
+        };
+
+        let trace = SensorTrace::new(
+            state.board.as_ref().map(|b| b.product.clone()),
+            wmi,
+            #[cfg(feature = "sio")]
+            sio,
+            &report,
+        );
+        let json = serde_json::to_string_pretty(&trace)
+            .map_err(|e| NoCrateError::Unknown(e.to_string()))?;
+        std::fs::write(&path, json).map_err(|e| {
+            NoCrateError::Unknown(i18n::tf(Msg::FileWrite, &[("path", &path), ("error", &e)]))
+        })
+    })
+    .await
+    .map_err(|e| NoCrateError::Unknown(e.to_string()))?
+}
+
+/// Replay a trace recorded by [`record_sensor_trace`] through the
+/// parsing code and compare the result with the recording.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn replay_sensor_trace(path: String) -> Result<TraceReplay, NoCrateError> {
+    let json = std::fs::read_to_string(&path).map_err(|e| {
+        NoCrateError::Unknown(i18n::tf(Msg::FileRead, &[("path", &path), ("error", &e)]))
+    })?;
+    let trace: SensorTrace = serde_json::from_str(&json).map_err(|e| {
+        NoCrateError::Unknown(i18n::tf(
+            Msg::TraceInvalid,
+            &[("path", &path), ("error", &e)],
+        ))
+    })?;
+    Ok(trace.replay())
+}
@@ -1,18 +1,47 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 
+pub use nocrate_core::aura::ambient::AmbilightZone;
+pub use nocrate_core::aura::openrgb::OpenRgbConfig;
+pub use nocrate_core::board::SioChannelConfig;
+pub use nocrate_core::i18n::Locale;
+pub use nocrate_core::sensors::polling::PollingTiers;
+pub use nocrate_core::sensors::units::UnitsConfig;
+pub use nocrate_core::sensors::SensorCalibration;
+
+use crate::aura::protocol::ColorCalibration;
 use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::power_source::PowerSource;
+use crate::wmi::asus_mgmt::{
+    DesktopFanMode, DesktopFanPolicy, FanCalibration, FanCurve, FanCurvePoint, ThermalProfile,
+    FAN_CURVE_POINTS,
+};
+use crate::wmi::ppt::PowerLimits;
 
 /// Global config file path, set once during app setup.
 static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
 
+/// Number of rotating backups kept next to the config file
+/// (`config.json.bak.1` is the newest).
+const BACKUP_COUNT: usize = 5;
+
+/// Minimum age of the newest backup before another is taken, so a burst
+/// of small changes (e.g. dragging a slider) does not rotate out every
+/// older backup.
+const BACKUP_INTERVAL: Duration = Duration::from_hours(1);
+
 /// Application configuration persisted as JSON.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct AppConfig {
     /// User-selected theme: "light" | "dark" | "system"
     pub theme: String,
@@ -20,9 +49,17 @@ pub struct AppConfig {
     /// Whether to minimize to system tray on close
     pub close_to_tray: bool,
 
+    /// Whether to start with only the tray icon, without showing the
+    /// main window (also requested by the `--minimized` argument)
+    pub start_minimized: bool,
+
     /// Whether to launch at system startup
     pub auto_start: bool,
 
+    /// Re-apply the last thermal profile, desktop fan policies and AURA
+    /// effect at startup
+    pub restore_on_startup: bool,
+
     /// Fan polling interval in milliseconds
     pub fan_poll_interval_ms: u64,
 
@@ -43,6 +80,502 @@ pub struct AppConfig {
 
     /// Temperature threshold in °C for alerts
     pub temp_alert_threshold: u8,
+
+    /// Alerts for fans reporting 0 RPM while they should be spinning
+    pub fan_stall: FanStallConfig,
+
+    /// Emergency full fan speed on critical temperatures, independent of
+    /// the fan curves
+    pub thermal_failsafe: ThermalFailsafeConfig,
+
+    /// Per-channel Super I/O display names and visibility
+    pub sio_channels: SioChannelConfig,
+
+    /// Per-sensor calibration keyed by unified sensor ID
+    /// (e.g. `sio/nct6798d/temp/1`)
+    pub sensor_calibration: HashMap<String, SensorCalibration>,
+
+    /// Per-sensor moving-average window in seconds keyed by unified sensor
+    /// ID; sensors without an entry are not smoothed
+    pub sensor_smoothing: HashMap<String, u16>,
+
+    /// Sensors read only every few seconds (e.g. drive temperatures),
+    /// keeping their last reading in between
+    pub sensor_polling: PollingTiers,
+
+    /// Units of the `display_value` added to readings in sensor commands
+    /// and events; raw values stay in °C
+    pub units: UnitsConfig,
+
+    /// Language of backend text: error messages, subsystem hints,
+    /// built-in sensor names and the tray menu (read at startup)
+    pub locale: Locale,
+
+    /// Slower sensor polling while the window is hidden to the tray
+    pub eco: EcoConfig,
+
+    /// Screen regions mirrored to AURA channels by the ambient effect
+    pub ambilight_zones: Vec<AmbilightZone>,
+
+    /// Forward software effects to an OpenRGB server instead of the
+    /// AURA HID devices
+    pub openrgb: OpenRgbConfig,
+
+    /// Time window in which AURA lighting is dimmed or turned off
+    pub lighting_schedule: LightingSchedule,
+
+    /// Per-controller gamma / white-point correction keyed by USB PID
+    /// as four lowercase hex digits (e.g. `19af`)
+    pub aura_calibration: HashMap<String, ColorCalibration>,
+
+    /// Laptop battery charge limit in percent, re-applied at startup
+    /// (`None` = leave the firmware setting alone)
+    pub battery_charge_limit: Option<u8>,
+
+    /// Laptop power limits per thermal profile, re-applied after every
+    /// profile switch
+    pub power_limits: HashMap<ThermalProfile, PowerLimits>,
+
+    /// Custom laptop fan curves per thermal profile, written after every
+    /// profile switch (profiles without an entry use the firmware curves)
+    pub laptop_fan_curves: HashMap<ThermalProfile, Vec<FanCurve>>,
+
+    /// Laptop settings on battery and switching on plug / unplug; the
+    /// `power_limits` and `laptop_fan_curves` above are the AC settings
+    pub power_source: PowerSourceConfig,
+
+    /// Q-Fan calibration results per desktop fan header index, measured
+    /// by `start_fan_calibration`
+    pub fan_calibration: HashMap<u8, FanCalibration>,
+
+    /// Desktop fan policies per header index as last set from the app,
+    /// re-applied at startup
+    pub desktop_fan_policies: HashMap<u8, DesktopFanPolicy>,
+
+    /// Named groups of desktop fan headers sharing one curve, e.g. all
+    /// chassis fans; a header belongs to at most one group
+    pub fan_groups: Vec<FanGroup>,
+
+    /// LibreHardwareMonitor integration
+    pub lhm: LhmConfig,
+
+    /// Read sensors from HWiNFO's shared memory when it is running
+    pub hwinfo_enabled: bool,
+
+    /// Values published to the RivaTuner Statistics Server overlay
+    pub rtss: RtssConfig,
+
+    /// Always-on-top sensor overlay window
+    pub overlay: OverlayConfig,
+
+    /// Prometheus `/metrics` HTTP endpoint
+    pub metrics: MetricsConfig,
+
+    /// MQTT publishing with Home Assistant discovery
+    pub mqtt: MqttConfig,
+
+    /// Local HTTP / WebSocket control API
+    pub api: ApiConfig,
+
+    /// System-wide shortcuts for profile, lighting and fan actions
+    pub hotkeys: HotkeyConfig,
+
+    /// Checking GitHub releases for a newer version
+    pub updates: UpdateConfig,
+
+    /// `WinRing0` kernel driver management
+    pub driver: DriverConfig,
+
+    /// Armoury Crate services stopped and disabled by
+    /// `pause_conflicting_services`, kept until they are restored
+    pub paused_services: Vec<PausedService>,
+}
+
+/// Fan stall detection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FanStallConfig {
+    pub enabled: bool,
+    /// Consecutive 0 RPM polls before a fan counts as stalled
+    pub polls: u32,
+    /// Duty in percent above which a watched fan must be spinning
+    pub min_duty_pct: u8,
+    /// Watched fans keyed by unified fan sensor ID (e.g.
+    /// `sio/nct6798d/fan/1`); fans without an entry are not watched
+    pub fans: HashMap<String, FanStallWatch>,
+}
+
+impl Default for FanStallConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            polls: 3,
+            min_duty_pct: 20,
+            fans: HashMap::new(),
+        }
+    }
+}
+
+/// Emergency thermal failsafe.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThermalFailsafeConfig {
+    pub enabled: bool,
+    /// Temperature in °C that triggers the failsafe
+    pub critical_temp_c: u8,
+    /// Seconds a temperature must stay at or above the limit
+    pub hold_secs: u64,
+    /// Also switch to the Performance thermal profile while triggered
+    pub switch_to_performance: bool,
+    /// Watched unified temperature sensor IDs; empty watches all
+    pub sensors: Vec<String>,
+}
+
+impl Default for ThermalFailsafeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            critical_temp_c: 95,
+            hold_secs: 5,
+            switch_to_performance: false,
+            sensors: Vec::new(),
+        }
+    }
+}
+
+/// Reduced sensor polling while the main window is hidden.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EcoConfig {
+    pub enabled: bool,
+    /// Seconds between sensor reads while hidden and no background
+    /// feature needs live readings
+    pub interval_secs: u64,
+    /// Stop the background history sampling while hidden instead of
+    /// slowing it to `interval_secs`; charts then have a gap
+    pub pause_history: bool,
+}
+
+impl Default for EcoConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 10,
+            pause_history: false,
+        }
+    }
+}
+
+/// Stall detection settings of one fan.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FanStallWatch {
+    pub enabled: bool,
+    /// Unified sensor ID of the fan's duty (`fan_percent`), if available;
+    /// without one the fan is expected to spin at all times
+    pub duty_sensor: Option<String>,
+}
+
+/// Laptop thermal settings per power source.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerSourceConfig {
+    /// Re-apply the thermal settings of the new source on plug / unplug
+    pub auto_switch: bool,
+    /// Thermal profile switched to on AC (`None` = keep the current one)
+    pub ac_profile: Option<ThermalProfile>,
+    /// Thermal profile switched to on battery (`None` = keep the current one)
+    pub battery_profile: Option<ThermalProfile>,
+    /// Power limits on battery per thermal profile; profiles without an
+    /// entry use the AC limits
+    pub battery_power_limits: HashMap<ThermalProfile, PowerLimits>,
+    /// Fan curves on battery per thermal profile; profiles without an
+    /// entry use the AC curves
+    pub battery_fan_curves: HashMap<ThermalProfile, Vec<FanCurve>>,
+}
+
+impl PowerSourceConfig {
+    /// Thermal profile configured for `source`, if any.
+    #[must_use]
+    pub const fn profile_for(&self, source: PowerSource) -> Option<ThermalProfile> {
+        match source {
+            PowerSource::Ac => self.ac_profile,
+            PowerSource::Battery => self.battery_profile,
+        }
+    }
+}
+
+/// Desktop fan headers controlled as one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanGroup {
+    pub name: String,
+    /// Member header indices (`FanType`)
+    pub fan_types: Vec<u8>,
+    /// Curve last written to every member by `set_fan_group_curve`
+    #[serde(default)]
+    pub curve: Option<FanGroupCurve>,
+}
+
+/// The curve shared by the members of a [`FanGroup`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanGroupCurve {
+    pub mode: DesktopFanMode,
+    pub points: [FanCurvePoint; FAN_CURVE_POINTS],
+}
+
+/// A Windows service stopped and disabled at the user's request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PausedService {
+    pub name: String,
+    /// Start type (`SERVICE_START_TYPE`) before it was disabled
+    pub start_type: u32,
+    /// It was running and is started again when restored
+    pub was_running: bool,
+}
+
+/// LibreHardwareMonitor integration settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LhmConfig {
+    /// Start LHM at launch when its WMI namespace is unavailable
+    pub auto_launch: bool,
+    /// `LibreHardwareMonitor.exe` location (`None` = search the usual
+    /// install folders)
+    pub path: Option<String>,
+    /// Sensor identifiers polled by the dashboard (empty = all sensors)
+    pub sensors: Vec<String>,
+}
+
+/// RivaTuner Statistics Server on-screen display output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RtssConfig {
+    pub enabled: bool,
+    /// Lines shown in the overlay, in order
+    pub items: Vec<RtssItem>,
+}
+
+/// One line of the RTSS overlay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RtssItem {
+    /// Unified sensor reading; `label` replaces the sensor name
+    Sensor { id: String, label: Option<String> },
+    /// Active thermal profile
+    ThermalProfile,
+}
+
+/// Always-on-top, click-through sensor overlay window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayConfig {
+    /// Shown at startup; follows the last toggle
+    pub visible: bool,
+    /// Unified sensor IDs shown, in order (empty = all temperatures and
+    /// fans)
+    pub sensors: Vec<String>,
+    /// Global shortcut toggling the overlay, e.g. `Ctrl+Shift+O`
+    /// (`None` = no shortcut)
+    pub hotkey: Option<String>,
+    /// Top-left corner in logical screen pixels; the window ignores the
+    /// mouse, so it cannot be dragged
+    pub x: i32,
+    pub y: i32,
+    /// Smallest change of a reading that is sent to the overlay; smaller
+    /// changes are held back until they add up (0 = send every change)
+    pub update_epsilon: f32,
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            sensors: Vec::new(),
+            hotkey: Some("Ctrl+Shift+O".into()),
+            x: 16,
+            y: 16,
+            update_epsilon: 0.5,
+        }
+    }
+}
+
+/// Prometheus metrics endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    /// Listen address; use `0.0.0.0:<port>` to allow scraping from other
+    /// machines
+    pub bind: String,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1:9184".into(),
+        }
+    }
+}
+
+/// MQTT broker connection and Home Assistant discovery settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    /// Stored in plain text like the rest of the config
+    pub password: Option<String>,
+    /// Topics are `<base_topic>/<computer name>/…`
+    pub base_topic: String,
+    /// Home Assistant discovery prefix
+    pub discovery_prefix: String,
+    /// Sensor publish interval in seconds
+    pub interval_secs: u64,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".into(),
+            port: 1883,
+            username: None,
+            password: None,
+            base_topic: "nocrate".into(),
+            discovery_prefix: "homeassistant".into(),
+            interval_secs: 10,
+        }
+    }
+}
+
+/// Local control API for scripts and tools such as Stream Deck plugins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub enabled: bool,
+    /// Port on `127.0.0.1`; the API is never exposed to the network
+    pub port: u16,
+    /// Access token required on every request; generated when the API
+    /// is first enabled
+    pub token: String,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8742,
+            token: String::new(),
+        }
+    }
+}
+
+/// System-wide shortcuts handled by the backend, so they also work while
+/// a full-screen game has focus. `None` or an empty string disables one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeyConfig {
+    /// Switch to the next thermal profile (Fn+F5 order)
+    pub cycle_profile: Option<String>,
+    /// Turn all AURA lighting off, or back on
+    pub toggle_aura: Option<String>,
+    /// Run every fan at 100 %, or restore the previous fan settings
+    pub fan_panic: Option<String>,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            cycle_profile: Some("Ctrl+Alt+F5".into()),
+            toggle_aura: Some("Ctrl+Alt+F6".into()),
+            fan_panic: Some("Ctrl+Alt+F12".into()),
+        }
+    }
+}
+
+/// Update check against the GitHub releases of the project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateConfig {
+    /// Check once in the background after startup
+    pub check_on_startup: bool,
+    /// Offer pre-releases (betas, release candidates) as updates
+    pub include_prereleases: bool,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            check_on_startup: true,
+            include_prereleases: false,
+        }
+    }
+}
+
+/// `WinRing0` kernel driver management.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DriverConfig {
+    /// The user installed the driver from the settings; until then it is
+    /// not loaded on launch
+    pub enabled: bool,
+    /// Leave the driver service registered (demand-start) on exit and only
+    /// close the device, instead of removing it every time
+    pub keep_installed: bool,
+}
+
+/// Daily window in which AURA lighting is dimmed.
+///
+/// Times are local `HH:MM`; a window whose end is before its start spans
+/// midnight (e.g. `22:00`–`07:00`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LightingSchedule {
+    pub enabled: bool,
+    pub start: String,
+    pub end: String,
+    /// Brightness inside the window in percent (0 = off)
+    pub brightness: u8,
+}
+
+impl Default for LightingSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "23:00".into(),
+            end: "07:00".into(),
+            brightness: 0,
+        }
+    }
+}
+
+impl LightingSchedule {
+    /// Whether `minute` (minutes since local midnight) falls inside the
+    /// window. Always `false` when disabled or the times are invalid.
+    #[must_use]
+    pub fn contains(&self, minute: u16) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (Some(start), Some(end)) = (parse_hhmm(&self.start), parse_hhmm(&self.end)) else {
+            return false;
+        };
+        if start <= end {
+            (start..end).contains(&minute)
+        } else {
+            minute >= start || minute < end
+        }
+    }
+}
+
+/// Parse `HH:MM` into minutes since midnight.
+fn parse_hhmm(s: &str) -> Option<u16> {
+    let (h, m) = s.trim().split_once(':')?;
+    let (h, m) = (h.parse::<u16>().ok()?, m.parse::<u16>().ok()?);
+    (h < 24 && m < 60).then_some(h * 60 + m)
 }
 
 impl Default for AppConfig {
@@ -50,7 +583,9 @@ impl Default for AppConfig {
         Self {
             theme: "system".into(),
             close_to_tray: false,
+            start_minimized: false,
             auto_start: false,
+            restore_on_startup: true,
             fan_poll_interval_ms: 2000,
             last_thermal_profile: 0,
             last_aura_effect: "static".into(),
@@ -58,6 +593,84 @@ impl Default for AppConfig {
             last_aura_speed: "medium".into(),
             temp_alert_enabled: true,
             temp_alert_threshold: 90,
+            fan_stall: FanStallConfig::default(),
+            thermal_failsafe: ThermalFailsafeConfig::default(),
+            sio_channels: SioChannelConfig::default(),
+            sensor_calibration: HashMap::new(),
+            sensor_smoothing: HashMap::new(),
+            sensor_polling: PollingTiers::default(),
+            units: UnitsConfig::default(),
+            locale: Locale::default(),
+            eco: EcoConfig::default(),
+            ambilight_zones: Vec::new(),
+            openrgb: OpenRgbConfig::default(),
+            lighting_schedule: LightingSchedule::default(),
+            aura_calibration: HashMap::new(),
+            battery_charge_limit: None,
+            power_limits: HashMap::new(),
+            laptop_fan_curves: HashMap::new(),
+            power_source: PowerSourceConfig::default(),
+            fan_calibration: HashMap::new(),
+            desktop_fan_policies: HashMap::new(),
+            fan_groups: Vec::new(),
+            lhm: LhmConfig::default(),
+            hwinfo_enabled: false,
+            rtss: RtssConfig::default(),
+            overlay: OverlayConfig::default(),
+            metrics: MetricsConfig::default(),
+            mqtt: MqttConfig::default(),
+            api: ApiConfig::default(),
+            hotkeys: HotkeyConfig::default(),
+            updates: UpdateConfig::default(),
+            driver: DriverConfig::default(),
+            paused_services: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Power limits in effect on `source`.
+    #[must_use]
+    pub fn power_limits_for(&self, source: PowerSource) -> HashMap<ThermalProfile, PowerLimits> {
+        let mut limits = self.power_limits.clone();
+        if source == PowerSource::Battery {
+            limits.extend(&self.power_source.battery_power_limits);
+        }
+        limits
+    }
+
+    /// Laptop fan curves in effect on `source`.
+    #[must_use]
+    pub fn laptop_fan_curves_for(
+        &self,
+        source: PowerSource,
+    ) -> HashMap<ThermalProfile, Vec<FanCurve>> {
+        let mut curves = self.laptop_fan_curves.clone();
+        if source == PowerSource::Battery {
+            curves.extend(self.power_source.battery_fan_curves.clone());
+        }
+        curves
+    }
+
+    /// Saved power limits of `source` only, without the AC fallback.
+    pub const fn power_limits_mut(
+        &mut self,
+        source: PowerSource,
+    ) -> &mut HashMap<ThermalProfile, PowerLimits> {
+        match source {
+            PowerSource::Ac => &mut self.power_limits,
+            PowerSource::Battery => &mut self.power_source.battery_power_limits,
+        }
+    }
+
+    /// Saved fan curves of `source` only, without the AC fallback.
+    pub const fn laptop_fan_curves_mut(
+        &mut self,
+        source: PowerSource,
+    ) -> &mut HashMap<ThermalProfile, Vec<FanCurve>> {
+        match source {
+            PowerSource::Ac => &mut self.laptop_fan_curves,
+            PowerSource::Battery => &mut self.power_source.battery_fan_curves,
         }
     }
 }
@@ -75,20 +688,23 @@ impl ConfigStore {
         // Ensure the directory exists
         if let Some(parent) = config_file.parent() {
             fs::create_dir_all(parent).map_err(|e| {
-                NoCrateError::Config(format!("Failed to create config directory: {e}"))
+                NoCrateError::Config(i18n::tf(Msg::ConfigDirCreate, &[("error", &e)]))
             })?;
         }
 
         CONFIG_PATH
             .set(config_file.clone())
-            .map_err(|_| NoCrateError::Config("Config path already initialized".into()))?;
+            .map_err(|_| NoCrateError::Config(i18n::t(Msg::ConfigAlreadyLoaded).into()))?;
 
         let config = if config_file.exists() {
             let data = fs::read_to_string(&config_file)
-                .map_err(|e| NoCrateError::Config(format!("Failed to read config file: {e}")))?;
+                .map_err(|e| NoCrateError::Config(i18n::tf(Msg::ConfigRead, &[("error", &e)])))?;
             serde_json::from_str(&data).unwrap_or_else(|e| {
-                eprintln!("Warning: config parse error ({e}), using defaults");
-                AppConfig::default()
+                eprintln!("Warning: config parse error ({e}), trying backups");
+                Self::load_newest_backup(&config_file).unwrap_or_else(|| {
+                    eprintln!("Warning: no usable config backup, using defaults");
+                    AppConfig::default()
+                })
             })
         } else {
             let default = AppConfig::default();
@@ -96,6 +712,7 @@ impl ConfigStore {
             let _ = Self::write_to_disk(&default);
             default
         };
+        i18n::set_locale(config.locale);
 
         Ok(Self {
             inner: RwLock::new(config),
@@ -118,22 +735,183 @@ impl ConfigStore {
     {
         let mut guard = self.inner.write();
         f(&mut guard);
+        i18n::set_locale(guard.locale);
+        Self::write_to_disk(&guard)?;
+        Ok(guard.clone())
+    }
+
+    /// Reload the config from disk if the file no longer matches the
+    /// in-memory config, i.e. it was edited externally.
+    ///
+    /// Returns the previous config when a change was loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not parse; the
+    /// in-memory config is kept in that case.
+    pub fn reload(&self) -> Result<Option<AppConfig>> {
+        let Some(path) = CONFIG_PATH.get() else {
+            return Err(NoCrateError::Config(i18n::t(Msg::ConfigNotLoaded).into()));
+        };
+        // Hold the lock so a concurrent update cannot be mistaken for an
+        // external edit
+        let mut guard = self.inner.write();
+        let data = fs::read_to_string(path)
+            .map_err(|e| NoCrateError::Config(i18n::tf(Msg::ConfigRead, &[("error", &e)])))?;
+        let value: serde_json::Value = serde_json::from_str(&data)
+            .map_err(|e| NoCrateError::Config(i18n::tf(Msg::ConfigParse, &[("error", &e)])))?;
+        let loaded: AppConfig = serde_json::from_value(value.clone())
+            .map_err(|e| NoCrateError::Config(i18n::tf(Msg::ConfigParse, &[("error", &e)])))?;
+
+        let current = serde_json::to_value(&*guard)
+            .map_err(|e| NoCrateError::Config(i18n::tf(Msg::ConfigSerialize, &[("error", &e)])))?;
+        if value == current {
+            return Ok(None);
+        }
+        i18n::set_locale(loaded.locale);
+        Ok(Some(std::mem::replace(&mut *guard, loaded)))
+    }
+
+    /// Modification time of the config file, if it exists.
+    pub fn modified() -> Option<SystemTime> {
+        fs::metadata(CONFIG_PATH.get()?)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    /// List the available backups, newest first.
+    pub fn backups() -> Vec<ConfigBackup> {
+        let Some(path) = CONFIG_PATH.get() else {
+            return Vec::new();
+        };
+        (1..=BACKUP_COUNT)
+            .filter_map(|index| {
+                let modified = fs::metadata(backup_path(path, index))
+                    .and_then(|m| m.modified())
+                    .ok()?;
+                Some(ConfigBackup {
+                    index,
+                    modified: modified
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |d| d.as_secs()),
+                })
+            })
+            .collect()
+    }
+
+    /// Replace the current config with backup `index` and persist it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backup is missing, does not parse, or the
+    /// restored config cannot be written.
+    pub fn restore_backup(&self, index: usize) -> Result<AppConfig> {
+        let Some(path) = CONFIG_PATH.get() else {
+            return Err(NoCrateError::Config(i18n::t(Msg::ConfigNotLoaded).into()));
+        };
+        if !(1..=BACKUP_COUNT).contains(&index) {
+            return Err(NoCrateError::Config(i18n::tf(
+                Msg::ConfigBackupIndex,
+                &[("index", &index), ("count", &BACKUP_COUNT)],
+            )));
+        }
+        let data = fs::read_to_string(backup_path(path, index)).map_err(|e| {
+            NoCrateError::Config(i18n::tf(
+                Msg::ConfigBackupRead,
+                &[("index", &index), ("error", &e)],
+            ))
+        })?;
+        let restored: AppConfig = serde_json::from_str(&data).map_err(|e| {
+            NoCrateError::Config(i18n::tf(
+                Msg::ConfigBackupInvalid,
+                &[("index", &index), ("error", &e)],
+            ))
+        })?;
+
+        let mut guard = self.inner.write();
+        *guard = restored;
+        i18n::set_locale(guard.locale);
         Self::write_to_disk(&guard)?;
         Ok(guard.clone())
     }
 
+    /// First backup that parses, newest first.
+    fn load_newest_backup(path: &Path) -> Option<AppConfig> {
+        (1..=BACKUP_COUNT).find_map(|index| {
+            let data = fs::read_to_string(backup_path(path, index)).ok()?;
+            let config = serde_json::from_str(&data).ok()?;
+            eprintln!("Recovered config from backup {index}");
+            Some(config)
+        })
+    }
+
     /// Write config to disk.
+    ///
+    /// The file is written to a temporary sibling and renamed over the
+    /// original, so a crash mid-write leaves the previous config intact.
     fn write_to_disk(config: &AppConfig) -> Result<()> {
         let Some(path) = CONFIG_PATH.get() else {
-            return Err(NoCrateError::Config("Config path not initialized".into()));
+            return Err(NoCrateError::Config(i18n::t(Msg::ConfigNotLoaded).into()));
         };
 
         let json = serde_json::to_string_pretty(config)
-            .map_err(|e| NoCrateError::Config(format!("Failed to serialize config: {e}")))?;
+            .map_err(|e| NoCrateError::Config(i18n::tf(Msg::ConfigSerialize, &[("error", &e)])))?;
 
-        fs::write(path, json)
-            .map_err(|e| NoCrateError::Config(format!("Failed to write config file: {e}")))?;
+        // Backups are best-effort; a failure must not block saving
+        if let Err(e) = rotate_backups(path) {
+            eprintln!("Warning: config backup failed: {e}");
+        }
+
+        let tmp = path.with_extension("json.tmp");
+        let write = || -> std::io::Result<()> {
+            let mut file = fs::File::create(&tmp)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()?;
+            drop(file);
+            fs::rename(&tmp, path)
+        };
+        write().map_err(|e| {
+            let _ = fs::remove_file(&tmp);
+            NoCrateError::Config(i18n::tf(Msg::ConfigWrite, &[("error", &e)]))
+        })
+    }
+}
+
+/// A rotating config backup.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigBackup {
+    /// 1 = newest
+    pub index: usize,
+    /// Last modification time (Unix seconds)
+    pub modified: u64,
+}
+
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".bak.{index}"));
+    PathBuf::from(name)
+}
 
-        Ok(())
+/// Shift `config.json.bak.N` up by one and copy the current file to
+/// `.bak.1`, unless the newest backup is younger than [`BACKUP_INTERVAL`].
+fn rotate_backups(path: &Path) -> std::io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let newest_age = fs::metadata(backup_path(path, 1))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok());
+    if newest_age.is_some_and(|age| age < BACKUP_INTERVAL) {
+        return Ok(());
+    }
+
+    for index in (1..BACKUP_COUNT).rev() {
+        let from = backup_path(path, index);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, index + 1))?;
+        }
     }
+    let _ = fs::copy(path, backup_path(path, 1))?;
+    Ok(())
 }
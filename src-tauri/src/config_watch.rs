@@ -0,0 +1,60 @@
+/// Config file hot-reload.
+///
+/// Power users may edit `config.json` by hand while the app is running. A
+/// background thread polls the file's modification time and, when the
+/// content no longer matches the in-memory config, reloads it, applies
+/// the runtime side effects and notifies the frontend. The app's own
+/// writes match the in-memory config and are ignored.
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::config::apply_config;
+use crate::config::ConfigStore;
+use crate::state::AppState;
+
+/// Interval between modification time checks.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Event emitted to the frontend with the reloaded config.
+pub const CONFIG_CHANGED_EVENT: &str = "config-changed";
+
+/// Spawn the config watcher thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-config-watch".into())
+        .spawn(move || watch_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Config: failed to spawn file watcher: {e}");
+    }
+}
+
+fn watch_loop(app: &AppHandle) {
+    let mut last_seen: Option<SystemTime> = ConfigStore::modified();
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let modified = ConfigStore::modified();
+        if modified == last_seen {
+            continue;
+        }
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        // Record the time even on failure so a half-saved or invalid file
+        // is reported once, not every poll
+        last_seen = modified;
+
+        match state.config.reload() {
+            Ok(Some(previous)) => {
+                eprintln!("Config: reloaded after external change");
+                let updated = state.config.get();
+                apply_config(&state, &previous, &updated);
+                let _ = app.emit(CONFIG_CHANGED_EVENT, updated);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Config: ignoring external change: {e}"),
+        }
+    }
+}
@@ -0,0 +1,348 @@
+/// Detection of other software driving the same hardware.
+///
+/// Armoury Crate, AI Suite and similar tools write fan policies through
+/// ASUS WMI or program the Super I/O through their own `WinRing0` copy, and
+/// periodically re-apply their own settings. When one of them runs next to
+/// the app, fan curves and lighting seem to revert on their own. The
+/// running services and processes are checked once after startup, logged
+/// and sent to the frontend; `get_conflicts` repeats the check on demand.
+///
+/// With the user's consent the Armoury Crate services can be stopped and
+/// disabled while the app manages the fans, and later put back with the
+/// start type they had.
+use std::ffi::OsString;
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_SERVICE_ALREADY_RUNNING, ERROR_SERVICE_DOES_NOT_EXIST,
+};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+};
+use windows::Win32::System::Services::{
+    ChangeServiceConfigW, CloseServiceHandle, ControlService, OpenSCManagerW, OpenServiceW,
+    QueryServiceConfigW, QueryServiceStatus, StartServiceW, ENUM_SERVICE_TYPE,
+    QUERY_SERVICE_CONFIGW, SC_HANDLE, SC_MANAGER_CONNECT, SERVICE_CHANGE_CONFIG,
+    SERVICE_CONTROL_STOP, SERVICE_DISABLED, SERVICE_ERROR, SERVICE_NO_CHANGE, SERVICE_QUERY_CONFIG,
+    SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START, SERVICE_START_TYPE, SERVICE_STATUS,
+    SERVICE_STOP,
+};
+
+use crate::config::PausedService;
+use crate::error::{NoCrateError, Result};
+
+/// Event emitted after startup with the detected `Vec<Conflict>`, only if
+/// any software was found.
+pub const CONFLICTS_EVENT: &str = "conflicts-detected";
+
+/// What a conflicting program interferes with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictArea {
+    /// Fan policies and curves (ASUS WMI or Super I/O).
+    Fans,
+    /// AURA lighting controllers.
+    Aura,
+    /// Super I/O and `SMBus` sensor reads through `WinRing0`.
+    Sensors,
+}
+
+/// A program known to fight over the same hardware.
+struct KnownSoftware {
+    id: &'static str,
+    name: &'static str,
+    services: &'static [&'static str],
+    processes: &'static [&'static str],
+    affects: &'static [ConflictArea],
+    hint: &'static str,
+}
+
+const KNOWN_SOFTWARE: [KnownSoftware; 5] = [
+    KnownSoftware {
+        id: "armoury_crate",
+        name: "Armoury Crate",
+        services: &[
+            "ArmouryCrateService",
+            "ArmouryCrateControlInterface",
+            "LightingService",
+            "asusm",
+        ],
+        processes: &[
+            "ArmouryCrate.exe",
+            "ArmouryCrate.Service.exe",
+            "ArmourySocketServer.exe",
+        ],
+        affects: &[ConflictArea::Fans, ConflictArea::Aura],
+        hint: "Armoury Crate 会周期性写回自己的风扇与灯效设置，请停止其服务或卸载",
+    },
+    KnownSoftware {
+        id: "ai_suite",
+        name: "AI Suite 3",
+        services: &["AsusFanControlService", "AsSysCtrlService"],
+        processes: &["AISuite3.exe", "FanXpert4.exe"],
+        affects: &[ConflictArea::Fans, ConflictArea::Sensors],
+        hint: "AI Suite 的 Fan Xpert 会覆盖风扇设置，请停止 AsusFanControlService 或卸载 AI Suite",
+    },
+    KnownSoftware {
+        id: "fan_control",
+        name: "FanControl",
+        services: &[],
+        processes: &["FanControl.exe"],
+        affects: &[ConflictArea::Fans, ConflictArea::Sensors],
+        hint: "FanControl 会按自己的曲线控制同一批风扇头，两者只应保留一个",
+    },
+    KnownSoftware {
+        id: "openrgb",
+        name: "OpenRGB",
+        services: &[],
+        processes: &["OpenRGB.exe"],
+        affects: &[ConflictArea::Aura, ConflictArea::Sensors],
+        hint: "OpenRGB 会同时访问 AURA 控制器与 SMBus，请退出 OpenRGB 或在其中禁用 ASUS 设备",
+    },
+    KnownSoftware {
+        id: "signalrgb",
+        name: "SignalRGB",
+        services: &[],
+        processes: &["SignalRgb.exe", "SignalRgbLauncher.exe"],
+        affects: &[ConflictArea::Aura],
+        hint: "SignalRGB 会持续写入 AURA 灯效，请退出 SignalRGB 或在其中禁用 ASUS 设备",
+    },
+];
+
+/// Conflicting software found running.
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    /// Stable identifier, e.g. `armoury_crate`.
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Service names of this software that are running.
+    pub services: Vec<&'static str>,
+    /// Process names of this software that are running.
+    pub processes: Vec<&'static str>,
+    pub affects: &'static [ConflictArea],
+    /// What the user can do about it.
+    pub hint: &'static str,
+}
+
+/// Check every known program against the running services and processes.
+pub fn detect() -> Vec<Conflict> {
+    let processes = running_processes();
+    KNOWN_SOFTWARE
+        .iter()
+        .filter_map(|sw| {
+            let services: Vec<_> = sw
+                .services
+                .iter()
+                .copied()
+                .filter(|s| service_running(s))
+                .collect();
+            let procs: Vec<_> = sw
+                .processes
+                .iter()
+                .copied()
+                .filter(|p| processes.iter().any(|r| r.eq_ignore_ascii_case(p)))
+                .collect();
+            (!services.is_empty() || !procs.is_empty()).then_some(Conflict {
+                id: sw.id,
+                name: sw.name,
+                services,
+                processes: procs,
+                affects: sw.affects,
+                hint: sw.hint,
+            })
+        })
+        .collect()
+}
+
+/// Detect conflicts once, log them and tell the frontend.
+pub fn report(app: &AppHandle) {
+    let conflicts = detect();
+    if conflicts.is_empty() {
+        return;
+    }
+    for c in &conflicts {
+        eprintln!(
+            "[Conflicts] {} is running (services: {:?}, processes: {:?})",
+            c.name, c.services, c.processes
+        );
+    }
+    let _ = app.emit(CONFLICTS_EVENT, &conflicts);
+}
+
+/// Executable names of all running processes.
+#[allow(unsafe_code)]
+fn running_processes() -> Vec<String> {
+    let mut names = Vec::new();
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return names;
+        };
+        let mut entry = PROCESSENTRY32W {
+            dwSize: u32::try_from(std::mem::size_of::<PROCESSENTRY32W>()).unwrap_or(0),
+            ..Default::default()
+        };
+        let mut next = Process32FirstW(snapshot, &raw mut entry);
+        while next.is_ok() {
+            let len = entry
+                .szExeFile
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(entry.szExeFile.len());
+            names.push(
+                OsString::from_wide(&entry.szExeFile[..len])
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            next = Process32NextW(snapshot, &raw mut entry);
+        }
+        let _ = CloseHandle(snapshot);
+    }
+    names
+}
+
+/// The services [`pause_service`] is meant for: those of Armoury Crate.
+pub fn pausable_services() -> &'static [&'static str] {
+    KNOWN_SOFTWARE
+        .iter()
+        .find(|sw| sw.id == "armoury_crate")
+        .map_or(&[], |sw| sw.services)
+}
+
+/// Disable the service `name` and stop it if it runs. Returns `None` if
+/// it is not installed.
+///
+/// # Errors
+///
+/// Returns `AccessDenied` without admin rights, or the error of the
+/// failed change; the start type is put back if stopping fails.
+#[allow(unsafe_code)]
+pub fn pause_service(name: &str) -> Result<Option<PausedService>> {
+    let access = SERVICE_QUERY_CONFIG | SERVICE_CHANGE_CONFIG | SERVICE_QUERY_STATUS | SERVICE_STOP;
+    with_service(name, access, |svc| unsafe {
+        let start_type = query_start_type(svc).map_err(|e| service_error(&e, name))?;
+        let was_running = query_running(svc);
+        set_start_type(svc, SERVICE_DISABLED).map_err(|e| service_error(&e, name))?;
+        if was_running {
+            let mut status = SERVICE_STATUS::default();
+            if let Err(e) = ControlService(svc, SERVICE_CONTROL_STOP, &raw mut status) {
+                let _ = set_start_type(svc, SERVICE_START_TYPE(start_type));
+                return Err(service_error(&e, name));
+            }
+        }
+        Ok(PausedService {
+            name: name.to_string(),
+            start_type,
+            was_running,
+        })
+    })
+}
+
+/// Put back the start type of a service paused by [`pause_service`] and
+/// start it again if it was running. A service uninstalled in the
+/// meantime counts as restored.
+///
+/// # Errors
+///
+/// Returns `AccessDenied` without admin rights, or the error of the
+/// failed change.
+#[allow(unsafe_code)]
+pub fn restore_service(service: &PausedService) -> Result<()> {
+    let name = service.name.as_str();
+    let _ = with_service(name, SERVICE_CHANGE_CONFIG | SERVICE_START, |svc| unsafe {
+        set_start_type(svc, SERVICE_START_TYPE(service.start_type))
+            .map_err(|e| service_error(&e, name))?;
+        if service.was_running {
+            match StartServiceW(svc, None) {
+                Err(e) if e.code() != ERROR_SERVICE_ALREADY_RUNNING.to_hresult() => {
+                    return Err(service_error(&e, name));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// Open the service `name` with `access` and run `f` on it. Returns
+/// `None` if the service is not installed.
+#[allow(unsafe_code)]
+fn with_service<T>(
+    name: &str,
+    access: u32,
+    f: impl FnOnce(SC_HANDLE) -> Result<T>,
+) -> Result<Option<T>> {
+    let wide: Vec<u16> = OsString::from(name)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        let scm = OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), SC_MANAGER_CONNECT)
+            .map_err(|e| service_error(&e, "Service Control Manager"))?;
+        let result = match OpenServiceW(scm, PCWSTR(wide.as_ptr()), access) {
+            Ok(svc) => {
+                let result = f(svc);
+                let _ = CloseServiceHandle(svc);
+                result.map(Some)
+            }
+            Err(e) if e.code() == ERROR_SERVICE_DOES_NOT_EXIST.to_hresult() => Ok(None),
+            Err(e) => Err(service_error(&e, name)),
+        };
+        let _ = CloseServiceHandle(scm);
+        result
+    }
+}
+
+fn service_error(err: &windows::core::Error, name: &str) -> NoCrateError {
+    NoCrateError::classify(err, format!("{name}: {err}"), NoCrateError::Unknown)
+}
+
+/// Whether the service `name` exists and is running.
+fn service_running(name: &str) -> bool {
+    with_service(name, SERVICE_QUERY_STATUS, |svc| Ok(query_running(svc)))
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+#[allow(unsafe_code)]
+fn query_running(svc: SC_HANDLE) -> bool {
+    let mut status = SERVICE_STATUS::default();
+    unsafe {
+        QueryServiceStatus(svc, &raw mut status).is_ok() && status.dwCurrentState == SERVICE_RUNNING
+    }
+}
+
+#[allow(unsafe_code)]
+unsafe fn query_start_type(svc: SC_HANDLE) -> windows::core::Result<u32> {
+    let mut needed = 0;
+    // The first call only reports the size of the variable-length config
+    let _ = QueryServiceConfigW(svc, None, 0, &raw mut needed);
+    let mut buf = vec![0u64; (needed as usize).div_ceil(8)];
+    let config = buf.as_mut_ptr().cast::<QUERY_SERVICE_CONFIGW>();
+    QueryServiceConfigW(svc, Some(config), needed, &raw mut needed)?;
+    Ok((*config).dwStartType.0)
+}
+
+#[allow(unsafe_code)]
+unsafe fn set_start_type(
+    svc: SC_HANDLE,
+    start_type: SERVICE_START_TYPE,
+) -> windows::core::Result<()> {
+    ChangeServiceConfigW(
+        svc,
+        ENUM_SERVICE_TYPE(SERVICE_NO_CHANGE),
+        start_type,
+        SERVICE_ERROR(SERVICE_NO_CHANGE),
+        PCWSTR::null(),
+        PCWSTR::null(),
+        None,
+        PCWSTR::null(),
+        PCWSTR::null(),
+        PCWSTR::null(),
+        PCWSTR::null(),
+    )
+}
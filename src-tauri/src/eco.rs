@@ -0,0 +1,82 @@
+/// Eco polling while the main window is hidden.
+///
+/// With the window in the tray nothing shows live readings, yet the
+/// background features would keep every sensor source busy once a second.
+/// A thread watches the window and, while it is hidden or minimized and
+/// no engine needs live readings (thermal failsafe, fan stall detection,
+/// the overlay, RTSS or a temperature-driven AURA effect), switches the
+/// aggregation to reading every source only once per eco interval. The
+/// history sampling, which only feeds the UI charts, slows down as well
+/// or pauses.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::aura::effects::SoftwareEffect;
+use crate::config::AppConfig;
+use crate::state::AppState;
+
+/// Interval between window checks.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Whether sensor polling is currently reduced.
+#[derive(Default)]
+pub struct EcoState {
+    active: AtomicBool,
+}
+
+impl EcoState {
+    /// Sensor sources are read only once per eco interval.
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+}
+
+/// Spawn the thread switching eco polling on and off.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-eco".into())
+        .spawn(move || watch_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Eco: failed to spawn watch thread: {e}");
+    }
+}
+
+/// The main window is hidden to the tray or minimized.
+pub fn window_hidden(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .is_none_or(|win| !win.is_visible().unwrap_or(true) || win.is_minimized().unwrap_or(false))
+}
+
+fn watch_loop(app: &AppHandle) {
+    loop {
+        thread::sleep(CHECK_INTERVAL);
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        let config = state.config.get();
+        let active =
+            config.eco.enabled && window_hidden(app) && !needs_live_sensors(&state, &config);
+        if state.eco.active.swap(active, Ordering::Relaxed) != active {
+            eprintln!(
+                "Eco: sensor polling {}",
+                if active { "reduced" } else { "restored" }
+            );
+        }
+    }
+}
+
+/// A background feature reacts to sensor readings and would lag behind
+/// with eco polling.
+fn needs_live_sensors(state: &AppState, config: &AppConfig) -> bool {
+    config.thermal_failsafe.enabled
+        || (config.fan_stall.enabled && config.fan_stall.fans.values().any(|w| w.enabled))
+        || config.overlay.visible
+        || config.rtss.enabled
+        || state
+            .aura_effects
+            .running()
+            .is_some_and(|r| matches!(r.effect, SoftwareEffect::Temperature { .. }))
+}
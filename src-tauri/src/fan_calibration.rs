@@ -0,0 +1,86 @@
+/// Q-Fan calibration of desktop fan headers.
+///
+/// The firmware runs the calibration by itself once started, sweeping
+/// the header's duty range for a minute or two. A background thread per
+/// run polls its progress, forwards it to the frontend and stores the
+/// measured result in the config, where low limit validation and curve
+/// previews pick it up.
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config_watch::CONFIG_CHANGED_EVENT;
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, FanCalibrationState};
+
+/// Interval between progress polls.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Give up on a run that has not finished after this long.
+const RUN_TIMEOUT: Duration = Duration::from_mins(5);
+
+/// Event emitted to the frontend with a `FanCalibrationStatus` after
+/// every poll.
+pub const FAN_CALIBRATION_EVENT: &str = "fan-calibration";
+
+/// Spawn the thread following a run that was just started on `fan_type`.
+pub fn spawn(app: AppHandle, fan_type: u8) {
+    let spawned = thread::Builder::new()
+        .name(format!("nocrate-fan-calibration-{fan_type}"))
+        .spawn(move || poll_loop(&app, fan_type));
+    if let Err(e) = spawned {
+        eprintln!("Fan calibration: failed to spawn poll thread: {e}");
+    }
+}
+
+fn poll_loop(app: &AppHandle, fan_type: u8) {
+    let started = Instant::now();
+
+    while started.elapsed() < RUN_TIMEOUT {
+        thread::sleep(POLL_INTERVAL);
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        let Some(wmi) = state.wmi.get() else {
+            return;
+        };
+
+        let status =
+            match wmi.execute(move |conn| asus_mgmt::get_fan_calibration_status(conn, fan_type)) {
+                Ok(status) => status,
+                Err(e) => {
+                    eprintln!("Fan calibration: header {fan_type}: {e}");
+                    return;
+                }
+            };
+        let _ = app.emit(FAN_CALIBRATION_EVENT, &status);
+
+        match status.state {
+            FanCalibrationState::Running => {}
+            FanCalibrationState::Done => {
+                if let Some(result) = status.result {
+                    eprintln!(
+                        "Fan calibration: header {fan_type} spins from {}% ({} RPM) to {} RPM",
+                        result.min_duty_pct, result.min_rpm, result.max_rpm
+                    );
+                    let saved = state.config.update(|cfg| {
+                        let _ = cfg.fan_calibration.insert(fan_type, result);
+                    });
+                    match saved {
+                        Ok(updated) => {
+                            let _ = app.emit(CONFIG_CHANGED_EVENT, updated);
+                        }
+                        Err(e) => eprintln!("Fan calibration: failed to save result: {e}"),
+                    }
+                }
+                return;
+            }
+            FanCalibrationState::Failed | FanCalibrationState::Idle => {
+                eprintln!("Fan calibration: header {fan_type} ended without a result");
+                return;
+            }
+        }
+    }
+    eprintln!("Fan calibration: header {fan_type} timed out");
+}
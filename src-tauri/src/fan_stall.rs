@@ -0,0 +1,121 @@
+/// Fan stall detection.
+///
+/// A background thread reads the unified sensors at the fan poll interval
+/// and counts consecutive 0 RPM readings of every watched fan. A fan
+/// whose duty is above the configured threshold, or that has no known
+/// duty, and reads 0 RPM for the configured number of polls is reported
+/// as stalled once; it is reported again as recovered when it spins or
+/// its duty drops. Alerts are emitted to the frontend, which shows them
+/// as notifications.
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::FanStallConfig;
+use crate::sensors::{self, UnifiedSensor};
+use crate::state::AppState;
+
+/// Shortest interval between checks, whatever the fan poll interval.
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Event emitted to the frontend with a [`FanStallAlert`] when a fan
+/// stalls or recovers.
+pub const FAN_STALL_EVENT: &str = "fan-stall";
+
+/// A watched fan changed between stalled and spinning.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanStallAlert {
+    /// Unified fan sensor ID.
+    pub sensor_id: String,
+    /// Display name of the fan sensor.
+    pub name: String,
+    pub stalled: bool,
+}
+
+/// Spawn the stall detection thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-fan-stall".into())
+        .spawn(move || watch_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Fan stall: failed to spawn watch thread: {e}");
+    }
+}
+
+fn watch_loop(app: &AppHandle) {
+    let mut detector = Detector::default();
+
+    loop {
+        let mut interval = MIN_POLL_INTERVAL;
+        if let Some(state) = app.try_state::<AppState>() {
+            let config = state.config.get();
+            interval = interval.max(Duration::from_millis(config.fan_poll_interval_ms));
+            let cfg = config.fan_stall;
+            if cfg.enabled && cfg.fans.values().any(|w| w.enabled) {
+                // Unsmoothed: a smoothing window would delay the 0 RPM readings
+                let readings = sensors::collect_with(&state, true).sensors;
+                for alert in detector.check(&cfg, &readings) {
+                    if alert.stalled {
+                        eprintln!("Fan stall: {} ({}) stopped", alert.name, alert.sensor_id);
+                    } else {
+                        eprintln!("Fan stall: {} ({}) recovered", alert.name, alert.sensor_id);
+                    }
+                    let _ = app.emit(FAN_STALL_EVENT, alert);
+                }
+            } else {
+                detector = Detector::default();
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// Per-fan stall state across polls.
+#[derive(Default)]
+struct Detector {
+    /// Consecutive 0 RPM polls per fan sensor ID
+    zero_polls: HashMap<String, u32>,
+}
+
+impl Detector {
+    /// Count this poll's readings and return the fans that changed state.
+    fn check(&mut self, cfg: &FanStallConfig, readings: &[UnifiedSensor]) -> Vec<FanStallAlert> {
+        let mut alerts = Vec::new();
+        for (id, watch) in &cfg.fans {
+            // Fans not reported this poll keep their count
+            let Some(fan) = readings.iter().find(|s| &s.id == id) else {
+                continue;
+            };
+            let driven = watch.duty_sensor.as_ref().is_none_or(|duty_id| {
+                readings
+                    .iter()
+                    .find(|s| &s.id == duty_id)
+                    .is_none_or(|duty| duty.value > f32::from(cfg.min_duty_pct))
+            });
+
+            if watch.enabled && driven && fan.value <= 0.0 {
+                let count = self.zero_polls.entry(id.clone()).or_default();
+                *count += 1;
+                if *count == cfg.polls.max(1) {
+                    alerts.push(FanStallAlert {
+                        sensor_id: id.clone(),
+                        name: fan.name.clone(),
+                        stalled: true,
+                    });
+                }
+            } else if let Some(count) = self.zero_polls.remove(id) {
+                if count >= cfg.polls.max(1) {
+                    alerts.push(FanStallAlert {
+                        sensor_id: id.clone(),
+                        name: fan.name.clone(),
+                        stalled: false,
+                    });
+                }
+            }
+        }
+        alerts
+    }
+}
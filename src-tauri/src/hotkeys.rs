@@ -0,0 +1,326 @@
+/// Global hotkeys for thermal profile, lighting and fan actions.
+///
+/// The shortcuts are registered system-wide through the global shortcut
+/// plugin and handled in the backend, so they keep working while a
+/// full-screen game has focus or the window is hidden. Like the overlay
+/// shortcut, the registrations follow `hotkeys` in the config.
+///
+/// Fan panic is a toggle: the first press saves the fan settings and runs
+/// every fan at 100 %, the second restores them. On laptops, switching
+/// the thermal profile in between also ends it, since the firmware loads
+/// the curves of the new profile. The thermal failsafe starts and ends it
+/// the same way.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+use crate::aura::schedule;
+use crate::commands::fan::{apply_thermal_profile, switch_thermal_profile};
+use crate::config::{AppConfig, HotkeyConfig};
+use crate::error::{NoCrateError, Result};
+use crate::i18n::Msg;
+use crate::state::{AppState, WmiThread};
+use crate::wmi::asus_mgmt::{
+    self, DesktopFanCurve, DesktopFanPolicy, DesktopFanProfile, FanCurve, FanCurvePoint, FanTarget,
+    FAN_CURVE_POINTS,
+};
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend};
+
+/// Interval at which the config is checked for changed shortcuts.
+const CONFIG_CHECK: Duration = Duration::from_secs(1);
+
+/// Event emitted to the frontend with `true` when fan panic starts and
+/// `false` when the previous fan settings are restored.
+pub const FAN_PANIC_EVENT: &str = "fan-panic";
+
+/// Whether AURA lighting was turned off by the hotkey.
+static AURA_OFF: AtomicBool = AtomicBool::new(false);
+
+/// Fan settings replaced by fan panic, `None` while it is not active.
+static FAN_BACKUP: Mutex<Option<FanBackup>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy)]
+enum Action {
+    CycleProfile,
+    ToggleAura,
+    FanPanic,
+}
+
+impl Action {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::CycleProfile => "cycle profile",
+            Self::ToggleAura => "toggle AURA",
+            Self::FanPanic => "fan panic",
+        }
+    }
+}
+
+/// Fan settings saved before fan panic.
+#[derive(Debug, Clone)]
+enum FanBackup {
+    /// Curves of the active profile; restored by re-applying the profile.
+    Laptop(Vec<FanCurve>),
+    /// Policy of each header and its curve in the policy's mode.
+    Desktop(Vec<(DesktopFanPolicy, DesktopFanCurve)>),
+}
+
+/// Spawn the hotkey thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-hotkeys".into())
+        .spawn(move || update_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Hotkeys: failed to spawn hotkey thread: {e}");
+    }
+}
+
+/// Whether AURA lighting is currently turned off by the hotkey.
+pub fn aura_off() -> bool {
+    AURA_OFF.load(Ordering::Relaxed)
+}
+
+fn update_loop(app: &AppHandle) {
+    // Shortcuts currently registered
+    let mut registered = HotkeyConfig {
+        cycle_profile: None,
+        toggle_aura: None,
+        fan_panic: None,
+    };
+
+    loop {
+        if let Some(state) = app.try_state::<AppState>() {
+            let cfg = state.config.get().hotkeys;
+            for (action, old, new) in [
+                (
+                    Action::CycleProfile,
+                    &registered.cycle_profile,
+                    &cfg.cycle_profile,
+                ),
+                (
+                    Action::ToggleAura,
+                    &registered.toggle_aura,
+                    &cfg.toggle_aura,
+                ),
+                (Action::FanPanic, &registered.fan_panic, &cfg.fan_panic),
+            ] {
+                if old != new {
+                    register(app, action, old.as_deref(), new.as_deref());
+                }
+            }
+            registered = cfg;
+        }
+        thread::sleep(CONFIG_CHECK);
+    }
+}
+
+/// Replace the shortcut `old` of `action` with `new`.
+fn register(app: &AppHandle, action: Action, old: Option<&str>, new: Option<&str>) {
+    let shortcuts = app.global_shortcut();
+    if let Some(old) = old.filter(|s| !s.is_empty()) {
+        let _ = shortcuts.unregister(old);
+    }
+    let Some(new) = new.filter(|s| !s.is_empty()) else {
+        return;
+    };
+    let registered = shortcuts.on_shortcut(new, move |app, _shortcut, event| {
+        if event.state() == ShortcutState::Pressed {
+            run(app, action);
+        }
+    });
+    match registered {
+        Ok(()) => eprintln!("Hotkeys: {} on {new}", action.name()),
+        Err(e) => eprintln!(
+            "Hotkeys: failed to register {new} for {}: {e}",
+            action.name()
+        ),
+    }
+}
+
+/// Run `action` on its own thread; the shortcut handler runs on the event
+/// loop, which must not wait for WMI.
+fn run(app: &AppHandle, action: Action) {
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("nocrate-hotkey-action".into())
+        .spawn(move || {
+            let Some(state) = app.try_state::<AppState>() else {
+                return;
+            };
+            let result = match action {
+                Action::CycleProfile => cycle_profile(&app, &state),
+                Action::ToggleAura => {
+                    toggle_aura(&state);
+                    Ok(())
+                }
+                Action::FanPanic => toggle_fan_panic(&app, &state),
+            };
+            if let Err(e) = result {
+                eprintln!("Hotkeys: {} failed: {e}", action.name());
+            }
+        });
+    if let Err(e) = spawned {
+        eprintln!("Hotkeys: failed to spawn action thread: {e}");
+    }
+}
+
+fn wmi(state: &AppState) -> Result<WmiThread> {
+    state.wmi.require(Msg::WmiNotInitialized)
+}
+
+/// Switch to the next profile in the Fn+F5 order.
+fn cycle_profile(app: &AppHandle, state: &AppState) -> Result<()> {
+    let next = wmi(state)?.execute(asus_mgmt::get_thermal_profile)?.next();
+    switch_thermal_profile(app, state, next)?;
+    eprintln!("Hotkeys: thermal profile {}", next.as_str());
+    Ok(())
+}
+
+/// Turn all lighting off, or back on at the brightness the lighting
+/// schedule currently requires.
+fn toggle_aura(state: &AppState) {
+    let off = !AURA_OFF.fetch_xor(true, Ordering::Relaxed);
+    let brightness = if off {
+        Some(0)
+    } else {
+        schedule::active_brightness(state)
+    };
+    for ctrl in state.aura.lock().iter() {
+        match brightness {
+            Some(b) => ctrl.dim(b),
+            None => ctrl.undim(),
+        }
+    }
+    state.aura_effects.set_brightness(brightness.unwrap_or(100));
+    eprintln!("Hotkeys: AURA lighting {}", if off { "off" } else { "on" });
+}
+
+fn toggle_fan_panic(app: &AppHandle, state: &AppState) -> Result<()> {
+    set_fan_panic(app, state, None).map(|_| ())
+}
+
+/// Start (`Some(true)`), end (`Some(false)`) or toggle (`None`) fan
+/// panic. Returns whether its state changed.
+pub fn set_fan_panic(app: &AppHandle, state: &AppState, active: Option<bool>) -> Result<bool> {
+    let wmi = wmi(state)?;
+    // Held throughout, so quick repeated presses run one after another
+    let mut backup = FAN_BACKUP.lock();
+    if active == Some(backup.is_some()) {
+        return Ok(false);
+    }
+
+    let active = if let Some(saved) = backup.clone() {
+        let config = state.config.get();
+        wmi.execute(move |conn| restore_fans(conn, &config, &saved))?;
+        *backup = None;
+        false
+    } else {
+        let saved = wmi.execute(save_fans)?;
+        let target = saved.clone();
+        if let Err(e) = wmi.execute(move |conn| max_fans(conn, &target)) {
+            let config = state.config.get();
+            let _ = wmi.execute(move |conn| restore_fans(conn, &config, &saved));
+            return Err(e);
+        }
+        *backup = Some(saved);
+        true
+    };
+    drop(backup);
+
+    let status = if active {
+        "on, all fans at 100%"
+    } else {
+        "off"
+    };
+    eprintln!("Fan panic: {status}");
+    let _ = app.emit(FAN_PANIC_EVENT, active);
+    Ok(true)
+}
+
+/// Read the fan settings that [`max_fans`] replaces.
+fn save_fans(conn: &impl AsusWmi) -> Result<FanBackup> {
+    match conn.backend() {
+        AsusWmiBackend::Laptop { .. } => {
+            let profile = asus_mgmt::get_thermal_profile(conn)?;
+            let mut curves = Vec::new();
+            for target in FanTarget::ALL {
+                curves.extend(asus_mgmt::get_laptop_fan_curve(conn, target, profile)?);
+            }
+            Ok(FanBackup::Laptop(curves))
+        }
+        AsusWmiBackend::Desktop { .. } => {
+            let mut headers = Vec::new();
+            for policy in asus_mgmt::get_all_desktop_fan_policies(conn) {
+                if let Some(curve) =
+                    asus_mgmt::get_desktop_fan_curve_pro(conn, policy.fan_type, policy.mode)?
+                {
+                    headers.push((policy, curve));
+                }
+            }
+            Ok(FanBackup::Desktop(headers))
+        }
+        AsusWmiBackend::AsusHW { .. } => Err(NoCrateError::Wmi(
+            "Fan control is not available on this board".into(),
+        )),
+    }
+}
+
+/// Write a 100 % curve to every fan in `saved`.
+fn max_fans(conn: &impl AsusWmi, saved: &FanBackup) -> Result<()> {
+    match saved {
+        FanBackup::Laptop(curves) => curves.iter().try_for_each(|curve| {
+            asus_mgmt::set_laptop_fan_curve(
+                conn,
+                &FanCurve {
+                    target: curve.target,
+                    points: full_duty(curve.points),
+                },
+            )
+        }),
+        FanBackup::Desktop(headers) => headers.iter().try_for_each(|(policy, curve)| {
+            asus_mgmt::set_desktop_fan_curve_pro(
+                conn,
+                &DesktopFanCurve {
+                    points: full_duty(curve.points),
+                    ..curve.clone()
+                },
+            )?;
+            // The curve is only followed in manual mode
+            if policy.profile == DesktopFanProfile::Manual {
+                return Ok(());
+            }
+            asus_mgmt::set_desktop_fan_policy(
+                conn,
+                &DesktopFanPolicy {
+                    profile: DesktopFanProfile::Manual,
+                    ..policy.clone()
+                },
+            )
+        }),
+    }
+}
+
+fn restore_fans(conn: &impl AsusWmi, config: &AppConfig, saved: &FanBackup) -> Result<()> {
+    match saved {
+        FanBackup::Laptop(_) => {
+            let profile = asus_mgmt::get_thermal_profile(conn)?;
+            apply_thermal_profile(conn, config, profile)
+        }
+        FanBackup::Desktop(headers) => headers.iter().try_for_each(|(policy, curve)| {
+            asus_mgmt::set_desktop_fan_curve_pro(conn, curve)?;
+            asus_mgmt::set_desktop_fan_policy(conn, policy)
+        }),
+    }
+}
+
+/// The same points with every duty at 100 %.
+fn full_duty(mut points: [FanCurvePoint; FAN_CURVE_POINTS]) -> [FanCurvePoint; FAN_CURVE_POINTS] {
+    for p in &mut points {
+        p.duty_pct = 100;
+    }
+    points
+}
@@ -0,0 +1,45 @@
+/// Single-instance guard.
+///
+/// A second copy of the app would fight the first for the `WinRing0`
+/// driver service and the HID devices, and add a second tray icon. The
+/// first instance holds a named mutex until it exits; a later one asks it
+/// over the [`ipc`](crate::ipc) pipe to show its window, then exits.
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{GetLastError, ERROR_ACCESS_DENIED, ERROR_ALREADY_EXISTS};
+use windows::Win32::System::Threading::CreateMutexW;
+use windows::Win32::UI::WindowsAndMessaging::{AllowSetForegroundWindow, ASFW_ANY};
+
+use crate::ipc::{self, Request};
+
+const MUTEX_NAME: PCWSTR = w!(r"Local\NoCrate.SingleInstance");
+
+/// Take the instance mutex for the life of the process.
+///
+/// Returns `false` if another instance already holds it. If the mutex
+/// cannot be created at all, the app starts anyway.
+#[allow(unsafe_code)]
+pub fn acquire() -> bool {
+    // The handle is never closed; Windows releases it when the process exits
+    match unsafe { CreateMutexW(None, false, MUTEX_NAME) } {
+        Ok(_) => (unsafe { GetLastError() }) != ERROR_ALREADY_EXISTS,
+        // Created by an instance running with a different security context
+        Err(e) if e.code() == ERROR_ACCESS_DENIED.to_hresult() => false,
+        Err(e) => {
+            eprintln!("Instance: failed to create mutex: {e}");
+            true
+        }
+    }
+}
+
+/// Ask the running instance to bring its window to the front.
+#[allow(unsafe_code)]
+pub fn hand_off() {
+    // Windows only lets the running instance take the foreground if the
+    // process the user just started allows it
+    let _ = unsafe { AllowSetForegroundWindow(ASFW_ANY) };
+    match ipc::send(Request::Show) {
+        Some(Ok(_)) => {}
+        Some(Err(e)) => eprintln!("NoCrate is already running: {e}"),
+        None => eprintln!("NoCrate is already running"),
+    }
+}
@@ -0,0 +1,284 @@
+/// Named pipe between the running app and `nocrate-cli`.
+///
+/// The app serves `\\.\pipe\nocrate`; a client writes one JSON
+/// [`Request`] terminated by a newline and reads back one JSON
+/// `Result<Reply, String>`. The app usually runs elevated, where the
+/// default DACL would leave the user's own non-elevated shell read-only,
+/// so the pipe grants read/write to the user running the app explicitly
+/// (full access for SYSTEM and administrators), and remote clients are
+/// rejected.
+///
+/// [`execute`] holds the hardware side of each request. It works on the
+/// individual subsystems rather than `AppState`, so the CLI can run the
+/// same code directly when the app is not running.
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::mem::ManuallyDrop;
+use std::os::windows::io::FromRawHandle;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{
+    ERROR_FILE_NOT_FOUND, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, GENERIC_READ, GENERIC_WRITE,
+    INVALID_HANDLE_VALUE,
+};
+use windows::Win32::Security::SECURITY_ATTRIBUTES;
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, FlushFileBuffers, FILE_FLAGS_AND_ATTRIBUTES, FILE_FLAG_FIRST_PIPE_INSTANCE,
+    FILE_SHARE_NONE, OPEN_EXISTING, PIPE_ACCESS_DUPLEX,
+};
+use windows::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, WaitNamedPipeW, PIPE_READMODE_BYTE,
+    PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_WAIT,
+};
+
+use crate::atk_events::THERMAL_PROFILE_CHANGED_EVENT;
+use crate::aura::controller::AuraController;
+use crate::commands::aura::stop_direct_output;
+use crate::commands::fan::apply_thermal_profile;
+use crate::config::AppConfig;
+use crate::i18n::{self, Msg};
+use crate::service;
+use crate::state::{AppState, WmiThread};
+use crate::wmi::asus_mgmt::{self, FanInfo, ThermalProfile};
+
+const PIPE_NAME: PCWSTR = w!(r"\\.\pipe\nocrate");
+
+/// Pipe buffer size in each direction.
+const BUFFER_SIZE: u32 = 4096;
+
+/// Largest accepted request line.
+const MAX_REQUEST: u64 = 64 * 1024;
+
+/// How long a client waits for a busy pipe.
+const BUSY_TIMEOUT_MS: u32 = 5000;
+
+/// A request from the CLI.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    /// Read all fan speeds.
+    Fans,
+    /// Read the thermal profile, or switch to `profile`.
+    Profile { profile: Option<ThermalProfile> },
+    /// Turn off every channel of every AURA controller.
+    AuraOff,
+    /// Bring the main window to the front (sent by a second instance).
+    Show,
+}
+
+/// Successful result of a [`Request`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reply {
+    Fans(Vec<FanInfo>),
+    /// The active profile (after switching, if one was requested).
+    Profile(ThermalProfile),
+    Done,
+}
+
+/// Run a request against the given subsystems.
+pub fn execute(
+    wmi: Option<&WmiThread>,
+    aura: &[AuraController],
+    config: &AppConfig,
+    request: Request,
+) -> Result<Reply, String> {
+    let wmi = || wmi.ok_or_else(|| i18n::t(Msg::WmiNotInitialized).to_string());
+    match request {
+        Request::Fans => wmi()?
+            .execute(|conn| Ok(asus_mgmt::get_all_fan_speeds(conn)))
+            .map(Reply::Fans)
+            .map_err(Into::into),
+        Request::Profile { profile: None } => wmi()?
+            .execute(asus_mgmt::get_thermal_profile)
+            .map(Reply::Profile)
+            .map_err(Into::into),
+        Request::Profile {
+            profile: Some(profile),
+        } => {
+            let config = config.clone();
+            wmi()?
+                .execute(move |conn| apply_thermal_profile(conn, &config, profile))
+                .map_err(|e| e.to_string())?;
+            Ok(Reply::Profile(profile))
+        }
+        Request::AuraOff => {
+            if aura.is_empty() {
+                return Err(i18n::t(Msg::AuraNotAvailable).into());
+            }
+            for ctrl in aura {
+                for channel in ctrl.channels() {
+                    ctrl.turn_off(channel.index).map_err(|e| e.to_string())?;
+                }
+            }
+            Ok(Reply::Done)
+        }
+        Request::Show => Err(i18n::t(Msg::AppNotRunning).into()),
+    }
+}
+
+/// Send a request to the running app.
+///
+/// Returns `None` if the app is not running.
+pub fn send(request: Request) -> Option<Result<Reply, String>> {
+    let pipe = match connect() {
+        Ok(pipe) => pipe?,
+        Err(e) => return Some(Err(e)),
+    };
+    Some(exchange(&pipe, request))
+}
+
+/// Open the client end of the pipe, `Ok(None)` if there is no server.
+#[allow(unsafe_code)]
+fn connect() -> Result<Option<File>, String> {
+    for _ in 0..2 {
+        let opened = unsafe {
+            CreateFileW(
+                PIPE_NAME,
+                GENERIC_READ.0 | GENERIC_WRITE.0,
+                FILE_SHARE_NONE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                None,
+            )
+        };
+        match opened {
+            // SAFETY: the handle was just opened and is owned by the File
+            Ok(handle) => return Ok(Some(unsafe { File::from_raw_handle(handle.0) })),
+            Err(e) if e.code() == ERROR_FILE_NOT_FOUND.to_hresult() => return Ok(None),
+            Err(e) if e.code() == ERROR_PIPE_BUSY.to_hresult() => {
+                let _ = unsafe { WaitNamedPipeW(PIPE_NAME, BUSY_TIMEOUT_MS) };
+            }
+            Err(e) => return Err(i18n::tf(Msg::AppConnect, &[("error", &e)])),
+        }
+    }
+    Err(i18n::t(Msg::AppBusy).into())
+}
+
+fn exchange(mut pipe: &File, request: Request) -> Result<Reply, String> {
+    let mut line = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    pipe.write_all(line.as_bytes())
+        .map_err(|e| i18n::tf(Msg::PipeSend, &[("error", &e)]))?;
+
+    let mut reply = String::new();
+    let _ = BufReader::new(pipe)
+        .read_line(&mut reply)
+        .map_err(|e| i18n::tf(Msg::PipeRead, &[("error", &e)]))?;
+    serde_json::from_str::<Result<Reply, String>>(&reply)
+        .map_err(|e| i18n::tf(Msg::AppReplyInvalid, &[("error", &e)]))?
+}
+
+/// Spawn the pipe server thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-ipc".into())
+        .spawn(move || serve_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("IPC: failed to spawn pipe thread: {e}");
+    }
+}
+
+#[allow(unsafe_code)]
+fn serve_loop(app: &AppHandle) {
+    let security =
+        match service::current_user_sid().and_then(|sid| service::pipe_security(Some(&sid))) {
+            Ok(security) => security,
+            Err(e) => {
+                eprintln!("IPC: failed to build pipe security: {e}");
+                return;
+            }
+        };
+    let attributes = SECURITY_ATTRIBUTES {
+        nLength: u32::try_from(size_of::<SECURITY_ATTRIBUTES>()).unwrap_or(0),
+        lpSecurityDescriptor: security.0,
+        bInheritHandle: false.into(),
+    };
+    // One instance, reused for every client: there is no window in which
+    // the pipe is missing and the CLI would think the app is not running
+    let handle = unsafe {
+        CreateNamedPipeW(
+            PIPE_NAME,
+            PIPE_ACCESS_DUPLEX | FILE_FLAG_FIRST_PIPE_INSTANCE,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            1,
+            BUFFER_SIZE,
+            BUFFER_SIZE,
+            0,
+            Some(&raw const attributes),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        eprintln!(
+            "IPC: failed to create pipe: {}",
+            windows::core::Error::from_win32()
+        );
+        return;
+    }
+    // SAFETY: the handle stays open for the life of the thread; the File
+    // is never dropped, so it does not close it
+    let pipe = ManuallyDrop::new(unsafe { File::from_raw_handle(handle.0) });
+
+    loop {
+        match unsafe { ConnectNamedPipe(handle, None) } {
+            Ok(()) => {}
+            // The client connected between create/disconnect and this call
+            Err(e) if e.code() == ERROR_PIPE_CONNECTED.to_hresult() => {}
+            Err(e) => {
+                eprintln!("IPC: failed to accept client: {e}");
+                thread::sleep(Duration::from_secs(1));
+                continue;
+            }
+        }
+        if let Err(e) = serve_client(app, &pipe) {
+            eprintln!("IPC: {e}");
+        }
+        let _ = unsafe { FlushFileBuffers(handle) };
+        let _ = unsafe { DisconnectNamedPipe(handle) };
+    }
+}
+
+fn serve_client(app: &AppHandle, mut pipe: &File) -> std::io::Result<()> {
+    let mut line = String::new();
+    let _ = BufReader::new(pipe.take(MAX_REQUEST)).read_line(&mut line)?;
+
+    let reply = match serde_json::from_str::<Request>(&line) {
+        Ok(request) => handle(app, request),
+        Err(e) => Err(i18n::tf(Msg::RequestInvalid, &[("error", &e)])),
+    };
+    let mut out = serde_json::to_string(&reply).unwrap_or_default();
+    out.push('\n');
+    pipe.write_all(out.as_bytes())
+}
+
+fn handle(app: &AppHandle, request: Request) -> Result<Reply, String> {
+    let Some(state) = app.try_state::<AppState>() else {
+        return Err(i18n::t(Msg::AppStarting).into());
+    };
+    if matches!(request, Request::Show) {
+        crate::show_main_window(app);
+        return Ok(Reply::Done);
+    }
+    // The AURA lock is only taken when needed, not across WMI calls
+    let reply = if matches!(request, Request::AuraOff) {
+        stop_direct_output(&state);
+        execute(None, &state.aura.lock(), &state.config.get(), request)
+    } else {
+        execute(state.wmi.get().as_ref(), &[], &state.config.get(), request)
+    };
+    if let (
+        Ok(_),
+        Request::Profile {
+            profile: Some(profile),
+        },
+    ) = (&reply, request)
+    {
+        let _ = app.emit(THERMAL_PROFILE_CHANGED_EVENT, profile);
+    }
+    reply
+}
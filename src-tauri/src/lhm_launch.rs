@@ -0,0 +1,176 @@
+/// LibreHardwareMonitor auto-launch.
+///
+/// LHM only publishes the `root\LibreHardwareMonitor` WMI namespace while
+/// it runs. When the namespace is unavailable but LHM is installed, NoCrate
+/// can start it minimized to the tray and reconnect once the namespace
+/// appears, so users don't have to manage a second app themselves. LHM
+/// needs admin rights for its WMI provider, which it inherits from an
+/// elevated NoCrate.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+
+use crate::config::LhmConfig;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::state::AppState;
+use crate::wmi::lhm::{self, LhmStatus};
+
+const EXE_NAME: &str = "LibreHardwareMonitor.exe";
+
+/// LHM settings file next to the executable.
+const SETTINGS_NAME: &str = "LibreHardwareMonitor.config";
+
+/// Settings making LHM start hidden in the tray instead of opening its
+/// window on top of NoCrate.
+const HIDDEN_SETTINGS: [(&str, &str); 3] = [
+    ("startMinMenuItem", "true"),
+    ("minTrayMenuItem", "true"),
+    ("minCloseMenuItem", "true"),
+];
+
+/// Settings file written when LHM has never been run.
+const EMPTY_SETTINGS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<configuration>
+  <appSettings>
+  </appSettings>
+</configuration>
+"#;
+
+/// How long to wait for the namespace after starting LHM; it enumerates
+/// all hardware before publishing sensors.
+const NAMESPACE_TIMEOUT: Duration = Duration::from_secs(30);
+
+const NAMESPACE_POLL: Duration = Duration::from_secs(1);
+
+/// Locate `LibreHardwareMonitor.exe`: the configured path, else the usual
+/// install folders.
+pub fn find_install(cfg: &LhmConfig) -> Option<PathBuf> {
+    if let Some(path) = &cfg.path {
+        return Some(PathBuf::from(path)).filter(|p| p.is_file());
+    }
+    ["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"]
+        .iter()
+        .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+        .flat_map(|base| {
+            [
+                base.join("LibreHardwareMonitor").join(EXE_NAME),
+                base.join("Programs")
+                    .join("LibreHardwareMonitor")
+                    .join(EXE_NAME),
+            ]
+        })
+        .find(|p| p.is_file())
+}
+
+/// Start LHM hidden and wait until its namespace is connected.
+///
+/// Returns the current status right away if LHM is already available.
+///
+/// # Errors
+///
+/// Returns an error if WMI is unavailable, LHM is not installed or fails
+/// to start, or the namespace does not appear in time.
+pub fn ensure_running(state: &AppState) -> Result<LhmStatus> {
+    let wmi = state.wmi.require(Msg::WmiNotInitialized)?;
+
+    let status = wmi.execute(|conn| {
+        let _ = conn.connect_lhm();
+        Ok(lhm::get_lhm_status(conn))
+    })?;
+    if !matches!(status, LhmStatus::Unavailable) {
+        return Ok(status);
+    }
+
+    let exe = find_install(&state.config.get().lhm)
+        .ok_or_else(|| NoCrateError::Unknown(i18n::t(Msg::LhmNotInstalled).into()))?;
+    if let Err(e) = hide_on_start(&exe) {
+        // Only cosmetic; LHM then opens its window
+        eprintln!("LHM: could not update settings: {e}");
+    }
+    let _child = Command::new(&exe)
+        .current_dir(exe.parent().unwrap_or_else(|| Path::new(".")))
+        .spawn()
+        .map_err(|e| {
+            NoCrateError::Unknown(i18n::tf(
+                Msg::LhmStart,
+                &[("path", &exe.display()), ("error", &e)],
+            ))
+        })?;
+    eprintln!("LHM: started {}", exe.display());
+
+    let deadline = Instant::now() + NAMESPACE_TIMEOUT;
+    while Instant::now() < deadline {
+        thread::sleep(NAMESPACE_POLL);
+        let status = wmi.execute(|conn| {
+            Ok(if conn.connect_lhm() {
+                lhm::get_lhm_status(conn)
+            } else {
+                LhmStatus::Unavailable
+            })
+        })?;
+        // Wait for sensors too, the namespace is published first
+        if matches!(status, LhmStatus::Available { .. }) {
+            return Ok(status);
+        }
+    }
+    Err(NoCrateError::Timeout(format!(
+        "LibreHardwareMonitor WMI namespace did not appear within {}s",
+        NAMESPACE_TIMEOUT.as_secs()
+    )))
+}
+
+/// Spawn the startup thread launching LHM if enabled in the config.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-lhm-launch".into())
+        .spawn(move || {
+            let Some(state) = app.try_state::<AppState>() else {
+                return;
+            };
+            if !state.config.get().lhm.auto_launch {
+                return;
+            }
+            match ensure_running(&state) {
+                Ok(status) => eprintln!("LHM: {status:?}"),
+                Err(e) => eprintln!("LHM: auto-launch failed: {e}"),
+            }
+        });
+    if let Err(e) = spawned {
+        eprintln!("LHM: failed to spawn launch thread: {e}");
+    }
+}
+
+/// Set [`HIDDEN_SETTINGS`] in the LHM settings file, creating it if needed.
+fn hide_on_start(exe: &Path) -> std::io::Result<()> {
+    let path = exe.with_file_name(SETTINGS_NAME);
+    let mut xml = match fs::read_to_string(&path) {
+        Ok(xml) => xml,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => EMPTY_SETTINGS.to_string(),
+        Err(e) => return Err(e),
+    };
+    for (key, value) in HIDDEN_SETTINGS {
+        xml = set_app_setting(&xml, key, value);
+    }
+    fs::write(path, xml)
+}
+
+/// Set `<add key="…" value="…" />` under `<appSettings>`, replacing an
+/// existing entry for the key.
+fn set_app_setting(xml: &str, key: &str, value: &str) -> String {
+    let entry = format!("<add key=\"{key}\" value=\"{value}\" />");
+    let marker = format!("<add key=\"{key}\"");
+    if let Some(start) = xml.find(&marker) {
+        if let Some(len) = xml[start..].find("/>") {
+            return format!("{}{entry}{}", &xml[..start], &xml[start + len + 2..]);
+        }
+    }
+    xml.find("</appSettings>").map_or_else(
+        || xml.to_string(),
+        |end| format!("{}  {entry}\n  {}", &xml[..end], &xml[end..]),
+    )
+}
@@ -1,22 +1,100 @@
+mod api;
+mod atk_events;
 mod aura;
+pub mod cli;
 mod commands;
 mod config;
-mod error;
-#[cfg(feature = "sio")]
-mod sio;
+mod config_watch;
+mod conflicts;
+mod eco;
+mod fan_calibration;
+mod fan_stall;
+mod hotkeys;
+mod instance;
+mod ipc;
+mod lhm_launch;
+mod logs;
+mod metrics;
+mod mqtt;
+mod overlay;
+mod power_source;
+mod restore;
+mod rtss;
+mod sensor_history;
+mod sensors;
+pub mod service;
+mod startup;
 mod state;
-mod wmi;
+mod thermal_failsafe;
+mod updates;
+
+#[cfg(feature = "sio")]
+use nocrate_core::{cpu, ec, sio, smbus};
+use nocrate_core::{board, curve, error, gpu, hwinfo, i18n, storage, trace, wmi};
 
+use i18n::Msg;
 use state::AppState;
 use tauri::menu::{Menu, MenuItem, Submenu};
 use tauri::tray::TrayIconBuilder;
 use tauri::Manager;
 
+/// Command-line argument to start with only the tray icon, passed by
+/// auto-start.
+pub(crate) const MINIMIZED_ARG: &str = "--minimized";
+
+/// Command-line argument to run against fake hardware: a simulated ASUS
+/// desktop board, AURA controller and Super I/O chip. For frontend
+/// development and tests without an ASUS board or admin rights.
+pub(crate) const SIMULATE_ARG: &str = "--simulate";
+
+/// Command-line argument to run only the background engines (sensors,
+/// fan curves, alerts) and the tray icon. The main window and its webview
+/// are created only when opened from the tray, and destroyed again when
+/// closed.
+pub(crate) const HEADLESS_ARG: &str = "--headless";
+
+/// Show, restore and focus the main window, creating it if needed.
+pub(crate) fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(win) = app
+        .get_webview_window("main")
+        .or_else(|| create_main_window(app))
+    {
+        let _ = win.show();
+        let _ = win.unminimize();
+        let _ = win.set_focus();
+    }
+}
+
+/// Create the main window, hidden, from its `tauri.conf.json` entry,
+/// which has `create: false` so `--headless` can skip it.
+fn create_main_window(app: &tauri::AppHandle) -> Option<tauri::WebviewWindow> {
+    let config = app.config().app.windows.iter().find(|w| w.label == "main")?;
+    tauri::WebviewWindowBuilder::from_config(app, config)
+        .and_then(tauri::WebviewWindowBuilder::build)
+        .inspect_err(|e| eprintln!("Failed to create the main window: {e}"))
+        .ok()
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let minimized_arg = std::env::args().any(|arg| arg == MINIMIZED_ARG);
+    let simulate = std::env::args().any(|arg| arg == SIMULATE_ARG);
+    let headless = std::env::args().any(|arg| arg == HEADLESS_ARG);
+    if !instance::acquire() {
+        // A background start does not pull the running window forward
+        if !(minimized_arg || headless) {
+            instance::hand_off();
+        }
+        return;
+    }
+    logs::capture();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .setup(|app| {
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .setup(move |app| {
+            logs::attach(app.handle().clone());
+
             // Resolve directories for config and resources
             let app_data_dir = app
                 .path()
@@ -30,9 +108,33 @@ pub fn run() {
             // Initialize application state.
             // WMI and AURA failures are non-fatal — the state is always
             // managed so commands can return clean errors instead of panics.
-            match AppState::new(app_data_dir, resource_dir) {
+            // The hardware is brought up in the background so the window
+            // does not wait for it.
+            match AppState::new(app_data_dir, resource_dir, simulate) {
                 Ok(state) => {
                     let _ = app.manage(state);
+                    startup::spawn(app.handle().clone(), |app| {
+                        aura::hotplug::spawn_monitor(app.clone());
+                        aura::schedule::spawn(app.clone());
+                        atk_events::spawn(app.clone());
+                        lhm_launch::spawn(app.clone());
+                        power_source::spawn(app.clone());
+                        restore::spawn(app.clone());
+                        conflicts::report(app);
+                    });
+                    config_watch::spawn(app.handle().clone());
+                    rtss::spawn(app.handle().clone());
+                    overlay::spawn(app.handle().clone());
+                    hotkeys::spawn(app.handle().clone());
+                    fan_stall::spawn(app.handle().clone());
+                    thermal_failsafe::spawn(app.handle().clone());
+                    sensor_history::spawn(app.handle().clone());
+                    eco::spawn(app.handle().clone());
+                    metrics::spawn(app.handle().clone());
+                    mqtt::spawn(app.handle().clone());
+                    api::spawn(app.handle().clone());
+                    ipc::spawn(app.handle().clone());
+                    updates::spawn(app.handle().clone());
                 }
                 Err(e) => {
                     eprintln!("Warning: Failed to initialize app state: {e}");
@@ -40,37 +142,61 @@ pub fn run() {
             }
 
             // ── System Tray ──────────────────────────────────
-            let show_item = MenuItem::with_id(app, "show", "显示主窗口", true, None::<&str>)?;
+            // Labels follow the locale loaded with the config above
+            let show_item =
+                MenuItem::with_id(app, "show", i18n::t(Msg::TrayShow), true, None::<&str>)?;
 
             // Thermal profile submenu
-            let profile_standard =
-                MenuItem::with_id(app, "profile_standard", "标准模式", true, None::<&str>)?;
-            let profile_performance =
-                MenuItem::with_id(app, "profile_performance", "性能模式", true, None::<&str>)?;
-            let profile_silent =
-                MenuItem::with_id(app, "profile_silent", "静音模式", true, None::<&str>)?;
+            let profile_standard = MenuItem::with_id(
+                app,
+                "profile_standard",
+                i18n::t(Msg::TrayProfileStandard),
+                true,
+                None::<&str>,
+            )?;
+            let profile_performance = MenuItem::with_id(
+                app,
+                "profile_performance",
+                i18n::t(Msg::TrayProfilePerformance),
+                true,
+                None::<&str>,
+            )?;
+            let profile_silent = MenuItem::with_id(
+                app,
+                "profile_silent",
+                i18n::t(Msg::TrayProfileSilent),
+                true,
+                None::<&str>,
+            )?;
             let profile_submenu = Submenu::with_items(
                 app,
-                "风扇配置",
+                i18n::t(Msg::TrayFanProfile),
                 true,
                 &[&profile_standard, &profile_performance, &profile_silent],
             )?;
 
-            let quit_item = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_item, &profile_submenu, &quit_item])?;
+            let overlay_item = MenuItem::with_id(
+                app,
+                "overlay",
+                i18n::t(Msg::TrayOverlay),
+                true,
+                None::<&str>,
+            )?;
+            let quit_item =
+                MenuItem::with_id(app, "quit", i18n::t(Msg::TrayQuit), true, None::<&str>)?;
+            let menu = Menu::with_items(
+                app,
+                &[&show_item, &profile_submenu, &overlay_item, &quit_item],
+            )?;
 
             let _tray = TrayIconBuilder::new()
                 .icon(app.default_window_icon().cloned().unwrap())
-                .tooltip("NoCrate — ASUS 主板控制")
+                .tooltip(i18n::t(Msg::TrayTooltip))
                 .menu(&menu)
                 .show_menu_on_left_click(false)
                 .on_menu_event(|app, event| match event.id.as_ref() {
                     "show" => {
-                        if let Some(win) = app.get_webview_window("main") {
-                            let _ = win.show();
-                            let _ = win.unminimize();
-                            let _ = win.set_focus();
-                        }
+                        show_main_window(app);
                     }
                     "profile_standard" | "profile_performance" | "profile_silent" => {
                         let profile = match event.id.as_ref() {
@@ -79,40 +205,66 @@ pub fn run() {
                             _ => wmi::asus_mgmt::ThermalProfile::Silent,
                         };
                         if let Some(state) = app.try_state::<AppState>() {
-                            if let Some(wmi) = &state.wmi {
+                            if let Some(wmi) = state.wmi.get() {
                                 let _ = wmi.execute(move |conn| {
                                     wmi::asus_mgmt::set_thermal_profile(conn, profile)
                                 });
                             }
                         }
                     }
+                    "overlay" => {
+                        let _ = overlay::toggle(app);
+                    }
                     "quit" => {
+                        rtss::release();
                         app.exit(0);
                     }
                     _ => {}
                 })
                 .on_tray_icon_event(|tray, event| {
                     if let tauri::tray::TrayIconEvent::DoubleClick { .. } = event {
-                        let app = tray.app_handle();
-                        if let Some(win) = app.get_webview_window("main") {
-                            let _ = win.show();
-                            let _ = win.unminimize();
-                            let _ = win.set_focus();
-                        }
+                        show_main_window(tray.app_handle());
                     }
                 })
                 .build(app)?;
 
+            // Headless: no window until it is opened from the tray
+            if headless {
+                return Ok(());
+            }
+
+            // The main window is created hidden and only shown here, so
+            // starting to the tray never flashes it
+            let _ = create_main_window(app.handle());
+            let start_minimized = minimized_arg
+                || app
+                    .try_state::<AppState>()
+                    .is_some_and(|state| state.config.get().start_minimized);
+            if !start_minimized {
+                show_main_window(app.handle());
+            }
+
             Ok(())
         })
-        .on_window_event(|window, event| {
+        .on_window_event(move |window, event| {
             // Intercept close if "close_to_tray" is enabled
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 let app = window.app_handle();
+                // Headless: closing destroys the window to free its memory
+                if headless {
+                    return;
+                }
                 if let Some(state) = app.try_state::<AppState>() {
                     if state.config.get().close_to_tray {
                         api.prevent_close();
                         let _ = window.hide();
+                        return;
+                    }
+                }
+                // The overlay would otherwise keep the app running
+                if window.label() == "main" {
+                    if let Some(overlay) = app.get_webview_window(overlay::OVERLAY_LABEL) {
+                        let _ = overlay.destroy();
                     }
                 }
             }
@@ -121,36 +273,145 @@ pub fn run() {
             commands::greet,
             commands::fan::get_fan_speed,
             commands::fan::get_all_fan_speeds,
+            commands::dashboard::get_dashboard_snapshot,
+            commands::capabilities::get_capabilities,
+            commands::status::get_system_status,
+            commands::status::get_conflicts,
+            commands::status::pause_conflicting_services,
+            commands::status::restore_paused_services,
+            commands::status::reinit_subsystem,
+            commands::fan::get_fan_status,
             commands::fan::get_thermal_profile,
             commands::fan::set_thermal_profile,
             commands::fan::get_default_fan_curve,
+            commands::fan::get_fan_curve,
+            commands::fan::set_fan_curve,
+            commands::fan::reset_fan_curves,
             commands::fan::get_wmi_backend,
+            commands::fan::get_wmi_health,
             commands::fan::get_desktop_fan_policies,
             commands::fan::set_desktop_fan_policy,
+            commands::fan::set_fan_low_limit,
+            commands::fan::copy_fan_curve,
+            commands::fan::get_desktop_fan_sources,
             commands::fan::get_asushw_sensors,
             commands::fan::get_desktop_fan_curve,
+            commands::fan::get_desktop_fan_curves,
             commands::fan::set_desktop_fan_curve,
+            commands::fan::get_desktop_fan_curve_3,
+            commands::fan::set_desktop_fan_curve_3,
+            commands::fan::start_fan_calibration,
+            commands::fan::get_fan_calibration_status,
+            commands::fan::preview_fan_curve,
+            commands::fan::get_fan_groups,
+            commands::fan::set_fan_group,
+            commands::fan::delete_fan_group,
+            commands::fan::set_fan_group_curve,
             commands::fan::probe_desktop_fan_types,
             commands::fan::test_asio_hw_fun,
+            commands::laptop::get_battery_charge_limit,
+            commands::laptop::set_battery_charge_limit,
+            commands::laptop::get_gpu_mode,
+            commands::laptop::set_gpu_mode,
+            commands::laptop::get_power_limits,
+            commands::laptop::set_power_limits,
+            commands::laptop::get_power_source,
             #[cfg(feature = "sio")]
             commands::fan::get_sio_sensors,
             #[cfg(feature = "sio")]
             commands::fan::get_sio_status,
+            #[cfg(feature = "sio")]
+            commands::fan::run_sio_diagnostics,
+            #[cfg(feature = "sio")]
+            commands::fan::clear_sio_intrusion,
+            #[cfg(feature = "sio")]
+            commands::fan::get_lpc_decode,
+            #[cfg(feature = "sio")]
+            commands::fan::dump_sio_registers,
+            #[cfg(feature = "sio")]
+            commands::driver::get_driver_status,
+            #[cfg(feature = "sio")]
+            commands::driver::install_driver,
+            #[cfg(feature = "sio")]
+            commands::driver::uninstall_driver,
+            #[cfg(feature = "sio")]
+            commands::driver::set_driver_resident,
+            commands::diagnostics::start_device_scan,
+            commands::diagnostics::cancel_device_scan,
+            commands::diagnostics::explore_wmi_classes,
+            commands::trace::record_sensor_trace,
+            commands::trace::replay_sensor_trace,
             commands::sensor::get_lhm_status,
             commands::sensor::get_lhm_sensors,
+            commands::sensor::get_lhm_hardware,
+            commands::sensor::query_lhm_sensors,
+            commands::sensor::get_lhm_install,
+            commands::sensor::launch_lhm,
+            commands::sensor::get_all_sensors_unified,
+            commands::sensor::get_sensor_stats,
+            commands::sensor::reset_sensor_stats,
+            commands::sensor::get_sensor_history,
+            commands::sensor::export_history,
+            commands::sensor::get_storage_temperatures,
+            commands::sensor::get_gpu_sensors,
+            commands::sensor::get_hwinfo_sensors,
+            #[cfg(feature = "sio")]
+            commands::sensor::get_ec_sensors,
+            #[cfg(feature = "sio")]
+            commands::sensor::get_cpu_temperatures,
             commands::aura::aura_is_available,
             commands::aura::aura_get_device_info,
+            commands::aura::aura_get_channels,
             commands::aura::aura_set_effect,
             commands::aura::aura_set_static_color,
             commands::aura::aura_turn_off,
             commands::aura::aura_set_direct_colors,
+            commands::aura::aura_save_to_flash,
+            commands::aura::aura_show_calibration_pattern,
+            commands::aura::aura_start_software_effect,
+            commands::aura::aura_stop_software_effect,
+            commands::aura::aura_get_software_effect,
+            commands::aura::aura_start_stream,
+            commands::aura::aura_push_stream_frame,
+            commands::aura::aura_stop_stream,
+            commands::aura::aura_get_stream_status,
+            commands::aura::aura_openrgb_get_devices,
+            #[cfg(feature = "sio")]
+            commands::aura::aura_get_dram_devices,
+            #[cfg(feature = "sio")]
+            commands::aura::aura_dram_set_effect,
+            #[cfg(feature = "sio")]
+            commands::aura::aura_dram_set_direct_colors,
             commands::config::get_config,
             commands::config::update_config,
+            commands::config::list_config_backups,
+            commands::config::restore_config_backup,
             commands::system::is_admin,
             commands::system::restart_as_admin,
             commands::system::set_auto_start,
             commands::system::get_auto_start_enabled,
+            commands::system::get_auto_start_method,
+            commands::system::get_board_info,
+            commands::system::toggle_overlay,
+            commands::system::regenerate_api_token,
+            commands::system::get_recent_logs,
+            commands::system::check_for_updates,
+            commands::system::get_service_status,
+            commands::system::install_service,
+            commands::system::uninstall_service,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(move |_app, event| {
+            // Headless: keep running in the tray with no window open;
+            // "Quit" exits with an explicit code
+            if let tauri::RunEvent::ExitRequested {
+                code: None, api, ..
+            } = event
+            {
+                if headless {
+                    api.prevent_exit();
+                }
+            }
+        });
 }
@@ -0,0 +1,136 @@
+/// In-app log capture for the diagnostics console.
+///
+/// The app and the hardware crate log with `eprintln!`. At startup the
+/// process's standard error handle is replaced with a pipe; a reader
+/// thread splits what arrives into lines, keeps the most recent ones for
+/// [`recent`], emits each as a [`LOG_LINE_EVENT`] and passes it on to the
+/// original stderr, so a terminal still shows the output.
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::mem::ManuallyDrop;
+use std::os::windows::io::{AsRawHandle, FromRawHandle};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::System::Console::{
+    GetConsoleMode, GetStdHandle, SetStdHandle, WriteConsoleW, CONSOLE_MODE, STD_ERROR_HANDLE,
+};
+use windows::Win32::System::Pipes::CreatePipe;
+
+/// Event emitted to the frontend with every [`LogLine`].
+pub const LOG_LINE_EVENT: &str = "log-line";
+
+/// Number of lines kept for [`recent`].
+const CAPACITY: usize = 2000;
+
+/// One line written to stderr.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    /// Milliseconds since the Unix epoch
+    pub time_ms: u64,
+    pub text: String,
+}
+
+static LINES: Mutex<VecDeque<LogLine>> = Mutex::new(VecDeque::new());
+
+/// Set once the app is running, so lines can be emitted.
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+/// Redirect stderr into the log buffer. Call once, before anything logs.
+#[allow(unsafe_code)]
+pub fn capture() {
+    let (mut read, mut write) = (HANDLE::default(), HANDLE::default());
+    if let Err(e) = unsafe { CreatePipe(&raw mut read, &raw mut write, None, 0) } {
+        eprintln!("Logs: failed to create pipe: {e}");
+        return;
+    }
+    // SAFETY: the read end was just created and is owned by the File
+    let reader = unsafe { File::from_raw_handle(read.0) };
+    // Not owned: the handle belongs to the console or the parent process
+    let original = unsafe { GetStdHandle(STD_ERROR_HANDLE) }
+        .ok()
+        .filter(|h| !h.is_invalid() && !h.0.is_null())
+        .map(|h| ManuallyDrop::new(unsafe { File::from_raw_handle(h.0) }));
+
+    // The reader must be running before anything is written to the pipe
+    let spawned = thread::Builder::new()
+        .name("nocrate-logs".into())
+        .spawn(move || read_loop(reader, original));
+    if let Err(e) = spawned {
+        let _ = unsafe { CloseHandle(write) };
+        eprintln!("Logs: failed to spawn reader thread: {e}");
+        return;
+    }
+    if let Err(e) = unsafe { SetStdHandle(STD_ERROR_HANDLE, write) } {
+        let _ = unsafe { CloseHandle(write) };
+        eprintln!("Logs: failed to redirect stderr: {e}");
+    }
+}
+
+/// Start emitting [`LOG_LINE_EVENT`]s to the frontend.
+pub fn attach(app: AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// The last `n` captured lines, oldest first.
+pub fn recent(n: usize) -> Vec<LogLine> {
+    let lines = LINES.lock();
+    lines
+        .iter()
+        .skip(lines.len().saturating_sub(n))
+        .cloned()
+        .collect()
+}
+
+fn read_loop(reader: File, mut original: Option<ManuallyDrop<File>>) {
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        let text = String::from_utf8_lossy(&buf).trim_end().to_string();
+        if let Some(out) = original.as_mut() {
+            pass_through(out, &buf, &text);
+        }
+
+        let line = LogLine {
+            time_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX)),
+            text,
+        };
+        {
+            let mut lines = LINES.lock();
+            if lines.len() == CAPACITY {
+                let _ = lines.pop_front();
+            }
+            lines.push_back(line.clone());
+        }
+        if let Some(app) = APP.get() {
+            let _ = app.emit(LOG_LINE_EVENT, line);
+        }
+    }
+}
+
+/// Write a line to the original stderr: as UTF-16 to a console, which
+/// would garble UTF-8 bytes, and unchanged to a file or pipe.
+#[allow(unsafe_code)]
+fn pass_through(out: &mut File, bytes: &[u8], text: &str) {
+    let handle = HANDLE(out.as_raw_handle());
+    let mut mode = CONSOLE_MODE::default();
+    if unsafe { GetConsoleMode(handle, &raw mut mode) }.is_ok() {
+        let wide: Vec<u16> = text.encode_utf16().chain("\r\n".encode_utf16()).collect();
+        let _ = unsafe { WriteConsoleW(handle, &wide, None, None) };
+    } else {
+        let _ = out.write_all(bytes);
+    }
+}
@@ -0,0 +1,168 @@
+/// Prometheus metrics endpoint.
+///
+/// An optional embedded HTTP listener serving `GET /metrics` in the
+/// Prometheus text format, so the machine can be scraped and graphed in
+/// Grafana. Every unified sensor becomes a gauge labelled with its stable
+/// ID, and the active thermal profile is exported as a 0/1 gauge per
+/// profile. Readings are collected per scrape; there is no cache.
+///
+/// The listener follows the config: it starts, stops or moves to a new
+/// address when `metrics` changes, from the settings page or on disk.
+use std::fmt::Write as _;
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::sensors::{self, SensorKind};
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, ThermalProfile};
+
+/// Interval at which the config is checked while waiting for requests.
+const CONFIG_CHECK: Duration = Duration::from_secs(1);
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Spawn the metrics listener thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-metrics".into())
+        .spawn(move || serve_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Metrics: failed to spawn listener thread: {e}");
+    }
+}
+
+fn serve_loop(app: &AppHandle) {
+    // Address the listener was last started on (also after a failed bind,
+    // so it is not retried until the config changes)
+    let mut bound: Option<String> = None;
+    let mut server: Option<Server> = None;
+
+    loop {
+        let Some(state) = app.try_state::<AppState>() else {
+            thread::sleep(CONFIG_CHECK);
+            continue;
+        };
+        let cfg = state.config.get().metrics;
+        let wanted = cfg.enabled.then_some(cfg.bind);
+        if wanted != bound {
+            // Dropping the server closes the old socket
+            server = None;
+            if let Some(bind) = &wanted {
+                match Server::http(bind) {
+                    Ok(s) => {
+                        eprintln!("Metrics: listening on http://{bind}/metrics");
+                        server = Some(s);
+                    }
+                    Err(e) => eprintln!("Metrics: failed to listen on {bind}: {e}"),
+                }
+            } else if bound.is_some() {
+                eprintln!("Metrics: listener stopped");
+            }
+            bound = wanted;
+        }
+
+        let Some(s) = &server else {
+            thread::sleep(CONFIG_CHECK);
+            continue;
+        };
+        match s.recv_timeout(CONFIG_CHECK) {
+            Ok(Some(request)) => {
+                let response = if request.method() == &Method::Get
+                    && request.url().split('?').next() == Some("/metrics")
+                {
+                    let content_type = Header::from_bytes("Content-Type", CONTENT_TYPE)
+                        .expect("static header is valid");
+                    Response::from_string(render(&state)).with_header(content_type)
+                } else {
+                    Response::from_string("Not Found").with_status_code(404)
+                };
+                let _ = request.respond(response);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Metrics: failed to accept request: {e}"),
+        }
+    }
+}
+
+/// Render all gauges in the Prometheus text exposition format.
+fn render(state: &AppState) -> String {
+    let snapshot = sensors::collect(state);
+    let mut out = String::new();
+
+    for kind in [
+        SensorKind::Temperature,
+        SensorKind::Fan,
+        SensorKind::FanPercent,
+        SensorKind::Current,
+        SensorKind::Flow,
+    ] {
+        let (name, help) = metric(kind);
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} gauge");
+        for s in snapshot.sensors.iter().filter(|s| s.kind == kind) {
+            let _ = writeln!(
+                out,
+                "{name}{{id=\"{}\",name=\"{}\",device=\"{}\",duplicate=\"{}\"}} {}",
+                escape(&s.id),
+                escape(&s.name),
+                escape(&s.device),
+                s.duplicate_of.is_some(),
+                s.value
+            );
+        }
+    }
+
+    let profile = state
+        .wmi
+        .get()
+        .and_then(|wmi| wmi.execute(asus_mgmt::get_thermal_profile).ok());
+    if let Some(active) = profile {
+        let _ = writeln!(
+            out,
+            "# HELP nocrate_thermal_profile Active thermal profile (1 = active)."
+        );
+        let _ = writeln!(out, "# TYPE nocrate_thermal_profile gauge");
+        for p in ThermalProfile::ALL {
+            let _ = writeln!(
+                out,
+                "nocrate_thermal_profile{{profile=\"{}\"}} {}",
+                p.as_str(),
+                u8::from(p == active)
+            );
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP nocrate_sensor_source_errors Sensor sources that failed in this scrape."
+    );
+    let _ = writeln!(out, "# TYPE nocrate_sensor_source_errors gauge");
+    let _ = writeln!(
+        out,
+        "nocrate_sensor_source_errors {}",
+        snapshot.errors.len()
+    );
+    out
+}
+
+/// Metric name and help text for a sensor kind.
+const fn metric(kind: SensorKind) -> (&'static str, &'static str) {
+    match kind {
+        SensorKind::Temperature => ("nocrate_temperature_celsius", "Temperature in °C."),
+        SensorKind::Fan => ("nocrate_fan_rpm", "Fan speed in RPM."),
+        SensorKind::FanPercent => ("nocrate_fan_duty_percent", "Fan duty cycle in percent."),
+        SensorKind::Current => ("nocrate_current_amperes", "Current in amperes."),
+        SensorKind::Flow => ("nocrate_flow_liters_per_hour", "Coolant flow in L/h."),
+    }
+}
+
+/// Escape a label value: backslash, double quote and newline.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,416 @@
+/// MQTT publishing with Home Assistant discovery.
+///
+/// When enabled, a background thread connects to the configured broker,
+/// announces every unified sensor, a thermal profile select and an AURA
+/// light through Home Assistant's discovery topics, and publishes all
+/// readings as one JSON object per interval. Profile and lighting
+/// commands from Home Assistant are applied like the frontend would.
+///
+/// Topics live under `<base_topic>/<computer name>/`:
+///
+/// - `status`: `online` / `offline` (retained, last will)
+/// - `sensors`: readings keyed by sanitized unified sensor ID
+/// - `profile`, `profile/set`: active thermal profile and commands
+/// - `aura/set`: JSON light commands (`state`, `color`, `effect`)
+///
+/// The same thread drives the connection, so publishing never blocks: a
+/// message is dropped if the request queue is full.
+use std::collections::HashSet;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rumqttc::{
+    Client, Connection, Event, LastWill, MqttOptions, Outgoing, Packet, QoS, RecvTimeoutError,
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::aura::protocol::{AuraDirection, AuraEffect, AuraSpeed, RgbColor};
+use crate::commands::aura::{stop_direct_output, with_aura};
+use crate::commands::fan::switch_thermal_profile;
+use crate::config::MqttConfig;
+use crate::sensors::{self, SensorKind, UnifiedSensor};
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, ThermalProfile};
+
+/// Longest wait for broker traffic before checking the config and the
+/// publish interval.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Pause after a connection error before the client reconnects.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+/// Request queue capacity, large enough for a full discovery burst.
+const QUEUE_CAPACITY: usize = 1024;
+
+/// Spawn the MQTT client thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-mqtt".into())
+        .spawn(move || client_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("MQTT: failed to spawn client thread: {e}");
+    }
+}
+
+fn client_loop(app: &AppHandle) {
+    let mut session: Option<Session> = None;
+
+    loop {
+        let Some(state) = app.try_state::<AppState>() else {
+            thread::sleep(TICK);
+            continue;
+        };
+        // Reconnect with the new settings whenever they change
+        let cfg = state.config.get().mqtt;
+        if session.as_ref().map(|s| &s.cfg) != cfg.enabled.then_some(&cfg) {
+            if let Some(old) = session.take() {
+                old.close();
+            }
+            if cfg.enabled {
+                session = Some(Session::open(cfg, &state));
+            }
+        }
+
+        match &mut session {
+            Some(s) => s.poll(app, &state),
+            None => thread::sleep(TICK),
+        }
+    }
+}
+
+/// JSON light command sent by Home Assistant.
+#[derive(Debug, Deserialize)]
+struct LightCommand {
+    state: String,
+    color: Option<RgbColor>,
+    effect: Option<AuraEffect>,
+}
+
+struct Session {
+    cfg: MqttConfig,
+    client: Client,
+    connection: Connection,
+    /// `<base_topic>/<node>`
+    topic: String,
+    node: String,
+    /// Home Assistant device block shared by all entities
+    device: Value,
+    connected: bool,
+    /// Last connection error, logged once until it changes
+    last_error: Option<String>,
+    /// Sensor object IDs announced on the current connection
+    announced: HashSet<String>,
+    next_publish: Instant,
+    /// Light state for commands that only change one attribute
+    effect: AuraEffect,
+    color: RgbColor,
+}
+
+impl Session {
+    fn open(cfg: MqttConfig, state: &AppState) -> Self {
+        let node = node_id();
+        let topic = format!("{}/{node}", cfg.base_topic.trim_end_matches('/'));
+
+        let mut options = MqttOptions::new(format!("nocrate-{node}"), &cfg.host, cfg.port);
+        let _ = options.set_keep_alive(KEEP_ALIVE);
+        let _ = options.set_last_will(LastWill::new(
+            format!("{topic}/status"),
+            "offline",
+            QoS::AtLeastOnce,
+            true,
+        ));
+        if let Some(user) = &cfg.username {
+            let _ = options.set_credentials(user, cfg.password.as_deref().unwrap_or_default());
+        }
+        let (client, connection) = Client::new(options, QUEUE_CAPACITY);
+        eprintln!("MQTT: connecting to {}:{}", cfg.host, cfg.port);
+
+        let board = state.board.as_ref();
+        let manufacturer = board
+            .and_then(|b| b.manufacturer.clone())
+            .unwrap_or_else(|| "ASUS".into());
+        let device = json!({
+            "identifiers": [format!("nocrate_{node}")],
+            "name": format!("NoCrate {node}"),
+            "manufacturer": manufacturer,
+            "model": board.map(|b| b.product.clone()),
+            "sw_version": env!("CARGO_PKG_VERSION"),
+        });
+
+        Self {
+            cfg,
+            client,
+            connection,
+            topic,
+            node,
+            device,
+            connected: false,
+            last_error: None,
+            announced: HashSet::new(),
+            next_publish: Instant::now(),
+            effect: AuraEffect::Static,
+            color: RgbColor::WHITE,
+        }
+    }
+
+    /// Mark the device offline and disconnect.
+    fn close(mut self) {
+        if self.connected {
+            self.publish("status", "offline", true);
+        }
+        let _ = self.client.try_disconnect();
+        // Drive the event loop until the disconnect has been sent
+        loop {
+            match self.connection.recv_timeout(TICK) {
+                Ok(Ok(Event::Outgoing(Outgoing::Disconnect)) | Err(_))
+                | Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => break,
+                Ok(Ok(_)) => {}
+            }
+        }
+        eprintln!("MQTT: disconnected");
+    }
+
+    fn poll(&mut self, app: &AppHandle, state: &AppState) {
+        match self.connection.recv_timeout(TICK) {
+            Ok(Ok(Event::Incoming(Packet::ConnAck(_)))) => self.on_connect(state),
+            Ok(Ok(Event::Incoming(Packet::Publish(p)))) => {
+                let payload = String::from_utf8_lossy(&p.payload).into_owned();
+                self.on_command(app, state, &p.topic, &payload);
+            }
+            Ok(Ok(_)) | Err(RecvTimeoutError::Timeout) => {}
+            Ok(Err(e)) => {
+                let e = e.to_string();
+                if self.last_error.as_ref() != Some(&e) {
+                    eprintln!("MQTT: connection error: {e}");
+                    self.last_error = Some(e);
+                }
+                self.connected = false;
+                thread::sleep(RETRY_DELAY);
+            }
+            Err(RecvTimeoutError::Disconnected) => thread::sleep(TICK),
+        }
+
+        if self.connected && Instant::now() >= self.next_publish {
+            self.publish_state(state);
+            self.next_publish = Instant::now() + Duration::from_secs(self.cfg.interval_secs.max(1));
+        }
+    }
+
+    fn on_connect(&mut self, state: &AppState) {
+        eprintln!("MQTT: connected to {}:{}", self.cfg.host, self.cfg.port);
+        self.connected = true;
+        self.last_error = None;
+        self.announced.clear();
+
+        for command in ["profile/set", "aura/set"] {
+            let _ = self
+                .client
+                .try_subscribe(format!("{}/{command}", self.topic), QoS::AtLeastOnce);
+        }
+        self.publish("status", "online", true);
+        self.announce_controls(state);
+        self.next_publish = Instant::now();
+    }
+
+    /// Announce the thermal profile select and the AURA light.
+    fn announce_controls(&self, state: &AppState) {
+        if state.wmi.is_some() {
+            let options: Vec<&str> = ThermalProfile::ALL.iter().map(|p| p.as_str()).collect();
+            self.announce(
+                "select",
+                "thermal_profile",
+                json!({
+                    "name": "Thermal profile",
+                    "icon": "mdi:fan",
+                    "command_topic": format!("{}/profile/set", self.topic),
+                    "state_topic": format!("{}/profile", self.topic),
+                    "options": options,
+                }),
+            );
+        }
+        if !state.aura.lock().is_empty() {
+            let effects: Vec<Value> = AuraEffect::ALL
+                .iter()
+                .filter(|e| **e != AuraEffect::Off)
+                .map(|e| json!(e))
+                .collect();
+            self.announce(
+                "light",
+                "aura",
+                json!({
+                    "name": "AURA",
+                    "schema": "json",
+                    "command_topic": format!("{}/aura/set", self.topic),
+                    // The controllers cannot be read back
+                    "optimistic": true,
+                    "supported_color_modes": ["rgb"],
+                    "effect": true,
+                    "effect_list": effects,
+                }),
+            );
+        }
+    }
+
+    fn announce_sensor(&self, object_id: &str, sensor: &UnifiedSensor) {
+        let (unit, device_class) = match sensor.kind {
+            SensorKind::Temperature => ("°C", Some("temperature")),
+            SensorKind::Fan => ("RPM", None),
+            SensorKind::FanPercent => ("%", None),
+            SensorKind::Current => ("A", Some("current")),
+            SensorKind::Flow => ("L/h", None),
+        };
+        let mut config = json!({
+            "name": sensor.name,
+            "state_topic": format!("{}/sensors", self.topic),
+            "value_template": format!("{{{{ value_json.{object_id} }}}}"),
+            "unit_of_measurement": unit,
+            "state_class": "measurement",
+        });
+        if let Some(class) = device_class {
+            config["device_class"] = json!(class);
+        }
+        self.announce("sensor", object_id, config);
+    }
+
+    /// Publish a retained discovery config for one entity, adding the
+    /// fields common to all entities.
+    fn announce(&self, component: &str, object_id: &str, mut config: Value) {
+        config["unique_id"] = json!(format!("nocrate_{}_{object_id}", self.node));
+        config["availability_topic"] = json!(format!("{}/status", self.topic));
+        config["device"] = self.device.clone();
+        let topic = format!(
+            "{}/{component}/nocrate_{}/{object_id}/config",
+            self.cfg.discovery_prefix, self.node
+        );
+        self.publish_raw(&topic, config.to_string(), true);
+    }
+
+    /// Publish all readings, announcing sensors seen for the first time.
+    fn publish_state(&mut self, state: &AppState) {
+        let snapshot = sensors::collect(state);
+        let mut values = Map::new();
+        for sensor in snapshot.sensors.iter().filter(|s| s.duplicate_of.is_none()) {
+            let object_id = sanitize(&sensor.id);
+            if self.announced.insert(object_id.clone()) {
+                self.announce_sensor(&object_id, sensor);
+            }
+            let value = (f64::from(sensor.value) * 10.0).round() / 10.0;
+            let _ = values.insert(object_id, json!(value));
+        }
+        self.publish("sensors", Value::Object(values).to_string(), false);
+
+        let profile = state
+            .wmi
+            .get()
+            .and_then(|wmi| wmi.execute(asus_mgmt::get_thermal_profile).ok());
+        if let Some(profile) = profile {
+            self.publish("profile", profile.as_str(), true);
+        }
+    }
+
+    fn on_command(&mut self, app: &AppHandle, state: &AppState, topic: &str, payload: &str) {
+        let Some(command) = topic
+            .strip_prefix(&self.topic)
+            .and_then(|t| t.strip_prefix('/'))
+        else {
+            return;
+        };
+        match command {
+            "profile/set" => {
+                let name = payload.trim();
+                match ThermalProfile::ALL.into_iter().find(|p| p.as_str() == name) {
+                    Some(profile) => self.set_profile(app, state, profile),
+                    None => eprintln!("MQTT: unknown thermal profile \"{name}\""),
+                }
+            }
+            "aura/set" => match serde_json::from_str::<LightCommand>(payload) {
+                Ok(cmd) => {
+                    if let Err(e) = self.set_light(state, &cmd) {
+                        eprintln!("MQTT: AURA command failed: {e}");
+                    }
+                }
+                Err(e) => eprintln!("MQTT: invalid AURA command: {e}"),
+            },
+            _ => {}
+        }
+    }
+
+    fn set_profile(&self, app: &AppHandle, state: &AppState, profile: ThermalProfile) {
+        match switch_thermal_profile(app, state, profile) {
+            Ok(()) => self.publish("profile", profile.as_str(), true),
+            Err(e) => eprintln!("MQTT: failed to switch thermal profile: {e}"),
+        }
+    }
+
+    /// Apply a light command to every channel of every AURA controller.
+    fn set_light(&mut self, state: &AppState, cmd: &LightCommand) -> Result<(), String> {
+        if let Some(color) = cmd.color {
+            self.color = color;
+        }
+        if let Some(effect) = cmd.effect.filter(|e| *e != AuraEffect::Off) {
+            self.effect = effect;
+        }
+        let on = !cmd.state.eq_ignore_ascii_case("off");
+        let (effect, color) = (self.effect, self.color);
+
+        stop_direct_output(state);
+        let count = state.aura.lock().len();
+        for device in 0..count {
+            with_aura(state, Some(device), |ctrl| {
+                for channel in ctrl.channels() {
+                    if on {
+                        ctrl.set_effect(
+                            channel.index,
+                            effect,
+                            color,
+                            AuraSpeed::Medium,
+                            AuraDirection::default(),
+                        )?;
+                    } else {
+                        ctrl.turn_off(channel.index)?;
+                    }
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Publish under this device's topic.
+    fn publish(&self, subtopic: &str, payload: impl Into<Vec<u8>>, retain: bool) {
+        self.publish_raw(&format!("{}/{subtopic}", self.topic), payload, retain);
+    }
+
+    fn publish_raw(&self, topic: &str, payload: impl Into<Vec<u8>>, retain: bool) {
+        if let Err(e) = self
+            .client
+            .try_publish(topic, QoS::AtLeastOnce, retain, payload)
+        {
+            eprintln!("MQTT: dropped message for {topic}: {e}");
+        }
+    }
+}
+
+/// Node ID from the computer name, so several machines can share a
+/// broker with the default base topic.
+fn node_id() -> String {
+    std::env::var("COMPUTERNAME").map_or_else(|_| "pc".into(), |name| sanitize(&name))
+}
+
+/// Lowercase ASCII letters and digits, everything else `_`; valid in
+/// topics, entity IDs and Jinja attribute names.
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,216 @@
+/// Always-on-top sensor overlay window.
+///
+/// A small frameless, transparent window that ignores the mouse, showing
+/// live temperatures and fan speeds on top of other apps. It loads the
+/// `/overlay` route of the frontend and is fed by a backend thread that
+/// emits sensor readings to it, so it does not poll on its own. Only the
+/// readings that changed by more than the configured epsilon are sent,
+/// with a full list when the shown sensors change and every few updates
+/// to resync. The thread also keeps the window and its global shortcut in
+/// line with the config, which may change from the settings page or on
+/// disk.
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+use crate::config::OverlayConfig;
+use crate::sensors::{self, SensorKind, UnifiedSensor};
+use crate::state::AppState;
+
+/// Window label of the overlay.
+pub const OVERLAY_LABEL: &str = "overlay";
+
+/// Event emitted to the overlay window with an [`OverlayUpdate`].
+pub const OVERLAY_SENSORS_EVENT: &str = "overlay-sensors";
+
+/// Interval between sensor updates and config checks.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A full list is sent at least every this many updates, so a window
+/// whose listener missed the first one catches up.
+const FULL_UPDATE_EVERY: u32 = 10;
+
+/// Sensors sent to the overlay.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayUpdate {
+    /// `sensors` is the whole list in display order; otherwise only the
+    /// sensors that changed, to be merged by ID.
+    pub full: bool,
+    pub sensors: Vec<UnifiedSensor>,
+}
+
+/// What the overlay last received, to send only changes.
+#[derive(Default)]
+struct Sent {
+    /// Shown IDs in order; a change forces a full update.
+    ids: Vec<String>,
+    /// Last value sent per ID.
+    values: HashMap<String, f32>,
+    /// Delta updates since the last full one.
+    since_full: u32,
+}
+
+impl Sent {
+    /// The update to emit for `shown`, or `None` if nothing changed.
+    fn update(&mut self, shown: Vec<UnifiedSensor>, epsilon: f32) -> Option<OverlayUpdate> {
+        let ids_changed =
+            shown.len() != self.ids.len() || shown.iter().zip(&self.ids).any(|(s, id)| &s.id != id);
+        let full = ids_changed || self.since_full >= FULL_UPDATE_EVERY;
+
+        let sensors: Vec<UnifiedSensor> = if full {
+            self.ids = shown.iter().map(|s| s.id.clone()).collect();
+            self.values.clear();
+            self.since_full = 0;
+            shown
+        } else {
+            self.since_full += 1;
+            shown
+                .into_iter()
+                .filter(|s| {
+                    self.values
+                        .get(&s.id)
+                        .is_none_or(|&last| (s.value - last).abs() > epsilon)
+                })
+                .collect()
+        };
+        for s in &sensors {
+            let _ = self.values.insert(s.id.clone(), s.value);
+        }
+        (full || !sensors.is_empty()).then_some(OverlayUpdate { full, sensors })
+    }
+}
+
+const WIDTH: f64 = 240.0;
+const HEIGHT: f64 = 200.0;
+
+/// Spawn the overlay thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-overlay".into())
+        .spawn(move || update_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Overlay: failed to spawn update thread: {e}");
+    }
+}
+
+/// Show the overlay if hidden, hide it otherwise, and remember the choice.
+///
+/// Returns whether the overlay is now visible.
+pub fn toggle(app: &AppHandle) -> bool {
+    let visible = !is_open(app);
+    if let Some(state) = app.try_state::<AppState>() {
+        match state.config.update(|cfg| cfg.overlay.visible = visible) {
+            Ok(cfg) => set_visible(app, &cfg.overlay, visible),
+            Err(e) => eprintln!("Overlay: failed to save visibility: {e}"),
+        }
+    }
+    visible
+}
+
+fn is_open(app: &AppHandle) -> bool {
+    app.get_webview_window(OVERLAY_LABEL).is_some()
+}
+
+/// Create or destroy the overlay window.
+fn set_visible(app: &AppHandle, cfg: &OverlayConfig, visible: bool) {
+    let window = app.get_webview_window(OVERLAY_LABEL);
+    match (window, visible) {
+        (None, true) => {
+            if let Err(e) = create(app, cfg) {
+                eprintln!("Overlay: failed to create window: {e}");
+            }
+        }
+        (Some(window), false) => {
+            // Destroy instead of close: the close-to-tray handler would
+            // only hide it
+            let _ = window.destroy();
+        }
+        _ => {}
+    }
+}
+
+fn create(app: &AppHandle, cfg: &OverlayConfig) -> tauri::Result<()> {
+    let window = WebviewWindowBuilder::new(app, OVERLAY_LABEL, WebviewUrl::App("overlay".into()))
+        .title("NoCrate Overlay")
+        .inner_size(WIDTH, HEIGHT)
+        .position(f64::from(cfg.x), f64::from(cfg.y))
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .focused(false)
+        .build()?;
+    // Clicks pass through to the window below
+    window.set_ignore_cursor_events(true)
+}
+
+fn update_loop(app: &AppHandle) {
+    // Shortcut currently registered for the overlay
+    let mut hotkey: Option<String> = None;
+    let mut sent = Sent::default();
+
+    loop {
+        if let Some(state) = app.try_state::<AppState>() {
+            let cfg = state.config.get().overlay;
+            if cfg.visible != is_open(app) {
+                set_visible(app, &cfg, cfg.visible);
+            }
+            if cfg.hotkey != hotkey {
+                register_hotkey(app, hotkey.as_deref(), cfg.hotkey.as_deref());
+                hotkey.clone_from(&cfg.hotkey);
+            }
+            if is_open(app) {
+                let shown = select(sensors::collect(&state).sensors, &cfg.sensors);
+                if let Some(update) = sent.update(shown, cfg.update_epsilon) {
+                    let _ = app.emit_to(OVERLAY_LABEL, OVERLAY_SENSORS_EVENT, update);
+                }
+            } else {
+                // A reopened window starts empty
+                sent = Sent::default();
+            }
+        }
+        thread::sleep(UPDATE_INTERVAL);
+    }
+}
+
+/// Replace the overlay shortcut `old` with `new`.
+fn register_hotkey(app: &AppHandle, old: Option<&str>, new: Option<&str>) {
+    let shortcuts = app.global_shortcut();
+    if let Some(old) = old {
+        let _ = shortcuts.unregister(old);
+    }
+    let Some(new) = new.filter(|s| !s.is_empty()) else {
+        return;
+    };
+    let registered = shortcuts.on_shortcut(new, |app, _shortcut, event| {
+        if event.state() == ShortcutState::Pressed {
+            let _ = toggle(app);
+        }
+    });
+    match registered {
+        Ok(()) => eprintln!("Overlay: toggle shortcut {new}"),
+        Err(e) => eprintln!("Overlay: failed to register shortcut {new}: {e}"),
+    }
+}
+
+/// Sensors listed in `ids` in that order, or all temperatures and fans
+/// (without duplicates) when `ids` is empty.
+fn select(sensors: Vec<UnifiedSensor>, ids: &[String]) -> Vec<UnifiedSensor> {
+    if ids.is_empty() {
+        return sensors
+            .into_iter()
+            .filter(|s| s.duplicate_of.is_none())
+            .filter(|s| matches!(s.kind, SensorKind::Temperature | SensorKind::Fan))
+            .collect();
+    }
+    ids.iter()
+        .filter_map(|id| sensors.iter().find(|s| &s.id == id).cloned())
+        .collect()
+}
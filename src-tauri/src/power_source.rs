@@ -0,0 +1,113 @@
+/// Switching of laptop thermal settings between AC and battery.
+///
+/// Not every model sends the ATK plug / unplug events, so a background
+/// thread polls the Windows power status instead. Every change is emitted
+/// to the frontend; with auto-switching enabled, the thermal profile
+/// configured for the new source is applied together with that source's
+/// power limits and fan curves.
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+use crate::atk_events::THERMAL_PROFILE_CHANGED_EVENT;
+use crate::commands::fan::apply_thermal_profile;
+use crate::state::AppState;
+use crate::wmi::asus_mgmt;
+
+/// Interval between power status checks.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// `ACLineStatus` of a machine running on battery.
+const AC_LINE_OFFLINE: u8 = 0;
+
+/// Event emitted to the frontend with the new [`PowerSource`] after the
+/// machine was plugged in or unplugged.
+pub const POWER_SOURCE_CHANGED_EVENT: &str = "power-source-changed";
+
+/// Where the machine draws its power from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+impl PowerSource {
+    /// Current power source. Machines without a battery, and any status
+    /// Windows cannot report, count as AC.
+    #[must_use]
+    pub fn current() -> Self {
+        if ac_line_status() == Some(AC_LINE_OFFLINE) {
+            Self::Battery
+        } else {
+            Self::Ac
+        }
+    }
+}
+
+/// `ACLineStatus` reported by Windows, if the call succeeds.
+#[allow(unsafe_code)]
+fn ac_line_status() -> Option<u8> {
+    let mut status = SYSTEM_POWER_STATUS::default();
+    // SAFETY: `status` is a valid, writable SYSTEM_POWER_STATUS
+    unsafe { GetSystemPowerStatus(&raw mut status) }.ok()?;
+    Some(status.ACLineStatus)
+}
+
+/// Spawn the power source thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-power-source".into())
+        .spawn(move || watch_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Power: failed to spawn watch thread: {e}");
+    }
+}
+
+fn watch_loop(app: &AppHandle) {
+    let mut last = PowerSource::current();
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let source = PowerSource::current();
+        if source == last {
+            continue;
+        }
+        last = source;
+        eprintln!("Power: now on {source:?}");
+        let _ = app.emit(POWER_SOURCE_CHANGED_EVENT, source);
+        if let Some(state) = app.try_state::<AppState>() {
+            switch_settings(app, &state, source);
+        }
+    }
+}
+
+/// Apply the thermal profile, power limits and fan curves of `source`.
+fn switch_settings(app: &AppHandle, state: &AppState, source: PowerSource) {
+    let config = state.config.get();
+    if !config.power_source.auto_switch {
+        return;
+    }
+    let Some(wmi) = state.wmi.get() else {
+        return;
+    };
+
+    let target = config.power_source.profile_for(source);
+    let switched = wmi.execute(move |conn| {
+        let profile = match target {
+            Some(p) => p,
+            None => asus_mgmt::get_thermal_profile(conn)?,
+        };
+        apply_thermal_profile(conn, &config, profile)?;
+        Ok(profile)
+    });
+    match switched {
+        Ok(profile) => {
+            let _ = app.emit(THERMAL_PROFILE_CHANGED_EVENT, profile);
+        }
+        Err(e) => eprintln!("Power: failed to apply {source:?} settings: {e}"),
+    }
+}
@@ -0,0 +1,108 @@
+/// Re-application of the last used settings at startup.
+///
+/// The thermal profile, desktop fan policies and AURA effect last set
+/// from the app are saved in the config. Once every subsystem is up, a
+/// background thread writes them back, unless restoring is disabled. With
+/// power source switching enabled, the profile configured for the current
+/// source wins over the last selected one.
+use std::thread;
+
+use serde::de::DeserializeOwned;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::atk_events::THERMAL_PROFILE_CHANGED_EVENT;
+use crate::aura::protocol::{AuraDirection, AuraEffect, AuraSpeed, RgbColor};
+use crate::commands::aura::with_aura;
+use crate::commands::fan::apply_thermal_profile;
+use crate::config::AppConfig;
+use crate::power_source::PowerSource;
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, ThermalProfile};
+
+/// Spawn the thread restoring the saved settings.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-restore".into())
+        .spawn(move || restore(&app));
+    if let Err(e) = spawned {
+        eprintln!("Restore: failed to spawn thread: {e}");
+    }
+}
+
+fn restore(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let config = state.config.get();
+    if !config.restore_on_startup {
+        return;
+    }
+    restore_thermal_profile(app, &state, &config);
+    restore_fan_policies(&state, &config);
+    restore_aura(&state, &config);
+}
+
+fn restore_thermal_profile(app: &AppHandle, state: &AppState, config: &AppConfig) {
+    let Some(wmi) = state.wmi.get() else {
+        return;
+    };
+    let by_source = if config.power_source.auto_switch {
+        config.power_source.profile_for(PowerSource::current())
+    } else {
+        None
+    };
+    let Some(profile) =
+        by_source.or_else(|| ThermalProfile::from_raw(config.last_thermal_profile.into()))
+    else {
+        return;
+    };
+
+    let snapshot = config.clone();
+    match wmi.execute(move |conn| apply_thermal_profile(conn, &snapshot, profile)) {
+        Ok(()) => {
+            eprintln!("Restore: thermal profile {}", profile.as_str());
+            let _ = app.emit(THERMAL_PROFILE_CHANGED_EVENT, profile);
+        }
+        Err(e) => eprintln!("Restore: failed to apply thermal profile: {e}"),
+    }
+}
+
+fn restore_fan_policies(state: &AppState, config: &AppConfig) {
+    let Some(wmi) = state.wmi.get() else {
+        return;
+    };
+    for policy in config.desktop_fan_policies.values().cloned() {
+        let fan_type = policy.fan_type;
+        if let Err(e) = wmi.execute(move |conn| asus_mgmt::set_desktop_fan_policy(conn, &policy)) {
+            eprintln!("Restore: failed to apply fan policy of header {fan_type}: {e}");
+        }
+    }
+}
+
+fn restore_aura(state: &AppState, config: &AppConfig) {
+    if state.aura.lock().is_empty() {
+        return;
+    }
+    let Some(effect) = parse_name::<AuraEffect>(&config.last_aura_effect) else {
+        eprintln!("Restore: unknown AURA effect {:?}", config.last_aura_effect);
+        return;
+    };
+    let color = RgbColor::from_hex(&config.last_aura_color).unwrap_or(RgbColor::WHITE);
+    let speed = parse_name::<AuraSpeed>(&config.last_aura_speed).unwrap_or(AuraSpeed::Medium);
+
+    let applied = with_aura(state, None, |ctrl| {
+        if effect == AuraEffect::Off {
+            ctrl.turn_off(0)
+        } else {
+            ctrl.set_effect(0, effect, color, speed, AuraDirection::default())
+        }
+    });
+    if let Err(e) = applied {
+        eprintln!("Restore: failed to apply AURA effect: {e}");
+    }
+}
+
+/// Parse a name saved as the serialized form of an enum, e.g. `"rainbow"`.
+fn parse_name<T: DeserializeOwned>(name: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(name.to_owned())).ok()
+}
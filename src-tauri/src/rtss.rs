@@ -0,0 +1,278 @@
+/// RivaTuner Statistics Server on-screen display output.
+///
+/// RTSS shows text from OSD slots in its `RTSSSharedMemoryV2` mapping in
+/// its in-game overlay. A background thread renders the configured items
+/// once per interval and writes them to a slot owned by NoCrate, claiming
+/// a free slot on first use and releasing it when the output is disabled.
+/// The layout and slot protocol follow `RTSSSharedMemory.h` from the RTSS
+/// SDK. RTSS is optional: while it is not running, updates are skipped.
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::CloseHandle;
+use windows::Win32::System::Memory::{
+    MapViewOfFile, OpenFileMappingW, UnmapViewOfFile, FILE_MAP_ALL_ACCESS,
+};
+
+use crate::config::RtssItem;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::sensors::{self, SensorKind, UnifiedSensor};
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, ThermalProfile};
+
+/// Interval between overlay updates.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+const MAPPING_NAME: &str = "RTSSSharedMemoryV2";
+
+/// Owner name written to the claimed OSD slot.
+const OWNER: &[u8] = b"NoCrate";
+
+/// `dwSignature` value `'RTSS'`.
+const SIGNATURE: u32 = 0x5254_5353;
+
+/// Oldest layout with OSD slots.
+const MIN_VERSION: u32 = 0x0002_0000;
+/// Version adding the 4 KiB `szOSDEx` text field.
+const OSD_EX_VERSION: u32 = 0x0002_0007;
+/// Version adding the `dwBusy` lock.
+const BUSY_VERSION: u32 = 0x0002_000E;
+
+/// Header field offsets.
+const OSD_ENTRY_SIZE: usize = 20;
+const OSD_ARR_OFFSET: usize = 24;
+const OSD_ARR_SIZE: usize = 28;
+const OSD_FRAME: usize = 32;
+const BUSY: usize = 36;
+
+/// OSD slot field offsets and lengths.
+const OSD_TEXT: usize = 0;
+const OSD_TEXT_LEN: usize = 256;
+const OSD_OWNER: usize = 256;
+const OSD_OWNER_LEN: usize = 256;
+const OSD_TEXT_EX: usize = 512;
+const OSD_TEXT_EX_LEN: usize = 4096;
+
+/// Spawn the overlay update thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-rtss".into())
+        .spawn(move || update_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("RTSS: failed to spawn overlay thread: {e}");
+    }
+}
+
+/// Clear NoCrate's OSD slot, e.g. before exiting.
+pub fn release() {
+    let _ = write_osd(None);
+}
+
+fn update_loop(app: &AppHandle) {
+    // Whether the last update reached RTSS, to log only state changes
+    let mut shown = false;
+
+    loop {
+        if let Some(state) = app.try_state::<AppState>() {
+            let cfg = state.config.get().rtss;
+            if cfg.enabled && !cfg.items.is_empty() {
+                let text = render(&state, &cfg.items);
+                match write_osd(Some(&text)) {
+                    Ok(()) if !shown => {
+                        eprintln!("RTSS: publishing overlay");
+                        shown = true;
+                    }
+                    Err(e) if shown => {
+                        eprintln!("RTSS: overlay unavailable: {e}");
+                        shown = false;
+                    }
+                    _ => {}
+                }
+            } else if shown {
+                release();
+                shown = false;
+            }
+        }
+        thread::sleep(UPDATE_INTERVAL);
+    }
+}
+
+/// Render the overlay text, one `label: value` line per item. Items whose
+/// value is unavailable are left out.
+fn render(state: &AppState, items: &[RtssItem]) -> String {
+    let wants_sensors = items
+        .iter()
+        .any(|item| matches!(item, RtssItem::Sensor { .. }));
+    let readings = if wants_sensors {
+        sensors::collect(state).sensors
+    } else {
+        Vec::new()
+    };
+
+    let mut lines = Vec::new();
+    for item in items {
+        match item {
+            RtssItem::Sensor { id, label } => {
+                if let Some(sensor) = readings.iter().find(|s| &s.id == id) {
+                    let label = label.as_deref().unwrap_or(&sensor.name);
+                    lines.push(format!("{label}: {}", format_value(sensor)));
+                }
+            }
+            RtssItem::ThermalProfile => {
+                let profile = state
+                    .wmi
+                    .get()
+                    .and_then(|wmi| wmi.execute(asus_mgmt::get_thermal_profile).ok());
+                if let Some(profile) = profile {
+                    lines.push(format!("Profile: {}", profile_name(profile)));
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn format_value(sensor: &UnifiedSensor) -> String {
+    let value = sensor.value;
+    match sensor.kind {
+        SensorKind::Temperature => format!("{:.0}{}", sensor.display_value, sensor.display_unit),
+        SensorKind::Fan => format!("{value:.0} RPM"),
+        SensorKind::FanPercent => format!("{value:.0}%"),
+        SensorKind::Current => format!("{value:.1} A"),
+        SensorKind::Flow => format!("{value:.0} L/h"),
+    }
+}
+
+const fn profile_name(profile: ThermalProfile) -> &'static str {
+    match profile {
+        ThermalProfile::Standard => "Standard",
+        ThermalProfile::Performance => "Performance",
+        ThermalProfile::Silent => "Silent",
+    }
+}
+
+/// Write `text` to NoCrate's OSD slot, or release the slot for `None`.
+#[allow(unsafe_code)]
+fn write_osd(text: Option<&str>) -> Result<()> {
+    let name: Vec<u16> = MAPPING_NAME.encode_utf16().chain(Some(0)).collect();
+    unsafe {
+        let mapping = OpenFileMappingW(FILE_MAP_ALL_ACCESS.0, false, PCWSTR(name.as_ptr()))
+            .map_err(|_| NoCrateError::Unknown(i18n::t(Msg::RtssNotRunning).into()))?;
+        let view = MapViewOfFile(mapping, FILE_MAP_ALL_ACCESS, 0, 0, 0);
+        if view.Value.is_null() {
+            let _ = CloseHandle(mapping);
+            return Err(NoCrateError::Unknown(
+                "Failed to map RTSS shared memory".into(),
+            ));
+        }
+        let result = update_slot(view.Value.cast::<u8>(), text);
+        let _ = UnmapViewOfFile(view);
+        let _ = CloseHandle(mapping);
+        result
+    }
+}
+
+/// Update the OSD slots of a mapped RTSS view.
+///
+/// # Safety
+///
+/// `base` must point to a mapped `RTSSSharedMemoryV2` view.
+#[allow(unsafe_code)]
+unsafe fn update_slot(base: *mut u8, text: Option<&str>) -> Result<()> {
+    let field =
+        |offset: usize| u32::from_le_bytes(unsafe { base.add(offset).cast::<[u8; 4]>().read() });
+    let version = field(4);
+    if field(0) != SIGNATURE || version < MIN_VERSION {
+        return Err(NoCrateError::Unknown(
+            "Unsupported RTSS shared memory layout".into(),
+        ));
+    }
+    let entry_size = field(OSD_ENTRY_SIZE) as usize;
+    let text_ex = version >= OSD_EX_VERSION;
+    let needed = if text_ex {
+        OSD_TEXT_EX + OSD_TEXT_EX_LEN
+    } else {
+        OSD_OWNER + OSD_OWNER_LEN
+    };
+    if entry_size < needed {
+        return Err(NoCrateError::Unknown(
+            "Unsupported RTSS shared memory layout".into(),
+        ));
+    }
+
+    // Newer RTSS versions guard the slots with a spin flag; skip this
+    // update instead of waiting when RTSS holds it. The view is page
+    // aligned, so the flag is 4-byte aligned
+    #[allow(clippy::cast_ptr_alignment)]
+    let busy = (version >= BUSY_VERSION)
+        .then(|| unsafe { AtomicU32::from_ptr(base.add(BUSY).cast::<u32>()) });
+    if busy.is_some_and(|b| b.fetch_or(1, Ordering::AcqRel) & 1 != 0) {
+        return Ok(());
+    }
+
+    // Slot 0 belongs to RTSS itself
+    let slots = (1..field(OSD_ARR_SIZE) as usize).map(|i| unsafe {
+        std::slice::from_raw_parts_mut(
+            base.add(field(OSD_ARR_OFFSET) as usize + i * entry_size),
+            entry_size,
+        )
+    });
+
+    if let Some(text) = text {
+        let bytes = encode(text);
+        let mut slots: Vec<&mut [u8]> = slots.collect();
+        let slot = slots
+            .iter()
+            .position(|s| owner(s) == OWNER)
+            .or_else(|| slots.iter().position(|s| owner(s).is_empty()));
+        if let Some(slot) = slot.map(|i| &mut *slots[i]) {
+            put_str(&mut slot[OSD_OWNER..OSD_OWNER + OSD_OWNER_LEN], OWNER);
+            if text_ex {
+                put_str(
+                    &mut slot[OSD_TEXT_EX..OSD_TEXT_EX + OSD_TEXT_EX_LEN],
+                    &bytes,
+                );
+            } else {
+                put_str(&mut slot[OSD_TEXT..OSD_TEXT + OSD_TEXT_LEN], &bytes);
+            }
+            // RTSS redraws when the frame counter changes
+            let frame = field(OSD_FRAME).wrapping_add(1).to_le_bytes();
+            unsafe { base.add(OSD_FRAME).cast::<[u8; 4]>().write(frame) };
+        }
+    } else {
+        for slot in slots.filter(|s| owner(s) == OWNER) {
+            slot[..needed].fill(0);
+        }
+    }
+
+    if let Some(b) = busy {
+        let _ = b.fetch_and(!1, Ordering::AcqRel);
+    }
+    Ok(())
+}
+
+/// Owner name of a slot, without the terminating NUL.
+fn owner(slot: &[u8]) -> &[u8] {
+    let field = &slot[OSD_OWNER..OSD_OWNER + OSD_OWNER_LEN];
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    &field[..end]
+}
+
+/// Copy `src` into a NUL-terminated fixed-size field, truncating if needed.
+fn put_str(field: &mut [u8], src: &[u8]) {
+    let len = src.len().min(field.len() - 1);
+    field[..len].copy_from_slice(&src[..len]);
+    field[len..].fill(0);
+}
+
+/// Encode as the ANSI (Latin-1) text RTSS expects; other characters,
+/// e.g. Chinese sensor names, are replaced with `?`.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect()
+}
@@ -0,0 +1,49 @@
+/// Background sampling for the sensor history.
+///
+/// The history is recorded whenever the sensors are aggregated. This
+/// thread aggregates them once a second when nothing else has, so charts
+/// have no gaps while the window is hidden and no other feature polls.
+/// With eco polling enabled, it samples only once per eco interval while
+/// the window is hidden, or not at all.
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+
+use crate::eco;
+use crate::sensors;
+use crate::state::AppState;
+
+/// Interval between checks.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Spawn the sampling thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-sensor-history".into())
+        .spawn(move || sample_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Sensor history: failed to spawn sampling thread: {e}");
+    }
+}
+
+fn sample_loop(app: &AppHandle) {
+    let mut last_sample: Option<Instant> = None;
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        let cfg = state.config.get().eco;
+        if cfg.enabled && eco::window_hidden(app) {
+            let interval = Duration::from_secs(cfg.interval_secs);
+            if cfg.pause_history || last_sample.is_some_and(|t| t.elapsed() < interval) {
+                continue;
+            }
+        }
+        if state.sensor_history.due() {
+            last_sample = Some(Instant::now());
+            let _ = sensors::collect(&state);
+        }
+    }
+}
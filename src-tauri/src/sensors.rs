@@ -0,0 +1,80 @@
+// 统一传感器聚合层（实现见 `nocrate_core::sensors`）
+// 此处从应用状态与配置组装来源。
+
+pub use nocrate_core::sensors::*;
+
+use std::time::Duration;
+
+use nocrate_core::sensors::polling::PollingTiers;
+
+use crate::state::AppState;
+
+/// 从所有可用来源读取并合并传感器（已应用滑动平均）
+pub fn collect(state: &AppState) -> UnifiedSensorSnapshot {
+    collect_with(state, false)
+}
+
+/// 同 [`collect`]，`unsmoothed` 为 true 时返回未经平滑的值
+pub fn collect_with(state: &AppState, unsmoothed: bool) -> UnifiedSensorSnapshot {
+    read(state, unsmoothed, None)
+}
+
+/// 只读取 `only` 中的来源（已应用校准，未经平滑）
+///
+/// 供每秒读取少数传感器的后台任务使用：不经过轮询缓存，
+/// 也不计入平滑、统计与历史，以免不完整的快照影响完整读取。
+/// 温度保护与温度灯效依赖这里的值未经平滑：平滑窗口较大时会推迟其反应。
+pub fn collect_only(state: &AppState, only: &[SensorSource]) -> UnifiedSensorSnapshot {
+    read(state, true, Some(only))
+}
+
+fn read(
+    state: &AppState,
+    unsmoothed: bool,
+    only: Option<&[SensorSource]>,
+) -> UnifiedSensorSnapshot {
+    let config = state.config.get();
+    let wmi = state.wmi.get();
+    #[cfg(feature = "sio")]
+    let (sio, ec, cpu) = (state.sio.get(), state.ec.get(), state.cpu.get());
+    let wants = |source: SensorSource| only.is_none_or(|o| o.contains(&source));
+    let full = only.is_none();
+    nocrate_core::sensors::collect(&Sources {
+        // LHM 与 ASUSHW 共用 WMI 线程
+        wmi: wmi
+            .as_ref()
+            .filter(|_| wants(SensorSource::AsusHw) || wants(SensorSource::Lhm)),
+        asushw: Some(&state.asushw),
+        asushw_interval: Duration::from_millis(config.fan_poll_interval_ms),
+        gpu: state.gpu.as_ref().filter(|_| wants(SensorSource::Gpu)),
+        storage: state
+            .storage
+            .as_ref()
+            .filter(|_| wants(SensorSource::Storage)),
+        #[cfg(feature = "sio")]
+        sio: sio.as_deref().filter(|_| wants(SensorSource::Sio)),
+        #[cfg(feature = "sio")]
+        ec: ec.as_deref().filter(|_| wants(SensorSource::Ec)),
+        #[cfg(feature = "sio")]
+        cpu: cpu.as_deref().filter(|_| wants(SensorSource::Cpu)),
+        lhm_sensors: config.lhm.sensors,
+        hwinfo_enabled: config.hwinfo_enabled && wants(SensorSource::Hwinfo),
+        calibration: config.sensor_calibration,
+        smoothing: config.sensor_smoothing,
+        smoother: full.then_some(&state.sensor_smoother),
+        unsmoothed,
+        polling: if state.eco.is_active() {
+            // 窗口隐藏且无后台功能需要实时读数：全部传感器按 eco 间隔读取
+            PollingTiers {
+                slow_interval_secs: config.eco.interval_secs,
+                slow: vec![String::new()],
+            }
+        } else {
+            config.sensor_polling
+        },
+        poller: full.then_some(&state.sensor_poller),
+        stats: full.then_some(&state.sensor_stats),
+        history: full.then_some(&state.sensor_history),
+        units: config.units,
+    })
+}
@@ -0,0 +1,1082 @@
+/// Optional Windows service that owns the privileged hardware access.
+///
+/// `nocrate-service.exe` runs as `LocalSystem` and serves
+/// `\\.\pipe\nocrate-service`. It keeps its own WMI connection and, with
+/// the `sio` feature, the `WinRing0` driver, and makes the calls the app
+/// forwards to it, so the app itself can run without administrator
+/// rights. A client keeps one connection open, writes one JSON
+/// [`ServiceRequest`] per line and reads back one JSON
+/// `Result<ServiceReply, WireError>` per line.
+///
+/// The pipe is restricted to SYSTEM, administrators and the user the
+/// service was installed for (`--allow <SID>` on its command line): that
+/// user gets the hardware access an elevated `NoCrate` has. WMI methods are
+/// only run on the detected ASUS instance; port I/O is limited to the
+/// ports, PCI registers and MSRs the app uses (see [`PortAllowlist`]).
+///
+/// The app uses the service when it is running and the app is not
+/// elevated. Over the service there is no ATK event subscription and no
+/// `LibreHardwareMonitor` namespace; both still work when the app runs
+/// as administrator.
+use std::env;
+use std::ffi::c_void;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::FromRawHandle;
+#[cfg(feature = "sio")]
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use windows::core::{w, PCWSTR, PWSTR};
+use windows::Win32::Foundation::{
+    CloseHandle, LocalFree, ERROR_CALL_NOT_IMPLEMENTED, ERROR_CANCELLED,
+    ERROR_FAILED_SERVICE_CONTROLLER_CONNECT, ERROR_FILE_NOT_FOUND, ERROR_PIPE_BUSY,
+    ERROR_PIPE_CONNECTED, ERROR_SERVICE_ALREADY_RUNNING, ERROR_SERVICE_DOES_NOT_EXIST,
+    ERROR_SERVICE_EXISTS, GENERIC_READ, GENERIC_WRITE, HANDLE, HLOCAL, INVALID_HANDLE_VALUE,
+    NO_ERROR,
+};
+use windows::Win32::Security::Authorization::{
+    ConvertSidToStringSidW, ConvertStringSecurityDescriptorToSecurityDescriptorW, SDDL_REVISION_1,
+};
+use windows::Win32::Security::{
+    GetTokenInformation, TokenUser, PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES, TOKEN_QUERY,
+    TOKEN_USER,
+};
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_FLAG_FIRST_PIPE_INSTANCE, FILE_SHARE_NONE,
+    OPEN_EXISTING, PIPE_ACCESS_DUPLEX,
+};
+use windows::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, WaitNamedPipeW, PIPE_READMODE_BYTE,
+    PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+};
+use windows::Win32::System::Services::{
+    ChangeServiceConfigW, CloseServiceHandle, ControlService, CreateServiceW, DeleteService,
+    OpenSCManagerW, OpenServiceW, QueryServiceStatus, RegisterServiceCtrlHandlerExW,
+    SetServiceStatus, StartServiceCtrlDispatcherW, StartServiceW, ENUM_SERVICE_TYPE, SC_HANDLE,
+    SC_MANAGER_CONNECT, SC_MANAGER_CREATE_SERVICE, SERVICE_ACCEPT_SHUTDOWN, SERVICE_ACCEPT_STOP,
+    SERVICE_ALL_ACCESS, SERVICE_AUTO_START, SERVICE_CONTROL_INTERROGATE, SERVICE_CONTROL_SHUTDOWN,
+    SERVICE_CONTROL_STOP, SERVICE_ERROR_NORMAL, SERVICE_NO_CHANGE, SERVICE_QUERY_STATUS,
+    SERVICE_RUNNING, SERVICE_START_PENDING, SERVICE_STATUS, SERVICE_STATUS_CURRENT_STATE,
+    SERVICE_STATUS_HANDLE, SERVICE_STOPPED, SERVICE_STOP_PENDING, SERVICE_TABLE_ENTRYW,
+    SERVICE_WIN32_OWN_PROCESS,
+};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetExitCodeProcess, OpenProcessToken, WaitForSingleObject, INFINITE,
+};
+use windows::Win32::UI::Shell::{ShellExecuteExW, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW};
+use windows::Win32::UI::WindowsAndMessaging::SW_HIDE;
+
+use crate::commands::system::is_admin;
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+#[cfg(feature = "sio")]
+use crate::sio::driver::{DriverHandle, PortForwarder, PortOp};
+#[cfg(feature = "sio")]
+use crate::sio::policy::PortAllowlist;
+use crate::state::WmiThread;
+use crate::wmi::connection::{AsusWmi, AsusWmiBackend, MethodOutput, WmiParam};
+use crate::wmi::trace::{self, WmiRequest, WmiResponse};
+
+const SERVICE_NAME: PCWSTR = w!("NoCrateService");
+
+const DISPLAY_NAME: PCWSTR = w!("NoCrate Hardware Service");
+
+const PIPE_NAME: PCWSTR = w!(r"\\.\pipe\nocrate-service");
+
+/// File name of the service binary, next to the app.
+const BINARY_NAME: &str = "nocrate-service.exe";
+
+/// The service only talks to an app of the same version.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Pipe buffer size in each direction.
+const BUFFER_SIZE: u32 = 4096;
+
+/// Largest accepted request line.
+const MAX_REQUEST: u64 = 64 * 1024;
+
+/// How long a client waits for a busy pipe.
+const BUSY_TIMEOUT_MS: u32 = 5000;
+
+const USAGE: &str = "\
+Usage: nocrate-service [command]
+
+Without a command the program runs under the Service Control Manager.
+
+Commands:
+  install [--allow <SID>]   Install and start the service for a user
+                            (default: the current user)
+  uninstall                 Stop and remove the service
+  --console [--allow <SID>] Serve the pipe in this console, for debugging";
+
+/// A request from the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "req", rename_all = "snake_case")]
+enum ServiceRequest {
+    /// Version and available hardware, sent first on every connection.
+    Hello,
+    /// An [`AsusWmi`] call; methods are run on `object_path`.
+    Wmi {
+        object_path: String,
+        call: WmiRequest,
+    },
+    /// [`AsusWmi::last_error`] of the service's connection.
+    LastError,
+    #[cfg(feature = "sio")]
+    Port { op: PortOp },
+}
+
+impl ServiceRequest {
+    /// Whether sending the request twice has the same effect as once.
+    /// Only these are resent after the pipe breaks: a write may already
+    /// have reached the hardware.
+    const fn is_idempotent(&self) -> bool {
+        match self {
+            Self::Hello | Self::LastError => true,
+            Self::Wmi { call, .. } => {
+                !matches!(call, WmiRequest::Devs { .. } | WmiRequest::Exec { .. })
+            }
+            #[cfg(feature = "sio")]
+            Self::Port { op } => op.is_read(),
+        }
+    }
+}
+
+/// Successful result of a [`ServiceRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ServiceReply {
+    Hello {
+        version: String,
+        /// `None` if the service has no ASUS WMI interface.
+        backend: Option<AsusWmiBackend>,
+        /// Whether the service has loaded `WinRing0`.
+        driver: bool,
+    },
+    Wmi(WmiResponse),
+    LastError(Option<u32>),
+    /// Value read by a port operation, 0 for writes.
+    Port(u64),
+}
+
+/// A [`NoCrateError`] on the pipe, rebuilt on the app side so error codes
+/// and retry logic work as with local hardware.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WireError {
+    code: String,
+    detail: String,
+}
+
+impl From<&NoCrateError> for WireError {
+    fn from(err: &NoCrateError) -> Self {
+        let detail = match err {
+            NoCrateError::Wmi(d)
+            | NoCrateError::Hid(d)
+            | NoCrateError::Config(d)
+            | NoCrateError::Sio(d)
+            | NoCrateError::Storage(d)
+            | NoCrateError::Gpu(d)
+            | NoCrateError::DriverBlocked(d)
+            | NoCrateError::AccessDenied(d)
+            | NoCrateError::DeviceNotPresent(d)
+            | NoCrateError::Timeout(d)
+            | NoCrateError::Busy(d)
+            | NoCrateError::Unknown(d) => d.clone(),
+            NoCrateError::WindowsApi(hr) => hr.to_string(),
+            NoCrateError::InvalidCurve(_) => err.to_string(),
+        };
+        Self {
+            code: err.code().to_string(),
+            detail,
+        }
+    }
+}
+
+impl From<WireError> for NoCrateError {
+    fn from(err: WireError) -> Self {
+        let WireError { code, detail } = err;
+        match code.as_str() {
+            "wmi" => Self::Wmi(detail),
+            "windows_api" => detail
+                .parse()
+                .map_or_else(|_| Self::Unknown(detail), Self::WindowsApi),
+            "hid" => Self::Hid(detail),
+            "config" => Self::Config(detail),
+            "sio" => Self::Sio(detail),
+            "storage" => Self::Storage(detail),
+            "gpu" => Self::Gpu(detail),
+            "driver_blocked" => Self::DriverBlocked(detail),
+            "access_denied" => Self::AccessDenied(detail),
+            "device_not_present" => Self::DeviceNotPresent(detail),
+            "timeout" => Self::Timeout(detail),
+            "busy" => Self::Busy(detail),
+            _ => Self::Unknown(detail),
+        }
+    }
+}
+
+/// Whether the service is installed and running.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ServiceStatus {
+    pub installed: bool,
+    pub running: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+/// Whether the app should go through the service: it is running and this
+/// process is not elevated.
+#[must_use]
+pub fn usable() -> bool {
+    !is_admin() && status().running
+}
+
+/// Connect to the service's WMI connection, for
+/// [`WmiThread::spawn_remote`].
+pub fn connect_wmi() -> Result<Box<dyn AsusWmi>> {
+    let (client, backend, _) = ServiceClient::connect()?;
+    let backend =
+        backend.ok_or_else(|| NoCrateError::DeviceNotPresent(i18n::t(Msg::ServiceNoWmi).into()))?;
+    Ok(Box::new(ServiceWmi { client, backend }))
+}
+
+/// A [remote](DriverHandle::remote) `WinRing0` handle on the service's
+/// driver.
+#[cfg(feature = "sio")]
+pub fn connect_driver() -> Result<DriverHandle> {
+    let (client, _, driver) = ServiceClient::connect()?;
+    if !driver {
+        return Err(NoCrateError::Sio(i18n::t(Msg::ServiceNoDriver).into()));
+    }
+    Ok(DriverHandle::remote(Box::new(ServiceDriver(client))))
+}
+
+/// One connection to the service, re-opened once if it breaks.
+struct ServiceClient {
+    pipe: Mutex<Option<BufReader<File>>>,
+}
+
+impl ServiceClient {
+    /// Connect and say hello. Returns the client, the service's WMI
+    /// backend and whether it has the driver.
+    fn connect() -> Result<(Self, Option<AsusWmiBackend>, bool)> {
+        let client = Self {
+            pipe: Mutex::new(Some(BufReader::new(open_pipe()?))),
+        };
+        match client.call(&ServiceRequest::Hello)? {
+            ServiceReply::Hello { version, .. } if version != VERSION => {
+                Err(NoCrateError::Unknown(i18n::tf(
+                    Msg::ServiceVersion,
+                    &[("version", &version), ("expected", &VERSION)],
+                )))
+            }
+            ServiceReply::Hello {
+                backend, driver, ..
+            } => Ok((client, backend, driver)),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    /// Send `request` and wait for the reply. A broken pipe is re-opened
+    /// and an [idempotent](ServiceRequest::is_idempotent) request sent
+    /// again once; failing that, the error is a `WindowsApi` one, which
+    /// makes the WMI thread reconnect.
+    fn call(&self, request: &ServiceRequest) -> Result<ServiceReply> {
+        let mut pipe = self.pipe.lock();
+        let mut last = None;
+        for attempt in 0..2 {
+            if attempt > 0 && !request.is_idempotent() {
+                break;
+            }
+            let mut reader = match pipe.take() {
+                Some(reader) => reader,
+                None => BufReader::new(open_pipe()?),
+            };
+            match exchange(&mut reader, request) {
+                Ok(reply) => {
+                    *pipe = Some(reader);
+                    return reply.map_err(Into::into);
+                }
+                Err(e) => last = Some(e),
+            }
+        }
+        drop(pipe);
+        let code = last
+            .and_then(|e| e.raw_os_error())
+            .map_or(0x8000_4005, |code| {
+                0x8007_0000 | (code.cast_unsigned() & 0xFFFF)
+            });
+        Err(NoCrateError::WindowsApi(code))
+    }
+}
+
+fn exchange(
+    reader: &mut BufReader<File>,
+    request: &ServiceRequest,
+) -> io::Result<std::result::Result<ServiceReply, WireError>> {
+    let mut line = serde_json::to_string(request).map_err(io::Error::other)?;
+    line.push('\n');
+    reader.get_mut().write_all(line.as_bytes())?;
+
+    let mut reply = String::new();
+    if reader.read_line(&mut reply)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    serde_json::from_str(&reply).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Open the client end of the pipe.
+#[allow(unsafe_code)]
+fn open_pipe() -> Result<File> {
+    for _ in 0..2 {
+        let opened = unsafe {
+            CreateFileW(
+                PIPE_NAME,
+                GENERIC_READ.0 | GENERIC_WRITE.0,
+                FILE_SHARE_NONE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                None,
+            )
+        };
+        match opened {
+            // SAFETY: the handle was just opened and is owned by the File
+            Ok(handle) => return Ok(unsafe { File::from_raw_handle(handle.0) }),
+            Err(e) if e.code() == ERROR_FILE_NOT_FOUND.to_hresult() => {
+                return Err(NoCrateError::DeviceNotPresent(
+                    i18n::t(Msg::ServiceNotRunning).into(),
+                ));
+            }
+            Err(e) if e.code() == ERROR_PIPE_BUSY.to_hresult() => {
+                let _ = unsafe { WaitNamedPipeW(PIPE_NAME, BUSY_TIMEOUT_MS) };
+            }
+            Err(e) => {
+                return Err(NoCrateError::classify(
+                    &e,
+                    i18n::tf(Msg::ServiceConnect, &[("error", &e)]),
+                    NoCrateError::Unknown,
+                ));
+            }
+        }
+    }
+    Err(NoCrateError::Busy(i18n::t(Msg::ServiceBusy).into()))
+}
+
+fn unexpected_reply() -> NoCrateError {
+    NoCrateError::Unknown(i18n::t(Msg::ServiceUnexpectedReply).into())
+}
+
+/// [`AsusWmi`] on the service's connection.
+struct ServiceWmi {
+    client: ServiceClient,
+    backend: AsusWmiBackend,
+}
+
+impl ServiceWmi {
+    fn call(&self, object_path: &str, call: WmiRequest) -> Result<WmiResponse> {
+        match self.client.call(&ServiceRequest::Wmi {
+            object_path: object_path.to_string(),
+            call,
+        })? {
+            ServiceReply::Wmi(response) => Ok(response),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    /// Call without an object path: the service uses its own instance.
+    fn value(&self, call: WmiRequest) -> Result<u32> {
+        match self.call("", call)? {
+            WmiResponse::Value(value) => Ok(value),
+            _ => Err(unexpected_reply()),
+        }
+    }
+}
+
+impl AsusWmi for ServiceWmi {
+    fn backend(&self) -> &AsusWmiBackend {
+        &self.backend
+    }
+
+    fn dsts(&self, device_id: u32) -> Result<u32> {
+        self.value(WmiRequest::Dsts { device_id })
+    }
+
+    fn devs(&self, device_id: u32, control: u32) -> Result<u32> {
+        self.value(WmiRequest::Devs { device_id, control })
+    }
+
+    fn exec_method_v2(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        params: &[(&str, WmiParam<'_>)],
+    ) -> Result<MethodOutput> {
+        let call = WmiRequest::Exec {
+            method: method_name.to_string(),
+            params: params
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), value.into()))
+                .collect(),
+        };
+        match self.call(object_path, call)? {
+            WmiResponse::Output(values) => Ok(MethodOutput::Simulated(values)),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn method_param_values(
+        &self,
+        object_path: &str,
+        method_name: &str,
+        param: &str,
+    ) -> Result<Vec<String>> {
+        let call = WmiRequest::ParamValues {
+            method: method_name.to_string(),
+            param: param.to_string(),
+        };
+        match self.call(object_path, call)? {
+            WmiResponse::Names(names) => Ok(names),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn last_error(&self) -> Option<u32> {
+        match self.client.call(&ServiceRequest::LastError) {
+            Ok(ServiceReply::LastError(code)) => code,
+            _ => None,
+        }
+    }
+
+    fn asushw_sensor_count(&self) -> Result<u32> {
+        self.value(WmiRequest::AsushwSensorCount)
+    }
+
+    fn asushw_sensor_info(&self, index: u32) -> Result<(u32, u32, u32, String)> {
+        match self.call("", WmiRequest::AsushwSensorInfo { index })? {
+            WmiResponse::SensorInfo(source, kind, data_type, name) => {
+                Ok((source, kind, data_type, name))
+            }
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn asushw_update_buffer(&self, source: u32) -> Result<()> {
+        match self.call("", WmiRequest::AsushwUpdateBuffer { source })? {
+            WmiResponse::Done => Ok(()),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn asushw_sensor_value(&self, index: u32) -> Result<u32> {
+        self.value(WmiRequest::AsushwSensorValue { index })
+    }
+}
+
+/// Port I/O on the service's driver.
+#[cfg(feature = "sio")]
+struct ServiceDriver(ServiceClient);
+
+#[cfg(feature = "sio")]
+impl PortForwarder for ServiceDriver {
+    fn forward(&self, op: PortOp) -> Result<u64> {
+        match self.0.call(&ServiceRequest::Port { op })? {
+            ServiceReply::Port(value) => Ok(value),
+            _ => Err(unexpected_reply()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Server
+// ---------------------------------------------------------------------------
+
+/// The hardware the service opened at startup.
+struct Server {
+    wmi: Option<WmiThread>,
+    backend: Option<AsusWmiBackend>,
+    #[cfg(feature = "sio")]
+    driver: Option<DriverHandle>,
+    /// What clients may do with `driver`.
+    #[cfg(feature = "sio")]
+    allowlist: PortAllowlist,
+}
+
+impl Server {
+    fn open() -> Self {
+        let wmi = WmiThread::spawn(&[])
+            .inspect_err(|e| eprintln!("Service: WMI unavailable: {e}"))
+            .ok();
+        let backend = wmi
+            .as_ref()
+            .and_then(|wmi| wmi.execute(|conn| Ok(conn.backend().clone())).ok());
+        #[cfg(feature = "sio")]
+        let driver = env::current_exe()
+            .map_err(|e| NoCrateError::Unknown(e.to_string()))
+            .and_then(|exe| DriverHandle::open(exe.parent().unwrap_or_else(|| Path::new("."))))
+            .inspect_err(|e| eprintln!("Service: WinRing0 unavailable: {e}"))
+            .ok();
+        #[cfg(feature = "sio")]
+        let allowlist = driver
+            .as_ref()
+            .map(PortAllowlist::detect)
+            .unwrap_or_default();
+        Self {
+            wmi,
+            backend,
+            #[cfg(feature = "sio")]
+            driver,
+            #[cfg(feature = "sio")]
+            allowlist,
+        }
+    }
+
+    fn handle(&self, request: ServiceRequest) -> Result<ServiceReply> {
+        match request {
+            ServiceRequest::Hello => Ok(ServiceReply::Hello {
+                version: VERSION.to_string(),
+                backend: self.backend.clone(),
+                #[cfg(feature = "sio")]
+                driver: self.driver.is_some(),
+                #[cfg(not(feature = "sio"))]
+                driver: false,
+            }),
+            ServiceRequest::Wmi { object_path, call } => {
+                let path = self.instance_path()?.to_string();
+                // Only method calls name an object; keep them on the ASUS instance
+                let names_object = matches!(
+                    call,
+                    WmiRequest::Exec { .. } | WmiRequest::ParamValues { .. }
+                );
+                if names_object && object_path != path {
+                    return Err(NoCrateError::AccessDenied(i18n::tf(
+                        Msg::ServiceForeignObject,
+                        &[("path", &object_path)],
+                    )));
+                }
+                self.wmi()?
+                    .execute(move |conn| trace::perform(conn, &path, &call))
+                    .map(ServiceReply::Wmi)
+            }
+            ServiceRequest::LastError => self
+                .wmi()?
+                .execute(|conn| Ok(conn.last_error()))
+                .map(ServiceReply::LastError),
+            #[cfg(feature = "sio")]
+            ServiceRequest::Port { op } => {
+                let driver = self
+                    .driver
+                    .as_ref()
+                    .ok_or_else(|| NoCrateError::Sio(i18n::t(Msg::ServiceNoDriver).into()))?;
+                self.allowlist.check(op)?;
+                driver.execute(op).map(ServiceReply::Port)
+            }
+        }
+    }
+
+    fn wmi(&self) -> Result<&WmiThread> {
+        self.wmi
+            .as_ref()
+            .ok_or_else(|| NoCrateError::Wmi(i18n::t(Msg::ServiceWmiLost).into()))
+    }
+
+    fn instance_path(&self) -> Result<&str> {
+        match &self.backend {
+            Some(
+                AsusWmiBackend::Laptop { instance_path }
+                | AsusWmiBackend::Desktop { instance_path }
+                | AsusWmiBackend::AsusHW { instance_path },
+            ) => Ok(instance_path),
+            None => Err(NoCrateError::DeviceNotPresent(
+                i18n::t(Msg::ServiceNoWmi).into(),
+            )),
+        }
+    }
+}
+
+/// Accept clients forever, one thread each.
+#[allow(unsafe_code)]
+fn listen(server: &Arc<Server>, allowed_sid: Option<&str>) -> Result<()> {
+    let security = pipe_security(allowed_sid)?;
+    let attributes = SECURITY_ATTRIBUTES {
+        nLength: u32::try_from(size_of::<SECURITY_ATTRIBUTES>()).unwrap_or(0),
+        lpSecurityDescriptor: security.0,
+        bInheritHandle: false.into(),
+    };
+    let mut first = FILE_FLAG_FIRST_PIPE_INSTANCE;
+    loop {
+        // The first instance fails if another process already owns the name
+        let handle = unsafe {
+            CreateNamedPipeW(
+                PIPE_NAME,
+                PIPE_ACCESS_DUPLEX | first,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                BUFFER_SIZE,
+                BUFFER_SIZE,
+                0,
+                Some(&raw const attributes),
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(windows::core::Error::from_win32().into());
+        }
+        first = FILE_FLAGS_AND_ATTRIBUTES(0);
+        // SAFETY: the handle was just created and is owned by the File
+        let pipe = unsafe { File::from_raw_handle(handle.0) };
+
+        match unsafe { ConnectNamedPipe(handle, None) } {
+            Ok(()) => {}
+            // The client connected between create and this call
+            Err(e) if e.code() == ERROR_PIPE_CONNECTED.to_hresult() => {}
+            Err(e) => {
+                eprintln!("Service: failed to accept client: {e}");
+                thread::sleep(Duration::from_secs(1));
+                continue;
+            }
+        }
+        let server = Arc::clone(server);
+        let spawned = thread::Builder::new()
+            .name("nocrate-service-client".into())
+            .spawn(move || {
+                if let Err(e) = serve_client(&server, &pipe) {
+                    eprintln!("Service: {e}");
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("Service: failed to spawn client thread: {e}");
+        }
+    }
+}
+
+/// Answer requests until the client disconnects.
+fn serve_client(server: &Server, pipe: &File) -> io::Result<()> {
+    let mut reader = BufReader::new(pipe);
+    loop {
+        let mut line = String::new();
+        if reader.by_ref().take(MAX_REQUEST).read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let reply = match serde_json::from_str::<ServiceRequest>(&line) {
+            Ok(request) => server.handle(request),
+            Err(e) => Err(NoCrateError::Unknown(i18n::tf(
+                Msg::RequestInvalid,
+                &[("error", &e)],
+            ))),
+        }
+        .map_err(|e| WireError::from(&e));
+        let mut out = serde_json::to_string(&reply).unwrap_or_default();
+        out.push('\n');
+        (&mut &*pipe).write_all(out.as_bytes())?;
+    }
+}
+
+/// DACL of the pipe: full access for SYSTEM and administrators,
+/// read/write for `allowed_sid`. Lives for the rest of the process.
+#[allow(unsafe_code)]
+pub(crate) fn pipe_security(allowed_sid: Option<&str>) -> Result<PSECURITY_DESCRIPTOR> {
+    let user = allowed_sid.map_or_else(String::new, |sid| format!("(A;;GRGW;;;{sid})"));
+    let sddl = to_wide(&format!("D:P(A;;GA;;;SY)(A;;GA;;;BA){user}"));
+    let mut security = PSECURITY_DESCRIPTOR::default();
+    unsafe {
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(
+            PCWSTR(sddl.as_ptr()),
+            SDDL_REVISION_1,
+            &raw mut security,
+            None,
+        )?;
+    }
+    Ok(security)
+}
+
+// ---------------------------------------------------------------------------
+// Service host
+// ---------------------------------------------------------------------------
+
+/// `SERVICE_STATUS_HANDLE` of the running service.
+static STATUS_HANDLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Set by the control handler when the service is asked to stop.
+static STOP_REQUESTED: Mutex<bool> = Mutex::new(false);
+static STOP_SIGNAL: Condvar = Condvar::new();
+
+/// The `--allow` SID the service was started with.
+static ALLOWED_SID: OnceLock<Option<String>> = OnceLock::new();
+
+/// Entry point of the `nocrate-service` binary.
+#[must_use]
+pub fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let allowed_sid = match args.iter().position(|a| a == "--allow") {
+        Some(i) => {
+            let Some(sid) = args.get(i + 1).filter(|sid| is_sid(sid)) else {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            };
+            Some(sid.clone())
+        }
+        None => None,
+    };
+
+    let result = match args.first().map(String::as_str) {
+        Some("install") => install_as(allowed_sid.as_deref()),
+        Some("uninstall") => remove(),
+        Some("--console") => listen(&Arc::new(Server::open()), allowed_sid.as_deref()),
+        None | Some("--allow") => {
+            let _ = ALLOWED_SID.set(allowed_sid);
+            dispatch()
+        }
+        Some(_) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Hand the process to the Service Control Manager.
+#[allow(unsafe_code)]
+fn dispatch() -> Result<()> {
+    let table = [
+        SERVICE_TABLE_ENTRYW {
+            lpServiceName: PWSTR(SERVICE_NAME.as_ptr().cast_mut()),
+            lpServiceProc: Some(service_main),
+        },
+        SERVICE_TABLE_ENTRYW::default(),
+    ];
+    match unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) } {
+        Ok(()) => Ok(()),
+        Err(e) if e.code() == ERROR_FAILED_SERVICE_CONTROLLER_CONNECT.to_hresult() => Err(
+            NoCrateError::Unknown(i18n::tf(Msg::ServiceNotStarted, &[("usage", &USAGE)])),
+        ),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[allow(unsafe_code)]
+unsafe extern "system" fn service_main(_argc: u32, _argv: *mut PWSTR) {
+    let Ok(handle) = RegisterServiceCtrlHandlerExW(SERVICE_NAME, Some(control_handler), None)
+    else {
+        return;
+    };
+    STATUS_HANDLE.store(handle.0 as usize, Ordering::Release);
+    report_status(SERVICE_START_PENDING);
+
+    let server = Arc::new(Server::open());
+    let allowed_sid = ALLOWED_SID.get().cloned().flatten();
+    let spawned = thread::Builder::new()
+        .name("nocrate-service-pipe".into())
+        .spawn(move || {
+            if let Err(e) = listen(&server, allowed_sid.as_deref()) {
+                eprintln!("Service: pipe server stopped: {e}");
+            }
+        });
+    if spawned.is_ok() {
+        report_status(SERVICE_RUNNING);
+        let mut stop = STOP_REQUESTED.lock();
+        while !*stop {
+            STOP_SIGNAL.wait(&mut stop);
+        }
+    }
+    report_status(SERVICE_STOPPED);
+}
+
+#[allow(unsafe_code)]
+unsafe extern "system" fn control_handler(
+    control: u32,
+    _event_type: u32,
+    _event_data: *mut c_void,
+    _context: *mut c_void,
+) -> u32 {
+    match control {
+        SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN => {
+            report_status(SERVICE_STOP_PENDING);
+            *STOP_REQUESTED.lock() = true;
+            let _ = STOP_SIGNAL.notify_all();
+            NO_ERROR.0
+        }
+        SERVICE_CONTROL_INTERROGATE => NO_ERROR.0,
+        _ => ERROR_CALL_NOT_IMPLEMENTED.0,
+    }
+}
+
+#[allow(unsafe_code)]
+fn report_status(state: SERVICE_STATUS_CURRENT_STATE) {
+    let status = SERVICE_STATUS {
+        dwServiceType: SERVICE_WIN32_OWN_PROCESS,
+        dwCurrentState: state,
+        dwControlsAccepted: if state == SERVICE_RUNNING {
+            SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN
+        } else {
+            0
+        },
+        ..Default::default()
+    };
+    let handle = SERVICE_STATUS_HANDLE(STATUS_HANDLE.load(Ordering::Acquire) as *mut c_void);
+    let _ = unsafe { SetServiceStatus(handle, &raw const status) };
+}
+
+// ---------------------------------------------------------------------------
+// Installation
+// ---------------------------------------------------------------------------
+
+/// Whether the service is installed and running.
+#[allow(unsafe_code)]
+#[must_use]
+pub fn status() -> ServiceStatus {
+    let running = with_service(SC_MANAGER_CONNECT, SERVICE_QUERY_STATUS, |svc| {
+        let mut status = SERVICE_STATUS::default();
+        unsafe { QueryServiceStatus(svc, &raw mut status)? };
+        Ok(status.dwCurrentState == SERVICE_RUNNING)
+    });
+    match running {
+        Ok(Some(running)) => ServiceStatus {
+            installed: true,
+            running,
+        },
+        Ok(None) | Err(_) => ServiceStatus {
+            installed: false,
+            running: false,
+        },
+    }
+}
+
+/// Install and start the service for the current user, through a UAC
+/// prompt unless this process is elevated.
+pub fn install() -> Result<()> {
+    let sid = current_user_sid()?;
+    if is_admin() {
+        install_as(Some(&sid))
+    } else {
+        run_elevated(&format!("install --allow {sid}"))
+    }
+}
+
+/// Stop and remove the service, through a UAC prompt unless this process
+/// is elevated.
+pub fn uninstall() -> Result<()> {
+    if is_admin() {
+        remove()
+    } else {
+        run_elevated("uninstall")
+    }
+}
+
+/// Create (or update) and start the service, giving `allowed_sid` (by
+/// default the current user) access to the pipe.
+#[allow(unsafe_code)]
+fn install_as(allowed_sid: Option<&str>) -> Result<()> {
+    let sid = match allowed_sid {
+        Some(sid) => sid.to_string(),
+        None => current_user_sid()?,
+    };
+    let binary = binary_path()?;
+    let command = to_wide(&format!("\"{}\" --allow {sid}", binary.display()));
+    unsafe {
+        let scm = ScHandle(OpenSCManagerW(
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SC_MANAGER_CREATE_SERVICE | SC_MANAGER_CONNECT,
+        )?);
+        let created = CreateServiceW(
+            scm.0,
+            SERVICE_NAME,
+            DISPLAY_NAME,
+            SERVICE_ALL_ACCESS,
+            SERVICE_WIN32_OWN_PROCESS,
+            SERVICE_AUTO_START,
+            SERVICE_ERROR_NORMAL,
+            PCWSTR(command.as_ptr()),
+            PCWSTR::null(),
+            None,
+            PCWSTR::null(),
+            // LocalSystem
+            PCWSTR::null(),
+            PCWSTR::null(),
+        );
+        let svc = match created {
+            Ok(svc) => ScHandle(svc),
+            // Reinstall: point the existing service at this binary and user
+            Err(e) if e.code() == ERROR_SERVICE_EXISTS.to_hresult() => {
+                let svc = ScHandle(OpenServiceW(scm.0, SERVICE_NAME, SERVICE_ALL_ACCESS)?);
+                ChangeServiceConfigW(
+                    svc.0,
+                    ENUM_SERVICE_TYPE(SERVICE_NO_CHANGE),
+                    SERVICE_AUTO_START,
+                    SERVICE_ERROR_NORMAL,
+                    PCWSTR(command.as_ptr()),
+                    PCWSTR::null(),
+                    None,
+                    PCWSTR::null(),
+                    PCWSTR::null(),
+                    PCWSTR::null(),
+                    PCWSTR::null(),
+                )?;
+                svc
+            }
+            Err(e) => return Err(e.into()),
+        };
+        match StartServiceW(svc.0, None) {
+            Ok(()) => Ok(()),
+            Err(e) if e.code() == ERROR_SERVICE_ALREADY_RUNNING.to_hresult() => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Stop and delete the service; not installed is not an error.
+#[allow(unsafe_code)]
+fn remove() -> Result<()> {
+    let removed = with_service(SC_MANAGER_CONNECT, SERVICE_ALL_ACCESS, |svc| {
+        let mut status = SERVICE_STATUS::default();
+        unsafe {
+            let _ = ControlService(svc, SERVICE_CONTROL_STOP, &raw mut status);
+            DeleteService(svc)?;
+        }
+        Ok(())
+    });
+    removed.map(|_| ())
+}
+
+/// Open the service with `access` and run `f` on it. `None` if it is not
+/// installed.
+#[allow(unsafe_code)]
+fn with_service<T>(
+    scm_access: u32,
+    access: u32,
+    f: impl FnOnce(SC_HANDLE) -> Result<T>,
+) -> Result<Option<T>> {
+    unsafe {
+        let scm = ScHandle(OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), scm_access)?);
+        match OpenServiceW(scm.0, SERVICE_NAME, access) {
+            Ok(svc) => {
+                let svc = ScHandle(svc);
+                f(svc.0).map(Some)
+            }
+            Err(e) if e.code() == ERROR_SERVICE_DOES_NOT_EXIST.to_hresult() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Closes a service or SCM handle on drop.
+struct ScHandle(SC_HANDLE);
+
+impl Drop for ScHandle {
+    #[allow(unsafe_code)]
+    fn drop(&mut self) {
+        let _ = unsafe { CloseServiceHandle(self.0) };
+    }
+}
+
+/// Run `nocrate-service <args>` through a UAC prompt and wait for it.
+#[allow(unsafe_code)]
+fn run_elevated(args: &str) -> Result<()> {
+    let binary = to_wide(&binary_path()?.to_string_lossy());
+    let args = to_wide(args);
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: u32::try_from(size_of::<SHELLEXECUTEINFOW>()).unwrap_or(0),
+        fMask: SEE_MASK_NOCLOSEPROCESS,
+        lpVerb: w!("runas"),
+        lpFile: PCWSTR(binary.as_ptr()),
+        lpParameters: PCWSTR(args.as_ptr()),
+        nShow: SW_HIDE.0,
+        ..Default::default()
+    };
+    unsafe {
+        if let Err(e) = ShellExecuteExW(&raw mut info) {
+            if e.code() == ERROR_CANCELLED.to_hresult() {
+                return Err(NoCrateError::AccessDenied(
+                    i18n::t(Msg::ElevationCancelled).into(),
+                ));
+            }
+            return Err(e.into());
+        }
+        let process = info.hProcess;
+        let _ = WaitForSingleObject(process, INFINITE);
+        let mut code = 0;
+        let exited = GetExitCodeProcess(process, &raw mut code);
+        let _ = CloseHandle(process);
+        exited?;
+        if code != 0 {
+            return Err(NoCrateError::Unknown(i18n::tf(
+                Msg::ServiceExitCode,
+                &[("name", &BINARY_NAME), ("code", &code)],
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// `nocrate-service.exe` next to the running executable.
+fn binary_path() -> Result<PathBuf> {
+    let exe = env::current_exe().map_err(|e| NoCrateError::Unknown(e.to_string()))?;
+    let binary = exe.with_file_name(BINARY_NAME);
+    if binary.exists() {
+        Ok(binary)
+    } else {
+        Err(NoCrateError::DeviceNotPresent(i18n::tf(
+            Msg::FileMissing,
+            &[("path", &binary.display())],
+        )))
+    }
+}
+
+/// String SID of the user running this process.
+#[allow(unsafe_code)]
+pub(crate) fn current_user_sid() -> Result<String> {
+    unsafe {
+        let mut token = HANDLE::default();
+        OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &raw mut token)?;
+        let mut needed = 0;
+        // The first call only reports the size
+        let _ = GetTokenInformation(token, TokenUser, None, 0, &raw mut needed);
+        let mut buf = vec![0u64; (needed as usize).div_ceil(8)];
+        let queried = GetTokenInformation(
+            token,
+            TokenUser,
+            Some(buf.as_mut_ptr().cast()),
+            needed,
+            &raw mut needed,
+        );
+        let _ = CloseHandle(token);
+        queried?;
+
+        let user = &*buf.as_ptr().cast::<TOKEN_USER>();
+        let mut string = PWSTR::null();
+        ConvertSidToStringSidW(user.User.Sid, &raw mut string)?;
+        let sid = string.to_string();
+        let _ = LocalFree(Some(HLOCAL(string.0.cast())));
+        sid.map_err(|e| NoCrateError::Unknown(e.to_string()))
+    }
+}
+
+/// Whether `s` looks like a string SID. Checked before it goes into the
+/// service command line and the pipe's SDDL.
+fn is_sid(s: &str) -> bool {
+    s.starts_with("S-1-") && s[4..].bytes().all(|b| b.is_ascii_digit() || b == b'-')
+}
+
+fn to_wide(s: &str) -> Vec<u16> {
+    std::ffi::OsStr::new(s)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
@@ -0,0 +1,35 @@
+/// Background hardware initialization.
+///
+/// Opening the WMI connection, installing the `WinRing0` driver and
+/// probing the Super I/O chip can take seconds. They run on a background
+/// thread once the state is managed, so the window shows right away and
+/// the frontend is told about each subsystem through
+/// [`SUBSYSTEM_READY_EVENT`](crate::state::SUBSYSTEM_READY_EVENT). The
+/// threads that need the hardware at startup (restoring settings,
+/// subscribing to ATK events, ...) are started once it is done.
+use std::thread;
+use std::time::Instant;
+
+use tauri::{AppHandle, Manager};
+
+use crate::state::AppState;
+
+/// Spawn the initialization thread; `then` runs on it afterwards.
+pub fn spawn(app: AppHandle, then: impl FnOnce(&AppHandle) + Send + 'static) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-init".into())
+        .spawn(move || {
+            let started = Instant::now();
+            if let Some(state) = app.try_state::<AppState>() {
+                state.init_hardware(&app);
+            }
+            eprintln!(
+                "Hardware initialized in {} ms",
+                started.elapsed().as_millis()
+            );
+            then(&app);
+        });
+    if let Err(e) = spawned {
+        eprintln!("Startup: failed to spawn initialization thread: {e}");
+    }
+}
@@ -1,95 +1,203 @@
+use std::future::Future;
 use std::path::PathBuf;
-use std::sync::mpsc;
-use std::thread;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
 
 use crate::aura::controller::AuraController;
+use crate::aura::hotplug::{self, DEVICES_CHANGED_EVENT};
+#[cfg(feature = "sio")]
+use crate::aura::dram::DramController;
+use crate::aura::effects::EffectEngine;
+use crate::aura::stream::FrameStream;
+use crate::board::{self, BoardInfo, BoardQuirks};
 use crate::config::ConfigStore;
+#[cfg(feature = "sio")]
+use crate::cpu::CpuMonitor;
+#[cfg(feature = "sio")]
+use crate::ec::EcMonitor;
 use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::gpu::GpuMonitor;
+use crate::power_source::PowerSource;
+use crate::eco::EcoState;
+use crate::sensors::history::SensorHistory;
+use crate::sensors::polling::SensorPoller;
+use crate::sensors::smoothing::SensorSmoother;
+use crate::sensors::stats::SensorStatsTracker;
+use crate::service;
+#[cfg(feature = "sio")]
+use crate::sio::driver::{self, DriverHandle};
 #[cfg(feature = "sio")]
 use crate::sio::SioMonitor;
+#[cfg(feature = "sio")]
+use crate::smbus::SmbusController;
+use crate::storage::StorageMonitor;
+use crate::wmi::asus_mgmt::{self, AsusHwCache};
 use crate::wmi::connection::WmiConnection;
+use crate::wmi::ppt;
 
-/// A request to execute on the WMI thread.
-type WmiRequest = Box<dyn FnOnce(&WmiConnection) + Send>;
+pub use crate::wmi::thread::{WmiHealth, WmiOp, WmiThread};
 
-/// Thread-safe handle to the dedicated WMI thread.
-///
-/// Because COM objects (IWbemServices) are not Send/Sync, we run all WMI
-/// operations on a single dedicated thread and communicate via channels.
-pub struct WmiThread {
-    sender: mpsc::Sender<WmiRequest>,
+/// Event emitted to the frontend when the WMI connection status changes.
+pub const WMI_STATUS_EVENT: &str = "wmi-status";
+
+/// Event emitted to the frontend each time a subsystem finishes its
+/// background initialization, see [`AppState::init_hardware`].
+pub const SUBSYSTEM_READY_EVENT: &str = "subsystem-ready";
+
+/// Payload of [`SUBSYSTEM_READY_EVENT`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemReady {
+    /// Subsystem name as used by `get_system_status`.
+    pub name: &'static str,
+    /// Why initialization failed, `None` if the subsystem is ready.
+    pub error: Option<NoCrateError>,
 }
 
-impl WmiThread {
-    /// Spawn the dedicated WMI thread and establish the COM connection.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the WMI connection fails during initialization.
-    pub fn spawn() -> Result<Self> {
-        let (init_tx, init_rx) = mpsc::channel::<std::result::Result<(), NoCrateError>>();
-        let (req_tx, req_rx) = mpsc::channel::<WmiRequest>();
-
-        let _handle = thread::Builder::new()
-            .name("nocrate-wmi".into())
-            .spawn(move || {
-                // Attempt to create the WMI connection on this thread
-                let conn = match WmiConnection::new() {
-                    Ok(c) => {
-                        let _ = init_tx.send(Ok(()));
-                        c
-                    }
-                    Err(e) => {
-                        let _ = init_tx.send(Err(e));
-                        return;
-                    }
-                };
-
-                // Process requests until the channel is closed
-                for request in req_rx {
-                    request(&conn);
-                }
-
-                // `conn` drops here → CoUninitialize on this thread
-            })
-            .map_err(|e| NoCrateError::Unknown(format!("Failed to spawn WMI thread: {e}")))?;
+/// Emit [`WMI_STATUS_EVENT`] whenever the connection status of `wmi`
+/// changes.
+pub fn forward_wmi_status(app: &AppHandle, wmi: &WmiThread) {
+    let handle = app.clone();
+    wmi.set_status_listener(move |health| {
+        let _ = handle.emit(WMI_STATUS_EVENT, health);
+    });
+}
 
-        // Wait for initialization result
-        init_rx
-            .recv()
-            .map_err(|_| NoCrateError::Wmi("WMI thread died during init".into()))??;
+/// Async variants of the [`WmiThread`] calls, for Tauri commands.
+pub trait WmiThreadAsync {
+    /// Like [`WmiThread::execute`], but waits for the result on the
+    /// blocking thread pool so async commands never block the runtime.
+    fn execute_async<F, T>(&self, f: F) -> impl Future<Output = Result<T>> + Send + 'static
+    where
+        F: FnOnce(&WmiConnection) -> Result<T> + Send + 'static,
+        T: Send + 'static;
 
-        Ok(Self { sender: req_tx })
-    }
+    /// Like [`WmiThread::execute_batch`], on the blocking thread pool.
+    fn execute_batch_async<T>(
+        &self,
+        ops: Vec<WmiOp<T>>,
+    ) -> impl Future<Output = Result<Vec<Result<T>>>> + Send + 'static
+    where
+        T: Send + 'static;
+}
 
-    /// Execute a closure on the WMI thread and receive the result.
-    ///
-    /// The closure runs on the dedicated WMI thread with access to the
-    /// `WmiConnection`. The result is sent back via a oneshot channel.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the WMI thread is dead or the closure returns an error.
-    pub fn execute<F, T>(&self, f: F) -> Result<T>
+impl WmiThreadAsync for WmiThread {
+    fn execute_async<F, T>(&self, f: F) -> impl Future<Output = Result<T>> + Send + 'static
     where
         F: FnOnce(&WmiConnection) -> Result<T> + Send + 'static,
         T: Send + 'static,
     {
-        let (tx, rx) = mpsc::channel();
+        let wmi = self.clone();
+        async move {
+            tauri::async_runtime::spawn_blocking(move || wmi.execute(f))
+                .await
+                .map_err(|e| NoCrateError::Wmi(i18n::tf(Msg::WmiTaskFailed, &[("error", &e)])))?
+        }
+    }
+
+    fn execute_batch_async<T>(
+        &self,
+        ops: Vec<WmiOp<T>>,
+    ) -> impl Future<Output = Result<Vec<Result<T>>>> + Send + 'static
+    where
+        T: Send + 'static,
+    {
+        self.execute_async(move |conn| Ok(ops.into_iter().map(|op| op(conn)).collect()))
+    }
+}
+
+/// A subsystem that may fail to initialize and can be re-initialized at
+/// runtime (after installing a driver, starting a service, ...).
+///
+/// Holders take a clone of the instance for each use, so a replacement
+/// never invalidates a call in progress.
+pub struct Subsystem<T> {
+    slot: RwLock<Slot<T>>,
+}
+
+struct Slot<T> {
+    value: Option<T>,
+    error: Option<NoCrateError>,
+    /// The first initialization attempt has not finished yet.
+    pending: bool,
+}
+
+impl<T: Clone> Subsystem<T> {
+    /// A subsystem whose first initialization is still to run, see
+    /// [`AppState::init_hardware`].
+    pub const fn pending() -> Self {
+        Self {
+            slot: RwLock::new(Slot {
+                value: None,
+                error: None,
+                pending: true,
+            }),
+        }
+    }
 
-        let request: WmiRequest = Box::new(move |conn| {
-            let result = f(conn);
-            let _ = tx.send(result);
-        });
+    /// The running instance, `None` if initialization failed or has not
+    /// finished yet.
+    pub fn get(&self) -> Option<T> {
+        self.slot.read().value.clone()
+    }
 
-        self.sender
-            .send(request)
-            .map_err(|_| NoCrateError::Wmi("WMI thread is no longer running".into()))?;
+    /// Whether an instance is running.
+    pub fn is_some(&self) -> bool {
+        self.slot.read().value.is_some()
+    }
+
+    /// Whether the first initialization is still running.
+    pub fn is_pending(&self) -> bool {
+        self.slot.read().pending
+    }
 
-        rx.recv()
-            .map_err(|_| NoCrateError::Wmi("WMI thread did not respond".into()))?
+    /// Why the last initialization failed.
+    pub fn error(&self) -> Option<NoCrateError> {
+        self.slot.read().error.clone()
+    }
+
+    /// The running instance, or why there is none (`missing` if unknown).
+    pub fn require(&self, missing: Msg) -> Result<T> {
+        let slot = self.slot.read();
+        if let Some(value) = &slot.value {
+            return Ok(value.clone());
+        }
+        Err(match &slot.error {
+            Some(e) => e.clone(),
+            None if slot.pending => NoCrateError::Busy(i18n::tf(
+                Msg::StillInitializing,
+                &[("what", &i18n::t(missing))],
+            )),
+            None => i18n::t(missing).into(),
+        })
+    }
+
+    /// Store the outcome of a new initialization attempt. A failed
+    /// attempt keeps the running instance, if any.
+    pub fn set(&self, result: Result<T>) {
+        let mut slot = self.slot.write();
+        slot.pending = false;
+        match result {
+            Ok(value) => {
+                slot.value = Some(value);
+                slot.error = None;
+            }
+            Err(e) => slot.error = Some(e),
+        }
+    }
+
+    /// Drop the running instance, recording `reason` as the error.
+    #[cfg(feature = "sio")]
+    pub fn clear(&self, reason: NoCrateError) {
+        let mut slot = self.slot.write();
+        slot.pending = false;
+        slot.value = None;
+        slot.error = Some(reason);
     }
 }
 
@@ -98,75 +206,452 @@ impl WmiThread {
 /// Holds shared resources accessible from all commands.
 pub struct AppState {
     /// WMI thread handle.
-    /// `None` if the WMI connection failed (e.g. no admin or no ASUS drivers).
-    pub wmi: Option<WmiThread>,
-    /// AURA controller behind a Mutex (HidDevice is Send but not Sync).
-    /// `None` if no controller was found at startup.
-    pub aura: Mutex<Option<AuraController>>,
+    /// `None` while starting up or if the WMI connection failed (e.g. no
+    /// admin or no ASUS drivers); the error is kept so the frontend can
+    /// show a meaningful explanation.
+    pub wmi: Subsystem<WmiThread>,
+    /// AURA controllers behind a Mutex (HidDevice is Send but not Sync).
+    /// Empty until discovery finishes, or if no controller was found.
+    pub aura: Mutex<Vec<AuraController>>,
+    /// Software lighting effects streamed to the AURA controllers.
+    pub aura_effects: EffectEngine,
+    /// Paced direct-mode output shared by software effects and external
+    /// frame sources.
+    pub aura_stream: FrameStream,
+    /// AURA RGB memory modules on the SMBus.
+    /// Empty until detection finishes, or if the driver or SMBus
+    /// controller is unavailable.
+    #[cfg(feature = "sio")]
+    pub aura_dram: RwLock<Vec<DramController>>,
     /// Persistent configuration store.
     pub config: ConfigStore,
-    /// If WMI initialization failed, the error message is stored here
-    /// so the frontend can show a meaningful explanation.
-    pub wmi_error: Option<String>,
+    /// Min / avg / max of every sensor since startup, updated by
+    /// [`sensors::collect`](crate::sensors::collect).
+    pub sensor_stats: SensorStatsTracker,
+    /// Downsampled history of every sensor for charts, updated like
+    /// `sensor_stats`.
+    pub sensor_history: SensorHistory,
+    /// Recent readings of the sensors with a smoothing window.
+    pub sensor_smoother: SensorSmoother,
+    /// Last readings of each sensor source, reused for the slow polling
+    /// tier.
+    pub sensor_poller: SensorPoller,
+    /// ASUSHW sensor metadata and buffer refresh times, shared by the
+    /// sensor commands and [`sensors::collect`](crate::sensors::collect).
+    pub asushw: Arc<AsusHwCache>,
+    /// Eco polling while the main window is hidden, see [`crate::eco`].
+    pub eco: EcoState,
+    /// [`Self::init_hardware`] has not finished yet.
+    initializing: AtomicBool,
+    /// Started with [`SIMULATE_ARG`](crate::SIMULATE_ARG): WMI, AURA and
+    /// the `WinRing0` driver are replaced by fake backends.
+    pub simulated: bool,
+    /// Board model from SMBIOS, `None` if it could not be read.
+    pub board: Option<BoardInfo>,
+    /// Per-board quirks (fan header names, SIO mapping, blocked device IDs).
+    pub quirks: &'static BoardQuirks,
+    /// 存储设备温度（NVMe / SATA SMART）
+    /// `None` 表示未找到可读取温度的驱动器
+    pub storage: Option<StorageMonitor>,
+    /// 存储设备枚举失败时的错误信息
+    pub storage_error: Option<NoCrateError>,
+    /// GPU 温度 / 风扇（NVML / ADL）
+    /// `None` 表示未安装 NVIDIA / AMD 驱动
+    pub gpu: Option<GpuMonitor>,
+    /// GPU 后端加载失败时的错误信息
+    pub gpu_error: Option<NoCrateError>,
     /// Super I/O 传感器监控器（读取风扇 RPM 和温度）
-    /// `None` 表示驱动加载失败或未检测到支持的芯片
+    /// `None` 表示驱动加载失败或未检测到支持的芯片，错误信息一并保存
+    #[cfg(feature = "sio")]
+    pub sio: Subsystem<Arc<SioMonitor>>,
+    /// `WinRing0` 驱动句柄，由 SIO / EC / CPU / `SMBus` 共享
+    #[cfg(feature = "sio")]
+    pub driver: Subsystem<Arc<DriverHandle>>,
+    /// 驱动文件所在目录，重新加载驱动时使用
     #[cfg(feature = "sio")]
-    pub sio: Option<SioMonitor>,
-    /// SIO 初始化失败时的错误信息
+    resource_dir: PathBuf,
+    /// ACPI 嵌入式控制器传感器（VRM / 芯片组 / 水冷温度等）
+    /// `None` 表示驱动不可用或主板不在 EC 偏移表中
     #[cfg(feature = "sio")]
-    pub sio_error: Option<String>,
+    pub ec: Subsystem<Arc<EcMonitor>>,
+    /// CPU 封装温度（MSR / AMD SMN）
+    #[cfg(feature = "sio")]
+    pub cpu: Subsystem<Arc<CpuMonitor>>,
 }
 
 impl AppState {
-    /// Create a new `AppState` by initializing all subsystems.
-    ///
-    /// WMI and AURA discovery failures are both non-fatal — the app
-    /// launches regardless, with degraded functionality.
+    /// Create a new `AppState` with the subsystems that are quick to set
+    /// up. WMI, AURA and the `WinRing0`-based sensors start out pending
+    /// and are brought up by [`Self::init_hardware`], so the window does
+    /// not wait for driver installation or Super I/O probing. With
+    /// `simulate` the hardware is faked, so no ASUS board or admin rights
+    /// are needed.
     pub fn new(
         app_data_dir: PathBuf,
         #[cfg_attr(not(feature = "sio"), allow(unused))] resource_dir: PathBuf,
+        simulate: bool,
     ) -> Result<Self> {
-        let (wmi, wmi_error) = match WmiThread::spawn() {
-            Ok(w) => (Some(w), None),
-            Err(e) => {
-                eprintln!("Warning: WMI initialization failed: {e}");
-                eprintln!("Fan control features will be unavailable.");
-                (None, Some(e.to_string()))
-            }
+        // 主板型号与兼容性修正表，WMI / SIO 初始化时使用
+        let board = if simulate {
+            Some(BoardInfo::simulated())
+        } else {
+            BoardInfo::detect()
         };
+        let quirks = board::quirks_for(board.as_ref().map(|b| b.product.as_str()));
+        if let Some(b) = &board {
+            eprintln!(
+                "Board: {} {}",
+                b.manufacturer.as_deref().unwrap_or("?"),
+                b.product
+            );
+        }
 
-        let aura = match AuraController::discover() {
-            Ok(ctrl) => {
-                eprintln!("AURA controller found: {:?}", ctrl.info());
-                Some(ctrl)
-            }
+        let config = ConfigStore::init(app_data_dir)?;
+
+        // 枚举存储设备温度（非致命，不依赖 WinRing0）
+        let (storage, storage_error) = match StorageMonitor::init() {
+            Ok(m) => (Some(m), None),
             Err(e) => {
-                eprintln!("AURA controller not found: {e}");
-                None
+                eprintln!("Warning: Storage temperature initialization failed: {e}");
+                (None, Some(e))
             }
         };
 
-        // 初始化 Super I/O 传感器监控（非致命）
-        #[cfg(feature = "sio")]
-        let (sio, sio_error) = match SioMonitor::init(&resource_dir) {
+        // 加载 GPU 厂商接口（非致命）
+        let (gpu, gpu_error) = match GpuMonitor::init() {
             Ok(m) => (Some(m), None),
             Err(e) => {
-                eprintln!("Warning: SIO initialization failed: {e}");
-                (None, Some(e.to_string()))
+                eprintln!("Warning: GPU sensor initialization failed: {e}");
+                (None, Some(e))
             }
         };
 
-        let config = ConfigStore::init(app_data_dir)?;
-
         Ok(Self {
-            wmi,
-            aura: Mutex::new(aura),
+            wmi: Subsystem::pending(),
+            aura: Mutex::new(Vec::new()),
+            aura_effects: EffectEngine::default(),
+            aura_stream: FrameStream::default(),
             config,
-            wmi_error,
+            sensor_stats: SensorStatsTracker::default(),
+            sensor_history: SensorHistory::default(),
+            sensor_smoother: SensorSmoother::default(),
+            sensor_poller: SensorPoller::default(),
+            asushw: Arc::default(),
+            eco: EcoState::default(),
+            initializing: AtomicBool::new(true),
+            simulated: simulate,
+            board,
+            quirks,
+            storage,
+            storage_error,
+            gpu,
+            gpu_error,
+            #[cfg(feature = "sio")]
+            sio: Subsystem::pending(),
             #[cfg(feature = "sio")]
-            sio,
+            driver: Subsystem::pending(),
             #[cfg(feature = "sio")]
-            sio_error,
+            resource_dir,
+            #[cfg(feature = "sio")]
+            ec: Subsystem::pending(),
+            #[cfg(feature = "sio")]
+            cpu: Subsystem::pending(),
+            #[cfg(feature = "sio")]
+            aura_dram: RwLock::new(Vec::new()),
         })
     }
+
+    /// Bring up the hardware subsystems left pending by [`Self::new`],
+    /// emitting [`SUBSYSTEM_READY_EVENT`] as each one finishes. Every
+    /// step is non-fatal — a failed subsystem keeps its error for
+    /// `get_system_status` and can be retried with `reinit_subsystem`.
+    ///
+    /// Runs on a background thread after the window is shown.
+    pub fn init_hardware(&self, app: &AppHandle) {
+        let started = Instant::now();
+        let ready = |name: &'static str, error: Option<NoCrateError>| {
+            eprintln!(
+                "Subsystem {name} {} after {} ms",
+                if error.is_some() { "failed" } else { "ready" },
+                started.elapsed().as_millis()
+            );
+            let _ = app.emit(SUBSYSTEM_READY_EVENT, SubsystemReady { name, error });
+        };
+
+        match self.reinit_wmi() {
+            Ok(wmi) => {
+                forward_wmi_status(app, &wmi);
+                self.restore_firmware_settings(&wmi);
+                ready("wmi", None);
+            }
+            Err(e) => {
+                eprintln!("Warning: WMI initialization failed: {e}");
+                eprintln!("Fan control features will be unavailable.");
+                ready("wmi", Some(e));
+            }
+        }
+
+        let controllers = self.reinit_aura(app);
+        ready(
+            "aura",
+            (controllers == 0 && !self.config.get().openrgb.enabled)
+                .then(|| NoCrateError::DeviceNotPresent(i18n::t(Msg::AuraNotFound).into())),
+        );
+
+        // 加载 WinRing0 驱动，由 SIO / EC / CPU / SMBus 共享同一句柄（非致命）
+        #[cfg(feature = "sio")]
+        {
+            let driver = self.open_driver();
+
+            // 初始化 Super I/O 传感器监控
+            let sio = init_sio(
+                self.simulated,
+                self.wmi.get().as_ref(),
+                &driver,
+                self.quirks,
+                &self.config,
+            )
+            .map(Arc::new);
+            if let Err(e) = &sio {
+                eprintln!("Warning: SIO initialization failed: {e}");
+            }
+            ready("sio", sio.as_ref().err().cloned());
+            self.sio.set(sio);
+
+            self.init_driver_users(&driver, &ready);
+        }
+
+        self.initializing.store(false, Ordering::Release);
+    }
+
+    /// 初始化共享驱动句柄的 EC / CPU / `SMBus` 子系统
+    #[cfg(feature = "sio")]
+    fn init_driver_users(
+        &self,
+        driver: &Result<Arc<DriverHandle>>,
+        ready: &dyn Fn(&'static str, Option<NoCrateError>),
+    ) {
+        // 初始化 EC 传感器
+        let ec = driver
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|d| EcMonitor::init(Arc::clone(d)))
+            .map(Arc::new);
+        if let Err(e) = &ec {
+            eprintln!("Warning: EC initialization failed: {e}");
+        }
+        ready("ec", ec.as_ref().err().cloned());
+        self.ec.set(ec);
+
+        // 初始化 CPU 温度读取
+        let cpu = driver
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|d| CpuMonitor::init(Arc::clone(d)))
+            .map(Arc::new);
+        if let Err(e) = &cpu {
+            eprintln!("Warning: CPU temperature initialization failed: {e}");
+        }
+        ready("cpu", cpu.as_ref().err().cloned());
+        self.cpu.set(cpu);
+
+        // 探测 SMBus 上的 AURA 内存条
+        let dram = driver
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|d| SmbusController::detect(Arc::clone(d)))
+            .map(|smbus| DramController::detect_all(&Arc::new(smbus)));
+        match dram {
+            Ok(modules) => {
+                *self.aura_dram.write() = modules;
+                ready("aura_dram", None);
+            }
+            Err(e) => {
+                eprintln!("Warning: SMBus initialization failed: {e}");
+                ready("aura_dram", Some(e));
+            }
+        }
+    }
+
+    /// 按用户要求安装并加载 `WinRing0` 驱动，记住选择后重新初始化依赖它的子系统
+    #[cfg(feature = "sio")]
+    pub fn install_driver(
+        &self,
+        ready: &dyn Fn(&'static str, Option<NoCrateError>),
+    ) -> Result<()> {
+        let _ = self.config.update(|c| c.driver.enabled = true)?;
+        let driver = self.driver.get().map_or_else(|| self.open_driver(), Ok);
+        if let Err(e) = &driver {
+            return Err(e.clone());
+        }
+        let sio = self.reinit_sio();
+        ready("sio", sio.err());
+        self.init_driver_users(&driver, ready);
+        Ok(())
+    }
+
+    /// 卸载 `WinRing0` 驱动：释放依赖它的子系统，停止并删除驱动服务
+    /// SIO 在 ASUS WMI 可用时改经 WMI 读取
+    #[cfg(feature = "sio")]
+    pub fn uninstall_driver(&self) -> Result<()> {
+        let _ = self.config.update(|c| c.driver.enabled = false)?;
+        let reason = NoCrateError::DeviceNotPresent(i18n::t(Msg::DriverNotInstalled).into());
+        self.sio.clear(reason.clone());
+        self.ec.clear(reason.clone());
+        self.cpu.clear(reason.clone());
+        self.aura_dram.write().clear();
+        let remote = self.driver.get().is_some_and(|d| d.is_remote());
+        self.driver.clear(reason);
+        let _ = self.reinit_sio();
+        // 由 NoCrate 服务持有的驱动不归本进程管理
+        if remote {
+            return Ok(());
+        }
+        driver::remove_service()
+    }
+
+    /// Whether [`Self::init_hardware`] is still running. Subsystems without
+    /// a [`Subsystem`] slot (AURA) are empty until it finishes.
+    pub fn is_initializing(&self) -> bool {
+        self.initializing.load(Ordering::Acquire)
+    }
+
+    /// Re-apply the settings the firmware loses across a shutdown.
+    fn restore_firmware_settings(&self, wmi: &WmiThread) {
+        // The firmware forgets the charge limit on some models after a
+        // full shutdown, so re-apply the saved one
+        if let Some(limit) = self.config.get().battery_charge_limit {
+            if let Err(e) =
+                wmi.execute(move |conn| asus_mgmt::set_battery_charge_limit(conn, limit))
+            {
+                eprintln!("Warning: failed to restore battery charge limit: {e}");
+            }
+        }
+
+        // Power limits and fan curves are reset by the firmware on boot
+        let saved = self.config.get();
+        let source = PowerSource::current();
+        let power_limits = saved.power_limits_for(source);
+        let laptop_fan_curves = saved.laptop_fan_curves_for(source);
+        if !(power_limits.is_empty() && laptop_fan_curves.is_empty()) {
+            let restored = wmi.execute(move |conn| {
+                let profile = asus_mgmt::get_thermal_profile(conn)?;
+                ppt::apply_for_profile(conn, &power_limits, profile)?;
+                asus_mgmt::apply_laptop_fan_curves(conn, &laptop_fan_curves, profile)
+            });
+            if let Err(e) = restored {
+                eprintln!("Warning: failed to restore power limits / fan curves: {e}");
+            }
+        }
+    }
+}
+
+impl AppState {
+    /// Spawn a new WMI thread, replacing the current one on success.
+    /// Without admin rights the calls go through the `NoCrate` service when
+    /// it is running.
+    pub fn reinit_wmi(&self) -> Result<WmiThread> {
+        let wmi = if self.simulated {
+            WmiThread::spawn_simulated()
+        } else if service::usable() {
+            WmiThread::spawn_remote(self.quirks.bad_device_ids, service::connect_wmi)
+        } else {
+            WmiThread::spawn(self.quirks.bad_device_ids)
+        };
+        self.asushw.invalidate();
+        self.wmi.set(wmi.clone());
+        wmi
+    }
+
+    /// Discover the AURA controllers again and replace the current ones,
+    /// carrying effects over like a hotplug reconnect. Returns the number
+    /// of controllers found.
+    pub fn reinit_aura(&self, app: &AppHandle) -> usize {
+        let count = if self.config.get().openrgb.enabled {
+            eprintln!("AURA: OpenRGB passthrough enabled, not opening HID devices");
+            self.aura.lock().clear();
+            0
+        } else {
+            hotplug::reconnect(self).unwrap_or_else(|e| {
+                eprintln!("AURA controller not found: {e}");
+                0
+            })
+        };
+        for ctrl in self.aura.lock().iter() {
+            eprintln!("AURA controller found: {:?}", ctrl.info());
+        }
+        let _ = app.emit(DEVICES_CHANGED_EVENT, ());
+        count
+    }
+
+    /// 重新探测 Super I/O 芯片；驱动未加载时先重新加载 `WinRing0`
+    #[cfg(feature = "sio")]
+    pub fn reinit_sio(&self) -> Result<Arc<SioMonitor>> {
+        let driver = self.driver.get().map_or_else(|| self.open_driver(), Ok);
+        let sio = init_sio(
+            self.simulated,
+            self.wmi.get().as_ref(),
+            &driver,
+            self.quirks,
+            &self.config,
+        )
+        .map(Arc::new);
+        self.sio.set(sio.clone());
+        sio
+    }
+
+    /// 加载 `WinRing0` 驱动并保存结果，模拟模式下使用假驱动
+    /// 没有管理员权限且 `NoCrate` 服务在运行时，改用服务持有的驱动
+    /// 用户未在设置中安装驱动时不加载
+    #[cfg(feature = "sio")]
+    fn open_driver(&self) -> Result<Arc<DriverHandle>> {
+        let driver = if self.simulated {
+            Ok(Arc::new(DriverHandle::simulated()))
+        } else if service::usable() {
+            service::connect_driver().map(Arc::new)
+        } else if !self.config.get().driver.enabled {
+            Err(NoCrateError::DeviceNotPresent(
+                i18n::t(Msg::DriverNotInstalled).into(),
+            ))
+        } else {
+            DriverHandle::open(&self.resource_dir).map(|driver| {
+                driver.set_resident(self.config.get().driver.keep_installed);
+                Arc::new(driver)
+            })
+        };
+        if let Err(e) = &driver {
+            eprintln!("Warning: WinRing0 driver unavailable: {e}");
+        }
+        self.driver.set(driver.clone());
+        driver
+    }
+}
+
+/// 初始化 Super I/O 监控并应用通道配置
+/// 优先经 ASUS WMI 读取，Desktop 后端不可用或读取失败时再用 `WinRing0` 驱动
+#[cfg(feature = "sio")]
+fn init_sio(
+    simulate: bool,
+    wmi: Option<&WmiThread>,
+    driver: &Result<Arc<DriverHandle>>,
+    quirks: &BoardQuirks,
+    config: &ConfigStore,
+) -> Result<SioMonitor> {
+    let with_driver = || {
+        driver.as_ref().map_err(Clone::clone).and_then(|d| {
+            if d.is_simulated() {
+                return Ok(SioMonitor::simulated(Arc::clone(d)));
+            }
+            SioMonitor::init(Arc::clone(d))
+        })
+    };
+    let sio = match wmi {
+        Some(wmi) if !simulate => SioMonitor::init_wmi(wmi.clone()).or_else(|e| {
+            eprintln!("SIO: ASUS WMI 访问不可用（{e}），改用 WinRing0 驱动");
+            with_driver()
+        }),
+        _ => with_driver(),
+    }?;
+    sio.set_channel_config(quirks.sio_channels(config.get().sio_channels));
+    Ok(sio)
 }
@@ -0,0 +1,215 @@
+/// Emergency thermal failsafe.
+///
+/// A background thread watches the unified temperature sensors every
+/// second. When any watched temperature stays at or above the critical
+/// limit for the configured time, every controllable fan is run at 100 %
+/// through fan panic, whatever the fan curves say, and the Performance
+/// profile is optionally selected. Once all temperatures have cooled well
+/// below the limit, the fans and the previous profile are restored. Both
+/// transitions are logged and emitted to the frontend.
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::atk_events::THERMAL_PROFILE_CHANGED_EVENT;
+use crate::commands::fan::apply_thermal_profile;
+use crate::config::ThermalFailsafeConfig;
+use crate::error::Result;
+use crate::hotkeys;
+use crate::i18n::Msg;
+use crate::sensors::{self, SensorKind, SensorSource, UnifiedSensor};
+use crate::state::AppState;
+use crate::wmi::asus_mgmt::{self, ThermalProfile};
+
+/// Interval between checks.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Degrees below the critical limit every watched temperature must reach
+/// before the failsafe releases.
+const RELEASE_HYSTERESIS_C: f32 = 10.0;
+
+/// Readings at or above this are open inputs, not real temperatures
+/// (unconnected Super I/O thermistors read 127 °C).
+const IMPLAUSIBLE_TEMP_C: f32 = 127.0;
+
+/// Event emitted to the frontend with a [`FailsafeAlert`] when the
+/// failsafe triggers or releases.
+pub const THERMAL_FAILSAFE_EVENT: &str = "thermal-failsafe";
+
+/// The failsafe triggered or released.
+#[derive(Debug, Clone, Serialize)]
+pub struct FailsafeAlert {
+    pub active: bool,
+    /// Unified ID of the hottest watched sensor.
+    pub sensor_id: String,
+    /// Display name of the hottest watched sensor.
+    pub name: String,
+    pub temp_c: f32,
+    /// `temp_c` in the unit set in `units`.
+    pub display_temp: f32,
+    pub display_unit: &'static str,
+}
+
+/// What the failsafe changed, so that only that is undone.
+struct Engaged {
+    /// Fan panic was started by the failsafe, not already active.
+    started_panic: bool,
+    /// Profile active before switching to Performance.
+    previous_profile: Option<ThermalProfile>,
+}
+
+/// Spawn the failsafe thread.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-thermal-failsafe".into())
+        .spawn(move || watch_loop(&app));
+    if let Err(e) = spawned {
+        eprintln!("Failsafe: failed to spawn watch thread: {e}");
+    }
+}
+
+fn watch_loop(app: &AppHandle) {
+    let mut critical_since: Option<Instant> = None;
+    let mut engaged: Option<Engaged> = None;
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        let cfg = state.config.get().thermal_failsafe;
+        if !cfg.enabled && engaged.is_none() {
+            critical_since = None;
+            continue;
+        }
+
+        let readings = sensors::collect_only(&state, &watched_sources(&cfg)).sensors;
+        let Some(hottest) = hottest(&cfg, &readings) else {
+            // No reading to hold the timer against: start over once one returns
+            critical_since = None;
+            continue;
+        };
+        let limit = f32::from(cfg.critical_temp_c);
+
+        if engaged.is_none() {
+            if hottest.value < limit {
+                critical_since = None;
+                continue;
+            }
+            let since = *critical_since.get_or_insert_with(Instant::now);
+            if since.elapsed() < Duration::from_secs(cfg.hold_secs) {
+                continue;
+            }
+            eprintln!(
+                "Failsafe: {} ({}) at {:.1} °C for {}s, running all fans at 100%",
+                hottest.name, hottest.id, hottest.value, cfg.hold_secs
+            );
+            engaged = Some(engage(app, &state, &cfg));
+            emit(app, true, hottest);
+        } else if !cfg.enabled || hottest.value < limit - RELEASE_HYSTERESIS_C {
+            eprintln!(
+                "Failsafe: {} ({}) down to {:.1} °C, restoring fans",
+                hottest.name, hottest.id, hottest.value
+            );
+            if let Some(e) = engaged.take() {
+                release(app, &state, &e);
+            }
+            critical_since = None;
+            emit(app, false, hottest);
+        }
+    }
+}
+
+/// Sources that can report a watched sensor; all of them when none are selected.
+fn watched_sources(cfg: &ThermalFailsafeConfig) -> Vec<SensorSource> {
+    if cfg.sensors.is_empty() {
+        return SensorSource::ALL.to_vec();
+    }
+    let mut sources: Vec<SensorSource> = cfg
+        .sensors
+        .iter()
+        .filter_map(|id| SensorSource::from_id(id))
+        .collect();
+    sources.sort_unstable();
+    sources.dedup();
+    sources
+}
+
+/// Hottest plausible reading among the watched temperature sensors.
+fn hottest<'a>(
+    cfg: &ThermalFailsafeConfig,
+    readings: &'a [UnifiedSensor],
+) -> Option<&'a UnifiedSensor> {
+    readings
+        .iter()
+        .filter(|s| s.kind == SensorKind::Temperature && s.duplicate_of.is_none())
+        .filter(|s| cfg.sensors.is_empty() || cfg.sensors.contains(&s.id))
+        .filter(|s| s.value < IMPLAUSIBLE_TEMP_C)
+        .max_by(|a, b| a.value.total_cmp(&b.value))
+}
+
+fn engage(app: &AppHandle, state: &AppState, cfg: &ThermalFailsafeConfig) -> Engaged {
+    let mut previous_profile = None;
+    // Before fan panic: a profile switch loads the profile's fan curves
+    if cfg.switch_to_performance {
+        match switch_profile(app, state, ThermalProfile::Performance) {
+            Ok(previous) => previous_profile = Some(previous),
+            Err(e) => eprintln!("Failsafe: failed to switch to Performance: {e}"),
+        }
+    }
+    let started_panic = hotkeys::set_fan_panic(app, state, Some(true)).unwrap_or_else(|e| {
+        eprintln!("Failsafe: failed to run fans at 100%: {e}");
+        false
+    });
+    Engaged {
+        started_panic,
+        previous_profile,
+    }
+}
+
+fn release(app: &AppHandle, state: &AppState, engaged: &Engaged) {
+    if engaged.started_panic {
+        if let Err(e) = hotkeys::set_fan_panic(app, state, Some(false)) {
+            eprintln!("Failsafe: failed to restore fans: {e}");
+        }
+    }
+    if let Some(previous) = engaged.previous_profile {
+        if let Err(e) = switch_profile(app, state, previous) {
+            eprintln!("Failsafe: failed to restore thermal profile: {e}");
+        }
+    }
+}
+
+/// Switch to `profile` and return the profile that was active. Not saved
+/// as the last selected profile.
+fn switch_profile(
+    app: &AppHandle,
+    state: &AppState,
+    profile: ThermalProfile,
+) -> Result<ThermalProfile> {
+    let wmi = state.wmi.require(Msg::WmiNotInitialized)?;
+    let config = state.config.get();
+    let previous = wmi.execute(move |conn| {
+        let previous = asus_mgmt::get_thermal_profile(conn)?;
+        apply_thermal_profile(conn, &config, profile)?;
+        Ok(previous)
+    })?;
+    let _ = app.emit(THERMAL_PROFILE_CHANGED_EVENT, profile);
+    Ok(previous)
+}
+
+fn emit(app: &AppHandle, active: bool, sensor: &UnifiedSensor) {
+    let _ = app.emit(
+        THERMAL_FAILSAFE_EVENT,
+        FailsafeAlert {
+            active,
+            sensor_id: sensor.id.clone(),
+            name: sensor.name.clone(),
+            temp_c: sensor.value,
+            display_temp: sensor.display_value,
+            display_unit: sensor.display_unit,
+        },
+    );
+}
@@ -0,0 +1,364 @@
+/// Update check against the project's GitHub releases.
+///
+/// `check` fetches the recent releases through `WinHTTP` (which follows the
+/// system proxy settings), picks the newest one by semantic version and
+/// compares it with the running version. The release notes and a download
+/// link, the installer asset if there is one, are returned as they are,
+/// so the frontend can show the changelog. Nothing is downloaded or
+/// installed. With `updates.check_on_startup` the check runs once in the
+/// background after startup and a newer release is announced through
+/// [`UPDATE_AVAILABLE_EVENT`].
+use std::cmp::Ordering;
+use std::ffi::c_void;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use windows::core::{w, HSTRING, PCWSTR};
+use windows::Win32::Networking::WinHttp::{
+    WinHttpCloseHandle, WinHttpConnect, WinHttpOpen, WinHttpOpenRequest, WinHttpQueryHeaders,
+    WinHttpReadData, WinHttpReceiveResponse, WinHttpSendRequest, WinHttpSetTimeouts,
+    INTERNET_DEFAULT_HTTPS_PORT, WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY, WINHTTP_FLAG_SECURE,
+    WINHTTP_QUERY_FLAG_NUMBER, WINHTTP_QUERY_STATUS_CODE,
+};
+
+use crate::error::{NoCrateError, Result};
+use crate::i18n::{self, Msg};
+use crate::state::AppState;
+
+/// Event emitted with an [`UpdateInfo`] when the startup check finds a
+/// newer release.
+pub const UPDATE_AVAILABLE_EVENT: &str = "update-available";
+
+/// Version of the running build.
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Delay before the startup check, so it does not compete with hardware
+/// initialization.
+const STARTUP_DELAY: Duration = Duration::from_secs(15);
+
+/// Timeout of each `WinHTTP` step (resolve, connect, send, receive), in ms.
+const HTTP_TIMEOUT_MS: i32 = 15_000;
+
+/// Outcome of an update check.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    /// Version of the newest release, without the leading `v` of the tag
+    pub latest_version: String,
+    pub update_available: bool,
+    pub prerelease: bool,
+    pub release_name: String,
+    /// Release notes in Markdown
+    pub changelog: String,
+    /// Release page on GitHub
+    pub release_url: String,
+    /// Installer of the release, or the release page if it has none
+    pub download_url: String,
+    /// ISO 8601 publication time
+    pub published_at: Option<String>,
+}
+
+/// The fields used from a GitHub release.
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    published_at: Option<String>,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Fetch the recent releases and compare the newest with the running
+/// version. Drafts, tags that are not semantic versions and, unless
+/// `include_prereleases`, pre-releases are skipped.
+pub fn check(include_prereleases: bool) -> Result<UpdateInfo> {
+    let current = Version::parse(CURRENT_VERSION).ok_or_else(|| {
+        NoCrateError::Unknown(i18n::tf(
+            Msg::UpdateVersionInvalid,
+            &[("version", &CURRENT_VERSION)],
+        ))
+    })?;
+    let body = get_releases()?;
+    let releases: Vec<Release> = serde_json::from_slice(&body).map_err(|e| {
+        NoCrateError::Unknown(i18n::tf(Msg::UpdateResponseInvalid, &[("error", &e)]))
+    })?;
+
+    let (latest, release) = releases
+        .into_iter()
+        .filter(|r| !r.draft && (include_prereleases || !r.prerelease))
+        .filter_map(|r| Some((Version::parse(&r.tag_name)?, r)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .ok_or_else(|| NoCrateError::Unknown(i18n::t(Msg::UpdateNoRelease).into()))?;
+
+    let download_url = installer(&release.assets).map_or_else(
+        || release.html_url.clone(),
+        |a| a.browser_download_url.clone(),
+    );
+    Ok(UpdateInfo {
+        current_version: CURRENT_VERSION.to_string(),
+        latest_version: release.tag_name.trim_start_matches(['v', 'V']).to_string(),
+        update_available: latest > current,
+        prerelease: release.prerelease,
+        release_name: release.name.unwrap_or_else(|| release.tag_name.clone()),
+        changelog: release.body.unwrap_or_default(),
+        release_url: release.html_url,
+        download_url,
+        published_at: release.published_at,
+    })
+}
+
+/// Run the check once in the background after startup, if enabled.
+pub fn spawn(app: AppHandle) {
+    let spawned = thread::Builder::new()
+        .name("nocrate-updates".into())
+        .spawn(move || {
+            thread::sleep(STARTUP_DELAY);
+            let Some(state) = app.try_state::<AppState>() else {
+                return;
+            };
+            let config = state.config.get().updates;
+            if !config.check_on_startup {
+                return;
+            }
+            match check(config.include_prereleases) {
+                Ok(info) if info.update_available => {
+                    eprintln!(
+                        "[Updates] {} is available (running {})",
+                        info.latest_version, info.current_version
+                    );
+                    let _ = app.emit(UPDATE_AVAILABLE_EVENT, &info);
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("[Updates] Check failed: {e}"),
+            }
+        });
+    if let Err(e) = spawned {
+        eprintln!("[Updates] Failed to spawn update check thread: {e}");
+    }
+}
+
+/// The installer among the release assets: the NSIS setup, else the MSI.
+fn installer(assets: &[Asset]) -> Option<&Asset> {
+    let named = |suffix: &str| {
+        assets
+            .iter()
+            .find(|a| a.name.to_ascii_lowercase().ends_with(suffix))
+    };
+    named("-setup.exe").or_else(|| named(".msi"))
+}
+
+/// `GET` the release list from the GitHub API.
+#[allow(unsafe_code)]
+fn get_releases() -> Result<Vec<u8>> {
+    let agent = HSTRING::from(format!("NoCrate/{CURRENT_VERSION}"));
+    unsafe {
+        let session = Handle::new(
+            WinHttpOpen(
+                &agent,
+                WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY,
+                PCWSTR::null(),
+                PCWSTR::null(),
+                0,
+            ),
+            "WinHttpOpen",
+        )?;
+        WinHttpSetTimeouts(
+            session.0,
+            HTTP_TIMEOUT_MS,
+            HTTP_TIMEOUT_MS,
+            HTTP_TIMEOUT_MS,
+            HTTP_TIMEOUT_MS,
+        )
+        .map_err(|e| http_error(&e, "WinHttpSetTimeouts"))?;
+        let connection = Handle::new(
+            WinHttpConnect(
+                session.0,
+                w!("api.github.com"),
+                INTERNET_DEFAULT_HTTPS_PORT,
+                0,
+            ),
+            "WinHttpConnect",
+        )?;
+        let request = Handle::new(
+            WinHttpOpenRequest(
+                connection.0,
+                w!("GET"),
+                w!("/repos/xiaoxiao-cvs/NoCrate/releases?per_page=20"),
+                PCWSTR::null(),
+                PCWSTR::null(),
+                std::ptr::null(),
+                WINHTTP_FLAG_SECURE,
+            ),
+            "WinHttpOpenRequest",
+        )?;
+
+        let headers: Vec<u16> = "Accept: application/vnd.github+json\r\n"
+            .encode_utf16()
+            .collect();
+        WinHttpSendRequest(request.0, Some(&headers), None, 0, 0, 0)
+            .map_err(|e| http_error(&e, "WinHttpSendRequest"))?;
+        WinHttpReceiveResponse(request.0, std::ptr::null_mut())
+            .map_err(|e| http_error(&e, "WinHttpReceiveResponse"))?;
+
+        let mut status = 0u32;
+        let mut len = u32::try_from(std::mem::size_of::<u32>()).unwrap_or(4);
+        WinHttpQueryHeaders(
+            request.0,
+            WINHTTP_QUERY_STATUS_CODE | WINHTTP_QUERY_FLAG_NUMBER,
+            PCWSTR::null(),
+            Some((&raw mut status).cast()),
+            &raw mut len,
+            std::ptr::null_mut(),
+        )
+        .map_err(|e| http_error(&e, "WinHttpQueryHeaders"))?;
+        match status {
+            200 => {}
+            // Unauthenticated requests are limited to 60 per hour
+            403 | 429 => {
+                return Err(NoCrateError::Busy(
+                    "GitHub API rate limit reached, try again later".into(),
+                ))
+            }
+            _ => {
+                return Err(NoCrateError::Unknown(format!(
+                    "GitHub returned HTTP {status}"
+                )))
+            }
+        }
+
+        let mut body = Vec::new();
+        let mut chunk = [0u8; 8192];
+        loop {
+            let mut read = 0u32;
+            WinHttpReadData(
+                request.0,
+                chunk.as_mut_ptr().cast(),
+                u32::try_from(chunk.len()).unwrap_or(u32::MAX),
+                &raw mut read,
+            )
+            .map_err(|e| http_error(&e, "WinHttpReadData"))?;
+            if read == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..read as usize]);
+        }
+        Ok(body)
+    }
+}
+
+fn http_error(err: &windows::core::Error, call: &str) -> NoCrateError {
+    NoCrateError::classify(err, format!("{call} failed: {err}"), NoCrateError::Unknown)
+}
+
+/// `WinHTTP` handle, closed on drop.
+struct Handle(*mut c_void);
+
+impl Handle {
+    /// Wrap the handle returned by `call`, which is null on failure.
+    fn new(raw: *mut c_void, call: &str) -> Result<Self> {
+        if raw.is_null() {
+            Err(http_error(&windows::core::Error::from_win32(), call))
+        } else {
+            Ok(Self(raw))
+        }
+    }
+}
+
+impl Drop for Handle {
+    #[allow(unsafe_code)]
+    fn drop(&mut self) {
+        unsafe {
+            let _ = WinHttpCloseHandle(self.0);
+        }
+    }
+}
+
+/// Semantic version `major.minor.patch[-pre]`; build metadata is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<String>,
+}
+
+impl Version {
+    /// Parse a version or release tag, with or without a leading `v`.
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches(['v', 'V']);
+        let text = text.split_once('+').map_or(text, |(version, _)| version);
+        let (core, pre) = text
+            .split_once('-')
+            .map_or((text, None), |(core, pre)| (core, Some(pre)));
+
+        let mut numbers = core.split('.').map(|n| n.parse::<u64>().ok());
+        let major = numbers.next()??;
+        let minor = numbers.next()??;
+        let patch = numbers.next()??;
+        if numbers.next().is_some() {
+            return None;
+        }
+        let pre = pre.map_or_else(Vec::new, |p| p.split('.').map(str::to_string).collect());
+        if pre.iter().any(String::is_empty) {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                // A pre-release comes before its release
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => compare_pre(&self.pre, &other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Pre-release precedence: numeric identifiers compare as numbers and
+/// before alphanumeric ones, and a shorter list that is a prefix of the
+/// other comes first (`beta` < `beta.2` < `beta.11` < `rc.1`).
+fn compare_pre(a: &[String], b: &[String]) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    a.len().cmp(&b.len())
+}
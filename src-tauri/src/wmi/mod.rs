@@ -1,3 +0,0 @@
-pub mod asus_mgmt;
-pub mod connection;
-pub mod lhm;
@@ -9,6 +9,7 @@ const FanPage = lazy(() => import("./pages/fan-page"));
 const SensorPage = lazy(() => import("./pages/sensor-page"));
 const AuraPage = lazy(() => import("./pages/aura-page"));
 const SettingsPage = lazy(() => import("./pages/settings-page"));
+const OverlayPage = lazy(() => import("./pages/overlay-page"));
 
 export function App() {
   return (
@@ -17,6 +18,15 @@ export function App() {
         <ToastProvider>
           <BrowserRouter>
             <Routes>
+              {/* Separate overlay window, outside the main layout */}
+              <Route
+                path="/overlay"
+                element={
+                  <Suspense>
+                    <OverlayPage />
+                  </Suspense>
+                }
+              />
               <Route element={<AppLayout />}>
                 <Route
                   path="/fan"
@@ -50,14 +50,17 @@ export function DesktopFanPolicyCard({
   onSaveCurve,
 }: DesktopFanPolicyCardProps) {
   const fanName =
-    DESKTOP_FAN_NAMES[policy.fan_type] ?? `风扇 ${policy.fan_type}`;
+    policy.name ??
+    DESKTOP_FAN_NAMES[policy.fan_type] ??
+    `风扇 ${policy.fan_type}`;
 
   // 编辑中的曲线点（只有用户拖拽修改后才会有值）
   const [editingPoints, setEditingPoints] = useState<FanCurvePoint[] | null>(null);
   const isDirty = editingPoints !== null;
 
-  // 根据 fan_type 索引在 SIO 风扇列表中查找对应通道的 RPM
-  const rpmReading = sioFans.find((f) => f.channel === policy.fan_type);
+  // 在 SIO 风扇列表中查找对应通道的 RPM；型号修正表未给出映射时按 fan_type 索引
+  const sioChannel = policy.sio_channel ?? policy.fan_type;
+  const rpmReading = sioFans.find((f) => f.channel === sioChannel);
 
   // 当前显示的曲线点：优先编辑中的，否则用硬件读取的
   const displayPoints = editingPoints ?? curve?.points ?? null;
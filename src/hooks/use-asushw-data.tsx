@@ -2,6 +2,7 @@ import { useCallback, useEffect, useState } from "react";
 
 import { getAsusHWSensors } from "@/lib/tauri-commands";
 import type { AsusHWSensor } from "@/lib/types";
+import { setVisibleInterval } from "@/lib/utils";
 
 /** Polling interval for ASUSHW sensor data (ms). */
 const POLL_INTERVAL = 2000;
@@ -26,7 +27,7 @@ export function useAsusHWData() {
 
   useEffect(() => {
     fetchSensors();
-    const timer = setInterval(fetchSensors, POLL_INTERVAL);
+    const timer = setVisibleInterval(fetchSensors, POLL_INTERVAL);
     return () => clearInterval(timer);
   }, [fetchSensors]);
 
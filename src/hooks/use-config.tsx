@@ -7,8 +7,10 @@ import {
   useState,
   type ReactNode,
 } from "react";
+import { listen } from "@tauri-apps/api/event";
 
 import {
+  CONFIG_CHANGED_EVENT,
   getConfig,
   updateConfig,
   type AppConfig,
@@ -28,7 +30,9 @@ const ConfigContext = createContext<ConfigContextValue | undefined>(undefined);
 const DEFAULT_CONFIG: AppConfig = {
   theme: "system",
   close_to_tray: false,
+  start_minimized: false,
   auto_start: false,
+  restore_on_startup: true,
   fan_poll_interval_ms: 2000,
   last_thermal_profile: 0,
   last_aura_effect: "static",
@@ -36,6 +40,74 @@ const DEFAULT_CONFIG: AppConfig = {
   last_aura_speed: "medium",
   temp_alert_enabled: true,
   temp_alert_threshold: 90,
+  fan_stall: { enabled: true, polls: 3, min_duty_pct: 20, fans: {} },
+  thermal_failsafe: {
+    enabled: true,
+    critical_temp_c: 95,
+    hold_secs: 5,
+    switch_to_performance: false,
+    sensors: [],
+  },
+  sio_channels: { aliases: {}, hidden: [] },
+  sensor_calibration: {},
+  sensor_smoothing: {},
+  sensor_polling: { slow_interval_secs: 10, slow: ["storage/"] },
+  units: { temperature: "celsius" },
+  locale: "zh-CN",
+  eco: { enabled: true, interval_secs: 10, pause_history: false },
+  ambilight_zones: [],
+  openrgb: { enabled: false, host: "127.0.0.1", port: 6742 },
+  lighting_schedule: {
+    enabled: false,
+    start: "23:00",
+    end: "07:00",
+    brightness: 0,
+  },
+  aura_calibration: {},
+  battery_charge_limit: null,
+  power_limits: {},
+  laptop_fan_curves: {},
+  power_source: {
+    auto_switch: false,
+    ac_profile: null,
+    battery_profile: null,
+    battery_power_limits: {},
+    battery_fan_curves: {},
+  },
+  fan_calibration: {},
+  desktop_fan_policies: {},
+  fan_groups: [],
+  lhm: { auto_launch: false, path: null, sensors: [] },
+  hwinfo_enabled: false,
+  rtss: { enabled: false, items: [] },
+  overlay: {
+    visible: false,
+    sensors: [],
+    hotkey: "Ctrl+Shift+O",
+    x: 16,
+    y: 16,
+    update_epsilon: 0.5,
+  },
+  metrics: { enabled: false, bind: "127.0.0.1:9184" },
+  mqtt: {
+    enabled: false,
+    host: "localhost",
+    port: 1883,
+    username: null,
+    password: null,
+    base_topic: "nocrate",
+    discovery_prefix: "homeassistant",
+    interval_secs: 10,
+  },
+  api: { enabled: false, port: 8742, token: "" },
+  hotkeys: {
+    cycle_profile: "Ctrl+Alt+F5",
+    toggle_aura: "Ctrl+Alt+F6",
+    fan_panic: "Ctrl+Alt+F12",
+  },
+  updates: { check_on_startup: true, include_prereleases: false },
+  driver: { enabled: false, keep_installed: false },
+  paused_services: [],
 };
 
 export function ConfigProvider({ children }: { children: ReactNode }) {
@@ -73,6 +145,16 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
     };
   }, [load]);
 
+  // Pick up hand edits of config.json reloaded by the backend
+  useEffect(() => {
+    const unlisten = listen<AppConfig>(CONFIG_CHANGED_EVENT, (event) => {
+      if (mountedRef.current) setConfig(event.payload);
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const update = useCallback(
     async (changes: ConfigUpdate) => {
       // Optimistic update
@@ -9,6 +9,7 @@ import { useCallback, useEffect, useRef, useState } from "react";
 
 import {
   getDesktopFanCurve,
+  getDesktopFanCurves,
   getDesktopFanPolicies,
   getSioSensors,
   setDesktopFanCurve as invokeSetCurve,
@@ -21,6 +22,7 @@ import type {
   FanCurvePoint,
   SioSnapshot,
 } from "@/lib/types";
+import { setVisibleInterval } from "@/lib/utils";
 
 /** Polling interval in milliseconds. */
 const POLL_INTERVAL_MS = 2_000;
@@ -74,24 +76,19 @@ export function useDesktopFanData(): UseDesktopFanDataReturn {
       setSioData(sensorData);
       setError(null);
 
-      // 自动加载每个风扇头当前模式的曲线
-      const curvePromises = policyData.map(async (p) => {
-        const key = curveKey(p.fan_type, p.mode);
-        try {
-          const curve = await getDesktopFanCurve(p.fan_type, p.mode);
-          return curve ? ([key, curve] as const) : null;
-        } catch {
-          return null;
-        }
-      });
-      const curveResults = await Promise.all(curvePromises);
+      // 自动加载每个风扇头当前模式的曲线，一次往返批量读取；
+      // 读取失败时保留已有曲线
+      const curveData = await getDesktopFanCurves(
+        policyData.map((p) => [p.fan_type, p.mode] as [number, DesktopFanMode]),
+      ).catch(() => []);
       if (!mountedRef.current) return;
 
       setCurves((prev) => {
         const next = new Map(prev);
-        for (const result of curveResults) {
-          if (result) next.set(result[0], result[1]);
-        }
+        policyData.forEach((p, i) => {
+          const curve = curveData[i];
+          if (curve) next.set(curveKey(p.fan_type, p.mode), curve);
+        });
         return next;
       });
     } catch (e) {
@@ -105,7 +102,7 @@ export function useDesktopFanData(): UseDesktopFanDataReturn {
   useEffect(() => {
     mountedRef.current = true;
     refresh();
-    const id = setInterval(refresh, POLL_INTERVAL_MS);
+    const id = setVisibleInterval(refresh, POLL_INTERVAL_MS);
     return () => {
       mountedRef.current = false;
       clearInterval(id);
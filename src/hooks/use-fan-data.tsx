@@ -5,13 +5,15 @@
  * so the UI can show skeletons or error messages.
  */
 import { useCallback, useEffect, useRef, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 
 import {
-  getAllFanSpeeds,
-  getThermalProfile,
+  getFanStatus,
   setThermalProfile as invokeSetThermalProfile,
+  THERMAL_PROFILE_CHANGED_EVENT,
 } from "@/lib/tauri-commands";
 import type { FanInfo, ThermalProfile } from "@/lib/types";
+import { setVisibleInterval } from "@/lib/utils";
 
 /** Polling interval in milliseconds. */
 const POLL_INTERVAL_MS = 2_000;
@@ -40,13 +42,10 @@ export function useFanData(): UseFanDataReturn {
 
   const refresh = useCallback(async () => {
     try {
-      const [fanData, profileData] = await Promise.all([
-        getAllFanSpeeds(),
-        getThermalProfile(),
-      ]);
+      const status = await getFanStatus();
       if (!mountedRef.current) return;
-      setFans(fanData);
-      setProfile(profileData);
+      setFans(status.fans);
+      setProfile(status.profile);
       setError(null);
     } catch (e) {
       if (!mountedRef.current) return;
@@ -59,10 +58,18 @@ export function useFanData(): UseFanDataReturn {
   useEffect(() => {
     mountedRef.current = true;
     refresh();
-    const id = setInterval(refresh, POLL_INTERVAL_MS);
+    const id = setVisibleInterval(refresh, POLL_INTERVAL_MS);
+    // Fn+F5 switches the profile without waiting for the next poll
+    const unlisten = listen<ThermalProfile>(
+      THERMAL_PROFILE_CHANGED_EVENT,
+      (event) => {
+        if (mountedRef.current) setProfile(event.payload);
+      },
+    );
     return () => {
       mountedRef.current = false;
       clearInterval(id);
+      void unlisten.then((fn) => fn());
     };
   }, [refresh]);
 
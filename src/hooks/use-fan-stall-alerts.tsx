@@ -0,0 +1,28 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+
+import { FAN_STALL_EVENT, type FanStallAlert } from "@/lib/tauri-commands";
+import { useToast } from "@/hooks/use-toast";
+
+/**
+ * Shows a toast for every fan stall reported by the backend, and when
+ * the fan spins again. Detection and per-fan settings live in the
+ * backend (`fan_stall` config), so alerts also fire while minimized.
+ */
+export function useFanStallAlerts() {
+  const toast = useToast();
+
+  useEffect(() => {
+    const unlisten = listen<FanStallAlert>(FAN_STALL_EVENT, (event) => {
+      const { name, stalled } = event.payload;
+      if (stalled) {
+        toast.error(`${name} 停转`, "风扇应在运转却读到 0 RPM，请检查接线");
+      } else {
+        toast.success(`${name} 已恢复`, "风扇重新开始转动");
+      }
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, [toast]);
+}
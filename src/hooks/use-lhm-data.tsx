@@ -2,6 +2,7 @@ import { useCallback, useEffect, useRef, useState } from "react";
 
 import { getLhmSensors, getLhmStatus } from "@/lib/tauri-commands";
 import type { LhmSensorSnapshot, LhmStatus } from "@/lib/types";
+import { setVisibleInterval } from "@/lib/utils";
 
 /** Polling interval for LHM sensor data (ms). */
 const POLL_INTERVAL = 2000;
@@ -72,7 +73,7 @@ export function useLhmData() {
       return;
     }
 
-    pollingRef.current = setInterval(fetchSensors, POLL_INTERVAL);
+    pollingRef.current = setVisibleInterval(fetchSensors, POLL_INTERVAL);
     return () => {
       if (pollingRef.current) {
         clearInterval(pollingRef.current);
@@ -0,0 +1,36 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+
+import {
+  THERMAL_FAILSAFE_EVENT,
+  type FailsafeAlert,
+} from "@/lib/tauri-commands";
+import { useToast } from "@/hooks/use-toast";
+
+/**
+ * Shows a toast when the backend thermal failsafe runs the fans at full
+ * speed, and when it hands control back to the fan curves.
+ */
+export function useThermalFailsafeAlerts() {
+  const toast = useToast();
+
+  useEffect(() => {
+    const unlisten = listen<FailsafeAlert>(THERMAL_FAILSAFE_EVENT, (event) => {
+      const { name, temp_c, active } = event.payload;
+      if (active) {
+        toast.error(
+          "紧急散热保护已触发",
+          `${name} 达到 ${temp_c.toFixed(0)}°C，所有风扇已切换到 100%`,
+        );
+      } else {
+        toast.success(
+          "紧急散热保护已解除",
+          `${name} 降至 ${temp_c.toFixed(0)}°C，已恢复风扇设置`,
+        );
+      }
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, [toast]);
+}
@@ -7,6 +7,8 @@ import { ElevationDialog } from "@/components/elevation-dialog";
 import { pageVariants, pageTransition, spring } from "@/lib/motion";
 import { useAdminStatus } from "@/hooks/use-admin-status";
 import { useLhmData } from "@/hooks/use-lhm-data";
+import { useFanStallAlerts } from "@/hooks/use-fan-stall-alerts";
+import { useThermalFailsafeAlerts } from "@/hooks/use-thermal-failsafe-alerts";
 import { useTempAlerts } from "@/hooks/use-temp-alerts";
 import { restartAsAdmin } from "@/lib/system-commands";
 import { useState } from "react";
@@ -51,6 +53,8 @@ export function AppLayout() {
   // Global LHM polling — drives temperature alerts
   const { snapshot } = useLhmData();
   useTempAlerts(snapshot);
+  useFanStallAlerts();
+  useThermalFailsafeAlerts();
 
   const showDialog = isAdmin === false && !elevationDismissed;
   const showBanner = isAdmin === false && elevationDismissed;
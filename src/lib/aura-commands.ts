@@ -1,7 +1,7 @@
 /**
  * AURA ARGB type definitions and Tauri invoke wrappers.
  */
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/invoke";
 
 // ─── Types ───────────────────────────────────────────────────
 
@@ -17,13 +17,109 @@ export type AuraEffect =
   | "breathing"
   | "color_cycle"
   | "rainbow"
-  | "spectrum_cycle";
+  | "spectrum_cycle"
+  | "strobe"
+  | "comet"
+  | "wave"
+  | "flash_and_dash";
+
+/** Direction of travelling effects (comet, wave, flash-and-dash). */
+export type AuraDirection = "forward" | "reverse";
 
 export type AuraSpeed = "slow" | "medium" | "fast";
 
+export type AuraChannelKind = "fixed" | "addressable";
+
+/** A lighting channel (onboard zone, 12V RGB or 5V ARGB header). */
+export interface AuraChannelInfo {
+  /** Value to pass as `channel` to the effect / direct commands. */
+  index: number;
+  name: string;
+  kind: AuraChannelKind;
+  /** LED count (for ARGB headers: maximum strip length). */
+  led_count: number;
+}
+
+/** Parsed controller configuration table. */
+export interface AuraConfigTable {
+  argb_headers: number;
+  rgb_headers: number;
+  mainboard_leds: number;
+}
+
 export interface AuraDeviceInfo {
+  /** Index to pass as `device` to the other AURA commands. */
+  index: number;
   pid: number;
   product: string;
+  path: string;
+  firmware: string | null;
+  config: AuraConfigTable | null;
+  channels: AuraChannelInfo[];
+  /** Effects supported on at least one channel; travelling effects need an addressable channel. */
+  effects: AuraEffect[];
+}
+
+/** AURA RGB memory module on the SMBus (requires the `sio` build). */
+export interface DramDeviceInfo {
+  /** Index to pass as `device` to the `auraDram*` commands. */
+  index: number;
+  address: number;
+  name: string;
+  led_count: number;
+}
+
+/** Direct-mode frame stream status. */
+export interface StreamStatus {
+  running: boolean;
+  fps: number;
+}
+
+/** Test pattern for tuning gamma / white-point calibration. */
+export type CalibrationPattern = "white" | "gray" | "ramp" | "primaries";
+
+/** Zone of an OpenRGB controller (passthrough mode). */
+export interface OpenRgbZone {
+  /** Index to pass as `channel` to the software effect commands. */
+  index: number;
+  name: string;
+  led_count: number;
+}
+
+/** Controller listed by the OpenRGB server (passthrough mode). */
+export interface OpenRgbDevice {
+  /** Index to pass as `device` to the software effect commands. */
+  index: number;
+  name: string;
+  zones: OpenRgbZone[];
+}
+
+/** Host-computed effect streamed as direct-mode frames. */
+export type SoftwareEffect =
+  | {
+      type: "temperature";
+      /** Unified sensor ID, e.g. `cpu/temp/package`. */
+      sensor_id: string;
+      /** Temperature (°C) shown as pure blue. */
+      min_temp: number;
+      /** Temperature (°C) shown as pure red. */
+      max_temp: number;
+    }
+  | {
+      type: "audio";
+      /** Gain applied to band levels (1 = unity). */
+      sensitivity: number;
+      /** Fraction of the previous level kept each frame, 0 ≤ x < 1. */
+      smoothing: number;
+    }
+  | {
+      /** Mirror screen edges; regions come from `AppConfig.ambilight_zones`. */
+      type: "ambient";
+    };
+
+export interface RunningEffect {
+  effect: SoftwareEffect;
+  target: { device: number; channel: number };
 }
 
 /** Display metadata for effects. */
@@ -40,6 +136,10 @@ export const AURA_EFFECTS: AuraEffectMeta[] = [
   { id: "color_cycle", label: "循环", hasColor: false },
   { id: "rainbow", label: "彩虹", hasColor: false },
   { id: "spectrum_cycle", label: "光谱", hasColor: false },
+  { id: "strobe", label: "频闪", hasColor: true },
+  { id: "comet", label: "彗星", hasColor: true },
+  { id: "wave", label: "波浪", hasColor: false },
+  { id: "flash_and_dash", label: "闪烁冲刺", hasColor: true },
 ];
 
 export const AURA_SPEEDS: { id: AuraSpeed; label: string }[] = [
@@ -48,6 +148,12 @@ export const AURA_SPEEDS: { id: AuraSpeed; label: string }[] = [
   { id: "fast", label: "快" },
 ];
 
+/**
+ * Emitted after the controller list is rebuilt (hot-plug, USB reset,
+ * resume from sleep). Device indices may change; re-query device info.
+ */
+export const AURA_DEVICES_CHANGED_EVENT = "aura-devices-changed";
+
 // ─── Color helpers ───────────────────────────────────────────
 
 export function rgbToHex(c: RgbColor): string {
@@ -70,28 +176,154 @@ export async function auraIsAvailable(): Promise<boolean> {
   return invoke<boolean>("aura_is_available");
 }
 
-export async function auraGetDeviceInfo(): Promise<AuraDeviceInfo> {
-  return invoke<AuraDeviceInfo>("aura_get_device_info");
+export async function auraGetDeviceInfo(): Promise<AuraDeviceInfo[]> {
+  return invoke<AuraDeviceInfo[]>("aura_get_device_info");
+}
+
+export async function auraGetChannels(
+  device?: number,
+): Promise<AuraChannelInfo[]> {
+  return invoke<AuraChannelInfo[]>("aura_get_channels", { device });
 }
 
 export async function auraSetEffect(
   effect: AuraEffect,
   color: RgbColor,
   speed: AuraSpeed,
+  device?: number,
+  channel?: number,
+  direction?: AuraDirection,
 ): Promise<void> {
-  return invoke<void>("aura_set_effect", { effect, color, speed });
+  return invoke<void>("aura_set_effect", {
+    effect,
+    color,
+    speed,
+    direction,
+    device,
+    channel,
+  });
 }
 
-export async function auraSetStaticColor(color: RgbColor): Promise<void> {
-  return invoke<void>("aura_set_static_color", { color });
+export async function auraSetStaticColor(
+  color: RgbColor,
+  device?: number,
+  channel?: number,
+): Promise<void> {
+  return invoke<void>("aura_set_static_color", { color, device, channel });
 }
 
-export async function auraTurnOff(): Promise<void> {
-  return invoke<void>("aura_turn_off");
+export async function auraTurnOff(
+  device?: number,
+  channel?: number,
+): Promise<void> {
+  return invoke<void>("aura_turn_off", { device, channel });
 }
 
 export async function auraSetDirectColors(
   colors: RgbColor[],
+  device?: number,
+  channel?: number,
+): Promise<void> {
+  return invoke<void>("aura_set_direct_colors", { colors, device, channel });
+}
+
+/** Show a calibration test pattern with the device's current correction. */
+export async function auraShowCalibrationPattern(
+  pattern: CalibrationPattern,
+  device?: number,
+  channel?: number,
+): Promise<void> {
+  return invoke<void>("aura_show_calibration_pattern", {
+    pattern,
+    device,
+    channel,
+  });
+}
+
+/** Persist the current effects to controller flash (survives reboot). */
+export async function auraSaveToFlash(device?: number): Promise<void> {
+  return invoke<void>("aura_save_to_flash", { device });
+}
+
+/**
+ * Start a software effect; replaces any running one. Firmware effect and
+ * direct colour commands stop it.
+ */
+export async function auraStartSoftwareEffect(
+  effect: SoftwareEffect,
+  device?: number,
+  channel?: number,
+): Promise<void> {
+  return invoke<void>("aura_start_software_effect", {
+    effect,
+    device,
+    channel,
+  });
+}
+
+/**
+ * Start the paced direct-mode stream (default 30 FPS, max 60). Frames
+ * pushed afterwards are flushed at that rate, sending only changed packets.
+ */
+export async function auraStartStream(fps?: number): Promise<void> {
+  return invoke<void>("aura_start_stream", { fps });
+}
+
+/** Queue a frame; replaces any frame for the channel not yet sent. */
+export async function auraPushStreamFrame(
+  colors: RgbColor[],
+  device?: number,
+  channel?: number,
+): Promise<void> {
+  return invoke<void>("aura_push_stream_frame", { colors, device, channel });
+}
+
+export async function auraStopStream(): Promise<void> {
+  return invoke<void>("aura_stop_stream");
+}
+
+export async function auraGetStreamStatus(): Promise<StreamStatus> {
+  return invoke<StreamStatus>("aura_get_stream_status");
+}
+
+export async function auraStopSoftwareEffect(): Promise<void> {
+  return invoke<void>("aura_stop_software_effect");
+}
+
+export async function auraGetSoftwareEffect(): Promise<RunningEffect | null> {
+  return invoke<RunningEffect | null>("aura_get_software_effect");
+}
+
+/** List controllers of the configured OpenRGB server (passthrough targets). */
+export async function auraOpenRgbGetDevices(): Promise<OpenRgbDevice[]> {
+  return invoke<OpenRgbDevice[]>("aura_openrgb_get_devices");
+}
+
+// ─── DRAM ────────────────────────────────────────────────────
+
+export async function auraGetDramDevices(): Promise<DramDeviceInfo[]> {
+  return invoke<DramDeviceInfo[]>("aura_get_dram_devices");
+}
+
+export async function auraDramSetEffect(
+  effect: AuraEffect,
+  color: RgbColor,
+  speed: AuraSpeed,
+  device?: number,
+  direction?: AuraDirection,
+): Promise<void> {
+  return invoke<void>("aura_dram_set_effect", {
+    effect,
+    color,
+    speed,
+    direction,
+    device,
+  });
+}
+
+export async function auraDramSetDirectColors(
+  colors: RgbColor[],
+  device?: number,
 ): Promise<void> {
-  return invoke<void>("aura_set_direct_colors", { colors });
+  return invoke<void>("aura_dram_set_direct_colors", { colors, device });
 }
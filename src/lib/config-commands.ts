@@ -1,10 +1,24 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/invoke";
+
+import type {
+  DesktopFanMode,
+  DesktopFanPolicy,
+  FanCalibration,
+  FanCurve,
+  FanCurvePoint,
+  PowerLimits,
+  ThermalProfile,
+} from "@/lib/types";
 
 // ─── Types ───────────────────────────────────────────────────
 export interface AppConfig {
   theme: string;
   close_to_tray: boolean;
+  /** 启动时只创建托盘图标，不显示主窗口（也可用 `--minimized` 参数） */
+  start_minimized: boolean;
   auto_start: boolean;
+  /** 启动时重新应用上次的散热模式、桌面风扇策略与 AURA 灯效 */
+  restore_on_startup: boolean;
   fan_poll_interval_ms: number;
   last_thermal_profile: number;
   last_aura_effect: string;
@@ -12,10 +26,267 @@ export interface AppConfig {
   last_aura_speed: string;
   temp_alert_enabled: boolean;
   temp_alert_threshold: number;
+  fan_stall: FanStallConfig;
+  thermal_failsafe: ThermalFailsafeConfig;
+  sio_channels: SioChannelConfig;
+  sensor_calibration: Record<string, SensorCalibration>;
+  /** 按传感器 ID 的滑动平均窗口（秒），未配置的传感器不平滑 */
+  sensor_smoothing: Record<string, number>;
+  sensor_polling: PollingTiers;
+  units: UnitsConfig;
+  /** 后端文本（错误信息、修复建议、内置传感器名称、托盘菜单）的语言 */
+  locale: Locale;
+  eco: EcoConfig;
+  ambilight_zones: AmbilightZone[];
+  openrgb: OpenRgbConfig;
+  lighting_schedule: LightingSchedule;
+  aura_calibration: Record<string, ColorCalibration>;
+  battery_charge_limit: number | null;
+  power_limits: Partial<Record<ThermalProfile, PowerLimits>>;
+  laptop_fan_curves: Partial<Record<ThermalProfile, FanCurve[]>>;
+  /** 电池供电时的设置与插拔电源时的切换；上面两项为接通电源时的设置 */
+  power_source: PowerSourceConfig;
+  /** Q-Fan 校准结果，键为桌面风扇头索引 */
+  fan_calibration: Record<number, FanCalibration>;
+  /** 在应用中最后设置的桌面风扇策略，键为风扇头索引，启动时重新应用 */
+  desktop_fan_policies: Record<number, DesktopFanPolicy>;
+  fan_groups: FanGroup[];
+  lhm: LhmConfig;
+  /** 运行 HWiNFO 时从其共享内存读取传感器 */
+  hwinfo_enabled: boolean;
+  rtss: RtssConfig;
+  overlay: OverlayConfig;
+  metrics: MetricsConfig;
+  mqtt: MqttConfig;
+  api: ApiConfig;
+  hotkeys: HotkeyConfig;
+  updates: UpdateConfig;
+  driver: DriverConfig;
+  paused_services: PausedService[];
+}
+
+/** Super I/O 通道别名与隐藏配置，键为 `fan/<channel>` 或 `temp/<channel>` */
+export interface SioChannelConfig {
+  aliases: Record<string, string>;
+  hidden: string[];
+}
+
+/** 窗口隐藏到托盘时降低轮询频率：无后台功能需要实时读数时每 interval_secs 秒读取一次；pause_history 停止历史记录采样 */
+export interface EcoConfig {
+  enabled: boolean;
+  interval_secs: number;
+  pause_history: boolean;
+}
+
+/** 轮询分级：ID 以 slow 中任一前缀开头的传感器每 slow_interval_secs 秒才读取一次 */
+export interface PollingTiers {
+  slow_interval_secs: number;
+  slow: string[];
+}
+
+/** 界面语言 */
+export type Locale = "zh-CN" | "en";
+
+/** 温度显示单位 */
+export type TemperatureUnit = "celsius" | "fahrenheit";
+
+/** 显示单位偏好：后端据此给读数附上 display_value / display_unit，原始值始终为 °C */
+export interface UnitsConfig {
+  temperature: TemperatureUnit;
+}
+
+/** 传感器校准：显示值 = 原始值 × scale + offset，键为统一传感器 ID */
+export interface SensorCalibration {
+  offset: number;
+  scale: number;
+}
+
+/** 氛围灯区域采样的屏幕边缘 */
+export type ScreenEdge = "top" | "bottom" | "left" | "right";
+
+/** 将一个 AURA 通道映射到屏幕边缘的一段，start / end 为沿边缘的比例 (0–1) */
+export interface AmbilightZone {
+  device: number;
+  channel: number;
+  edge: ScreenEdge;
+  start: number;
+  end: number;
+  /** LED 0 位于 end 一侧 */
+  reverse: boolean;
+}
+
+/** LibreHardwareMonitor 集成：WMI 命名空间不可用时自动启动 LHM，path 为空则搜索常见安装目录 */
+export interface LhmConfig {
+  auto_launch: boolean;
+  path: string | null;
+  /** 仪表盘轮询的传感器 ID，空数组表示全部 */
+  sensors: string[];
+}
+
+/** RTSS 叠加层的一行：统一传感器（label 为空则用传感器名称）或当前风扇配置 */
+export type RtssItem =
+  | { type: "sensor"; id: string; label: string | null }
+  | { type: "thermal_profile" };
+
+/** RivaTuner Statistics Server 游戏内叠加层输出，items 按顺序逐行显示 */
+export interface RtssConfig {
+  enabled: boolean;
+  items: RtssItem[];
+}
+
+/** 置顶叠加层窗口：sensors 为显示的统一传感器 ID（空数组表示全部温度与风扇），hotkey 为切换快捷键，x / y 为逻辑像素位置 */
+export interface OverlayConfig {
+  visible: boolean;
+  sensors: string[];
+  hotkey: string | null;
+  x: number;
+  y: number;
+  /** 读数变化超过该值才推送给叠加层（0 = 每次变化都推送） */
+  update_epsilon: number;
+}
+
+/** 风扇停转检测：占空比高于 min_duty_pct（或占空比未知）时连续 polls 次读到 0 RPM 即报警 */
+export interface FanStallConfig {
+  enabled: boolean;
+  polls: number;
+  min_duty_pct: number;
+  /** 被监视的风扇，键为统一风扇传感器 ID；未列出的风扇不检测 */
+  fans: Record<string, FanStallWatch>;
+}
+
+/** 紧急散热保护：任一被监视温度持续 hold_secs 秒不低于 critical_temp_c 时全部风扇 100%，可选切换到性能模式 */
+export interface ThermalFailsafeConfig {
+  enabled: boolean;
+  critical_temp_c: number;
+  hold_secs: number;
+  switch_to_performance: boolean;
+  /** 被监视的统一温度传感器 ID，为空时监视全部 */
+  sensors: string[];
+}
+
+/** 单个风扇的停转检测设置，duty_sensor 为对应的占空比（fan_percent）传感器 ID */
+export interface FanStallWatch {
+  enabled: boolean;
+  duty_sensor: string | null;
+}
+
+/** 按电源区分的笔记本散热设置：未设置电池值的模式沿用接通电源时的功耗墙与风扇曲线 */
+export interface PowerSourceConfig {
+  /** 插拔电源时自动应用对应电源的设置 */
+  auto_switch: boolean;
+  /** 接通电源时切换到的模式，null 为保持当前模式 */
+  ac_profile: ThermalProfile | null;
+  /** 电池供电时切换到的模式，null 为保持当前模式 */
+  battery_profile: ThermalProfile | null;
+  battery_power_limits: Partial<Record<ThermalProfile, PowerLimits>>;
+  battery_fan_curves: Partial<Record<ThermalProfile, FanCurve[]>>;
+}
+
+/** Prometheus 指标端点：启用后在 bind 地址提供 `GET /metrics`，改为 `0.0.0.0:<端口>` 可供局域网抓取 */
+export interface MetricsConfig {
+  enabled: boolean;
+  bind: string;
+}
+
+/** MQTT 发布与 Home Assistant 自动发现：主题为 `<base_topic>/<计算机名>/…`，interval_secs 为传感器发布间隔 */
+export interface MqttConfig {
+  enabled: boolean;
+  host: string;
+  port: number;
+  username: string | null;
+  password: string | null;
+  base_topic: string;
+  discovery_prefix: string;
+  interval_secs: number;
+}
+
+/** 本地 HTTP/WebSocket 控制 API（仅监听 127.0.0.1）：请求需带 `Authorization: Bearer <token>` 或 `?token=`；token 为空时启用后自动生成 */
+export interface ApiConfig {
+  enabled: boolean;
+  port: number;
+  token: string;
+}
+
+/** 全局快捷键（游戏全屏时同样有效），null 或空字符串表示不注册：cycle_profile 切换到下一个风扇配置，toggle_aura 开关全部 AURA 灯光，fan_panic 让所有风扇 100% 运行，再按一次恢复 */
+export interface HotkeyConfig {
+  cycle_profile: string | null;
+  toggle_aura: string | null;
+  fan_panic: string | null;
+}
+
+/** 检查 GitHub 上的新版本：check_on_startup 启动后在后台检查一次，include_prereleases 是否把预发布版本作为更新 */
+export interface UpdateConfig {
+  check_on_startup: boolean;
+  include_prereleases: boolean;
+}
+
+/**
+ * WinRing0 驱动：enabled 表示用户已在设置中安装，未安装时启动不加载驱动；
+ * keep_installed 退出时保留驱动服务（按需启动），只关闭设备句柄
+ */
+export interface DriverConfig {
+  enabled: boolean;
+  keep_installed: boolean;
+}
+
+/** 作为一组控制的桌面风扇头，每个风扇头最多属于一组 */
+export interface FanGroup {
+  name: string;
+  /** 成员风扇头索引（FanType） */
+  fan_types: number[];
+  /** 最近一次由 setFanGroupCurve 写入所有成员的曲线 */
+  curve: FanGroupCurve | null;
+}
+
+/** 风扇组共用的 8 点曲线 */
+export interface FanGroupCurve {
+  mode: DesktopFanMode;
+  points: FanCurvePoint[];
+}
+
+/** 经用户同意后停止并禁用的 Windows 服务，恢复前一直保留 */
+export interface PausedService {
+  name: string;
+  /** 禁用前的启动类型（SERVICE_START_TYPE） */
+  start_type: number;
+  /** 暂停时是否在运行，恢复时会重新启动 */
+  was_running: boolean;
+}
+
+/** OpenRGB 直通：启用后不再打开 AURA HID 设备，软件灯效经 OpenRGB SDK 服务器输出 */
+export interface OpenRgbConfig {
+  enabled: boolean;
+  host: string;
+  port: number;
+}
+
+/** 灯光定时：每天在 start–end（本地时间 HH:MM，可跨午夜）内将 AURA 亮度降至 brightness%（0 为关闭） */
+export interface LightingSchedule {
+  enabled: boolean;
+  start: string;
+  end: string;
+  brightness: number;
+}
+
+/** AURA 颜色校准：分量先做 gamma 再乘白点系数 (0–1)，键为 4 位小写十六进制 USB PID */
+export interface ColorCalibration {
+  gamma: number;
+  red: number;
+  green: number;
+  blue: number;
+}
+
+/** 配置轮换备份，index 1 为最新，modified 为 Unix 秒 */
+export interface ConfigBackup {
+  index: number;
+  modified: number;
 }
 
 export type ConfigUpdate = Partial<AppConfig>;
 
+/** 外部修改 config.json 并重新加载后触发的事件，payload 为新的 AppConfig */
+export const CONFIG_CHANGED_EVENT = "config-changed";
+
 // ─── Invoke Wrappers ─────────────────────────────────────────
 export async function getConfig(): Promise<AppConfig> {
   return invoke<AppConfig>("get_config");
@@ -26,3 +297,12 @@ export async function updateConfig(
 ): Promise<AppConfig> {
   return invoke<AppConfig>("update_config", updates);
 }
+
+export async function listConfigBackups(): Promise<ConfigBackup[]> {
+  return invoke<ConfigBackup[]>("list_config_backups");
+}
+
+/** 从第 index 个备份恢复配置并立即生效 */
+export async function restoreConfigBackup(index: number): Promise<AppConfig> {
+  return invoke<AppConfig>("restore_config_backup", { index });
+}
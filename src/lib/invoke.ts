@@ -0,0 +1,90 @@
+import { invoke as tauriInvoke, type InvokeArgs } from "@tauri-apps/api/core";
+
+/** 后端 NoCrateError 的 code，前端据此给出处理建议（如"以管理员身份运行"） */
+export type ErrorCode =
+  | "wmi"
+  | "windows_api"
+  | "hid"
+  | "config"
+  | "sio"
+  | "storage"
+  | "gpu"
+  | "driver_blocked"
+  | "access_denied"
+  | "device_not_present"
+  | "timeout"
+  | "busy"
+  | "invalid_curve"
+  | "unknown";
+
+/** 风扇曲线违反的一条规则，`index` 为从 0 开始的点序号 */
+export type CurveViolation =
+  | { kind: "temp_not_ascending"; index: number; temp_c: number; prev_temp_c: number }
+  | {
+      kind: "duty_descending";
+      index: number;
+      duty_pct: number;
+      prev_duty_pct: number;
+    }
+  | { kind: "temp_out_of_range"; index: number; temp_c: number }
+  | { kind: "duty_out_of_range"; index: number; duty_pct: number }
+  | {
+      kind: "below_stall_duty";
+      index: number;
+      duty_pct: number;
+      min_duty_pct: number;
+    }
+  | { kind: "no_top_point"; temp_c: number }
+  | { kind: "empty" };
+
+/** 后端命令返回的错误；`String(e)` 仍得到错误信息本身 */
+export class BackendError extends Error {
+  readonly code: ErrorCode;
+  /** 仅 `invalid_curve` 错误带有，列出曲线的全部违规项 */
+  readonly violations: CurveViolation[];
+
+  constructor(
+    code: ErrorCode,
+    message: string,
+    violations: CurveViolation[] = [],
+  ) {
+    super(message);
+    this.name = "BackendError";
+    this.code = code;
+    this.violations = violations;
+  }
+
+  override toString(): string {
+    return this.message;
+  }
+}
+
+function isStructured(e: unknown): e is {
+  code: ErrorCode;
+  message: string;
+  violations?: CurveViolation[];
+} {
+  return (
+    typeof e === "object" &&
+    e !== null &&
+    typeof (e as { code?: unknown }).code === "string" &&
+    typeof (e as { message?: unknown }).message === "string"
+  );
+}
+
+/** 同 Tauri 的 invoke，但把后端的 `{ code, message }` 错误转为 BackendError */
+export async function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await tauriInvoke<T>(cmd, args);
+  } catch (e) {
+    if (isStructured(e)) {
+      throw new BackendError(e.code, e.message, e.violations);
+    }
+    throw e;
+  }
+}
+
+/** 错误的 code；非后端错误时为 null */
+export function errorCode(e: unknown): ErrorCode | null {
+  return e instanceof BackendError ? e.code : null;
+}
@@ -1,4 +1,7 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke, type ErrorCode } from "@/lib/invoke";
+
+import type { PausedService } from "@/lib/config-commands";
+import type { BoardSummary, UnifiedSensor } from "@/lib/types";
 
 export async function isAdmin(): Promise<boolean> {
   return invoke<boolean>("is_admin");
@@ -11,3 +14,300 @@ export async function isAdmin(): Promise<boolean> {
 export async function restartAsAdmin(): Promise<void> {
   return invoke<void>("restart_as_admin");
 }
+
+/** 开机自启的方式：task 为以最高权限运行的计划任务，registry 为注册表 Run 项（登录时会弹出 UAC） */
+export type AutoStartMethod = "task" | "registry";
+
+/** 当前开机自启的方式，未启用时为 null */
+export async function getAutoStartMethod(): Promise<AutoStartMethod | null> {
+  return invoke<AutoStartMethod | null>("get_auto_start_method");
+}
+
+/** 子系统状态：initializing 为启动后仍在后台初始化，degraded 为功能受限或正在恢复，disabled 为在设置中关闭 */
+export type SubsystemState =
+  | "ready"
+  | "initializing"
+  | "degraded"
+  | "unavailable"
+  | "disabled";
+
+/** 单个子系统（wmi / lhm / aura / storage / gpu / sio / ec / cpu）的状态 */
+export interface SubsystemStatus {
+  name: string;
+  state: SubsystemState;
+  /** 未就绪的原因（如已知） */
+  error: { code: ErrorCode; message: string } | null;
+  /** 检测结果，如 Super I/O 芯片型号、LHM 传感器数量 */
+  detail: string | null;
+  /** 建议的处理方式，可直接显示 */
+  remediation: string | null;
+}
+
+/** 全部子系统的状态 */
+export interface SystemStatus {
+  /** 当前进程是否以管理员身份运行 */
+  admin: boolean;
+  /** 是否以 --simulate 启动 */
+  simulated: boolean;
+  subsystems: SubsystemStatus[];
+}
+
+/** 一次读取全部子系统的状态、错误与处理建议 */
+export async function getSystemStatus(): Promise<SystemStatus> {
+  return invoke<SystemStatus>("get_system_status");
+}
+
+/** 子系统完成后台初始化时推送的事件，payload 为 SubsystemReady */
+export const SUBSYSTEM_READY_EVENT = "subsystem-ready";
+
+/** 后台初始化结果：name 同 SubsystemStatus（另有 aura_dram），error 为 null 表示已就绪 */
+export interface SubsystemReady {
+  name: string;
+  error: { code: ErrorCode; message: string } | null;
+}
+
+/** 可在运行时重新初始化的子系统 */
+export type ReinitSubsystem = "wmi" | "sio" | "aura";
+
+/** 重新初始化子系统（如安装驱动或以管理员身份重启后），失败时保留正在运行的实例 */
+export async function reinitSubsystem(name: ReinitSubsystem): Promise<void> {
+  return invoke<void>("reinit_subsystem", { name });
+}
+
+/** 读取主板型号及已应用的修正表（风扇头名称、SIO 映射、屏蔽的设备 ID） */
+export async function getBoardInfo(): Promise<BoardSummary> {
+  return invoke<BoardSummary>("get_board_info");
+}
+
+/** 显示或隐藏传感器叠加层窗口，返回切换后是否可见 */
+export async function toggleOverlay(): Promise<boolean> {
+  return invoke<boolean>("toggle_overlay");
+}
+
+/** 后端推送给叠加层窗口的传感器事件，payload 为 OverlayUpdate */
+export const OVERLAY_SENSORS_EVENT = "overlay-sensors";
+
+/** 叠加层更新：full 为 true 时是按显示顺序的完整列表，否则只含变化超过阈值的传感器，按 ID 合并 */
+export interface OverlayUpdate {
+  full: boolean;
+  sensors: UnifiedSensor[];
+}
+
+/** 一行后端日志（stderr 输出），time_ms 为 Unix 毫秒时间戳 */
+export interface LogLine {
+  time_ms: number;
+  text: string;
+}
+
+/** 读取最近 n 行后端日志，按时间从旧到新 */
+export async function getRecentLogs(n: number): Promise<LogLine[]> {
+  return invoke<LogLine[]>("get_recent_logs", { n });
+}
+
+/** 后端每输出一行日志触发的事件，payload 为 LogLine */
+export const LOG_LINE_EVENT = "log-line";
+
+/** 重新生成本地控制 API 的 token 并返回新值，旧 token 的连接会被断开 */
+export async function regenerateApiToken(): Promise<string> {
+  return invoke<string>("regenerate_api_token");
+}
+
+/** 设备 ID 扫描范围（含两端） */
+export interface DeviceIdRange {
+  start: number;
+  end: number;
+}
+
+/** 扫描过程中推送的事件，payload 为 DeviceScanEvent */
+export const DEVICE_SCAN_EVENT = "device-scan";
+
+/** 扫描事件：hit 为有返回值的设备 ID，progress 每 64 个 ID 一次，finished 为扫描结束（含取消与出错） */
+export type DeviceScanEvent =
+  | { kind: "hit"; device_id: number; value: number }
+  | { kind: "progress"; scanned: number; total: number }
+  | {
+      kind: "finished";
+      scanned: number;
+      total: number;
+      hits: number;
+      errors: number;
+      cancelled: boolean;
+      error: { code: ErrorCode; message: string } | null;
+    };
+
+/**
+ * 以 DSTS 逐个读取设备 ID，结果通过 DEVICE_SCAN_EVENT 推送
+ * ranges 省略时扫描 test_asio 示例的默认范围；intervalMs 为两次调用间隔（最少 2 ms）
+ */
+export async function startDeviceScan(
+  ranges?: DeviceIdRange[],
+  intervalMs?: number,
+): Promise<void> {
+  return invoke<void>("start_device_scan", { ranges, intervalMs });
+}
+
+/** 取消正在进行的设备 ID 扫描 */
+export async function cancelDeviceScan(): Promise<void> {
+  return invoke<void>("cancel_device_scan");
+}
+
+/** WMI 方法的输入/输出参数；id 为 ID 限定符（参数顺序），values 为枚举参数声明的取值 */
+export interface WmiParamInfo {
+  name: string;
+  cim_type: string;
+  id: number | null;
+  values: string[];
+}
+
+/** WMI 方法签名；method_id 为 WmiMethodId 限定符，outputs 含 ReturnValue */
+export interface WmiMethodInfo {
+  name: string;
+  method_id: number | null;
+  inputs: WmiParamInfo[];
+  outputs: WmiParamInfo[];
+}
+
+/** 一个 ASUS WMI 类的方法列表，固件未定义该类时 error 为原因、methods 为空 */
+export interface WmiClassInfo {
+  class_name: string;
+  methods: WmiMethodInfo[];
+  error: string | null;
+}
+
+/** 列出 ASUSManagement / ASUSHW / ASUSATKWMI_WMNB 的方法与参数签名，只读取类定义，不调用方法 */
+export async function exploreWmiClasses(): Promise<WmiClassInfo[]> {
+  return invoke<WmiClassInfo[]>("explore_wmi_classes");
+}
+
+/** 冲突软件影响的范围 */
+export type ConflictArea = "fans" | "aura" | "sensors";
+
+/** 正在运行、会与 NoCrate 争用同一硬件的软件（如 Armoury Crate），services / processes 为检测到的服务与进程名 */
+export interface Conflict {
+  id: string;
+  name: string;
+  services: string[];
+  processes: string[];
+  affects: ConflictArea[];
+  hint: string;
+}
+
+/** 启动后检测到冲突软件时触发的事件，payload 为 Conflict[] */
+export const CONFLICTS_EVENT = "conflicts-detected";
+
+/** 重新检测正在运行的冲突软件 */
+export async function getConflicts(): Promise<Conflict[]> {
+  return invoke<Conflict[]>("get_conflicts");
+}
+
+/**
+ * 停止并禁用 Armoury Crate 相关服务（含 asusm），避免其后台代理反复覆盖风扇策略。
+ * 服务在恢复前保持禁用（重启后亦然）。调用前界面必须先征得用户明确同意；需要管理员权限。
+ * 返回当前已暂停的服务列表。
+ */
+export async function pauseConflictingServices(): Promise<PausedService[]> {
+  return invoke<PausedService[]>("pause_conflicting_services");
+}
+
+/** 恢复已暂停服务的启动类型，并重新启动暂停前在运行的服务 */
+export async function restorePausedServices(): Promise<void> {
+  return invoke<void>("restore_paused_services");
+}
+
+/** 更新检查结果 */
+export interface UpdateInfo {
+  current_version: string;
+  /** 最新版本号，不含标签前缀 v */
+  latest_version: string;
+  update_available: boolean;
+  prerelease: boolean;
+  release_name: string;
+  /** 更新日志（Markdown） */
+  changelog: string;
+  /** GitHub 发布页 */
+  release_url: string;
+  /** 安装包下载地址，发布中没有安装包时为发布页 */
+  download_url: string;
+  /** 发布时间（ISO 8601） */
+  published_at: string | null;
+}
+
+/** 启动检查发现新版本时触发的事件，payload 为 UpdateInfo */
+export const UPDATE_AVAILABLE_EVENT = "update-available";
+
+/** 查询 GitHub 最新发布并与当前版本比较 */
+export async function checkForUpdates(): Promise<UpdateInfo> {
+  return invoke<UpdateInfo>("check_for_updates");
+}
+
+/** NoCrate 后台服务状态 */
+export interface ServiceStatus {
+  installed: boolean;
+  running: boolean;
+}
+
+/** 查询 NoCrate 后台服务是否已安装、是否在运行 */
+export async function getServiceStatus(): Promise<ServiceStatus> {
+  return invoke<ServiceStatus>("get_service_status");
+}
+
+/**
+ * 为当前用户安装并启动 NoCrate 后台服务（未提权时弹出 UAC 提示）。
+ * 服务以 LocalSystem 持有 WinRing0 与 WMI，界面随后无需管理员权限；
+ * 安装后调用 reinitSubsystem 或重启程序以切换到服务。
+ */
+export async function installService(): Promise<ServiceStatus> {
+  return invoke<ServiceStatus>("install_service");
+}
+
+/** 停止并卸载 NoCrate 后台服务（未提权时弹出 UAC 提示） */
+export async function uninstallService(): Promise<ServiceStatus> {
+  return invoke<ServiceStatus>("uninstall_service");
+}
+
+/** WinRing0 驱动状态 */
+export interface DriverStatus {
+  /** 用户已在设置中安装驱动 */
+  enabled: boolean;
+  /** 退出时保留驱动服务 */
+  keep_installed: boolean;
+  /** 驱动服务已注册 */
+  installed: boolean;
+  /** 驱动服务在运行，即驱动已加载 */
+  running: boolean;
+  /** 已加载驱动报告的版本号 */
+  version: string | null;
+  /** 服务登记的驱动文件路径 */
+  image_path: string | null;
+  /** 本次构建内嵌了驱动 */
+  embedded: boolean;
+  /** Windows 阻止了驱动（易受攻击驱动阻止列表或内存完整性） */
+  blocked: boolean;
+  /** 驱动由 NoCrate 后台服务持有 */
+  via_service: boolean;
+  /** 驱动无法打开的原因 */
+  error: { code: ErrorCode; message: string } | null;
+}
+
+/** 查询 WinRing0 驱动是否已安装、已加载 */
+export async function getDriverStatus(): Promise<DriverStatus> {
+  return invoke<DriverStatus>("get_driver_status");
+}
+
+/** 设置退出时是否保留驱动服务；保留时下次启动只需打开设备，仍可用 uninstallDriver 卸载 */
+export async function setDriverResident(keepInstalled: boolean): Promise<DriverStatus> {
+  return invoke<DriverStatus>("set_driver_resident", { keepInstalled });
+}
+
+/**
+ * 安装并加载 WinRing0 驱动，随后初始化依赖它的传感器（逐个发出 subsystem-ready 事件）。
+ * 选择会被记住，之后启动时自动加载。
+ */
+export async function installDriver(): Promise<DriverStatus> {
+  return invoke<DriverStatus>("install_driver");
+}
+
+/** 释放依赖驱动的传感器并停止、删除驱动服务，重新安装前不再加载 */
+export async function uninstallDriver(): Promise<DriverStatus> {
+  return invoke<DriverStatus>("uninstall_driver");
+}
@@ -4,22 +4,53 @@
  * Each function corresponds to a `#[tauri::command]` defined in
  * `src-tauri/src/commands/fan.rs`.
  */
-import { invoke } from "@tauri-apps/api/core";
+import type { RunningEffect, StreamStatus } from "@/lib/aura-commands";
+import type { FanGroupCurve } from "@/lib/config-commands";
+import { invoke } from "@/lib/invoke";
 
 import type {
   AsusHWSensor,
+  CpuSnapshot,
+  CurvePreviewPoint,
   DesktopFanCurve,
+  DesktopFanCurve3,
   DesktopFanMode,
   DesktopFanPolicy,
+  DesktopFanSources,
+  EcSnapshot,
+  FanCalibrationStatus,
   FanCurve,
+  FanCurvePoint,
   FanInfo,
+  FanStatus,
   FanTarget,
+  GpuDeviceSnapshot,
+  GpuMode,
+  GpuModeChange,
+  GpuModeInfo,
+  HistorySeries,
+  HwinfoSnapshot,
+  LhmHardware,
+  LhmSensor,
   LhmSensorSnapshot,
   LhmStatus,
+  LowLimitWarning,
+  LpcDecodeDump,
+  PowerLimits,
+  PowerLimitsInfo,
+  PowerSource,
+  SensorSourceError,
+  SensorStatsSnapshot,
+  SioDiagnostics,
+  SioRegisterDump,
   SioSnapshot,
   SioStatus,
+  StorageTempReading,
   ThermalProfile,
+  UnifiedSensor,
+  UnifiedSensorSnapshot,
   WmiBackend,
+  WmiHealth,
 } from "@/lib/types";
 
 /** Read a single fan's RPM. */
@@ -32,6 +63,74 @@ export async function getAllFanSpeeds(): Promise<FanInfo[]> {
   return invoke<FanInfo[]>("get_all_fan_speeds");
 }
 
+/** Read all fan speeds and the thermal profile in one round-trip (for polling). */
+export async function getFanStatus(): Promise<FanStatus> {
+  return invoke<FanStatus>("get_fan_status");
+}
+
+/** AURA lighting state as shown on the dashboard. */
+export interface AuraSummary {
+  available: boolean;
+  /** Last firmware effect set from the app, e.g. "rainbow". */
+  effect: string;
+  /** Last colour set from the app as #RRGGBB. */
+  color: string;
+  software_effect: RunningEffect | null;
+  stream: StreamStatus;
+}
+
+/** Everything the dashboard shows, assembled on the backend. */
+export interface DashboardSnapshot {
+  backend: WmiBackend;
+  /** null if WMI is unavailable or the backend has no thermal profile. */
+  profile: ThermalProfile | null;
+  /** Fan headers read through ASUS WMI. */
+  fans: FanInfo[];
+  /** Fan speed / duty sensors of the other sources (Super I/O, EC, GPU, ...). */
+  fan_sensors: UnifiedSensor[];
+  /** Temperature sensors without duplicates. */
+  temperatures: UnifiedSensor[];
+  sensor_errors: SensorSourceError[];
+  aura: AuraSummary;
+}
+
+/** Read backend type, profile, fans, temperatures and AURA state in one invoke. */
+export async function getDashboardSnapshot(): Promise<DashboardSnapshot> {
+  return invoke<DashboardSnapshot>("get_dashboard_snapshot");
+}
+
+/** What the detected hardware supports; gate features on this instead of the backend string. */
+export interface Capabilities {
+  backend: WmiBackend;
+  is_laptop: boolean;
+  is_desktop: boolean;
+  /** The firmware reports a switchable thermal profile. */
+  can_set_thermal_profile: boolean;
+  /** Desktop fan headers accept a manual duty curve. */
+  can_set_fan_duty: boolean;
+  /** Fan curves can be written (laptop curve banks or desktop headers). */
+  has_curve_write: boolean;
+  has_fan_calibration: boolean;
+  has_battery_limit: boolean;
+  /** Laptop GPU MUX or dGPU switch. */
+  has_gpu_mode: boolean;
+  /** Laptop PPT power limits. */
+  has_power_limits: boolean;
+  has_asushw_sensors: boolean;
+  has_sio: boolean;
+  has_ec: boolean;
+  has_aura: boolean;
+  /** AURA memory modules on the SMBus. */
+  has_aura_dram: boolean;
+  has_gpu_sensors: boolean;
+  has_storage_sensors: boolean;
+}
+
+/** Probe which features the detected backends support. */
+export async function getCapabilities(): Promise<Capabilities> {
+  return invoke<Capabilities>("get_capabilities");
+}
+
 /** Get the currently active thermal profile. */
 export async function getThermalProfile(): Promise<ThermalProfile> {
   return invoke<ThermalProfile>("get_thermal_profile");
@@ -51,6 +150,32 @@ export async function getDefaultFanCurve(
   return invoke<FanCurve>("get_default_fan_curve", { target });
 }
 
+/** Read a profile's fan curve (laptop: saved or firmware curve); `source` defaults to AC. */
+export async function getFanCurve(
+  target: FanTarget,
+  profile?: ThermalProfile,
+  source?: PowerSource,
+): Promise<FanCurve> {
+  return invoke<FanCurve>("get_fan_curve", { target, profile, source });
+}
+
+/** Save a custom laptop fan curve; written now if the profile and source are active. */
+export async function setFanCurve(
+  profile: ThermalProfile,
+  curve: FanCurve,
+  source?: PowerSource,
+): Promise<void> {
+  return invoke<void>("set_fan_curve", { profile, curve, source });
+}
+
+/** Drop a profile's custom fan curves of a power source (default: AC). */
+export async function resetFanCurves(
+  profile: ThermalProfile,
+  source?: PowerSource,
+): Promise<void> {
+  return invoke<void>("reset_fan_curves", { profile, source });
+}
+
 // ─── Desktop-specific commands ───────────────────────────────
 
 /** Detect whether the WMI backend is "desktop" or "laptop". */
@@ -58,6 +183,39 @@ export async function getWmiBackend(): Promise<WmiBackend> {
   return invoke<WmiBackend>("get_wmi_backend");
 }
 
+/** Read the WMI connection health; `null` if WMI never initialized. */
+export async function getWmiHealth(): Promise<WmiHealth | null> {
+  return invoke<WmiHealth | null>("get_wmi_health");
+}
+
+/** Event emitted with a {@link WmiHealth} when the WMI connection status changes. */
+export const WMI_STATUS_EVENT = "wmi-status";
+
+/** A watched fan stopped spinning (`stalled`) or started again. */
+export interface FanStallAlert {
+  sensor_id: string;
+  name: string;
+  stalled: boolean;
+}
+
+/** Event emitted with a {@link FanStallAlert} by the backend stall detection. */
+export const FAN_STALL_EVENT = "fan-stall";
+
+/** The thermal failsafe ran all fans at 100 % (`active`) or released them. */
+export interface FailsafeAlert {
+  active: boolean;
+  /** Hottest watched sensor at the time. */
+  sensor_id: string;
+  name: string;
+  temp_c: number;
+  /** `temp_c` in the unit set in the `units` config. */
+  display_temp: number;
+  display_unit: string;
+}
+
+/** Event emitted with a {@link FailsafeAlert} by the backend thermal failsafe. */
+export const THERMAL_FAILSAFE_EVENT = "thermal-failsafe";
+
 /** Read fan policies for all present desktop fan headers. */
 export async function getDesktopFanPolicies(): Promise<DesktopFanPolicy[]> {
   return invoke<DesktopFanPolicy[]>("get_desktop_fan_policies");
@@ -81,6 +239,15 @@ export async function getDesktopFanCurve(
   });
 }
 
+/** 批量读取多条曲线（一次后端往返），结果与请求顺序一致。 */
+export async function getDesktopFanCurves(
+  requests: [fanType: number, mode: DesktopFanMode][],
+): Promise<(DesktopFanCurve | null)[]> {
+  return invoke<(DesktopFanCurve | null)[]>("get_desktop_fan_curves", {
+    requests,
+  });
+}
+
 /** 写入桌面风扇头的 8 点曲线。 */
 export async function setDesktopFanCurve(
   curve: DesktopFanCurve,
@@ -88,6 +255,24 @@ export async function setDesktopFanCurve(
   return invoke<void>("set_desktop_fan_curve", { curve });
 }
 
+/** 读取桌面风扇头在指定模式下的 3 点曲线（非 Pro 固件）。 */
+export async function getDesktopFanCurve3(
+  fanType: number,
+  mode: DesktopFanMode,
+): Promise<DesktopFanCurve3 | null> {
+  return invoke<DesktopFanCurve3 | null>("get_desktop_fan_curve_3", {
+    fanType,
+    mode,
+  });
+}
+
+/** 写入桌面风扇头的 3 点曲线（非 Pro 固件）。 */
+export async function setDesktopFanCurve3(
+  curve: DesktopFanCurve3,
+): Promise<void> {
+  return invoke<void>("set_desktop_fan_curve_3", { curve });
+}
+
 /** 探测所有存在的风扇头及其支持的控制模式。 */
 export async function probeDesktopFanTypes(): Promise<
   [number, DesktopFanMode[]][]
@@ -95,6 +280,132 @@ export async function probeDesktopFanTypes(): Promise<
   return invoke<[number, DesktopFanMode[]][]>("probe_desktop_fan_types");
 }
 
+/** List the temperature sources each desktop fan header can follow. */
+export async function getDesktopFanSources(): Promise<DesktopFanSources[]> {
+  return invoke<DesktopFanSources[]>("get_desktop_fan_sources");
+}
+
+/**
+ * Change a desktop fan header's low limit (RPM, 0 = ignore). Rejected if
+ * out of range; risky limits are written and returned as warnings.
+ */
+export async function setFanLowLimit(
+  fanType: number,
+  lowLimit: number,
+): Promise<LowLimitWarning[]> {
+  return invoke<LowLimitWarning[]>("set_fan_low_limit", {
+    fanType,
+    lowLimit,
+  });
+}
+
+/** Result of copying a fan curve to one header. */
+export interface FanCurveCopy {
+  fan_type: number;
+  /** Warnings for the copied low limit, as from {@link setFanLowLimit}. */
+  warnings: LowLimitWarning[];
+}
+
+/**
+ * Copy the policy and curve of header `from` to the headers `to`, or to
+ * every chassis header when `to` is omitted ("apply to all chassis fans").
+ * Nothing is written if the low limit or the curve is invalid for any
+ * target (the latter rejects with an `invalid_curve` BackendError).
+ */
+export async function copyFanCurve(
+  from: number,
+  to?: number[],
+): Promise<FanCurveCopy[]> {
+  return invoke<FanCurveCopy[]>("copy_fan_curve", { from, to });
+}
+
+/**
+ * Start Q-Fan calibration of a desktop fan header. Progress arrives as
+ * {@link FAN_CALIBRATION_EVENT} events; the result is saved to the config.
+ */
+export async function startFanCalibration(fanType: number): Promise<void> {
+  return invoke<void>("start_fan_calibration", { fanType });
+}
+
+/** Read the calibration progress of a desktop fan header. */
+export async function getFanCalibrationStatus(
+  fanType: number,
+): Promise<FanCalibrationStatus> {
+  return invoke<FanCalibrationStatus>("get_fan_calibration_status", {
+    fanType,
+  });
+}
+
+/** Event emitted with a {@link FanCalibrationStatus} while a calibration runs. */
+export const FAN_CALIBRATION_EVENT = "fan-calibration";
+
+/**
+ * Run a temperature trace through a fan curve without touching hardware.
+ * RPMs are estimated from the saved calibration of `fanType`, if any.
+ */
+export async function previewFanCurve(
+  points: FanCurvePoint[],
+  temps: number[],
+  fanType?: number,
+): Promise<CurvePreviewPoint[]> {
+  return invoke<CurvePreviewPoint[]>("preview_fan_curve", {
+    points,
+    temps,
+    fanType,
+  });
+}
+
+// ─── Fan groups ──────────────────────────────────────────────
+
+/** A header of a fan group and its current speed. */
+export interface FanGroupMember {
+  fan_type: number;
+  /** Board header name from the quirks table, if known. */
+  name: string | null;
+  /** `null` when the header is not measured through Super I/O. */
+  rpm: number | null;
+}
+
+/** A fan group with the speeds of its headers. */
+export interface FanGroupStatus {
+  name: string;
+  members: FanGroupMember[];
+  /** Slowest / fastest measured header, `null` if none is measured. */
+  min_rpm: number | null;
+  max_rpm: number | null;
+  curve: FanGroupCurve | null;
+}
+
+/** Read the fan groups with member speeds and their min / max RPM. */
+export async function getFanGroups(): Promise<FanGroupStatus[]> {
+  return invoke<FanGroupStatus[]>("get_fan_groups");
+}
+
+/**
+ * Create a fan group or change the headers of an existing one. Rejected
+ * if a header is already in another group.
+ */
+export async function setFanGroup(
+  name: string,
+  fanTypes: number[],
+): Promise<void> {
+  return invoke<void>("set_fan_group", { name, fanTypes });
+}
+
+/** Delete a fan group; its headers keep their last curve. */
+export async function deleteFanGroup(name: string): Promise<void> {
+  return invoke<void>("delete_fan_group", { name });
+}
+
+/** Write one 8-point curve to every header of a fan group. */
+export async function setFanGroupCurve(
+  name: string,
+  mode: DesktopFanMode,
+  points: FanCurvePoint[],
+): Promise<void> {
+  return invoke<void>("set_fan_group_curve", { name, mode, points });
+}
+
 // ─── ASUSHW sensor commands ──────────────────────────────────
 
 /** Read all ASUSHW sensors (temperatures + fan RPMs). */
@@ -102,6 +413,65 @@ export async function getAsusHWSensors(): Promise<AsusHWSensor[]> {
   return invoke<AsusHWSensor[]>("get_asushw_sensors");
 }
 
+// ─── Laptop-only commands ───────────────────────────────────
+
+/** Event emitted with an {@link AtkEvent} for every ATK hardware event. */
+export const ATK_EVENT = "atk-event";
+
+/**
+ * Emitted with the new {@link ThermalProfile} after the Fn+F5 profile key
+ * switched it.
+ */
+export const THERMAL_PROFILE_CHANGED_EVENT = "thermal-profile-changed";
+
+/**
+ * Emitted with `true` when the fan panic hotkey ran every fan at 100 % and
+ * `false` when it restored the previous fan settings.
+ */
+export const FAN_PANIC_EVENT = "fan-panic";
+
+/** Saved battery charge limit in percent, or null if never set. */
+export async function getBatteryChargeLimit(): Promise<number | null> {
+  return invoke<number | null>("get_battery_charge_limit");
+}
+
+/** Stop charging at `percent` (20–100); re-applied on startup. */
+export async function setBatteryChargeLimit(percent: number): Promise<void> {
+  return invoke<void>("set_battery_charge_limit", { percent });
+}
+
+/** Read the GPU mode and which switches the laptop supports. */
+export async function getGpuMode(): Promise<GpuModeInfo> {
+  return invoke<GpuModeInfo>("get_gpu_mode");
+}
+
+/** Switch the GPU mode; check `reboot_required` in the result. */
+export async function setGpuMode(mode: GpuMode): Promise<GpuModeChange> {
+  return invoke<GpuModeChange>("set_gpu_mode", { mode });
+}
+
+/** Read the power-limit ranges for this model and the firmware values. */
+export async function getPowerLimits(): Promise<PowerLimitsInfo> {
+  return invoke<PowerLimitsInfo>("get_power_limits");
+}
+
+/** Save a profile's power limits; applied now if the profile and source are active. */
+export async function setPowerLimits(
+  profile: ThermalProfile,
+  limits: PowerLimits,
+  source?: PowerSource,
+): Promise<void> {
+  return invoke<void>("set_power_limits", { profile, limits, source });
+}
+
+/** Whether the machine currently runs on AC or battery. */
+export async function getPowerSource(): Promise<PowerSource> {
+  return invoke<PowerSource>("get_power_source");
+}
+
+/** Event emitted with the new {@link PowerSource} on plug / unplug. */
+export const POWER_SOURCE_CHANGED_EVENT = "power-source-changed";
+
 // ─── Super I/O 传感器命令 ────────────────────────────────────
 
 /** 读取 Super I/O 芯片的所有风扇转速与温度传感器 */
@@ -109,11 +479,31 @@ export async function getSioSensors(): Promise<SioSnapshot> {
   return invoke<SioSnapshot>("get_sio_sensors");
 }
 
+/** 清除机箱入侵锁存位 */
+export async function clearSioIntrusion(): Promise<void> {
+  return invoke<void>("clear_sio_intrusion");
+}
+
 /** 获取 Super I/O 模块状态（芯片型号、是否可用等） */
 export async function getSioStatus(): Promise<SioStatus> {
   return invoke<SioStatus>("get_sio_status");
 }
 
+/** 按需执行 Super I/O 诊断（LDN 激活状态、基地址、关键寄存器与原始端口值），供排查时导出 */
+export async function runSioDiagnostics(): Promise<SioDiagnostics> {
+  return invoke<SioDiagnostics>("run_sio_diagnostics");
+}
+
+/** 转储 Super I/O 芯片 HW Monitor 的全部寄存器，供远程排查温度 / 风扇通道映射 */
+export async function dumpSioRegisters(): Promise<SioRegisterDump> {
+  return invoke<SioRegisterDump>("dump_sio_registers");
+}
+
+/** 导出 LPC / eSPI 桥的 I/O 解码寄存器，排查 SIO 读数全为 0xFF 的问题（需要 WinRing0 驱动） */
+export async function getLpcDecode(): Promise<LpcDecodeDump> {
+  return invoke<LpcDecodeDump>("get_lpc_decode");
+}
+
 // ─── LibreHardwareMonitor 传感器命令 ─────────────────────────
 
 /** 检测 LHM 服务是否可用。 */
@@ -125,3 +515,113 @@ export async function getLhmStatus(): Promise<LhmStatus> {
 export async function getLhmSensors(): Promise<LhmSensorSnapshot> {
   return invoke<LhmSensorSnapshot>("get_lhm_sensors");
 }
+
+/** List LHM hardware nodes, for picking sensors by hardware. */
+export async function getLhmHardware(): Promise<LhmHardware[]> {
+  return invoke<LhmHardware[]>("get_lhm_hardware");
+}
+
+/**
+ * Query LHM sensors under one hardware node (`parent`, e.g. `/gpu-nvidia/0`)
+ * or whose identifier starts with `prefix`; all sensors if neither is given.
+ */
+export async function queryLhmSensors(filter: {
+  parent?: string;
+  prefix?: string;
+}): Promise<LhmSensor[]> {
+  return invoke<LhmSensor[]>("query_lhm_sensors", filter);
+}
+
+/**
+ * Read HWiNFO's shared memory. Fails if HWiNFO is not running or
+ * "Shared Memory Support" is off.
+ */
+export async function getHwinfoSensors(): Promise<HwinfoSnapshot> {
+  return invoke<HwinfoSnapshot>("get_hwinfo_sensors");
+}
+
+/** Path of the installed LibreHardwareMonitor.exe, or null if not found. */
+export async function getLhmInstall(): Promise<string | null> {
+  return invoke<string | null>("get_lhm_install");
+}
+
+/**
+ * Start LibreHardwareMonitor hidden if its WMI namespace is unavailable and
+ * wait (up to 30 s) for its sensors.
+ */
+export async function launchLhm(): Promise<LhmStatus> {
+  return invoke<LhmStatus>("launch_lhm");
+}
+
+// ─── ACPI EC 传感器命令 ──────────────────────────────────────
+
+/** 读取当前主板 EC 偏移表中的全部传感器。 */
+export async function getEcSensors(): Promise<EcSnapshot> {
+  return invoke<EcSnapshot>("get_ec_sensors");
+}
+
+/** 直接读取 CPU 封装 / Tctl / CCD 温度（无需 LHM）。 */
+export async function getCpuTemperatures(): Promise<CpuSnapshot> {
+  return invoke<CpuSnapshot>("get_cpu_temperatures");
+}
+
+/** 读取 NVMe / SATA 驱动器温度（无需 WinRing0 驱动）。 */
+export async function getStorageTemperatures(): Promise<StorageTempReading[]> {
+  return invoke<StorageTempReading[]>("get_storage_temperatures");
+}
+
+/** 读取 GPU 温度与风扇（NVML / ADL，无需 LHM）。 */
+export async function getGpuSensors(): Promise<GpuDeviceSnapshot[]> {
+  return invoke<GpuDeviceSnapshot[]>("get_gpu_sensors");
+}
+
+/** 读取所有来源的传感器，合并为带稳定 ID 的统一列表；`unsmoothed` 为 true 时返回未经滑动平均的值。 */
+export async function getAllSensorsUnified(
+  unsmoothed?: boolean,
+): Promise<UnifiedSensorSnapshot> {
+  return invoke<UnifiedSensorSnapshot>("get_all_sensors_unified", { unsmoothed });
+}
+
+/** 读取各传感器自启动以来的最小 / 平均 / 最大值（总体及按散热模式）。 */
+export async function getSensorStats(): Promise<SensorStatsSnapshot> {
+  return invoke<SensorStatsSnapshot>("get_sensor_stats");
+}
+
+/** 清空传感器统计并重新开始。 */
+export async function resetSensorStats(): Promise<void> {
+  return invoke<void>("reset_sensor_stats");
+}
+
+/** 读取最近 `seconds` 秒的传感器历史（分级降采样，`ids` 为空时为全部传感器）。 */
+export async function getSensorHistory(
+  seconds: number,
+  ids?: string[],
+): Promise<HistorySeries> {
+  return invoke<HistorySeries>("get_sensor_history", { ids, seconds });
+}
+
+/** 将最近 `range` 秒的传感器历史写入 CSV 文件（`sensorIds` 为空时为全部传感器），返回写入的行数。 */
+export async function exportHistory(
+  sensorIds: string[],
+  range: number,
+  path: string,
+): Promise<number> {
+  return invoke<number>("export_history", { sensorIds, range, path });
+}
+
+/** 传感器录制回放结果：report 为回放时的解析结果（结构同录制文件中的 expected） */
+export interface TraceReplay {
+  report: Record<string, unknown>;
+  /** 解析结果与录制时一致 */
+  matches_recording: boolean;
+}
+
+/** 录制一次 WMI 与 Super I/O 原始读取及其解析结果，写入 `path`（JSON），可附在问题反馈中 */
+export async function recordSensorTrace(path: string): Promise<void> {
+  return invoke<void>("record_sensor_trace", { path });
+}
+
+/** 以录制文件中的原始读取重新解析，并与录制时的结果比对 */
+export async function replaySensorTrace(path: string): Promise<TraceReplay> {
+  return invoke<TraceReplay>("replay_sensor_trace", { path });
+}
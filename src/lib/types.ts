@@ -10,10 +10,23 @@
 /** Identifies a fan header on the motherboard. */
 export type FanTarget = "cpu" | "gpu" | "mid";
 
+/**
+ * Whether 0 RPM means a stopped fan or an empty header: `stopped` once the
+ * header has been seen spinning (or the firmware flags it present).
+ */
+export type FanPresence = "spinning" | "stopped" | "absent";
+
 /** RPM snapshot for a single fan header. */
 export interface FanInfo {
   target: FanTarget;
   rpm: number;
+  status: FanPresence;
+}
+
+/** Fan speeds and thermal profile, read in one backend round-trip. */
+export interface FanStatus {
+  fans: FanInfo[];
+  profile: ThermalProfile;
 }
 
 /** A single temperature → duty-cycle mapping point. */
@@ -35,6 +48,9 @@ export interface FanCurve {
 /** ASUS thermal-profile presets. */
 export type ThermalProfile = "standard" | "performance" | "silent";
 
+/** Where a laptop draws its power from. */
+export type PowerSource = "ac" | "battery";
+
 /** Display metadata for a thermal profile. */
 export interface ThermalProfileMeta {
   id: ThermalProfile;
@@ -84,6 +100,20 @@ export interface DesktopFanPolicy {
   profile: DesktopFanProfile;
   source: string;
   low_limit: number;
+  /** 主板丝印名称（来自型号修正表） */
+  name: string | null;
+  /** 对应的 Super I/O 风扇通道（来自型号修正表） */
+  sio_channel: number | null;
+}
+
+/** Temperature sources a desktop fan header can follow. */
+export interface DesktopFanSources {
+  fan_type: number;
+  /** Source of the current policy (may be empty on the CPU header). */
+  current: string;
+  available: string[];
+  /** `false` when the firmware declares no sources and defaults are listed. */
+  declared: boolean;
 }
 
 /** 桌面主板 8 点风扇曲线。 */
@@ -93,6 +123,47 @@ export interface DesktopFanCurve {
   points: FanCurvePoint[];
 }
 
+/** 桌面主板 3 点风扇曲线（Low / Mid / High），用于只支持非 Pro 方法的固件。 */
+export interface DesktopFanCurve3 {
+  fan_type: number;
+  mode: DesktopFanMode;
+  points: FanCurvePoint[];
+}
+
+/** State of a Q-Fan calibration run. */
+export type FanCalibrationState = "idle" | "running" | "done" | "failed";
+
+/** Measured characteristics of a desktop fan header. */
+export interface FanCalibration {
+  /** Lowest duty at which the fan keeps spinning. */
+  min_duty_pct: number;
+  min_rpm: number;
+  max_rpm: number;
+}
+
+/** Why an accepted desktop fan low limit may trigger "CPU fan error" at boot. */
+export type LowLimitWarning =
+  | { kind: "above_min_rpm"; min_rpm: number }
+  | { kind: "monitoring_disabled" }
+  | { kind: "uncalibrated" };
+
+/** Progress of a Q-Fan calibration run. */
+export interface FanCalibrationStatus {
+  fan_type: number;
+  state: FanCalibrationState;
+  progress_pct: number;
+  /** Set once the run is done. */
+  result: FanCalibration | null;
+}
+
+/** One sample of a fan curve preview. */
+export interface CurvePreviewPoint {
+  temp_c: number;
+  duty_pct: number;
+  /** Estimated speed; null without calibration data. */
+  rpm: number | null;
+}
+
 /** Display names for desktop fan header indices. */
 export const DESKTOP_FAN_NAMES: Record<number, string> = {
   0: "CPU Fan",
@@ -105,9 +176,111 @@ export const DESKTOP_FAN_NAMES: Record<number, string> = {
   7: "机箱风扇 7",
 };
 
+/** 主板风扇头：FanType 索引、丝印名称与对应的 SIO 通道 */
+export interface FanHeader {
+  fan_type: number;
+  name: string;
+  sio_channel: number | null;
+}
+
+/** SMBIOS 主板信息与按型号应用的修正表 */
+export interface BoardSummary {
+  info: {
+    manufacturer: string | null;
+    product: string;
+    version: string | null;
+  } | null;
+  quirks: {
+    fan_headers: FanHeader[];
+    bad_device_ids: number[];
+  };
+}
+
 /** WMI backend type. */
 export type WmiBackend = "desktop" | "laptop" | "asushw" | "unavailable";
 
+/** WMI connection status; the connection is re-created after repeated COM failures. */
+export type WmiStatus = "connected" | "reconnecting" | "disconnected";
+
+export interface WmiHealth {
+  status: WmiStatus;
+  consecutive_failures: number;
+  reconnects: number;
+  last_error: string | null;
+}
+
+// ─── Laptop types ────────────────────────────────────────────
+
+/** Decoded ASUS ATK hardware event (hotkeys, AC plug, lid flip). */
+export type AtkEventKind =
+  | "profile_key"
+  | "on_battery"
+  | "on_ac"
+  | "lid_flip"
+  | "keyboard_backlight_up"
+  | "keyboard_backlight_down"
+  | "keyboard_backlight_toggle"
+  | "rog_key"
+  | "other";
+
+export interface AtkEvent {
+  /** Raw ATK notify code. */
+  code: number;
+  kind: AtkEventKind;
+}
+
+/** Laptop GPU mode: dGPU off / hybrid / MUX to dGPU. */
+export type GpuMode = "eco" | "standard" | "ultimate";
+
+export interface GpuModeInfo {
+  mode: GpuMode;
+  /** GPU MUX present (ultimate available) */
+  mux_supported: boolean;
+  /** dGPU disable present (eco available) */
+  eco_supported: boolean;
+}
+
+export interface GpuModeChange {
+  /** The MUX was switched; the new mode applies after a reboot */
+  reboot_required: boolean;
+}
+
+/** Adjustable laptop power limit. */
+export type PowerLimit = "spl" | "sppt" | "fppt" | "nv_dynamic_boost";
+
+/** Power limits in watts; null leaves the firmware value alone. */
+export interface PowerLimits {
+  spl: number | null;
+  sppt: number | null;
+  fppt: number | null;
+  nv_dynamic_boost: number | null;
+}
+
+export interface PowerLimitRange {
+  limit: PowerLimit;
+  min: number;
+  max: number;
+}
+
+export interface PowerLimitsInfo {
+  board: string | null;
+  ranges: PowerLimitRange[];
+  current: PowerLimits;
+}
+
+export const POWER_LIMIT_LABELS: Record<PowerLimit, string> = {
+  spl: "持续功耗 (SPL)",
+  sppt: "短时功耗 (SPPT)",
+  fppt: "瞬时功耗 (FPPT)",
+  nv_dynamic_boost: "NVIDIA Dynamic Boost",
+};
+
+export const GPU_MODE_LABELS: Record<GpuMode, string> = {
+  eco: "集显模式",
+  standard: "标准模式",
+  ultimate: "独显直连",
+};
+
 // ─── ASUSHW Sensor types ─────────────────────────────────────
 
 /** A single sensor reading from the ASUSHW backend. */
@@ -129,6 +302,8 @@ export interface SioFanReading {
   name: string;
   rpm: number;
   channel: number;
+  /** 启动以来转过的通道在 0 RPM 时为 `stopped`，否则为 `absent` */
+  status: FanPresence;
 }
 
 /** Super I/O 单个温度通道的读数 */
@@ -138,10 +313,18 @@ export interface SioTempReading {
   channel: number;
 }
 
+/** Super I/O 机箱入侵检测状态 */
+export interface SioIntrusionReading {
+  name: string;
+  triggered: boolean;
+}
+
 /** Super I/O 一次完整采样快照 */
 export interface SioSnapshot {
   fans: SioFanReading[];
   temps: SioTempReading[];
+  /** 芯片不支持入侵检测时为 null */
+  intrusion: SioIntrusionReading | null;
   chip_name: string;
 }
 
@@ -152,6 +335,224 @@ export interface SioStatus {
   error: string | null;
 }
 
+/** 一个配置端口（0x2E / 0x4E）上的 Super I/O 诊断结果 */
+export interface SioPortDiagnostics {
+  config_port: number;
+  /** 芯片 ID，0xFFFF 表示端口上没有芯片 */
+  chip_id: number;
+  /** HW Monitor 逻辑设备激活寄存器（0x30） */
+  activate_reg: number;
+  active: boolean;
+  /** HW Monitor 基地址 */
+  base: number;
+  /** bank 0 寄存器 0x4F，基地址无效时为 null */
+  vendor_id: number | null;
+  /** 厂商 ID 是否为 Nuvoton 的 0x5C */
+  vendor_id_ok: boolean;
+  systin_raw: number | null;
+  /** 基地址附近端口的原始值，仅直接端口访问时读取 */
+  raw_ports: { port: number; value: number }[];
+  alt_vendor_id: number | null;
+}
+
+/** Super I/O 诊断结果 */
+export interface SioDiagnostics {
+  /** 访问方式（WinRing0 / ASUS WMI） */
+  access: string;
+  ports: SioPortDiagnostics[];
+}
+
+/** Super I/O HW Monitor 寄存器转储：每个 bank 含寄存器 0x00–0xFF 的值，ITE 芯片只有 bank 0 */
+export interface SioRegisterDump {
+  chip_name: string;
+  chip_id: number;
+  base_addr: number;
+  /** 访问方式（WinRing0 / ASUS WMI） */
+  access: string;
+  banks: { bank: number; registers: number[] }[];
+}
+
+/** LPC / eSPI 桥的一段 I/O 解码范围（AMD Wide IO / Intel LGIR） */
+export interface LpcDecodeRange {
+  name: string;
+  base: number;
+  size: number;
+  enabled: boolean;
+}
+
+/** LPC / eSPI 桥解码寄存器导出 */
+export interface LpcDecodeDump {
+  bridge: "amd_fch" | "intel_pch";
+  bus: number;
+  device: number;
+  function: number;
+  vendor_id: number;
+  device_id: number;
+  /** 0x2E/0x2F、0x4E/0x4F 配置端口是否被转发 */
+  sio_2e_decoded: boolean;
+  sio_4e_decoded: boolean;
+  /** 原始寄存器值 */
+  registers: { offset: number; name: string; value: number }[];
+  ranges: LpcDecodeRange[];
+  /** 各 HW Monitor 基地址由哪段范围转发，range 为 null 表示未被转发 */
+  hwm: { base: number; range: string | null }[];
+}
+
+// ─── ACPI 嵌入式控制器 (EC) 传感器类型 ─────────────────────────
+
+/** EC 传感器类型 */
+export type EcSensorKind = "temperature" | "fan" | "current" | "flow";
+
+/** 单个 EC 传感器读数 */
+export interface EcReading {
+  name: string;
+  kind: EcSensorKind;
+  value: number;
+  register: number;
+}
+
+/** EC 传感器快照 */
+export interface EcSnapshot {
+  board: string;
+  readings: EcReading[];
+}
+
+// ─── CPU 温度（MSR / SMN）────────────────────────────────────
+
+/** 单个 CPU 温度读数（Package / Tctl / CCD n） */
+export interface CpuTempReading {
+  name: string;
+  temp_c: number;
+}
+
+/** CPU 温度快照 */
+export interface CpuSnapshot {
+  brand: string;
+  temps: CpuTempReading[];
+}
+
+// ─── 统一传感器 ──────────────────────────────────────────────
+
+/** 传感器数据来源 */
+export type SensorSource =
+  | "sio"
+  | "ec"
+  | "cpu"
+  | "gpu"
+  | "storage"
+  | "asushw"
+  | "lhm"
+  | "hwinfo";
+
+/** 传感器物理量类型 */
+export type SensorKind =
+  | "temperature"
+  | "fan"
+  | "fan_percent"
+  | "current"
+  | "flow";
+
+/** 统一格式的传感器读数 */
+export interface UnifiedSensor {
+  /** 稳定 ID，如 `sio/nct6798d/temp/1` */
+  id: string;
+  source: SensorSource;
+  kind: SensorKind;
+  name: string;
+  device: string;
+  /** 已应用校准与滑动平均的值 */
+  value: number;
+  /** 校准前的原始读数 */
+  raw_value: number;
+  /** 按单位偏好换算后的 value */
+  display_value: number;
+  /** display_value 的单位符号，如 "°F"、"RPM" */
+  display_unit: string;
+  /** 与更高优先级来源重复时指向其 ID */
+  duplicate_of: string | null;
+}
+
+/** 读取失败的来源 */
+export interface SensorSourceError {
+  source: SensorSource;
+  message: string;
+}
+
+/** 聚合后的传感器快照 */
+export interface UnifiedSensorSnapshot {
+  sensors: UnifiedSensor[];
+  errors: SensorSourceError[];
+}
+
+/** 单个传感器的统计值 */
+export interface SensorStats {
+  min: number;
+  avg: number;
+  max: number;
+  /** 计入的读数个数 */
+  samples: number;
+}
+
+/** 传感器历史中的一个时间桶 */
+export interface HistoryPoint {
+  /** 桶起始时间（Unix 秒） */
+  t: number;
+  min: number;
+  avg: number;
+  max: number;
+}
+
+/** 传感器历史查询结果 */
+export interface HistorySeries {
+  /** 桶宽（秒）：10 分钟内为 1，2 小时内为 10，更长为 60 */
+  resolution_s: number;
+  /** 按传感器 ID，按时间升序 */
+  series: Record<string, HistoryPoint[]>;
+}
+
+/** 自启动（或上次重置）以来的传感器统计 */
+export interface SensorStatsSnapshot {
+  /** 开始统计的时间（Unix 秒） */
+  since: number;
+  /** 按传感器 ID */
+  session: Record<string, SensorStats>;
+  /** 按散热模式、再按传感器 ID */
+  by_profile: Partial<Record<ThermalProfile, Record<string, SensorStats>>>;
+}
+
+// ─── 存储设备温度（NVMe / SATA SMART）──────────────────────────
+
+/** 驱动器总线类型 */
+export type StorageBus = "nvme" | "sata";
+
+/** 单个驱动器温度读数 */
+export interface StorageTempReading {
+  index: number;
+  model: string;
+  bus: StorageBus;
+  temp_c: number;
+}
+
+// ─── GPU 传感器（NVML / ADL）─────────────────────────────────
+
+/** GPU 传感器类型 */
+export type GpuSensorKind = "temperature" | "fan_rpm" | "fan_percent";
+
+/** 单个 GPU 传感器读数 */
+export interface GpuReading {
+  name: string;
+  kind: GpuSensorKind;
+  value: number;
+}
+
+/** 单块 GPU 的读数 */
+export interface GpuDeviceSnapshot {
+  index: number;
+  name: string;
+  backend: "nvml" | "adl";
+  readings: GpuReading[];
+}
+
 // ─── LibreHardwareMonitor (LHM) 传感器类型 ───────────────────
 
 /** LHM 服务可用性状态 */
@@ -171,6 +572,53 @@ export interface LhmSensor {
   parent: string;
 }
 
+/** LHM 硬件节点（CPU、GPU、主板、SuperIO 芯片等） */
+export interface LhmHardware {
+  identifier: string;
+  name: string;
+  hardware_type: string;
+  /** 顶层节点为空字符串 */
+  parent: string;
+}
+
+/** HWiNFO 读数类型 */
+export type HwinfoReadingType =
+  | "temperature"
+  | "voltage"
+  | "fan"
+  | "current"
+  | "power"
+  | "clock"
+  | "usage"
+  | "other";
+
+/** HWiNFO 传感器（设备） */
+export interface HwinfoSensor {
+  id: number;
+  instance: number;
+  name: string;
+}
+
+/** HWiNFO 单个读数 */
+export interface HwinfoReading {
+  reading_type: HwinfoReadingType;
+  /** 所属传感器在 `sensors` 中的下标 */
+  sensor_index: number;
+  id: number;
+  label: string;
+  unit: string;
+  value: number;
+  min: number;
+  max: number;
+}
+
+/** HWiNFO 共享内存快照 */
+export interface HwinfoSnapshot {
+  version: number;
+  sensors: HwinfoSensor[];
+  readings: HwinfoReading[];
+}
+
 /** LHM 传感器分组快照 */
 export interface LhmSensorSnapshot {
   temperatures: LhmSensor[];
@@ -5,3 +5,13 @@ import { twMerge } from "tailwind-merge";
 export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs));
 }
+
+/**
+ * Like `setInterval`, but skips ticks while the page is hidden (main
+ * window in the tray or minimized), so hidden UI does not keep polling.
+ */
+export function setVisibleInterval(fn: () => unknown, ms: number) {
+  return setInterval(() => {
+    if (!document.hidden) void fn();
+  }, ms);
+}
@@ -1,6 +1,7 @@
 import { motion } from "motion/react";
 import { Fan, Loader2, Monitor, Thermometer } from "lucide-react";
 import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 
 import { DesktopFanPolicyCard } from "@/components/desktop-fan-policy-card";
 import { FanCurveEditor } from "@/components/fan-curve-editor";
@@ -10,6 +11,10 @@ import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { useAsusHWData } from "@/hooks/use-asushw-data";
 import { useDesktopFanData } from "@/hooks/use-desktop-fan-data";
 import { useFanData } from "@/hooks/use-fan-data";
+import {
+  SUBSYSTEM_READY_EVENT,
+  type SubsystemReady,
+} from "@/lib/system-commands";
 import { getWmiBackend } from "@/lib/tauri-commands";
 import { spring, staggerContainer, staggerItem } from "@/lib/motion";
 import { FAN_TARGET_LABELS, type FanCurvePoint, type WmiBackend } from "@/lib/types";
@@ -31,9 +36,21 @@ export default function FanPage() {
   const [backendError, setBackendError] = useState<string | null>(null);
 
   useEffect(() => {
-    getWmiBackend()
-      .then(setBackend)
-      .catch((e) => setBackendError(String(e)));
+    const fetchBackend = () =>
+      getWmiBackend()
+        .then((b) => {
+          setBackend(b);
+          setBackendError(null);
+        })
+        .catch((e) => setBackendError(String(e)));
+    void fetchBackend();
+    // WMI is initialized in the background; detect again once it is up
+    const unlisten = listen<SubsystemReady>(SUBSYSTEM_READY_EVENT, (event) => {
+      if (event.payload.name === "wmi") void fetchBackend();
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
   }, []);
 
   // Show loading spinner until backend is detected
@@ -0,0 +1,57 @@
+/**
+ * Sensor overlay — rendered in the separate always-on-top, click-through
+ * overlay window. Readings are pushed by the backend; the page does not
+ * poll. Updates carry either the full list or only the changed sensors.
+ */
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+
+import { OVERLAY_SENSORS_EVENT, type OverlayUpdate } from "@/lib/system-commands";
+import type { SensorKind, UnifiedSensor } from "@/lib/types";
+
+const UNITS: Record<SensorKind, string> = {
+  temperature: "°C",
+  fan: "RPM",
+  fan_percent: "%",
+  current: "A",
+  flow: "L/h",
+};
+
+export default function OverlayPage() {
+  const [sensors, setSensors] = useState<UnifiedSensor[]>([]);
+
+  // The window is transparent; only the panel below is drawn
+  useEffect(() => {
+    document.body.style.background = "transparent";
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listen<OverlayUpdate>(OVERLAY_SENSORS_EVENT, (event) => {
+      const { full, sensors: changed } = event.payload;
+      if (full) {
+        setSensors(changed);
+        return;
+      }
+      const byId = new Map(changed.map((s) => [s.id, s]));
+      setSensors((prev) => prev.map((s) => byId.get(s.id) ?? s));
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (sensors.length === 0) return null;
+
+  return (
+    <div className="inline-block rounded-lg bg-black/60 px-3 py-2 font-mono text-xs text-white">
+      {sensors.map((s) => (
+        <div key={s.id} className="flex justify-between gap-4">
+          <span className="truncate text-white/70">{s.name}</span>
+          <span className="tabular-nums">
+            {s.value.toFixed(s.kind === "current" ? 1 : 0)} {UNITS[s.kind]}
+          </span>
+        </div>
+      ))}
+    </div>
+  );
+}
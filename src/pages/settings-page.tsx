@@ -17,10 +17,11 @@ import {
   Download,
   Upload,
   Thermometer,
+  EyeOff,
 } from "lucide-react";
-import { invoke } from "@tauri-apps/api/core";
 import { openUrl } from "@tauri-apps/plugin-opener";
 
+import { invoke } from "@/lib/invoke";
 import { staggerContainer, staggerItem, spring } from "@/lib/motion";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Switch } from "@/components/ui/switch";
@@ -125,7 +126,7 @@ export default function SettingsPage() {
   };
 
   const handleToggle = async (
-    key: "close_to_tray",
+    key: "close_to_tray" | "start_minimized",
     value: boolean,
   ) => {
     try {
@@ -289,6 +290,16 @@ export default function SettingsPage() {
                   onCheckedChange={(v) => handleToggle("close_to_tray", v)}
                 />
               </SettingRow>
+              <SettingRow
+                icon={EyeOff}
+                label="启动时最小化到托盘"
+                description="启动后不显示主窗口，只创建托盘图标"
+              >
+                <Switch
+                  checked={config.start_minimized}
+                  onCheckedChange={(v) => handleToggle("start_minimized", v)}
+                />
+              </SettingRow>
               <SettingRow
                 icon={Power}
                 label="开机自启"